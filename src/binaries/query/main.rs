@@ -31,10 +31,13 @@ use common_tracing::set_panic_hook;
 use databend_query::api::HttpService;
 use databend_query::api::RpcService;
 use databend_query::clusters::ClusterDiscovery;
+use databend_query::maintenance::MaintenanceService;
 use databend_query::metrics::MetricService;
+use databend_query::servers::FlightSQLHandler;
 use databend_query::servers::HttpHandler;
 use databend_query::servers::HttpHandlerKind;
 use databend_query::servers::MySQLHandler;
+use databend_query::servers::PostgresHandler;
 use databend_query::servers::Server;
 use databend_query::servers::ShutdownHandle;
 use databend_query::GlobalServices;
@@ -125,7 +128,7 @@ async fn main_entrypoint() -> Result<()> {
     {
         let hostname = conf.query.mysql_handler_host.clone();
         let listening = format!("{}:{}", hostname, conf.query.mysql_handler_port);
-        let mut handler = MySQLHandler::create()?;
+        let mut handler = MySQLHandler::create(conf.clone())?;
         let listening = handler.start(listening.parse()?).await?;
         shutdown_handle.add_service(handler);
 
@@ -137,6 +140,22 @@ async fn main_entrypoint() -> Result<()> {
         );
     }
 
+    // PostgreSQL handler.
+    {
+        let hostname = conf.query.postgres_handler_host.clone();
+        let listening = format!("{}:{}", hostname, conf.query.postgres_handler_port);
+        let mut handler = PostgresHandler::create()?;
+        let listening = handler.start(listening.parse()?).await?;
+        shutdown_handle.add_service(handler);
+
+        info!(
+            "Listening for PostgreSQL compatibility protocol: {}, Usage: psql -U root -h {} -p {}",
+            listening,
+            listening.ip(),
+            listening.port(),
+        );
+    }
+
     // ClickHouse HTTP handler.
     {
         let hostname = conf.query.clickhouse_http_handler_host.clone();
@@ -196,6 +215,17 @@ async fn main_entrypoint() -> Result<()> {
         info!("Listening for RPC API (interserver): {}", listening);
     }
 
+    // Arrow Flight SQL handler.
+    {
+        let hostname = conf.query.flight_sql_handler_host.clone();
+        let listening = format!("{}:{}", hostname, conf.query.flight_sql_handler_port);
+
+        let mut srv = FlightSQLHandler::create(conf.clone())?;
+        let listening = srv.start(listening.parse()?).await?;
+        shutdown_handle.add_service(srv);
+        info!("Listening for Arrow Flight SQL API: {}", listening);
+    }
+
     // Cluster register.
     {
         ClusterDiscovery::instance()
@@ -207,6 +237,9 @@ async fn main_entrypoint() -> Result<()> {
         );
     }
 
+    // Background maintenance of the tables, after the node joined the cluster.
+    MaintenanceService::instance().start();
+
     // Print information to users.
     println!("Databend Query");
     println!();
@@ -273,6 +306,15 @@ async fn main_entrypoint() -> Result<()> {
         "    connect via: mysql -uroot -h{} -P{}",
         conf.query.mysql_handler_host, conf.query.mysql_handler_port
     );
+    println!("PostgreSQL");
+    println!(
+        "    listened at {}:{}",
+        conf.query.postgres_handler_host, conf.query.postgres_handler_port
+    );
+    println!(
+        "    connect via: psql -U root -h {} -p {}",
+        conf.query.postgres_handler_host, conf.query.postgres_handler_port
+    );
     println!("Clickhouse(http)");
     println!(
         "    listened at {}:{}",
@@ -303,6 +345,11 @@ async fn main_entrypoint() -> Result<()> {
             .parse()?
         )
     );
+    println!("Flight SQL");
+    println!(
+        "    listened at {}:{}",
+        conf.query.flight_sql_handler_host, conf.query.flight_sql_handler_port
+    );
 
     info!("Ready for connections.");
     shutdown_handle.wait_for_termination_request().await;
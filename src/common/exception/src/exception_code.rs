@@ -133,6 +133,11 @@ build_exceptions! {
     PanicError(1104),
     TableInfoError(1106),
     ReadTableDataError(1107),
+    AddColumnExistError(1108),
+    DropColumnEmptyError(1109),
+    UnknownIndex(1110),
+    IndexAlreadyExists(1111),
+    UnsupportedIndex(1112),
 
     // Data Related Errors
 
@@ -154,6 +159,16 @@ build_exceptions! {
     ///
     /// For example: try to with 3 columns into a table with 4 columns.
     TableSchemaMismatch(1303),
+    /// IllegalStream is used when the source table of a stream can not be
+    /// read as the stream was created on.
+    ///
+    /// For example: the source table has been recreated.
+    IllegalStream(1304),
+    /// StaleMaterializedView is used when reading a materialized view which
+    /// failed to be maintained on the insertion into its source table.
+    ///
+    /// For example: the view is read before `REFRESH MATERIALIZED VIEW`.
+    StaleMaterializedView(1305),
 }
 
 // Metasvr errors [2001, 3000].
@@ -170,6 +185,7 @@ build_exceptions! {
     OCCRetryFailure(2011),
     TableNotWritable(2012),
     TableHistoricalDataNotFound(2013),
+    TableLockExpired(2014),
 
     // User api error codes.
     UnknownUser(2201),
@@ -247,6 +263,7 @@ build_exceptions! {
     IllegalTenantQuotaFormat(2901),
     TenantQuotaUnknown(2902),
     TenantQuotaExceeded(2903),
+    StorageQuotaExceeded(2904),
 
 }
 
@@ -125,3 +125,29 @@ where T: io::Read
         }
     }
 }
+
+/// Decode a signed integer encoded with variable-length zig-zag coding (e.g. the `int`
+/// and `long` of Avro) at `pos` of `data`, `pos` is moved past the integer.
+/// Returns `None` and leaves `pos` unchanged if `data` ends before the integer.
+pub fn read_zigzag_varint(data: &[u8], pos: &mut usize) -> Result<Option<i64>> {
+    let mut value = 0u64;
+    let mut shift = 0;
+    let mut p = *pos;
+    loop {
+        if p >= data.len() {
+            return Ok(None);
+        }
+        let byte = data[p];
+        p += 1;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+        if shift > 63 {
+            return Err(ErrorCode::Overflow("read_zigzag_varint got overflow byte"));
+        }
+    }
+    *pos = p;
+    Ok(Some((value >> 1) as i64 ^ -((value & 1) as i64)))
+}
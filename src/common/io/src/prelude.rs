@@ -15,6 +15,7 @@
 pub use bytes::BufMut;
 pub use bytes::BytesMut;
 
+pub use crate::binary_read::read_zigzag_varint;
 pub use crate::binary_read::BinaryRead;
 pub use crate::binary_write::put_uvarint;
 pub use crate::binary_write::BinaryWrite;
@@ -67,3 +67,28 @@ fn test_write_and_read() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_read_zigzag_varint() -> Result<()> {
+    // 0, -1, 1, -64, 64, i64::MAX, i64::MIN
+    let data = [
+        0x00, 0x01, 0x02, 0x7f, 0x80, 0x01, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0x01, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01,
+    ];
+    let mut pos = 0;
+    for expected in [0, -1, 1, -64, 64, i64::MAX, i64::MIN] {
+        assert_eq!(read_zigzag_varint(&data, &mut pos)?, Some(expected));
+    }
+    assert_eq!(pos, data.len());
+    assert_eq!(read_zigzag_varint(&data, &mut pos)?, None);
+
+    // truncated, `pos` is not moved
+    let mut pos = 0;
+    assert_eq!(read_zigzag_varint(&[0x80, 0x80], &mut pos)?, None);
+    assert_eq!(pos, 0);
+
+    // too many bytes
+    let mut pos = 0;
+    assert!(read_zigzag_varint(&[0xff; 11], &mut pos).is_err());
+    Ok(())
+}
@@ -39,6 +39,16 @@ impl ColumnLeaves {
         Self { column_leaves }
     }
 
+    /// Build the column leaves of the schema, the `leaf_ids` are replaced by the given ids
+    /// of the leaf columns in DFS order, such as the column ids of the table schema.
+    pub fn new_from_schema_with_leaf_ids(schema: &ArrowSchema, leaf_ids: &[usize]) -> Self {
+        let mut column_leaves = Self::new_from_schema(schema);
+        for column_leaf in column_leaves.column_leaves.iter_mut() {
+            column_leaf.replace_leaf_ids(leaf_ids);
+        }
+        column_leaves
+    }
+
     /// Traverse the fields in DFS order to get [`ColumnLeaf`].
     ///
     /// If the data type is [`ArrowType::Struct`], we should expand its inner fields.
@@ -115,4 +125,15 @@ impl ColumnLeaf {
             children,
         }
     }
+
+    fn replace_leaf_ids(&mut self, leaf_ids: &[usize]) {
+        for leaf_id in self.leaf_ids.iter_mut() {
+            *leaf_id = leaf_ids[*leaf_id];
+        }
+        if let Some(children) = self.children.as_mut() {
+            for child in children.iter_mut() {
+                child.replace_leaf_ids(leaf_ids);
+            }
+        }
+    }
 }
@@ -15,6 +15,7 @@
 use crate::kv_api_key::check_segment;
 use crate::kv_api_key::check_segment_absent;
 use crate::kv_api_key::check_segment_present;
+use crate::lease_api::ID_GEN_LEASE_REVISION;
 use crate::schema_api_keys::ID_GEN_DATABASE;
 use crate::schema_api_keys::ID_GEN_TABLE;
 use crate::share_api_keys::ID_GEN_SHARE;
@@ -54,6 +55,13 @@ impl IdGenerator {
             resource: ID_GEN_SHARE.to_string(),
         }
     }
+
+    /// Create a key for generating the revisions of lease holders with KVApi
+    pub fn lease_revision() -> Self {
+        Self {
+            resource: ID_GEN_LEASE_REVISION.to_string(),
+        }
+    }
 }
 
 impl KVApiKey for IdGenerator {
@@ -116,6 +124,16 @@ mod t {
             assert_eq!(g, t2);
         }
 
+        // Lease revision generator
+        {
+            let g = IdGenerator::lease_revision();
+            let k = g.to_key();
+            assert_eq!("__fd_id_gen/lease_revision", k);
+
+            let t2 = IdGenerator::from_key(&k)?;
+            assert_eq!(g, t2);
+        }
+
         Ok(())
     }
 
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::time::Duration;
 use std::time::SystemTime;
 use std::time::UNIX_EPOCH;
 
@@ -45,6 +46,7 @@ use tracing::info;
 
 use crate::ApiBuilder;
 use crate::KVApi;
+use crate::LeaseApi;
 
 pub struct KVApiTestSuite {}
 
@@ -62,6 +64,10 @@ impl KVApiTestSuite {
         self.kv_meta(&builder.build().await).await?;
         self.kv_list(&builder.build().await).await?;
         self.kv_mget(&builder.build().await).await?;
+        self.kv_lease(&builder.build().await).await?;
+        self.kv_lease_expire(&builder.build().await).await?;
+        self.kv_lease_concurrent_acquire(&builder.build().await)
+            .await?;
         self.kv_txn_absent_seq_0(&builder.build().await).await?;
         self.kv_transaction(&builder.build().await).await?;
         self.kv_delete_by_prefix_transaction(&builder.build().await)
@@ -448,6 +454,160 @@ impl KVApiTestSuite {
         Ok(())
     }
 
+    #[tracing::instrument(level = "info", skip(self, kv))]
+    pub async fn kv_lease<KV: KVApi>(&self, kv: &KV) -> anyhow::Result<()> {
+        info!("--- KVApiTestSuite::kv_lease() start");
+
+        let ttl = Duration::from_secs(60);
+
+        info!("--- holders are ordered by revision");
+        let rev1 = kv.acquire_lease("lease/1", b"h1".to_vec(), ttl).await?;
+        let rev2 = kv.acquire_lease("lease/1", b"h2".to_vec(), ttl).await?;
+        let rev3 = kv.acquire_lease("lease/2", b"h3".to_vec(), ttl).await?;
+        let rev4 = kv.acquire_lease("lease/1", b"h4".to_vec(), ttl).await?;
+        assert!(rev1 < rev2 && rev2 < rev3 && rev3 < rev4);
+
+        let holders = kv.list_lease_holders("lease/1").await?;
+        assert_eq!(
+            holders
+                .iter()
+                .map(|h| (h.revision, h.value.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (rev1, b"h1".to_vec()),
+                (rev2, b"h2".to_vec()),
+                (rev4, b"h4".to_vec())
+            ]
+        );
+        assert!(holders.iter().all(|h| h.expire_at.is_some()));
+
+        info!("--- leases are not mixed up with the ones they prefix");
+        {
+            kv.acquire_lease("lease/1/1", b"h5".to_vec(), ttl).await?;
+            let holders = kv.list_lease_holders("lease/1").await?;
+            assert_eq!(holders.len(), 3);
+        }
+
+        info!("--- extend a live holder");
+        {
+            assert!(kv.extend_lease("lease/1", rev1, ttl).await?);
+        }
+
+        info!("--- the next holder owns the lease once the first is released");
+        {
+            kv.release_lease("lease/1", rev1).await?;
+            let holders = kv.list_lease_holders("lease/1").await?;
+            assert_eq!(
+                holders.iter().map(|h| h.revision).collect::<Vec<_>>(),
+                vec![rev2, rev4]
+            );
+        }
+
+        info!("--- a released holder can not be extended, releasing again is a no-op");
+        {
+            assert!(!kv.extend_lease("lease/1", rev1, ttl).await?);
+            kv.release_lease("lease/1", rev1).await?;
+            assert_eq!(kv.list_lease_holders("lease/1").await?.len(), 2);
+        }
+
+        info!("--- a new holder queues after the existing ones");
+        {
+            let rev6 = kv.acquire_lease("lease/1", b"h6".to_vec(), ttl).await?;
+            let holders = kv.list_lease_holders("lease/1").await?;
+            assert_eq!(
+                holders.iter().map(|h| h.revision).collect::<Vec<_>>(),
+                vec![rev2, rev4, rev6]
+            );
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "info", skip(self, kv))]
+    pub async fn kv_lease_concurrent_acquire<KV: KVApi>(&self, kv: &KV) -> anyhow::Result<()> {
+        info!("--- KVApiTestSuite::kv_lease_concurrent_acquire() start");
+
+        let ttl = Duration::from_secs(60);
+
+        // Acquire and list right away, as a holder does to check if it owns the lease.
+        let acquire = |value: &'static [u8]| async move {
+            let revision = kv.acquire_lease("lease_race", value.to_vec(), ttl).await?;
+            let holders = kv.list_lease_holders("lease_race").await?;
+            let revisions = holders.iter().map(|h| h.revision).collect::<Vec<_>>();
+            Ok::<_, anyhow::Error>((revision, revisions))
+        };
+
+        info!("--- a holder sees every holder with a smaller revision once it is queued");
+        for _ in 0..10 {
+            let (a, b) = tokio::join!(acquire(b"a"), acquire(b"b"));
+            let ((rev_a, seen_by_a), (rev_b, seen_by_b)) = (a?, b?);
+            assert_ne!(rev_a, rev_b);
+
+            assert!(seen_by_a.contains(&rev_a));
+            assert!(seen_by_b.contains(&rev_b));
+            if rev_a < rev_b {
+                assert!(seen_by_b.contains(&rev_a));
+            } else {
+                assert!(seen_by_a.contains(&rev_b));
+            }
+
+            kv.release_lease("lease_race", rev_a).await?;
+            kv.release_lease("lease_race", rev_b).await?;
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "info", skip(self, kv))]
+    pub async fn kv_lease_expire<KV: KVApi>(&self, kv: &KV) -> anyhow::Result<()> {
+        info!("--- KVApiTestSuite::kv_lease_expire() start");
+
+        let rev1 = kv
+            .acquire_lease("lease_exp", b"h1".to_vec(), Duration::from_secs(2))
+            .await?;
+        let rev2 = kv
+            .acquire_lease("lease_exp", b"h2".to_vec(), Duration::from_secs(60))
+            .await?;
+
+        info!("--- the holder that is not extended expires, the next one owns the lease");
+        {
+            tokio::time::sleep(tokio::time::Duration::from_millis(3000)).await;
+            let holders = kv.list_lease_holders("lease_exp").await?;
+            assert_eq!(
+                holders.iter().map(|h| h.revision).collect::<Vec<_>>(),
+                vec![rev2]
+            );
+        }
+
+        info!("--- an expired holder can not be extended");
+        {
+            assert!(
+                !kv.extend_lease("lease_exp", rev1, Duration::from_secs(60))
+                    .await?
+            );
+            assert_eq!(kv.list_lease_holders("lease_exp").await?.len(), 1);
+        }
+
+        info!("--- an extended holder outlives its first ttl");
+        {
+            let rev3 = kv
+                .acquire_lease("lease_ext", b"h3".to_vec(), Duration::from_secs(2))
+                .await?;
+            assert!(
+                kv.extend_lease("lease_ext", rev3, Duration::from_secs(60))
+                    .await?
+            );
+            tokio::time::sleep(tokio::time::Duration::from_millis(3000)).await;
+            let holders = kv.list_lease_holders("lease_ext").await?;
+            assert_eq!(
+                holders.iter().map(|h| h.revision).collect::<Vec<_>>(),
+                vec![rev3]
+            );
+        }
+
+        Ok(())
+    }
+
     fn check_transaction_responses(
         &self,
         reply: &TxnReply,
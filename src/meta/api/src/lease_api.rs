@@ -0,0 +1,215 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use async_trait::async_trait;
+use common_meta_types::errors::app_error::AppError;
+use common_meta_types::errors::app_error::TxnRetryMaxTimes;
+use common_meta_types::txn_op::Request;
+use common_meta_types::ConditionResult;
+use common_meta_types::KVAppError;
+use common_meta_types::KVMeta;
+use common_meta_types::MatchSeq;
+use common_meta_types::Operation;
+use common_meta_types::TxnOp;
+use common_meta_types::TxnPutRequest;
+use common_meta_types::TxnRequest;
+use common_meta_types::UpsertKVReq;
+use tracing::debug;
+
+use crate::get_u64_value;
+use crate::send_txn;
+use crate::serialize_u64;
+use crate::txn_cond_seq;
+use crate::txn_op_put;
+use crate::IdGenerator;
+use crate::KVApi;
+use crate::TXN_MAX_RETRY_TIMES;
+
+const PREFIX_LEASE: &str = "__fd_lease";
+
+pub(crate) const ID_GEN_LEASE_REVISION: &str = "lease_revision";
+
+/// A holder of a lease that has neither released it nor let it expire.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeaseHolder {
+    /// Increasing across all the leases, the holder with the smallest revision owns the lease.
+    pub revision: u64,
+    /// Seconds since the unix epoch when the holder expires if not extended.
+    pub expire_at: Option<u64>,
+    /// The value the lease was acquired with, e.g., who is holding it.
+    pub value: Vec<u8>,
+}
+
+/// Leases with a TTL on top of the kv api, to serialize the access to a resource across nodes.
+///
+/// Acquiring the lease `name` queues a holder `__fd_lease/<name>/<revision>` that expires
+/// unless it is extended within the TTL. The holders are served in the order of their revisions:
+/// the one with the smallest revision owns the lease, the others wait for those before them
+/// to be released or to expire.
+#[async_trait]
+pub trait LeaseApi: Send + Sync {
+    /// Queue a new holder of the lease and return its revision.
+    ///
+    /// The revision is allocated and the holder is written atomically, so that the holders
+    /// become visible in the order of their revisions.
+    async fn acquire_lease(
+        &self,
+        name: &str,
+        value: Vec<u8>,
+        ttl: Duration,
+    ) -> Result<u64, KVAppError>;
+
+    /// Extend the TTL of a holder, returns false if it has expired or been released already.
+    async fn extend_lease(
+        &self,
+        name: &str,
+        revision: u64,
+        ttl: Duration,
+    ) -> Result<bool, KVAppError>;
+
+    /// Remove a holder from the lease, it is a no-op if the holder has expired.
+    async fn release_lease(&self, name: &str, revision: u64) -> Result<(), KVAppError>;
+
+    /// List the live holders of the lease ordered by revision, the first one owns the lease.
+    async fn list_lease_holders(&self, name: &str) -> Result<Vec<LeaseHolder>, KVAppError>;
+}
+
+#[async_trait]
+impl<KV: KVApi + ?Sized> LeaseApi for KV {
+    async fn acquire_lease(
+        &self,
+        name: &str,
+        value: Vec<u8>,
+        ttl: Duration,
+    ) -> Result<u64, KVAppError> {
+        let id_generator = IdGenerator::lease_revision();
+
+        let mut retry = 0;
+        while retry < TXN_MAX_RETRY_TIMES {
+            retry += 1;
+            let (id_seq, last_revision) = get_u64_value(self, &id_generator).await?;
+            debug!(id_seq, last_revision, name, "acquire_lease");
+
+            // Bump the revision and queue the holder in one transaction, if another holder took
+            // the revision in between, the seq of the generator changes and it is retried. Thus a
+            // holder with a smaller revision never becomes visible after one with a larger.
+            let revision = last_revision + 1;
+            let condition = vec![txn_cond_seq(&id_generator, ConditionResult::Eq, id_seq)];
+            let holder = TxnOp {
+                request: Some(Request::Put(TxnPutRequest {
+                    key: lease_holder_key(name, revision),
+                    value: value.clone(),
+                    prev_value: false,
+                    expire_at: lease_meta(ttl).expire_at,
+                })),
+            };
+            let if_then = vec![txn_op_put(&id_generator, serialize_u64(revision)?), holder];
+
+            let txn_req = TxnRequest {
+                condition,
+                if_then,
+                else_then: vec![],
+            };
+
+            let (succ, _responses) = send_txn(self, txn_req).await?;
+            debug!(name, revision, succ, "acquire_lease");
+
+            if succ {
+                return Ok(revision);
+            }
+        }
+
+        Err(KVAppError::AppError(AppError::TxnRetryMaxTimes(
+            TxnRetryMaxTimes::new("acquire_lease", TXN_MAX_RETRY_TIMES),
+        )))
+    }
+
+    async fn extend_lease(
+        &self,
+        name: &str,
+        revision: u64,
+        ttl: Duration,
+    ) -> Result<bool, KVAppError> {
+        let res = self
+            .upsert_kv(UpsertKVReq::new(
+                &lease_holder_key(name, revision),
+                MatchSeq::GE(1),
+                Operation::AsIs,
+                Some(lease_meta(ttl)),
+            ))
+            .await?;
+
+        Ok(res.result.is_some())
+    }
+
+    async fn release_lease(&self, name: &str, revision: u64) -> Result<(), KVAppError> {
+        self.upsert_kv(UpsertKVReq::new(
+            &lease_holder_key(name, revision),
+            MatchSeq::Any,
+            Operation::Delete,
+            None,
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    async fn list_lease_holders(&self, name: &str) -> Result<Vec<LeaseHolder>, KVAppError> {
+        let prefix = format!("{}/{}/", PREFIX_LEASE, name);
+        let values = self.prefix_list_kv(&prefix).await?;
+
+        let mut holders = Vec::with_capacity(values.len());
+        for (key, seq_v) in values {
+            // Skip the holders of the leases whose names are prefixed by this one, e.g., `a/b` of `a`.
+            let revision = match key[prefix.len()..].parse::<u64>() {
+                Ok(revision) => revision,
+                Err(_) => continue,
+            };
+            holders.push(LeaseHolder {
+                revision,
+                expire_at: seq_v.meta.and_then(|meta| meta.expire_at),
+                value: seq_v.data,
+            });
+        }
+        holders.sort_by_key(|holder| holder.revision);
+
+        Ok(holders)
+    }
+}
+
+/// __fd_lease/<name>/<revision>
+fn lease_holder_key(name: &str, revision: u64) -> String {
+    format!("{}/{}/{}", PREFIX_LEASE, name, revision)
+}
+
+fn lease_meta(ttl: Duration) -> KVMeta {
+    let expire_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        + ttl;
+
+    // Round up, so that the holder lives at least for `ttl`.
+    let mut expire_secs = expire_at.as_secs();
+    if expire_at.subsec_nanos() > 0 {
+        expire_secs += 1;
+    }
+
+    KVMeta {
+        expire_at: Some(expire_secs),
+    }
+}
@@ -20,6 +20,7 @@ mod id_generator;
 mod kv_api;
 mod kv_api_key;
 mod kv_api_test_suite;
+mod lease_api;
 mod schema_api;
 mod schema_api_impl;
 mod schema_api_keys;
@@ -41,6 +42,8 @@ pub use kv_api::KVApi;
 pub use kv_api_key::KVApiKey;
 pub use kv_api_key::KVApiKeyError;
 pub use kv_api_test_suite::KVApiTestSuite;
+pub use lease_api::LeaseApi;
+pub use lease_api::LeaseHolder;
 pub use schema_api::SchemaApi;
 pub(crate) use schema_api_impl::get_db_or_err;
 pub use schema_api_test_suite::SchemaApiTestSuite;
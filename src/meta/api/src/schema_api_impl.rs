@@ -2081,14 +2081,53 @@ impl<KV: KVApi> SchemaApi for KV {
                 )));
             }
 
+            let mut condition = vec![
+                // table is not changed
+                txn_cond_seq(&tbid, Eq, tb_meta_seq),
+            ];
+            let mut if_then = vec![
+                txn_op_put(&tbid, serialize_struct(&req.new_table_meta)?), // tb_id -> tb_meta
+            ];
+
+            // Upsert the options of other tables in the same transaction.
+            for option_req in &req.upsert_table_options {
+                let id = TableId {
+                    table_id: option_req.table_id,
+                };
+                let (seq, table_meta): (_, Option<TableMeta>) = get_struct_value(self, &id).await?;
+                if seq == 0 || table_meta.is_none() {
+                    return Err(KVAppError::AppError(AppError::UnknownTableId(
+                        UnknownTableId::new(option_req.table_id, "update_table_meta"),
+                    )));
+                }
+                if option_req.seq.match_seq(seq).is_err() {
+                    return Err(KVAppError::AppError(AppError::from(
+                        TableVersionMismatched::new(
+                            option_req.table_id,
+                            option_req.seq,
+                            seq,
+                            "update_table_meta",
+                        ),
+                    )));
+                }
+                let mut table_meta = table_meta.unwrap();
+                for (k, opt_v) in &option_req.options {
+                    match opt_v {
+                        None => {
+                            table_meta.options.remove(k);
+                        }
+                        Some(v) => {
+                            table_meta.options.insert(k.to_string(), v.to_string());
+                        }
+                    }
+                }
+                condition.push(txn_cond_seq(&id, Eq, seq));
+                if_then.push(txn_op_put(&id, serialize_struct(&table_meta)?));
+            }
+
             let txn_req = TxnRequest {
-                condition: vec![
-                    // table is not changed
-                    txn_cond_seq(&tbid, Eq, tb_meta_seq),
-                ],
-                if_then: vec![
-                    txn_op_put(&tbid, serialize_struct(&req.new_table_meta)?), // tb_id -> tb_meta
-                ],
+                condition,
+                if_then,
                 else_then: vec![],
             };
 
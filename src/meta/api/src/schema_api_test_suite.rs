@@ -2055,6 +2055,7 @@ impl SchemaApiTestSuite {
                     table_id,
                     seq: MatchSeq::Exact(table_version),
                     new_table_meta: new_table_meta.clone(),
+                    upsert_table_options: vec![],
                 })
                 .await?;
 
@@ -2074,6 +2075,7 @@ impl SchemaApiTestSuite {
                         table_id,
                         seq: MatchSeq::Exact(table_version + 1),
                         new_table_meta: new_table_meta.clone(),
+                        upsert_table_options: vec![],
                     })
                     .await;
 
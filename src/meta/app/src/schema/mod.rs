@@ -56,6 +56,8 @@ pub use table::GetTableReq;
 pub use table::ListTableReq;
 pub use table::RenameTableReply;
 pub use table::RenameTableReq;
+pub use table::SkippingIndexMeta;
+pub use table::SkippingIndexType;
 pub use table::TableCopiedFileInfo;
 pub use table::TableCopiedFileLock;
 pub use table::TableCopiedFileLockKey;
@@ -66,6 +68,7 @@ pub use table::TableIdListKey;
 pub use table::TableIdToName;
 pub use table::TableIdent;
 pub use table::TableInfo;
+pub use table::TableLockMeta;
 pub use table::TableMeta;
 pub use table::TableNameIdent;
 pub use table::TableStatistics;
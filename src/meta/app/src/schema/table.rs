@@ -22,6 +22,8 @@ use std::sync::Arc;
 
 use chrono::DateTime;
 use chrono::Utc;
+use common_expression::ColumnId;
+use common_expression::Scalar;
 use common_expression::TableSchema;
 use common_meta_types::MatchSeq;
 use common_storage::StorageParams;
@@ -196,6 +198,35 @@ pub struct TableStatistics {
     pub index_data_bytes: u64,
 }
 
+/// Type of a data skipping index.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SkippingIndexType {
+    /// Min and max values of the expression in a block.
+    MinMax,
+    /// Distinct values of the expression in a block, if there are no more than the given number.
+    Set(u64),
+    /// A bloom filter of the ngrams of the given length, for string expressions.
+    NgramBloomFilter(u64),
+}
+
+impl Display for SkippingIndexType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SkippingIndexType::MinMax => write!(f, "minmax"),
+            SkippingIndexType::Set(max_size) => write!(f, "set({})", max_size),
+            SkippingIndexType::NgramBloomFilter(n) => write!(f, "ngrambf({})", n),
+        }
+    }
+}
+
+/// A data skipping index defined on an expression of a table.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SkippingIndexMeta {
+    pub index_type: SkippingIndexType,
+    /// The indexed expression, e.g. `to_date(ts)`.
+    pub expr: String,
+}
+
 /// The essential state that defines what a table is.
 ///
 /// It is what a meta store just needs to save.
@@ -222,6 +253,12 @@ pub struct TableMeta {
     // if used in CreateTableReq, this field MUST set to None.
     pub drop_on: Option<DateTime<Utc>>,
     pub statistics: TableStatistics,
+    // The data skipping indexes, keyed by index name.
+    pub skipping_indexes: BTreeMap<String, SkippingIndexMeta>,
+    // The default values of the columns added by `ALTER TABLE ADD COLUMN`, keyed by column id.
+    // They are evaluated once when the column is added, and fill the column for the blocks
+    // written before it.
+    pub column_default_values: BTreeMap<ColumnId, Scalar>,
 }
 
 impl TableInfo {
@@ -298,6 +335,8 @@ impl Default for TableMeta {
             field_comments: vec![],
             drop_on: None,
             statistics: Default::default(),
+            skipping_indexes: BTreeMap::new(),
+            column_default_values: BTreeMap::new(),
         }
     }
 }
@@ -544,6 +583,9 @@ pub struct UpdateTableMetaReq {
     pub table_id: u64,
     pub seq: MatchSeq,
     pub new_table_meta: TableMeta,
+    /// Options of other tables to upsert in the same transaction,
+    /// e.g. the offsets of the streams consumed by this update.
+    pub upsert_table_options: Vec<UpsertTableOptionReq>,
 }
 
 impl UpsertTableOptionReq {
@@ -708,3 +750,12 @@ pub struct TableCopiedFileLockKey {
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct TableCopiedFileLock {}
+
+/// Who is holding or waiting for a table lock.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TableLockMeta {
+    pub user: String,
+    pub node: String,
+    pub query_id: String,
+    pub created_on: DateTime<Utc>,
+}
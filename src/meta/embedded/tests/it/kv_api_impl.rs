@@ -57,3 +57,21 @@ async fn test_kv_mget() -> anyhow::Result<()> {
     let kv = MetaEmbedded::new_temp().await?;
     KVApiTestSuite {}.kv_mget(&kv).await
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_kv_lease() -> anyhow::Result<()> {
+    let kv = MetaEmbedded::new_temp().await?;
+    KVApiTestSuite {}.kv_lease(&kv).await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_kv_lease_expire() -> anyhow::Result<()> {
+    let kv = MetaEmbedded::new_temp().await?;
+    KVApiTestSuite {}.kv_lease_expire(&kv).await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_kv_lease_concurrent_acquire() -> anyhow::Result<()> {
+    let kv = MetaEmbedded::new_temp().await?;
+    KVApiTestSuite {}.kv_lease_concurrent_acquire(&kv).await
+}
@@ -55,6 +55,8 @@ impl FromToProto for dv::DataSchema {
             min_reader_ver: MIN_READER_VER,
             fields: fs,
             metadata: self.meta().clone(),
+            // column ids are only maintained by `TableMeta`
+            next_column_id: 0,
         };
         Ok(p)
     }
@@ -82,6 +84,7 @@ impl FromToProto for dv::DataField {
             name: self.name().clone(),
             default_expr: self.default_expr().cloned(),
             data_type: Some(self.data_type().to_pb()?),
+            column_id: 0,
         };
         Ok(p)
     }
@@ -129,6 +132,7 @@ impl FromToProto for dv::DataTypeImpl {
                 Ok(dv::DataTypeImpl::VariantObject(dv::VariantObjectType {}))
             }
             Dt::IntervalType(x) => Ok(dv::DataTypeImpl::Interval(dv::IntervalType::from_pb(x)?)),
+            Dt::DecimalType(x) => Ok(dv::DataTypeImpl::Decimal(dv::DecimalType::from_pb(x)?)),
         }
     }
 
@@ -321,6 +325,16 @@ impl FromToProto for dv::DataTypeImpl {
                 };
                 Ok(p)
             }
+            dv::DataTypeImpl::Decimal(x) => {
+                let inn = x.to_pb()?;
+
+                let p = pb::DataType {
+                    ver: VER,
+                    min_reader_ver: MIN_READER_VER,
+                    dt: Some(Dt::DecimalType(inn)),
+                };
+                Ok(p)
+            }
         }
     }
 }
@@ -542,6 +556,26 @@ impl FromToProto for dv::IntervalType {
     }
 }
 
+impl FromToProto for dv::DecimalType {
+    type PB = pb::Decimal;
+    fn from_pb(p: pb::Decimal) -> Result<Self, Incompatible>
+    where Self: Sized {
+        reader_check_msg(p.ver, p.min_reader_ver)?;
+
+        Ok(Self::create(p.precision as u8, p.scale as u8))
+    }
+
+    fn to_pb(&self) -> Result<pb::Decimal, Incompatible> {
+        let p = pb::Decimal {
+            ver: VER,
+            min_reader_ver: MIN_READER_VER,
+            precision: self.precision() as u32,
+            scale: self.scale() as u32,
+        };
+        Ok(p)
+    }
+}
+
 impl FromToProto for dv::VariantType {
     type PB = pb::Variant;
     fn from_pb(p: pb::Variant) -> Result<Self, Incompatible>
@@ -82,6 +82,85 @@ impl FromToProto for mt::TableCopiedFileLock {
     }
 }
 
+impl FromToProto for mt::TableLockMeta {
+    type PB = pb::TableLockMeta;
+    fn from_pb(p: pb::TableLockMeta) -> Result<Self, Incompatible> {
+        reader_check_msg(p.ver, p.min_reader_ver)?;
+
+        let v = Self {
+            user: p.user,
+            node: p.node,
+            query_id: p.query_id,
+            created_on: DateTime::<Utc>::from_pb(p.created_on)?,
+        };
+        Ok(v)
+    }
+
+    fn to_pb(&self) -> Result<pb::TableLockMeta, Incompatible> {
+        let p = pb::TableLockMeta {
+            ver: VER,
+            min_reader_ver: MIN_READER_VER,
+            user: self.user.clone(),
+            node: self.node.clone(),
+            query_id: self.query_id.clone(),
+            created_on: self.created_on.to_pb()?,
+        };
+        Ok(p)
+    }
+}
+
+impl FromToProto for mt::SkippingIndexMeta {
+    type PB = pb::SkippingIndexMeta;
+    fn from_pb(p: pb::SkippingIndexMeta) -> Result<Self, Incompatible> {
+        reader_check_msg(p.ver, p.min_reader_ver)?;
+
+        let index_type = match p.index_type {
+            Some(pb::skipping_index_meta::IndexType::MinMax(_)) => mt::SkippingIndexType::MinMax,
+            Some(pb::skipping_index_meta::IndexType::Set(set)) => {
+                mt::SkippingIndexType::Set(set.max_size)
+            }
+            Some(pb::skipping_index_meta::IndexType::NgramBloomFilter(bf)) => {
+                mt::SkippingIndexType::NgramBloomFilter(bf.n)
+            }
+            None => {
+                return Err(Incompatible {
+                    reason: "SkippingIndexMeta.index_type can not be None".to_string(),
+                });
+            }
+        };
+
+        let v = Self {
+            index_type,
+            expr: p.expr,
+        };
+        Ok(v)
+    }
+
+    fn to_pb(&self) -> Result<pb::SkippingIndexMeta, Incompatible> {
+        let index_type = match self.index_type {
+            mt::SkippingIndexType::MinMax => {
+                pb::skipping_index_meta::IndexType::MinMax(pb::skipping_index_meta::MinMax {})
+            }
+            mt::SkippingIndexType::Set(max_size) => {
+                pb::skipping_index_meta::IndexType::Set(pb::skipping_index_meta::Set { max_size })
+            }
+            mt::SkippingIndexType::NgramBloomFilter(n) => {
+                pb::skipping_index_meta::IndexType::NgramBloomFilter(
+                    pb::skipping_index_meta::NgramBloomFilter { n },
+                )
+            }
+        };
+
+        let p = pb::SkippingIndexMeta {
+            ver: VER,
+            min_reader_ver: MIN_READER_VER,
+            index_type: Some(index_type),
+            expr: self.expr.clone(),
+        };
+        Ok(p)
+    }
+}
+
 impl FromToProto for mt::TableNameIdent {
     type PB = pb::TableNameIdent;
     fn from_pb(p: pb::TableNameIdent) -> Result<Self, Incompatible> {
@@ -174,9 +253,28 @@ impl FromToProto for mt::TableMeta {
             p.catalog
         };
 
+        let next_column_id = schema.next_column_id;
+        let column_ids = schema
+            .fields
+            .iter()
+            .map(|f| f.column_id)
+            .collect::<Vec<_>>();
         let dv_schema = dv::DataSchema::from_pb(schema)?;
+        let mut schema = from_schema(&dv_schema);
+        // Schemas written by older versions do not record the column ids,
+        // they keep the column ids assigned by position.
+        if next_column_id > 0 {
+            let fields = schema
+                .fields()
+                .iter()
+                .zip(column_ids)
+                .map(|(f, column_id)| f.clone().with_column_id(column_id))
+                .collect();
+            schema =
+                ce::TableSchema::new_from_column_ids(fields, schema.meta().clone(), next_column_id);
+        }
         let v = Self {
-            schema: Arc::new(from_schema(&dv_schema)),
+            schema: Arc::new(schema),
             catalog,
             engine: p.engine,
             engine_options: p.engine_options,
@@ -205,17 +303,37 @@ impl FromToProto for mt::TableMeta {
                 .map(mt::TableStatistics::from_pb)
                 .transpose()?
                 .unwrap_or_default(),
+            skipping_indexes: p
+                .skipping_indexes
+                .into_iter()
+                .map(|(name, index)| Ok((name, mt::SkippingIndexMeta::from_pb(index)?)))
+                .collect::<Result<_, Incompatible>>()?,
+            column_default_values: p
+                .column_default_values
+                .into_iter()
+                .map(|(column_id, value)| {
+                    let value =
+                        serde_json::from_str::<ce::Scalar>(&value).map_err(|e| Incompatible {
+                            reason: format!("invalid column default value: {}", e),
+                        })?;
+                    Ok((column_id, value))
+                })
+                .collect::<Result<_, Incompatible>>()?,
         };
         Ok(v)
     }
 
     fn to_pb(&self) -> Result<pb::TableMeta, Incompatible> {
-        let schema = to_schema(&self.schema);
+        let mut schema = to_schema(&self.schema).to_pb()?;
+        schema.next_column_id = self.schema.next_column_id();
+        for (pb_field, field) in schema.fields.iter_mut().zip(self.schema.fields()) {
+            pb_field.column_id = field.column_id();
+        }
         let p = pb::TableMeta {
             ver: VER,
             min_reader_ver: MIN_READER_VER,
             catalog: self.catalog.clone(),
-            schema: Some(schema.to_pb()?),
+            schema: Some(schema),
             engine: self.engine.clone(),
             engine_options: self.engine_options.clone(),
             storage_params: match self.storage_params.clone() {
@@ -240,6 +358,21 @@ impl FromToProto for mt::TableMeta {
             comment: self.comment.clone(),
             field_comments: self.field_comments.clone(),
             statistics: Some(self.statistics.to_pb()?),
+            skipping_indexes: self
+                .skipping_indexes
+                .iter()
+                .map(|(name, index)| Ok((name.clone(), index.to_pb()?)))
+                .collect::<Result<_, Incompatible>>()?,
+            column_default_values: self
+                .column_default_values
+                .iter()
+                .map(|(column_id, value)| {
+                    let value = serde_json::to_string(value).map_err(|e| Incompatible {
+                        reason: format!("invalid column default value: {}", e),
+                    })?;
+                    Ok((*column_id, value))
+                })
+                .collect::<Result<_, Incompatible>>()?,
         };
         Ok(p)
     }
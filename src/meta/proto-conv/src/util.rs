@@ -76,6 +76,24 @@ const META_CHANGE_LOG: &[(u64, &str)] = &[
     ),
     (22, "2022-12-13: Add: users.proto/FileFormatOptions::quote"),
     (23, "2022-12-28: Add: table.proto/TableMeta::part_prefix"),
+    (
+        24,
+        "2023-01-07: Add: metadata.proto/DataSchema::next_column_id, DataField::column_id",
+    ),
+    (25, "2023-01-16: Add: datatype.proto/DataType::decimal_type"),
+    (26, "2023-01-27: Add: table.proto/TableLockMeta"),
+    (
+        27,
+        "2023-01-30: Add: table.proto/TableMeta::skipping_indexes, table.proto/SkippingIndexMeta",
+    ),
+    (
+        28,
+        "2023-02-06: Add: user.proto/AuthInfo::Password::PasswordHashMethod::ScramSha256",
+    ),
+    (
+        29,
+        "2023-02-08: Add: table.proto/TableMeta::column_default_values",
+    ),
 ];
 
 /// The version to write into a message and it is also the version of the message reader.
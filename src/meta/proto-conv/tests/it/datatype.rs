@@ -15,6 +15,7 @@
 //! Test UserStageInfo
 
 use common_datavalues::DataTypeImpl;
+use common_datavalues::DecimalType;
 use common_datavalues::TimestampType;
 
 use crate::common;
@@ -34,6 +35,17 @@ fn test_datatype_v15() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_datatype_v25() -> anyhow::Result<()> {
+    // It is generated with common::test_pb_from_to.
+    let datatype_v25 = vec![
+        178, 1, 10, 8, 18, 16, 3, 160, 6, 25, 168, 6, 1, 160, 6, 25, 168, 6, 1,
+    ];
+    let want = DecimalType::new_impl(18, 3);
+    common::test_load_old(func_name!(), datatype_v25.as_slice(), want)?;
+    Ok(())
+}
+
 fn test_datatype() -> DataTypeImpl {
     TimestampType::new_impl()
 }
@@ -203,6 +203,10 @@ fn new_table_meta() -> mt::TableMeta {
         field_comments: vec!["c".to_string(); 21],
         drop_on: None,
         statistics: Default::default(),
+        skipping_indexes: btreemap! {s("idx") => new_skipping_index_meta_v27()},
+        column_default_values: btreemap! {
+            20 => ce::Scalar::Timestamp(1675814400000000),
+        },
     }
 }
 
@@ -264,6 +268,8 @@ fn new_table_meta_v10() -> mt::TableMeta {
         field_comments: vec!["c".to_string(); 21],
         drop_on: None,
         statistics: Default::default(),
+        skipping_indexes: btreemap! {},
+        column_default_values: btreemap! {},
     }
 }
 
@@ -325,6 +331,8 @@ fn new_table_meta_v12() -> mt::TableMeta {
         field_comments: vec!["c".to_string(); 21],
         drop_on: None,
         statistics: Default::default(),
+        skipping_indexes: btreemap! {},
+        column_default_values: btreemap! {},
     }
 }
 
@@ -386,6 +394,8 @@ fn new_table_meta_v23() -> mt::TableMeta {
         field_comments: vec!["c".to_string(); 21],
         drop_on: None,
         statistics: Default::default(),
+        skipping_indexes: btreemap! {},
+        column_default_values: btreemap! {},
     }
 }
 
@@ -401,6 +411,22 @@ fn new_table_copied_file_lock_v7() -> mt::TableCopiedFileLock {
     mt::TableCopiedFileLock {}
 }
 
+fn new_table_lock_meta_v26() -> mt::TableLockMeta {
+    mt::TableLockMeta {
+        user: "root".to_string(),
+        node: "node1".to_string(),
+        query_id: "query1".to_string(),
+        created_on: Utc.ymd(2014, 11, 28).and_hms(12, 0, 9),
+    }
+}
+
+fn new_skipping_index_meta_v27() -> mt::SkippingIndexMeta {
+    mt::SkippingIndexMeta {
+        index_type: mt::SkippingIndexType::Set(100),
+        expr: "to_date(ts)".to_string(),
+    }
+}
+
 #[test]
 fn test_pb_from_to() -> anyhow::Result<()> {
     let db = new_db_meta();
@@ -413,6 +439,19 @@ fn test_pb_from_to() -> anyhow::Result<()> {
     let got = mt::TableMeta::from_pb(p)?;
     assert_eq!(tbl, got);
 
+    // The column ids are kept after the columns are dropped and added.
+    let mut tbl = new_table_meta();
+    let mut schema = tbl.schema.as_ref().clone();
+    schema.drop_column("bool").unwrap();
+    schema
+        .add_column(&ce::TableField::new("bool2", ce::TableDataType::Boolean))
+        .unwrap();
+    assert_eq!(schema.field_with_name("bool2").unwrap().column_id(), 19);
+    tbl.schema = Arc::new(schema);
+    let p = tbl.to_pb()?;
+    let got = mt::TableMeta::from_pb(p)?;
+    assert_eq!(tbl, got);
+
     let share = new_share_meta();
     let p = share.to_pb()?;
     let got = share::ShareMeta::from_pb(p)?;
@@ -529,6 +568,26 @@ fn test_build_pb_buf() -> anyhow::Result<()> {
         println!("copied_file_lock:{:?}", buf);
     }
 
+    // TableLockMeta
+    {
+        let table_lock_meta = new_table_lock_meta_v26();
+        let p = table_lock_meta.to_pb()?;
+
+        let mut buf = vec![];
+        common_protos::prost::Message::encode(&p, &mut buf)?;
+        println!("table_lock_meta:{:?}", buf);
+    }
+
+    // SkippingIndexMeta
+    {
+        let skipping_index_meta = new_skipping_index_meta_v27();
+        let p = skipping_index_meta.to_pb()?;
+
+        let mut buf = vec![];
+        common_protos::prost::Message::encode(&p, &mut buf)?;
+        println!("skipping_index_meta:{:?}", buf);
+    }
+
     Ok(())
 }
 
@@ -924,5 +983,36 @@ fn test_load_old() -> anyhow::Result<()> {
         assert_eq!(want, got);
     }
 
+    // TableLockMeta is loadable
+    {
+        let table_lock_meta_v26: Vec<u8> = vec![
+            10, 4, 114, 111, 111, 116, 18, 5, 110, 111, 100, 101, 49, 26, 6, 113, 117, 101, 114,
+            121, 49, 34, 23, 50, 48, 49, 52, 45, 49, 49, 45, 50, 56, 32, 49, 50, 58, 48, 48, 58,
+            48, 57, 32, 85, 84, 67, 160, 6, 26, 168, 6, 1,
+        ];
+        let p: pb::TableLockMeta =
+            common_protos::prost::Message::decode(table_lock_meta_v26.as_slice())
+                .map_err(print_err)?;
+
+        let got = mt::TableLockMeta::from_pb(p).map_err(print_err)?;
+        let want = new_table_lock_meta_v26();
+        assert_eq!(want, got);
+    }
+
+    // SkippingIndexMeta is loadable
+    {
+        let skipping_index_meta_v27: Vec<u8> = vec![
+            18, 2, 8, 100, 34, 11, 116, 111, 95, 100, 97, 116, 101, 40, 116, 115, 41, 160, 6, 27,
+            168, 6, 1,
+        ];
+        let p: pb::SkippingIndexMeta =
+            common_protos::prost::Message::decode(skipping_index_meta_v27.as_slice())
+                .map_err(print_err)?;
+
+        let got = mt::SkippingIndexMeta::from_pb(p).map_err(print_err)?;
+        let want = new_skipping_index_meta_v27();
+        assert_eq!(want, got);
+    }
+
     Ok(())
 }
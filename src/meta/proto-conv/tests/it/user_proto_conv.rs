@@ -67,6 +67,14 @@ fn test_user_info() -> UserInfo {
     }
 }
 
+fn test_scram_auth_info_v28() -> mt::AuthInfo {
+    // The secret of the password "pencil" in the example of RFC 7677.
+    mt::AuthInfo::Password {
+        hash_value: b"SCRAM-SHA-256$4096:W22ZaJ0SNY7soEsUEjb6gQ==$WG5d8oPm3OtcPnkdi4Uo7BkeZkBFzpcXkuLmtbsT4qY=:wfPLwcE6nTWhTAmQ7tl2KeoiWGPlZqQxSrmfPwDl2dU=".to_vec(),
+        hash_method: mt::PasswordHashMethod::ScramSha256,
+    }
+}
+
 pub(crate) fn test_fs_stage_info() -> mt::UserStageInfo {
     mt::UserStageInfo {
         stage_name: "fs://dir/to/files".to_string(),
@@ -319,6 +327,10 @@ fn test_user_pb_from_to() -> anyhow::Result<()> {
     let got = mt::UserInfo::from_pb(test_user_info_pb)?;
     assert_eq!(got, test_user_info);
 
+    let scram_auth_info = test_scram_auth_info_v28();
+    let got = mt::AuthInfo::from_pb(scram_auth_info.to_pb()?)?;
+    assert_eq!(got, scram_auth_info);
+
     Ok(())
 }
 
@@ -484,6 +496,16 @@ fn test_build_user_pb_buf() -> anyhow::Result<()> {
         println!("user_info: {:?}", buf);
     }
 
+    // AuthInfo with a SCRAM-SHA-256 secret
+    {
+        let auth_info = test_scram_auth_info_v28();
+        let p = auth_info.to_pb()?;
+
+        let mut buf = vec![];
+        common_protos::prost::Message::encode(&p, &mut buf)?;
+        println!("scram_auth_info: {:?}", buf);
+    }
+
     // StageFile
     {
         let stage_file = test_stage_file();
@@ -629,6 +651,25 @@ fn test_load_old_user() -> anyhow::Result<()> {
         assert!(got.option.flags().is_empty());
     }
 
+    // AuthInfo with a SCRAM-SHA-256 secret is loadable
+    {
+        let scram_auth_info_v28: Vec<u8> = vec![
+            18, 138, 1, 10, 133, 1, 83, 67, 82, 65, 77, 45, 83, 72, 65, 45, 50, 53, 54, 36, 52, 48,
+            57, 54, 58, 87, 50, 50, 90, 97, 74, 48, 83, 78, 89, 55, 115, 111, 69, 115, 85, 69, 106,
+            98, 54, 103, 81, 61, 61, 36, 87, 71, 53, 100, 56, 111, 80, 109, 51, 79, 116, 99, 80,
+            110, 107, 100, 105, 52, 85, 111, 55, 66, 107, 101, 90, 107, 66, 70, 122, 112, 99, 88,
+            107, 117, 76, 109, 116, 98, 115, 84, 52, 113, 89, 61, 58, 119, 102, 80, 76, 119, 99,
+            69, 54, 110, 84, 87, 104, 84, 65, 109, 81, 55, 116, 108, 50, 75, 101, 111, 105, 87, 71,
+            80, 108, 90, 113, 81, 120, 83, 114, 109, 102, 80, 119, 68, 108, 50, 100, 85, 61, 16, 3,
+            160, 6, 28, 168, 6, 1,
+        ];
+        let p: pb::AuthInfo = common_protos::prost::Message::decode(scram_auth_info_v28.as_slice())
+            .map_err(print_err)?;
+        let got = mt::AuthInfo::from_pb(p).map_err(print_err)?;
+        let want = test_scram_auth_info_v28();
+        assert_eq!(want, got);
+    }
+
     Ok(())
 }
 
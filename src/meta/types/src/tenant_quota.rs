@@ -34,6 +34,15 @@ pub struct TenantQuota {
 
     // The max number of users can be created in the tenant.
     pub max_users: u32,
+
+    // The max storage(bytes) can be used by the tables of the tenant(0 is no limited).
+    pub max_storage_in_bytes: u64,
+
+    // The max storage(bytes) can be used by the tables per database(0 is no limited).
+    pub max_storage_per_database_in_bytes: u64,
+
+    // The max rows per table(0 is no limited).
+    pub max_rows_per_table: u64,
 }
 
 impl TryFrom<Vec<u8>> for TenantQuota {
@@ -15,12 +15,16 @@ use std::str::FromStr;
 
 use common_exception::ErrorCode;
 use common_exception::Result;
+use hmac::Hmac;
+use hmac::Mac;
+use rand::RngCore;
 use sha2::Digest;
 use sha2::Sha256;
 
 const NO_PASSWORD_STR: &str = "no_password";
 const SHA256_PASSWORD_STR: &str = "sha256_password";
 const DOUBLE_SHA1_PASSWORD_STR: &str = "double_sha1_password";
+const SCRAM_SHA256_PASSWORD_STR: &str = "scram_sha256_password";
 const JWT_AUTH_STR: &str = "jwt";
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
@@ -28,6 +32,7 @@ pub enum AuthType {
     NoPassword,
     Sha256Password,
     DoubleSha1Password,
+    ScramSha256Password,
     JWT,
 }
 
@@ -37,6 +42,7 @@ impl std::str::FromStr for AuthType {
         match s {
             SHA256_PASSWORD_STR => Ok(AuthType::Sha256Password),
             DOUBLE_SHA1_PASSWORD_STR => Ok(AuthType::DoubleSha1Password),
+            SCRAM_SHA256_PASSWORD_STR => Ok(AuthType::ScramSha256Password),
             NO_PASSWORD_STR => Ok(AuthType::NoPassword),
             JWT_AUTH_STR => Ok(AuthType::JWT),
             _ => Err(ErrorCode::InvalidAuthInfo(AuthType::bad_auth_types(s))),
@@ -50,6 +56,7 @@ impl AuthType {
             AuthType::NoPassword => NO_PASSWORD_STR,
             AuthType::Sha256Password => SHA256_PASSWORD_STR,
             AuthType::DoubleSha1Password => DOUBLE_SHA1_PASSWORD_STR,
+            AuthType::ScramSha256Password => SCRAM_SHA256_PASSWORD_STR,
             AuthType::JWT => JWT_AUTH_STR,
         }
     }
@@ -59,6 +66,7 @@ impl AuthType {
             NO_PASSWORD_STR,
             SHA256_PASSWORD_STR,
             DOUBLE_SHA1_PASSWORD_STR,
+            SCRAM_SHA256_PASSWORD_STR,
             JWT_AUTH_STR,
         ];
         let all = all
@@ -73,6 +81,7 @@ impl AuthType {
         match self {
             AuthType::Sha256Password => Some(PasswordHashMethod::Sha256),
             AuthType::DoubleSha1Password => Some(PasswordHashMethod::DoubleSha1),
+            AuthType::ScramSha256Password => Some(PasswordHashMethod::ScramSha256),
             _ => None,
         }
     }
@@ -103,7 +112,9 @@ impl AuthInfo {
         match auth_type {
             AuthType::NoPassword => Ok(AuthInfo::None),
             AuthType::JWT => Ok(AuthInfo::JWT),
-            AuthType::Sha256Password | AuthType::DoubleSha1Password => match auth_string {
+            AuthType::Sha256Password
+            | AuthType::DoubleSha1Password
+            | AuthType::ScramSha256Password => match auth_string {
                 Some(p) => {
                     let method = auth_type.get_password_type().unwrap();
                     Ok(AuthInfo::Password {
@@ -167,6 +178,7 @@ impl AuthInfo {
             } => match t {
                 PasswordHashMethod::Sha256 => AuthType::Sha256Password,
                 PasswordHashMethod::DoubleSha1 => AuthType::DoubleSha1Password,
+                PasswordHashMethod::ScramSha256 => AuthType::ScramSha256Password,
             },
         }
     }
@@ -232,6 +244,9 @@ impl AuthInfo {
                 PasswordHashMethod::Sha256 => Err(ErrorCode::AuthenticateFailure(
                     "login with sha256_password user for mysql protocol not supported yet.",
                 )),
+                PasswordHashMethod::ScramSha256 => Err(ErrorCode::AuthenticateFailure(
+                    "login with scram_sha256_password user for mysql protocol not supported yet.",
+                )),
             },
             _ => Err(ErrorCode::AuthenticateFailure(format!(
                 "user require auth type {}",
@@ -239,6 +254,22 @@ impl AuthInfo {
             ))),
         }
     }
+
+    // Only the hash of the password is stored, so the postgres protocol asks the client
+    // for the cleartext password and checks it against the hash.
+    pub fn auth_postgres(&self, password_input: &[u8]) -> Result<bool> {
+        match self {
+            AuthInfo::None => Ok(true),
+            AuthInfo::Password {
+                hash_value: p,
+                hash_method: t,
+            } => Ok(t.verify(p, password_input)),
+            _ => Err(ErrorCode::AuthenticateFailure(format!(
+                "user require auth type {}",
+                self.get_type().to_str()
+            ))),
+        }
+    }
 }
 
 impl Default for AuthInfo {
@@ -262,18 +293,36 @@ impl Default for AuthInfo {
 pub enum PasswordHashMethod {
     DoubleSha1 = 1,
     Sha256 = 2,
+    ScramSha256 = 3,
 }
 
 impl PasswordHashMethod {
+    /// The stored value of a password. The SCRAM secret is salted randomly, it is checked
+    /// by `verify` instead of comparing the hash values.
     pub fn hash(self, user_input: &[u8]) -> Vec<u8> {
         match self {
             PasswordHashMethod::DoubleSha1 => double_sha1(user_input).to_vec(),
             PasswordHashMethod::Sha256 => Sha256::digest(user_input).to_vec(),
+            PasswordHashMethod::ScramSha256 => ScramSha256Secret::generate(user_input)
+                .encode()
+                .into_bytes(),
+        }
+    }
+
+    /// Checks the cleartext password against the stored value.
+    pub fn verify(self, hash_value: &[u8], user_input: &[u8]) -> bool {
+        match self {
+            PasswordHashMethod::ScramSha256 => ScramSha256Secret::parse(hash_value)
+                .map_or(false, |secret| secret.verify(user_input)),
+            _ => self.hash(user_input) == hash_value,
         }
     }
 
     fn to_string(self, hash_value: &[u8]) -> String {
-        hex::encode(hash_value)
+        match self {
+            PasswordHashMethod::ScramSha256 => String::from_utf8_lossy(hash_value).to_string(),
+            _ => hex::encode(hash_value),
+        }
     }
 }
 
@@ -282,3 +331,118 @@ impl Default for PasswordHashMethod {
         PasswordHashMethod::Sha256
     }
 }
+
+const SCRAM_SHA256_PREFIX: &str = "SCRAM-SHA-256";
+const SCRAM_ITERATIONS: u32 = 4096;
+const SCRAM_SALT_LEN: usize = 16;
+
+/// The SCRAM-SHA-256 secret of a password (RFC 5802, RFC 7677), stored in the PostgreSQL
+/// format `SCRAM-SHA-256$<iterations>:<salt>$<StoredKey>:<ServerKey>` with base64 values.
+///
+/// The password is not normalized by SASLprep, like PostgreSQL does for the passwords
+/// which are not valid UTF-8.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScramSha256Secret {
+    pub iterations: u32,
+    pub salt: Vec<u8>,
+    pub stored_key: Vec<u8>,
+    pub server_key: Vec<u8>,
+}
+
+impl ScramSha256Secret {
+    pub fn generate(password: &[u8]) -> Self {
+        let mut salt = vec![0; SCRAM_SALT_LEN];
+        rand::thread_rng().fill_bytes(&mut salt);
+        Self::create(password, salt, SCRAM_ITERATIONS)
+    }
+
+    pub fn create(password: &[u8], salt: Vec<u8>, iterations: u32) -> Self {
+        let salted_password = scram_salted_password(password, &salt, iterations);
+        let client_key = hmac_sha256(&salted_password, b"Client Key");
+        ScramSha256Secret {
+            iterations,
+            salt,
+            stored_key: Sha256::digest(client_key).to_vec(),
+            server_key: hmac_sha256(&salted_password, b"Server Key"),
+        }
+    }
+
+    pub fn parse(hash_value: &[u8]) -> Result<Self> {
+        let invalid = || ErrorCode::InvalidAuthInfo("invalid SCRAM-SHA-256 secret");
+        let secret = std::str::from_utf8(hash_value).map_err(|_| invalid())?;
+        let (prefix, rest) = secret.split_once('$').ok_or_else(invalid)?;
+        let (params, keys) = rest.split_once('$').ok_or_else(invalid)?;
+        let (iterations, salt) = params.split_once(':').ok_or_else(invalid)?;
+        let (stored_key, server_key) = keys.split_once(':').ok_or_else(invalid)?;
+        if prefix != SCRAM_SHA256_PREFIX {
+            return Err(invalid());
+        }
+
+        Ok(ScramSha256Secret {
+            iterations: iterations.parse().map_err(|_| invalid())?,
+            salt: base64::decode(salt).map_err(|_| invalid())?,
+            stored_key: base64::decode(stored_key).map_err(|_| invalid())?,
+            server_key: base64::decode(server_key).map_err(|_| invalid())?,
+        })
+    }
+
+    pub fn encode(&self) -> String {
+        format!(
+            "{}${}:{}${}:{}",
+            SCRAM_SHA256_PREFIX,
+            self.iterations,
+            base64::encode(&self.salt),
+            base64::encode(&self.stored_key),
+            base64::encode(&self.server_key)
+        )
+    }
+
+    /// Checks a cleartext password, for the protocols without SCRAM.
+    pub fn verify(&self, password: &[u8]) -> bool {
+        let secret = Self::create(password, self.salt.clone(), self.iterations);
+        secret.stored_key == self.stored_key && secret.server_key == self.server_key
+    }
+
+    /// Checks the `ClientProof` of a client over the `AuthMessage` of the exchange.
+    pub fn verify_client_proof(&self, auth_message: &[u8], client_proof: &[u8]) -> bool {
+        let client_signature = hmac_sha256(&self.stored_key, auth_message);
+        if client_proof.len() != client_signature.len() {
+            return false;
+        }
+
+        let client_key = client_proof
+            .iter()
+            .zip(client_signature.iter())
+            .map(|(a, b)| a ^ b)
+            .collect::<Vec<_>>();
+        Sha256::digest(client_key).as_slice() == self.stored_key.as_slice()
+    }
+
+    /// The `ServerSignature` proving to the client that the server knows the secret.
+    pub fn server_signature(&self, auth_message: &[u8]) -> Vec<u8> {
+        hmac_sha256(&self.server_key, auth_message)
+    }
+}
+
+fn hmac_sha256(key: &[u8], message: &[u8]) -> Vec<u8> {
+    // HMAC accepts keys of any length.
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key).unwrap();
+    Mac::update(&mut mac, message);
+    mac.finalize().into_bytes().to_vec()
+}
+
+// Hi() of RFC 5802, PBKDF2 with HMAC-SHA-256 and a single block.
+fn scram_salted_password(password: &[u8], salt: &[u8], iterations: u32) -> Vec<u8> {
+    let mut message = salt.to_vec();
+    message.extend_from_slice(&1u32.to_be_bytes());
+
+    let mut u = hmac_sha256(password, &message);
+    let mut result = u.clone();
+    for _ in 1..iterations {
+        u = hmac_sha256(password, &u);
+        for (r, v) in result.iter_mut().zip(u.iter()) {
+            *r ^= v;
+        }
+    }
+    result
+}
@@ -167,12 +167,15 @@ impl UserOption {
 #[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, num_derive::FromPrimitive)]
 pub enum UserOptionFlag {
     TenantSetting = 1 << 0,
+    /// The user is only allowed to connect through TLS.
+    RequireTls = 1 << 1,
 }
 
 impl std::fmt::Display for UserOptionFlag {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             UserOptionFlag::TenantSetting => write!(f, "TENANTSETTING"),
+            UserOptionFlag::RequireTls => write!(f, "REQUIRETLS"),
         }
     }
 }
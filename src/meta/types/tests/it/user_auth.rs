@@ -0,0 +1,76 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::exception::Result;
+use common_meta_types::AuthInfo;
+use common_meta_types::AuthType;
+use common_meta_types::PasswordHashMethod;
+use common_meta_types::ScramSha256Secret;
+
+// The example exchange of RFC 7677, the password is "pencil".
+const SALT: &str = "W22ZaJ0SNY7soEsUEjb6gQ==";
+const SECRET: &str = "SCRAM-SHA-256$4096:W22ZaJ0SNY7soEsUEjb6gQ==$WG5d8oPm3OtcPnkdi4Uo7BkeZkBFzpcXkuLmtbsT4qY=:wfPLwcE6nTWhTAmQ7tl2KeoiWGPlZqQxSrmfPwDl2dU=";
+const AUTH_MESSAGE: &str = "n=user,r=rOprNGfwEbeRWgbNEkqO,\
+    r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096,\
+    c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0";
+
+#[test]
+fn test_scram_sha256_secret() -> Result<()> {
+    let secret = ScramSha256Secret::create(b"pencil", base64::decode(SALT).unwrap(), 4096);
+    assert_eq!(secret.encode(), SECRET);
+    assert_eq!(ScramSha256Secret::parse(SECRET.as_bytes())?, secret);
+    assert!(ScramSha256Secret::parse(b"SCRAM-SHA-1$4096:c2FsdA==$a2V5:a2V5").is_err());
+
+    assert!(secret.verify(b"pencil"));
+    assert!(!secret.verify(b"pencil2"));
+
+    let proof = base64::decode("dHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ=").unwrap();
+    assert!(secret.verify_client_proof(AUTH_MESSAGE.as_bytes(), &proof));
+    assert!(!secret.verify_client_proof(b"n=user,r=other", &proof));
+    assert_eq!(
+        base64::encode(secret.server_signature(AUTH_MESSAGE.as_bytes())),
+        "6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4="
+    );
+    Ok(())
+}
+
+#[test]
+fn test_scram_sha256_auth_info() -> Result<()> {
+    let auth_info = AuthInfo::create2(
+        &Some(AuthType::ScramSha256Password),
+        &Some("pencil".to_string()),
+    )?;
+    assert_eq!(auth_info.get_type(), AuthType::ScramSha256Password);
+    assert_eq!(
+        auth_info.get_password_type(),
+        Some(PasswordHashMethod::ScramSha256)
+    );
+    assert!(
+        auth_info
+            .get_auth_string()
+            .starts_with("SCRAM-SHA-256$4096:")
+    );
+
+    // The secrets are salted randomly.
+    let other = AuthInfo::create2(
+        &Some(AuthType::ScramSha256Password),
+        &Some("pencil".to_string()),
+    )?;
+    assert_ne!(auth_info, other);
+
+    assert!(auth_info.auth_postgres(b"pencil")?);
+    assert!(!auth_info.auth_postgres(b"pencil2")?);
+    assert!(auth_info.auth_mysql(b"pencil", b"salt").is_err());
+    Ok(())
+}
@@ -21,6 +21,7 @@ use common_exception::Result;
 use crate::ast::write_comma_separated_list;
 use crate::ast::write_period_separated_list;
 use crate::ast::Identifier;
+use crate::ast::OrderByExpr;
 use crate::ast::Query;
 use crate::parser::token::Token;
 
@@ -138,7 +139,12 @@ pub enum Expr<'a> {
     /// A literal value, such as string, number, date or NULL
     Literal { span: &'a [Token<'a>], lit: Literal },
     /// `COUNT(*)` expression
-    CountAll { span: &'a [Token<'a>] },
+    CountAll {
+        span: &'a [Token<'a>],
+        window: Option<WindowSpec<'a>>,
+    },
+    /// `?` placeholder of a prepared statement, bound to the parameter by the binder
+    Placeholder { span: &'a [Token<'a>] },
     /// `(foo, bar)`
     Tuple {
         span: &'a [Token<'a>],
@@ -152,6 +158,9 @@ pub enum Expr<'a> {
         name: Identifier<'a>,
         args: Vec<Expr<'a>>,
         params: Vec<Literal>,
+        /// The window specification following `OVER`, only set for window functions,
+        /// like `ROW_NUMBER() OVER (PARTITION BY a ORDER BY b)`
+        window: Option<WindowSpec<'a>>,
     },
     /// `CASE ... WHEN ... ELSE ...` expression
     Case {
@@ -210,6 +219,40 @@ pub enum Expr<'a> {
     },
 }
 
+/// The window specification of a window function call:
+/// `OVER ([PARTITION BY <expr>, ...] [ORDER BY <expr>, ...] [<frame>])`
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowSpec<'a> {
+    pub partition_by: Vec<Expr<'a>>,
+    pub order_by: Vec<OrderByExpr<'a>>,
+    pub window_frame: Option<WindowFrame<'a>>,
+}
+
+/// `{ROWS | RANGE} BETWEEN <start_bound> AND <end_bound>`, or `{ROWS | RANGE} <start_bound>`
+/// which is a shorthand of `{ROWS | RANGE} BETWEEN <start_bound> AND CURRENT ROW`
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowFrame<'a> {
+    pub units: WindowFrameUnits,
+    pub start_bound: WindowFrameBound<'a>,
+    pub end_bound: WindowFrameBound<'a>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowFrameUnits {
+    Rows,
+    Range,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WindowFrameBound<'a> {
+    /// `CURRENT ROW`
+    CurrentRow,
+    /// `<expr> PRECEDING` or `UNBOUNDED PRECEDING`
+    Preceding(Option<Box<Expr<'a>>>),
+    /// `<expr> FOLLOWING` or `UNBOUNDED FOLLOWING`
+    Following(Option<Box<Expr<'a>>>),
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SubqueryModifier {
     Any,
@@ -255,6 +298,10 @@ pub enum TypeName {
     Int64,
     Float32,
     Float64,
+    Decimal {
+        precision: u8,
+        scale: u8,
+    },
     Date,
     Timestamp,
     String,
@@ -377,7 +424,8 @@ impl<'a> Expr<'a> {
             | Expr::Substring { span, .. }
             | Expr::Trim { span, .. }
             | Expr::Literal { span, .. }
-            | Expr::CountAll { span }
+            | Expr::CountAll { span, .. }
+            | Expr::Placeholder { span }
             | Expr::Tuple { span, .. }
             | Expr::FunctionCall { span, .. }
             | Expr::Case { span, .. }
@@ -409,6 +457,63 @@ impl Display for IntervalKind {
     }
 }
 
+impl<'a> Display for WindowSpec<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut first = true;
+        if !self.partition_by.is_empty() {
+            first = false;
+            write!(f, "PARTITION BY ")?;
+            write_comma_separated_list(f, &self.partition_by)?;
+        }
+        if !self.order_by.is_empty() {
+            if !first {
+                write!(f, " ")?;
+            }
+            first = false;
+            write!(f, "ORDER BY ")?;
+            write_comma_separated_list(f, &self.order_by)?;
+        }
+        if let Some(frame) = &self.window_frame {
+            if !first {
+                write!(f, " ")?;
+            }
+            write!(f, "{frame}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Display for WindowFrame<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} BETWEEN {} AND {}",
+            self.units, self.start_bound, self.end_bound
+        )
+    }
+}
+
+impl Display for WindowFrameUnits {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WindowFrameUnits::Rows => write!(f, "ROWS"),
+            WindowFrameUnits::Range => write!(f, "RANGE"),
+        }
+    }
+}
+
+impl<'a> Display for WindowFrameBound<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WindowFrameBound::CurrentRow => write!(f, "CURRENT ROW"),
+            WindowFrameBound::Preceding(None) => write!(f, "UNBOUNDED PRECEDING"),
+            WindowFrameBound::Preceding(Some(n)) => write!(f, "{n} PRECEDING"),
+            WindowFrameBound::Following(None) => write!(f, "UNBOUNDED FOLLOWING"),
+            WindowFrameBound::Following(Some(n)) => write!(f, "{n} FOLLOWING"),
+        }
+    }
+}
+
 impl Display for SubqueryModifier {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -553,6 +658,9 @@ impl Display for TypeName {
             TypeName::Float64 => {
                 write!(f, "Float64")?;
             }
+            TypeName::Decimal { precision, scale } => {
+                write!(f, "Decimal({}, {})", precision, scale)?;
+            }
             TypeName::Date => {
                 write!(f, "DATE")?;
             }
@@ -787,8 +895,14 @@ impl<'a> Display for Expr<'a> {
             Expr::Literal { lit, .. } => {
                 write!(f, "{lit}")?;
             }
-            Expr::CountAll { .. } => {
+            Expr::CountAll { window, .. } => {
                 write!(f, "COUNT(*)")?;
+                if let Some(window) = window {
+                    write!(f, " OVER ({window})")?;
+                }
+            }
+            Expr::Placeholder { .. } => {
+                write!(f, "?")?;
             }
             Expr::Tuple { exprs, .. } => {
                 write!(f, "(")?;
@@ -803,6 +917,7 @@ impl<'a> Display for Expr<'a> {
                 name,
                 args,
                 params,
+                window,
                 ..
             } => {
                 write!(f, "{name}")?;
@@ -817,6 +932,9 @@ impl<'a> Display for Expr<'a> {
                 }
                 write_comma_separated_list(f, args)?;
                 write!(f, ")")?;
+                if let Some(window) = window {
+                    write!(f, " OVER ({window})")?;
+                }
             }
             Expr::Case {
                 operand,
@@ -430,13 +430,26 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         self.children.push(node);
     }
 
-    fn visit_count_all(&mut self, _span: &'ast [Token<'ast>]) {
-        let name = "Function CountAll".to_string();
+    fn visit_count_all(
+        &mut self,
+        _span: &'ast [Token<'ast>],
+        window: &'ast Option<WindowSpec<'ast>>,
+    ) {
+        let name = match window {
+            Some(window) => format!("Function CountAll Over({window})"),
+            None => "Function CountAll".to_string(),
+        };
         let format_ctx = AstFormatContext::new(name);
         let node = FormatTreeNode::new(format_ctx);
         self.children.push(node);
     }
 
+    fn visit_placeholder(&mut self, _span: &'ast [Token<'ast>]) {
+        let format_ctx = AstFormatContext::new("Placeholder".to_string());
+        let node = FormatTreeNode::new(format_ctx);
+        self.children.push(node);
+    }
+
     fn visit_tuple(&mut self, _span: &'ast [Token<'ast>], elements: &'ast [Expr<'ast>]) {
         let mut children = Vec::with_capacity(elements.len());
         for element in elements.iter() {
@@ -456,17 +469,21 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         name: &'ast Identifier<'ast>,
         args: &'ast [Expr<'ast>],
         _params: &'ast [Literal],
+        window: &'ast Option<WindowSpec<'ast>>,
     ) {
         let mut children = Vec::with_capacity(args.len());
         for arg in args.iter() {
             self.visit_expr(arg);
             children.push(self.children.pop().unwrap());
         }
-        let node_name = if distinct {
+        let mut node_name = if distinct {
             format!("Function {name}Distinct")
         } else {
             format!("Function {name}")
         };
+        if let Some(window) = window {
+            node_name = format!("{node_name} Over({window})");
+        }
         let format_ctx = AstFormatContext::with_children(node_name, children.len());
         let node = FormatTreeNode::with_children(format_ctx, children);
         self.children.push(node);
@@ -1036,6 +1053,60 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         self.children.push(node);
     }
 
+    fn visit_merge_into(&mut self, merge_into: &'ast MergeIntoStmt<'ast>) {
+        let mut children = Vec::new();
+        self.visit_table_reference(&merge_into.table);
+        children.push(self.children.pop().unwrap());
+        self.visit_table_reference(&merge_into.source);
+        children.push(self.children.pop().unwrap());
+        self.visit_expr(&merge_into.join_expr);
+        children.push(self.children.pop().unwrap());
+
+        for merge_option in merge_into.merge_options.iter() {
+            let mut option_children = Vec::new();
+            let (name, selection) = match merge_option {
+                MergeOption::Match(clause) => {
+                    let name = match &clause.operation {
+                        MatchOperation::Update { update_list } => {
+                            for update_expr in update_list.iter() {
+                                self.visit_identifier(&update_expr.name);
+                                option_children.push(self.children.pop().unwrap());
+                                self.visit_expr(&update_expr.expr);
+                                option_children.push(self.children.pop().unwrap());
+                            }
+                            "MatchedUpdate".to_string()
+                        }
+                        MatchOperation::Delete => "MatchedDelete".to_string(),
+                    };
+                    (name, &clause.selection)
+                }
+                MergeOption::Unmatch(clause) => {
+                    for column in clause.columns.iter().flatten() {
+                        self.visit_identifier(column);
+                        option_children.push(self.children.pop().unwrap());
+                    }
+                    for value in clause.values.iter() {
+                        self.visit_expr(value);
+                        option_children.push(self.children.pop().unwrap());
+                    }
+                    ("UnmatchedInsert".to_string(), &clause.selection)
+                }
+            };
+            if let Some(selection) = selection {
+                self.visit_expr(selection);
+                option_children.push(self.children.pop().unwrap());
+            }
+            let format_ctx = AstFormatContext::with_children(name, option_children.len());
+            let node = FormatTreeNode::with_children(format_ctx, option_children);
+            children.push(node);
+        }
+
+        let name = "MergeInto".to_string();
+        let format_ctx = AstFormatContext::with_children(name, children.len());
+        let node = FormatTreeNode::with_children(format_ctx, children);
+        self.children.push(node);
+    }
+
     fn visit_show_databases(&mut self, stmt: &'ast ShowDatabasesStmt<'ast>) {
         let mut children = Vec::new();
         if let Some(limit) = &stmt.limit {
@@ -1309,6 +1380,26 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
                 let action_format_ctx = AstFormatContext::new(action_name);
                 FormatTreeNode::new(action_format_ctx)
             }
+            AlterTableAction::AddColumn { column } => {
+                self.visit_column_definition(column);
+                let column_node = self.children.pop().unwrap();
+                let action_name = "Action AddColumn".to_string();
+                let action_format_ctx = AstFormatContext::with_children(action_name, 1);
+                FormatTreeNode::with_children(action_format_ctx, vec![column_node])
+            }
+            AlterTableAction::DropColumn { column } => {
+                let action_name = format!("Action DropColumn {}", column);
+                let action_format_ctx = AstFormatContext::new(action_name);
+                FormatTreeNode::new(action_format_ctx)
+            }
+            AlterTableAction::RenameColumn {
+                old_column,
+                new_column,
+            } => {
+                let action_name = format!("Action RenameColumn {} To {}", old_column, new_column);
+                let action_format_ctx = AstFormatContext::new(action_name);
+                FormatTreeNode::new(action_format_ctx)
+            }
             AlterTableAction::AlterTableClusterKey { cluster_by } => {
                 let mut cluster_by_children = Vec::with_capacity(cluster_by.len());
                 for cluster_by_expr in cluster_by.iter() {
@@ -1408,6 +1499,28 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         self.children.push(node);
     }
 
+    fn visit_create_index(&mut self, stmt: &'ast CreateIndexStmt<'ast>) {
+        self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.table);
+        let table_child = self.children.pop().unwrap();
+        self.visit_expr(&stmt.expr);
+        let expr_child = self.children.pop().unwrap();
+
+        let name = format!("CreateIndex {} {}", stmt.index, stmt.index_type);
+        let format_ctx = AstFormatContext::with_children(name, 2);
+        let node = FormatTreeNode::with_children(format_ctx, vec![table_child, expr_child]);
+        self.children.push(node);
+    }
+
+    fn visit_drop_index(&mut self, stmt: &'ast DropIndexStmt<'ast>) {
+        self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.table);
+        let child = self.children.pop().unwrap();
+
+        let name = format!("DropIndex {}", stmt.index);
+        let format_ctx = AstFormatContext::with_children(name, 1);
+        let node = FormatTreeNode::with_children(format_ctx, vec![child]);
+        self.children.push(node);
+    }
+
     fn visit_create_view(&mut self, stmt: &'ast CreateViewStmt<'ast>) {
         self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.view);
         let view_child = self.children.pop().unwrap();
@@ -1442,6 +1555,50 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         self.children.push(node);
     }
 
+    fn visit_create_materialized_view(&mut self, stmt: &'ast CreateMaterializedViewStmt<'ast>) {
+        self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.view);
+        let view_child = self.children.pop().unwrap();
+        self.visit_query(&stmt.query);
+        let query_child = self.children.pop().unwrap();
+
+        let name = "CreateMaterializedView".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 2);
+        let node = FormatTreeNode::with_children(format_ctx, vec![view_child, query_child]);
+        self.children.push(node);
+    }
+
+    fn visit_refresh_materialized_view(&mut self, stmt: &'ast RefreshMaterializedViewStmt<'ast>) {
+        self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.view);
+        let child = self.children.pop().unwrap();
+
+        let name = "RefreshMaterializedView".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 1);
+        let node = FormatTreeNode::with_children(format_ctx, vec![child]);
+        self.children.push(node);
+    }
+
+    fn visit_create_stream(&mut self, stmt: &'ast CreateStreamStmt<'ast>) {
+        self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.stream);
+        let stream_child = self.children.pop().unwrap();
+        self.visit_table_ref(&None, &stmt.table_database, &stmt.table);
+        let table_child = self.children.pop().unwrap();
+
+        let name = "CreateStream".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 2);
+        let node = FormatTreeNode::with_children(format_ctx, vec![stream_child, table_child]);
+        self.children.push(node);
+    }
+
+    fn visit_drop_stream(&mut self, stmt: &'ast DropStreamStmt<'ast>) {
+        self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.stream);
+        let child = self.children.pop().unwrap();
+
+        let name = "DropStream".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 1);
+        let node = FormatTreeNode::with_children(format_ctx, vec![child]);
+        self.children.push(node);
+    }
+
     fn visit_show_users(&mut self) {
         let name = "ShowUsers".to_string();
         let format_ctx = AstFormatContext::new(name);
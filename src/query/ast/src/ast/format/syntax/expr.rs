@@ -22,6 +22,7 @@ use crate::ast::format::syntax::NEST_FACTOR;
 use crate::ast::BinaryOperator;
 use crate::ast::Expr;
 use crate::ast::MapAccessor;
+use crate::ast::WindowSpec;
 
 pub(crate) fn pretty_expr(expr: Expr) -> RcDoc {
     match expr {
@@ -220,7 +221,8 @@ pub(crate) fn pretty_expr(expr: Expr) -> RcDoc {
             .append(pretty_expr(*expr))
             .append(RcDoc::text(")")),
         Expr::Literal { lit, .. } => RcDoc::text(lit.to_string()),
-        Expr::CountAll { .. } => RcDoc::text("COUNT(*)"),
+        Expr::CountAll { window, .. } => RcDoc::text("COUNT(*)").append(pretty_window(window)),
+        Expr::Placeholder { .. } => RcDoc::text("?"),
         Expr::Tuple { exprs, .. } => RcDoc::text("(")
             .append(inline_comma(exprs.into_iter().map(pretty_expr)))
             .append(RcDoc::text(")")),
@@ -229,6 +231,7 @@ pub(crate) fn pretty_expr(expr: Expr) -> RcDoc {
             name,
             args,
             params,
+            window,
             ..
         } => RcDoc::text(name.to_string())
             .append(if !params.is_empty() {
@@ -249,7 +252,8 @@ pub(crate) fn pretty_expr(expr: Expr) -> RcDoc {
                 RcDoc::nil()
             })
             .append(inline_comma(args.into_iter().map(pretty_expr)))
-            .append(RcDoc::text(")")),
+            .append(RcDoc::text(")"))
+            .append(pretty_window(window)),
         Expr::Case {
             operand,
             conditions,
@@ -363,3 +367,13 @@ pub(crate) fn pretty_expr(expr: Expr) -> RcDoc {
             .append(RcDoc::text(")")),
     }
 }
+
+fn pretty_window(window: Option<WindowSpec>) -> RcDoc {
+    match window {
+        Some(window) => RcDoc::space()
+            .append(RcDoc::text("OVER"))
+            .append(RcDoc::space())
+            .append(RcDoc::text(format!("({window})"))),
+        None => RcDoc::nil(),
+    }
+}
@@ -0,0 +1,109 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use crate::ast::write_comma_separated_list;
+use crate::ast::Expr;
+use crate::ast::Identifier;
+use crate::ast::TableReference;
+use crate::ast::UpdateExpr;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeIntoStmt<'a> {
+    pub table: TableReference<'a>,
+    pub source: TableReference<'a>,
+    pub join_expr: Expr<'a>,
+    /// The `WHEN` clauses, which are evaluated in order.
+    pub merge_options: Vec<MergeOption<'a>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MergeOption<'a> {
+    Match(MatchedClause<'a>),
+    Unmatch(UnmatchedClause<'a>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchedClause<'a> {
+    pub selection: Option<Expr<'a>>,
+    pub operation: MatchOperation<'a>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatchOperation<'a> {
+    Update { update_list: Vec<UpdateExpr<'a>> },
+    Delete,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnmatchedClause<'a> {
+    pub selection: Option<Expr<'a>>,
+    pub columns: Option<Vec<Identifier<'a>>>,
+    pub values: Vec<Expr<'a>>,
+}
+
+impl Display for MergeIntoStmt<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "MERGE INTO {} USING {} ON {}",
+            self.table, self.source, self.join_expr
+        )?;
+        for merge_option in self.merge_options.iter() {
+            write!(f, " {merge_option}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for MergeOption<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MergeOption::Match(clause) => {
+                write!(f, "WHEN MATCHED")?;
+                if let Some(selection) = &clause.selection {
+                    write!(f, " AND {selection}")?;
+                }
+                write!(f, " THEN ")?;
+                match &clause.operation {
+                    MatchOperation::Update { update_list } => {
+                        write!(f, "UPDATE SET ")?;
+                        write_comma_separated_list(f, update_list)?;
+                    }
+                    MatchOperation::Delete => {
+                        write!(f, "DELETE")?;
+                    }
+                }
+            }
+            MergeOption::Unmatch(clause) => {
+                write!(f, "WHEN NOT MATCHED")?;
+                if let Some(selection) = &clause.selection {
+                    write!(f, " AND {selection}")?;
+                }
+                write!(f, " THEN INSERT")?;
+                if let Some(columns) = &clause.columns {
+                    write!(f, " (")?;
+                    write_comma_separated_list(f, columns)?;
+                    write!(f, ")")?;
+                }
+                write!(f, " VALUES (")?;
+                write_comma_separated_list(f, &clause.values)?;
+                write!(f, ")")?;
+            }
+        }
+        Ok(())
+    }
+}
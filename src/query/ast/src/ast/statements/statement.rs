@@ -76,6 +76,8 @@ pub enum Statement<'a> {
 
     Update(UpdateStmt<'a>),
 
+    MergeInto(MergeIntoStmt<'a>),
+
     // Catalogs
     ShowCatalogs(ShowCatalogsStmt<'a>),
     ShowCreateCatalog(ShowCreateCatalogStmt<'a>),
@@ -108,10 +110,20 @@ pub enum Statement<'a> {
     AnalyzeTable(AnalyzeTableStmt<'a>),
     ExistsTable(ExistsTableStmt<'a>),
 
+    // Indexes
+    CreateIndex(CreateIndexStmt<'a>),
+    DropIndex(DropIndexStmt<'a>),
+
     // Views
     CreateView(CreateViewStmt<'a>),
     AlterView(AlterViewStmt<'a>),
     DropView(DropViewStmt<'a>),
+    CreateMaterializedView(CreateMaterializedViewStmt<'a>),
+    RefreshMaterializedView(RefreshMaterializedViewStmt<'a>),
+
+    // Streams
+    CreateStream(CreateStreamStmt<'a>),
+    DropStream(DropStreamStmt<'a>),
 
     // User
     ShowUsers,
@@ -224,6 +236,7 @@ impl<'a> Display for Statement<'a> {
                 }
             }
             Statement::Update(update) => write!(f, "{update}")?,
+            Statement::MergeInto(merge_into) => write!(f, "{merge_into}")?,
             Statement::Copy(stmt) => write!(f, "{stmt}")?,
             Statement::ShowSettings { like } => {
                 write!(f, "SHOW SETTINGS")?;
@@ -298,9 +311,15 @@ impl<'a> Display for Statement<'a> {
             Statement::OptimizeTable(stmt) => write!(f, "{stmt}")?,
             Statement::AnalyzeTable(stmt) => write!(f, "{stmt}")?,
             Statement::ExistsTable(stmt) => write!(f, "{stmt}")?,
+            Statement::CreateIndex(stmt) => write!(f, "{stmt}")?,
+            Statement::DropIndex(stmt) => write!(f, "{stmt}")?,
             Statement::CreateView(stmt) => write!(f, "{stmt}")?,
             Statement::AlterView(stmt) => write!(f, "{stmt}")?,
             Statement::DropView(stmt) => write!(f, "{stmt}")?,
+            Statement::CreateMaterializedView(stmt) => write!(f, "{stmt}")?,
+            Statement::RefreshMaterializedView(stmt) => write!(f, "{stmt}")?,
+            Statement::CreateStream(stmt) => write!(f, "{stmt}")?,
+            Statement::DropStream(stmt) => write!(f, "{stmt}")?,
             Statement::ShowUsers => write!(f, "SHOW USERS")?,
             Statement::ShowRoles => write!(f, "SHOW ROLES")?,
             Statement::CreateUser(stmt) => write!(f, "{stmt}")?,
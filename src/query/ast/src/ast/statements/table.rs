@@ -282,6 +282,16 @@ pub enum AlterTableAction<'a> {
     RenameTable {
         new_table: Identifier<'a>,
     },
+    AddColumn {
+        column: ColumnDefinition<'a>,
+    },
+    DropColumn {
+        column: Identifier<'a>,
+    },
+    RenameColumn {
+        old_column: Identifier<'a>,
+        new_column: Identifier<'a>,
+    },
     AlterTableClusterKey {
         cluster_by: Vec<Expr<'a>>,
     },
@@ -301,6 +311,18 @@ impl Display for AlterTableAction<'_> {
             AlterTableAction::RenameTable { new_table } => {
                 write!(f, "RENAME TO {new_table}")
             }
+            AlterTableAction::AddColumn { column } => {
+                write!(f, "ADD COLUMN {column}")
+            }
+            AlterTableAction::DropColumn { column } => {
+                write!(f, "DROP COLUMN {column}")
+            }
+            AlterTableAction::RenameColumn {
+                old_column,
+                new_column,
+            } => {
+                write!(f, "RENAME COLUMN {old_column} TO {new_column}")
+            }
             AlterTableAction::AlterTableClusterKey { cluster_by } => {
                 write!(f, "CLUSTER BY ")?;
                 write_comma_separated_list(f, cluster_by)
@@ -463,6 +485,7 @@ pub enum Engine {
     Fuse,
     View,
     Random,
+    Delta,
 }
 
 impl Display for Engine {
@@ -473,6 +496,7 @@ impl Display for Engine {
             Engine::Fuse => write!(f, "FUSE"),
             Engine::View => write!(f, "VIEW"),
             Engine::Random => write!(f, "RANDOM"),
+            Engine::Delta => write!(f, "DELTA"),
         }
     }
 }
@@ -157,6 +157,7 @@ pub enum AccountMgrLevel {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum UserOptionItem {
     TenantSetting(bool),
+    RequireTls(bool),
     DefaultRole(String),
 }
 
@@ -166,6 +167,9 @@ impl UserOptionItem {
             Self::TenantSetting(enabled) => {
                 option.switch_option_flag(UserOptionFlag::TenantSetting, *enabled);
             }
+            Self::RequireTls(enabled) => {
+                option.switch_option_flag(UserOptionFlag::RequireTls, *enabled);
+            }
             Self::DefaultRole(v) => option.set_default_role(Some(v.clone())),
         }
     }
@@ -228,6 +232,8 @@ impl Display for UserOptionItem {
         match self {
             UserOptionItem::TenantSetting(true) => write!(f, "TENANTSETTING"),
             UserOptionItem::TenantSetting(false) => write!(f, "NOTENANTSETTING"),
+            UserOptionItem::RequireTls(true) => write!(f, "REQUIRETLS"),
+            UserOptionItem::RequireTls(false) => write!(f, "NOREQUIRETLS"),
             UserOptionItem::DefaultRole(v) => write!(f, "DEFAULT_ROLE = '{}'", v),
         }
     }
@@ -229,7 +229,11 @@ pub enum ExprElement<'a> {
         lit: Literal,
     },
     /// `Count(*)` expression
-    CountAll,
+    CountAll {
+        window: Option<WindowSpec<'a>>,
+    },
+    /// `?` placeholder of a prepared statement
+    Placeholder,
     /// `(foo, bar)`
     Tuple {
         exprs: Vec<Expr<'a>>,
@@ -241,6 +245,7 @@ pub enum ExprElement<'a> {
         name: Identifier<'a>,
         args: Vec<Expr<'a>>,
         params: Vec<Literal>,
+        window: Option<WindowSpec<'a>>,
     },
     /// `CASE ... WHEN ... ELSE ...` expression
     Case {
@@ -406,7 +411,11 @@ impl<'a, I: Iterator<Item = WithSpan<'a, ExprElement<'a>>>> PrattParser<I> for E
                 span: elem.span.0,
                 lit,
             },
-            ExprElement::CountAll => Expr::CountAll { span: elem.span.0 },
+            ExprElement::CountAll { window } => Expr::CountAll {
+                span: elem.span.0,
+                window,
+            },
+            ExprElement::Placeholder => Expr::Placeholder { span: elem.span.0 },
             ExprElement::Tuple { exprs } => Expr::Tuple {
                 span: elem.span.0,
                 exprs,
@@ -416,12 +425,14 @@ impl<'a, I: Iterator<Item = WithSpan<'a, ExprElement<'a>>>> PrattParser<I> for E
                 name,
                 args,
                 params,
+                window,
             } => Expr::FunctionCall {
                 span: elem.span.0,
                 distinct,
                 name,
                 args,
                 params,
+                window,
             },
             ExprElement::Case {
                 operand,
@@ -717,9 +728,12 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
             trim_where: Some((trim_where, Box::new(trim_str))),
         },
     );
-    let count_all = value(ExprElement::CountAll, rule! {
-        COUNT ~ "(" ~ "*" ~ ^")"
-    });
+    let count_all = map(
+        rule! {
+            COUNT ~ "(" ~ "*" ~ ^")" ~ #window_clause?
+        },
+        |(_, _, _, _, window)| ExprElement::CountAll { window },
+    );
     let tuple = map(
         rule! {
             "(" ~ #comma_separated_list0_ignore_trailling(subexpr(0)) ~ ","? ~ ^")"
@@ -739,12 +753,14 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
             ~ DISTINCT?
             ~ #comma_separated_list0(subexpr(0))?
             ~ ")"
+            ~ #window_clause?
         },
-        |(name, _, opt_distinct, opt_args, _)| ExprElement::FunctionCall {
+        |(name, _, opt_distinct, opt_args, _, window)| ExprElement::FunctionCall {
             distinct: opt_distinct.is_some(),
             name,
             args: opt_args.unwrap_or_default(),
             params: vec![],
+            window,
         },
     );
     let function_call_with_param = map(
@@ -752,12 +768,14 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
             #function_name
             ~ "(" ~ #comma_separated_list1(literal) ~ ")"
             ~ "(" ~ DISTINCT? ~ #comma_separated_list0(subexpr(0))? ~ ")"
+            ~ #window_clause?
         },
-        |(name, _, params, _, _, opt_distinct, opt_args, _)| ExprElement::FunctionCall {
+        |(name, _, params, _, _, opt_distinct, opt_args, _, window)| ExprElement::FunctionCall {
             distinct: opt_distinct.is_some(),
             name,
             args: opt_args.unwrap_or_default(),
             params,
+            window,
         },
     );
     let case = map(
@@ -811,6 +829,7 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
     // and then will be converted back to a floating point literal if the map access
     // is not following a primary element nor a postfix element.
     let literal = map(literal, |lit| ExprElement::Literal { lit });
+    let placeholder = value(ExprElement::Placeholder, rule! { Placeholder });
     let array = map(
         // Array that contains a single literal item will be parsed as a bracket map access,
         // and then will be converted back to an array if the map access is not following
@@ -897,6 +916,7 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
             | #column_ref : "<column>"
             | #map_access : "[<key>] | .<key> | :<key>"
             | #literal : "<literal>"
+            | #placeholder : "`?`"
             | #array : "`[...]`"
         ),
     )))(i)?;
@@ -904,6 +924,85 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
     Ok((rest, WithSpan { span, elem }))
 }
 
+pub fn window_clause(i: Input) -> IResult<WindowSpec> {
+    map(
+        rule! {
+            OVER ~ "(" ~ #window_spec ~ ^")"
+        },
+        |(_, _, spec, _)| spec,
+    )(i)
+}
+
+pub fn window_spec(i: Input) -> IResult<WindowSpec> {
+    map(
+        rule! {
+            ( PARTITION ~ ^BY ~ ^#comma_separated_list1(subexpr(0)) )?
+            ~ ( ORDER ~ ^BY ~ ^#comma_separated_list1(order_by_expr) )?
+            ~ #window_frame?
+        },
+        |(opt_partition, opt_order, window_frame)| WindowSpec {
+            partition_by: opt_partition.map(|(_, _, exprs)| exprs).unwrap_or_default(),
+            order_by: opt_order.map(|(_, _, exprs)| exprs).unwrap_or_default(),
+            window_frame,
+        },
+    )(i)
+}
+
+pub fn window_frame(i: Input) -> IResult<WindowFrame> {
+    let between = map(
+        rule! {
+            #window_frame_units ~ BETWEEN ~ ^#window_frame_bound ~ ^AND ~ ^#window_frame_bound
+        },
+        |(units, _, start_bound, _, end_bound)| WindowFrame {
+            units,
+            start_bound,
+            end_bound,
+        },
+    );
+    let single = map(
+        rule! {
+            #window_frame_units ~ ^#window_frame_bound
+        },
+        |(units, start_bound)| WindowFrame {
+            units,
+            start_bound,
+            end_bound: WindowFrameBound::CurrentRow,
+        },
+    );
+
+    rule!(
+        #between
+        | #single
+    )(i)
+}
+
+pub fn window_frame_units(i: Input) -> IResult<WindowFrameUnits> {
+    alt((
+        value(WindowFrameUnits::Rows, rule! { ROWS }),
+        value(WindowFrameUnits::Range, rule! { RANGE }),
+    ))(i)
+}
+
+pub fn window_frame_bound(i: Input) -> IResult<WindowFrameBound> {
+    alt((
+        value(WindowFrameBound::CurrentRow, rule! { CURRENT ~ ROW }),
+        value(
+            WindowFrameBound::Preceding(None),
+            rule! { UNBOUNDED ~ PRECEDING },
+        ),
+        value(
+            WindowFrameBound::Following(None),
+            rule! { UNBOUNDED ~ FOLLOWING },
+        ),
+        map(rule! { #subexpr(0) ~ PRECEDING }, |(expr, _)| {
+            WindowFrameBound::Preceding(Some(Box::new(expr)))
+        }),
+        map(rule! { #subexpr(0) ~ FOLLOWING }, |(expr, _)| {
+            WindowFrameBound::Following(Some(Box::new(expr)))
+        }),
+    ))(i)
+}
+
 pub fn unary_op(i: Input) -> IResult<UnaryOperator> {
     // Plus and Minus are parsed as binary op at first.
     value(UnaryOperator::Not, rule! { NOT })(i)
@@ -1103,6 +1202,20 @@ pub fn type_name(i: Input) -> IResult<TypeName> {
     );
     let ty_float32 = value(TypeName::Float32, rule! { FLOAT32 | FLOAT });
     let ty_float64 = value(TypeName::Float64, rule! { FLOAT64 | DOUBLE });
+    let ty_decimal = map_res(
+        rule! { DECIMAL ~ "(" ~ #literal_u64 ~ ( "," ~ #literal_u64 )? ~ ")" },
+        |(_, _, precision, opt_scale, _)| {
+            let precision = u8::try_from(precision)
+                .map_err(|_| ErrorKind::Other("invalid decimal precision"))?;
+            let scale = match opt_scale {
+                Some((_, scale)) => {
+                    u8::try_from(scale).map_err(|_| ErrorKind::Other("invalid decimal scale"))?
+                }
+                None => 0,
+            };
+            Ok(TypeName::Decimal { precision, scale })
+        },
+    );
     let ty_array = map(
         rule! { ARRAY ~ ( "(" ~ #type_name ~ ")" )? },
         |(_, opt_item_type)| TypeName::Array {
@@ -1157,6 +1270,7 @@ pub fn type_name(i: Input) -> IResult<TypeName> {
             | #ty_int64
             | #ty_float32
             | #ty_float64
+            | #ty_decimal
             | #ty_array
             | #ty_tuple
             | #ty_date
@@ -128,6 +128,23 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
         },
     );
 
+    let merge_into = map(
+        rule! {
+            MERGE ~ INTO ~ #merge_target_table
+            ~ USING ~ ^#table_reference
+            ~ ON ~ ^#expr
+            ~ #merge_option+
+        },
+        |(_, _, table, _, source, _, join_expr, merge_options)| {
+            Statement::MergeInto(MergeIntoStmt {
+                table,
+                source,
+                join_expr,
+                merge_options,
+            })
+        },
+    );
+
     let show_settings = map(
         rule! {
             SHOW ~ SETTINGS ~ (LIKE ~ #literal_string)?
@@ -551,6 +568,52 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             })
         },
     );
+    let create_index = map(
+        rule! {
+            CREATE ~ INDEX ~ ( IF ~ NOT ~ EXISTS )?
+            ~ #ident ~ TYPE ~ #index_type
+            ~ ON ~ #peroid_separated_idents_1_to_3
+            ~ "(" ~ #expr ~ ")"
+        },
+        |(
+            _,
+            _,
+            opt_if_not_exists,
+            index,
+            _,
+            index_type,
+            _,
+            (catalog, database, table),
+            _,
+            expr,
+            _,
+        )| {
+            Statement::CreateIndex(CreateIndexStmt {
+                if_not_exists: opt_if_not_exists.is_some(),
+                index,
+                index_type,
+                catalog,
+                database,
+                table,
+                expr,
+            })
+        },
+    );
+    let drop_index = map(
+        rule! {
+            DROP ~ INDEX ~ ( IF ~ EXISTS )? ~ #ident
+            ~ ON ~ #peroid_separated_idents_1_to_3
+        },
+        |(_, _, opt_if_exists, index, _, (catalog, database, table))| {
+            Statement::DropIndex(DropIndexStmt {
+                if_exists: opt_if_exists.is_some(),
+                index,
+                catalog,
+                database,
+                table,
+            })
+        },
+    );
     let create_view = map(
         rule! {
             CREATE ~ VIEW ~ ( IF ~ NOT ~ EXISTS )?
@@ -580,6 +643,64 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             })
         },
     );
+    let create_materialized_view = map(
+        rule! {
+            CREATE ~ MATERIALIZED ~ VIEW ~ ( IF ~ NOT ~ EXISTS )?
+            ~ #peroid_separated_idents_1_to_3
+            ~ AS ~ #query
+        },
+        |(_, _, _, opt_if_not_exists, (catalog, database, view), _, query)| {
+            Statement::CreateMaterializedView(CreateMaterializedViewStmt {
+                if_not_exists: opt_if_not_exists.is_some(),
+                catalog,
+                database,
+                view,
+                query: Box::new(query),
+            })
+        },
+    );
+    let refresh_materialized_view = map(
+        rule! {
+            REFRESH ~ MATERIALIZED ~ VIEW ~ #peroid_separated_idents_1_to_3
+        },
+        |(_, _, _, (catalog, database, view))| {
+            Statement::RefreshMaterializedView(RefreshMaterializedViewStmt {
+                catalog,
+                database,
+                view,
+            })
+        },
+    );
+    let create_stream = map(
+        rule! {
+            CREATE ~ STREAM ~ ( IF ~ NOT ~ EXISTS )?
+            ~ #peroid_separated_idents_1_to_3
+            ~ ON ~ TABLE ~ #peroid_separated_idents_1_to_2
+        },
+        |(_, _, opt_if_not_exists, (catalog, database, stream), _, _, (table_database, table))| {
+            Statement::CreateStream(CreateStreamStmt {
+                if_not_exists: opt_if_not_exists.is_some(),
+                catalog,
+                database,
+                stream,
+                table_database,
+                table,
+            })
+        },
+    );
+    let drop_stream = map(
+        rule! {
+            DROP ~ STREAM ~ ( IF ~ EXISTS )? ~ #peroid_separated_idents_1_to_3
+        },
+        |(_, _, opt_if_exists, (catalog, database, stream))| {
+            Statement::DropStream(DropStreamStmt {
+                if_exists: opt_if_exists.is_some(),
+                catalog,
+                database,
+                stream,
+            })
+        },
+    );
     let alter_view = map(
         rule! {
             ALTER ~ VIEW
@@ -1003,6 +1124,9 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             #set_variable : "`SET <variable> = <value>`"
             | #unset_variable : "`UNSET <variable>`"
         ),
+        rule!(
+            #merge_into : "`MERGE INTO <table> USING <source> ON <expr> { WHEN MATCHED [AND <expr>] THEN { UPDATE SET <column> = <expr> [, ...] | DELETE } | WHEN NOT MATCHED [AND <expr>] THEN INSERT [(<column>, ...)] VALUES (<expr>, ...) } ...`"
+        ),
         rule!(
             #show_tables : "`SHOW [FULL] TABLES [FROM <database>] [<show_limit>]`"
             | #show_create_table : "`SHOW CREATE TABLE [<database>.]<table>`"
@@ -1019,10 +1143,20 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             | #analyze_table : "`ANALYZE TABLE [<database>.]<table>`"
             | #exists_table : "`EXISTS TABLE [<database>.]<table>`"
         ),
+        rule!(
+            #create_index : "`CREATE INDEX [IF NOT EXISTS] <index> TYPE (MINMAX | SET(<max_size>) | NGRAMBF(<n>)) ON [<database>.]<table>(<expr>)`"
+            | #drop_index : "`DROP INDEX [IF EXISTS] <index> ON [<database>.]<table>`"
+        ),
         rule!(
             #create_view : "`CREATE VIEW [IF NOT EXISTS] [<database>.]<view> AS SELECT ...`"
             | #drop_view : "`DROP VIEW [IF EXISTS] [<database>.]<view>`"
             | #alter_view : "`ALTER VIEW [<database>.]<view> AS SELECT ...`"
+            | #create_materialized_view : "`CREATE MATERIALIZED VIEW [IF NOT EXISTS] [<database>.]<view> AS SELECT ...`"
+            | #refresh_materialized_view : "`REFRESH MATERIALIZED VIEW [<database>.]<view>`"
+        ),
+        rule!(
+            #create_stream : "`CREATE STREAM [IF NOT EXISTS] [<database>.]<stream> ON TABLE [<database>.]<table>`"
+            | #drop_stream : "`DROP STREAM [IF EXISTS] [<database>.]<stream>`"
         ),
         rule!(
             #show_users : "`SHOW USERS`"
@@ -1441,6 +1575,30 @@ pub fn alter_table_action(i: Input) -> IResult<AlterTableAction> {
         |(_, _, new_table)| AlterTableAction::RenameTable { new_table },
     );
 
+    let add_column = map(
+        rule! {
+            ADD ~ COLUMN? ~ #column_def
+        },
+        |(_, _, column)| AlterTableAction::AddColumn { column },
+    );
+
+    let drop_column = map(
+        rule! {
+            DROP ~ COLUMN? ~ #ident
+        },
+        |(_, _, column)| AlterTableAction::DropColumn { column },
+    );
+
+    let rename_column = map(
+        rule! {
+            RENAME ~ COLUMN ~ #ident ~ TO ~ #ident
+        },
+        |(_, _, old_column, _, new_column)| AlterTableAction::RenameColumn {
+            old_column,
+            new_column,
+        },
+    );
+
     let alter_table_cluster_key = map(
         rule! {
             CLUSTER ~ ^BY ~ ^"(" ~ ^#comma_separated_list1(expr) ~ ^")"
@@ -1474,8 +1632,11 @@ pub fn alter_table_action(i: Input) -> IResult<AlterTableAction> {
 
     rule!(
         #rename_table
+        | #rename_column
+        | #add_column
         | #alter_table_cluster_key
         | #drop_table_cluster_key
+        | #drop_column
         | #recluster_table
         | #revert_table
     )(i)
@@ -1598,6 +1759,7 @@ pub fn engine(i: Input) -> IResult<Engine> {
         value(Engine::Fuse, rule! { FUSE }),
         value(Engine::View, rule! { VIEW }),
         value(Engine::Random, rule! { RANDOM }),
+        value(Engine::Delta, rule! { DELTA }),
     ));
 
     map(
@@ -1608,6 +1770,20 @@ pub fn engine(i: Input) -> IResult<Engine> {
     )(i)
 }
 
+pub fn index_type(i: Input) -> IResult<IndexType> {
+    alt((
+        value(IndexType::MinMax, rule! { MINMAX }),
+        map(
+            rule! { SET ~ "(" ~ ^#literal_u64 ~ ^")" },
+            |(_, _, max_size, _)| IndexType::Set(max_size),
+        ),
+        map(
+            rule! { NGRAMBF ~ "(" ~ ^#literal_u64 ~ ^")" },
+            |(_, _, n, _)| IndexType::NgramBloomFilter(n),
+        ),
+    ))(i)
+}
+
 pub fn database_engine(i: Input) -> IResult<DatabaseEngine> {
     let engine = alt((value(DatabaseEngine::Default, rule! {DEFAULT}),));
 
@@ -1652,6 +1828,7 @@ pub fn catalog_type(i: Input) -> IResult<CatalogType> {
     let catalog_type = alt((
         value(CatalogType::Default, rule! {DEFAULT}),
         value(CatalogType::Hive, rule! {HIVE}),
+        value(CatalogType::Iceberg, rule! {ICEBERG}),
     ));
     map(rule! { ^#catalog_type }, |catalog_type| catalog_type)(i)
 }
@@ -1669,6 +1846,8 @@ pub fn user_option(i: Input) -> IResult<UserOptionItem> {
             UserOptionItem::TenantSetting(false),
             rule! { NOTENANTSETTING },
         ),
+        value(UserOptionItem::RequireTls(true), rule! { REQUIRETLS }),
+        value(UserOptionItem::RequireTls(false), rule! { NOREQUIRETLS }),
         default_role_option,
     ))(i)
 }
@@ -1692,6 +1871,10 @@ pub fn auth_type(i: Input) -> IResult<AuthType> {
         value(AuthType::NoPassword, rule! { NO_PASSWORD }),
         value(AuthType::Sha256Password, rule! { SHA256_PASSWORD }),
         value(AuthType::DoubleSha1Password, rule! { DOUBLE_SHA1_PASSWORD }),
+        value(
+            AuthType::ScramSha256Password,
+            rule! { SCRAM_SHA256_PASSWORD },
+        ),
         value(AuthType::JWT, rule! { JWT }),
     ))(i)
 }
@@ -1772,6 +1955,62 @@ pub fn table_reference_only(i: Input) -> IResult<TableReference> {
     )(i)
 }
 
+pub fn merge_target_table(i: Input) -> IResult<TableReference> {
+    map(
+        consumed(rule! {
+            #peroid_separated_idents_1_to_3 ~ #table_alias?
+        }),
+        |(input, ((catalog, database, table), alias))| TableReference::Table {
+            span: input.0,
+            catalog,
+            database,
+            table,
+            alias,
+            travel_point: None,
+        },
+    )(i)
+}
+
+pub fn merge_option(i: Input) -> IResult<MergeOption> {
+    let match_operation = alt((
+        map(
+            rule! { UPDATE ~ ^SET ~ ^#comma_separated_list1(update_expr) },
+            |(_, _, update_list)| MatchOperation::Update { update_list },
+        ),
+        value(MatchOperation::Delete, rule! { DELETE }),
+    ));
+    let matched_clause = map(
+        rule! {
+            WHEN ~ MATCHED ~ ( AND ~ ^#expr )? ~ ^THEN ~ ^#match_operation
+        },
+        |(_, _, opt_selection, _, operation)| {
+            MergeOption::Match(MatchedClause {
+                selection: opt_selection.map(|(_, selection)| selection),
+                operation,
+            })
+        },
+    );
+    let unmatched_clause = map(
+        rule! {
+            WHEN ~ NOT ~ ^MATCHED ~ ( AND ~ ^#expr )? ~ ^THEN ~ ^INSERT
+            ~ ( "(" ~ ^#comma_separated_list1(ident) ~ ^")" )?
+            ~ ^VALUES ~ ^"(" ~ ^#comma_separated_list1(expr) ~ ^")"
+        },
+        |(_, _, _, opt_selection, _, _, opt_columns, _, _, values, _)| {
+            MergeOption::Unmatch(UnmatchedClause {
+                selection: opt_selection.map(|(_, selection)| selection),
+                columns: opt_columns.map(|(_, columns, _)| columns),
+                values,
+            })
+        },
+    );
+
+    rule!(
+        #matched_clause
+        | #unmatched_clause
+    )(i)
+}
+
 pub fn update_expr(i: Input) -> IResult<UpdateExpr> {
     map(rule! { ( #ident ~ "=" ~ ^#expr ) }, |(name, _, expr)| {
         UpdateExpr { name, expr }
@@ -113,6 +113,7 @@ pub enum TokenKind {
     CommentBlock,
 
     #[regex(r#"[_a-zA-Z][_$a-zA-Z0-9]*"#)]
+    #[regex(r#"\$[_a-zA-Z][_$a-zA-Z0-9]*"#)]
     Ident,
 
     #[regex(r#"`[^`]*`"#)]
@@ -233,6 +234,9 @@ pub enum TokenKind {
     /// A cube root math operator in PostgreSQL
     #[token("||/")]
     PGCubeRoot,
+    /// Placeholder `?` used for parameters of MySQL prepared statements
+    #[token("?")]
+    Placeholder,
 
     // Keywords
     //
@@ -299,6 +303,8 @@ pub enum TokenKind {
     CENTURY,
     #[token("CLUSTER", ignore(ascii_case))]
     CLUSTER,
+    #[token("COLUMN", ignore(ascii_case))]
+    COLUMN,
     #[token("COMMENT", ignore(ascii_case))]
     COMMENT,
     #[token("COMMENTS", ignore(ascii_case))]
@@ -327,6 +333,8 @@ pub enum TokenKind {
     CSV,
     #[token("CURRENT_TIMESTAMP", ignore(ascii_case))]
     CURRENT_TIMESTAMP,
+    #[token("CURRENT", ignore(ascii_case))]
+    CURRENT,
     #[token("DATABASE", ignore(ascii_case))]
     DATABASE,
     #[token("DATABASES", ignore(ascii_case))]
@@ -345,10 +353,14 @@ pub enum TokenKind {
     DAY,
     #[token("DECADE", ignore(ascii_case))]
     DECADE,
+    #[token("DECIMAL", ignore(ascii_case))]
+    DECIMAL,
     #[token("DEFAULT", ignore(ascii_case))]
     DEFAULT,
     #[token("DELETE", ignore(ascii_case))]
     DELETE,
+    #[token("DELTA", ignore(ascii_case))]
+    DELTA,
     #[token("DESC", ignore(ascii_case))]
     DESC,
     #[token("DESCRIBE", ignore(ascii_case))]
@@ -419,6 +431,8 @@ pub enum TokenKind {
     FLOAT64,
     #[token("FOR", ignore(ascii_case))]
     FOR,
+    #[token("FOLLOWING", ignore(ascii_case))]
+    FOLLOWING,
     #[token("FORCE", ignore(ascii_case))]
     FORCE,
     #[token("FORMAT", ignore(ascii_case))]
@@ -451,12 +465,16 @@ pub enum TokenKind {
     HOUR,
     #[token("INTERSECT", ignore(ascii_case))]
     INTERSECT,
+    #[token("ICEBERG", ignore(ascii_case))]
+    ICEBERG,
     #[token("IDENTIFIED", ignore(ascii_case))]
     IDENTIFIED,
     #[token("IF", ignore(ascii_case))]
     IF,
     #[token("IN", ignore(ascii_case))]
     IN,
+    #[token("INDEX", ignore(ascii_case))]
+    INDEX,
     #[token("INNER", ignore(ascii_case))]
     INNER,
     #[token("INSERT", ignore(ascii_case))]
@@ -515,10 +533,16 @@ pub enum TokenKind {
     MAX_FILE_SIZE,
     #[token("MASTER_KEY", ignore(ascii_case))]
     MASTER_KEY,
+    #[token("MATCHED", ignore(ascii_case))]
+    MATCHED,
+    #[token("MATERIALIZED", ignore(ascii_case))]
+    MATERIALIZED,
     #[token("MEMO", ignore(ascii_case))]
     MEMO,
     #[token("MEMORY", ignore(ascii_case))]
     MEMORY,
+    #[token("MERGE", ignore(ascii_case))]
+    MERGE,
     #[token("METRICS", ignore(ascii_case))]
     METRICS,
     #[token("MICROSECONDS", ignore(ascii_case))]
@@ -527,16 +551,22 @@ pub enum TokenKind {
     MILLENIUM,
     #[token("MILLISECONDS", ignore(ascii_case))]
     MILLISECONDS,
+    #[token("MINMAX", ignore(ascii_case))]
+    MINMAX,
     #[token("MINUTE", ignore(ascii_case))]
     MINUTE,
     #[token("MONTH", ignore(ascii_case))]
     MONTH,
     #[token("NATURAL", ignore(ascii_case))]
     NATURAL,
+    #[token("NGRAMBF", ignore(ascii_case))]
+    NGRAMBF,
     #[token("NO_PASSWORD", ignore(ascii_case))]
     NO_PASSWORD,
     #[token("NOT", ignore(ascii_case))]
     NOT,
+    #[token("NOREQUIRETLS", ignore(ascii_case))]
+    NOREQUIRETLS,
     #[token("NOTENANTSETTING", ignore(ascii_case))]
     NOTENANTSETTING,
     #[token("NULL", ignore(ascii_case))]
@@ -557,6 +587,12 @@ pub enum TokenKind {
     ORDER,
     #[token("OUTER", ignore(ascii_case))]
     OUTER,
+    #[token("OVER", ignore(ascii_case))]
+    OVER,
+    #[token("PARTITION", ignore(ascii_case))]
+    PARTITION,
+    #[token("PRECEDING", ignore(ascii_case))]
+    PRECEDING,
     #[token("ON_ERROR", ignore(ascii_case))]
     ON_ERROR,
     #[token("OVERWRITE", ignore(ascii_case))]
@@ -587,6 +623,8 @@ pub enum TokenKind {
     RECORD_DELIMITER,
     #[token("REFERENCE_USAGE", ignore(ascii_case))]
     REFERENCE_USAGE,
+    #[token("REFRESH", ignore(ascii_case))]
+    REFRESH,
     #[token("REGEXP", ignore(ascii_case))]
     REGEXP,
     #[token("RENAME", ignore(ascii_case))]
@@ -595,12 +633,20 @@ pub enum TokenKind {
     GRANT,
     #[token("ROLE", ignore(ascii_case))]
     ROLE,
+    #[token("ROW", ignore(ascii_case))]
+    ROW,
+    #[token("ROWS", ignore(ascii_case))]
+    ROWS,
+    #[token("RANGE", ignore(ascii_case))]
+    RANGE,
     #[token("PRESIGN", ignore(ascii_case))]
     PRESIGN,
     #[token("PRIVILEGES", ignore(ascii_case))]
     PRIVILEGES,
     #[token("REMOVE", ignore(ascii_case))]
     REMOVE,
+    #[token("REQUIRETLS", ignore(ascii_case))]
+    REQUIRETLS,
     #[token("REVOKE", ignore(ascii_case))]
     REVOKE,
     #[token("RECURSIVE", ignore(ascii_case))]
@@ -617,6 +663,8 @@ pub enum TokenKind {
     SCHEMA,
     #[token("SCHEMAS", ignore(ascii_case))]
     SCHEMAS,
+    #[token("SCRAM_SHA256_PASSWORD", ignore(ascii_case))]
+    SCRAM_SHA256_PASSWORD,
     #[token("SECOND", ignore(ascii_case))]
     SECOND,
     #[token("SELECT", ignore(ascii_case))]
@@ -667,6 +715,8 @@ pub enum TokenKind {
     SUPER,
     #[token("STATUS", ignore(ascii_case))]
     STATUS,
+    #[token("STREAM", ignore(ascii_case))]
+    STREAM,
     #[token("STRING", ignore(ascii_case))]
     STRING,
     #[token("SUBSTRING", ignore(ascii_case))]
@@ -729,6 +779,8 @@ pub enum TokenKind {
     UINT8,
     #[token("UNDROP", ignore(ascii_case))]
     UNDROP,
+    #[token("UNBOUNDED", ignore(ascii_case))]
+    UNBOUNDED,
     #[token("UNSIGNED", ignore(ascii_case))]
     UNSIGNED,
     #[token("URL", ignore(ascii_case))]
@@ -835,6 +887,7 @@ impl TokenKind {
                 | AtSign
                 | PGSquareRoot
                 | PGCubeRoot
+                | Placeholder
                 | EOI
         )
     }
@@ -1084,6 +1137,9 @@ impl TokenKind {
             | TokenKind::ORDER
             // | TokenKind::OVERLAPS
             // | TokenKind::RETURNING
+            | TokenKind::OVER
+            | TokenKind::RANGE
+            | TokenKind::ROWS
             | TokenKind::STAGE
             | TokenKind::SHARE
             | TokenKind::SHARES
@@ -217,7 +217,17 @@ pub trait Visitor<'ast>: Sized {
 
     fn visit_literal(&mut self, _span: &'ast [Token<'ast>], _lit: &'ast Literal) {}
 
-    fn visit_count_all(&mut self, _span: &'ast [Token<'ast>]) {}
+    fn visit_count_all(
+        &mut self,
+        _span: &'ast [Token<'ast>],
+        window: &'ast Option<WindowSpec<'ast>>,
+    ) {
+        if let Some(window) = window {
+            self.visit_window_spec(window);
+        }
+    }
+
+    fn visit_placeholder(&mut self, _span: &'ast [Token<'ast>]) {}
 
     fn visit_tuple(&mut self, _span: &'ast [Token<'ast>], elements: &'ast [Expr<'ast>]) {
         for element in elements {
@@ -232,10 +242,23 @@ pub trait Visitor<'ast>: Sized {
         _name: &'ast Identifier<'ast>,
         args: &'ast [Expr<'ast>],
         _params: &'ast [Literal],
+        window: &'ast Option<WindowSpec<'ast>>,
     ) {
         for arg in args {
             walk_expr(self, arg);
         }
+        if let Some(window) = window {
+            self.visit_window_spec(window);
+        }
+    }
+
+    fn visit_window_spec(&mut self, window: &'ast WindowSpec<'ast>) {
+        for expr in window.partition_by.iter() {
+            walk_expr(self, expr);
+        }
+        for order_by in window.order_by.iter() {
+            walk_expr(self, &order_by.expr);
+        }
     }
 
     fn visit_case_when(
@@ -388,6 +411,8 @@ pub trait Visitor<'ast>: Sized {
 
     fn visit_update(&mut self, _update: &'ast UpdateStmt<'ast>) {}
 
+    fn visit_merge_into(&mut self, _merge_into: &'ast MergeIntoStmt<'ast>) {}
+
     fn visit_show_catalogs(&mut self, _stmt: &'ast ShowCatalogsStmt<'ast>) {}
 
     fn visit_show_create_catalog(&mut self, _stmt: &'ast ShowCreateCatalogStmt<'ast>) {}
@@ -440,12 +465,24 @@ pub trait Visitor<'ast>: Sized {
 
     fn visit_exists_table(&mut self, _stmt: &'ast ExistsTableStmt<'ast>) {}
 
+    fn visit_create_index(&mut self, _stmt: &'ast CreateIndexStmt<'ast>) {}
+
+    fn visit_drop_index(&mut self, _stmt: &'ast DropIndexStmt<'ast>) {}
+
     fn visit_create_view(&mut self, _stmt: &'ast CreateViewStmt<'ast>) {}
 
     fn visit_alter_view(&mut self, _stmt: &'ast AlterViewStmt<'ast>) {}
 
     fn visit_drop_view(&mut self, _stmt: &'ast DropViewStmt<'ast>) {}
 
+    fn visit_create_materialized_view(&mut self, _stmt: &'ast CreateMaterializedViewStmt<'ast>) {}
+
+    fn visit_refresh_materialized_view(&mut self, _stmt: &'ast RefreshMaterializedViewStmt<'ast>) {}
+
+    fn visit_create_stream(&mut self, _stmt: &'ast CreateStreamStmt<'ast>) {}
+
+    fn visit_drop_stream(&mut self, _stmt: &'ast DropStreamStmt<'ast>) {}
+
     fn visit_show_users(&mut self) {}
 
     fn visit_create_user(&mut self, _stmt: &'ast CreateUserStmt) {}
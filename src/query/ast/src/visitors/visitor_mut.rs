@@ -222,7 +222,13 @@ pub trait VisitorMut: Sized {
 
     fn visit_literal(&mut self, _span: &mut &[Token<'_>], _lit: &mut Literal) {}
 
-    fn visit_count_all(&mut self, _span: &mut &[Token<'_>]) {}
+    fn visit_count_all(&mut self, _span: &mut &[Token<'_>], window: &mut Option<WindowSpec<'_>>) {
+        if let Some(window) = window {
+            self.visit_window_spec(window);
+        }
+    }
+
+    fn visit_placeholder(&mut self, _span: &mut &[Token<'_>]) {}
 
     fn visit_tuple(&mut self, _span: &mut &[Token<'_>], elements: &mut [Expr<'_>]) {
         for elem in elements.iter_mut() {
@@ -237,10 +243,23 @@ pub trait VisitorMut: Sized {
         _name: &mut Identifier<'_>,
         args: &mut [Expr<'_>],
         _params: &mut [Literal],
+        window: &mut Option<WindowSpec<'_>>,
     ) {
         for arg in args.iter_mut() {
             walk_expr_mut(self, arg);
         }
+        if let Some(window) = window {
+            self.visit_window_spec(window);
+        }
+    }
+
+    fn visit_window_spec(&mut self, window: &mut WindowSpec<'_>) {
+        for expr in window.partition_by.iter_mut() {
+            walk_expr_mut(self, expr);
+        }
+        for order_by in window.order_by.iter_mut() {
+            walk_expr_mut(self, &mut order_by.expr);
+        }
     }
 
     fn visit_case_when(
@@ -391,6 +410,8 @@ pub trait VisitorMut: Sized {
 
     fn visit_update(&mut self, _update: &mut UpdateStmt<'_>) {}
 
+    fn visit_merge_into(&mut self, _merge_into: &mut MergeIntoStmt<'_>) {}
+
     fn visit_show_catalogs(&mut self, _stmt: &mut ShowCatalogsStmt<'_>) {}
 
     fn visit_show_create_catalog(&mut self, _stmt: &mut ShowCreateCatalogStmt<'_>) {}
@@ -443,12 +464,24 @@ pub trait VisitorMut: Sized {
 
     fn visit_exists_table(&mut self, _stmt: &mut ExistsTableStmt<'_>) {}
 
+    fn visit_create_index(&mut self, _stmt: &mut CreateIndexStmt<'_>) {}
+
+    fn visit_drop_index(&mut self, _stmt: &mut DropIndexStmt<'_>) {}
+
     fn visit_create_view(&mut self, _stmt: &mut CreateViewStmt<'_>) {}
 
     fn visit_alter_view(&mut self, _stmt: &mut AlterViewStmt<'_>) {}
 
     fn visit_drop_view(&mut self, _stmt: &mut DropViewStmt<'_>) {}
 
+    fn visit_create_materialized_view(&mut self, _stmt: &mut CreateMaterializedViewStmt<'_>) {}
+
+    fn visit_refresh_materialized_view(&mut self, _stmt: &mut RefreshMaterializedViewStmt<'_>) {}
+
+    fn visit_create_stream(&mut self, _stmt: &mut CreateStreamStmt<'_>) {}
+
+    fn visit_drop_stream(&mut self, _stmt: &mut DropStreamStmt<'_>) {}
+
     fn visit_show_users(&mut self) {}
 
     fn visit_create_user(&mut self, _stmt: &mut CreateUserStmt) {}
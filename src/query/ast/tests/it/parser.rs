@@ -85,12 +85,14 @@ fn test_statement() {
         r#"create table if not exists a.b (a int) 's3://testbucket/admin/data/'
              connection=(aws_key_id='minioadmin' aws_secret_key='minioadmin' endpoint_url='http://127.0.0.1:9900')
              location_prefix = 'db';"#,
+        r#"create table t engine = delta 's3://testbucket/delta/t/' connection=(aws_key_id='minioadmin' aws_secret_key='minioadmin');"#,
         r#"truncate table a;"#,
         r#"truncate table "a".b;"#,
         r#"drop table a;"#,
         r#"drop table if exists a."b";"#,
         r#"use "a";"#,
         r#"create catalog ctl type=hive connection=(url='<hive-meta-store>' thrift_protocol='binary');"#,
+        r#"create catalog ctl type=iceberg connection=(url='s3://bucket/warehouse/' access_key_id='minioadmin');"#,
         r#"create database if not exists a;"#,
         r#"create database ctl.t engine = Default;"#,
         r#"create database t engine = Default;"#,
@@ -113,6 +115,7 @@ fn test_statement() {
         r#"ALTER USER u1 WITH DEFAULT_ROLE = 'role1';"#,
         r#"ALTER USER u1 WITH DEFAULT_ROLE = 'role1', TENANTSETTING;"#,
         r#"CREATE USER u1 IDENTIFIED BY '123456' WITH DEFAULT_ROLE='role123', TENANTSETTING"#,
+        r#"ALTER USER u1 WITH REQUIRETLS, NOTENANTSETTING;"#,
         r#"DROP database if exists db1;"#,
         r#"select distinct a, count(*) from t where a = 1 and b - 1 < a group by a having a = 1;"#,
         r#"select * from t4;"#,
@@ -157,6 +160,9 @@ fn test_statement() {
         r#"ALTER TABLE t CLUSTER BY(c1);"#,
         r#"ALTER TABLE t DROP CLUSTER KEY;"#,
         r#"ALTER TABLE t RECLUSTER FINAL WHERE c1 > 0;"#,
+        r#"ALTER TABLE t ADD COLUMN c Int64 DEFAULT 1;"#,
+        r#"ALTER TABLE t DROP COLUMN c;"#,
+        r#"ALTER TABLE t RENAME COLUMN a TO b;"#,
         r#"ALTER DATABASE IF EXISTS ctl.c RENAME TO a;"#,
         r#"ALTER DATABASE c RENAME TO a;"#,
         r#"ALTER DATABASE ctl.c RENAME TO a;"#,
@@ -348,6 +354,14 @@ fn test_statement() {
         r#"SHOW GRANTS ON DATABASE db;"#,
         r#"SHOW GRANTS OF SHARE t;"#,
         r#"UPDATE db1.tb1 set a = a + 1, b = 2 WHERE c > 3;"#,
+        r#"MERGE INTO t USING s ON t.a = s.a WHEN MATCHED THEN DELETE WHEN NOT MATCHED THEN INSERT VALUES (s.a);"#,
+        r#"CREATE INDEX IF NOT EXISTS idx TYPE ngrambf(3) ON db.t(lower(s));"#,
+        r#"CREATE INDEX idx TYPE set(100) ON t(a);"#,
+        r#"DROP INDEX IF EXISTS idx ON t;"#,
+        r#"create materialized view mv as select number % 3 as a from numbers(1000);"#,
+        r#"REFRESH MATERIALIZED VIEW db.mv;"#,
+        r#"CREATE STREAM IF NOT EXISTS db.s ON TABLE db.t;"#,
+        r#"DROP STREAM s;"#,
         r#"UNSET max_threads;"#,
         r#"UNSET (max_threads, sql_dialect);"#,
     ];
@@ -554,6 +568,7 @@ fn test_expr() {
         r#"1 is distinct from 2"#,
         r#"a is distinct from b"#,
         r#"1 is not distinct from null"#,
+        r#"number % ?"#,
     ];
 
     for case in cases {
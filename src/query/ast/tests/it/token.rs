@@ -56,6 +56,7 @@ fn test_lexer() {
         r#"@abc 123"#,
         r#"42 3.5 4. .001 5e2 1.925e-3 .38e+7 1.e-01 0xfff x'deedbeef'"#,
         r#"create table "user" (id int, name varchar /* the user name */);"#,
+        r#"select $action, $row_id from s"#,
     ];
 
     for case in cases {
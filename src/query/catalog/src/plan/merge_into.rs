@@ -0,0 +1,48 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_expression::RemoteExpr;
+
+/// The executable form of a `MERGE INTO` statement.
+///
+/// The source is joined with the target table by a right outer join, the source is the
+/// build side. The expressions evaluated on the matched rows refer to a joined block, whose
+/// columns are the columns of the target table followed by the columns of the source.
+/// The other expressions refer to either the target block or the source block only.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MergeIntoInfo {
+    /// Equi-join keys evaluated on the target block, whose columns are wrapped nullable
+    /// as the probe side of a right outer join.
+    pub target_keys: Vec<RemoteExpr>,
+    /// Equi-join keys evaluated on the source block.
+    pub source_keys: Vec<RemoteExpr>,
+    /// Evaluated on the joined block, the first satisfied action applies.
+    pub matched_actions: Vec<MatchedAction>,
+    /// Evaluated on the source block, the first satisfied action applies.
+    pub unmatched_actions: Vec<UnmatchedAction>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MatchedAction {
+    pub condition: Option<RemoteExpr>,
+    /// The new values of the updated columns, `None` means the rows are deleted.
+    pub update_list: Option<Vec<(usize, RemoteExpr)>>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct UnmatchedAction {
+    pub condition: Option<RemoteExpr>,
+    /// One value for each column of the target table.
+    pub values: Vec<RemoteExpr>,
+}
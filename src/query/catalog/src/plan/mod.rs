@@ -13,6 +13,7 @@
 // limitations under the License.
 
 mod datasource;
+mod merge_into;
 mod partition;
 mod partition_statistics;
 mod projection;
@@ -20,6 +21,7 @@ mod pushdown;
 mod stage_file_info;
 
 pub use datasource::*;
+pub use merge_into::*;
 pub use partition::*;
 pub use partition_statistics::PartStatistics;
 pub use projection::Projection;
@@ -21,11 +21,14 @@ use chrono::Utc;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_expression::BlockCompactThresholds;
+pub use common_expression::ColumnId;
 use common_expression::DataBlock;
 use common_expression::RemoteExpr;
 use common_expression::Scalar;
+use common_expression::TableField;
 use common_expression::TableSchema;
 use common_meta_app::schema::DatabaseType;
+use common_meta_app::schema::SkippingIndexMeta;
 use common_meta_app::schema::TableInfo;
 use common_meta_types::MetaId;
 use common_pipeline_core::Pipeline;
@@ -40,8 +43,6 @@ use crate::table::column_stats_provider_impls::DummyColumnStatisticsProvider;
 use crate::table_context::TableContext;
 use crate::table_mutator::TableMutator;
 
-pub type ColumnId = u32;
-
 #[async_trait::async_trait]
 pub trait Table: Sync + Send {
     fn name(&self) -> &str {
@@ -127,6 +128,77 @@ pub trait Table: Sync + Send {
         )))
     }
 
+    async fn add_column(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        field: &TableField,
+        comment: &str,
+    ) -> Result<()> {
+        let (_, _, _) = (ctx, field, comment);
+
+        Err(ErrorCode::Unimplemented(format!(
+            "add_column operation for table {} is not implemented. table engine : {}",
+            self.name(),
+            self.get_table_info().meta.engine
+        )))
+    }
+
+    async fn drop_column(&self, ctx: Arc<dyn TableContext>, column: &str) -> Result<()> {
+        let (_, _) = (ctx, column);
+
+        Err(ErrorCode::Unimplemented(format!(
+            "drop_column operation for table {} is not implemented. table engine : {}",
+            self.name(),
+            self.get_table_info().meta.engine
+        )))
+    }
+
+    async fn rename_column(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        old_column: &str,
+        new_column: &str,
+    ) -> Result<()> {
+        let (_, _, _) = (ctx, old_column, new_column);
+
+        Err(ErrorCode::Unimplemented(format!(
+            "rename_column operation for table {} is not implemented. table engine : {}",
+            self.name(),
+            self.get_table_info().meta.engine
+        )))
+    }
+
+    async fn create_skipping_index(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        name: &str,
+        meta: SkippingIndexMeta,
+        if_not_exists: bool,
+    ) -> Result<()> {
+        let (_, _, _, _) = (ctx, name, meta, if_not_exists);
+
+        Err(ErrorCode::Unimplemented(format!(
+            "create_skipping_index operation for table {} is not implemented. table engine : {}",
+            self.name(),
+            self.get_table_info().meta.engine
+        )))
+    }
+
+    async fn drop_skipping_index(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        name: &str,
+        if_exists: bool,
+    ) -> Result<()> {
+        let (_, _, _) = (ctx, name, if_exists);
+
+        Err(ErrorCode::Unimplemented(format!(
+            "drop_skipping_index operation for table {} is not implemented. table engine : {}",
+            self.name(),
+            self.get_table_info().meta.engine
+        )))
+    }
+
     /// Gather partitions to be scanned according to the push_downs
     async fn read_partitions(
         &self,
@@ -240,6 +312,23 @@ pub trait Table: Sync + Send {
         )))
     }
 
+    async fn update(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        filter: Option<RemoteExpr<String>>,
+        col_indices: Vec<usize>,
+        update_list: Vec<(usize, RemoteExpr<String>)>,
+        pipeline: &mut Pipeline,
+    ) -> Result<()> {
+        let (_, _, _, _, _) = (ctx, filter, col_indices, update_list, pipeline);
+
+        Err(ErrorCode::Unimplemented(format!(
+            "table {},  of engine type {}, does not support UPDATE",
+            self.name(),
+            self.get_table_info().engine(),
+        )))
+    }
+
     fn get_block_compact_thresholds(&self) -> BlockCompactThresholds {
         BlockCompactThresholds {
             max_rows_per_block: 1000 * 1000,
@@ -21,10 +21,14 @@ use std::time::SystemTime;
 use common_base::base::Progress;
 use common_base::base::ProgressValues;
 use common_exception::Result;
+use common_expression::types::DataType;
+use common_expression::types::NumberDataType;
 use common_expression::DataBlock;
 use common_expression::FunctionContext;
 use common_io::prelude::FormatSettings;
+use common_meta_app::schema::UpsertTableOptionReq;
 use common_meta_types::RoleInfo;
+use common_meta_types::TenantQuota;
 use common_meta_types::UserInfo;
 use common_settings::Settings;
 use common_storage::DataOperator;
@@ -62,6 +66,34 @@ pub struct StageAttachment {
     pub copy_options: BTreeMap<String, String>,
 }
 
+/// A typed parameter of a prepared statement, bound to a `?` placeholder.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlaceholderValue {
+    Null,
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    String(String),
+    /// Date in `YYYY-MM-DD` format.
+    Date(String),
+    /// Timestamp in `YYYY-MM-DD hh:mm:ss[.ffffff]` format, in the timezone of the session.
+    Timestamp(String),
+}
+
+impl PlaceholderValue {
+    pub fn data_type(&self) -> DataType {
+        match self {
+            PlaceholderValue::Null => DataType::Null,
+            PlaceholderValue::Int(_) => DataType::Number(NumberDataType::Int64),
+            PlaceholderValue::UInt(_) => DataType::Number(NumberDataType::UInt64),
+            PlaceholderValue::Float(_) => DataType::Number(NumberDataType::Float64),
+            PlaceholderValue::String(_) => DataType::String,
+            PlaceholderValue::Date(_) => DataType::Date,
+            PlaceholderValue::Timestamp(_) => DataType::Timestamp,
+        }
+    }
+}
+
 #[async_trait::async_trait]
 pub trait TableContext: Send + Sync {
     /// Build a table instance the plan wants to operate on.
@@ -108,5 +140,13 @@ pub trait TableContext: Send + Sync {
     async fn get_table(&self, catalog: &str, database: &str, table: &str)
     -> Result<Arc<dyn Table>>;
     fn get_processes_info(&self) -> Vec<ProcessInfo>;
+    /// Get the quota of the current tenant.
+    async fn get_tenant_quota(&self) -> Result<TenantQuota>;
     fn get_stage_attachment(&self) -> Option<StageAttachment>;
+    /// Get the parameters of the prepared statement, bound to its `?` placeholders in order.
+    fn get_placeholders(&self) -> Vec<PlaceholderValue>;
+    /// Record the new offset of a stream read by the current query, it will be
+    /// advanced together with the next table commit.
+    fn consume_stream(&self, offset: UpsertTableOptionReq);
+    fn get_consumed_streams(&self) -> Vec<UpsertTableOptionReq>;
 }
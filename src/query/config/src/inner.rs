@@ -119,6 +119,10 @@ pub struct QueryConfig {
     pub num_cpus: u64,
     pub mysql_handler_host: String,
     pub mysql_handler_port: u16,
+    pub mysql_handler_tls_server_cert: String,
+    pub mysql_handler_tls_server_key: String,
+    pub postgres_handler_host: String,
+    pub postgres_handler_port: u16,
     pub max_active_sessions: u64,
     pub max_server_memory_usage: u64,
     pub max_memory_limit_enabled: bool,
@@ -128,6 +132,8 @@ pub struct QueryConfig {
     pub http_handler_port: u16,
     pub http_handler_result_timeout_secs: u64,
     pub flight_api_address: String,
+    pub flight_sql_handler_host: String,
+    pub flight_sql_handler_port: u16,
     pub admin_api_address: String,
     pub metric_api_address: String,
     pub http_handler_tls_server_cert: String,
@@ -167,6 +173,8 @@ pub struct QueryConfig {
     pub table_cache_bloom_index_meta_count: u64,
     /// Max bytes of cached bloom index
     pub table_cache_bloom_index_data_bytes: u64,
+    /// Local folder for the data spilled by queries
+    pub spill_dir: String,
     /// If in management mode, only can do some meta level operations(database/table/user/stage etc.) with metasrv.
     pub management_mode: bool,
     pub jwt_key_file: String,
@@ -178,6 +186,12 @@ pub struct QueryConfig {
     pub share_endpoint_auth_token_file: String,
     pub tenant_quota: Option<TenantQuota>,
     pub internal_enable_sandbox_tenant: bool,
+    /// Enable the background compaction and purge of fuse tables
+    pub maintenance_enabled: bool,
+    /// Interval in seconds between two rounds of the background maintenance
+    pub maintenance_interval_secs: u64,
+    /// Max number of tables maintained concurrently by this node
+    pub maintenance_max_concurrency: u64,
 }
 
 impl Default for QueryConfig {
@@ -188,6 +202,10 @@ impl Default for QueryConfig {
             num_cpus: 0,
             mysql_handler_host: "127.0.0.1".to_string(),
             mysql_handler_port: 3307,
+            mysql_handler_tls_server_cert: "".to_string(),
+            mysql_handler_tls_server_key: "".to_string(),
+            postgres_handler_host: "127.0.0.1".to_string(),
+            postgres_handler_port: 15432,
             max_active_sessions: 256,
             max_server_memory_usage: 0,
             max_memory_limit_enabled: false,
@@ -197,6 +215,8 @@ impl Default for QueryConfig {
             http_handler_port: 8000,
             http_handler_result_timeout_secs: 60,
             flight_api_address: "127.0.0.1:9090".to_string(),
+            flight_sql_handler_host: "127.0.0.1".to_string(),
+            flight_sql_handler_port: 8900,
             admin_api_address: "127.0.0.1:8080".to_string(),
             metric_api_address: "127.0.0.1:7070".to_string(),
             api_tls_server_cert: "".to_string(),
@@ -222,6 +242,7 @@ impl Default for QueryConfig {
             table_cache_segment_count: 10240,
             table_cache_bloom_index_meta_count: 3000,
             table_cache_bloom_index_data_bytes: 1024 * 1024 * 1024,
+            spill_dir: "_spill".to_string(),
             management_mode: false,
             jwt_key_file: "".to_string(),
             async_insert_max_data_size: 10000,
@@ -232,6 +253,9 @@ impl Default for QueryConfig {
             share_endpoint_auth_token_file: "".to_string(),
             tenant_quota: None,
             internal_enable_sandbox_tenant: false,
+            maintenance_enabled: false,
+            maintenance_interval_secs: 300,
+            maintenance_max_concurrency: 2,
         }
     }
 }
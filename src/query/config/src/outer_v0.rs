@@ -26,6 +26,8 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_types::AuthInfo;
 use common_meta_types::AuthType;
+use common_meta_types::PasswordHashMethod;
+use common_meta_types::ScramSha256Secret;
 use common_meta_types::TenantQuota;
 use common_storage::CacheConfig as InnerCacheConfig;
 use common_storage::StorageAzblobConfig as InnerStorageAzblobConfig;
@@ -1162,6 +1164,18 @@ pub struct QueryConfig {
     #[clap(long, default_value = "3307")]
     pub mysql_handler_port: u16,
 
+    #[clap(long, default_value_t)]
+    pub mysql_handler_tls_server_cert: String,
+
+    #[clap(long, default_value_t)]
+    pub mysql_handler_tls_server_key: String,
+
+    #[clap(long, default_value = "127.0.0.1")]
+    pub postgres_handler_host: String,
+
+    #[clap(long, default_value = "15432")]
+    pub postgres_handler_port: u16,
+
     #[clap(long, default_value = "256")]
     pub max_active_sessions: u64,
 
@@ -1198,6 +1212,12 @@ pub struct QueryConfig {
     #[clap(long, default_value = "127.0.0.1:9090")]
     pub flight_api_address: String,
 
+    #[clap(long, default_value = "127.0.0.1")]
+    pub flight_sql_handler_host: String,
+
+    #[clap(long, default_value = "8900")]
+    pub flight_sql_handler_port: u16,
+
     #[clap(long, default_value = "127.0.0.1:8080")]
     pub admin_api_address: String,
 
@@ -1291,6 +1311,10 @@ pub struct QueryConfig {
     #[clap(long, default_value = "1073741824")]
     pub table_cache_bloom_index_data_bytes: u64,
 
+    /// Local folder for the data spilled by queries, e.g. hash aggregation
+    #[clap(long, default_value = "_spill")]
+    pub spill_dir: String,
+
     /// If in management mode, only can do some meta level operations(database/table/user/stage etc.) with metasrv.
     #[clap(long)]
     pub management_mode: bool,
@@ -1324,6 +1348,18 @@ pub struct QueryConfig {
 
     #[clap(long)]
     pub internal_enable_sandbox_tenant: bool,
+
+    /// Enable the background compaction and purge of fuse tables
+    #[clap(long)]
+    pub maintenance_enabled: bool,
+
+    /// Interval in seconds between two rounds of the background maintenance
+    #[clap(long, default_value = "300")]
+    pub maintenance_interval_secs: u64,
+
+    /// Max number of tables maintained concurrently by this node
+    #[clap(long, default_value = "2")]
+    pub maintenance_max_concurrency: u64,
 }
 
 impl Default for QueryConfig {
@@ -1342,6 +1378,10 @@ impl TryInto<InnerQueryConfig> for QueryConfig {
             num_cpus: self.num_cpus,
             mysql_handler_host: self.mysql_handler_host,
             mysql_handler_port: self.mysql_handler_port,
+            mysql_handler_tls_server_cert: self.mysql_handler_tls_server_cert,
+            mysql_handler_tls_server_key: self.mysql_handler_tls_server_key,
+            postgres_handler_host: self.postgres_handler_host,
+            postgres_handler_port: self.postgres_handler_port,
             max_active_sessions: self.max_active_sessions,
             max_server_memory_usage: self.max_server_memory_usage,
             max_memory_limit_enabled: self.max_memory_limit_enabled,
@@ -1351,6 +1391,8 @@ impl TryInto<InnerQueryConfig> for QueryConfig {
             http_handler_port: self.http_handler_port,
             http_handler_result_timeout_secs: self.http_handler_result_timeout_secs,
             flight_api_address: self.flight_api_address,
+            flight_sql_handler_host: self.flight_sql_handler_host,
+            flight_sql_handler_port: self.flight_sql_handler_port,
             admin_api_address: self.admin_api_address,
             metric_api_address: self.metric_api_address,
             http_handler_tls_server_cert: self.http_handler_tls_server_cert,
@@ -1376,6 +1418,7 @@ impl TryInto<InnerQueryConfig> for QueryConfig {
             table_cache_segment_count: self.table_cache_segment_count,
             table_cache_bloom_index_meta_count: self.table_cache_bloom_index_meta_count,
             table_cache_bloom_index_data_bytes: self.table_cache_bloom_index_data_bytes,
+            spill_dir: self.spill_dir,
             management_mode: self.management_mode,
             jwt_key_file: self.jwt_key_file,
             async_insert_max_data_size: self.async_insert_max_data_size,
@@ -1388,6 +1431,9 @@ impl TryInto<InnerQueryConfig> for QueryConfig {
             share_endpoint_auth_token_file: self.share_endpoint_auth_token_file,
             tenant_quota: self.quota,
             internal_enable_sandbox_tenant: self.internal_enable_sandbox_tenant,
+            maintenance_enabled: self.maintenance_enabled,
+            maintenance_interval_secs: self.maintenance_interval_secs,
+            maintenance_max_concurrency: self.maintenance_max_concurrency,
         })
     }
 }
@@ -1401,6 +1447,10 @@ impl From<InnerQueryConfig> for QueryConfig {
             num_cpus: inner.num_cpus,
             mysql_handler_host: inner.mysql_handler_host,
             mysql_handler_port: inner.mysql_handler_port,
+            mysql_handler_tls_server_cert: inner.mysql_handler_tls_server_cert,
+            mysql_handler_tls_server_key: inner.mysql_handler_tls_server_key,
+            postgres_handler_host: inner.postgres_handler_host,
+            postgres_handler_port: inner.postgres_handler_port,
             max_active_sessions: inner.max_active_sessions,
             max_server_memory_usage: inner.max_server_memory_usage,
             max_memory_limit_enabled: inner.max_memory_limit_enabled,
@@ -1415,6 +1465,8 @@ impl From<InnerQueryConfig> for QueryConfig {
             http_handler_port: inner.http_handler_port,
             http_handler_result_timeout_secs: inner.http_handler_result_timeout_secs,
             flight_api_address: inner.flight_api_address,
+            flight_sql_handler_host: inner.flight_sql_handler_host,
+            flight_sql_handler_port: inner.flight_sql_handler_port,
             admin_api_address: inner.admin_api_address,
             metric_api_address: inner.metric_api_address,
             http_handler_tls_server_cert: inner.http_handler_tls_server_cert,
@@ -1441,6 +1493,7 @@ impl From<InnerQueryConfig> for QueryConfig {
             table_cache_segment_count: inner.table_cache_segment_count,
             table_cache_bloom_index_meta_count: inner.table_cache_bloom_index_meta_count,
             table_cache_bloom_index_data_bytes: inner.table_cache_bloom_index_data_bytes,
+            spill_dir: inner.spill_dir,
             management_mode: inner.management_mode,
             jwt_key_file: inner.jwt_key_file,
             async_insert_max_data_size: inner.async_insert_max_data_size,
@@ -1451,6 +1504,9 @@ impl From<InnerQueryConfig> for QueryConfig {
             share_endpoint_auth_token_file: inner.share_endpoint_auth_token_file,
             quota: inner.tenant_quota,
             internal_enable_sandbox_tenant: inner.internal_enable_sandbox_tenant,
+            maintenance_enabled: inner.maintenance_enabled,
+            maintenance_interval_secs: inner.maintenance_interval_secs,
+            maintenance_max_concurrency: inner.maintenance_max_concurrency,
         }
     }
 }
@@ -1782,6 +1838,19 @@ impl TryInto<AuthInfo> for UserAuthConfig {
                     }
                 }
             }
+            // The SCRAM secret is set in the PostgreSQL format, not in hex.
+            AuthType::ScramSha256Password => match self.auth_string {
+                None => Err(ErrorCode::InvalidConfig("must set auth_string")),
+                Some(s) => {
+                    ScramSha256Secret::parse(s.as_bytes()).map_err(|e| {
+                        ErrorCode::InvalidConfig(format!("invalid SCRAM secret: {}", e.message()))
+                    })?;
+                    Ok(AuthInfo::Password {
+                        hash_value: s.into_bytes(),
+                        hash_method: PasswordHashMethod::ScramSha256,
+                    })
+                }
+            },
         }
     }
 }
@@ -1792,6 +1861,8 @@ impl From<AuthInfo> for UserAuthConfig {
         let auth_string = inner.get_auth_string();
         let auth_string = if auth_string.is_empty() {
             None
+        } else if inner.get_type() == AuthType::ScramSha256Password {
+            Some(auth_string)
         } else {
             Some(hex::encode(auth_string))
         };
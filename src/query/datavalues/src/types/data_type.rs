@@ -22,6 +22,7 @@ use enum_dispatch::enum_dispatch;
 use super::type_array::ArrayType;
 use super::type_boolean::BooleanType;
 use super::type_date::DateType;
+use super::type_decimal::DecimalType;
 use super::type_id::TypeID;
 use super::type_nullable::NullableType;
 use super::type_primitive::Float32Type;
@@ -60,6 +61,7 @@ pub enum DataTypeImpl {
     UInt64(UInt64Type),
     Float32(Float32Type),
     Float64(Float64Type),
+    Decimal(DecimalType),
     Date(DateType),
     Timestamp(TimestampType),
     String(StringType),
@@ -122,6 +124,9 @@ pub fn from_arrow_type(dt: &ArrowType) -> DataTypeImpl {
         ArrowType::Boolean => DataTypeImpl::Boolean(BooleanType::default()),
         ArrowType::Float32 => DataTypeImpl::Float32(Float32Type::default()),
         ArrowType::Float64 => DataTypeImpl::Float64(Float64Type::default()),
+        ArrowType::Decimal(precision, scale) | ArrowType::Decimal256(precision, scale) => {
+            DecimalType::new_impl(*precision as u8, *scale as u8)
+        }
 
         // TODO support other list
         ArrowType::List(f) | ArrowType::LargeList(f) | ArrowType::FixedSizeList(f, _) => {
@@ -15,6 +15,7 @@
 use std::sync::Arc;
 
 use super::type_array::ArrayType;
+use super::type_decimal::DecimalType;
 use super::type_nullable::NullableType;
 use super::type_struct::StructType;
 use super::DataType;
@@ -51,6 +52,13 @@ pub fn equal(lhs: &DataTypeImpl, rhs: &DataTypeImpl) -> bool {
         | Float64 | String | Date | Timestamp | Interval | Null | Variant | VariantArray
         | VariantObject => true,
 
+        Decimal => {
+            let lhs: DecimalType = lhs.to_owned().try_into().unwrap();
+            let rhs: DecimalType = rhs.to_owned().try_into().unwrap();
+
+            lhs.precision() == rhs.precision() && lhs.scale() == rhs.scale()
+        }
+
         Nullable => {
             let lhs: NullableType = lhs.to_owned().try_into().unwrap();
             let rhs: NullableType = rhs.to_owned().try_into().unwrap();
@@ -16,6 +16,7 @@ pub mod data_type;
 pub mod type_array;
 pub mod type_boolean;
 pub mod type_date;
+pub mod type_decimal;
 pub mod type_interval;
 pub mod type_null;
 pub mod type_nullable;
@@ -35,6 +36,7 @@ pub use eq::*;
 pub use type_array::*;
 pub use type_boolean::*;
 pub use type_date::*;
+pub use type_decimal::*;
 pub use type_id::*;
 pub use type_interval::*;
 pub use type_null::*;
@@ -0,0 +1,72 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_arrow::arrow::datatypes::DataType as ArrowType;
+
+use super::data_type::DataType;
+use super::type_id::TypeID;
+use crate::prelude::*;
+
+/// The max precision of a decimal that is stored in 128 bits, a wider decimal is stored in 256 bits.
+pub const MAX_DECIMAL128_PRECISION: u8 = 38;
+
+/// Decimal type with a fixed `precision` (the total number of digits)
+/// and `scale` (the number of digits after the decimal point).
+#[derive(Default, Clone, Hash, serde::Deserialize, serde::Serialize)]
+pub struct DecimalType {
+    precision: u8,
+    scale: u8,
+}
+
+impl DecimalType {
+    pub fn create(precision: u8, scale: u8) -> Self {
+        DecimalType { precision, scale }
+    }
+
+    pub fn new_impl(precision: u8, scale: u8) -> DataTypeImpl {
+        DataTypeImpl::Decimal(Self::create(precision, scale))
+    }
+
+    pub fn precision(&self) -> u8 {
+        self.precision
+    }
+
+    pub fn scale(&self) -> u8 {
+        self.scale
+    }
+}
+
+impl DataType for DecimalType {
+    fn data_type_id(&self) -> TypeID {
+        TypeID::Decimal
+    }
+
+    fn name(&self) -> String {
+        format!("Decimal({}, {})", self.precision, self.scale)
+    }
+
+    fn arrow_type(&self) -> ArrowType {
+        if self.precision > MAX_DECIMAL128_PRECISION {
+            ArrowType::Decimal256(self.precision as usize, self.scale as usize)
+        } else {
+            ArrowType::Decimal(self.precision as usize, self.scale as usize)
+        }
+    }
+}
+
+impl std::fmt::Debug for DecimalType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
@@ -33,6 +33,10 @@ pub enum TypeID {
     Float32,
     Float64,
 
+    /// A fixed-point number with the precision and scale, it's physical type is
+    /// Decimal128 if the precision is less than or equal to 38, otherwise Decimal256.
+    Decimal,
+
     String,
 
     /// A 32-bit date representing the elapsed time since UNIX epoch (1970-01-01)
@@ -215,6 +219,7 @@ impl TypeID {
             UInt64 => PhysicalTypeID::UInt64,
             Float32 => PhysicalTypeID::Float32,
             Float64 => PhysicalTypeID::Float64,
+            Decimal => PhysicalTypeID::Decimal,
 
             String => PhysicalTypeID::String,
             Array => PhysicalTypeID::Array,
@@ -265,4 +270,6 @@ pub enum PhysicalTypeID {
     Float32,
     /// A 64-bit floating point number.
     Float64,
+    /// A 128-bit or 256-bit fixed-point number.
+    Decimal,
 }
@@ -16,6 +16,12 @@ use common_datavalues::remove_nullable;
 use common_datavalues::DataTypeImpl;
 use common_datavalues::DataValue;
 
+use crate::types::decimal::i256;
+use crate::types::decimal::Decimal;
+use crate::types::decimal::DecimalDataType;
+use crate::types::decimal::DecimalScalar;
+use crate::types::decimal::DecimalSize;
+use crate::types::decimal::MAX_DECIMAL128_PRECISION;
 use crate::types::number::NumberScalar;
 use crate::types::NumberDataType;
 use crate::with_number_type;
@@ -39,6 +45,19 @@ pub fn from_type(datatype: &DataTypeImpl) -> TableDataType {
         DataTypeImpl::Null(_) => TableDataType::Null,
         DataTypeImpl::Nullable(v) => TableDataType::Nullable(Box::new(from_type(v.inner_type()))),
         DataTypeImpl::Boolean(_) => TableDataType::Boolean,
+        DataTypeImpl::Decimal(ty) => {
+            TableDataType::Decimal(if ty.precision() > MAX_DECIMAL128_PRECISION {
+                DecimalDataType::Decimal256(DecimalSize {
+                    precision: ty.precision(),
+                    scale: ty.scale(),
+                })
+            } else {
+                DecimalDataType::Decimal128(DecimalSize {
+                    precision: ty.precision(),
+                    scale: ty.scale(),
+                })
+            })
+        }
         DataTypeImpl::Timestamp(_) => TableDataType::Timestamp,
         DataTypeImpl::Date(_) => TableDataType::Date,
         DataTypeImpl::String(_) => TableDataType::String,
@@ -111,6 +130,25 @@ pub fn from_scalar(datavalue: &DataValue, datatype: &DataTypeImpl) -> Scalar {
         DataTypeImpl::Float64(_) => {
             Scalar::Number(NumberScalar::Float64(datavalue.as_f64().unwrap().into()))
         }
+        DataTypeImpl::Decimal(_) => {
+            // Decimals are stored as `Float64` in the legacy `DataValue`.
+            let value = datavalue.as_f64().unwrap();
+            match from_type(datatype) {
+                TableDataType::Decimal(DecimalDataType::Decimal128(size)) => {
+                    Scalar::Decimal(DecimalScalar::Decimal128(
+                        i128::from_float(value, size).unwrap_or_default(),
+                        size,
+                    ))
+                }
+                TableDataType::Decimal(DecimalDataType::Decimal256(size)) => {
+                    Scalar::Decimal(DecimalScalar::Decimal256(
+                        i256::from_float(value, size).unwrap_or_default(),
+                        size,
+                    ))
+                }
+                _ => unreachable!(),
+            }
+        }
         DataTypeImpl::Timestamp(_) => Scalar::Timestamp(datavalue.as_i64().unwrap()),
         DataTypeImpl::Date(_) => Scalar::Date(datavalue.as_i64().unwrap() as i32),
         DataTypeImpl::String(_) => Scalar::String(datavalue.as_string().unwrap()),
@@ -59,6 +59,7 @@ pub fn scalar_to_datavalue(scalar: &Scalar) -> DataValue {
             }
             crate::types::number::NumberScalar::Float64(x) => DataValue::Float64((*x).into()),
         },
+        Scalar::Decimal(x) => DataValue::Float64(x.to_float64()),
         Scalar::Timestamp(x) => DataValue::Int64(*x),
         Scalar::Date(x) => DataValue::Int64(*x as i64),
         Scalar::Boolean(x) => DataValue::Boolean(*x),
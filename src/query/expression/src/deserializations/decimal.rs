@@ -0,0 +1,144 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Cursor;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_io::cursor_ext::collect_number;
+use common_io::prelude::FormatSettings;
+
+use crate::types::decimal::read_decimal_with_size;
+use crate::types::decimal::Decimal;
+use crate::types::decimal::DecimalSize;
+use crate::Column;
+use crate::Scalar;
+use crate::TypeDeserializer;
+
+pub struct DecimalDeserializer<T: Decimal> {
+    pub builder: Vec<T>,
+    pub size: DecimalSize,
+}
+
+impl<T: Decimal> DecimalDeserializer<T> {
+    pub fn with_capacity(size: DecimalSize, capacity: usize) -> Self {
+        Self {
+            builder: Vec::with_capacity(capacity),
+            size,
+        }
+    }
+
+    /// Parse a whole decimal text such as `-12.34`.
+    pub fn de_text(&mut self, buf: &[u8]) -> Result<()> {
+        let value = read_decimal_with_size::<T>(buf, self.size).map_err(ErrorCode::BadBytes)?;
+        self.builder.push(value);
+        Ok(())
+    }
+
+    /// Parse the decimal text at the position of the reader, and move the reader
+    /// to the end of the number.
+    pub fn read_text<R: AsRef<[u8]>>(&mut self, reader: &mut Cursor<R>) -> Result<()> {
+        let pos = reader.position() as usize;
+        let buf = &reader.get_ref().as_ref()[pos..];
+        let (n_in, _) = collect_number(buf);
+        if n_in == 0 {
+            return Err(ErrorCode::BadBytes(format!(
+                "Incorrect decimal value at: {:?}",
+                String::from_utf8_lossy(&buf[..buf.len().min(16)])
+            )));
+        }
+        self.de_text(&buf[..n_in])?;
+        reader.set_position((pos + n_in) as u64);
+        Ok(())
+    }
+
+    fn read_binary(&mut self, reader: &[u8]) -> Result<usize> {
+        let len = std::mem::size_of::<T>();
+        if reader.len() < len {
+            return Err(ErrorCode::BadBytes(format!(
+                "Decimal needs {} bytes, but only {} bytes left",
+                len,
+                reader.len()
+            )));
+        }
+        // Decimals are stored as the little-endian bytes of the underlying integer.
+        let value = unsafe { std::ptr::read_unaligned(reader.as_ptr() as *const T) };
+        self.builder.push(value);
+        Ok(len)
+    }
+}
+
+impl<T: Decimal> TypeDeserializer for DecimalDeserializer<T> {
+    fn memory_size(&self) -> usize {
+        self.builder.len() * std::mem::size_of::<T>()
+    }
+
+    fn len(&self) -> usize {
+        self.builder.len()
+    }
+
+    fn de_binary(&mut self, reader: &mut &[u8], _format: &FormatSettings) -> Result<()> {
+        let len = self.read_binary(reader)?;
+        *reader = &reader[len..];
+        Ok(())
+    }
+
+    fn de_default(&mut self) {
+        self.builder.push(T::zero());
+    }
+
+    fn de_fixed_binary_batch(
+        &mut self,
+        reader: &[u8],
+        step: usize,
+        rows: usize,
+        _format: &FormatSettings,
+    ) -> Result<()> {
+        for row in 0..rows {
+            self.read_binary(&reader[step * row..])?;
+        }
+        Ok(())
+    }
+
+    fn de_json(&mut self, value: &serde_json::Value, _format: &FormatSettings) -> Result<()> {
+        match value {
+            serde_json::Value::Number(v) => self.de_text(v.to_string().as_bytes()),
+            serde_json::Value::String(v) => self.de_text(v.as_bytes()),
+            _ => Err(ErrorCode::from("Incorrect json value, must be decimal")),
+        }
+    }
+
+    fn append_data_value(&mut self, value: Scalar, _format: &FormatSettings) -> Result<()> {
+        let v = value
+            .as_decimal()
+            .and_then(T::try_downcast_scalar)
+            .ok_or_else(|| ErrorCode::from("Unable to get decimal value"))?;
+        self.builder.push(v);
+        Ok(())
+    }
+
+    fn pop_data_value(&mut self) -> Result<()> {
+        match self.builder.pop() {
+            Some(_) => Ok(()),
+            None => Err(ErrorCode::from(
+                "Decimal column is empty when pop data value",
+            )),
+        }
+    }
+
+    fn finish_to_column(&mut self) -> Column {
+        self.builder.shrink_to_fit();
+        T::upcast_column(std::mem::take(&mut self.builder).into(), self.size)
+    }
+}
@@ -25,6 +25,7 @@ use crate::expression::Span;
 use crate::function::EvalContext;
 use crate::property::Domain;
 use crate::type_check::check_simple_cast;
+use crate::type_check::get_simple_cast_params;
 use crate::types::any::AnyType;
 use crate::types::array::ArrayColumn;
 use crate::types::nullable::NullableColumn;
@@ -489,6 +490,7 @@ impl<'a> Evaluator<'a> {
         let (val, ty) = eval_function(
             span,
             cast_fn,
+            &get_simple_cast_params(&dest_type.remove_nullable()),
             [(value, src_type.clone())],
             self.func_ctx,
             num_rows,
@@ -843,6 +845,7 @@ impl<'a, Index: ColumnIndex> ConstantFolder<'a, Index> {
         let (domain, ty) = calculate_function_domain(
             span,
             cast_fn,
+            &get_simple_cast_params(&dest_type.remove_nullable()),
             [(domain.clone(), src_type.clone())],
             self.func_ctx,
             self.fn_registry,
@@ -17,6 +17,7 @@ use common_exception::Result;
 use itertools::Itertools;
 
 use crate::types::array::ArrayColumnBuilder;
+use crate::types::decimal::DecimalColumnBuilder;
 use crate::types::nullable::NullableColumn;
 use crate::types::number::NumberColumn;
 use crate::types::string::StringColumnBuilder;
@@ -104,6 +105,13 @@ impl Column {
                     Self::concat_arg_types::<NumberType<NUM_TYPE>>(columns)
                 }
             }),
+            Column::Decimal(col) => {
+                let mut builder = DecimalColumnBuilder::with_capacity(&col.data_type(), capacity);
+                for c in columns {
+                    builder.append_column(c.as_decimal().unwrap());
+                }
+                Column::Decimal(builder.build())
+            }
             Column::Boolean(_) => Self::concat_arg_types::<BooleanType>(columns),
             Column::String(_) => {
                 let data_capacity = columns.iter().map(|c| c.memory_size() - c.len() * 8).sum();
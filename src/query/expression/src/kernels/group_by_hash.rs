@@ -29,6 +29,7 @@ use primitive_types::U256;
 use primitive_types::U512;
 
 use crate::types::boolean::BooleanType;
+use crate::types::decimal::DecimalColumn;
 use crate::types::nullable::NullableColumn;
 use crate::types::number::Number;
 use crate::types::number::NumberColumn;
@@ -38,6 +39,7 @@ use crate::types::DataType;
 use crate::types::NumberDataType;
 use crate::types::NumberType;
 use crate::types::ValueType;
+use crate::with_decimal_type;
 use crate::with_number_mapped_type;
 use crate::Column;
 use crate::TypeDeserializer;
@@ -418,6 +420,11 @@ pub fn serialize_column_binary(column: &Column, row: usize, vec: &mut Vec<u8>) {
         Column::Number(v) => with_number_mapped_type!(|NUM_TYPE| match v {
             NumberColumn::NUM_TYPE(v) => vec.extend_from_slice(v[row].to_le_bytes().as_ref()),
         }),
+        Column::Decimal(v) => with_decimal_type!(|DECIMAL_TYPE| match v {
+            DecimalColumn::DECIMAL_TYPE(v, _) => {
+                vec.extend_from_slice(v[row].to_le_bytes().as_ref())
+            }
+        }),
         Column::Boolean(v) => vec.push(v.get_bit(row) as u8),
         Column::String(v) => {
             BinaryWrite::write_binary(vec, unsafe { v.index_unchecked(row) }).unwrap()
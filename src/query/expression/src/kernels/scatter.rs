@@ -16,6 +16,7 @@ use common_arrow::arrow::bitmap::MutableBitmap;
 use common_exception::Result;
 
 use crate::types::array::ArrayColumnBuilder;
+use crate::types::decimal::DecimalColumn;
 use crate::types::nullable::NullableColumn;
 use crate::types::number::NumberColumn;
 use crate::types::string::StringColumnBuilder;
@@ -29,6 +30,7 @@ use crate::types::StringType;
 use crate::types::TimestampType;
 use crate::types::ValueType;
 use crate::types::VariantType;
+use crate::with_decimal_type;
 use crate::with_number_mapped_type;
 use crate::BlockEntry;
 use crate::Column;
@@ -121,6 +123,18 @@ impl Column {
                     scatter_size
                 ),
             }),
+            Column::Decimal(column) => with_decimal_type!(|DECIMAL_TYPE| match column {
+                DecimalColumn::DECIMAL_TYPE(values, size) => {
+                    let mut builders = vec![Vec::with_capacity(length); scatter_size];
+                    for (index, item) in indices.iter().zip(values.iter()) {
+                        builders[index.to_usize()].push(*item);
+                    }
+                    builders
+                        .into_iter()
+                        .map(|b| Column::Decimal(DecimalColumn::DECIMAL_TYPE(b.into(), *size)))
+                        .collect()
+                }
+            }),
             Column::EmptyArray { .. } => Self::scatter_repeat_scalars::<I>(
                 &Scalar::EmptyArray,
                 data_type,
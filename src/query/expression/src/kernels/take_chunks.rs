@@ -16,6 +16,8 @@ use common_arrow::arrow::compute::merge_sort::MergeSlice;
 use itertools::Itertools;
 
 use crate::types::array::ArrayColumnBuilder;
+use crate::types::decimal::Decimal;
+use crate::types::decimal::DecimalColumn;
 use crate::types::nullable::NullableColumn;
 use crate::types::number::NumberColumn;
 use crate::types::AnyType;
@@ -29,6 +31,7 @@ use crate::types::StringType;
 use crate::types::TimestampType;
 use crate::types::ValueType;
 use crate::types::VariantType;
+use crate::with_decimal_mapped_type;
 use crate::with_number_mapped_type;
 use crate::BlockEntry;
 use crate::Column;
@@ -198,6 +201,19 @@ impl Column {
                     Self::take_block_value_types::<NumberType<NUM_TYPE>>(columns, builder, indices)
                 }
             }),
+            Column::Decimal(column) => with_decimal_mapped_type!(|DECIMAL_TYPE| match column {
+                DecimalColumn::DECIMAL_TYPE(_, size) => {
+                    let mut builder = Vec::with_capacity(result_size);
+                    for &(block_index, row, times) in indices {
+                        let (values, _) = DECIMAL_TYPE::try_downcast_column(
+                            columns[block_index].as_decimal().unwrap(),
+                        )
+                        .unwrap();
+                        builder.extend(std::iter::repeat(values[row]).take(times));
+                    }
+                    DECIMAL_TYPE::upcast_column(builder.into(), *size)
+                }
+            }),
             Column::Boolean(_) => {
                 let builder = BooleanType::create_builder(result_size, &[]);
                 Self::take_block_value_types::<BooleanType>(columns, builder, indices)
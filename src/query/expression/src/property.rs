@@ -15,6 +15,9 @@
 use enum_as_inner::EnumAsInner;
 
 use crate::types::boolean::BooleanDomain;
+use crate::types::decimal::Decimal;
+use crate::types::decimal::DecimalDataType;
+use crate::types::decimal::DecimalDomain;
 use crate::types::nullable::NullableDomain;
 use crate::types::number::NumberDomain;
 use crate::types::number::NumberScalar;
@@ -32,6 +35,8 @@ use crate::types::NumberType;
 use crate::types::StringType;
 use crate::types::TimestampType;
 use crate::types::ValueType;
+use crate::with_decimal_mapped_type;
+use crate::with_decimal_type;
 use crate::with_number_type;
 use crate::Scalar;
 
@@ -67,6 +72,7 @@ pub enum FunctionDomain<T: ValueType> {
 #[derive(Debug, Clone, PartialEq, EnumAsInner)]
 pub enum Domain {
     Number(NumberDomain),
+    Decimal(DecimalDomain),
     Boolean(BooleanDomain),
     String(StringDomain),
     Timestamp(SimpleDomain<i64>),
@@ -137,6 +143,19 @@ impl Domain {
             DataType::Number(NumberDataType::Float64) => {
                 Domain::Number(NumberDomain::Float64(NumberType::<F64>::full_domain()))
             }
+            DataType::Decimal(ty) => {
+                with_decimal_mapped_type!(|DECIMAL_TYPE| match ty {
+                    DecimalDataType::DECIMAL_TYPE(size) => {
+                        Domain::Decimal(DecimalDomain::DECIMAL_TYPE(
+                            SimpleDomain {
+                                min: DECIMAL_TYPE::min_for_precision(size.precision),
+                                max: DECIMAL_TYPE::max_for_precision(size.precision),
+                            },
+                            *size,
+                        ))
+                    }
+                })
+            }
             DataType::Timestamp => Domain::Timestamp(TimestampType::full_domain()),
             DataType::Date => Domain::Date(DateType::full_domain()),
             DataType::Null => Domain::Nullable(NullableDomain {
@@ -169,6 +188,19 @@ impl Domain {
                     _ => unreachable!("unable to merge {this:?} with {other:?}"),
                 })
             }
+            (Domain::Decimal(this), Domain::Decimal(other)) => {
+                with_decimal_type!(|TYPE| match (this, other) {
+                    (DecimalDomain::TYPE(this, size), DecimalDomain::TYPE(other, _)) =>
+                        Domain::Decimal(DecimalDomain::TYPE(
+                            SimpleDomain {
+                                min: this.min.min(other.min),
+                                max: this.max.max(other.max),
+                            },
+                            *size,
+                        )),
+                    _ => unreachable!("unable to merge {this:?} with {other:?}"),
+                })
+            }
             (Domain::Boolean(this), Domain::Boolean(other)) => Domain::Boolean(BooleanDomain {
                 has_false: this.has_false || other.has_false,
                 has_true: this.has_true || other.has_true,
@@ -293,6 +325,16 @@ impl Domain {
             Domain::Number(NumberDomain::Float64(SimpleDomain { min, max })) if min == max => {
                 Some(Scalar::Number(NumberScalar::Float64(*min)))
             }
+            Domain::Decimal(domain) => {
+                with_decimal_type!(|TYPE| match domain {
+                    DecimalDomain::TYPE(SimpleDomain { min, max }, size) if min == max => {
+                        Some(Scalar::Decimal(crate::types::decimal::DecimalScalar::TYPE(
+                            *min, *size,
+                        )))
+                    }
+                    _ => None,
+                })
+            }
             Domain::Boolean(BooleanDomain {
                 has_false: true,
                 has_true: false,
@@ -37,6 +37,10 @@ use serde::Serialize;
 use crate::types::array::ArrayColumn;
 use crate::types::date::DATE_MAX;
 use crate::types::date::DATE_MIN;
+use crate::types::decimal::Decimal;
+use crate::types::decimal::DecimalColumn;
+use crate::types::decimal::DecimalDataType;
+use crate::types::decimal::DecimalSize;
 use crate::types::nullable::NullableColumn;
 use crate::types::timestamp::TIMESTAMP_MAX;
 use crate::types::timestamp::TIMESTAMP_MIN;
@@ -48,6 +52,7 @@ use crate::types::NumberType;
 use crate::types::StringType;
 use crate::types::TimestampType;
 use crate::types::VariantType;
+use crate::with_decimal_mapped_type;
 use crate::with_number_mapped_type;
 use crate::with_number_type;
 use crate::BlockEntry;
@@ -71,10 +76,36 @@ pub struct DataField {
     data_type: DataType,
 }
 
+pub type ColumnId = u32;
+
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(from = "TableSchemaSerde")]
 pub struct TableSchema {
     pub(crate) fields: Vec<TableField>,
     pub(crate) metadata: BTreeMap<String, String>,
+    // The column id that will be assigned to the next added column.
+    pub(crate) next_column_id: ColumnId,
+}
+
+/// The persisted form of [`TableSchema`], schemas written before column ids were
+/// introduced are loaded with the column ids assigned by position.
+#[derive(Deserialize)]
+struct TableSchemaSerde {
+    fields: Vec<TableField>,
+    #[serde(default)]
+    metadata: BTreeMap<String, String>,
+    #[serde(default)]
+    next_column_id: ColumnId,
+}
+
+impl From<TableSchemaSerde> for TableSchema {
+    fn from(s: TableSchemaSerde) -> Self {
+        if s.next_column_id == 0 {
+            TableSchema::new_from(s.fields, s.metadata)
+        } else {
+            TableSchema::new_from_column_ids(s.fields, s.metadata, s.next_column_id)
+        }
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
@@ -82,6 +113,10 @@ pub struct TableField {
     name: String,
     default_expr: Option<String>,
     data_type: TableDataType,
+    /// The id of the first leaf column of this field, the leaf columns of a nested
+    /// field take the consecutive ids after it.
+    #[serde(default)]
+    column_id: ColumnId,
 }
 
 /// DataType with more information that is only available for table field, e.g, the
@@ -93,6 +128,7 @@ pub enum TableDataType {
     Boolean,
     String,
     Number(NumberDataType),
+    Decimal(DecimalDataType),
     Timestamp,
     Date,
     Nullable(Box<TableDataType>),
@@ -241,18 +277,99 @@ impl TableSchema {
         Self {
             fields: vec![],
             metadata: BTreeMap::new(),
+            next_column_id: 0,
         }
     }
 
     pub fn new(fields: Vec<TableField>) -> Self {
+        Self::new_from(fields, BTreeMap::new())
+    }
+
+    /// Create a schema and assign the column ids of the fields by position.
+    pub fn new_from(fields: Vec<TableField>, metadata: BTreeMap<String, String>) -> Self {
+        let mut next_column_id = 0;
+        let fields = fields
+            .into_iter()
+            .map(|field| {
+                let field = field.with_column_id(next_column_id);
+                next_column_id += field.data_type.num_leaf_columns();
+                field
+            })
+            .collect();
         Self {
             fields,
-            metadata: BTreeMap::new(),
+            metadata,
+            next_column_id,
         }
     }
 
-    pub fn new_from(fields: Vec<TableField>, metadata: BTreeMap<String, String>) -> Self {
-        Self { fields, metadata }
+    /// Create a schema from fields whose column ids have already been assigned.
+    pub fn new_from_column_ids(
+        fields: Vec<TableField>,
+        metadata: BTreeMap<String, String>,
+        next_column_id: ColumnId,
+    ) -> Self {
+        Self {
+            fields,
+            metadata,
+            next_column_id,
+        }
+    }
+
+    #[inline]
+    pub fn next_column_id(&self) -> ColumnId {
+        self.next_column_id
+    }
+
+    /// Returns the column ids of all the leaf columns in DFS order, which is the order
+    /// of the leaf columns in the storage formats.
+    pub fn to_leaf_column_ids(&self) -> Vec<ColumnId> {
+        self.fields
+            .iter()
+            .flat_map(|field| field.leaf_column_ids())
+            .collect()
+    }
+
+    /// Add a new column at the end of the schema. The column is assigned a new column id,
+    /// so that the data of the columns dropped before can never be mistaken for it.
+    pub fn add_column(&mut self, field: &TableField) -> Result<()> {
+        if self.has_field(field.name()) {
+            return Err(ErrorCode::AddColumnExistError(format!(
+                "Column \"{}\" already exists",
+                field.name()
+            )));
+        }
+        let field = field.clone().with_column_id(self.next_column_id);
+        self.next_column_id += field.data_type.num_leaf_columns();
+        self.fields.push(field);
+        Ok(())
+    }
+
+    pub fn drop_column(&mut self, name: &str) -> Result<()> {
+        let (index, _) = self.column_with_name(name).ok_or_else(|| {
+            ErrorCode::UnknownColumn(format!("Column \"{}\" does not exist", name))
+        })?;
+        if self.fields.len() == 1 {
+            return Err(ErrorCode::DropColumnEmptyError(
+                "Can't drop the only column of a table",
+            ));
+        }
+        self.fields.remove(index);
+        Ok(())
+    }
+
+    pub fn rename_column(&mut self, old_name: &str, new_name: &str) -> Result<()> {
+        let (index, _) = self.column_with_name(old_name).ok_or_else(|| {
+            ErrorCode::UnknownColumn(format!("Column \"{}\" does not exist", old_name))
+        })?;
+        if self.has_field(new_name) {
+            return Err(ErrorCode::AddColumnExistError(format!(
+                "Column \"{}\" already exists",
+                new_name
+            )));
+        }
+        self.fields[index].name = new_name.to_string();
+        Ok(())
     }
 
     /// Returns an immutable reference of the vector of `Field` instances.
@@ -343,7 +460,7 @@ impl TableSchema {
             .iter()
             .map(|idx| self.fields()[*idx].clone())
             .collect();
-        Self::new_from(fields, self.meta().clone())
+        Self::new_from_column_ids(fields, self.meta().clone(), self.next_column_id)
     }
 
     /// project with inner columns by path.
@@ -353,7 +470,7 @@ impl TableSchema {
             .iter()
             .map(|path| Self::traverse_paths(self.fields(), path).unwrap())
             .collect();
-        Self::new_from(fields, self.meta().clone())
+        Self::new_from_column_ids(fields, self.meta().clone(), self.next_column_id)
     }
 
     fn traverse_paths(fields: &[TableField], path: &[usize]) -> Result<TableField> {
@@ -386,12 +503,16 @@ impl TableSchema {
             fields_type,
         } = &field.data_type
         {
+            let mut column_id = field.column_id;
             let fields = fields_name
                 .iter()
                 .zip(fields_type)
                 .map(|(name, ty)| {
                     let inner_name = format!("{}:{}", field_name, name.to_lowercase());
-                    TableField::new(&inner_name, ty.clone())
+                    let inner_field =
+                        TableField::new(&inner_name, ty.clone()).with_column_id(column_id);
+                    column_id += ty.num_leaf_columns();
+                    inner_field
                 })
                 .collect::<Vec<_>>();
             return Self::traverse_paths(&fields, &path[1..]);
@@ -428,7 +549,7 @@ impl TableSchema {
     /// project will do column pruning.
     #[must_use]
     pub fn project_by_fields(&self, fields: Vec<TableField>) -> Self {
-        Self::new_from(fields, self.meta().clone())
+        Self::new_from_column_ids(fields, self.meta().clone(), self.next_column_id)
     }
 
     pub fn to_arrow(&self) -> ArrowSchema {
@@ -499,6 +620,7 @@ impl TableField {
             name: name.to_string(),
             default_expr: None,
             data_type,
+            column_id: 0,
         }
     }
 
@@ -508,6 +630,21 @@ impl TableField {
         self
     }
 
+    #[must_use]
+    pub fn with_column_id(mut self, column_id: ColumnId) -> Self {
+        self.column_id = column_id;
+        self
+    }
+
+    pub fn column_id(&self) -> ColumnId {
+        self.column_id
+    }
+
+    /// Returns the column ids of the leaf columns of this field in DFS order.
+    pub fn leaf_column_ids(&self) -> Vec<ColumnId> {
+        (self.column_id..self.column_id + self.data_type.num_leaf_columns()).collect()
+    }
+
     pub fn name(&self) -> &String {
         &self.name
     }
@@ -539,6 +676,7 @@ impl From<&TableDataType> for DataType {
             TableDataType::Boolean => DataType::Boolean,
             TableDataType::String => DataType::String,
             TableDataType::Number(ty) => DataType::Number(*ty),
+            TableDataType::Decimal(ty) => DataType::Decimal(*ty),
             TableDataType::Timestamp => DataType::Timestamp,
             TableDataType::Date => DataType::Date,
             TableDataType::Nullable(ty) => DataType::Nullable(Box::new((&**ty).into())),
@@ -553,6 +691,19 @@ impl From<&TableDataType> for DataType {
 }
 
 impl TableDataType {
+    /// Returns the number of the leaf columns of this type in the storage formats.
+    pub fn num_leaf_columns(&self) -> ColumnId {
+        match self {
+            TableDataType::Nullable(ty) | TableDataType::Array(ty) | TableDataType::Map(ty) => {
+                ty.num_leaf_columns()
+            }
+            TableDataType::Tuple { fields_type, .. } => {
+                fields_type.iter().map(|ty| ty.num_leaf_columns()).sum()
+            }
+            _ => 1,
+        }
+    }
+
     pub fn wrap_nullable(&self) -> Self {
         match self {
             TableDataType::Nullable(_) => self.clone(),
@@ -602,6 +753,9 @@ impl TableDataType {
                 NumberDataType::Float32 => "FLOAT".to_string(),
                 NumberDataType::Float64 => "DOUBLE".to_string(),
             },
+            TableDataType::Decimal(ty) => {
+                format!("DECIMAL({}, {})", ty.precision(), ty.scale())
+            }
             TableDataType::String => "VARCHAR".to_string(),
             TableDataType::Nullable(inner_ty) => format!("{} NULL", inner_ty.sql_name()),
             _ => self.to_string().to_uppercase(),
@@ -643,6 +797,27 @@ impl TableDataType {
                     ),
                 })),
             },
+            TableDataType::Decimal(ty) => {
+                // Keep the random values small enough to fit into the precision.
+                let bound = 10i64.pow(ty.precision().min(18) as u32) - 1;
+                BlockEntry {
+                    data_type: DataType::Decimal(*ty),
+                    value: Value::Column(with_decimal_mapped_type!(|DECIMAL_TYPE| match ty {
+                        DecimalDataType::DECIMAL_TYPE(size) =>
+                            Column::Decimal(DecimalColumn::DECIMAL_TYPE(
+                                (0..len)
+                                    .map(|_| {
+                                        DECIMAL_TYPE::from_i64(
+                                            SmallRng::from_entropy().gen_range(-bound..=bound),
+                                        )
+                                    })
+                                    .collect::<Vec<_>>()
+                                    .into(),
+                                *size,
+                            )),
+                    })),
+                }
+            }
             TableDataType::Timestamp => BlockEntry {
                 data_type: DataType::Timestamp,
                 value: Value::Column(TimestampType::from_data(
@@ -819,6 +994,7 @@ impl From<&ArrowField> for TableField {
             name: f.name.clone(),
             data_type: f.into(),
             default_expr: None,
+            column_id: 0,
         }
     }
 }
@@ -846,12 +1022,29 @@ impl From<&ArrowField> for TableDataType {
             | ArrowDataType::LargeList(f)
             | ArrowDataType::FixedSizeList(f, _) =>
                 TableDataType::Array(Box::new(f.as_ref().into())),
+            // The keys of a map are always strings, only the type of the values is kept.
+            ArrowDataType::Map(f, _) => match f.data_type() {
+                ArrowDataType::Struct(fields) if fields.len() == 2 =>
+                    TableDataType::Map(Box::new((&fields[1]).into())),
+                _ => unimplemented!("data_type: {:?}", f.data_type()),
+            },
 
             ArrowDataType::Binary
             | ArrowDataType::LargeBinary
             | ArrowDataType::Utf8
             | ArrowDataType::LargeUtf8 => TableDataType::String,
 
+            ArrowDataType::Decimal(precision, scale) =>
+                TableDataType::Decimal(DecimalDataType::Decimal128(DecimalSize {
+                    precision: *precision as u8,
+                    scale: *scale as u8,
+                })),
+            ArrowDataType::Decimal256(precision, scale) =>
+                TableDataType::Decimal(DecimalDataType::Decimal256(DecimalSize {
+                    precision: *precision as u8,
+                    scale: *scale as u8,
+                })),
+
             ArrowDataType::Timestamp(_, _) => TableDataType::Timestamp,
             ArrowDataType::Date32 | ArrowDataType::Date64 => TableDataType::Date,
 
@@ -910,6 +1103,7 @@ impl From<&DataType> for ArrowDataType {
             DataType::Number(ty) => with_number_type!(|TYPE| match ty {
                 NumberDataType::TYPE => ArrowDataType::TYPE,
             }),
+            DataType::Decimal(ty) => ty.into(),
             DataType::Timestamp => ArrowDataType::Timestamp(TimeUnit::Microsecond, None),
             DataType::Date => ArrowDataType::Date32,
             DataType::Nullable(ty) => ty.as_ref().into(),
@@ -965,6 +1159,7 @@ impl From<&TableDataType> for ArrowDataType {
             TableDataType::Number(ty) => with_number_type!(|TYPE| match ty {
                 NumberDataType::TYPE => ArrowDataType::TYPE,
             }),
+            TableDataType::Decimal(ty) => ty.into(),
             TableDataType::Timestamp => ArrowDataType::Timestamp(TimeUnit::Microsecond, None),
             TableDataType::Date => ArrowDataType::Date32,
             TableDataType::Nullable(ty) => ty.as_ref().into(),
@@ -1004,6 +1199,19 @@ impl From<&TableDataType> for ArrowDataType {
     }
 }
 
+impl From<&DecimalDataType> for ArrowDataType {
+    fn from(ty: &DecimalDataType) -> Self {
+        match ty {
+            DecimalDataType::Decimal128(size) => {
+                ArrowDataType::Decimal(size.precision as usize, size.scale as usize)
+            }
+            DecimalDataType::Decimal256(size) => {
+                ArrowDataType::Decimal256(size.precision as usize, size.scale as usize)
+            }
+        }
+    }
+}
+
 /// Convert a `DataType` to `TableDataType`.
 /// Generally, we don't allow to convert `DataType` to `TableDataType` directly.
 /// But for some special cases, for example creating table from a query without specifying
@@ -1018,6 +1226,7 @@ pub fn infer_schema_type(data_type: &DataType) -> Result<TableDataType> {
         DataType::EmptyArray => Ok(TableDataType::EmptyArray),
         DataType::String => Ok(TableDataType::String),
         DataType::Number(number_type) => Ok(TableDataType::Number(*number_type)),
+        DataType::Decimal(ty) => Ok(TableDataType::Decimal(*ty)),
         DataType::Timestamp => Ok(TableDataType::Timestamp),
         DataType::Date => Ok(TableDataType::Date),
         DataType::Nullable(inner_type) => Ok(TableDataType::Nullable(Box::new(infer_schema_type(
@@ -23,6 +23,7 @@ use crate::expression::RawExpr;
 use crate::expression::Span;
 use crate::function::FunctionRegistry;
 use crate::function::FunctionSignature;
+use crate::types::decimal::DecimalDataType;
 use crate::types::number::NumberDataType;
 use crate::types::number::NumberScalar;
 use crate::types::DataType;
@@ -70,7 +71,13 @@ pub fn check<Index: ColumnIndex>(
             } else {
                 // fast path to eval function for cast
                 if let Some(cast_fn) = check_simple_cast(expr.data_type(), *is_try, dest_type) {
-                    return check_function(span.clone(), &cast_fn, &[], &[expr], fn_registry);
+                    return check_function(
+                        span.clone(),
+                        &cast_fn,
+                        &get_simple_cast_params(dest_type),
+                        &[expr],
+                        fn_registry,
+                    );
                 }
                 Ok(Expr::Cast {
                     span: span.clone(),
@@ -171,7 +178,10 @@ pub fn check_function<Index: ColumnIndex>(
     if args.len() == 1 {
         let is_try_cast = name.starts_with("try_");
         match check_simple_cast(args[0].data_type(), is_try_cast, args[0].data_type()) {
-            Some(simple_cast_name) if simple_cast_name == name => {
+            Some(simple_cast_name)
+                if simple_cast_name == name
+                    && params == get_simple_cast_params(args[0].data_type()) =>
+            {
                 if is_try_cast {
                     return check_function(span, "to_nullable", params, args, fn_registry);
                 } else {
@@ -431,6 +441,16 @@ pub fn can_auto_cast_to(src_ty: &DataType, dest_ty: &DataType) -> bool {
             || *dest_num_ty == NumberDataType::Float64
             || src_num_ty.can_lossless_cast_to(*dest_num_ty)
         }
+        (DataType::Number(src_num_ty), DataType::Decimal(dest_ty)) => {
+            // integers can cast to decimals that have enough leading digits
+            !src_num_ty.is_float()
+                && dest_ty.leading_digits()
+                    >= DecimalDataType::from_integer_bit_width(src_num_ty.bit_width())
+                        .leading_digits()
+        }
+        (DataType::Decimal(src_ty), DataType::Decimal(dest_ty)) => {
+            src_ty.scale() <= dest_ty.scale() && src_ty.leading_digits() <= dest_ty.leading_digits()
+        }
 
         // Note: comment these because : select 'str' -1 will auto transform into: `minus(CAST('str' AS Date), CAST(1 AS Int64))`
         // (DataType::String, DataType::Date) => true,
@@ -464,6 +484,19 @@ pub fn common_super_type(ty1: DataType, ty2: DataType) -> Option<DataType> {
         (DataType::Number(num1), DataType::Number(num2)) => {
             Some(DataType::Number(num1.lossful_super_type(num2)))
         }
+        (DataType::Decimal(ty1), DataType::Decimal(ty2)) => {
+            Some(DataType::Decimal(ty1.super_type(&ty2)))
+        }
+        (DataType::Decimal(ty), DataType::Number(num))
+        | (DataType::Number(num), DataType::Decimal(ty)) => {
+            if num.is_float() {
+                Some(DataType::Number(NumberDataType::Float64))
+            } else {
+                Some(DataType::Decimal(ty.super_type(
+                    &DecimalDataType::from_integer_bit_width(num.bit_width()),
+                )))
+            }
+        }
 
         (DataType::String, DataType::Timestamp) | (DataType::Timestamp, DataType::String) => {
             Some(DataType::Timestamp)
@@ -490,7 +523,10 @@ pub fn check_simple_cast(
         return None;
     }
 
-    let function_name = format!("to_{}", dest_type.to_string().to_lowercase());
+    let function_name = match dest_type {
+        DataType::Decimal(_) => "to_decimal".to_string(),
+        _ => format!("to_{}", dest_type.to_string().to_lowercase()),
+    };
 
     if is_simple_cast_function(&function_name) {
         let prefix = if is_try { "try_" } else { "" };
@@ -500,8 +536,17 @@ pub fn check_simple_cast(
     }
 }
 
+/// Returns the parameters of the simple cast function to `dest_type`,
+/// e.g. the precision and scale for `to_decimal`.
+pub fn get_simple_cast_params(dest_type: &DataType) -> Vec<usize> {
+    match dest_type {
+        DataType::Decimal(ty) => vec![ty.precision() as usize, ty.scale() as usize],
+        _ => vec![],
+    }
+}
+
 pub fn is_simple_cast_function(name: &str) -> bool {
-    const SIMPLE_CAST_FUNCTIONS: &[&str; 15] = &[
+    const SIMPLE_CAST_FUNCTIONS: &[&str; 16] = &[
         "to_string",
         "to_uint8",
         "to_uint16",
@@ -513,6 +558,7 @@ pub fn is_simple_cast_function(name: &str) -> bool {
         "to_int64",
         "to_float32",
         "to_float64",
+        "to_decimal",
         "to_timestamp",
         "to_date",
         "to_variant",
@@ -16,6 +16,7 @@ pub mod any;
 pub mod array;
 pub mod boolean;
 pub mod date;
+pub mod decimal;
 pub mod empty_array;
 pub mod generic;
 pub mod map;
@@ -40,6 +41,7 @@ pub use self::any::AnyType;
 pub use self::array::ArrayType;
 pub use self::boolean::BooleanType;
 pub use self::date::DateType;
+pub use self::decimal::*;
 pub use self::empty_array::EmptyArrayType;
 pub use self::generic::GenericType;
 pub use self::map::MapType;
@@ -53,6 +55,7 @@ pub use self::timestamp::TimestampType;
 pub use self::variant::VariantType;
 use crate::deserializations::ArrayDeserializer;
 use crate::deserializations::DateDeserializer;
+use crate::deserializations::DecimalDeserializer;
 use crate::deserializations::NullableDeserializer;
 use crate::deserializations::NumberDeserializer;
 use crate::deserializations::TimestampDeserializer;
@@ -75,6 +78,7 @@ pub enum DataType {
     Boolean,
     String,
     Number(NumberDataType),
+    Decimal(DecimalDataType),
     Timestamp,
     Date,
     Nullable(Box<DataType>),
@@ -165,6 +169,11 @@ impl DataType {
         }
     }
 
+    #[inline]
+    pub fn is_decimal(&self) -> bool {
+        matches!(self, DataType::Decimal(_))
+    }
+
     #[inline]
     pub fn is_date_or_date_time(&self) -> bool {
         matches!(self, DataType::Timestamp | DataType::Date)
@@ -229,6 +238,12 @@ impl DataType {
                     NumberDeserializer::<F64, f64>::with_capacity(capacity).into()
                 }
             },
+            DataType::Decimal(DecimalDataType::Decimal128(size)) => {
+                DecimalDeserializer::<i128>::with_capacity(*size, capacity).into()
+            }
+            DataType::Decimal(DecimalDataType::Decimal256(size)) => {
+                DecimalDeserializer::<i256>::with_capacity(*size, capacity).into()
+            }
             DataType::Date => DateDeserializer::with_capacity(capacity).into(),
             DataType::Timestamp => TimestampDeserializer::with_capacity(capacity).into(),
             DataType::Nullable(inner_ty) => {
@@ -265,6 +280,9 @@ impl DataType {
                 NumberDataType::Float64 => "DOUBLE".to_string(),
             },
             DataType::String => "VARCHAR".to_string(),
+            DataType::Decimal(decimal) => {
+                format!("DECIMAL({}, {})", decimal.precision(), decimal.scale())
+            }
             DataType::Nullable(inner_ty) => format!("{} NULL", inner_ty.sql_name()),
             _ => self.to_string().to_uppercase(),
         }
@@ -288,6 +306,7 @@ impl DataType {
                 NumberDataType::Float32 => NumberScalar::Float32(OrderedFloat(0.0)),
                 NumberDataType::Float64 => NumberScalar::Float64(OrderedFloat(0.0)),
             }),
+            DataType::Decimal(ty) => Scalar::Decimal(ty.default_scalar()),
             DataType::Timestamp => Scalar::Timestamp(0),
             DataType::Date => Scalar::Date(0),
             DataType::Nullable(_) => Scalar::Null,
@@ -0,0 +1,968 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::ops::Range;
+
+use common_arrow::arrow::buffer::Buffer;
+use enum_as_inner::EnumAsInner;
+pub use ethnum::i256;
+use ethnum::AsI256;
+use itertools::Itertools;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::SimpleDomain;
+use crate::property::Domain;
+use crate::types::DataType;
+use crate::types::ValueType;
+use crate::utils::arrow::buffer_into_mut;
+use crate::values::Column;
+use crate::values::Scalar;
+use crate::ColumnBuilder;
+use crate::ScalarRef;
+
+pub const MAX_DECIMAL128_PRECISION: u8 = 38;
+pub const MAX_DECIMAL256_PRECISION: u8 = 76;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecimalType<T: Decimal>(PhantomData<T>);
+
+pub type Decimal128Type = DecimalType<i128>;
+pub type Decimal256Type = DecimalType<i256>;
+
+impl<Num: Decimal> ValueType for DecimalType<Num> {
+    type Scalar = Num;
+    type ScalarRef<'a> = Num;
+    type Column = Buffer<Num>;
+    type Domain = SimpleDomain<Num>;
+    type ColumnIterator<'a> = std::iter::Cloned<std::slice::Iter<'a, Num>>;
+    type ColumnBuilder = Vec<Num>;
+
+    #[inline]
+    fn upcast_gat<'short, 'long: 'short>(long: Num) -> Num {
+        long
+    }
+
+    fn to_owned_scalar<'a>(scalar: Self::ScalarRef<'a>) -> Self::Scalar {
+        scalar
+    }
+
+    fn to_scalar_ref<'a>(scalar: &'a Self::Scalar) -> Self::ScalarRef<'a> {
+        *scalar
+    }
+
+    fn try_downcast_scalar<'a>(scalar: &'a ScalarRef) -> Option<Self::ScalarRef<'a>> {
+        Num::try_downcast_scalar(scalar.as_decimal()?)
+    }
+
+    fn try_downcast_column<'a>(col: &'a Column) -> Option<Self::Column> {
+        Num::try_downcast_column(col.as_decimal()?).map(|(col, _)| col)
+    }
+
+    fn try_downcast_domain(domain: &Domain) -> Option<SimpleDomain<Num>> {
+        Num::try_downcast_domain(domain.as_decimal()?)
+    }
+
+    fn try_downcast_builder<'a>(
+        builder: &'a mut ColumnBuilder,
+    ) -> Option<&'a mut Self::ColumnBuilder> {
+        match builder {
+            ColumnBuilder::Decimal(b) => Num::try_downcast_builder(b),
+            _ => None,
+        }
+    }
+
+    // The precision and scale are not part of the value type, so the values
+    // must be upcasted with `Decimal::upcast_*` which takes the `DecimalSize`.
+    fn upcast_scalar(_scalar: Self::Scalar) -> Scalar {
+        unimplemented!()
+    }
+
+    fn upcast_column(_col: Self::Column) -> Column {
+        unimplemented!()
+    }
+
+    fn upcast_domain(_domain: SimpleDomain<Num>) -> Domain {
+        unimplemented!()
+    }
+
+    fn column_len<'a>(col: &'a Self::Column) -> usize {
+        col.len()
+    }
+
+    fn index_column<'a>(col: &'a Self::Column, index: usize) -> Option<Self::ScalarRef<'a>> {
+        col.get(index).cloned()
+    }
+
+    unsafe fn index_column_unchecked<'a>(
+        col: &'a Self::Column,
+        index: usize,
+    ) -> Self::ScalarRef<'a> {
+        *col.get_unchecked(index)
+    }
+
+    fn slice_column<'a>(col: &'a Self::Column, range: Range<usize>) -> Self::Column {
+        col.clone().slice(range.start, range.end - range.start)
+    }
+
+    fn iter_column<'a>(col: &'a Self::Column) -> Self::ColumnIterator<'a> {
+        col.iter().cloned()
+    }
+
+    fn column_to_builder(col: Self::Column) -> Self::ColumnBuilder {
+        buffer_into_mut(col)
+    }
+
+    fn builder_len(builder: &Self::ColumnBuilder) -> usize {
+        builder.len()
+    }
+
+    fn push_item(builder: &mut Self::ColumnBuilder, item: Self::Scalar) {
+        builder.push(item);
+    }
+
+    fn push_default(builder: &mut Self::ColumnBuilder) {
+        builder.push(Num::default());
+    }
+
+    fn append_column(builder: &mut Self::ColumnBuilder, other: &Self::Column) {
+        builder.extend_from_slice(other);
+    }
+
+    fn build_column(builder: Self::ColumnBuilder) -> Self::Column {
+        builder.into()
+    }
+
+    fn build_scalar(builder: Self::ColumnBuilder) -> Self::Scalar {
+        assert_eq!(builder.len(), 1);
+        builder[0]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DecimalSize {
+    pub precision: u8,
+    pub scale: u8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, EnumAsInner)]
+pub enum DecimalDataType {
+    Decimal128(DecimalSize),
+    Decimal256(DecimalSize),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, EnumAsInner, Serialize, Deserialize)]
+pub enum DecimalScalar {
+    Decimal128(i128, DecimalSize),
+    Decimal256(i256, DecimalSize),
+}
+
+#[derive(Clone, PartialEq, EnumAsInner)]
+pub enum DecimalColumn {
+    Decimal128(Buffer<i128>, DecimalSize),
+    Decimal256(Buffer<i256>, DecimalSize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, EnumAsInner)]
+pub enum DecimalColumnBuilder {
+    Decimal128(Vec<i128>, DecimalSize),
+    Decimal256(Vec<i256>, DecimalSize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, EnumAsInner)]
+pub enum DecimalDomain {
+    Decimal128(SimpleDomain<i128>, DecimalSize),
+    Decimal256(SimpleDomain<i256>, DecimalSize),
+}
+
+impl DecimalDataType {
+    /// Choose the narrowest physical representation that is able to hold
+    /// all values of the given precision.
+    pub fn from_size(size: DecimalSize) -> Result<DecimalDataType, String> {
+        if size.precision < 1 || size.precision > MAX_DECIMAL256_PRECISION {
+            return Err(format!(
+                "Decimal precision must be between 1 and {}, but got {}",
+                MAX_DECIMAL256_PRECISION, size.precision
+            ));
+        }
+        if size.scale > size.precision {
+            return Err(format!(
+                "Decimal scale must be between 0 and precision {}, but got {}",
+                size.precision, size.scale
+            ));
+        }
+        if size.precision <= MAX_DECIMAL128_PRECISION {
+            Ok(DecimalDataType::Decimal128(size))
+        } else {
+            Ok(DecimalDataType::Decimal256(size))
+        }
+    }
+
+    pub fn size(&self) -> DecimalSize {
+        crate::with_decimal_type!(|DECIMAL_TYPE| match self {
+            DecimalDataType::DECIMAL_TYPE(size) => *size,
+        })
+    }
+
+    pub fn precision(&self) -> u8 {
+        self.size().precision
+    }
+
+    pub fn scale(&self) -> u8 {
+        self.size().scale
+    }
+
+    /// Number of digits before the decimal point.
+    pub fn leading_digits(&self) -> u8 {
+        self.precision() - self.scale()
+    }
+
+    pub fn max_precision(&self) -> u8 {
+        match self {
+            DecimalDataType::Decimal128(_) => MAX_DECIMAL128_PRECISION,
+            DecimalDataType::Decimal256(_) => MAX_DECIMAL256_PRECISION,
+        }
+    }
+
+    pub fn default_scalar(&self) -> DecimalScalar {
+        crate::with_decimal_type!(|DECIMAL_TYPE| match self {
+            DecimalDataType::DECIMAL_TYPE(size) => {
+                DecimalScalar::DECIMAL_TYPE(Default::default(), *size)
+            }
+        })
+    }
+
+    /// The smallest decimal type that is able to hold every value of an integer type.
+    pub fn from_integer_bit_width(bit_width: u8) -> DecimalDataType {
+        let precision = match bit_width {
+            8 => 3,
+            16 => 5,
+            32 => 10,
+            _ => 20,
+        };
+        DecimalDataType::Decimal128(DecimalSize {
+            precision,
+            scale: 0,
+        })
+    }
+
+    /// Infer the result type of the arithmetic operation between two decimals.
+    ///
+    /// - `+`/`-`: scale is the max scale, and one more leading digit for the carry.
+    /// - `*`: both the precisions and the scales are added up.
+    /// - `/`: the scale is at least 6 digits, and the leading digits are kept
+    ///   from the dividend plus the scale of the divisor.
+    ///
+    /// The precision is capped by the max precision of `Decimal256`.
+    pub fn binary_result_type(
+        a: &DecimalDataType,
+        b: &DecimalDataType,
+        op: DecimalArithmeticOp,
+    ) -> Result<DecimalDataType, String> {
+        let (precision, scale) = match op {
+            DecimalArithmeticOp::Plus | DecimalArithmeticOp::Minus => {
+                let scale = a.scale().max(b.scale());
+                let leading = a.leading_digits().max(b.leading_digits());
+                (leading as u32 + scale as u32 + 1, scale as u32)
+            }
+            DecimalArithmeticOp::Multiply => (
+                a.precision() as u32 + b.precision() as u32,
+                a.scale() as u32 + b.scale() as u32,
+            ),
+            DecimalArithmeticOp::Divide => {
+                let scale = a.scale().max(6).min(MAX_DECIMAL256_PRECISION / 2);
+                (
+                    a.leading_digits() as u32 + b.scale() as u32 + scale as u32,
+                    scale as u32,
+                )
+            }
+        };
+        if scale > MAX_DECIMAL256_PRECISION as u32 {
+            return Err(format!(
+                "Decimal scale {} of the result exceeds the max precision {}",
+                scale, MAX_DECIMAL256_PRECISION
+            ));
+        }
+        let precision = precision.min(MAX_DECIMAL256_PRECISION as u32).max(scale);
+        DecimalDataType::from_size(DecimalSize {
+            precision: precision.max(1) as u8,
+            scale: scale as u8,
+        })
+    }
+
+    /// The smallest decimal type that both decimals can be losslessly casted to,
+    /// unless the precision exceeds the max precision of `Decimal256`.
+    pub fn super_type(&self, other: &DecimalDataType) -> DecimalDataType {
+        let scale = self.scale().max(other.scale());
+        let leading = self.leading_digits().max(other.leading_digits());
+        let precision = (leading as u32 + scale as u32).min(MAX_DECIMAL256_PRECISION as u32);
+        DecimalDataType::from_size(DecimalSize {
+            precision: precision.max(1) as u8,
+            scale,
+        })
+        .unwrap()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecimalArithmeticOp {
+    Plus,
+    Minus,
+    Multiply,
+    Divide,
+}
+
+impl DecimalScalar {
+    pub fn size(&self) -> DecimalSize {
+        crate::with_decimal_type!(|DECIMAL_TYPE| match self {
+            DecimalScalar::DECIMAL_TYPE(_, size) => *size,
+        })
+    }
+
+    pub fn data_type(&self) -> DecimalDataType {
+        crate::with_decimal_type!(|DECIMAL_TYPE| match self {
+            DecimalScalar::DECIMAL_TYPE(_, size) => DecimalDataType::DECIMAL_TYPE(*size),
+        })
+    }
+
+    pub fn domain(&self) -> DecimalDomain {
+        crate::with_decimal_type!(|DECIMAL_TYPE| match self {
+            DecimalScalar::DECIMAL_TYPE(num, size) => DecimalDomain::DECIMAL_TYPE(
+                SimpleDomain {
+                    min: *num,
+                    max: *num,
+                },
+                *size
+            ),
+        })
+    }
+
+    pub fn to_float64(&self) -> f64 {
+        crate::with_decimal_type!(|DECIMAL_TYPE| match self {
+            DecimalScalar::DECIMAL_TYPE(num, size) => num.to_float64(size.scale),
+        })
+    }
+}
+
+impl PartialOrd for DecimalScalar {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        crate::with_decimal_type!(|DECIMAL_TYPE| match (self, other) {
+            (DecimalScalar::DECIMAL_TYPE(lhs, lsize), DecimalScalar::DECIMAL_TYPE(rhs, rsize))
+                if lsize.scale == rsize.scale =>
+            {
+                lhs.partial_cmp(rhs)
+            }
+            _ => None,
+        })
+    }
+}
+
+impl Display for DecimalScalar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        crate::with_decimal_type!(|DECIMAL_TYPE| match self {
+            DecimalScalar::DECIMAL_TYPE(num, size) => {
+                write!(f, "{}", num.display(size.scale))
+            }
+        })
+    }
+}
+
+impl DecimalColumn {
+    pub fn len(&self) -> usize {
+        crate::with_decimal_type!(|DECIMAL_TYPE| match self {
+            DecimalColumn::DECIMAL_TYPE(col, _) => col.len(),
+        })
+    }
+
+    pub fn size(&self) -> DecimalSize {
+        crate::with_decimal_type!(|DECIMAL_TYPE| match self {
+            DecimalColumn::DECIMAL_TYPE(_, size) => *size,
+        })
+    }
+
+    pub fn data_type(&self) -> DecimalDataType {
+        crate::with_decimal_type!(|DECIMAL_TYPE| match self {
+            DecimalColumn::DECIMAL_TYPE(_, size) => DecimalDataType::DECIMAL_TYPE(*size),
+        })
+    }
+
+    pub fn index(&self, index: usize) -> Option<DecimalScalar> {
+        crate::with_decimal_type!(|DECIMAL_TYPE| match self {
+            DecimalColumn::DECIMAL_TYPE(col, size) => {
+                Some(DecimalScalar::DECIMAL_TYPE(col.get(index).cloned()?, *size))
+            }
+        })
+    }
+
+    /// # Safety
+    /// Assumes that the `index` is not out of range.
+    pub unsafe fn index_unchecked(&self, index: usize) -> DecimalScalar {
+        crate::with_decimal_type!(|DECIMAL_TYPE| match self {
+            DecimalColumn::DECIMAL_TYPE(col, size) => {
+                DecimalScalar::DECIMAL_TYPE(*col.get_unchecked(index), *size)
+            }
+        })
+    }
+
+    pub fn slice(&self, range: Range<usize>) -> Self {
+        assert!(
+            range.end <= self.len(),
+            "range {:?} out of len {}",
+            range,
+            self.len()
+        );
+
+        crate::with_decimal_type!(|DECIMAL_TYPE| match self {
+            DecimalColumn::DECIMAL_TYPE(col, size) => DecimalColumn::DECIMAL_TYPE(
+                col.clone().slice(range.start, range.end - range.start),
+                *size
+            ),
+        })
+    }
+
+    pub fn domain(&self) -> DecimalDomain {
+        assert!(self.len() > 0);
+        crate::with_decimal_type!(|DECIMAL_TYPE| match self {
+            DecimalColumn::DECIMAL_TYPE(col, size) => {
+                let (min, max) = col.iter().minmax().into_option().unwrap();
+                DecimalDomain::DECIMAL_TYPE(
+                    SimpleDomain {
+                        min: *min,
+                        max: *max,
+                    },
+                    *size,
+                )
+            }
+        })
+    }
+
+    pub fn memory_size(&self) -> usize {
+        crate::with_decimal_mapped_type!(|DECIMAL_TYPE| match self {
+            DecimalColumn::DECIMAL_TYPE(col, _) => col.len() * std::mem::size_of::<DECIMAL_TYPE>(),
+        })
+    }
+}
+
+impl DecimalColumnBuilder {
+    pub fn from_column(col: DecimalColumn) -> Self {
+        crate::with_decimal_type!(|DECIMAL_TYPE| match col {
+            DecimalColumn::DECIMAL_TYPE(col, size) => {
+                DecimalColumnBuilder::DECIMAL_TYPE(buffer_into_mut(col), size)
+            }
+        })
+    }
+
+    pub fn repeat(scalar: DecimalScalar, n: usize) -> DecimalColumnBuilder {
+        crate::with_decimal_type!(|DECIMAL_TYPE| match scalar {
+            DecimalScalar::DECIMAL_TYPE(num, size) => {
+                DecimalColumnBuilder::DECIMAL_TYPE(vec![num; n], size)
+            }
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        crate::with_decimal_type!(|DECIMAL_TYPE| match self {
+            DecimalColumnBuilder::DECIMAL_TYPE(col, _) => col.len(),
+        })
+    }
+
+    pub fn with_capacity(ty: &DecimalDataType, capacity: usize) -> Self {
+        crate::with_decimal_type!(|DECIMAL_TYPE| match ty {
+            DecimalDataType::DECIMAL_TYPE(size) => {
+                DecimalColumnBuilder::DECIMAL_TYPE(Vec::with_capacity(capacity), *size)
+            }
+        })
+    }
+
+    pub fn push(&mut self, item: DecimalScalar) {
+        crate::with_decimal_type!(|DECIMAL_TYPE| match (self, item) {
+            (
+                DecimalColumnBuilder::DECIMAL_TYPE(builder, builder_size),
+                DecimalScalar::DECIMAL_TYPE(value, value_size),
+            ) => {
+                debug_assert_eq!(*builder_size, value_size);
+                builder.push(value)
+            }
+            (builder, scalar) => unreachable!("unable to push {scalar:?} to {builder:?}"),
+        })
+    }
+
+    pub fn push_default(&mut self) {
+        crate::with_decimal_mapped_type!(|DECIMAL_TYPE| match self {
+            DecimalColumnBuilder::DECIMAL_TYPE(builder, _) => builder.push(DECIMAL_TYPE::default()),
+        })
+    }
+
+    pub fn append_column(&mut self, other: &DecimalColumn) {
+        crate::with_decimal_type!(|DECIMAL_TYPE| match (self, other) {
+            (
+                DecimalColumnBuilder::DECIMAL_TYPE(builder, builder_size),
+                DecimalColumn::DECIMAL_TYPE(other, other_size),
+            ) => {
+                debug_assert_eq!(builder_size, other_size);
+                builder.extend_from_slice(other);
+            }
+            (this, other) => unreachable!("unable append {other:?} onto {this:?}"),
+        })
+    }
+
+    pub fn build(self) -> DecimalColumn {
+        crate::with_decimal_type!(|DECIMAL_TYPE| match self {
+            DecimalColumnBuilder::DECIMAL_TYPE(builder, size) => {
+                DecimalColumn::DECIMAL_TYPE(builder.into(), size)
+            }
+        })
+    }
+
+    pub fn build_scalar(self) -> DecimalScalar {
+        assert_eq!(self.len(), 1);
+
+        crate::with_decimal_type!(|DECIMAL_TYPE| match self {
+            DecimalColumnBuilder::DECIMAL_TYPE(builder, size) => {
+                DecimalScalar::DECIMAL_TYPE(builder[0], size)
+            }
+        })
+    }
+
+    pub fn pop(&mut self) -> Option<DecimalScalar> {
+        crate::with_decimal_type!(|DECIMAL_TYPE| match self {
+            DecimalColumnBuilder::DECIMAL_TYPE(builder, size) => {
+                builder
+                    .pop()
+                    .map(|num| DecimalScalar::DECIMAL_TYPE(num, *size))
+            }
+        })
+    }
+}
+
+impl DecimalDomain {
+    pub fn size(&self) -> DecimalSize {
+        crate::with_decimal_type!(|DECIMAL_TYPE| match self {
+            DecimalDomain::DECIMAL_TYPE(_, size) => *size,
+        })
+    }
+}
+
+pub trait Decimal:
+    Debug
+    + Display
+    + Default
+    + Clone
+    + Copy
+    + PartialEq
+    + Eq
+    + PartialOrd
+    + Ord
+    + std::hash::Hash
+    + Sync
+    + Send
+    + 'static
+{
+    fn zero() -> Self;
+    fn one() -> Self;
+    /// Returns `10^n`.
+    fn e(n: u32) -> Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+    fn checked_neg(self) -> Option<Self>;
+
+    fn min_for_precision(precision: u8) -> Self;
+    fn max_for_precision(precision: u8) -> Self;
+
+    fn from_i128(value: i128) -> Self;
+    fn from_i256(value: i256) -> Option<Self>;
+    fn to_i256(self) -> i256;
+
+    fn from_u64(value: u64) -> Self {
+        Self::from_i128(value as i128)
+    }
+
+    fn from_i64(value: i64) -> Self {
+        Self::from_i128(value as i128)
+    }
+
+    /// Converts a float into the decimal with the given scale, returns `None` on overflow.
+    fn from_float(value: f64, size: DecimalSize) -> Option<Self>;
+    fn to_float64(self, scale: u8) -> f64;
+
+    fn data_type(size: DecimalSize) -> DecimalDataType;
+    fn try_downcast_scalar(scalar: &DecimalScalar) -> Option<Self>;
+    fn try_downcast_column(column: &DecimalColumn) -> Option<(Buffer<Self>, DecimalSize)>;
+    fn try_downcast_builder(builder: &mut DecimalColumnBuilder) -> Option<&mut Vec<Self>>;
+    fn try_downcast_domain(domain: &DecimalDomain) -> Option<SimpleDomain<Self>>;
+    fn upcast_scalar(scalar: Self, size: DecimalSize) -> Scalar;
+    fn upcast_column(col: Buffer<Self>, size: DecimalSize) -> Column;
+    fn upcast_domain(domain: SimpleDomain<Self>, size: DecimalSize) -> Domain;
+
+    /// Checks whether the value fits into the precision.
+    fn fits_precision(self, precision: u8) -> bool {
+        self >= Self::min_for_precision(precision) && self <= Self::max_for_precision(precision)
+    }
+
+    /// Changes the scale of the value, rounding half away from zero when the
+    /// scale is reduced. Returns `None` on overflow.
+    fn rescale(self, from_scale: u8, to_scale: u8) -> Option<Self> {
+        if from_scale == to_scale {
+            Some(self)
+        } else if from_scale < to_scale {
+            self.checked_mul(Self::e((to_scale - from_scale) as u32))
+        } else {
+            let factor = Self::e((from_scale - to_scale) as u32);
+            let half = factor.checked_div(Self::from_i128(2))?;
+            let rounded = if self >= Self::zero() {
+                self.checked_add(half)?
+            } else {
+                self.checked_sub(half)?
+            };
+            rounded.checked_div(factor)
+        }
+    }
+
+    /// Formats the value with `scale` digits after the decimal point.
+    fn display(self, scale: u8) -> String {
+        let digits = self.to_string();
+        if scale == 0 {
+            return digits;
+        }
+        let (sign, digits) = match digits.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", digits.as_str()),
+        };
+        let scale = scale as usize;
+        let digits = format!("{:0>width$}", digits, width = scale + 1);
+        let (int_part, frac_part) = digits.split_at(digits.len() - scale);
+        format!("{sign}{int_part}.{frac_part}")
+    }
+}
+
+impl Decimal for i128 {
+    fn zero() -> Self {
+        0
+    }
+
+    fn one() -> Self {
+        1
+    }
+
+    fn e(n: u32) -> Self {
+        10_i128.pow(n)
+    }
+
+    fn checked_add(self, rhs: Self) -> Option<Self> {
+        i128::checked_add(self, rhs)
+    }
+
+    fn checked_sub(self, rhs: Self) -> Option<Self> {
+        i128::checked_sub(self, rhs)
+    }
+
+    fn checked_mul(self, rhs: Self) -> Option<Self> {
+        i128::checked_mul(self, rhs)
+    }
+
+    fn checked_div(self, rhs: Self) -> Option<Self> {
+        i128::checked_div(self, rhs)
+    }
+
+    fn checked_neg(self) -> Option<Self> {
+        i128::checked_neg(self)
+    }
+
+    fn min_for_precision(precision: u8) -> Self {
+        -Self::max_for_precision(precision)
+    }
+
+    fn max_for_precision(precision: u8) -> Self {
+        10_i128.pow(precision as u32) - 1
+    }
+
+    fn from_i128(value: i128) -> Self {
+        value
+    }
+
+    fn from_i256(value: i256) -> Option<Self> {
+        if value >= i256::from(i128::MIN) && value <= i256::from(i128::MAX) {
+            Some(value.as_i128())
+        } else {
+            None
+        }
+    }
+
+    fn to_i256(self) -> i256 {
+        i256::from(self)
+    }
+
+    fn from_float(value: f64, size: DecimalSize) -> Option<Self> {
+        let value = (value * 10_f64.powi(size.scale as i32)).round();
+        if !value.is_finite() || value.abs() >= 10_f64.powi(size.precision as i32) {
+            return None;
+        }
+        Some(value as i128)
+    }
+
+    fn to_float64(self, scale: u8) -> f64 {
+        self as f64 / 10_f64.powi(scale as i32)
+    }
+
+    fn data_type(size: DecimalSize) -> DecimalDataType {
+        DecimalDataType::Decimal128(size)
+    }
+
+    fn try_downcast_scalar(scalar: &DecimalScalar) -> Option<Self> {
+        scalar.as_decimal128().map(|(num, _)| *num)
+    }
+
+    fn try_downcast_column(column: &DecimalColumn) -> Option<(Buffer<Self>, DecimalSize)> {
+        column
+            .as_decimal128()
+            .map(|(col, size)| (col.clone(), *size))
+    }
+
+    fn try_downcast_builder(builder: &mut DecimalColumnBuilder) -> Option<&mut Vec<Self>> {
+        builder.as_decimal128_mut().map(|(builder, _)| builder)
+    }
+
+    fn try_downcast_domain(domain: &DecimalDomain) -> Option<SimpleDomain<Self>> {
+        domain.as_decimal128().map(|(domain, _)| domain.clone())
+    }
+
+    fn upcast_scalar(scalar: Self, size: DecimalSize) -> Scalar {
+        Scalar::Decimal(DecimalScalar::Decimal128(scalar, size))
+    }
+
+    fn upcast_column(col: Buffer<Self>, size: DecimalSize) -> Column {
+        Column::Decimal(DecimalColumn::Decimal128(col, size))
+    }
+
+    fn upcast_domain(domain: SimpleDomain<Self>, size: DecimalSize) -> Domain {
+        Domain::Decimal(DecimalDomain::Decimal128(domain, size))
+    }
+}
+
+impl Decimal for i256 {
+    fn zero() -> Self {
+        i256::ZERO
+    }
+
+    fn one() -> Self {
+        i256::ONE
+    }
+
+    fn e(n: u32) -> Self {
+        i256::from(10_i128).pow(n)
+    }
+
+    fn checked_add(self, rhs: Self) -> Option<Self> {
+        i256::checked_add(self, rhs)
+    }
+
+    fn checked_sub(self, rhs: Self) -> Option<Self> {
+        i256::checked_sub(self, rhs)
+    }
+
+    fn checked_mul(self, rhs: Self) -> Option<Self> {
+        i256::checked_mul(self, rhs)
+    }
+
+    fn checked_div(self, rhs: Self) -> Option<Self> {
+        i256::checked_div(self, rhs)
+    }
+
+    fn checked_neg(self) -> Option<Self> {
+        i256::checked_neg(self)
+    }
+
+    fn min_for_precision(precision: u8) -> Self {
+        -Self::max_for_precision(precision)
+    }
+
+    fn max_for_precision(precision: u8) -> Self {
+        Self::e(precision as u32) - i256::ONE
+    }
+
+    fn from_i128(value: i128) -> Self {
+        i256::from(value)
+    }
+
+    fn from_i256(value: i256) -> Option<Self> {
+        Some(value)
+    }
+
+    fn to_i256(self) -> i256 {
+        self
+    }
+
+    fn from_float(value: f64, size: DecimalSize) -> Option<Self> {
+        let value = (value * 10_f64.powi(size.scale as i32)).round();
+        if !value.is_finite() || value.abs() >= 10_f64.powi(size.precision as i32) {
+            return None;
+        }
+        Some(value.as_i256())
+    }
+
+    fn to_float64(self, scale: u8) -> f64 {
+        self.as_f64() / 10_f64.powi(scale as i32)
+    }
+
+    fn data_type(size: DecimalSize) -> DecimalDataType {
+        DecimalDataType::Decimal256(size)
+    }
+
+    fn try_downcast_scalar(scalar: &DecimalScalar) -> Option<Self> {
+        scalar.as_decimal256().map(|(num, _)| *num)
+    }
+
+    fn try_downcast_column(column: &DecimalColumn) -> Option<(Buffer<Self>, DecimalSize)> {
+        column
+            .as_decimal256()
+            .map(|(col, size)| (col.clone(), *size))
+    }
+
+    fn try_downcast_builder(builder: &mut DecimalColumnBuilder) -> Option<&mut Vec<Self>> {
+        builder.as_decimal256_mut().map(|(builder, _)| builder)
+    }
+
+    fn try_downcast_domain(domain: &DecimalDomain) -> Option<SimpleDomain<Self>> {
+        domain.as_decimal256().map(|(domain, _)| domain.clone())
+    }
+
+    fn upcast_scalar(scalar: Self, size: DecimalSize) -> Scalar {
+        Scalar::Decimal(DecimalScalar::Decimal256(scalar, size))
+    }
+
+    fn upcast_column(col: Buffer<Self>, size: DecimalSize) -> Column {
+        Column::Decimal(DecimalColumn::Decimal256(col, size))
+    }
+
+    fn upcast_domain(domain: SimpleDomain<Self>, size: DecimalSize) -> Domain {
+        Domain::Decimal(DecimalDomain::Decimal256(domain, size))
+    }
+}
+
+/// Parses a decimal literal such as `-12.345` or `1.2e3` into a decimal with the given size.
+///
+/// Extra fractional digits are rounded half away from zero.
+pub fn read_decimal_with_size<T: Decimal>(buf: &[u8], size: DecimalSize) -> Result<T, String> {
+    let err = || {
+        format!(
+            "unable to cast '{}' to Decimal({}, {})",
+            String::from_utf8_lossy(buf),
+            size.precision,
+            size.scale
+        )
+    };
+    let overflow = || {
+        format!(
+            "Decimal overflow: '{}' does not fit into Decimal({}, {})",
+            String::from_utf8_lossy(buf),
+            size.precision,
+            size.scale
+        )
+    };
+
+    let text = std::str::from_utf8(buf).map_err(|_| err())?.trim();
+    let (negative, text) = match text.as_bytes().first() {
+        Some(b'-') => (true, &text[1..]),
+        Some(b'+') => (false, &text[1..]),
+        _ => (false, text),
+    };
+    let (mantissa, exponent) = match text.find(|c| c == 'e' || c == 'E') {
+        Some(pos) => {
+            let exponent = text[pos + 1..].parse::<i32>().map_err(|_| err())?;
+            (&text[..pos], exponent)
+        }
+        None => (text, 0),
+    };
+    let (int_part, frac_part) = match mantissa.find('.') {
+        Some(pos) => (&mantissa[..pos], &mantissa[pos + 1..]),
+        None => (mantissa, ""),
+    };
+    if int_part.is_empty() && frac_part.is_empty() {
+        return Err(err());
+    }
+
+    // The number of digits of `mantissa` that are after the decimal point,
+    // taking the exponent into account. Negative means the value must be scaled up.
+    let frac_digits = frac_part.len() as i32 - exponent;
+    let keep = (size.scale as i32 - frac_digits).min(0);
+
+    let ten = T::from_i128(10);
+    let mut value = T::zero();
+    let mut dropped = 0;
+    let mut round_up = false;
+    let total = int_part.len() + frac_part.len();
+    for (i, c) in int_part.bytes().chain(frac_part.bytes()).enumerate() {
+        if !c.is_ascii_digit() {
+            return Err(err());
+        }
+        // Digits that are smaller than the target scale are dropped,
+        // only the first dropped digit decides the rounding.
+        if (total - i) as i32 <= -keep {
+            if dropped == 0 {
+                round_up = c >= b'5';
+            }
+            dropped += 1;
+            continue;
+        }
+        let digit = T::from_i128((c - b'0') as i128);
+        value = value
+            .checked_mul(ten)
+            .and_then(|v| v.checked_add(digit))
+            .ok_or_else(overflow)?;
+    }
+    if round_up {
+        value = value.checked_add(T::one()).ok_or_else(overflow)?;
+    }
+    let scale_up = size.scale as i32 - frac_digits;
+    if scale_up > 0 {
+        value = value
+            .checked_mul(T::e(scale_up as u32))
+            .ok_or_else(overflow)?;
+    }
+    if negative {
+        value = value.checked_neg().ok_or_else(overflow)?;
+    }
+    if !value.fits_precision(size.precision) {
+        return Err(overflow());
+    }
+    Ok(value)
+}
+
+#[macro_export]
+macro_rules! with_decimal_type {
+    ( | $t:tt | $($tail:tt)* ) => {
+        match_template::match_template! {
+            $t = [Decimal128, Decimal256],
+            $($tail)*
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! with_decimal_mapped_type {
+    (| $t:tt | $($tail:tt)*) => {
+        match_template::match_template! {
+            $t = [
+                Decimal128 => i128, Decimal256 => $crate::types::decimal::i256
+            ],
+            $($tail)*
+        }
+    }
+}
@@ -23,10 +23,16 @@ use common_arrow::arrow::io::ipc::read::read_file_metadata;
 use common_arrow::arrow::io::ipc::read::FileReader;
 use common_arrow::arrow::io::ipc::write::FileWriter;
 use common_arrow::arrow::io::ipc::write::WriteOptions as IpcWriteOptions;
+use common_arrow::ArrayRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
 
 use crate::BlockEntry;
 use crate::Column;
 use crate::ColumnBuilder;
+use crate::DataBlock;
+use crate::DataField;
+use crate::DataSchema;
 use crate::TableDataType;
 use crate::Value;
 
@@ -93,6 +99,52 @@ pub fn deserialize_column(bytes: &[u8]) -> Option<Column> {
     Some(Column::from_arrow(col.as_ref(), &data_type))
 }
 
+/// Serialize the block into arrow IPC format, used to spill the block to local disk.
+pub fn serialize_block(block: DataBlock) -> Result<Vec<u8>> {
+    let block = block.convert_to_full();
+    let fields = block
+        .columns()
+        .iter()
+        .enumerate()
+        .map(|(idx, entry)| DataField::new(&idx.to_string(), entry.data_type.clone()))
+        .collect::<Vec<_>>();
+    let schema = DataSchema::new(fields);
+
+    let mut buffer = Vec::new();
+    let mut writer = FileWriter::new(
+        &mut buffer,
+        schema.to_arrow(),
+        None,
+        IpcWriteOptions::default(),
+    );
+    writer.start()?;
+    writer.write(
+        &common_arrow::arrow::chunk::Chunk::<ArrayRef>::try_from(block)?,
+        None,
+    )?;
+    writer.finish()?;
+    Ok(buffer)
+}
+
+/// Deserialize the block written by `serialize_block`.
+pub fn deserialize_block(bytes: &[u8]) -> Result<DataBlock> {
+    let mut cursor = Cursor::new(bytes);
+    let metadata = read_file_metadata(&mut cursor)?;
+    let fields = metadata
+        .schema
+        .fields
+        .iter()
+        .map(DataField::from)
+        .collect::<Vec<_>>();
+    let schema = DataSchema::new(fields);
+
+    let mut reader = FileReader::new(cursor, metadata, None, None);
+    match reader.next() {
+        Some(chunk) => DataBlock::from_arrow_chunk(&chunk?, &schema),
+        None => Err(ErrorCode::Internal("The serialized block is empty")),
+    }
+}
+
 /// Convert a column to a arrow array.
 pub fn column_to_arrow_array(column: &BlockEntry, num_rows: usize) -> Box<dyn Array> {
     match &column.value {
@@ -20,7 +20,7 @@ use comfy_table::Cell;
 use comfy_table::Table;
 use itertools::Itertools;
 use num_traits::FromPrimitive;
-use rust_decimal::Decimal;
+use rust_decimal::Decimal as RustDecimal;
 use rust_decimal::RoundingStrategy;
 
 use crate::block::DataBlock;
@@ -33,6 +33,11 @@ use crate::property::Domain;
 use crate::property::FunctionProperty;
 use crate::types::boolean::BooleanDomain;
 use crate::types::date::date_to_string;
+use crate::types::decimal::Decimal;
+use crate::types::decimal::DecimalColumn;
+use crate::types::decimal::DecimalDataType;
+use crate::types::decimal::DecimalDomain;
+use crate::types::decimal::DecimalScalar;
 use crate::types::nullable::NullableDomain;
 use crate::types::number::NumberColumn;
 use crate::types::number::NumberDataType;
@@ -49,6 +54,7 @@ use crate::values::Scalar;
 use crate::values::ScalarRef;
 use crate::values::Value;
 use crate::values::ValueRef;
+use crate::with_decimal_type;
 use crate::with_number_type;
 use crate::Column;
 use crate::ColumnIndex;
@@ -101,6 +107,7 @@ impl<'a> Debug for ScalarRef<'a> {
             ScalarRef::Null => write!(f, "NULL"),
             ScalarRef::EmptyArray => write!(f, "[] :: Array(Nothing)"),
             ScalarRef::Number(val) => write!(f, "{val:?}"),
+            ScalarRef::Decimal(val) => write!(f, "{val:?}"),
             ScalarRef::Boolean(val) => write!(f, "{val}"),
             ScalarRef::String(s) => write!(f, "{:?}", String::from_utf8_lossy(s)),
             ScalarRef::Timestamp(t) => write!(f, "{t:?}"),
@@ -130,6 +137,7 @@ impl Debug for Column {
             Column::Null { len } => f.debug_struct("Null").field("len", len).finish(),
             Column::EmptyArray { len } => f.debug_struct("EmptyArray").field("len", len).finish(),
             Column::Number(col) => write!(f, "{col:?}"),
+            Column::Decimal(col) => write!(f, "{col:?}"),
             Column::Boolean(col) => f.debug_tuple("Boolean").field(col).finish(),
             Column::String(col) => write!(f, "{col:?}"),
             Column::Timestamp(col) => write!(f, "{col:?}"),
@@ -152,6 +160,7 @@ impl<'a> Display for ScalarRef<'a> {
             ScalarRef::Null => write!(f, "NULL"),
             ScalarRef::EmptyArray => write!(f, "[]"),
             ScalarRef::Number(val) => write!(f, "{:?}", val),
+            ScalarRef::Decimal(val) => write!(f, "{val}"),
             ScalarRef::Boolean(val) => write!(f, "{val}"),
             ScalarRef::String(s) => write!(f, "{:?}", String::from_utf8_lossy(s)),
             ScalarRef::Timestamp(t) => write!(f, "{}", timestamp_to_string(*t, chrono_tz::Tz::UTC)),
@@ -184,6 +193,7 @@ impl Display for Scalar {
             Scalar::Null => write!(f, "NULL"),
             Scalar::EmptyArray => write!(f, "[]"),
             Scalar::Number(n) => write!(f, "{}", n),
+            Scalar::Decimal(d) => write!(f, "{}", d),
             Scalar::Boolean(b) => write!(f, "{}", b),
             Scalar::String(s) => match std::str::from_utf8(s) {
                 Ok(v) => write!(f, "{}", v),
@@ -232,7 +242,7 @@ impl Debug for NumberScalar {
             NumberScalar::Int16(val) => write!(f, "{val}_i16"),
             NumberScalar::Int32(val) => write!(f, "{val}_i32"),
             NumberScalar::Int64(val) => write!(f, "{val}_i64"),
-            NumberScalar::Float32(val) => match Decimal::from_f32(val.0) {
+            NumberScalar::Float32(val) => match RustDecimal::from_f32(val.0) {
                 Some(d) => write!(
                     f,
                     "{}_f32",
@@ -241,7 +251,7 @@ impl Debug for NumberScalar {
                 ),
                 None => write!(f, "{val}_f32"),
             },
-            NumberScalar::Float64(val) => match Decimal::from_f64(val.0) {
+            NumberScalar::Float64(val) => match RustDecimal::from_f64(val.0) {
                 Some(d) => write!(
                     f,
                     "{}_f64",
@@ -265,7 +275,7 @@ impl Display for NumberScalar {
             NumberScalar::Int16(val) => write!(f, "{val}"),
             NumberScalar::Int32(val) => write!(f, "{val}"),
             NumberScalar::Int64(val) => write!(f, "{val}"),
-            NumberScalar::Float32(val) => match Decimal::from_f32(val.0) {
+            NumberScalar::Float32(val) => match RustDecimal::from_f32(val.0) {
                 Some(d) => write!(
                     f,
                     "{}",
@@ -274,7 +284,7 @@ impl Display for NumberScalar {
                 ),
                 None => write!(f, "{val}"),
             },
-            NumberScalar::Float64(val) => match Decimal::from_f64(val.0) {
+            NumberScalar::Float64(val) => match RustDecimal::from_f64(val.0) {
                 Some(d) => write!(
                     f,
                     "{}",
@@ -303,7 +313,7 @@ impl Debug for NumberColumn {
                 .field(&format_args!(
                     "[{}]",
                     &val.iter()
-                        .map(|x| match Decimal::from_f32(x.0) {
+                        .map(|x| match RustDecimal::from_f32(x.0) {
                             Some(d) => d
                                 .round_dp_with_strategy(
                                     FLOAT_NUM_FRAC_DIGITS,
@@ -321,7 +331,7 @@ impl Debug for NumberColumn {
                 .field(&format_args!(
                     "[{}]",
                     &val.iter()
-                        .map(|x| match Decimal::from_f64(x.0) {
+                        .map(|x| match RustDecimal::from_f64(x.0) {
                             Some(d) => d
                                 .round_dp_with_strategy(
                                     FLOAT_NUM_FRAC_DIGITS,
@@ -338,6 +348,33 @@ impl Debug for NumberColumn {
     }
 }
 
+impl Debug for DecimalScalar {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecimalScalar::Decimal128(val, size) => {
+                write!(f, "{}_d128", val.display(size.scale))
+            }
+            DecimalScalar::Decimal256(val, size) => {
+                write!(f, "{}_d256", val.display(size.scale))
+            }
+        }
+    }
+}
+
+impl Debug for DecimalColumn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        with_decimal_type!(|DECIMAL_TYPE| match self {
+            DecimalColumn::DECIMAL_TYPE(val, size) => f
+                .debug_tuple(stringify!(DECIMAL_TYPE))
+                .field(&format_args!(
+                    "[{}]",
+                    &val.iter().map(|x| x.display(size.scale)).join(", ")
+                ))
+                .finish(),
+        })
+    }
+}
+
 impl Debug for StringColumn {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("StringColumn")
@@ -420,6 +457,7 @@ impl Display for DataType {
             DataType::Boolean => write!(f, "Boolean"),
             DataType::String => write!(f, "String"),
             DataType::Number(num) => write!(f, "{num}"),
+            DataType::Decimal(decimal) => write!(f, "{decimal}"),
             DataType::Timestamp => write!(f, "Timestamp"),
             DataType::Date => write!(f, "Date"),
             DataType::Null => write!(f, "NULL"),
@@ -452,6 +490,7 @@ impl Display for TableDataType {
             TableDataType::Boolean => write!(f, "Boolean"),
             TableDataType::String => write!(f, "String"),
             TableDataType::Number(num) => write!(f, "{num}"),
+            TableDataType::Decimal(decimal) => write!(f, "{decimal}"),
             TableDataType::Timestamp => write!(f, "Timestamp"),
             TableDataType::Date => write!(f, "Date"),
             TableDataType::Null => write!(f, "NULL"),
@@ -497,6 +536,13 @@ impl Display for NumberDataType {
     }
 }
 
+impl Display for DecimalDataType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        let size = self.size();
+        write!(f, "Decimal({}, {})", size.precision, size.scale)
+    }
+}
+
 impl<Index: ColumnIndex> Display for Expr<Index> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -651,6 +697,19 @@ impl Display for NumberDomain {
     }
 }
 
+impl Display for DecimalDomain {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        with_decimal_type!(|DECIMAL_TYPE| match self {
+            DecimalDomain::DECIMAL_TYPE(domain, size) => write!(
+                f,
+                "{{{}..={}}}",
+                domain.min.display(size.scale),
+                domain.max.display(size.scale)
+            ),
+        })
+    }
+}
+
 impl<T: Display> Display for SimpleDomain<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "{{{}..={}}}", self.min, self.max)
@@ -660,6 +719,7 @@ impl Display for Domain {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
             Domain::Number(domain) => write!(f, "{domain}"),
+            Domain::Decimal(domain) => write!(f, "{domain}"),
             Domain::Boolean(domain) => write!(f, "{domain}"),
             Domain::String(domain) => write!(f, "{domain}"),
             Domain::Timestamp(domain) => write!(f, "{domain}"),
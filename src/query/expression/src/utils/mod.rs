@@ -55,6 +55,7 @@ use crate::Value;
 pub fn eval_function(
     span: Span,
     fn_name: &str,
+    params: &[usize],
     args: impl IntoIterator<Item = (Value<AnyType>, DataType)>,
     func_ctx: FunctionContext,
     num_rows: usize,
@@ -80,7 +81,7 @@ pub fn eval_function(
     let raw_expr = RawExpr::FunctionCall {
         span,
         name: fn_name.to_string(),
-        params: vec![],
+        params: params.to_vec(),
         args,
     };
     let expr = crate::type_check::check(&raw_expr, fn_registry)?;
@@ -93,6 +94,7 @@ pub fn eval_function(
 pub fn calculate_function_domain(
     span: Span,
     fn_name: &str,
+    params: &[usize],
     args: impl IntoIterator<Item = (Domain, DataType)>,
     fn_ctx: FunctionContext,
     fn_registry: &FunctionRegistry,
@@ -114,7 +116,7 @@ pub fn calculate_function_domain(
     let raw_expr = RawExpr::FunctionCall {
         span,
         name: fn_name.to_string(),
-        params: vec![],
+        params: params.to_vec(),
         args,
     };
     let expr = crate::type_check::check(&raw_expr, fn_registry)?;
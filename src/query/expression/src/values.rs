@@ -36,6 +36,9 @@ use crate::property::Domain;
 use crate::types::array::ArrayColumn;
 use crate::types::array::ArrayColumnBuilder;
 use crate::types::boolean::BooleanDomain;
+use crate::types::decimal::DecimalColumn;
+use crate::types::decimal::DecimalColumnBuilder;
+use crate::types::decimal::DecimalScalar;
 use crate::types::nullable::NullableColumn;
 use crate::types::nullable::NullableColumnBuilder;
 use crate::types::nullable::NullableDomain;
@@ -56,6 +59,7 @@ use crate::utils::arrow::buffer_into_mut;
 use crate::utils::arrow::constant_bitmap;
 use crate::utils::arrow::deserialize_column;
 use crate::utils::arrow::serialize_column;
+use crate::with_decimal_type;
 use crate::with_integer_mapped_type;
 use crate::with_number_mapped_type;
 use crate::with_number_type;
@@ -78,6 +82,7 @@ pub enum Scalar {
     Null,
     EmptyArray,
     Number(NumberScalar),
+    Decimal(DecimalScalar),
     Timestamp(i64),
     Date(i32),
     Boolean(bool),
@@ -93,6 +98,7 @@ pub enum ScalarRef<'a> {
     Null,
     EmptyArray,
     Number(NumberScalar),
+    Decimal(DecimalScalar),
     Boolean(bool),
     String(&'a [u8]),
     Timestamp(i64),
@@ -107,6 +113,7 @@ pub enum Column {
     Null { len: usize },
     EmptyArray { len: usize },
     Number(NumberColumn),
+    Decimal(DecimalColumn),
     Boolean(Bitmap),
     String(StringColumn),
     Timestamp(Buffer<i64>),
@@ -126,6 +133,7 @@ pub enum ColumnBuilder {
         len: usize,
     },
     Number(NumberColumnBuilder),
+    Decimal(DecimalColumnBuilder),
     Boolean(MutableBitmap),
     String(StringColumnBuilder),
     Timestamp(Vec<i64>),
@@ -240,6 +248,7 @@ impl Scalar {
             Scalar::Null => ScalarRef::Null,
             Scalar::EmptyArray => ScalarRef::EmptyArray,
             Scalar::Number(n) => ScalarRef::Number(*n),
+            Scalar::Decimal(d) => ScalarRef::Decimal(*d),
             Scalar::Boolean(b) => ScalarRef::Boolean(*b),
             Scalar::String(s) => ScalarRef::String(s.as_slice()),
             Scalar::Timestamp(t) => ScalarRef::Timestamp(*t),
@@ -257,6 +266,7 @@ impl<'a> ScalarRef<'a> {
             ScalarRef::Null => Scalar::Null,
             ScalarRef::EmptyArray => Scalar::EmptyArray,
             ScalarRef::Number(n) => Scalar::Number(*n),
+            ScalarRef::Decimal(d) => Scalar::Decimal(*d),
             ScalarRef::Boolean(b) => Scalar::Boolean(*b),
             ScalarRef::String(s) => Scalar::String(s.to_vec()),
             ScalarRef::Timestamp(t) => Scalar::Timestamp(*t),
@@ -287,6 +297,7 @@ impl<'a> ScalarRef<'a> {
             }),
             ScalarRef::EmptyArray => Domain::Array(None),
             ScalarRef::Number(num) => Domain::Number(num.domain()),
+            ScalarRef::Decimal(d) => Domain::Decimal(d.domain()),
             ScalarRef::Boolean(true) => Domain::Boolean(BooleanDomain {
                 has_false: false,
                 has_true: true,
@@ -329,6 +340,8 @@ impl<'a> ScalarRef<'a> {
             ScalarRef::Number(NumberScalar::Int16(_)) => 2,
             ScalarRef::Number(NumberScalar::Int32(_)) => 4,
             ScalarRef::Number(NumberScalar::Int64(_)) => 8,
+            ScalarRef::Decimal(DecimalScalar::Decimal128(_, _)) => 16,
+            ScalarRef::Decimal(DecimalScalar::Decimal256(_, _)) => 32,
             ScalarRef::Boolean(_) => 1,
             ScalarRef::String(s) => s.len(),
             ScalarRef::Timestamp(_) => 8,
@@ -365,6 +378,7 @@ impl PartialOrd for Scalar {
             (Scalar::Null, Scalar::Null) => Some(Ordering::Equal),
             (Scalar::EmptyArray, Scalar::EmptyArray) => Some(Ordering::Equal),
             (Scalar::Number(n1), Scalar::Number(n2)) => n1.partial_cmp(n2),
+            (Scalar::Decimal(d1), Scalar::Decimal(d2)) => d1.partial_cmp(d2),
             (Scalar::Boolean(b1), Scalar::Boolean(b2)) => b1.partial_cmp(b2),
             (Scalar::String(s1), Scalar::String(s2)) => s1.partial_cmp(s2),
             (Scalar::Timestamp(t1), Scalar::Timestamp(t2)) => t1.partial_cmp(t2),
@@ -397,6 +411,7 @@ impl PartialOrd for ScalarRef<'_> {
             (ScalarRef::Null, ScalarRef::Null) => Some(Ordering::Equal),
             (ScalarRef::EmptyArray, ScalarRef::EmptyArray) => Some(Ordering::Equal),
             (ScalarRef::Number(n1), ScalarRef::Number(n2)) => n1.partial_cmp(n2),
+            (ScalarRef::Decimal(d1), ScalarRef::Decimal(d2)) => d1.partial_cmp(d2),
             (ScalarRef::Boolean(b1), ScalarRef::Boolean(b2)) => b1.partial_cmp(b2),
             (ScalarRef::String(s1), ScalarRef::String(s2)) => s1.partial_cmp(s2),
             (ScalarRef::Timestamp(t1), ScalarRef::Timestamp(t2)) => t1.partial_cmp(t2),
@@ -424,6 +439,11 @@ impl Hash for ScalarRef<'_> {
                     v.hash(state);
                 }
             }),
+            ScalarRef::Decimal(t) => with_decimal_type!(|DECIMAL_TYPE| match t {
+                DecimalScalar::DECIMAL_TYPE(v, _) => {
+                    v.hash(state);
+                }
+            }),
             ScalarRef::Boolean(v) => v.hash(state),
             ScalarRef::String(v) => v.hash(state),
             ScalarRef::Timestamp(v) => v.hash(state),
@@ -460,6 +480,13 @@ impl PartialOrd for Column {
                     _ => None,
                 })
             }
+            (Column::Decimal(col1), Column::Decimal(col2)) => {
+                with_decimal_type!(|DECIMAL_TYPE| match (col1, col2) {
+                    (DecimalColumn::DECIMAL_TYPE(c1, _), DecimalColumn::DECIMAL_TYPE(c2, _)) =>
+                        c1.iter().partial_cmp(c2.iter()),
+                    _ => None,
+                })
+            }
             (Column::Boolean(col1), Column::Boolean(col2)) => col1.iter().partial_cmp(col2.iter()),
             (Column::String(col1), Column::String(col2)) => col1.iter().partial_cmp(col2.iter()),
             (Column::Timestamp(col1), Column::Timestamp(col2)) => {
@@ -496,6 +523,7 @@ impl Column {
             Column::Null { len } => *len,
             Column::EmptyArray { len } => *len,
             Column::Number(col) => col.len(),
+            Column::Decimal(col) => col.len(),
             Column::Boolean(col) => col.len(),
             Column::String(col) => col.len(),
             Column::Timestamp(col) => col.len(),
@@ -512,6 +540,7 @@ impl Column {
             Column::Null { .. } => Some(ScalarRef::Null),
             Column::EmptyArray { .. } => Some(ScalarRef::EmptyArray),
             Column::Number(col) => Some(ScalarRef::Number(col.index(index)?)),
+            Column::Decimal(col) => Some(ScalarRef::Decimal(col.index(index)?)),
             Column::Boolean(col) => Some(ScalarRef::Boolean(col.get(index)?)),
             Column::String(col) => Some(ScalarRef::String(col.index(index)?)),
             Column::Timestamp(col) => Some(ScalarRef::Timestamp(col.get(index).cloned()?)),
@@ -535,6 +564,7 @@ impl Column {
             Column::Null { .. } => ScalarRef::Null,
             Column::EmptyArray { .. } => ScalarRef::EmptyArray,
             Column::Number(col) => ScalarRef::Number(col.index_unchecked(index)),
+            Column::Decimal(col) => ScalarRef::Decimal(col.index_unchecked(index)),
             Column::Boolean(col) => ScalarRef::Boolean(col.get_bit_unchecked(index)),
             Column::String(col) => ScalarRef::String(col.index_unchecked(index)),
             Column::Timestamp(col) => ScalarRef::Timestamp(*col.get_unchecked(index)),
@@ -574,6 +604,7 @@ impl Column {
                 len: range.end - range.start,
             },
             Column::Number(col) => Column::Number(col.slice(range)),
+            Column::Decimal(col) => Column::Decimal(col.slice(range)),
             Column::Boolean(col) => {
                 Column::Boolean(col.clone().slice(range.start, range.end - range.start))
             }
@@ -614,6 +645,7 @@ impl Column {
             }),
             Column::EmptyArray { .. } => Domain::Array(None),
             Column::Number(col) => Domain::Number(col.domain()),
+            Column::Decimal(col) => Domain::Decimal(col.domain()),
             Column::Boolean(col) => Domain::Boolean(BooleanDomain {
                 has_false: col.unset_bits() > 0,
                 has_true: col.len() - col.unset_bits() > 0,
@@ -665,6 +697,7 @@ impl Column {
             Column::Number(c) => with_number_type!(|NUM_TYPE| match c {
                 NumberColumn::NUM_TYPE(_) => DataType::Number(NumberDataType::NUM_TYPE),
             }),
+            Column::Decimal(c) => DataType::Decimal(c.data_type()),
             Column::Boolean(_) => DataType::Boolean,
             Column::String(_) => DataType::String,
             Column::Timestamp(_) => DataType::Timestamp,
@@ -787,6 +820,27 @@ impl Column {
                     .unwrap(),
                 )
             }
+            Column::Decimal(DecimalColumn::Decimal128(col, _)) => Box::new(
+                common_arrow::arrow::array::PrimitiveArray::<i128>::try_new(
+                    arrow_type,
+                    col.clone(),
+                    None,
+                )
+                .unwrap(),
+            ),
+            Column::Decimal(DecimalColumn::Decimal256(col, _)) => {
+                let values = unsafe {
+                    std::mem::transmute::<Buffer<i256>, Buffer<common_arrow::arrow::types::i256>>(
+                        col.clone(),
+                    )
+                };
+                Box::new(
+                    common_arrow::arrow::array::PrimitiveArray::<common_arrow::arrow::types::i256>::try_new(
+                        arrow_type, values, None,
+                    )
+                    .unwrap(),
+                )
+            }
             Column::Boolean(col) => Box::new(
                 common_arrow::arrow::array::BooleanArray::try_new(arrow_type, col.clone(), None)
                     .unwrap(),
@@ -965,6 +1019,35 @@ impl Column {
                 let col = unsafe { std::mem::transmute::<Buffer<f64>, Buffer<F64>>(col) };
                 Column::Number(NumberColumn::Float64(col))
             }
+            ArrowDataType::Decimal(precision, scale) => {
+                let values = arrow_col
+                    .as_any()
+                    .downcast_ref::<common_arrow::arrow::array::PrimitiveArray<i128>>()
+                    .expect("fail to read from arrow: array should be `PrimitiveArray<i128>`")
+                    .values()
+                    .clone();
+                Column::Decimal(DecimalColumn::Decimal128(values, DecimalSize {
+                    precision: *precision as u8,
+                    scale: *scale as u8,
+                }))
+            }
+            ArrowDataType::Decimal256(precision, scale) => {
+                let values = arrow_col
+                    .as_any()
+                    .downcast_ref::<common_arrow::arrow::array::PrimitiveArray<common_arrow::arrow::types::i256>>()
+                    .expect("fail to read from arrow: array should be `PrimitiveArray<i256>`")
+                    .values()
+                    .clone();
+                let values = unsafe {
+                    std::mem::transmute::<Buffer<common_arrow::arrow::types::i256>, Buffer<i256>>(
+                        values,
+                    )
+                };
+                Column::Decimal(DecimalColumn::Decimal256(values, DecimalSize {
+                    precision: *precision as u8,
+                    scale: *scale as u8,
+                }))
+            }
             ArrowDataType::Boolean => Column::Boolean(
                 arrow_col
                     .as_any()
@@ -1136,6 +1219,32 @@ impl Column {
                     offsets: offsets.into(),
                 }))
             }
+            ArrowDataType::Map(_, _) => {
+                let map_col = arrow_col
+                    .as_any()
+                    .downcast_ref::<common_arrow::arrow::array::MapArray>()
+                    .expect("fail to read from arrow: array should be `MapArray`");
+
+                // A map column is an array of (key, value) tuples with string keys.
+                let entry_type = match data_type {
+                    DataType::Map(value_type) => {
+                        DataType::Tuple(vec![DataType::String, (**value_type).clone()])
+                    }
+                    DataType::Array(entry_type) => (**entry_type).clone(),
+                    _ => unreachable!("map can not be converted to {data_type}"),
+                };
+                let values = Column::from_arrow(&**map_col.field(), &entry_type);
+                let offsets = map_col
+                    .offsets()
+                    .buffer()
+                    .iter()
+                    .map(|x| *x as u64)
+                    .collect::<Vec<_>>();
+                Column::Array(Box::new(ArrayColumn {
+                    values,
+                    offsets: offsets.into(),
+                }))
+            }
             ArrowDataType::Struct(_) => {
                 let struct_type = data_type.as_tuple().unwrap();
                 let arrow_col = arrow_col
@@ -1189,6 +1298,8 @@ impl Column {
             Column::Number(NumberColumn::Int16(_)) => self.len() * 2,
             Column::Number(NumberColumn::Int32(_)) => self.len() * 4,
             Column::Number(NumberColumn::Int64(_)) => self.len() * 8,
+            Column::Decimal(DecimalColumn::Decimal128(_, _)) => self.len() * 16,
+            Column::Decimal(DecimalColumn::Decimal256(_, _)) => self.len() * 32,
             Column::Boolean(c) => c.as_slice().0.len(),
             Column::String(col) => col.data.len() + col.offsets.len() * 8,
             Column::Timestamp(col) => col.len() * 8,
@@ -1255,6 +1366,7 @@ impl ColumnBuilder {
             Column::Null { len } => ColumnBuilder::Null { len },
             Column::EmptyArray { len } => ColumnBuilder::EmptyArray { len },
             Column::Number(col) => ColumnBuilder::Number(NumberColumnBuilder::from_column(col)),
+            Column::Decimal(col) => ColumnBuilder::Decimal(DecimalColumnBuilder::from_column(col)),
             Column::Boolean(col) => ColumnBuilder::Boolean(bitmap_into_mut(col)),
             Column::String(col) => ColumnBuilder::String(StringColumnBuilder::from_column(col)),
             Column::Timestamp(col) => ColumnBuilder::Timestamp(buffer_into_mut(col)),
@@ -1307,6 +1419,9 @@ impl ColumnBuilder {
             },
             ScalarRef::EmptyArray => ColumnBuilder::EmptyArray { len: n },
             ScalarRef::Number(num) => ColumnBuilder::Number(NumberColumnBuilder::repeat(*num, n)),
+            ScalarRef::Decimal(dec) => {
+                ColumnBuilder::Decimal(DecimalColumnBuilder::repeat(*dec, n))
+            }
             ScalarRef::Boolean(b) => ColumnBuilder::Boolean(constant_bitmap(*b, n)),
             ScalarRef::String(s) => ColumnBuilder::String(StringColumnBuilder::repeat(s, n)),
             ScalarRef::Timestamp(d) => ColumnBuilder::Timestamp(vec![*d; n]),
@@ -1337,6 +1452,7 @@ impl ColumnBuilder {
             ColumnBuilder::Null { len } => *len,
             ColumnBuilder::EmptyArray { len } => *len,
             ColumnBuilder::Number(col) => col.len(),
+            ColumnBuilder::Decimal(col) => col.len(),
             ColumnBuilder::Boolean(builder) => builder.len(),
             ColumnBuilder::String(builder) => builder.len(),
             ColumnBuilder::Timestamp(builder) => builder.len(),
@@ -1355,6 +1471,9 @@ impl ColumnBuilder {
             DataType::Number(num_ty) => {
                 ColumnBuilder::Number(NumberColumnBuilder::with_capacity(num_ty, capacity))
             }
+            DataType::Decimal(ty) => {
+                ColumnBuilder::Decimal(DecimalColumnBuilder::with_capacity(ty, capacity))
+            }
             DataType::Boolean => ColumnBuilder::Boolean(MutableBitmap::with_capacity(capacity)),
             DataType::String => {
                 ColumnBuilder::String(StringColumnBuilder::with_capacity(capacity, 0))
@@ -1401,6 +1520,7 @@ impl ColumnBuilder {
             (ColumnBuilder::Null { len }, ScalarRef::Null) => *len += 1,
             (ColumnBuilder::EmptyArray { len }, ScalarRef::EmptyArray) => *len += 1,
             (ColumnBuilder::Number(builder), ScalarRef::Number(value)) => builder.push(value),
+            (ColumnBuilder::Decimal(builder), ScalarRef::Decimal(value)) => builder.push(value),
             (ColumnBuilder::Boolean(builder), ScalarRef::Boolean(value)) => builder.push(value),
             (ColumnBuilder::String(builder), ScalarRef::String(value)) => {
                 builder.put_slice(value);
@@ -1439,6 +1559,7 @@ impl ColumnBuilder {
             ColumnBuilder::Null { len } => *len += 1,
             ColumnBuilder::EmptyArray { len } => *len += 1,
             ColumnBuilder::Number(builder) => builder.push_default(),
+            ColumnBuilder::Decimal(builder) => builder.push_default(),
             ColumnBuilder::Boolean(builder) => builder.push(false),
             ColumnBuilder::String(builder) => builder.commit_row(),
             ColumnBuilder::Timestamp(builder) => builder.push(0),
@@ -1469,6 +1590,9 @@ impl ColumnBuilder {
             (ColumnBuilder::Number(builder), Column::Number(column)) => {
                 builder.append_column(column);
             }
+            (ColumnBuilder::Decimal(builder), Column::Decimal(column)) => {
+                builder.append_column(column);
+            }
             (ColumnBuilder::Boolean(builder), Column::Boolean(other)) => {
                 append_bitmap(builder, other);
             }
@@ -1512,6 +1636,7 @@ impl ColumnBuilder {
             ColumnBuilder::Null { len } => Column::Null { len },
             ColumnBuilder::EmptyArray { len } => Column::EmptyArray { len },
             ColumnBuilder::Number(builder) => Column::Number(builder.build()),
+            ColumnBuilder::Decimal(builder) => Column::Decimal(builder.build()),
             ColumnBuilder::Boolean(builder) => Column::Boolean(builder.into()),
             ColumnBuilder::String(builder) => Column::String(builder.build()),
             ColumnBuilder::Timestamp(builder) => Column::Timestamp(builder.into()),
@@ -1532,6 +1657,7 @@ impl ColumnBuilder {
             ColumnBuilder::Null { .. } => Scalar::Null,
             ColumnBuilder::EmptyArray { .. } => Scalar::EmptyArray,
             ColumnBuilder::Number(builder) => Scalar::Number(builder.build_scalar()),
+            ColumnBuilder::Decimal(builder) => Scalar::Decimal(builder.build_scalar()),
             ColumnBuilder::Boolean(builder) => Scalar::Boolean(builder.get(0)),
             ColumnBuilder::String(builder) => Scalar::String(builder.build_scalar()),
             ColumnBuilder::Timestamp(builder) => Scalar::Timestamp(builder[0]),
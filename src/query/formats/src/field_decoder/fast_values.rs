@@ -21,12 +21,14 @@ use chrono_tz::Tz;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_expression::types::date::check_date;
+use common_expression::types::decimal::Decimal;
 use common_expression::types::number::Number;
 use common_expression::types::timestamp::check_timestamp;
 use common_expression::uniform_date;
 use common_expression::ArrayDeserializer;
 use common_expression::BooleanDeserializer;
 use common_expression::DateDeserializer;
+use common_expression::DecimalDeserializer;
 use common_expression::NullDeserializer;
 use common_expression::NullableDeserializer;
 use common_expression::NumberDeserializer;
@@ -118,6 +120,8 @@ impl FastFieldDecoderValues {
             TypeDeserializerImpl::UInt64(c) => self.read_int(c, reader),
             TypeDeserializerImpl::Float32(c) => self.read_float(c, reader),
             TypeDeserializerImpl::Float64(c) => self.read_float(c, reader),
+            TypeDeserializerImpl::Decimal128(c) => self.read_decimal(c, reader),
+            TypeDeserializerImpl::Decimal256(c) => self.read_decimal(c, reader),
             TypeDeserializerImpl::Date(c) => self.read_date(c, reader, positions),
             TypeDeserializerImpl::Timestamp(c) => self.read_timestamp(c, reader, positions),
             TypeDeserializerImpl::String(c) => self.read_string(c, reader, positions),
@@ -203,6 +207,14 @@ impl FastFieldDecoderValues {
         Ok(())
     }
 
+    fn read_decimal<T: Decimal, R: AsRef<[u8]>>(
+        &self,
+        column: &mut DecimalDeserializer<T>,
+        reader: &mut Cursor<R>,
+    ) -> Result<()> {
+        column.read_text(reader)
+    }
+
     fn read_string_inner<R: AsRef<[u8]>>(
         &self,
         reader: &mut Cursor<R>,
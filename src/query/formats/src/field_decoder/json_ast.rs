@@ -19,12 +19,14 @@ use chrono_tz::Tz;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_expression::types::date::check_date;
+use common_expression::types::decimal::Decimal;
 use common_expression::types::number::Number;
 use common_expression::types::timestamp::check_timestamp;
 use common_expression::uniform_date;
 use common_expression::ArrayDeserializer;
 use common_expression::BooleanDeserializer;
 use common_expression::DateDeserializer;
+use common_expression::DecimalDeserializer;
 use common_expression::NullDeserializer;
 use common_expression::NullableDeserializer;
 use common_expression::NumberDeserializer;
@@ -80,6 +82,8 @@ impl FieldJsonAstDecoder {
             TypeDeserializerImpl::UInt64(c) => self.read_int(c, value),
             TypeDeserializerImpl::Float32(c) => self.read_float(c, value),
             TypeDeserializerImpl::Float64(c) => self.read_float(c, value),
+            TypeDeserializerImpl::Decimal128(c) => self.read_decimal(c, value),
+            TypeDeserializerImpl::Decimal256(c) => self.read_decimal(c, value),
             TypeDeserializerImpl::Date(c) => self.read_date(c, value),
             TypeDeserializerImpl::Timestamp(c) => self.read_timestamp(c, value),
             TypeDeserializerImpl::String(c) => self.read_string(c, value),
@@ -135,6 +139,18 @@ impl FieldJsonAstDecoder {
         }
     }
 
+    fn read_decimal<T: Decimal>(
+        &self,
+        column: &mut DecimalDeserializer<T>,
+        value: &Value,
+    ) -> Result<()> {
+        match value {
+            Value::Number(v) => column.de_text(v.to_string().as_bytes()),
+            Value::String(v) => column.de_text(v.as_bytes()),
+            _ => Err(ErrorCode::BadBytes("Incorrect json value, must be decimal")),
+        }
+    }
+
     fn read_float<T, P>(&self, column: &mut NumberDeserializer<T, P>, value: &Value) -> Result<()>
     where
         T: Number + Unmarshal<T> + StatBuffer + From<P>,
@@ -18,12 +18,14 @@ use bstr::ByteSlice;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_expression::types::date::check_date;
+use common_expression::types::decimal::Decimal;
 use common_expression::types::number::Number;
 use common_expression::types::timestamp::check_timestamp;
 use common_expression::uniform_date;
 use common_expression::ArrayDeserializer;
 use common_expression::BooleanDeserializer;
 use common_expression::DateDeserializer;
+use common_expression::DecimalDeserializer;
 use common_expression::NullDeserializer;
 use common_expression::NullableDeserializer;
 use common_expression::NumberDeserializer;
@@ -80,6 +82,8 @@ pub trait FieldDecoderRowBased: FieldDecoder {
             TypeDeserializerImpl::UInt64(c) => self.read_int(c, reader, raw),
             TypeDeserializerImpl::Float32(c) => self.read_float(c, reader, raw),
             TypeDeserializerImpl::Float64(c) => self.read_float(c, reader, raw),
+            TypeDeserializerImpl::Decimal128(c) => self.read_decimal(c, reader, raw),
+            TypeDeserializerImpl::Decimal256(c) => self.read_decimal(c, reader, raw),
             TypeDeserializerImpl::Date(c) => self.read_date(c, reader, raw),
             TypeDeserializerImpl::Timestamp(c) => self.read_timestamp(c, reader, raw),
             TypeDeserializerImpl::String(c) => self.read_string(c, reader, raw),
@@ -178,6 +182,15 @@ pub trait FieldDecoderRowBased: FieldDecoder {
         Ok(())
     }
 
+    fn read_decimal<T: Decimal, R: AsRef<[u8]>>(
+        &self,
+        column: &mut DecimalDeserializer<T>,
+        reader: &mut Cursor<R>,
+        _raw: bool,
+    ) -> Result<()> {
+        column.read_text(reader)
+    }
+
     fn read_string<R: AsRef<[u8]>>(
         &self,
         column: &mut StringDeserializer,
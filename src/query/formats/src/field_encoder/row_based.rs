@@ -16,6 +16,7 @@ use common_arrow::arrow::bitmap::Bitmap;
 use common_arrow::arrow::buffer::Buffer;
 use common_expression::types::array::ArrayColumn;
 use common_expression::types::date::date_to_string;
+use common_expression::types::decimal::DecimalColumn;
 use common_expression::types::nullable::NullableColumn;
 use common_expression::types::number::NumberColumn;
 use common_expression::types::string::StringColumn;
@@ -50,6 +51,7 @@ pub trait FieldEncoderRowBased {
                 NumberColumn::Float32(c) => self.write_float(c, row_index, out_buf, raw),
                 NumberColumn::Float64(c) => self.write_float(c, row_index, out_buf, raw),
             },
+            Column::Decimal(c) => self.write_decimal(c, row_index, out_buf, raw),
             Column::Date(c) => self.write_date(c, row_index, out_buf, raw),
             Column::Timestamp(c) => self.write_timestamp(c, row_index, out_buf, raw),
             Column::String(c) => self.write_string(c, row_index, out_buf, raw),
@@ -126,6 +128,17 @@ pub trait FieldEncoderRowBased {
         v.0.write_field(out_buf, self.common_settings())
     }
 
+    fn write_decimal(
+        &self,
+        column: &DecimalColumn,
+        row_index: usize,
+        out_buf: &mut Vec<u8>,
+        _raw: bool,
+    ) {
+        let v = unsafe { column.index_unchecked(row_index) };
+        out_buf.extend_from_slice(v.to_string().as_bytes());
+    }
+
     fn write_string(
         &self,
         column: &StringColumn,
@@ -86,6 +86,8 @@ fn scalar_to_json(s: ScalarRef<'_>, format: &FormatSettings) -> JsonValue {
                 JsonValue::Number(serde_json::Number::from_f64(v.into()).unwrap())
             }
         },
+        // Decimals are written as strings to keep the precision.
+        ScalarRef::Decimal(x) => JsonValue::String(x.to_string()),
         ScalarRef::Date(v) => {
             let dt = DateConverter::to_date(&v, format.timezone);
             serde_json::to_value(dt.format("%Y-%m-%d").to_string()).unwrap()
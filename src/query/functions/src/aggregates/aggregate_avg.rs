@@ -20,6 +20,11 @@ use std::sync::Arc;
 use common_arrow::arrow::bitmap::Bitmap;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_expression::types::decimal::Decimal;
+use common_expression::types::decimal::DecimalDataType;
+use common_expression::types::decimal::DecimalSize;
+use common_expression::types::decimal::DecimalType;
+use common_expression::types::i256;
 use common_expression::types::number::Float64Type;
 use common_expression::types::number::Int8Type;
 use common_expression::types::number::Number;
@@ -196,6 +201,185 @@ where
     }
 }
 
+#[derive(Serialize, Deserialize)]
+struct AggregateDecimalAvgState<T: Decimal> {
+    #[serde(bound(deserialize = "T: DeserializeOwned"))]
+    pub value: T,
+    pub count: u64,
+}
+
+/// The average of decimals keeps 4 more digits after the decimal point than
+/// the argument, and the precision is widened to the max precision of the
+/// same physical type.
+#[derive(Clone)]
+pub struct AggregateDecimalAvgFunction<T> {
+    display_name: String,
+    arg_type: DecimalDataType,
+    return_type: DecimalDataType,
+    t: PhantomData<T>,
+}
+
+impl<T> AggregateDecimalAvgFunction<T>
+where T: Decimal + Serialize + DeserializeOwned
+{
+    pub fn try_create(
+        display_name: &str,
+        arguments: Vec<DataType>,
+    ) -> Result<AggregateFunctionRef> {
+        let arg_type = *arguments[0].as_decimal().unwrap();
+        let max_precision = arg_type.max_precision();
+        let return_type = DecimalDataType::from_size(DecimalSize {
+            precision: max_precision,
+            scale: (arg_type.scale() + 4).min(max_precision),
+        })
+        .map_err(ErrorCode::BadArguments)?;
+        Ok(Arc::new(Self {
+            display_name: display_name.to_string(),
+            arg_type,
+            return_type,
+            t: PhantomData,
+        }))
+    }
+
+    fn overflow(&self) -> ErrorCode {
+        ErrorCode::Overflow(format!(
+            "Decimal overflow in {}, the sum does not fit into {}",
+            self.display_name,
+            DataType::Decimal(self.return_type)
+        ))
+    }
+
+    fn add(&self, state: &mut AggregateDecimalAvgState<T>, value: T, count: u64) -> Result<()> {
+        state.value = state
+            .value
+            .checked_add(value)
+            .filter(|value| value.fits_precision(self.return_type.precision()))
+            .ok_or_else(|| self.overflow())?;
+        state.count += count;
+        Ok(())
+    }
+}
+
+impl<T> AggregateFunction for AggregateDecimalAvgFunction<T>
+where T: Decimal + Serialize + DeserializeOwned
+{
+    fn name(&self) -> &str {
+        "AggregateDecimalAvgFunction"
+    }
+
+    fn return_type(&self) -> Result<DataType> {
+        Ok(DataType::Decimal(self.return_type))
+    }
+
+    fn init_state(&self, place: StateAddr) {
+        place.write(|| AggregateDecimalAvgState::<T> {
+            value: T::zero(),
+            count: 0,
+        });
+    }
+
+    fn state_layout(&self) -> Layout {
+        Layout::new::<AggregateDecimalAvgState<T>>()
+    }
+
+    fn accumulate(
+        &self,
+        place: StateAddr,
+        columns: &[Column],
+        validity: Option<&Bitmap>,
+        _input_rows: usize,
+    ) -> Result<()> {
+        let state = place.get::<AggregateDecimalAvgState<T>>();
+        let column = DecimalType::<T>::try_downcast_column(&columns[0]).unwrap();
+        match validity {
+            Some(validity) => {
+                for (value, valid) in column.iter().zip(validity.iter()) {
+                    if valid {
+                        self.add(state, *value, 1)?;
+                    }
+                }
+            }
+            None => {
+                for value in column.iter() {
+                    self.add(state, *value, 1)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn accumulate_keys(
+        &self,
+        places: &[StateAddr],
+        offset: usize,
+        columns: &[Column],
+        _input_rows: usize,
+    ) -> Result<()> {
+        let column = DecimalType::<T>::try_downcast_column(&columns[0]).unwrap();
+        for (value, place) in column.iter().zip(places.iter()) {
+            let state = place.next(offset).get::<AggregateDecimalAvgState<T>>();
+            self.add(state, *value, 1)?;
+        }
+        Ok(())
+    }
+
+    fn accumulate_row(&self, place: StateAddr, columns: &[Column], row: usize) -> Result<()> {
+        let column = DecimalType::<T>::try_downcast_column(&columns[0]).unwrap();
+        let state = place.get::<AggregateDecimalAvgState<T>>();
+        self.add(state, column[row], 1)
+    }
+
+    fn serialize(&self, place: StateAddr, writer: &mut Vec<u8>) -> Result<()> {
+        let state = place.get::<AggregateDecimalAvgState<T>>();
+        serialize_into_buf(writer, state)
+    }
+
+    fn deserialize(&self, place: StateAddr, reader: &mut &[u8]) -> Result<()> {
+        let state = place.get::<AggregateDecimalAvgState<T>>();
+        *state = deserialize_from_slice(reader)?;
+        Ok(())
+    }
+
+    fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let rhs = rhs.get::<AggregateDecimalAvgState<T>>();
+        let state = place.get::<AggregateDecimalAvgState<T>>();
+        self.add(state, rhs.value, rhs.count)
+    }
+
+    fn merge_result(&self, place: StateAddr, builder: &mut ColumnBuilder) -> Result<()> {
+        let state = place.get::<AggregateDecimalAvgState<T>>();
+        let builder = DecimalType::<T>::try_downcast_builder(builder).unwrap();
+        if state.count == 0 {
+            builder.push(T::zero());
+            return Ok(());
+        }
+        let avg = state
+            .value
+            .to_i256()
+            .rescale(self.arg_type.scale(), self.return_type.scale())
+            .and_then(|sum| {
+                let count = i256::from(state.count);
+                // Round half away from zero.
+                let half = if sum >= i256::ZERO {
+                    count / 2
+                } else {
+                    -count / 2
+                };
+                sum.checked_add(half)?.checked_div(count)
+            })
+            .and_then(T::from_i256)
+            .ok_or_else(|| self.overflow())?;
+        builder.push(avg);
+        Ok(())
+    }
+}
+
+impl<T> fmt::Display for AggregateDecimalAvgFunction<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
+
 pub fn try_create_aggregate_avg_function(
     display_name: &str,
     _params: Vec<Scalar>,
@@ -216,6 +400,12 @@ pub fn try_create_aggregate_avg_function(
                 arguments,
             )
         }
+        DataType::Decimal(DecimalDataType::Decimal128(_)) => {
+            AggregateDecimalAvgFunction::<i128>::try_create(display_name, arguments)
+        }
+        DataType::Decimal(DecimalDataType::Decimal256(_)) => {
+            AggregateDecimalAvgFunction::<i256>::try_create(display_name, arguments)
+        }
         _ => Err(ErrorCode::BadDataValueType(format!(
             "AggregateAvgFunction does not support type '{:?}'",
             arguments[0]
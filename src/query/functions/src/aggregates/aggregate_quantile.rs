@@ -0,0 +1,391 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::alloc::Layout;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use common_arrow::arrow::bitmap::Bitmap;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::types::number::Number;
+use common_expression::types::number::NumberScalar;
+use common_expression::types::number::F64;
+use common_expression::types::DataType;
+use common_expression::types::NumberDataType;
+use common_expression::types::NumberType;
+use common_expression::types::ValueType;
+use common_expression::with_number_mapped_type;
+use common_expression::Column;
+use common_expression::ColumnBuilder;
+use common_expression::Scalar;
+use common_io::prelude::*;
+use num_traits::AsPrimitive;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::StateAddr;
+use crate::aggregates::aggregate_function_factory::AggregateFunctionDescription;
+use crate::aggregates::aggregator_common::assert_unary_arguments;
+use crate::aggregates::aggregator_common::assert_unary_params;
+use crate::aggregates::AggregateFunction;
+use crate::aggregates::AggregateFunctionRef;
+
+/// Keeps all the aggregated values, so the memory used by a group grows linearly with
+/// its number of values. `quantile_tdigest` computes an approximate quantile in bounded
+/// memory for the large data sets.
+#[derive(Serialize, Deserialize)]
+struct AggregateQuantileState<T> {
+    #[serde(bound(deserialize = "T: DeserializeOwned"))]
+    pub values: Vec<T>,
+}
+
+impl<T> AggregateQuantileState<T>
+where T: Number + AsPrimitive<f64>
+{
+    #[inline(always)]
+    fn add(&mut self, value: T) {
+        self.values.push(value);
+    }
+
+    #[inline(always)]
+    fn merge(&mut self, other: &Self) {
+        self.values.extend_from_slice(&other.values);
+    }
+
+    /// Linear interpolation between the two closest ranks, the same way as
+    /// `percentile_cont` does. The values must not be empty.
+    fn interpolated(&mut self, level: f64) -> f64 {
+        self.values.sort_unstable();
+
+        let pos = level * (self.values.len() - 1) as f64;
+        let lower = pos.floor() as usize;
+        let upper = pos.ceil() as usize;
+        let lower_value: f64 = self.values[lower].as_();
+        let upper_value: f64 = self.values[upper].as_();
+        lower_value + (upper_value - lower_value) * (pos - lower as f64)
+    }
+
+    /// Returns one of the input values without interpolation. The values must not be empty.
+    fn exact(&mut self, level: f64) -> T {
+        let len = self.values.len();
+        let n = if level < 1.0 {
+            (level * len as f64) as usize
+        } else {
+            len - 1
+        };
+        *self.values.select_nth_unstable(n).1
+    }
+}
+
+#[derive(Clone)]
+pub struct AggregateQuantileFunction<T> {
+    display_name: String,
+    arguments: Vec<DataType>,
+    levels: Vec<f64>,
+    return_array: bool,
+    exact: bool,
+    t: PhantomData<T>,
+}
+
+impl<T> AggregateFunction for AggregateQuantileFunction<T>
+where T: Number + AsPrimitive<f64> + Serialize + DeserializeOwned
+{
+    fn name(&self) -> &str {
+        "AggregateQuantileFunction"
+    }
+
+    fn return_type(&self) -> Result<DataType> {
+        if self.exact {
+            Ok(self.arguments[0].clone())
+        } else if self.return_array {
+            Ok(DataType::Array(Box::new(DataType::Number(
+                NumberDataType::Float64,
+            ))))
+        } else {
+            Ok(DataType::Number(NumberDataType::Float64))
+        }
+    }
+
+    fn init_state(&self, place: StateAddr) {
+        place.write(|| AggregateQuantileState::<T> { values: vec![] });
+    }
+
+    fn state_layout(&self) -> Layout {
+        Layout::new::<AggregateQuantileState<T>>()
+    }
+
+    fn accumulate(
+        &self,
+        place: StateAddr,
+        columns: &[Column],
+        validity: Option<&Bitmap>,
+        _input_rows: usize,
+    ) -> Result<()> {
+        let state = place.get::<AggregateQuantileState<T>>();
+        let column = NumberType::<T>::try_downcast_column(&columns[0]).unwrap();
+        match validity {
+            Some(bitmap) => {
+                for (value, is_valid) in column.iter().zip(bitmap.iter()) {
+                    if is_valid {
+                        state.add(*value);
+                    }
+                }
+            }
+            None => {
+                for value in column.iter() {
+                    state.add(*value);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn accumulate_keys(
+        &self,
+        places: &[StateAddr],
+        offset: usize,
+        columns: &[Column],
+        _input_rows: usize,
+    ) -> Result<()> {
+        let column = NumberType::<T>::try_downcast_column(&columns[0]).unwrap();
+
+        column.iter().zip(places.iter()).for_each(|(value, place)| {
+            let place = place.next(offset);
+            let state = place.get::<AggregateQuantileState<T>>();
+            state.add(*value);
+        });
+        Ok(())
+    }
+
+    fn accumulate_row(&self, place: StateAddr, columns: &[Column], row: usize) -> Result<()> {
+        let column = NumberType::<T>::try_downcast_column(&columns[0]).unwrap();
+
+        let state = place.get::<AggregateQuantileState<T>>();
+        state.add(column[row]);
+        Ok(())
+    }
+
+    fn serialize(&self, place: StateAddr, writer: &mut Vec<u8>) -> Result<()> {
+        let state = place.get::<AggregateQuantileState<T>>();
+        serialize_into_buf(writer, state)
+    }
+
+    fn deserialize(&self, place: StateAddr, reader: &mut &[u8]) -> Result<()> {
+        let state = place.get::<AggregateQuantileState<T>>();
+        *state = deserialize_from_slice(reader)?;
+
+        Ok(())
+    }
+
+    fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let state = place.get::<AggregateQuantileState<T>>();
+        let rhs = rhs.get::<AggregateQuantileState<T>>();
+        state.merge(rhs);
+        Ok(())
+    }
+
+    fn merge_result(&self, place: StateAddr, builder: &mut ColumnBuilder) -> Result<()> {
+        let state = place.get::<AggregateQuantileState<T>>();
+        // Like the other aggregate functions, the default value is returned if no values are
+        // aggregated, and turned into NULL by the `OrNull` adaptor.
+        if state.values.is_empty() {
+            builder.push_default();
+            return Ok(());
+        }
+
+        if self.exact {
+            let builder = NumberType::<T>::try_downcast_builder(builder).unwrap();
+            builder.push(state.exact(self.levels[0]));
+        } else if self.return_array {
+            let builder = builder.as_array_mut().unwrap();
+            let inner = builder
+                .builder
+                .as_number_mut()
+                .unwrap()
+                .as_float64_mut()
+                .unwrap();
+
+            inner.reserve(self.levels.len());
+            for level in self.levels.iter() {
+                inner.push(state.interpolated(*level).into());
+            }
+            builder.offsets.push(builder.builder.len() as u64);
+        } else {
+            let builder = NumberType::<F64>::try_downcast_builder(builder).unwrap();
+            builder.push(state.interpolated(self.levels[0]).into());
+        }
+        Ok(())
+    }
+
+    fn need_manual_drop_state(&self) -> bool {
+        true
+    }
+
+    unsafe fn drop_state(&self, place: StateAddr) {
+        let state = place.get::<AggregateQuantileState<T>>();
+        std::ptr::drop_in_place(state);
+    }
+}
+
+impl<T> fmt::Display for AggregateQuantileFunction<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
+
+impl<T> AggregateQuantileFunction<T>
+where T: Number + AsPrimitive<f64> + Serialize + DeserializeOwned
+{
+    pub fn try_create(
+        display_name: &str,
+        arguments: Vec<DataType>,
+        levels: Vec<f64>,
+        return_array: bool,
+        exact: bool,
+    ) -> Result<AggregateFunctionRef> {
+        Ok(Arc::new(Self {
+            display_name: display_name.to_string(),
+            arguments,
+            levels,
+            return_array,
+            exact,
+            t: PhantomData,
+        }))
+    }
+}
+
+/// Parses the quantile levels from the parameters, `0.5` is used if none is given.
+pub(crate) fn get_levels(display_name: &str, params: &[Scalar]) -> Result<Vec<f64>> {
+    if params.is_empty() {
+        return Ok(vec![0.5]);
+    }
+
+    params
+        .iter()
+        .map(|param| {
+            let level: f64 = match param {
+                Scalar::Number(number) => with_number_mapped_type!(|NUM_TYPE| match number {
+                    NumberScalar::NUM_TYPE(v) => v.as_(),
+                }),
+                _ => {
+                    return Err(ErrorCode::BadArguments(format!(
+                        "{} level must be a number, but got {:?}",
+                        display_name, param
+                    )));
+                }
+            };
+
+            if !(0.0..=1.0).contains(&level) {
+                return Err(ErrorCode::BadArguments(format!(
+                    "{} level must be between 0 and 1, but got {}",
+                    display_name, level
+                )));
+            }
+            Ok(level)
+        })
+        .collect()
+}
+
+fn create_quantile_function(
+    display_name: &str,
+    arguments: Vec<DataType>,
+    levels: Vec<f64>,
+    return_array: bool,
+    exact: bool,
+) -> Result<AggregateFunctionRef> {
+    assert_unary_arguments(display_name, arguments.len())?;
+    with_number_mapped_type!(|NUM_TYPE| match &arguments[0] {
+        DataType::Number(NumberDataType::NUM_TYPE) => {
+            AggregateQuantileFunction::<NUM_TYPE>::try_create(
+                display_name,
+                arguments,
+                levels,
+                return_array,
+                exact,
+            )
+        }
+        _ => Err(ErrorCode::BadDataValueType(format!(
+            "AggregateQuantileFunction does not support type '{:?}'",
+            arguments[0]
+        ))),
+    })
+}
+
+pub fn try_create_aggregate_median_function(
+    display_name: &str,
+    params: Vec<Scalar>,
+    arguments: Vec<DataType>,
+) -> Result<AggregateFunctionRef> {
+    if !params.is_empty() {
+        return Err(ErrorCode::NumberArgumentsNotMatch(format!(
+            "{} expect to have no parameters, but got {}",
+            display_name,
+            params.len()
+        )));
+    }
+    create_quantile_function(display_name, arguments, vec![0.5], false, false)
+}
+
+pub fn try_create_aggregate_quantile_function(
+    display_name: &str,
+    params: Vec<Scalar>,
+    arguments: Vec<DataType>,
+) -> Result<AggregateFunctionRef> {
+    if !params.is_empty() {
+        assert_unary_params(display_name, params.len())?;
+    }
+    let levels = get_levels(display_name, &params)?;
+    create_quantile_function(display_name, arguments, levels, false, false)
+}
+
+pub fn try_create_aggregate_quantiles_function(
+    display_name: &str,
+    params: Vec<Scalar>,
+    arguments: Vec<DataType>,
+) -> Result<AggregateFunctionRef> {
+    let levels = get_levels(display_name, &params)?;
+    create_quantile_function(display_name, arguments, levels, true, false)
+}
+
+pub fn try_create_aggregate_quantile_exact_function(
+    display_name: &str,
+    params: Vec<Scalar>,
+    arguments: Vec<DataType>,
+) -> Result<AggregateFunctionRef> {
+    if !params.is_empty() {
+        assert_unary_params(display_name, params.len())?;
+    }
+    let levels = get_levels(display_name, &params)?;
+    create_quantile_function(display_name, arguments, levels, false, true)
+}
+
+pub fn aggregate_median_function_desc() -> AggregateFunctionDescription {
+    AggregateFunctionDescription::creator(Box::new(try_create_aggregate_median_function))
+}
+
+pub fn aggregate_quantile_function_desc() -> AggregateFunctionDescription {
+    AggregateFunctionDescription::creator(Box::new(try_create_aggregate_quantile_function))
+}
+
+pub fn aggregate_quantiles_function_desc() -> AggregateFunctionDescription {
+    AggregateFunctionDescription::creator(Box::new(try_create_aggregate_quantiles_function))
+}
+
+pub fn aggregate_quantile_exact_function_desc() -> AggregateFunctionDescription {
+    AggregateFunctionDescription::creator(Box::new(try_create_aggregate_quantile_exact_function))
+}
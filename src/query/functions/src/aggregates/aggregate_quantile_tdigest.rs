@@ -0,0 +1,360 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::alloc::Layout;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use common_arrow::arrow::bitmap::Bitmap;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::types::number::Number;
+use common_expression::types::number::F64;
+use common_expression::types::DataType;
+use common_expression::types::NumberDataType;
+use common_expression::types::NumberType;
+use common_expression::types::ValueType;
+use common_expression::with_number_mapped_type;
+use common_expression::Column;
+use common_expression::ColumnBuilder;
+use common_expression::Scalar;
+use common_io::prelude::*;
+use num_traits::AsPrimitive;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::StateAddr;
+use crate::aggregates::aggregate_function_factory::AggregateFunctionDescription;
+use crate::aggregates::aggregate_quantile::get_levels;
+use crate::aggregates::aggregator_common::assert_unary_arguments;
+use crate::aggregates::aggregator_common::assert_unary_params;
+use crate::aggregates::AggregateFunction;
+use crate::aggregates::AggregateFunctionRef;
+
+/// The bigger the compression is, the more centroids are kept and the more accurate the result is.
+const TDIGEST_COMPRESSION: f64 = 100.0;
+/// Compress the digest once so many centroids are accumulated.
+const TDIGEST_BUFFER_SIZE: usize = 1024;
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+/// A merging t-digest, see https://github.com/tdunning/t-digest.
+///
+/// New values are appended as singleton centroids and merged into the sorted
+/// centroids in batches, so two digests can be merged by concatenating their
+/// centroids and compressing again.
+#[derive(Serialize, Deserialize)]
+struct AggregateQuantileTDigestState {
+    centroids: Vec<Centroid>,
+    unmerged: usize,
+    total: f64,
+    min: f64,
+    max: f64,
+}
+
+impl AggregateQuantileTDigestState {
+    fn new() -> Self {
+        Self {
+            centroids: vec![],
+            unmerged: 0,
+            total: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    #[inline(always)]
+    fn add(&mut self, value: f64) {
+        if value.is_nan() {
+            return;
+        }
+
+        self.centroids.push(Centroid {
+            mean: value,
+            weight: 1.0,
+        });
+        self.unmerged += 1;
+        self.total += 1.0;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+
+        if self.unmerged > TDIGEST_BUFFER_SIZE {
+            self.compress();
+        }
+    }
+
+    fn merge(&mut self, other: &Self) {
+        if other.centroids.is_empty() {
+            return;
+        }
+
+        self.centroids.extend_from_slice(&other.centroids);
+        self.unmerged += other.centroids.len();
+        self.total += other.total;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+
+        if self.unmerged > TDIGEST_BUFFER_SIZE {
+            self.compress();
+        }
+    }
+
+    /// Sorts the centroids and merges the neighbours as long as the merged
+    /// centroid stays under the size limit of its quantile.
+    fn compress(&mut self) {
+        if self.unmerged == 0 {
+            return;
+        }
+        self.unmerged = 0;
+        if self.centroids.len() <= 1 {
+            return;
+        }
+
+        self.centroids
+            .sort_unstable_by(|a, b| a.mean.total_cmp(&b.mean));
+
+        let mut merged = Vec::with_capacity(self.centroids.len());
+        let mut current = self.centroids[0];
+        let mut weight_so_far = 0.0;
+        for centroid in self.centroids[1..].iter() {
+            let q0 = weight_so_far / self.total;
+            let q2 = (weight_so_far + current.weight + centroid.weight) / self.total;
+            let limit =
+                4.0 * self.total * f64::min(q0 * (1.0 - q0), q2 * (1.0 - q2)) / TDIGEST_COMPRESSION;
+
+            if current.weight + centroid.weight <= limit {
+                let weight = current.weight + centroid.weight;
+                current.mean += (centroid.mean - current.mean) * centroid.weight / weight;
+                current.weight = weight;
+            } else {
+                weight_so_far += current.weight;
+                merged.push(current);
+                current = *centroid;
+            }
+        }
+        merged.push(current);
+        self.centroids = merged;
+    }
+
+    /// Interpolates between the centers of the neighbouring centroids, the
+    /// minimum and maximum values are used as the outer bounds. The digest must not be empty.
+    fn quantile(&mut self, level: f64) -> f64 {
+        self.compress();
+        if self.centroids.len() == 1 {
+            return self.centroids[0].mean;
+        }
+
+        let x = level * self.total;
+        let first = self.centroids[0];
+        if x < first.weight / 2.0 {
+            return self.min + (first.mean - self.min) * x / (first.weight / 2.0);
+        }
+        let last = self.centroids[self.centroids.len() - 1];
+        let last_x = self.total - last.weight / 2.0;
+        if x > last_x {
+            return last.mean + (self.max - last.mean) * (x - last_x) / (last.weight / 2.0);
+        }
+
+        let mut prev_x = first.weight / 2.0;
+        let mut prev_mean = first.mean;
+        let mut weight_so_far = first.weight;
+        for centroid in self.centroids[1..].iter() {
+            let current_x = weight_so_far + centroid.weight / 2.0;
+            if current_x >= x {
+                return prev_mean
+                    + (centroid.mean - prev_mean) * (x - prev_x) / (current_x - prev_x);
+            }
+            weight_so_far += centroid.weight;
+            prev_x = current_x;
+            prev_mean = centroid.mean;
+        }
+        last.mean
+    }
+}
+
+#[derive(Clone)]
+pub struct AggregateQuantileTDigestFunction<T> {
+    display_name: String,
+    _arguments: Vec<DataType>,
+    level: f64,
+    t: PhantomData<T>,
+}
+
+impl<T> AggregateFunction for AggregateQuantileTDigestFunction<T>
+where T: Number + AsPrimitive<f64>
+{
+    fn name(&self) -> &str {
+        "AggregateQuantileTDigestFunction"
+    }
+
+    fn return_type(&self) -> Result<DataType> {
+        Ok(DataType::Number(NumberDataType::Float64))
+    }
+
+    fn init_state(&self, place: StateAddr) {
+        place.write(AggregateQuantileTDigestState::new);
+    }
+
+    fn state_layout(&self) -> Layout {
+        Layout::new::<AggregateQuantileTDigestState>()
+    }
+
+    fn accumulate(
+        &self,
+        place: StateAddr,
+        columns: &[Column],
+        validity: Option<&Bitmap>,
+        _input_rows: usize,
+    ) -> Result<()> {
+        let state = place.get::<AggregateQuantileTDigestState>();
+        let column = NumberType::<T>::try_downcast_column(&columns[0]).unwrap();
+        match validity {
+            Some(bitmap) => {
+                for (value, is_valid) in column.iter().zip(bitmap.iter()) {
+                    if is_valid {
+                        state.add(value.as_());
+                    }
+                }
+            }
+            None => {
+                for value in column.iter() {
+                    state.add(value.as_());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn accumulate_keys(
+        &self,
+        places: &[StateAddr],
+        offset: usize,
+        columns: &[Column],
+        _input_rows: usize,
+    ) -> Result<()> {
+        let column = NumberType::<T>::try_downcast_column(&columns[0]).unwrap();
+
+        column.iter().zip(places.iter()).for_each(|(value, place)| {
+            let place = place.next(offset);
+            let state = place.get::<AggregateQuantileTDigestState>();
+            state.add(value.as_());
+        });
+        Ok(())
+    }
+
+    fn accumulate_row(&self, place: StateAddr, columns: &[Column], row: usize) -> Result<()> {
+        let column = NumberType::<T>::try_downcast_column(&columns[0]).unwrap();
+
+        let state = place.get::<AggregateQuantileTDigestState>();
+        state.add(column[row].as_());
+        Ok(())
+    }
+
+    fn serialize(&self, place: StateAddr, writer: &mut Vec<u8>) -> Result<()> {
+        let state = place.get::<AggregateQuantileTDigestState>();
+        state.compress();
+        serialize_into_buf(writer, state)
+    }
+
+    fn deserialize(&self, place: StateAddr, reader: &mut &[u8]) -> Result<()> {
+        let state = place.get::<AggregateQuantileTDigestState>();
+        *state = deserialize_from_slice(reader)?;
+
+        Ok(())
+    }
+
+    fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let state = place.get::<AggregateQuantileTDigestState>();
+        let rhs = rhs.get::<AggregateQuantileTDigestState>();
+        state.merge(rhs);
+        Ok(())
+    }
+
+    fn merge_result(&self, place: StateAddr, builder: &mut ColumnBuilder) -> Result<()> {
+        let state = place.get::<AggregateQuantileTDigestState>();
+        // The same as `quantile`, the default value is returned if no values are aggregated.
+        if state.centroids.is_empty() {
+            builder.push_default();
+            return Ok(());
+        }
+
+        let builder = NumberType::<F64>::try_downcast_builder(builder).unwrap();
+        builder.push(state.quantile(self.level).into());
+        Ok(())
+    }
+
+    fn need_manual_drop_state(&self) -> bool {
+        true
+    }
+
+    unsafe fn drop_state(&self, place: StateAddr) {
+        let state = place.get::<AggregateQuantileTDigestState>();
+        std::ptr::drop_in_place(state);
+    }
+}
+
+impl<T> fmt::Display for AggregateQuantileTDigestFunction<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
+
+impl<T> AggregateQuantileTDigestFunction<T>
+where T: Number + AsPrimitive<f64>
+{
+    pub fn try_create(
+        display_name: &str,
+        arguments: Vec<DataType>,
+        level: f64,
+    ) -> Result<AggregateFunctionRef> {
+        Ok(Arc::new(Self {
+            display_name: display_name.to_string(),
+            _arguments: arguments,
+            level,
+            t: PhantomData,
+        }))
+    }
+}
+
+pub fn try_create_aggregate_quantile_tdigest_function(
+    display_name: &str,
+    params: Vec<Scalar>,
+    arguments: Vec<DataType>,
+) -> Result<AggregateFunctionRef> {
+    if !params.is_empty() {
+        assert_unary_params(display_name, params.len())?;
+    }
+    assert_unary_arguments(display_name, arguments.len())?;
+    let level = get_levels(display_name, &params)?[0];
+
+    with_number_mapped_type!(|NUM_TYPE| match &arguments[0] {
+        DataType::Number(NumberDataType::NUM_TYPE) => {
+            AggregateQuantileTDigestFunction::<NUM_TYPE>::try_create(display_name, arguments, level)
+        }
+        _ => Err(ErrorCode::BadDataValueType(format!(
+            "AggregateQuantileTDigestFunction does not support type '{:?}'",
+            arguments[0]
+        ))),
+    })
+}
+
+pub fn aggregate_quantile_tdigest_function_desc() -> AggregateFunctionDescription {
+    AggregateFunctionDescription::creator(Box::new(try_create_aggregate_quantile_tdigest_function))
+}
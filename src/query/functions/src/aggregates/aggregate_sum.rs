@@ -20,6 +20,11 @@ use std::sync::Arc;
 use common_arrow::arrow::bitmap::Bitmap;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_expression::types::decimal::Decimal;
+use common_expression::types::decimal::DecimalDataType;
+use common_expression::types::decimal::DecimalSize;
+use common_expression::types::decimal::DecimalType;
+use common_expression::types::i256;
 use common_expression::types::number::Int8Type;
 use common_expression::types::number::Number;
 use common_expression::types::ArgType;
@@ -185,6 +190,147 @@ where
     }
 }
 
+/// The sum of decimals keeps the scale of the argument and widens the
+/// precision to the max precision of the same physical type.
+#[derive(Clone)]
+pub struct AggregateDecimalSumFunction<T> {
+    display_name: String,
+    return_type: DecimalDataType,
+    t: PhantomData<T>,
+}
+
+impl<T> AggregateDecimalSumFunction<T>
+where T: Decimal + Serialize + DeserializeOwned
+{
+    pub fn try_create(
+        display_name: &str,
+        arguments: Vec<DataType>,
+    ) -> Result<AggregateFunctionRef> {
+        let arg_type = arguments[0].as_decimal().unwrap();
+        let return_type = DecimalDataType::from_size(DecimalSize {
+            precision: arg_type.max_precision(),
+            scale: arg_type.scale(),
+        })
+        .map_err(ErrorCode::BadArguments)?;
+        Ok(Arc::new(Self {
+            display_name: display_name.to_owned(),
+            return_type,
+            t: PhantomData,
+        }))
+    }
+
+    fn add(&self, state: &mut AggregateSumState<T>, other: T) -> Result<()> {
+        match state.value.checked_add(other) {
+            Some(value) if value.fits_precision(self.return_type.precision()) => {
+                state.value = value;
+                Ok(())
+            }
+            _ => Err(ErrorCode::Overflow(format!(
+                "Decimal overflow in {}, the sum does not fit into {}",
+                self.display_name,
+                DataType::Decimal(self.return_type)
+            ))),
+        }
+    }
+}
+
+impl<T> AggregateFunction for AggregateDecimalSumFunction<T>
+where T: Decimal + Serialize + DeserializeOwned
+{
+    fn name(&self) -> &str {
+        "AggregateDecimalSumFunction"
+    }
+
+    fn return_type(&self) -> Result<DataType> {
+        Ok(DataType::Decimal(self.return_type))
+    }
+
+    fn init_state(&self, place: StateAddr) {
+        place.write(|| AggregateSumState::<T> { value: T::zero() });
+    }
+
+    fn state_layout(&self) -> Layout {
+        Layout::new::<AggregateSumState<T>>()
+    }
+
+    fn accumulate(
+        &self,
+        place: StateAddr,
+        columns: &[Column],
+        validity: Option<&Bitmap>,
+        _input_rows: usize,
+    ) -> Result<()> {
+        let state = place.get::<AggregateSumState<T>>();
+        let column = DecimalType::<T>::try_downcast_column(&columns[0]).unwrap();
+        match validity {
+            Some(validity) => {
+                for (value, valid) in column.iter().zip(validity.iter()) {
+                    if valid {
+                        self.add(state, *value)?;
+                    }
+                }
+            }
+            None => {
+                for value in column.iter() {
+                    self.add(state, *value)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn accumulate_keys(
+        &self,
+        places: &[StateAddr],
+        offset: usize,
+        columns: &[Column],
+        _input_rows: usize,
+    ) -> Result<()> {
+        let column = DecimalType::<T>::try_downcast_column(&columns[0]).unwrap();
+        for (value, place) in column.iter().zip(places.iter()) {
+            let state = place.next(offset).get::<AggregateSumState<T>>();
+            self.add(state, *value)?;
+        }
+        Ok(())
+    }
+
+    fn accumulate_row(&self, place: StateAddr, columns: &[Column], row: usize) -> Result<()> {
+        let column = DecimalType::<T>::try_downcast_column(&columns[0]).unwrap();
+        let state = place.get::<AggregateSumState<T>>();
+        self.add(state, column[row])
+    }
+
+    fn serialize(&self, place: StateAddr, writer: &mut Vec<u8>) -> Result<()> {
+        let state = place.get::<AggregateSumState<T>>();
+        serialize_into_buf(writer, &state.value)
+    }
+
+    fn deserialize(&self, place: StateAddr, reader: &mut &[u8]) -> Result<()> {
+        let state = place.get::<AggregateSumState<T>>();
+        state.value = deserialize_from_slice(reader)?;
+        Ok(())
+    }
+
+    fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let rhs = rhs.get::<AggregateSumState<T>>();
+        let state = place.get::<AggregateSumState<T>>();
+        self.add(state, rhs.value)
+    }
+
+    fn merge_result(&self, place: StateAddr, builder: &mut ColumnBuilder) -> Result<()> {
+        let state = place.get::<AggregateSumState<T>>();
+        let builder = DecimalType::<T>::try_downcast_builder(builder).unwrap();
+        builder.push(state.value);
+        Ok(())
+    }
+}
+
+impl<T> fmt::Display for AggregateDecimalSumFunction<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
+
 pub fn try_create_aggregate_sum_function(
     display_name: &str,
     _params: Vec<Scalar>,
@@ -205,6 +351,12 @@ pub fn try_create_aggregate_sum_function(
                 arguments,
             )
         }
+        DataType::Decimal(DecimalDataType::Decimal128(_)) => {
+            AggregateDecimalSumFunction::<i128>::try_create(display_name, arguments)
+        }
+        DataType::Decimal(DecimalDataType::Decimal256(_)) => {
+            AggregateDecimalSumFunction::<i256>::try_create(display_name, arguments)
+        }
         _ => Err(ErrorCode::BadDataValueType(format!(
             "AggregateSumFunction does not support type '{:?}'",
             arguments[0]
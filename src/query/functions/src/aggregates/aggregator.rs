@@ -23,6 +23,11 @@ use super::aggregate_covariance::aggregate_covariance_sample_desc;
 use super::aggregate_min_max_any::aggregate_any_function_desc;
 use super::aggregate_min_max_any::aggregate_max_function_desc;
 use super::aggregate_min_max_any::aggregate_min_function_desc;
+use super::aggregate_quantile::aggregate_median_function_desc;
+use super::aggregate_quantile::aggregate_quantile_exact_function_desc;
+use super::aggregate_quantile::aggregate_quantile_function_desc;
+use super::aggregate_quantile::aggregate_quantiles_function_desc;
+use super::aggregate_quantile_tdigest::aggregate_quantile_tdigest_function_desc;
 use super::aggregate_stddev_pop::aggregate_stddev_pop_function_desc;
 use super::aggregate_window_funnel::aggregate_window_funnel_function_desc;
 use super::AggregateCountFunction;
@@ -59,6 +64,15 @@ impl Aggregators {
         );
 
         factory.register("retention", aggregate_retention_function_desc());
+
+        factory.register("median", aggregate_median_function_desc());
+        factory.register("quantile", aggregate_quantile_function_desc());
+        factory.register("quantiles", aggregate_quantiles_function_desc());
+        factory.register("quantile_exact", aggregate_quantile_exact_function_desc());
+        factory.register(
+            "quantile_tdigest",
+            aggregate_quantile_tdigest_function_desc(),
+        );
     }
 
     pub fn register_combinator(factory: &mut AggregateFunctionFactory) {
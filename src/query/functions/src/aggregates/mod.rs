@@ -28,6 +28,8 @@ mod aggregate_covariance;
 mod aggregate_distinct_state;
 mod aggregate_min_max_any;
 mod aggregate_null_result;
+mod aggregate_quantile;
+mod aggregate_quantile_tdigest;
 mod aggregate_retention;
 mod aggregate_scalar_state;
 mod aggregate_stddev_pop;
@@ -51,6 +53,8 @@ pub use aggregate_function_state::StateAddr;
 pub use aggregate_function_state::StateAddrs;
 pub use aggregate_min_max_any::AggregateMinMaxAnyFunction;
 pub use aggregate_null_result::AggregateNullResultFunction;
+pub use aggregate_quantile::AggregateQuantileFunction;
+pub use aggregate_quantile_tdigest::AggregateQuantileTDigestFunction;
 pub use aggregate_retention::AggregateRetentionFunction;
 pub use aggregate_sum::AggregateSumFunction;
 pub use aggregator::Aggregators;
@@ -0,0 +1,637 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+use common_arrow::arrow::bitmap::MutableBitmap;
+use common_expression::types::decimal::*;
+use common_expression::types::nullable::NullableColumn;
+use common_expression::types::nullable::NullableDomain;
+use common_expression::types::number::NumberScalar;
+use common_expression::types::number::F64;
+use common_expression::types::AnyType;
+use common_expression::types::DataType;
+use common_expression::types::NumberDataType;
+use common_expression::types::NumberType;
+use common_expression::types::ValueType;
+use common_expression::with_integer_mapped_type;
+use common_expression::wrap_nullable;
+use common_expression::Column;
+use common_expression::ColumnBuilder;
+use common_expression::Domain;
+use common_expression::EvalContext;
+use common_expression::Function;
+use common_expression::FunctionDomain;
+use common_expression::FunctionProperty;
+use common_expression::FunctionRegistry;
+use common_expression::FunctionSignature;
+use common_expression::Scalar;
+use common_expression::ScalarRef;
+use common_expression::Value;
+use common_expression::ValueRef;
+
+pub fn register(registry: &mut FunctionRegistry) {
+    for (name, op) in [
+        ("plus", DecimalArithmeticOp::Plus),
+        ("minus", DecimalArithmeticOp::Minus),
+        ("multiply", DecimalArithmeticOp::Multiply),
+        ("divide", DecimalArithmeticOp::Divide),
+    ] {
+        register_decimal_factory(registry, name, move |_, args_type| {
+            arithmetic_function(name, op, args_type)
+        });
+    }
+
+    register_decimal_factory(registry, "minus", |_, args_type| {
+        let ty = match args_type {
+            [DataType::Decimal(ty)] => *ty,
+            _ => return None,
+        };
+        Some(Function {
+            signature: FunctionSignature {
+                name: "minus".to_string(),
+                args_type: vec![DataType::Decimal(ty)],
+                return_type: DataType::Decimal(ty),
+                property: FunctionProperty::default(),
+            },
+            calc_domain: Box::new(|_| FunctionDomain::Full),
+            eval: Box::new(move |args, ctx| {
+                eval_rows(args, ctx, &DataType::Decimal(ty), false, |row| {
+                    let (value, _) = decimal_to_i256(&row[0]);
+                    // The range of a decimal is symmetric, so negation never overflows.
+                    Ok(i256_to_scalar(-value, &ty).unwrap())
+                })
+            }),
+        })
+    });
+
+    let comparisons: [(&'static str, fn(Ordering) -> bool); 6] = [
+        ("eq", |ord| ord == Ordering::Equal),
+        ("noteq", |ord| ord != Ordering::Equal),
+        ("lt", |ord| ord == Ordering::Less),
+        ("lte", |ord| ord != Ordering::Greater),
+        ("gt", |ord| ord == Ordering::Greater),
+        ("gte", |ord| ord != Ordering::Less),
+    ];
+    for (name, cmp) in comparisons {
+        register_decimal_factory(registry, name, move |_, args_type| {
+            comparison_function(name, cmp, args_type)
+        });
+    }
+
+    for is_try in [false, true] {
+        let name = if is_try {
+            "try_to_decimal"
+        } else {
+            "to_decimal"
+        };
+        register_decimal_factory(registry, name, move |params, args_type| {
+            to_decimal_function(name, is_try, params, args_type)
+        });
+    }
+
+    for dest_type in ALL_DEST_TYPES {
+        for is_try in [false, true] {
+            let prefix = if is_try { "try_" } else { "" };
+            let name = format!("{prefix}to_{}", dest_type.to_string().to_lowercase());
+            let factory_name = name.clone();
+            let dest_type = dest_type.clone();
+            register_decimal_factory(registry, &name, move |_, args_type| {
+                from_decimal_function(&factory_name, is_try, dest_type.clone(), args_type)
+            });
+        }
+    }
+}
+
+const ALL_DEST_TYPES: [DataType; 11] = [
+    DataType::String,
+    DataType::Number(NumberDataType::UInt8),
+    DataType::Number(NumberDataType::UInt16),
+    DataType::Number(NumberDataType::UInt32),
+    DataType::Number(NumberDataType::UInt64),
+    DataType::Number(NumberDataType::Int8),
+    DataType::Number(NumberDataType::Int16),
+    DataType::Number(NumberDataType::Int32),
+    DataType::Number(NumberDataType::Int64),
+    DataType::Number(NumberDataType::Float32),
+    DataType::Number(NumberDataType::Float64),
+];
+
+/// Registers the factory for the non-nullable arguments, together with a
+/// factory that unwraps the nullable arguments and passes the NULLs through.
+fn register_decimal_factory<F>(registry: &mut FunctionRegistry, name: &str, factory: F)
+where F: Fn(&[usize], &[DataType]) -> Option<Function> + Clone + Send + Sync + 'static {
+    let inner_factory = factory.clone();
+    registry.register_function_factory(name, move |params, args_type| {
+        inner_factory(params, args_type).map(Arc::new)
+    });
+    registry.register_function_factory(name, move |params, args_type| {
+        if !args_type.iter().any(|ty| ty.is_nullable()) {
+            return None;
+        }
+        let args_type = args_type
+            .iter()
+            .map(|ty| ty.remove_nullable())
+            .collect::<Vec<_>>();
+        factory(params, &args_type).map(|f| Arc::new(passthrough_nullable(f)))
+    });
+}
+
+fn passthrough_nullable(function: Function) -> Function {
+    let Function {
+        signature,
+        calc_domain,
+        eval,
+    } = function;
+    Function {
+        signature: FunctionSignature {
+            name: signature.name,
+            args_type: signature
+                .args_type
+                .iter()
+                .map(|ty| ty.wrap_nullable())
+                .collect(),
+            return_type: signature.return_type.wrap_nullable(),
+            property: signature.property,
+        },
+        calc_domain: Box::new(move |args_domain| {
+            let mut has_null = false;
+            let mut inner_domains = Vec::with_capacity(args_domain.len());
+            for domain in args_domain {
+                let NullableDomain {
+                    has_null: arg_has_null,
+                    value,
+                } = domain.as_nullable().unwrap();
+                has_null |= *arg_has_null;
+                match value {
+                    Some(value) => inner_domains.push(value.as_ref().clone()),
+                    None => {
+                        return FunctionDomain::Domain(Domain::Nullable(NullableDomain {
+                            has_null: true,
+                            value: None,
+                        }));
+                    }
+                }
+            }
+            match calc_domain(&inner_domains) {
+                FunctionDomain::Domain(Domain::Nullable(NullableDomain {
+                    has_null: inner_has_null,
+                    value,
+                })) => FunctionDomain::Domain(Domain::Nullable(NullableDomain {
+                    has_null: has_null || inner_has_null,
+                    value,
+                })),
+                FunctionDomain::Domain(domain) => {
+                    FunctionDomain::Domain(Domain::Nullable(NullableDomain {
+                        has_null,
+                        value: Some(Box::new(domain)),
+                    }))
+                }
+                FunctionDomain::Full => FunctionDomain::Full,
+                FunctionDomain::MayThrow => FunctionDomain::MayThrow,
+            }
+        }),
+        eval: Box::new(move |args, ctx| wrap_nullable(|args, ctx| eval(args, ctx))(args, ctx)),
+    }
+}
+
+/// Integers take part in the decimal arithmetic as decimals with scale 0.
+fn as_decimal_type(ty: &DataType) -> Option<DecimalDataType> {
+    match ty {
+        DataType::Decimal(ty) => Some(*ty),
+        DataType::Number(num_ty) if !num_ty.is_float() => {
+            Some(DecimalDataType::from_integer_bit_width(num_ty.bit_width()))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the argument types if one of the arguments is a decimal and the
+/// other one is a float, in which case both are evaluated as `Float64`.
+fn float_args_type(args_type: &[DataType]) -> Option<Vec<DataType>> {
+    let float64 = DataType::Number(NumberDataType::Float64);
+    match args_type {
+        [DataType::Decimal(ty), DataType::Number(num_ty)] if num_ty.is_float() => {
+            Some(vec![DataType::Decimal(*ty), float64])
+        }
+        [DataType::Number(num_ty), DataType::Decimal(ty)] if num_ty.is_float() => {
+            Some(vec![float64, DataType::Decimal(*ty)])
+        }
+        _ => None,
+    }
+}
+
+fn arithmetic_function(
+    name: &str,
+    op: DecimalArithmeticOp,
+    args_type: &[DataType],
+) -> Option<Function> {
+    if let Some(args_type) = float_args_type(args_type) {
+        return Some(Function {
+            signature: FunctionSignature {
+                name: name.to_string(),
+                args_type,
+                return_type: DataType::Number(NumberDataType::Float64),
+                property: FunctionProperty::default(),
+            },
+            calc_domain: Box::new(|_| FunctionDomain::MayThrow),
+            eval: Box::new(move |args, ctx| {
+                eval_rows(
+                    args,
+                    ctx,
+                    &DataType::Number(NumberDataType::Float64),
+                    false,
+                    |row| {
+                        let (a, b) = (to_float64(&row[0]), to_float64(&row[1]));
+                        let value = match op {
+                            DecimalArithmeticOp::Plus => a + b,
+                            DecimalArithmeticOp::Minus => a - b,
+                            DecimalArithmeticOp::Multiply => a * b,
+                            DecimalArithmeticOp::Divide => {
+                                if b == 0.0 {
+                                    return Err("divided by zero".to_string());
+                                }
+                                a / b
+                            }
+                        };
+                        Ok(Scalar::Number(NumberScalar::Float64(F64::from(value))))
+                    },
+                )
+            }),
+        });
+    }
+
+    if args_type.len() != 2 || (!args_type[0].is_decimal() && !args_type[1].is_decimal()) {
+        return None;
+    }
+    let a = as_decimal_type(&args_type[0])?;
+    let b = as_decimal_type(&args_type[1])?;
+    let return_type = DecimalDataType::binary_result_type(&a, &b, op).ok()?;
+    let name = name.to_string();
+
+    Some(Function {
+        signature: FunctionSignature {
+            name: name.clone(),
+            args_type: vec![DataType::Decimal(a), DataType::Decimal(b)],
+            return_type: DataType::Decimal(return_type),
+            property: FunctionProperty::default(),
+        },
+        calc_domain: Box::new(|_| FunctionDomain::MayThrow),
+        eval: Box::new(move |args, ctx| {
+            let scale = return_type.scale();
+            eval_rows(args, ctx, &DataType::Decimal(return_type), false, |row| {
+                let (a, a_scale) = decimal_to_i256(&row[0]);
+                let (b, b_scale) = decimal_to_i256(&row[1]);
+                let value = match op {
+                    DecimalArithmeticOp::Plus => a
+                        .rescale(a_scale, scale)
+                        .zip(b.rescale(b_scale, scale))
+                        .and_then(|(a, b)| a.checked_add(b)),
+                    DecimalArithmeticOp::Minus => a
+                        .rescale(a_scale, scale)
+                        .zip(b.rescale(b_scale, scale))
+                        .and_then(|(a, b)| a.checked_sub(b)),
+                    DecimalArithmeticOp::Multiply => a
+                        .checked_mul(b)
+                        .and_then(|v| v.rescale(a_scale + b_scale, scale)),
+                    DecimalArithmeticOp::Divide => {
+                        if b == i256::ZERO {
+                            return Err("divided by zero".to_string());
+                        }
+                        // Scale up the dividend so that the quotient has the result scale.
+                        a.rescale(a_scale, scale + b_scale)
+                            .and_then(|a| a.checked_div(b))
+                    }
+                };
+                value
+                    .and_then(|value| i256_to_scalar(value, &return_type))
+                    .ok_or_else(|| {
+                        format!(
+                            "Decimal overflow in {name}, the result does not fit into {}",
+                            DataType::Decimal(return_type)
+                        )
+                    })
+            })
+        }),
+    })
+}
+
+fn comparison_function(
+    name: &str,
+    cmp: fn(Ordering) -> bool,
+    args_type: &[DataType],
+) -> Option<Function> {
+    let is_float = float_args_type(args_type).is_some();
+    let args_type = match float_args_type(args_type) {
+        Some(args_type) => args_type,
+        None => {
+            if args_type.len() != 2 || (!args_type[0].is_decimal() && !args_type[1].is_decimal()) {
+                return None;
+            }
+            vec![
+                DataType::Decimal(as_decimal_type(&args_type[0])?),
+                DataType::Decimal(as_decimal_type(&args_type[1])?),
+            ]
+        }
+    };
+
+    Some(Function {
+        signature: FunctionSignature {
+            name: name.to_string(),
+            args_type,
+            return_type: DataType::Boolean,
+            property: FunctionProperty::default(),
+        },
+        calc_domain: Box::new(|_| FunctionDomain::Full),
+        eval: Box::new(move |args, ctx| {
+            eval_rows(args, ctx, &DataType::Boolean, false, |row| {
+                let ord = if is_float {
+                    F64::from(to_float64(&row[0])).cmp(&F64::from(to_float64(&row[1])))
+                } else {
+                    let (a, a_scale) = decimal_to_i256(&row[0]);
+                    let (b, b_scale) = decimal_to_i256(&row[1]);
+                    compare_decimal(a, a_scale, b, b_scale)
+                };
+                Ok(Scalar::Boolean(cmp(ord)))
+            })
+        }),
+    })
+}
+
+fn compare_decimal(a: i256, a_scale: u8, b: i256, b_scale: u8) -> Ordering {
+    let scale = a_scale.max(b_scale);
+    match (a.rescale(a_scale, scale), b.rescale(b_scale, scale)) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        // Overflow only happens when scaling up a non-zero value, which means
+        // it has a larger magnitude than the other side.
+        (None, _) => a.cmp(&i256::ZERO),
+        (_, None) => i256::ZERO.cmp(&b),
+    }
+}
+
+fn to_decimal_function(
+    name: &str,
+    is_try: bool,
+    params: &[usize],
+    args_type: &[DataType],
+) -> Option<Function> {
+    let (precision, scale) = match params {
+        [precision, scale] => (u8::try_from(*precision).ok()?, u8::try_from(*scale).ok()?),
+        _ => return None,
+    };
+    let dest_type = DecimalDataType::from_size(DecimalSize { precision, scale }).ok()?;
+    match args_type {
+        [DataType::Decimal(_) | DataType::Number(_) | DataType::String | DataType::Boolean] => {}
+        _ => return None,
+    }
+    let return_type = if is_try {
+        DataType::Decimal(dest_type).wrap_nullable()
+    } else {
+        DataType::Decimal(dest_type)
+    };
+
+    Some(Function {
+        signature: FunctionSignature {
+            name: name.to_string(),
+            args_type: args_type.to_vec(),
+            return_type,
+            property: FunctionProperty::default(),
+        },
+        calc_domain: Box::new(move |_| {
+            if is_try {
+                FunctionDomain::Full
+            } else {
+                FunctionDomain::MayThrow
+            }
+        }),
+        eval: Box::new(move |args, ctx| {
+            eval_rows(args, ctx, &DataType::Decimal(dest_type), is_try, |row| {
+                cast_to_decimal(&row[0], &dest_type)
+            })
+        }),
+    })
+}
+
+fn cast_to_decimal(scalar: &ScalarRef, dest_type: &DecimalDataType) -> Result<Scalar, String> {
+    let size = dest_type.size();
+    let overflow = || {
+        format!(
+            "Decimal overflow: {} does not fit into {}",
+            scalar,
+            DataType::Decimal(*dest_type)
+        )
+    };
+    match scalar {
+        ScalarRef::Decimal(_) => {
+            let (value, scale) = decimal_to_i256(scalar);
+            value
+                .rescale(scale, size.scale)
+                .and_then(|value| i256_to_scalar(value, dest_type))
+                .ok_or_else(overflow)
+        }
+        ScalarRef::Boolean(value) => i256_to_scalar(
+            i256::from(*value as i128) * i256::e(size.scale as u32),
+            dest_type,
+        )
+        .ok_or_else(overflow),
+        ScalarRef::Number(NumberScalar::Float32(value)) => {
+            float_to_decimal(value.0 as f64, dest_type).ok_or_else(overflow)
+        }
+        ScalarRef::Number(NumberScalar::Float64(value)) => {
+            float_to_decimal(value.0, dest_type).ok_or_else(overflow)
+        }
+        ScalarRef::Number(value) => {
+            let value = with_integer_mapped_type!(|NUM_TYPE| match value {
+                NumberScalar::NUM_TYPE(value) => i256::from(*value),
+                _ => unreachable!(),
+            });
+            value
+                .rescale(0, size.scale)
+                .and_then(|value| i256_to_scalar(value, dest_type))
+                .ok_or_else(overflow)
+        }
+        ScalarRef::String(value) => {
+            match dest_type {
+                DecimalDataType::Decimal128(size) => read_decimal_with_size::<i128>(value, *size)
+                    .map(|v| i128::upcast_scalar(v, *size)),
+                DecimalDataType::Decimal256(size) => read_decimal_with_size::<i256>(value, *size)
+                    .map(|v| i256::upcast_scalar(v, *size)),
+            }
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn float_to_decimal(value: f64, dest_type: &DecimalDataType) -> Option<Scalar> {
+    match dest_type {
+        DecimalDataType::Decimal128(size) => {
+            i128::from_float(value, *size).map(|v| i128::upcast_scalar(v, *size))
+        }
+        DecimalDataType::Decimal256(size) => {
+            i256::from_float(value, *size).map(|v| i256::upcast_scalar(v, *size))
+        }
+    }
+}
+
+fn from_decimal_function(
+    name: &str,
+    is_try: bool,
+    dest_type: DataType,
+    args_type: &[DataType],
+) -> Option<Function> {
+    let src_type = match args_type {
+        [DataType::Decimal(ty)] => *ty,
+        _ => return None,
+    };
+    let return_type = if is_try {
+        dest_type.wrap_nullable()
+    } else {
+        dest_type.clone()
+    };
+    // Only the conversion to integers may fail because of overflow.
+    let may_throw = !is_try && matches!(&dest_type, DataType::Number(ty) if !ty.is_float());
+
+    Some(Function {
+        signature: FunctionSignature {
+            name: name.to_string(),
+            args_type: vec![DataType::Decimal(src_type)],
+            return_type,
+            property: FunctionProperty::default(),
+        },
+        calc_domain: Box::new(move |_| {
+            if may_throw {
+                FunctionDomain::MayThrow
+            } else {
+                FunctionDomain::Full
+            }
+        }),
+        eval: Box::new(move |args, ctx| {
+            eval_rows(args, ctx, &dest_type, is_try, |row| {
+                cast_from_decimal(&row[0], &dest_type)
+            })
+        }),
+    })
+}
+
+fn cast_from_decimal(scalar: &ScalarRef, dest_type: &DataType) -> Result<Scalar, String> {
+    let (value, scale) = decimal_to_i256(scalar);
+    match dest_type {
+        DataType::String => Ok(Scalar::String(value.display(scale).into_bytes())),
+        DataType::Number(NumberDataType::Float32) => Ok(Scalar::Number(NumberScalar::Float32(
+            (value.to_float64(scale) as f32).into(),
+        ))),
+        DataType::Number(NumberDataType::Float64) => Ok(Scalar::Number(NumberScalar::Float64(
+            value.to_float64(scale).into(),
+        ))),
+        DataType::Number(num_ty) => {
+            let overflow = || format!("Decimal overflow: {} does not fit into {}", scalar, num_ty);
+            let value = value.rescale(scale, 0).ok_or_else(overflow)?;
+            with_integer_mapped_type!(|NUM_TYPE| match num_ty {
+                NumberDataType::NUM_TYPE => {
+                    if value < i256::from(NUM_TYPE::MIN) || value > i256::from(NUM_TYPE::MAX) {
+                        return Err(overflow());
+                    }
+                    Ok(NumberType::<NUM_TYPE>::upcast_scalar(
+                        value.as_i128() as NUM_TYPE
+                    ))
+                }
+                _ => unreachable!(),
+            })
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn decimal_to_i256(scalar: &ScalarRef) -> (i256, u8) {
+    match scalar {
+        ScalarRef::Decimal(DecimalScalar::Decimal128(value, size)) => {
+            (i256::from(*value), size.scale)
+        }
+        ScalarRef::Decimal(DecimalScalar::Decimal256(value, size)) => (*value, size.scale),
+        _ => unreachable!(),
+    }
+}
+
+/// Converts the value into the decimal type, returns `None` if it does not
+/// fit into the precision.
+fn i256_to_scalar(value: i256, ty: &DecimalDataType) -> Option<Scalar> {
+    if !value.fits_precision(ty.precision()) {
+        return None;
+    }
+    match ty {
+        DecimalDataType::Decimal128(size) => {
+            Some(i128::upcast_scalar(i128::from_i256(value)?, *size))
+        }
+        DecimalDataType::Decimal256(size) => Some(i256::upcast_scalar(value, *size)),
+    }
+}
+
+fn to_float64(scalar: &ScalarRef) -> f64 {
+    match scalar {
+        ScalarRef::Decimal(value) => value.to_float64(),
+        ScalarRef::Number(NumberScalar::Float64(value)) => value.0,
+        _ => unreachable!(),
+    }
+}
+
+/// Evaluates the function row by row. Rows that fail are reported as errors,
+/// or turned into NULLs if `is_try` is set.
+fn eval_rows(
+    args: &[ValueRef<AnyType>],
+    ctx: &mut EvalContext,
+    return_type: &DataType,
+    is_try: bool,
+    f: impl Fn(&[ScalarRef]) -> Result<Scalar, String>,
+) -> Value<AnyType> {
+    let len = args.iter().find_map(|arg| match arg {
+        ValueRef::Column(col) => Some(col.len()),
+        _ => None,
+    });
+    let num_rows = len.unwrap_or(1);
+
+    let mut builder = ColumnBuilder::with_capacity(return_type, num_rows);
+    let mut validity = MutableBitmap::with_capacity(num_rows);
+    let mut row_args = Vec::with_capacity(args.len());
+    for row in 0..num_rows {
+        row_args.clear();
+        row_args.extend(args.iter().map(|arg| match arg {
+            ValueRef::Scalar(scalar) => scalar.clone(),
+            ValueRef::Column(col) => unsafe { col.index_unchecked(row) },
+        }));
+        match f(&row_args) {
+            Ok(value) => {
+                builder.push(value.as_ref());
+                validity.push(true);
+            }
+            Err(err) => {
+                if !is_try {
+                    ctx.set_error(row, err);
+                }
+                builder.push_default();
+                validity.push(false);
+            }
+        }
+    }
+
+    match (len, is_try) {
+        (Some(_), false) => Value::Column(builder.build()),
+        (Some(_), true) => Value::Column(Column::Nullable(Box::new(NullableColumn {
+            column: builder.build(),
+            validity: validity.into(),
+        }))),
+        (None, true) if !validity.get(0) => Value::Scalar(Scalar::Null),
+        (None, _) => Value::Scalar(builder.build_scalar()),
+    }
+}
@@ -24,6 +24,7 @@ mod array;
 mod boolean;
 mod control;
 mod datetime;
+mod decimal;
 mod geo;
 mod math;
 mod tuple;
@@ -57,6 +58,7 @@ fn builtin_functions() -> FunctionRegistry {
     control::register(&mut registry);
     comparison::register(&mut registry);
     datetime::register(&mut registry);
+    decimal::register(&mut registry);
     math::register(&mut registry);
     string::register(&mut registry);
     string_multi_args::register(&mut registry);
@@ -49,6 +49,7 @@ fn test_agg() {
     test_agg_stddev_pop(file, eval_aggr);
     test_agg_window_funnel(file, eval_aggr);
     test_agg_approx_count_distinct(file, eval_aggr);
+    test_agg_quantile(file, eval_aggr);
 }
 
 #[test]
@@ -73,6 +74,7 @@ fn test_agg_group_by() {
     test_agg_stddev_pop(file, simulate_two_groups_group_by);
     test_agg_window_funnel(file, simulate_two_groups_group_by);
     test_agg_approx_count_distinct(file, simulate_two_groups_group_by);
+    test_agg_quantile(file, simulate_two_groups_group_by);
 }
 
 fn get_example() -> Vec<(&'static str, Column)> {
@@ -360,3 +362,21 @@ fn test_agg_approx_count_distinct(file: &mut impl Write, simulator: impl Aggrega
         simulator,
     );
 }
+
+fn test_agg_quantile(file: &mut impl Write, simulator: impl AggregationSimulator) {
+    run_agg_ast(file, "median(a)", get_example().as_slice(), simulator);
+    run_agg_ast(file, "median(x_null)", get_example().as_slice(), simulator);
+    run_agg_ast(file, "quantile(1)(a)", get_example().as_slice(), simulator);
+    run_agg_ast(
+        file,
+        "quantile_exact(a)",
+        get_example().as_slice(),
+        simulator,
+    );
+    run_agg_ast(
+        file,
+        "quantile_tdigest(a)",
+        get_example().as_slice(),
+        simulator,
+    );
+}
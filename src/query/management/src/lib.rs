@@ -13,6 +13,7 @@
 // limitations under the License.
 
 mod cluster;
+mod lock;
 mod quota;
 mod role;
 mod serde;
@@ -23,6 +24,8 @@ mod user;
 
 pub use cluster::ClusterApi;
 pub use cluster::ClusterMgr;
+pub use lock::TableLockApi;
+pub use lock::TableLockMgr;
 pub use quota::QuotaApi;
 pub use quota::QuotaMgr;
 pub use role::RoleApi;
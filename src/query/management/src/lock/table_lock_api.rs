@@ -0,0 +1,40 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use common_exception::Result;
+use common_meta_app::schema::TableLockMeta;
+
+#[async_trait::async_trait]
+pub trait TableLockApi: Sync + Send {
+    // Create a lock revision of the table, it expires if not extended within `expire_secs`.
+    async fn create_lock_revision(
+        &self,
+        table_id: u64,
+        expire_secs: u64,
+        lock_meta: &TableLockMeta,
+    ) -> Result<u64>;
+
+    // List the live lock revisions of the table, ordered by revision.
+    // The first one holds the lock, the others are waiting for it.
+    async fn list_lock_revisions(&self, table_id: u64) -> Result<Vec<(u64, TableLockMeta)>>;
+
+    // Extend the lease of a lock revision, fails if it has already expired.
+    async fn extend_lock_revision(
+        &self,
+        table_id: u64,
+        expire_secs: u64,
+        revision: u64,
+    ) -> Result<()>;
+
+    async fn delete_lock_revision(&self, table_id: u64, revision: u64) -> Result<()>;
+}
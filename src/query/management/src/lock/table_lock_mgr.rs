@@ -0,0 +1,124 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::base::escape_for_key;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_api::KVApi;
+use common_meta_api::LeaseApi;
+use common_meta_app::schema::TableLockMeta;
+
+use crate::lock::TableLockApi;
+use crate::serde::deserialize_struct;
+use crate::serde::serialize_struct;
+
+static TABLE_LOCK_LEASE_PREFIX: &str = "table_lock";
+
+/// Table locks on top of the leases of the kv api.
+///
+/// The lock of a table is the lease `table_lock/<tenant>/<table_id>`,
+/// a lock revision is the revision of a holder of the lease.
+pub struct TableLockMgr {
+    kv_api: Arc<dyn KVApi>,
+    lease_prefix: String,
+}
+
+impl TableLockMgr {
+    pub fn create(kv_api: Arc<dyn KVApi>, tenant: &str) -> Result<Self> {
+        if tenant.is_empty() {
+            return Err(ErrorCode::TenantIsEmpty(
+                "Tenant can not empty(while table lock mgr create)",
+            ));
+        }
+
+        Ok(TableLockMgr {
+            kv_api,
+            lease_prefix: format!("{}/{}", TABLE_LOCK_LEASE_PREFIX, escape_for_key(tenant)?),
+        })
+    }
+
+    fn lease_name(&self, table_id: u64) -> String {
+        format!("{}/{}", self.lease_prefix, table_id)
+    }
+}
+
+#[async_trait::async_trait]
+impl TableLockApi for TableLockMgr {
+    async fn create_lock_revision(
+        &self,
+        table_id: u64,
+        expire_secs: u64,
+        lock_meta: &TableLockMeta,
+    ) -> Result<u64> {
+        let value = serialize_struct(lock_meta, ErrorCode::Internal, || "")?;
+        let revision = self
+            .kv_api
+            .acquire_lease(
+                &self.lease_name(table_id),
+                value,
+                Duration::from_secs(expire_secs),
+            )
+            .await?;
+        Ok(revision)
+    }
+
+    async fn list_lock_revisions(&self, table_id: u64) -> Result<Vec<(u64, TableLockMeta)>> {
+        let holders = self
+            .kv_api
+            .list_lease_holders(&self.lease_name(table_id))
+            .await?;
+
+        let mut revisions = Vec::with_capacity(holders.len());
+        for holder in holders {
+            let lock_meta = deserialize_struct(&holder.value, ErrorCode::Internal, || "")?;
+            revisions.push((holder.revision, lock_meta));
+        }
+        Ok(revisions)
+    }
+
+    async fn extend_lock_revision(
+        &self,
+        table_id: u64,
+        expire_secs: u64,
+        revision: u64,
+    ) -> Result<()> {
+        let extended = self
+            .kv_api
+            .extend_lease(
+                &self.lease_name(table_id),
+                revision,
+                Duration::from_secs(expire_secs),
+            )
+            .await?;
+
+        if extended {
+            Ok(())
+        } else {
+            Err(ErrorCode::TableLockExpired(format!(
+                "Lock revision {} of table id {} has expired",
+                revision, table_id
+            )))
+        }
+    }
+
+    async fn delete_lock_revision(&self, table_id: u64, revision: u64) -> Result<()> {
+        // The revision may have expired already.
+        self.kv_api
+            .release_lease(&self.lease_name(table_id), revision)
+            .await?;
+        Ok(())
+    }
+}
@@ -15,5 +15,6 @@
 mod cluster;
 mod setting;
 mod stage;
+mod table_lock;
 mod udf;
 mod user;
@@ -0,0 +1,98 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+
+use chrono::Utc;
+use common_base::base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_management::*;
+use common_meta_app::schema::TableLockMeta;
+use common_meta_embedded::MetaEmbedded;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_table_lock_revisions() -> Result<()> {
+    let lock_api = new_table_lock_api().await?;
+
+    let rev1 = lock_api
+        .create_lock_revision(1, 60, &new_lock_meta("query1"))
+        .await?;
+    let rev2 = lock_api
+        .create_lock_revision(1, 60, &new_lock_meta("query2"))
+        .await?;
+    let rev3 = lock_api
+        .create_lock_revision(2, 60, &new_lock_meta("query3"))
+        .await?;
+    assert!(rev1 < rev2);
+    assert!(rev2 < rev3);
+
+    // The revisions are ordered, and separated by tables.
+    let revisions = lock_api.list_lock_revisions(1).await?;
+    assert_eq!(
+        revisions
+            .iter()
+            .map(|(rev, meta)| (*rev, meta.query_id.as_str()))
+            .collect::<Vec<_>>(),
+        vec![(rev1, "query1"), (rev2, "query2")]
+    );
+
+    lock_api.extend_lock_revision(1, 60, rev1).await?;
+
+    // The next revision holds the lock once the first one is deleted.
+    lock_api.delete_lock_revision(1, rev1).await?;
+    let revisions = lock_api.list_lock_revisions(1).await?;
+    assert_eq!(revisions.len(), 1);
+    assert_eq!(revisions[0].0, rev2);
+
+    // A deleted revision can't be extended.
+    let res = lock_api.extend_lock_revision(1, 60, rev1).await;
+    assert_eq!(res.unwrap_err().code(), ErrorCode::TABLE_LOCK_EXPIRED);
+
+    // Deleting is idempotent.
+    lock_api.delete_lock_revision(1, rev1).await?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_table_lock_expired() -> Result<()> {
+    let lock_api = new_table_lock_api().await?;
+
+    let rev = lock_api
+        .create_lock_revision(1, 1, &new_lock_meta("query1"))
+        .await?;
+    assert_eq!(lock_api.list_lock_revisions(1).await?.len(), 1);
+
+    tokio::time::sleep(std::time::Duration::from_secs(3)).await;
+    assert!(lock_api.list_lock_revisions(1).await?.is_empty());
+
+    let res = lock_api.extend_lock_revision(1, 1, rev).await;
+    assert_eq!(res.unwrap_err().code(), ErrorCode::TABLE_LOCK_EXPIRED);
+
+    Ok(())
+}
+
+fn new_lock_meta(query_id: &str) -> TableLockMeta {
+    TableLockMeta {
+        user: "root".to_string(),
+        node: "node1".to_string(),
+        query_id: query_id.to_string(),
+        created_on: Utc::now(),
+    }
+}
+
+async fn new_table_lock_api() -> Result<TableLockMgr> {
+    let test_api = Arc::new(MetaEmbedded::new_temp().await?);
+    TableLockMgr::create(test_api, "test-tenant-id")
+}
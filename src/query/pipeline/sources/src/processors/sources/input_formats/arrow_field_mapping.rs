@@ -0,0 +1,229 @@
+//  Copyright 2023 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::sync::Arc;
+
+use common_arrow::arrow::array::Array;
+use common_arrow::arrow::chunk::Chunk as ArrowChunk;
+use common_arrow::arrow::compute::cast::can_cast_types;
+use common_arrow::arrow::compute::cast::cast;
+use common_arrow::arrow::compute::cast::CastOptions;
+use common_arrow::arrow::datatypes::DataType as ArrowDataType;
+use common_arrow::arrow::datatypes::Field;
+use common_arrow::arrow::datatypes::Schema as ArrowSchema;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::types::DataType;
+use common_expression::TableDataType;
+use common_expression::TableSchema;
+use common_expression::TableSchemaRef;
+
+/// How a column of the table is read from a field of an arrow based file (Avro, ORC).
+#[derive(Clone, Debug)]
+pub struct FieldMapping {
+    /// Position of the field in the file.
+    pub position: usize,
+    /// Arrow type that the array read from the file is cast to before converting it
+    /// to a column.
+    pub target_type: ArrowDataType,
+}
+
+/// Rewrite the arrow types which have no counterpart in databend to the closest
+/// supported ones, e.g. enums are read as strings and fixed binaries as strings.
+pub fn normalize_field(format_name: &str, field: &Field) -> Result<Field> {
+    let data_type = normalize_data_type(format_name, &field.name, &field.data_type)?;
+    Ok(Field::new(field.name.clone(), data_type, field.is_nullable))
+}
+
+fn normalize_data_type(
+    format_name: &str,
+    name: &str,
+    data_type: &ArrowDataType,
+) -> Result<ArrowDataType> {
+    let data_type = match data_type {
+        ArrowDataType::Null
+        | ArrowDataType::Boolean
+        | ArrowDataType::Int8
+        | ArrowDataType::Int16
+        | ArrowDataType::Int32
+        | ArrowDataType::Int64
+        | ArrowDataType::UInt8
+        | ArrowDataType::UInt16
+        | ArrowDataType::UInt32
+        | ArrowDataType::UInt64
+        | ArrowDataType::Float32
+        | ArrowDataType::Float64
+        | ArrowDataType::Utf8
+        | ArrowDataType::LargeUtf8
+        | ArrowDataType::Binary
+        | ArrowDataType::LargeBinary
+        | ArrowDataType::Date32
+        | ArrowDataType::Date64
+        | ArrowDataType::Decimal(_, _)
+        | ArrowDataType::Decimal256(_, _)
+        | ArrowDataType::Timestamp(_, _) => data_type.clone(),
+        ArrowDataType::Float16 => ArrowDataType::Float32,
+        ArrowDataType::Time32(_) | ArrowDataType::Time64(_) | ArrowDataType::Duration(_) => {
+            ArrowDataType::Int64
+        }
+        ArrowDataType::FixedSizeBinary(_) => ArrowDataType::Binary,
+        ArrowDataType::Dictionary(_, values, _) => normalize_data_type(format_name, name, values)?,
+        ArrowDataType::List(f) => ArrowDataType::List(Box::new(normalize_field(format_name, f)?)),
+        ArrowDataType::LargeList(f) | ArrowDataType::FixedSizeList(f, _) => {
+            ArrowDataType::LargeList(Box::new(normalize_field(format_name, f)?))
+        }
+        // Map columns only have string keys.
+        ArrowDataType::Map(f, sorted) => match f.data_type() {
+            ArrowDataType::Struct(fields)
+                if fields.len() == 2
+                    && matches!(
+                        fields[0].data_type,
+                        ArrowDataType::Utf8 | ArrowDataType::LargeUtf8
+                    ) =>
+            {
+                ArrowDataType::Map(Box::new(normalize_field(format_name, f)?), *sorted)
+            }
+            _ => {
+                return Err(ErrorCode::BadBytes(format!(
+                    "{format_name} field {name} is a map with non-string keys, which is not supported"
+                )));
+            }
+        },
+        ArrowDataType::Struct(fields) => ArrowDataType::Struct(
+            fields
+                .iter()
+                .map(|f| normalize_field(format_name, f))
+                .collect::<Result<Vec<_>>>()?,
+        ),
+        ArrowDataType::Extension(_, inner, _) => normalize_data_type(format_name, name, inner)?,
+        other => {
+            return Err(ErrorCode::BadBytes(format!(
+                "{format_name} field {name} has unsupported type {other:?}"
+            )));
+        }
+    };
+    Ok(data_type)
+}
+
+/// Infer the table schema of the fields of a file.
+pub fn infer_table_schema(format_name: &str, fields: &[Field]) -> Result<TableSchemaRef> {
+    let fields = fields
+        .iter()
+        .map(|f| normalize_field(format_name, f))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Arc::new(TableSchema::from(&ArrowSchema::from(fields))))
+}
+
+/// Find the field of the file for each column of `schema`.
+///
+/// If the type of the field differs from the column, the field will be cast to the
+/// type of the column, e.g. an `int` field can be loaded into a `BIGINT` column.
+pub fn map_fields(
+    format_name: &str,
+    fields: &[Field],
+    schema: &TableSchemaRef,
+) -> Result<Vec<FieldMapping>> {
+    let mut mappings = Vec::with_capacity(schema.num_fields());
+    for f in schema.fields().iter() {
+        let position = fields
+            .iter()
+            .rposition(|c| c.name.eq_ignore_ascii_case(f.name()))
+            .ok_or_else(|| {
+                ErrorCode::BadBytes(format!(
+                    "schema field size mismatch, expected to find column: {}",
+                    f.name()
+                ))
+            })?;
+        let file_field = &fields[position];
+        let normalized = normalize_field(format_name, file_field)?;
+        let file_type = TableDataType::from(&normalized);
+        let target_type = if file_type.remove_nullable() == f.data_type().remove_nullable()
+            || is_map_of_entries(&file_type, f.data_type())
+        {
+            normalized.data_type
+        } else {
+            let target_type = ArrowDataType::from(f.data_type());
+            if !can_cast_types(&file_field.data_type, &target_type) {
+                return Err(ErrorCode::BadBytes(format!(
+                    "{format_name} field {} of type {} can not be loaded into column {} of type {}",
+                    file_field.name,
+                    file_type,
+                    f.name(),
+                    f.data_type()
+                )));
+            }
+            target_type
+        };
+        mappings.push(FieldMapping {
+            position,
+            target_type,
+        });
+    }
+    Ok(mappings)
+}
+
+/// A map is stored as an array of (key, value) tuples, so a map field can be loaded into
+/// an `ARRAY(TUPLE(STRING, T))` column as it is.
+fn is_map_of_entries(file_type: &TableDataType, column_type: &TableDataType) -> bool {
+    match DataType::from(&file_type.remove_nullable()) {
+        DataType::Map(value_type) => {
+            DataType::Array(Box::new(DataType::Tuple(vec![
+                DataType::String,
+                *value_type,
+            ]))) == DataType::from(&column_type.remove_nullable())
+        }
+        _ => false,
+    }
+}
+
+/// Mark the fields of the file which are used by the mappings.
+pub fn projection(num_fields: usize, mappings: &[FieldMapping]) -> Vec<bool> {
+    let mut projection = vec![false; num_fields];
+    for mapping in mappings {
+        projection[mapping.position] = true;
+    }
+    projection
+}
+
+/// Cast the arrays read from the file to the types expected by the mappings.
+///
+/// `arrays` holds the arrays of the fields in the file, in the order of the file.
+pub fn project_arrays(
+    format_name: &str,
+    arrays: &[Option<Box<dyn Array>>],
+    mappings: &[FieldMapping],
+) -> Result<ArrowChunk<Box<dyn Array>>> {
+    let mut columns = Vec::with_capacity(mappings.len());
+    for mapping in mappings {
+        let array = match arrays[mapping.position].as_ref() {
+            Some(array) => array,
+            None => {
+                return Err(ErrorCode::Internal(format!(
+                    "{format_name} field at position {} is not read",
+                    mapping.position
+                )));
+            }
+        };
+        let array = if array.data_type() == &mapping.target_type {
+            array.clone()
+        } else {
+            cast(array.as_ref(), &mapping.target_type, CastOptions {
+                wrapped: true,
+                partial: false,
+            })?
+        };
+        columns.push(array);
+    }
+    Ok(ArrowChunk::try_new(columns)?)
+}
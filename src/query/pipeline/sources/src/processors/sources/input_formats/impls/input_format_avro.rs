@@ -0,0 +1,558 @@
+//  Copyright 2023 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::any::Any;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::io::Cursor;
+use std::io::SeekFrom;
+use std::ops::Range;
+use std::sync::Arc;
+
+use common_arrow::arrow::datatypes::Field;
+use common_arrow::arrow::io::avro::avro_schema;
+use common_arrow::arrow::io::avro::avro_schema::file::Block;
+use common_arrow::arrow::io::avro::avro_schema::file::CompressedBlock;
+use common_arrow::arrow::io::avro::avro_schema::file::FileMetadata;
+use common_arrow::arrow::io::avro::avro_schema::schema::Schema as AvroSchema;
+use common_arrow::arrow::io::avro::read::deserialize;
+use common_arrow::arrow::io::avro::read::infer_schema;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::DataBlock;
+use common_expression::DataSchema;
+use common_expression::TableSchemaRef;
+use common_io::prelude::read_zigzag_varint;
+use common_meta_types::UserStageInfo;
+use common_pipeline_core::Pipeline;
+use common_settings::Settings;
+use futures::AsyncSeekExt;
+use opendal::Object;
+use opendal::Operator;
+use serde::Deserializer;
+use serde::Serializer;
+
+use crate::processors::sources::input_formats::arrow_field_mapping::infer_table_schema;
+use crate::processors::sources::input_formats::arrow_field_mapping::map_fields;
+use crate::processors::sources::input_formats::arrow_field_mapping::project_arrays;
+use crate::processors::sources::input_formats::arrow_field_mapping::projection;
+use crate::processors::sources::input_formats::arrow_field_mapping::FieldMapping;
+use crate::processors::sources::input_formats::input_context::InputContext;
+use crate::processors::sources::input_formats::input_pipeline::AligningStateTrait;
+use crate::processors::sources::input_formats::input_pipeline::BlockBuilderTrait;
+use crate::processors::sources::input_formats::input_pipeline::InputFormatPipe;
+use crate::processors::sources::input_formats::input_pipeline::RowBatchTrait;
+use crate::processors::sources::input_formats::input_split::split_by_size;
+use crate::processors::sources::input_formats::input_split::DynData;
+use crate::processors::sources::input_formats::input_split::FileInfo;
+use crate::processors::sources::input_formats::input_split::SplitInfo;
+use crate::processors::sources::input_formats::InputFormat;
+
+const FORMAT_NAME: &str = "Avro";
+const SYNC_MARKER_SIZE: usize = 16;
+// A block header is made of two longs, each takes at most 10 bytes.
+const MAX_BLOCK_HEADER_SIZE: usize = 20;
+
+pub struct InputFormatAvro;
+
+fn avro_error(path: &str, e: avro_schema::error::Error) -> ErrorCode {
+    ErrorCode::BadBytes(format!("invalid avro file {}: {:?}", path, e))
+}
+
+#[async_trait::async_trait]
+impl InputFormat for InputFormatAvro {
+    async fn get_splits(
+        &self,
+        files: &[String],
+        stage_info: &UserStageInfo,
+        op: &Operator,
+        _settings: &Arc<Settings>,
+    ) -> Result<Vec<Arc<SplitInfo>>> {
+        let split_size = stage_info.copy_options.split_size;
+        let mut infos = vec![];
+        for path in files {
+            let obj = op.object(path);
+            let size = obj.metadata().await?.content_length() as usize;
+            let mut reader = obj.reader().await?;
+            let metadata = avro_schema::read_async::read_metadata(&mut reader)
+                .await
+                .map_err(|e| avro_error(path, e))?;
+            let header_size = reader.seek(SeekFrom::Current(0)).await? as usize;
+            let file_meta = Arc::new(FileMeta::try_create(metadata)?);
+
+            // Blocks are separated by sync markers, so a split can be located at any
+            // offset of the file, it reads the blocks starting inside of it.
+            let data_size = size - header_size;
+            let split_offsets = if split_size > 0 && data_size > split_size {
+                split_by_size(data_size, split_size)
+            } else {
+                vec![(0, data_size)]
+            };
+            let num_file_splits = split_offsets.len();
+            tracing::debug!(
+                "split avro file {} of size {} to {} splits",
+                path,
+                size,
+                num_file_splits,
+            );
+            let file_info = Arc::new(FileInfo {
+                path: path.clone(),
+                size,
+                num_splits: num_file_splits,
+                compress_alg: None,
+            });
+            for (i, (offset, size)) in split_offsets.into_iter().enumerate() {
+                infos.push(Arc::new(SplitInfo {
+                    file: file_info.clone(),
+                    seq_in_file: i,
+                    offset: header_size + offset,
+                    size,
+                    num_file_splits,
+                    format_info: Some(Arc::new(SplitMeta {
+                        file: file_meta.clone(),
+                    })),
+                }));
+            }
+        }
+        Ok(infos)
+    }
+
+    async fn infer_schema(&self, path: &str, op: &Operator) -> Result<TableSchemaRef> {
+        let obj = op.object(path);
+        let mut reader = obj.reader().await?;
+        let metadata = avro_schema::read_async::read_metadata(&mut reader)
+            .await
+            .map_err(|e| avro_error(path, e))?;
+        let arrow_schema = infer_schema(&metadata.record)?;
+        infer_table_schema(FORMAT_NAME, &arrow_schema.fields)
+    }
+
+    fn exec_copy(&self, ctx: Arc<InputContext>, pipeline: &mut Pipeline) -> Result<()> {
+        AvroFormatPipe::execute_copy_aligned(ctx, pipeline)
+    }
+
+    fn exec_stream(&self, ctx: Arc<InputContext>, pipeline: &mut Pipeline) -> Result<()> {
+        AvroFormatPipe::execute_stream(ctx, pipeline)
+    }
+}
+
+pub struct AvroFormatPipe;
+
+#[async_trait::async_trait]
+impl InputFormatPipe for AvroFormatPipe {
+    type SplitMeta = SplitMeta;
+    type ReadBatch = Vec<u8>;
+    type RowBatch = AvroBlocks;
+    type AligningState = AligningState;
+    type BlockBuilder = AvroBlockBuilder;
+
+    async fn read_split(
+        ctx: Arc<InputContext>,
+        split_info: Arc<SplitInfo>,
+    ) -> Result<Self::RowBatch> {
+        let meta = Self::get_split_meta(&split_info).expect("must success");
+        let file = meta.file.clone();
+        let op = ctx.source.get_operator()?;
+        let obj = op.object(&split_info.file.path);
+        let split_start = split_info.offset;
+        let split_end = split_start + split_info.size;
+
+        // The split reads the blocks starting in [split_start, split_end), the first
+        // one is the block following the first sync marker ending at or after
+        // split_start. The first split starts right after the header.
+        let read_start = if split_info.seq_in_file == 0 {
+            split_start
+        } else {
+            split_start - SYNC_MARKER_SIZE
+        };
+        let mut buffer = SplitBuffer {
+            obj,
+            path: split_info.file.path.clone(),
+            file_size: split_info.file.size,
+            read_ahead: ctx.read_batch_size,
+            start: read_start,
+            data: vec![],
+        };
+        buffer.fill(split_end).await?;
+
+        let mut pos = if split_info.seq_in_file == 0 {
+            split_start
+        } else {
+            let marker = &file.metadata.marker;
+            match buffer.data[..split_info.size + SYNC_MARKER_SIZE - 1]
+                .windows(SYNC_MARKER_SIZE)
+                .position(|w| w == marker)
+            {
+                Some(p) => read_start + p + SYNC_MARKER_SIZE,
+                None => split_end,
+            }
+        };
+
+        let mut blocks = vec![];
+        while pos < split_end {
+            let mut header_end = pos - buffer.start;
+            let (rows, size) = match parse_block_header(&buffer.data, &mut header_end)? {
+                Some(header) => header,
+                None => {
+                    // The header of the last block may be shorter than the max size.
+                    let header_end = std::cmp::min(pos + MAX_BLOCK_HEADER_SIZE, buffer.file_size);
+                    if header_end <= buffer.start + buffer.data.len() {
+                        return Err(ErrorCode::BadBytes(format!(
+                            "invalid avro file {}: unexpected end of file",
+                            buffer.path
+                        )));
+                    }
+                    buffer.fill(header_end).await?;
+                    continue;
+                }
+            };
+            let block_end = buffer.start + header_end + size + SYNC_MARKER_SIZE;
+            buffer.fill(block_end).await?;
+            check_sync_marker(&buffer.data, header_end + size, &file.metadata.marker)?;
+            blocks.push(AvroBlock {
+                rows,
+                range: header_end..header_end + size,
+            });
+            pos = block_end;
+        }
+        tracing::debug!(
+            "read {} blocks from avro split {}",
+            blocks.len(),
+            split_info
+        );
+
+        let mappings = Arc::new(map_fields(FORMAT_NAME, &file.fields, &ctx.schema)?);
+        Ok(AvroBlocks {
+            file,
+            mappings,
+            data: Arc::new(buffer.data),
+            blocks,
+        })
+    }
+}
+
+/// Bytes of an avro file read on demand from the storage.
+struct SplitBuffer {
+    obj: Object,
+    path: String,
+    file_size: usize,
+    read_ahead: usize,
+    // offset of `data` in the file
+    start: usize,
+    data: Vec<u8>,
+}
+
+impl SplitBuffer {
+    /// Make sure the bytes before `end` are in the buffer.
+    async fn fill(&mut self, end: usize) -> Result<()> {
+        let buffer_end = self.start + self.data.len();
+        if end <= buffer_end {
+            return Ok(());
+        }
+        if end > self.file_size {
+            return Err(ErrorCode::BadBytes(format!(
+                "invalid avro file {}: unexpected end of file",
+                self.path
+            )));
+        }
+        let read_end = std::cmp::min(
+            std::cmp::max(end, buffer_end + self.read_ahead),
+            self.file_size,
+        );
+        let data = self
+            .obj
+            .range_read(buffer_end as u64..read_end as u64)
+            .await?;
+        self.data.extend_from_slice(&data);
+        Ok(())
+    }
+}
+
+/// Parse the header of the block at `pos`, which is made of the number of rows and the
+/// size of the data, `pos` is moved to the data of the block.
+/// Returns `None` if `data` ends before the header.
+fn parse_block_header(data: &[u8], pos: &mut usize) -> Result<Option<(usize, usize)>> {
+    let mut p = *pos;
+    let rows = match read_zigzag_varint(data, &mut p)? {
+        Some(rows) => rows,
+        None => return Ok(None),
+    };
+    let size = match read_zigzag_varint(data, &mut p)? {
+        Some(size) => size,
+        None => return Ok(None),
+    };
+    if rows < 0 || size < 0 {
+        return Err(ErrorCode::BadBytes(format!(
+            "invalid avro block: {} rows of {} bytes",
+            rows, size
+        )));
+    }
+    *pos = p;
+    Ok(Some((rows as usize, size as usize)))
+}
+
+fn check_sync_marker(data: &[u8], pos: usize, marker: &[u8; 16]) -> Result<()> {
+    if &data[pos..pos + SYNC_MARKER_SIZE] != marker {
+        return Err(ErrorCode::BadBytes(
+            "invalid avro block: sync marker mismatch",
+        ));
+    }
+    Ok(())
+}
+
+pub struct FileMeta {
+    pub metadata: FileMetadata,
+    // all fields in the avro file
+    pub fields: Vec<Field>,
+    pub avro_schemas: Vec<AvroSchema>,
+}
+
+impl FileMeta {
+    fn try_create(metadata: FileMetadata) -> Result<Self> {
+        let fields = infer_schema(&metadata.record)?.fields;
+        let avro_schemas = metadata
+            .record
+            .fields
+            .iter()
+            .map(|f| f.schema.clone())
+            .collect();
+        Ok(FileMeta {
+            metadata,
+            fields,
+            avro_schemas,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct SplitMeta {
+    pub file: Arc<FileMeta>,
+}
+
+impl Debug for SplitMeta {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "avro split meta")
+    }
+}
+
+impl serde::Serialize for SplitMeta {
+    fn serialize<S>(&self, _serializer: S) -> Result<S::Ok, S::Error>
+    where S: Serializer {
+        unimplemented!()
+    }
+}
+
+impl<'a> serde::Deserialize<'a> for SplitMeta {
+    fn deserialize<D: Deserializer<'a>>(_deserializer: D) -> Result<Self, D::Error> {
+        unimplemented!()
+    }
+}
+
+#[typetag::serde(name = "avro_split")]
+impl DynData for SplitMeta {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct AvroBlock {
+    pub rows: usize,
+    // range of the (maybe compressed) data in `AvroBlocks::data`
+    pub range: Range<usize>,
+}
+
+pub struct AvroBlocks {
+    pub file: Arc<FileMeta>,
+    pub mappings: Arc<Vec<FieldMapping>>,
+    pub data: Arc<Vec<u8>>,
+    pub blocks: Vec<AvroBlock>,
+}
+
+impl RowBatchTrait for AvroBlocks {
+    fn size(&self) -> usize {
+        self.blocks.iter().map(|b| b.range.len()).sum()
+    }
+
+    fn rows(&self) -> usize {
+        self.blocks.iter().map(|b| b.rows).sum()
+    }
+}
+
+impl Debug for AvroBlocks {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "AvroBlocks")
+    }
+}
+
+impl AvroBlocks {
+    fn deserialize(&self, data_schema: &DataSchema) -> Result<Vec<DataBlock>> {
+        let fields = &self.file.fields;
+        let projection = projection(fields.len(), &self.mappings);
+        let mut decompressed = Block::new(0, vec![]);
+        let mut data_blocks = Vec::with_capacity(self.blocks.len());
+        for block in self.blocks.iter() {
+            let mut compressed =
+                CompressedBlock::new(block.rows, self.data[block.range.clone()].to_vec());
+            avro_schema::read::decompress_block(
+                &mut compressed,
+                &mut decompressed,
+                self.file.metadata.compression,
+            )
+            .map_err(|e| ErrorCode::BadBytes(format!("invalid avro block: {:?}", e)))?;
+
+            // The chunk only holds the projected fields, in the order of the file.
+            let chunk = deserialize(&decompressed, fields, &self.file.avro_schemas, &projection)?;
+            let mut projected = chunk.into_arrays().into_iter();
+            let arrays = projection
+                .iter()
+                .map(|used| if *used { projected.next() } else { None })
+                .collect::<Vec<_>>();
+            let chunk = project_arrays(FORMAT_NAME, &arrays, &self.mappings)?;
+            data_blocks.push(DataBlock::from_arrow_chunk(&chunk, data_schema)?);
+        }
+        Ok(data_blocks)
+    }
+}
+
+pub struct AvroBlockBuilder {
+    ctx: Arc<InputContext>,
+}
+
+impl BlockBuilderTrait for AvroBlockBuilder {
+    type Pipe = AvroFormatPipe;
+
+    fn create(ctx: Arc<InputContext>) -> Self {
+        AvroBlockBuilder { ctx }
+    }
+
+    fn deserialize(&mut self, batch: Option<AvroBlocks>) -> Result<Vec<DataBlock>> {
+        if let Some(batch) = batch {
+            let data_blocks = batch.deserialize(&self.ctx.data_schema())?;
+            if data_blocks.is_empty() {
+                return Ok(vec![]);
+            }
+            let block = DataBlock::concat(&data_blocks)?;
+
+            let block_total_rows = block.num_rows();
+            let num_rows_per_block = self.ctx.block_compact_thresholds.max_rows_per_block;
+            let blocks: Vec<DataBlock> = (0..block_total_rows)
+                .step_by(num_rows_per_block)
+                .map(|idx| {
+                    if idx + num_rows_per_block < block_total_rows {
+                        block.slice(idx..idx + num_rows_per_block)
+                    } else {
+                        block.slice(idx..block_total_rows)
+                    }
+                })
+                .collect();
+
+            Ok(blocks)
+        } else {
+            Ok(vec![])
+        }
+    }
+}
+
+/// Used by streaming load, which receives the whole file before deserializing it.
+pub struct AligningState {
+    ctx: Arc<InputContext>,
+    split_info: Arc<SplitInfo>,
+    buffers: Vec<Vec<u8>>,
+}
+
+impl AligningStateTrait for AligningState {
+    type Pipe = AvroFormatPipe;
+
+    fn try_create(ctx: &Arc<InputContext>, split_info: &Arc<SplitInfo>) -> Result<Self> {
+        Ok(AligningState {
+            ctx: ctx.clone(),
+            split_info: split_info.clone(),
+            buffers: vec![],
+        })
+    }
+
+    fn align(&mut self, read_batch: Option<Vec<u8>>) -> Result<Vec<AvroBlocks>> {
+        if let Some(b) = read_batch {
+            self.buffers.push(b);
+            return Ok(vec![]);
+        }
+
+        let path = &self.split_info.file.path;
+        let data = self.buffers.concat();
+        let size = data.len();
+        let mut cursor = Cursor::new(data);
+        let metadata =
+            avro_schema::read::read_metadata(&mut cursor).map_err(|e| avro_error(path, e))?;
+        let header_size = cursor.position() as usize;
+        let file = Arc::new(FileMeta::try_create(metadata)?);
+        let mappings = Arc::new(map_fields(FORMAT_NAME, &file.fields, &self.ctx.schema)?);
+        let data = cursor.into_inner();
+
+        // Group the blocks into batches of about `read_batch_size` bytes, so they
+        // can be deserialized in parallel.
+        let mut batches = vec![];
+        let mut blocks = vec![];
+        let mut batch_size = 0;
+        let mut pos = header_size;
+        while pos < size {
+            let mut header_end = pos;
+            let (rows, block_size) =
+                parse_block_header(&data, &mut header_end)?.ok_or_else(|| {
+                    ErrorCode::BadBytes(format!(
+                        "invalid avro file {}: unexpected end of file",
+                        path
+                    ))
+                })?;
+            let block_end = header_end + block_size + SYNC_MARKER_SIZE;
+            if block_end > size {
+                return Err(ErrorCode::BadBytes(format!(
+                    "invalid avro file {}: unexpected end of file",
+                    path
+                )));
+            }
+            check_sync_marker(&data, header_end + block_size, &file.metadata.marker)?;
+            blocks.push(AvroBlock {
+                rows,
+                range: header_end..header_end + block_size,
+            });
+            batch_size += block_size;
+            pos = block_end;
+            if batch_size >= self.ctx.read_batch_size {
+                batches.push(std::mem::take(&mut blocks));
+                batch_size = 0;
+            }
+        }
+        if !blocks.is_empty() {
+            batches.push(blocks);
+        }
+
+        tracing::info!(
+            "align avro file {} of {} bytes to {} batches",
+            path,
+            size,
+            batches.len()
+        );
+        // The batches share the buffer of the file.
+        let data = Arc::new(data);
+        Ok(batches
+            .into_iter()
+            .map(|blocks| AvroBlocks {
+                file: file.clone(),
+                mappings: mappings.clone(),
+                data: data.clone(),
+                blocks,
+            })
+            .collect())
+    }
+}
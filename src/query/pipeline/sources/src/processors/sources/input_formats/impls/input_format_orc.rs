@@ -0,0 +1,445 @@
+//  Copyright 2023 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::any::Any;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::sync::Arc;
+
+use common_arrow::arrow::datatypes::DataType as ArrowDataType;
+use common_arrow::arrow::datatypes::Field;
+use common_arrow::arrow::io::orc::format;
+use common_arrow::arrow::io::orc::format::read::FileMetadata;
+use common_arrow::arrow::io::orc::read::deserialize;
+use common_arrow::arrow::io::orc::read::infer_schema;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::DataBlock;
+use common_expression::DataSchema;
+use common_expression::TableSchemaRef;
+use common_meta_types::UserStageInfo;
+use common_pipeline_core::Pipeline;
+use common_settings::Settings;
+use opendal::Operator;
+use serde::Deserializer;
+use serde::Serializer;
+
+use crate::processors::sources::input_formats::arrow_field_mapping::infer_table_schema;
+use crate::processors::sources::input_formats::arrow_field_mapping::map_fields;
+use crate::processors::sources::input_formats::arrow_field_mapping::project_arrays;
+use crate::processors::sources::input_formats::arrow_field_mapping::FieldMapping;
+use crate::processors::sources::input_formats::input_context::InputContext;
+use crate::processors::sources::input_formats::input_pipeline::AligningStateTrait;
+use crate::processors::sources::input_formats::input_pipeline::BlockBuilderTrait;
+use crate::processors::sources::input_formats::input_pipeline::InputFormatPipe;
+use crate::processors::sources::input_formats::input_pipeline::RowBatchTrait;
+use crate::processors::sources::input_formats::input_split::DynData;
+use crate::processors::sources::input_formats::input_split::FileInfo;
+use crate::processors::sources::input_formats::input_split::SplitInfo;
+use crate::processors::sources::input_formats::InputFormat;
+
+const FORMAT_NAME: &str = "ORC";
+// The metadata of an ORC file is at the end of the file, read the tail of the file
+// first to avoid reading the whole file in most cases.
+const TAIL_SIZE: usize = 256 * 1024;
+
+pub struct InputFormatOrc;
+
+fn orc_error(path: &str, e: format::error::Error) -> ErrorCode {
+    ErrorCode::BadBytes(format!("invalid orc file {}: {:?}", path, e))
+}
+
+async fn read_file_meta(op: &Operator, path: &str) -> Result<(usize, FileMeta)> {
+    let obj = op.object(path);
+    let size = obj.metadata().await?.content_length() as usize;
+    let tail_offset = size - std::cmp::min(size, TAIL_SIZE);
+    let tail = obj.range_read(tail_offset as u64..size as u64).await?;
+    let mut reader = PartialFileReader::new(tail_offset, size, Arc::new(tail));
+    let metadata = match format::read::read_metadata(&mut reader) {
+        Ok(metadata) => metadata,
+        Err(_) if tail_offset > 0 => {
+            // the metadata is larger than the tail
+            let data = obj.read().await?;
+            let mut reader = PartialFileReader::new(0, size, Arc::new(data));
+            format::read::read_metadata(&mut reader).map_err(|e| orc_error(path, e))?
+        }
+        Err(e) => return Err(orc_error(path, e)),
+    };
+    Ok((size, FileMeta::try_create(metadata)?))
+}
+
+#[async_trait::async_trait]
+impl InputFormat for InputFormatOrc {
+    async fn get_splits(
+        &self,
+        files: &[String],
+        _stage_info: &UserStageInfo,
+        op: &Operator,
+        _settings: &Arc<Settings>,
+    ) -> Result<Vec<Arc<SplitInfo>>> {
+        let mut infos = vec![];
+        for path in files {
+            let (size, file_meta) = read_file_meta(op, path).await?;
+            let file_meta = Arc::new(file_meta);
+            let stripes = &file_meta.metadata.footer.stripes;
+
+            let file_info = Arc::new(FileInfo {
+                path: path.clone(),
+                size,
+                num_splits: stripes.len(),
+                compress_alg: None,
+            });
+
+            // Each stripe is a split.
+            let num_file_splits = stripes.len();
+            for (i, stripe) in stripes.iter().enumerate() {
+                let meta = Arc::new(SplitMeta {
+                    file: file_meta.clone(),
+                    stripe: i,
+                });
+                infos.push(Arc::new(SplitInfo {
+                    file: file_info.clone(),
+                    seq_in_file: i,
+                    offset: stripe.offset() as usize,
+                    size: (stripe.index_length() + stripe.data_length() + stripe.footer_length())
+                        as usize,
+                    num_file_splits,
+                    format_info: Some(meta),
+                }));
+            }
+        }
+        Ok(infos)
+    }
+
+    async fn infer_schema(&self, path: &str, op: &Operator) -> Result<TableSchemaRef> {
+        let (_, file_meta) = read_file_meta(op, path).await?;
+        infer_table_schema(FORMAT_NAME, &file_meta.fields)
+    }
+
+    fn exec_copy(&self, ctx: Arc<InputContext>, pipeline: &mut Pipeline) -> Result<()> {
+        OrcFormatPipe::execute_copy_aligned(ctx, pipeline)
+    }
+
+    fn exec_stream(&self, ctx: Arc<InputContext>, pipeline: &mut Pipeline) -> Result<()> {
+        OrcFormatPipe::execute_stream(ctx, pipeline)
+    }
+}
+
+pub struct OrcFormatPipe;
+
+#[async_trait::async_trait]
+impl InputFormatPipe for OrcFormatPipe {
+    type SplitMeta = SplitMeta;
+    type ReadBatch = Vec<u8>;
+    type RowBatch = StripeInMemory;
+    type AligningState = AligningState;
+    type BlockBuilder = OrcBlockBuilder;
+
+    async fn read_split(
+        ctx: Arc<InputContext>,
+        split_info: Arc<SplitInfo>,
+    ) -> Result<Self::RowBatch> {
+        let meta = Self::get_split_meta(&split_info).expect("must success");
+        let op = ctx.source.get_operator()?;
+        let obj = op.object(&split_info.file.path);
+        let offset = split_info.offset as u64;
+        let data = obj
+            .range_read(offset..offset + split_info.size as u64)
+            .await?;
+        let mappings = Arc::new(get_mappings(&meta.file, &ctx.schema)?);
+        Ok(StripeInMemory {
+            file: meta.file.clone(),
+            mappings,
+            stripe: meta.stripe,
+            path: split_info.file.path.clone(),
+            offset: split_info.offset,
+            data: Arc::new(data),
+        })
+    }
+}
+
+fn get_mappings(file: &FileMeta, schema: &TableSchemaRef) -> Result<Vec<FieldMapping>> {
+    let mappings = map_fields(FORMAT_NAME, &file.fields, schema)?;
+    for mapping in mappings.iter() {
+        let field = &file.fields[mapping.position];
+        if matches!(
+            field.data_type,
+            ArrowDataType::List(_)
+                | ArrowDataType::LargeList(_)
+                | ArrowDataType::Map(_, _)
+                | ArrowDataType::Struct(_)
+        ) {
+            return Err(ErrorCode::Unimplemented(format!(
+                "loading nested ORC field {} of type {:?} is not supported yet",
+                field.name, field.data_type
+            )));
+        }
+    }
+    Ok(mappings)
+}
+
+/// A reader of an ORC file, of which only the bytes in [offset, offset + data.len())
+/// are in memory.
+struct PartialFileReader {
+    offset: usize,
+    file_size: usize,
+    data: Arc<Vec<u8>>,
+    pos: usize,
+}
+
+impl PartialFileReader {
+    fn new(offset: usize, file_size: usize, data: Arc<Vec<u8>>) -> Self {
+        PartialFileReader {
+            offset,
+            file_size,
+            data,
+            pos: offset,
+        }
+    }
+}
+
+impl Read for PartialFileReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.pos < self.offset || self.pos > self.offset + self.data.len() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                format!(
+                    "read at {} out of the range [{}, {})",
+                    self.pos,
+                    self.offset,
+                    self.offset + self.data.len()
+                ),
+            ));
+        }
+        let remain = &self.data[self.pos - self.offset..];
+        let n = std::cmp::min(remain.len(), buf.len());
+        buf[..n].copy_from_slice(&remain[..n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+impl Seek for PartialFileReader {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let pos = match pos {
+            SeekFrom::Start(p) => p as i64,
+            SeekFrom::End(p) => self.file_size as i64 + p,
+            SeekFrom::Current(p) => self.pos as i64 + p,
+        };
+        if pos < 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "seek before the start of the file",
+            ));
+        }
+        self.pos = pos as usize;
+        Ok(self.pos as u64)
+    }
+}
+
+pub struct FileMeta {
+    pub metadata: FileMetadata,
+    // all top level fields in the orc file
+    pub fields: Vec<Field>,
+}
+
+impl FileMeta {
+    fn try_create(metadata: FileMetadata) -> Result<Self> {
+        let fields = infer_schema(&metadata.footer)?.fields;
+        Ok(FileMeta { metadata, fields })
+    }
+
+    /// Id of the column of a top level field, the id 0 is the root struct.
+    fn column_id(&self, position: usize) -> u32 {
+        self.metadata.footer.types[0].subtypes[position]
+    }
+}
+
+#[derive(Clone)]
+pub struct SplitMeta {
+    pub file: Arc<FileMeta>,
+    pub stripe: usize,
+}
+
+impl Debug for SplitMeta {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "orc split meta")
+    }
+}
+
+impl serde::Serialize for SplitMeta {
+    fn serialize<S>(&self, _serializer: S) -> Result<S::Ok, S::Error>
+    where S: Serializer {
+        unimplemented!()
+    }
+}
+
+impl<'a> serde::Deserialize<'a> for SplitMeta {
+    fn deserialize<D: Deserializer<'a>>(_deserializer: D) -> Result<Self, D::Error> {
+        unimplemented!()
+    }
+}
+
+#[typetag::serde(name = "orc_split")]
+impl DynData for SplitMeta {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct StripeInMemory {
+    pub file: Arc<FileMeta>,
+    pub mappings: Arc<Vec<FieldMapping>>,
+    pub stripe: usize,
+    pub path: String,
+    // offset of `data` in the file
+    pub offset: usize,
+    pub data: Arc<Vec<u8>>,
+}
+
+impl RowBatchTrait for StripeInMemory {
+    fn size(&self) -> usize {
+        let stripe = &self.file.metadata.footer.stripes[self.stripe];
+        (stripe.index_length() + stripe.data_length() + stripe.footer_length()) as usize
+    }
+
+    fn rows(&self) -> usize {
+        self.file.metadata.footer.stripes[self.stripe].number_of_rows() as usize
+    }
+}
+
+impl Debug for StripeInMemory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "StripeInMemory")
+    }
+}
+
+impl StripeInMemory {
+    fn deserialize(&self, data_schema: &DataSchema) -> Result<DataBlock> {
+        let fields = &self.file.fields;
+        let mut reader = PartialFileReader::new(self.offset, self.file_size(), self.data.clone());
+        let mut arrays = (0..fields.len()).map(|_| None).collect::<Vec<_>>();
+        let mut scratch = vec![];
+        for mapping in self.mappings.iter() {
+            let column = format::read::read_stripe_column(
+                &mut reader,
+                &self.file.metadata,
+                self.stripe,
+                self.file.column_id(mapping.position),
+                scratch,
+            )
+            .map_err(|e| orc_error(&self.path, e))?;
+            let array = deserialize(fields[mapping.position].data_type.clone(), &column)?;
+            scratch = column.into_inner();
+            arrays[mapping.position] = Some(array);
+        }
+        let chunk = project_arrays(FORMAT_NAME, &arrays, &self.mappings)?;
+        DataBlock::from_arrow_chunk(&chunk, data_schema)
+    }
+
+    fn file_size(&self) -> usize {
+        // The postscript is never read when reading a stripe.
+        self.offset + self.data.len()
+    }
+}
+
+pub struct OrcBlockBuilder {
+    ctx: Arc<InputContext>,
+}
+
+impl BlockBuilderTrait for OrcBlockBuilder {
+    type Pipe = OrcFormatPipe;
+
+    fn create(ctx: Arc<InputContext>) -> Self {
+        OrcBlockBuilder { ctx }
+    }
+
+    fn deserialize(&mut self, batch: Option<StripeInMemory>) -> Result<Vec<DataBlock>> {
+        if let Some(stripe) = batch {
+            let block = stripe.deserialize(&self.ctx.data_schema())?;
+
+            let block_total_rows = block.num_rows();
+            let num_rows_per_block = self.ctx.block_compact_thresholds.max_rows_per_block;
+            let blocks: Vec<DataBlock> = (0..block_total_rows)
+                .step_by(num_rows_per_block)
+                .map(|idx| {
+                    if idx + num_rows_per_block < block_total_rows {
+                        block.slice(idx..idx + num_rows_per_block)
+                    } else {
+                        block.slice(idx..block_total_rows)
+                    }
+                })
+                .collect();
+
+            Ok(blocks)
+        } else {
+            Ok(vec![])
+        }
+    }
+}
+
+/// Used by streaming load, which receives the whole file before deserializing it.
+pub struct AligningState {
+    ctx: Arc<InputContext>,
+    split_info: Arc<SplitInfo>,
+    buffers: Vec<Vec<u8>>,
+}
+
+impl AligningStateTrait for AligningState {
+    type Pipe = OrcFormatPipe;
+
+    fn try_create(ctx: &Arc<InputContext>, split_info: &Arc<SplitInfo>) -> Result<Self> {
+        Ok(AligningState {
+            ctx: ctx.clone(),
+            split_info: split_info.clone(),
+            buffers: vec![],
+        })
+    }
+
+    fn align(&mut self, read_batch: Option<Vec<u8>>) -> Result<Vec<StripeInMemory>> {
+        if let Some(b) = read_batch {
+            self.buffers.push(b);
+            return Ok(vec![]);
+        }
+
+        let path = &self.split_info.file.path;
+        let data = Arc::new(self.buffers.concat());
+        let size = data.len();
+        let mut reader = PartialFileReader::new(0, size, data.clone());
+        let metadata = format::read::read_metadata(&mut reader).map_err(|e| orc_error(path, e))?;
+        let file = Arc::new(FileMeta::try_create(metadata)?);
+        let mappings = Arc::new(get_mappings(&file, &self.ctx.schema)?);
+
+        let num_stripes = file.metadata.footer.stripes.len();
+        tracing::info!(
+            "align orc file {} of {} bytes to {} stripes",
+            path,
+            size,
+            num_stripes
+        );
+        Ok((0..num_stripes)
+            .map(|stripe| StripeInMemory {
+                file: file.clone(),
+                mappings: mappings.clone(),
+                stripe,
+                path: path.clone(),
+                offset: 0,
+                data: data.clone(),
+            })
+            .collect())
+    }
+}
@@ -12,8 +12,10 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+pub mod input_format_avro;
 pub mod input_format_csv;
 pub mod input_format_ndjson;
+pub mod input_format_orc;
 pub mod input_format_parquet;
 pub mod input_format_tsv;
 pub mod input_format_xml;
@@ -36,8 +36,10 @@ use common_settings::Settings;
 use opendal::raw::CompressAlgorithm;
 use opendal::Operator;
 
+use crate::processors::sources::input_formats::impls::input_format_avro::InputFormatAvro;
 use crate::processors::sources::input_formats::impls::input_format_csv::InputFormatCSV;
 use crate::processors::sources::input_formats::impls::input_format_ndjson::InputFormatNDJson;
+use crate::processors::sources::input_formats::impls::input_format_orc::InputFormatOrc;
 use crate::processors::sources::input_formats::impls::input_format_parquet::InputFormatParquet;
 use crate::processors::sources::input_formats::impls::input_format_tsv::InputFormatTSV;
 use crate::processors::sources::input_formats::impls::input_format_xml::InputFormatXML;
@@ -139,6 +141,8 @@ impl InputContext {
             StageFileFormatType::NdJson => Ok(Arc::new(InputFormatNDJson::create())),
             StageFileFormatType::Parquet => Ok(Arc::new(InputFormatParquet {})),
             StageFileFormatType::Xml => Ok(Arc::new(InputFormatXML::create())),
+            StageFileFormatType::Avro => Ok(Arc::new(InputFormatAvro {})),
+            StageFileFormatType::Orc => Ok(Arc::new(InputFormatOrc {})),
             format => Err(ErrorCode::Internal(format!(
                 "Unsupported file format: {:?}",
                 format
@@ -12,6 +12,7 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+mod arrow_field_mapping;
 mod beyond_end_reader;
 mod impls;
 mod input_context;
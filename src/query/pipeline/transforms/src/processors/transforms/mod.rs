@@ -20,6 +20,7 @@ pub mod transform_limit;
 pub mod transform_multi_sort_merge;
 pub mod transform_sort_merge;
 pub mod transform_sort_partial;
+pub mod transform_sort_spill;
 
 pub use transform::*;
 pub use transform_block_compact::*;
@@ -28,3 +29,4 @@ pub use transform_limit::*;
 pub use transform_multi_sort_merge::*;
 pub use transform_sort_merge::*;
 pub use transform_sort_partial::*;
+pub use transform_sort_spill::*;
@@ -80,7 +80,7 @@ pub fn try_add_multi_sort_merge(
 }
 
 /// A cursor point to a certain row in a data block.
-struct Cursor {
+pub(crate) struct Cursor {
     pub input_index: usize,
     pub row_index: usize,
 
@@ -0,0 +1,388 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::ErrorKind;
+use std::io::Read;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Instant;
+
+use common_arrow::arrow::compute::sort::row::RowConverter;
+use common_arrow::arrow::compute::sort::row::SortField;
+use common_arrow::arrow::compute::sort::SortOptions;
+use common_arrow::arrow::datatypes::DataType as ArrowDataType;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::utils::arrow::column_to_arrow_array;
+use common_expression::utils::arrow::deserialize_block;
+use common_expression::utils::arrow::serialize_block;
+use common_expression::DataBlock;
+use common_expression::DataSchemaRef;
+use common_expression::SortColumnDescription;
+use common_pipeline_core::processors::port::InputPort;
+use common_pipeline_core::processors::port::OutputPort;
+use common_pipeline_core::processors::processor::Event;
+use common_pipeline_core::processors::processor::ProcessorPtr;
+use common_pipeline_core::processors::Processor;
+use tracing::info;
+use uuid::Uuid;
+
+use super::transform_multi_sort_merge::Cursor;
+use crate::processors::transforms::Aborting;
+
+/// A sorted run, which is either spilled to a local file or kept in memory.
+enum SortedRun {
+    /// Blocks in the file are prefixed by their length in bytes.
+    Spilled(BufReader<File>),
+    Memory(VecDeque<DataBlock>),
+}
+
+impl SortedRun {
+    fn next_block(&mut self) -> Result<Option<DataBlock>> {
+        match self {
+            SortedRun::Memory(blocks) => Ok(blocks.pop_front()),
+            SortedRun::Spilled(reader) => {
+                let mut length = [0; 8];
+                match reader.read_exact(&mut length) {
+                    Ok(_) => {}
+                    Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+                    Err(e) => return Err(e.into()),
+                }
+                let mut data = vec![0; u64::from_le_bytes(length) as usize];
+                reader.read_exact(&mut data)?;
+                Ok(Some(deserialize_block(&data)?))
+            }
+        }
+    }
+}
+
+enum SpillState {
+    /// Consume the partially sorted blocks, the buffered blocks are spilled as a sorted run
+    /// when they exceed the memory budget.
+    Consume,
+    /// All the input blocks are consumed, merge the sorted runs.
+    Merge,
+    Finished,
+}
+
+/// An external merge sort in replacement of `TransformSortMerge` for the sort without limit.
+///
+/// The partially sorted blocks are buffered until they exceed `bytes_threshold`, then they are
+/// merged into one sorted run and written to `spill_dir`. When the input is finished, all the
+/// sorted runs are merged by a k-way merge and streamed out in blocks of `block_size` rows, so
+/// that only the current block of each run is kept in memory.
+pub struct TransformSortSpill {
+    input: Arc<InputPort>,
+    output: Arc<OutputPort>,
+    input_data: Option<DataBlock>,
+    output_data: VecDeque<DataBlock>,
+    state: SpillState,
+
+    block_size: usize,
+    bytes_threshold: usize,
+    spill_dir: PathBuf,
+    sort_columns_descriptions: Vec<SortColumnDescription>,
+    row_converter: RowConverter,
+
+    buffered_blocks: Vec<DataBlock>,
+    buffered_bytes: usize,
+    runs: Vec<SortedRun>,
+    /// The current block of each run.
+    current_blocks: Vec<Option<DataBlock>>,
+    /// Heap that yields the cursors of the runs in increasing order.
+    heap: BinaryHeap<Reverse<Cursor>>,
+
+    aborting: Arc<AtomicBool>,
+}
+
+impl TransformSortSpill {
+    pub fn try_create(
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+        output_schema: DataSchemaRef,
+        block_size: usize,
+        bytes_threshold: usize,
+        spill_dir: PathBuf,
+        sort_columns_descriptions: Vec<SortColumnDescription>,
+    ) -> Result<ProcessorPtr> {
+        let sort_fields = sort_columns_descriptions
+            .iter()
+            .map(|d| {
+                SortField::new_with_options(
+                    ArrowDataType::from(output_schema.field(d.offset).data_type()),
+                    SortOptions {
+                        descending: !d.asc,
+                        nulls_first: d.nulls_first,
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+
+        Ok(ProcessorPtr::create(Box::new(TransformSortSpill {
+            input,
+            output,
+            input_data: None,
+            output_data: VecDeque::new(),
+            state: SpillState::Consume,
+            block_size,
+            bytes_threshold,
+            spill_dir,
+            sort_columns_descriptions,
+            row_converter: RowConverter::new(sort_fields),
+            buffered_blocks: vec![],
+            buffered_bytes: 0,
+            runs: vec![],
+            current_blocks: vec![],
+            heap: BinaryHeap::new(),
+            aborting: Arc::new(AtomicBool::new(false)),
+        })))
+    }
+
+    /// Merges the buffered blocks into one sorted run, split by `block_size`.
+    fn merge_buffered_blocks(&mut self) -> Result<VecDeque<DataBlock>> {
+        let blocks = std::mem::take(&mut self.buffered_blocks);
+        self.buffered_bytes = 0;
+        if blocks.is_empty() {
+            return Ok(VecDeque::new());
+        }
+
+        let aborting = self.aborting.clone();
+        let aborting: Aborting = Arc::new(Box::new(move || aborting.load(Ordering::Relaxed)));
+        let block =
+            DataBlock::merge_sort(&blocks, &self.sort_columns_descriptions, None, aborting)?;
+
+        let num_rows = block.num_rows();
+        let mut output = VecDeque::with_capacity(num_rows / self.block_size + 1);
+        let mut start = 0;
+        while start < num_rows {
+            let end = std::cmp::min(start + self.block_size, num_rows);
+            output.push_back(block.slice(start..end));
+            start = end;
+        }
+        Ok(output)
+    }
+
+    fn spill_buffered_blocks(&mut self) -> Result<()> {
+        let instant = Instant::now();
+        let blocks = self.merge_buffered_blocks()?;
+
+        std::fs::create_dir_all(&self.spill_dir)?;
+        let path = self.spill_dir.join(Uuid::new_v4().simple().to_string());
+        let mut writer = BufWriter::new(File::create(&path)?);
+        let mut bytes = 0;
+        for block in blocks {
+            let data = serialize_block(block)?;
+            writer.write_all(&(data.len() as u64).to_le_bytes())?;
+            writer.write_all(&data)?;
+            bytes += data.len();
+        }
+        writer.flush()?;
+
+        info!(
+            "Spill sorted run({} bytes) to {:?}, elapsed: {:?}",
+            bytes,
+            path,
+            instant.elapsed()
+        );
+        self.runs
+            .push(SortedRun::Spilled(BufReader::new(File::open(&path)?)));
+        Ok(())
+    }
+
+    /// Reads the next block of the run and pushes its cursor into the heap.
+    fn next_run_block(&mut self, run_index: usize) -> Result<Option<DataBlock>> {
+        self.current_blocks[run_index] = None;
+        while let Some(block) = self.runs[run_index].next_block()? {
+            if block.is_empty() {
+                continue;
+            }
+
+            let columns = self
+                .sort_columns_descriptions
+                .iter()
+                .map(|d| column_to_arrow_array(block.get_by_offset(d.offset), block.num_rows()))
+                .collect::<Vec<_>>();
+            let rows = self.row_converter.convert_columns(&columns)?;
+            self.heap.push(Reverse(Cursor::try_create(run_index, rows)));
+            self.current_blocks[run_index] = Some(block.clone());
+            return Ok(Some(block));
+        }
+        Ok(None)
+    }
+
+    fn start_merge(&mut self) -> Result<()> {
+        if self.runs.is_empty() {
+            // Nothing is spilled, the same as `TransformSortMerge`.
+            self.output_data = self.merge_buffered_blocks()?;
+            self.state = SpillState::Finished;
+            return Ok(());
+        }
+
+        let blocks = self.merge_buffered_blocks()?;
+        self.runs.push(SortedRun::Memory(blocks));
+        self.current_blocks = vec![None; self.runs.len()];
+        for run_index in 0..self.runs.len() {
+            self.next_run_block(run_index)?;
+        }
+        self.state = SpillState::Merge;
+        Ok(())
+    }
+
+    /// Pops the smallest rows from the heap to build the next output block.
+    fn merge_next_block(&mut self) -> Result<Option<DataBlock>> {
+        let mut blocks = Vec::with_capacity(self.runs.len());
+        let mut block_indices = vec![0; self.runs.len()];
+        for (run_index, block) in self.current_blocks.iter().enumerate() {
+            if let Some(block) = block {
+                block_indices[run_index] = blocks.len();
+                blocks.push(block.clone());
+            }
+        }
+
+        // (block index, start row, length)
+        let mut slices: Vec<(usize, usize, usize)> = vec![];
+        let mut num_rows = 0;
+        while num_rows < self.block_size {
+            let Reverse(mut cursor) = match self.heap.pop() {
+                Some(cursor) => cursor,
+                None => break,
+            };
+            let run_index = cursor.input_index;
+            let block_index = block_indices[run_index];
+            let row_index = cursor.advance();
+            match slices.last_mut() {
+                Some((index, start, len))
+                    if *index == block_index && *start + *len == row_index =>
+                {
+                    *len += 1
+                }
+                _ => slices.push((block_index, row_index, 1)),
+            }
+            num_rows += 1;
+
+            if cursor.is_finished() {
+                if let Some(block) = self.next_run_block(run_index)? {
+                    block_indices[run_index] = blocks.len();
+                    blocks.push(block);
+                }
+            } else {
+                self.heap.push(Reverse(cursor));
+            }
+        }
+
+        if num_rows == 0 {
+            return Ok(None);
+        }
+        Ok(Some(DataBlock::take_by_slices_limit_from_blocks(
+            &blocks, &slices, None,
+        )))
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for TransformSortSpill {
+    fn name(&self) -> String {
+        "SortSpillTransform".to_string()
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn interrupt(&self) {
+        self.aborting.store(true, Ordering::Release);
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if self.aborting.load(Ordering::Relaxed) {
+            return Err(ErrorCode::AbortedQuery(
+                "Aborted query, because the server is shutting down or the query was killed.",
+            ));
+        }
+
+        if self.output.is_finished() {
+            self.input.finish();
+            return Ok(Event::Finished);
+        }
+
+        if !self.output.can_push() {
+            self.input.set_not_need_data();
+            return Ok(Event::NeedConsume);
+        }
+
+        if let Some(data_block) = self.output_data.pop_front() {
+            self.output.push_data(Ok(data_block));
+            return Ok(Event::NeedConsume);
+        }
+
+        match self.state {
+            SpillState::Consume => {
+                if self.input_data.is_some() {
+                    return Ok(Event::Sync);
+                }
+
+                if self.input.has_data() {
+                    self.input_data = Some(self.input.pull_data().unwrap()?);
+                    return Ok(Event::Sync);
+                }
+
+                if self.input.is_finished() {
+                    return Ok(Event::Sync);
+                }
+
+                self.input.set_need_data();
+                Ok(Event::NeedData)
+            }
+            SpillState::Merge => Ok(Event::Sync),
+            SpillState::Finished => {
+                self.output.finish();
+                Ok(Event::Finished)
+            }
+        }
+    }
+
+    fn process(&mut self) -> Result<()> {
+        match self.state {
+            SpillState::Consume => match self.input_data.take() {
+                Some(data_block) => {
+                    self.buffered_bytes += data_block.memory_size();
+                    self.buffered_blocks.push(data_block);
+                    if self.buffered_bytes >= self.bytes_threshold {
+                        self.spill_buffered_blocks()?;
+                    }
+                    Ok(())
+                }
+                None => self.start_merge(),
+            },
+            SpillState::Merge => {
+                match self.merge_next_block()? {
+                    Some(data_block) => self.output_data.push_back(data_block),
+                    None => self.state = SpillState::Finished,
+                }
+                Ok(())
+            }
+            SpillState::Finished => Ok(()),
+        }
+    }
+}
@@ -12,8 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
+use common_ast::ast::UriLocation;
 use common_base::base::GlobalInstance;
 use common_catalog::catalog::Catalog;
 pub use common_catalog::catalog::CatalogManager;
@@ -25,8 +27,11 @@ use common_exception::Result;
 use common_meta_app::schema::CatalogType;
 use common_meta_app::schema::CreateCatalogReq;
 use common_meta_app::schema::DropCatalogReq;
+use common_sql::binder::parse_uri_location;
+use common_storage::StorageParams;
 #[cfg(feature = "hive")]
 use common_storages_hive::HiveCatalog;
+use common_storages_iceberg::IcebergCatalog;
 use dashmap::DashMap;
 
 use crate::catalogs::DatabaseCatalog;
@@ -133,6 +138,14 @@ impl CatalogManagerHelper for CatalogManager {
                     self.insert_catalog(ctl_name, catalog, if_not_exists)
                 }
             }
+            CatalogType::Iceberg => {
+                let storage_params = iceberg_storage_params(&req.meta.options)?;
+                let ctl_name = &req.name_ident.catalog_name;
+                let catalog: Arc<dyn Catalog> =
+                    Arc::new(IcebergCatalog::try_create(ctl_name, storage_params)?);
+
+                self.insert_catalog(ctl_name, catalog, req.if_not_exists)
+            }
         }
     }
 
@@ -156,3 +169,52 @@ impl CatalogManagerHelper for CatalogManager {
         }
     }
 }
+
+/// The root of an iceberg catalog is given by the `URL` option, the other options
+/// are the connection of the storage, the same as the ones of an external location.
+fn iceberg_storage_params(options: &BTreeMap<String, String>) -> Result<StorageParams> {
+    let mut conns = options.clone();
+    let url = conns
+        .remove("url")
+        .ok_or_else(|| ErrorCode::InvalidArgument("expected field: URL"))?;
+    // The warehouse is always a directory.
+    let url = if url.ends_with('/') {
+        url
+    } else {
+        format!("{}/", url)
+    };
+
+    let mut location = match url.strip_prefix("fs://") {
+        Some(path) => UriLocation::new(
+            "fs".to_string(),
+            "".to_string(),
+            path.to_string(),
+            "".to_string(),
+            conns,
+        ),
+        None => {
+            let parsed = url::Url::parse(&url).map_err(|e| {
+                ErrorCode::InvalidArgument(format!("invalid iceberg catalog url {}: {}", url, e))
+            })?;
+            let name = match (parsed.host_str(), parsed.port()) {
+                (Some(host), Some(port)) => format!("{}:{}", host, port),
+                (Some(host), None) => host.to_string(),
+                (None, _) => {
+                    return Err(ErrorCode::InvalidArgument(format!(
+                        "invalid iceberg catalog url {}",
+                        url
+                    )));
+                }
+            };
+            UriLocation::new(
+                parsed.scheme().to_string(),
+                name,
+                parsed.path().to_string(),
+                "".to_string(),
+                conns,
+            )
+        }
+    };
+    let (storage_params, _) = parse_uri_location(&mut location)?;
+    Ok(storage_params)
+}
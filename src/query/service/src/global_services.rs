@@ -29,6 +29,7 @@ use storages_common_table_meta::caches::CacheManager;
 use crate::api::DataExchangeManager;
 use crate::catalogs::CatalogManagerHelper;
 use crate::clusters::ClusterDiscovery;
+use crate::maintenance::MaintenanceService;
 use crate::servers::http::v1::HttpQueryManager;
 use crate::sessions::SessionManager;
 
@@ -74,6 +75,7 @@ impl GlobalServices {
         )
         .await?;
         RoleCacheManager::init()?;
+        MaintenanceService::init(&config)?;
 
         Ok(())
     }
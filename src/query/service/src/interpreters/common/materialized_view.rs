@@ -0,0 +1,341 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_ast::parser::parse_sql;
+use common_ast::parser::tokenize_sql;
+use common_ast::Backtrace;
+use common_catalog::catalog::CatalogManager;
+use common_catalog::table::Table;
+use common_catalog::table_context::TableContext;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::DataBlock;
+use common_meta_app::schema::UpsertTableOptionReq;
+use common_meta_types::MatchSeq;
+use common_sql::optimizer::optimize;
+use common_sql::optimizer::OptimizerConfig;
+use common_sql::optimizer::OptimizerContext;
+use common_sql::Binder;
+use common_sql::Metadata;
+use common_sql::NameResolutionContext;
+use common_storages_fuse::FuseTable;
+use futures::TryStreamExt;
+use parking_lot::RwLock;
+use storages_common_table_meta::table::OPT_KEY_MATERIALIZED_VIEWS;
+use storages_common_table_meta::table::OPT_KEY_MATERIALIZED_VIEW_QUERY;
+use storages_common_table_meta::table::OPT_KEY_MATERIALIZED_VIEW_SOURCE;
+use storages_common_table_meta::table::OPT_KEY_MATERIALIZED_VIEW_STALE;
+use tracing::error;
+use tracing::info;
+
+use crate::interpreters::InsertInterpreterV2;
+use crate::interpreters::Interpreter;
+use crate::sessions::QueryContext;
+use crate::sql::plans::insert::Insert;
+use crate::sql::plans::insert::InsertInputSource;
+
+/// `(catalog, database, table)` of a table.
+type TableRef = (String, String, String);
+
+/// The materialized views maintained on insertion into `table`.
+pub fn materialized_views(table: &dyn Table) -> Result<Vec<TableRef>> {
+    match table.options().get(OPT_KEY_MATERIALIZED_VIEWS) {
+        Some(views) => Ok(serde_json::from_str(views)?),
+        None => Ok(vec![]),
+    }
+}
+
+/// Register the materialized view `view` on its source table, so that insertions
+/// into the source table are also applied to the view.
+pub async fn register_materialized_view(
+    ctx: &QueryContext,
+    source: &TableRef,
+    view: TableRef,
+) -> Result<()> {
+    let (catalog, database, table) = source;
+    let tenant = ctx.get_tenant();
+    let catalog = ctx.get_catalog(catalog)?;
+    // Read the table from catalog, since the cached one may be stale.
+    let table = catalog.get_table(&tenant, database, table).await?;
+
+    let mut views = materialized_views(table.as_ref())?;
+    if !views.contains(&view) {
+        views.push(view);
+    }
+    let req = UpsertTableOptionReq::new(
+        &table.get_table_info().ident,
+        OPT_KEY_MATERIALIZED_VIEWS,
+        serde_json::to_string(&views)?,
+    );
+    catalog.upsert_table_option(&tenant, database, req).await?;
+    Ok(())
+}
+
+/// Max attempts to update the staleness of a materialized view that is concurrently updated.
+const MAX_STALE_UPDATE_ATTEMPTS: usize = 10;
+
+/// Mark the materialized view `(catalog, database, name)` stale, or clear the mark after
+/// it is rebuilt. The reads of a stale view are refused until it is refreshed.
+///
+/// The view is re-read and the mark re-applied if the view is updated concurrently,
+/// e.g., by an insertion maintaining it.
+pub async fn set_materialized_view_stale(
+    ctx: &QueryContext,
+    catalog: &str,
+    database: &str,
+    name: &str,
+    stale: bool,
+) -> Result<()> {
+    let tenant = ctx.get_tenant();
+    let catalog = ctx.get_catalog(catalog)?;
+    let mut attempts = 0;
+    loop {
+        // Read the table from catalog, since the cached one may be stale.
+        let view = catalog.get_table(&tenant, database, name).await?;
+        if view.options().contains_key(OPT_KEY_MATERIALIZED_VIEW_STALE) == stale {
+            return Ok(());
+        }
+
+        let ident = &view.get_table_info().ident;
+        let req = UpsertTableOptionReq {
+            table_id: ident.table_id,
+            seq: MatchSeq::Exact(ident.seq),
+            options: HashMap::from([(
+                OPT_KEY_MATERIALIZED_VIEW_STALE.to_string(),
+                stale.then(|| "true".to_string()),
+            )]),
+        };
+        attempts += 1;
+        match catalog.upsert_table_option(&tenant, database, req).await {
+            Ok(_) => return Ok(()),
+            Err(e)
+                if e.code() == ErrorCode::TABLE_VERSION_MISMATCHED
+                    && attempts < MAX_STALE_UPDATE_ATTEMPTS =>
+            {
+                info!(
+                    "materialized view {}.{} is updated concurrently, retry setting stale to {}",
+                    database, name, stale
+                );
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Mark the materialized views on `table` stale before it is mutated other than by
+/// appending, since the views are only maintained on insertion.
+pub async fn invalidate_materialized_views(ctx: &QueryContext, table: &dyn Table) -> Result<()> {
+    for (catalog, database, name) in materialized_views(table)? {
+        match set_materialized_view_stale(ctx, &catalog, &database, &name, true).await {
+            // The view has been dropped.
+            Err(e) if e.code() == ErrorCode::UNKNOWN_TABLE => continue,
+            res => res?,
+        }
+    }
+    Ok(())
+}
+
+/// Build the insertion that appends the rows of the materialized view `view` to it.
+///
+/// The query of the view is planned with `ctx`, thus reads the tables pinned in `ctx`.
+pub async fn build_materialized_view_insertion(
+    ctx: Arc<QueryContext>,
+    catalog: &str,
+    database: &str,
+    view: &dyn Table,
+    overwrite: bool,
+) -> Result<InsertInterpreterV2> {
+    let query = view
+        .options()
+        .get(OPT_KEY_MATERIALIZED_VIEW_QUERY)
+        .ok_or_else(|| {
+            ErrorCode::BadArguments(format!(
+                "{}.{} is not a materialized view",
+                database,
+                view.name()
+            ))
+        })?;
+
+    // The views are maintained one by one, the others on the source table may be stale,
+    // thus the query must not read them as aggregating indexes.
+    let settings = ctx.get_settings();
+    let tokens = tokenize_sql(query)?;
+    let backtrace = Backtrace::new();
+    let (stmt, _) = parse_sql(&tokens, settings.get_sql_dialect()?, &backtrace)?;
+    let binder = Binder::new(
+        ctx.clone(),
+        CatalogManager::instance(),
+        NameResolutionContext::try_from(settings.as_ref())?,
+        Arc::new(RwLock::new(Metadata::default())),
+    );
+    let plan = binder.bind(&stmt).await?;
+    let opt_ctx = Arc::new(OptimizerContext::new(OptimizerConfig {
+        enable_distributed_optimization: !ctx.get_cluster().is_empty(),
+        enable_aggregating_index: false,
+    }));
+    let plan = optimize(ctx.clone(), opt_ctx, plan)?;
+    let insert = Insert {
+        catalog: catalog.to_string(),
+        database: database.to_string(),
+        table: view.name().to_string(),
+        table_id: view.get_id(),
+        schema: view.schema(),
+        overwrite,
+        source: InsertInputSource::SelectPlan(Box::new(plan)),
+    };
+    InsertInterpreterV2::try_create(ctx, insert, false)
+}
+
+/// Commit the insertion into `table`, then maintain the materialized views on it.
+///
+/// The query of each view is run over the segments appended by this insertion only,
+/// and the result is appended to the view. Overwriting `table` rebuilds its views.
+///
+/// The views are not committed in the same transaction as `table`. A view failed to be
+/// maintained is marked stale instead of failing the committed insertion, and is skipped
+/// by the later insertions until it is rebuilt. The insertion fails only if the view can't
+/// be marked stale either.
+pub async fn commit_insertion(
+    ctx: Arc<QueryContext>,
+    table: Arc<dyn Table>,
+    operations: Vec<DataBlock>,
+    overwrite: bool,
+) -> Result<()> {
+    let views = materialized_views(table.as_ref())?;
+    if views.is_empty() {
+        return table.commit_insertion(ctx, operations, overwrite).await;
+    }
+
+    let appended: Arc<dyn Table> =
+        FuseTable::try_from_table(table.as_ref())?.appended_table(&operations)?;
+    table
+        .commit_insertion(ctx.clone(), operations, overwrite)
+        .await?;
+
+    let tenant = ctx.get_tenant();
+    for (catalog, database, name) in views {
+        let view = match ctx
+            .get_catalog(&catalog)?
+            .get_table(&tenant, &database, &name)
+            .await
+        {
+            Ok(view) => view,
+            // The view has been dropped.
+            Err(e) if e.code() == ErrorCode::UNKNOWN_TABLE => continue,
+            Err(e) => {
+                error!(
+                    "failed to read materialized view {}.{} of table {}: {}",
+                    database,
+                    name,
+                    table.name(),
+                    e
+                );
+                continue;
+            }
+        };
+        let source = match view.options().get(OPT_KEY_MATERIALIZED_VIEW_SOURCE) {
+            Some(source) => serde_json::from_str::<TableRef>(source)?,
+            None => continue,
+        };
+        // Appending to a stale view does not make it fresh, only rebuilding does.
+        if !overwrite && view.options().contains_key(OPT_KEY_MATERIALIZED_VIEW_STALE) {
+            continue;
+        }
+
+        info!(
+            "maintain materialized view {}.{} of table {}, overwrite: {}",
+            database,
+            name,
+            table.name(),
+            overwrite
+        );
+        let res = match maintain_materialized_view(
+            ctx.clone(),
+            &catalog,
+            &database,
+            view.as_ref(),
+            &source,
+            appended.clone(),
+            overwrite,
+        )
+        .await
+        {
+            Ok(()) if overwrite => {
+                set_materialized_view_stale(&ctx, &catalog, &database, &name, false).await
+            }
+            Ok(()) => Ok(()),
+            Err(e) => {
+                error!(
+                    "failed to maintain materialized view {}.{} of table {}, mark it stale: {}",
+                    database,
+                    name,
+                    table.name(),
+                    e
+                );
+                match set_materialized_view_stale(&ctx, &catalog, &database, &name, true).await {
+                    // The view has been dropped.
+                    Err(e) if e.code() == ErrorCode::UNKNOWN_TABLE => Ok(()),
+                    // The view misses the rows of this insertion but would still be read.
+                    Err(e) => {
+                        return Err(e.add_message_back(format!(
+                            " (while marking materialized view {}.{} stale, it misses the rows inserted into table {})",
+                            database,
+                            name,
+                            table.name()
+                        )));
+                    }
+                    Ok(()) => Ok(()),
+                }
+            }
+        };
+        // A view failed to be marked fresh stays stale until it is refreshed again.
+        if let Err(e) = res {
+            error!(
+                "failed to clear the stale mark of materialized view {}.{}: {}",
+                database, name, e
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Append the rows of the materialized view `view` over `appended`, the rows appended
+/// to its source table, or rebuild the view over the whole source table if `overwrite`.
+async fn maintain_materialized_view(
+    ctx: Arc<QueryContext>,
+    catalog: &str,
+    database: &str,
+    view: &dyn Table,
+    source: &TableRef,
+    appended: Arc<dyn Table>,
+    overwrite: bool,
+) -> Result<()> {
+    let view_ctx = ctx.create_derived()?;
+    if !overwrite {
+        let (source_catalog, source_database, source_table) = source;
+        view_ctx.pin_table(source_catalog, source_database, source_table, appended);
+    }
+    let interpreter =
+        build_materialized_view_insertion(view_ctx.clone(), catalog, database, view, overwrite)
+            .await?;
+    interpreter
+        .execute(view_ctx)
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    Ok(())
+}
@@ -13,7 +13,15 @@
 // limitations under the License.
 
 mod grant;
+mod materialized_view;
 mod table;
+mod table_lock;
 
 pub use grant::validate_grant_object_exists;
+pub use materialized_view::build_materialized_view_insertion;
+pub use materialized_view::commit_insertion;
+pub use materialized_view::invalidate_materialized_views;
+pub use materialized_view::register_materialized_view;
+pub use materialized_view::set_materialized_view_stale;
 pub use table::append2table;
+pub use table_lock::TableMutationLock;
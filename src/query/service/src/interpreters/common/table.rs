@@ -22,6 +22,7 @@ use common_exception::Result;
 use common_expression::DataSchemaRef;
 use common_pipeline_core::Pipeline;
 
+use crate::interpreters::common::commit_insertion;
 use crate::pipelines::processors::TransformResortAddOn;
 use crate::pipelines::PipelineBuildResult;
 use crate::sessions::QueryContext;
@@ -78,7 +79,7 @@ pub fn append2table(
                 let append_entries = ctx.consume_precommit_blocks();
                 // We must put the commit operation to global runtime, which will avoid the "dispatch dropped without returning error" in tower
                 return GlobalIORuntime::instance().block_on(async move {
-                    table.commit_insertion(ctx, append_entries, overwrite).await
+                    commit_insertion(ctx, table, append_entries, overwrite).await
                 });
             }
 
@@ -0,0 +1,240 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use common_base::base::tokio;
+use common_base::base::tokio::sync::Notify;
+use common_base::base::tokio::task::JoinHandle;
+use common_base::base::tokio::time::sleep;
+use common_catalog::table::Table;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_management::TableLockApi;
+use common_meta_app::schema::TableLockMeta;
+use common_users::UserApiProvider;
+use futures::future::select;
+use futures::future::Either;
+use tracing::error;
+use tracing::info;
+
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+use crate::storages::fuse::FuseTable;
+
+/// Interval of checking whether the lock revisions ahead of ours are gone.
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Serializes the mutations of a table, such as UPDATE, DELETE, MERGE INTO, OPTIMIZE
+/// and RECLUSTER, across the cluster.
+///
+/// A lock revision is created as a lease in meta and kept alive by a heartbeat,
+/// the lock is held once all the smaller revisions of the table are released or expired.
+/// If the heartbeat fails, the lock is lost and the query is aborted, so that it does not
+/// commit while another query may hold the lock.
+pub struct TableMutationLock {
+    lock_api: Arc<dyn TableLockApi>,
+    table_id: u64,
+    revision: u64,
+    lost: Arc<AtomicBool>,
+    shutdown: Arc<AtomicBool>,
+    shutdown_notify: Arc<Notify>,
+    shutdown_handler: Option<JoinHandle<()>>,
+}
+
+impl TableMutationLock {
+    /// Wait for the lock of the table.
+    ///
+    /// Returns `None` if the table is not mutated by committing snapshots, thus needs no lock.
+    pub async fn try_lock(
+        ctx: Arc<QueryContext>,
+        table: &dyn Table,
+    ) -> Result<Option<TableMutationLock>> {
+        if FuseTable::try_from_table(table).is_err() {
+            return Ok(None);
+        }
+
+        let lock = Self::create(ctx.clone(), table).await?;
+        if let Err(cause) = lock.wait(ctx.as_ref()).await {
+            lock.unlock().await?;
+            return Err(cause);
+        }
+
+        Ok(Some(lock))
+    }
+
+    /// Take the lock of the table only if nobody else holds or waits for it.
+    ///
+    /// Returns `None` if the lock is not available, used by the background jobs
+    /// which should give way to the user queries instead of queueing behind them.
+    pub async fn try_lock_nowait(
+        ctx: Arc<QueryContext>,
+        table: &dyn Table,
+    ) -> Result<Option<TableMutationLock>> {
+        let lock = Self::create(ctx, table).await?;
+
+        let revs = lock.lock_api.list_lock_revisions(lock.table_id).await?;
+        match revs.first() {
+            Some((holder, _)) if *holder == lock.revision => Ok(Some(lock)),
+            _ => {
+                lock.unlock().await?;
+                Ok(None)
+            }
+        }
+    }
+
+    async fn create(ctx: Arc<QueryContext>, table: &dyn Table) -> Result<TableMutationLock> {
+        let lock_api = UserApiProvider::instance().get_table_lock_api_client(&ctx.get_tenant())?;
+        let table_id = table.get_id();
+        let expire_secs = ctx.get_settings().get_table_lock_expire_secs()?;
+
+        let lock_meta = TableLockMeta {
+            user: ctx.get_current_user()?.name,
+            node: ctx.get_cluster().local_id.clone(),
+            query_id: ctx.get_id(),
+            created_on: Utc::now(),
+        };
+        let revision = lock_api
+            .create_lock_revision(table_id, expire_secs, &lock_meta)
+            .await?;
+
+        let mut lock = TableMutationLock {
+            lock_api,
+            table_id,
+            revision,
+            lost: Arc::new(AtomicBool::new(false)),
+            shutdown: Arc::new(AtomicBool::new(false)),
+            shutdown_notify: Arc::new(Notify::new()),
+            shutdown_handler: None,
+        };
+        lock.start_heartbeat(expire_secs, ctx.get_aborting());
+        Ok(lock)
+    }
+
+    /// Returns an error if the heartbeat has failed, the lock may be held by others then.
+    pub fn check_lost(&self) -> Result<()> {
+        if self.lost.load(Ordering::Acquire) {
+            return Err(ErrorCode::TableLockExpired(format!(
+                "Lock revision {} of table id {} is lost, the heartbeat failed",
+                self.revision, self.table_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Stop extending the lock revision and delete it.
+    ///
+    /// Returns an error if the lock was lost before, even though the revision is deleted.
+    pub async fn unlock(mut self) -> Result<()> {
+        self.stop_heartbeat();
+        if let Some(shutdown_handler) = self.shutdown_handler.take() {
+            if let Err(shutdown_failure) = shutdown_handler.await {
+                return Err(ErrorCode::TokioError(format!(
+                    "Cannot shutdown table lock heartbeat, cause {:?}",
+                    shutdown_failure
+                )));
+            }
+        }
+
+        self.lock_api
+            .delete_lock_revision(self.table_id, self.revision)
+            .await?;
+        self.check_lost()
+    }
+
+    async fn wait(&self, ctx: &QueryContext) -> Result<()> {
+        loop {
+            let revs = self.lock_api.list_lock_revisions(self.table_id).await?;
+
+            if !revs.iter().any(|(revision, _)| *revision == self.revision) {
+                return Err(ErrorCode::TableLockExpired(format!(
+                    "Lock revision {} of table id {} has expired while waiting",
+                    self.revision, self.table_id
+                )));
+            }
+
+            // Revisions are ordered, the smallest one holds the lock.
+            let (holder, holder_meta) = &revs[0];
+            if *holder == self.revision {
+                return Ok(());
+            }
+
+            info!(
+                "table {} is locked by query {} on node {}, revision {}, waiting with revision {}",
+                self.table_id, holder_meta.query_id, holder_meta.node, holder, self.revision
+            );
+
+            if ctx.get_aborting().load(Ordering::Relaxed) {
+                return Err(ErrorCode::AbortedQuery(
+                    "Aborted query, because the server is shutting down or the query was killed.",
+                ));
+            }
+
+            sleep(LOCK_RETRY_INTERVAL).await;
+        }
+    }
+
+    fn start_heartbeat(&mut self, expire_secs: u64, aborting: Arc<AtomicBool>) {
+        let lost = self.lost.clone();
+        let shutdown = self.shutdown.clone();
+        let shutdown_notify = self.shutdown_notify.clone();
+        let lock_api = self.lock_api.clone();
+        let table_id = self.table_id;
+        let revision = self.revision;
+        // Extend the lease well before it expires.
+        let interval = Duration::from_secs(expire_secs) / 3;
+
+        self.shutdown_handler = Some(tokio::spawn(async move {
+            let mut shutdown_notified = Box::pin(shutdown_notify.notified());
+
+            while !shutdown.load(Ordering::Relaxed) {
+                match select(shutdown_notified, Box::pin(sleep(interval))).await {
+                    Either::Left((_, _)) => {
+                        break;
+                    }
+                    Either::Right((_, new_shutdown_notified)) => {
+                        shutdown_notified = new_shutdown_notified;
+                        if let Err(failure) = lock_api
+                            .extend_lock_revision(table_id, expire_secs, revision)
+                            .await
+                        {
+                            error!("Table lock heartbeat failure: {:?}", failure);
+                            // Others may take the lock once the revision expires,
+                            // abort the query so that it does not commit without the lock.
+                            lost.store(true, Ordering::Release);
+                            aborting.store(true, Ordering::Release);
+                            break;
+                        }
+                    }
+                }
+            }
+        }));
+    }
+
+    fn stop_heartbeat(&self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        self.shutdown_notify.notify_waiters();
+    }
+}
+
+impl Drop for TableMutationLock {
+    fn drop(&mut self) {
+        // Not unlocked explicitly, e.g. the query is cancelled,
+        // stop extending so that the revision expires soon.
+        self.stop_heartbeat();
+    }
+}
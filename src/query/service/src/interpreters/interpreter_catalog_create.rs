@@ -63,6 +63,7 @@ impl Interpreter for CreateCatalogInterpreter {
                     return Err(err);
                 }
             }
+            CatalogType::Iceberg => {}
         }
         let catalog_manager = CatalogManager::instance();
         catalog_manager.create_user_defined_catalog(self.plan.clone().into())?;
@@ -40,6 +40,7 @@ use tracing::error;
 use tracing::info;
 
 use crate::interpreters::common::append2table;
+use crate::interpreters::common::commit_insertion;
 use crate::interpreters::Interpreter;
 use crate::interpreters::SelectInterpreterV2;
 use crate::pipelines::PipelineBuildResult;
@@ -393,9 +394,7 @@ impl CopyInterpreterV2 {
                         operations.len(),
                         start.elapsed().as_secs()
                     );
-                    to_table
-                        .commit_insertion(ctx.clone(), operations, false)
-                        .await?;
+                    commit_insertion(ctx.clone(), to_table, operations, false).await?;
 
                     // 2. Try to purge copied files if purge option is true, if error will skip.
                     // If a file is already copied(status with AlreadyCopied) we will try to purge them.
@@ -14,11 +14,14 @@
 
 use std::sync::Arc;
 
+use common_catalog::table::Table;
 use common_exception::Result;
 use common_expression::DataSchemaRef;
 use common_pipeline_core::Pipeline;
 use common_sql::plans::DeletePlan;
 
+use crate::interpreters::common::invalidate_materialized_views;
+use crate::interpreters::common::TableMutationLock;
 use crate::interpreters::Interpreter;
 use crate::pipelines::executor::ExecutorSettings;
 use crate::pipelines::executor::PipelineCompleteExecutor;
@@ -58,7 +61,34 @@ impl Interpreter for DeleteInterpreter {
         let catalog_name = self.plan.catalog_name.as_str();
         let db_name = self.plan.database_name.as_str();
         let tbl_name = self.plan.table_name.as_str();
-        let tbl = self.ctx.get_table(catalog_name, db_name, tbl_name).await?;
+        let mut tbl = self.ctx.get_table(catalog_name, db_name, tbl_name).await?;
+
+        // Serialize the mutations of the table across the cluster.
+        let table_lock = TableMutationLock::try_lock(self.ctx.clone(), tbl.as_ref()).await?;
+        if table_lock.is_some() {
+            // the table may have been mutated while waiting for the lock
+            tbl = self
+                .ctx
+                .get_catalog(catalog_name)?
+                .get_table(self.ctx.get_tenant().as_str(), db_name, tbl_name)
+                .await?;
+        }
+
+        let res = self.delete(tbl).await;
+        if let Some(table_lock) = table_lock {
+            table_lock.unlock().await?;
+        }
+        res?;
+
+        Ok(PipelineBuildResult::create())
+    }
+}
+
+impl DeleteInterpreter {
+    async fn delete(&self, tbl: Arc<dyn Table>) -> Result<()> {
+        // Invalidate the views before the mutation, since they are only maintained on insertion.
+        invalidate_materialized_views(&self.ctx, tbl.as_ref()).await?;
+
         let (filter, col_indices) = if let Some(scalar) = &self.plan.selection {
             let filter = scalar.as_expr()?.as_remote_expr();
             let col_indices = scalar.used_columns().into_iter().collect();
@@ -82,6 +112,6 @@ impl Interpreter for DeleteInterpreter {
             drop(executor);
         }
 
-        Ok(PipelineBuildResult::create())
+        Ok(())
     }
 }
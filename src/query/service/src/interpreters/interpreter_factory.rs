@@ -15,7 +15,6 @@
 use std::sync::Arc;
 
 use common_ast::ast::ExplainKind;
-use common_exception::ErrorCode;
 use common_exception::Result;
 use tracing::error;
 
@@ -141,6 +140,15 @@ impl InterpreterFactory {
                 ctx,
                 *rename_table.clone(),
             )?)),
+            Plan::AddTableColumn(add_table_column) => Ok(Arc::new(
+                AddTableColumnInterpreter::try_create(ctx, *add_table_column.clone())?,
+            )),
+            Plan::DropTableColumn(drop_table_column) => Ok(Arc::new(
+                DropTableColumnInterpreter::try_create(ctx, *drop_table_column.clone())?,
+            )),
+            Plan::RenameTableColumn(rename_table_column) => Ok(Arc::new(
+                RenameTableColumnInterpreter::try_create(ctx, *rename_table_column.clone())?,
+            )),
             Plan::AlterTableClusterKey(alter_table_cluster_key) => Ok(Arc::new(
                 AlterTableClusterKeyInterpreter::try_create(ctx, *alter_table_cluster_key.clone())?,
             )),
@@ -165,6 +173,16 @@ impl InterpreterFactory {
                 *exists_table.clone(),
             )?)),
 
+            // Indexes
+            Plan::CreateIndex(create_index) => Ok(Arc::new(CreateIndexInterpreter::try_create(
+                ctx,
+                *create_index.clone(),
+            )?)),
+            Plan::DropIndex(drop_index) => Ok(Arc::new(DropIndexInterpreter::try_create(
+                ctx,
+                *drop_index.clone(),
+            )?)),
+
             // Views
             Plan::CreateView(create_view) => Ok(Arc::new(CreateViewInterpreter::try_create(
                 ctx,
@@ -178,6 +196,22 @@ impl InterpreterFactory {
                 ctx,
                 *drop_view.clone(),
             )?)),
+            Plan::CreateMaterializedView(create_view) => Ok(Arc::new(
+                CreateMaterializedViewInterpreter::try_create(ctx, *create_view.clone())?,
+            )),
+            Plan::RefreshMaterializedView(refresh_view) => Ok(Arc::new(
+                RefreshMaterializedViewInterpreter::try_create(ctx, *refresh_view.clone())?,
+            )),
+
+            // Streams
+            Plan::CreateStream(create_stream) => Ok(Arc::new(CreateStreamInterpreter::try_create(
+                ctx,
+                *create_stream.clone(),
+            )?)),
+            Plan::DropStream(drop_stream) => Ok(Arc::new(DropStreamInterpreter::try_create(
+                ctx,
+                *drop_stream.clone(),
+            )?)),
 
             // Users
             Plan::CreateUser(create_user) => Ok(Arc::new(CreateUserInterpreter::try_create(
@@ -200,9 +234,15 @@ impl InterpreterFactory {
                 *delete.clone(),
             )?)),
 
-            Plan::Update(_update) => Err(ErrorCode::Unimplemented(
-                "Unimplement for update".to_string(),
-            )),
+            Plan::Update(update) => Ok(Arc::new(UpdateInterpreter::try_create(
+                ctx,
+                *update.clone(),
+            )?)),
+
+            Plan::MergeInto(merge_into) => Ok(Arc::new(MergeIntoInterpreter::try_create(
+                ctx,
+                *merge_into.clone(),
+            )?)),
 
             // Roles
             Plan::CreateRole(create_role) => Ok(Arc::new(CreateRoleInterpreter::try_create(
@@ -23,6 +23,7 @@ use aho_corasick::AhoCorasick;
 use common_ast::ast::Expr as AExpr;
 use common_ast::parser::parse_comma_separated_exprs;
 use common_ast::parser::parse_expr;
+use common_ast::parser::token::TokenKind;
 use common_ast::parser::tokenize_sql;
 use common_ast::Backtrace;
 use common_ast::Dialect;
@@ -83,6 +84,7 @@ use tracing::error;
 use tracing::info;
 
 use crate::interpreters::common::append2table;
+use crate::interpreters::common::commit_insertion;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::pipelines::processors::TransformResortAddOn;
@@ -253,9 +255,7 @@ impl InsertInterpreterV2 {
                             append_entries.len(),
                             start.elapsed().as_secs()
                         );
-                        table
-                            .commit_insertion(ctx.clone(), append_entries, overwrite)
-                            .await?;
+                        commit_insertion(ctx.clone(), table, append_entries, overwrite).await?;
 
                         if stage_info.copy_options.purge {
                             info!(
@@ -406,7 +406,7 @@ impl Interpreter for InsertInterpreterV2 {
                             let append_entries = ctx.consume_precommit_blocks();
                             // We must put the commit operation to global runtime, which will avoid the "dispatch dropped without returning error" in tower
                             return GlobalIORuntime::instance().block_on(async move {
-                                table.commit_insertion(ctx, append_entries, overwrite).await
+                                commit_insertion(ctx, table, append_entries, overwrite).await
                             });
                         }
 
@@ -633,10 +633,21 @@ impl ValueSource {
                     self.ctx.clone(),
                     &self.name_resolution_ctx,
                     bind_context,
-                    metadata,
+                    metadata.clone(),
                 )
                 .await?;
 
+                // The placeholders of the next rows are bound to the parameters following the ones of this row.
+                let row_placeholders = tokens
+                    .iter()
+                    .filter(|token| token.kind == TokenKind::Placeholder)
+                    .count();
+                if row_placeholders > 0 {
+                    let mut metadata = metadata.write();
+                    let offset = metadata.placeholder_offset();
+                    metadata.set_placeholder_offset(offset + row_placeholders);
+                }
+
                 let format = self.ctx.get_format_settings()?;
                 for (append_idx, deser) in desers.iter_mut().enumerate().take(col_size) {
                     deser.append_data_value(values[append_idx].clone(), &format)?;
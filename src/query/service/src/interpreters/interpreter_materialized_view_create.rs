@@ -0,0 +1,117 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::CreateTableReq;
+use common_meta_app::schema::TableMeta;
+use common_meta_app::schema::TableNameIdent;
+use common_sql::plans::CreateMaterializedViewPlan;
+
+use crate::interpreters::common::register_materialized_view;
+use crate::interpreters::InsertInterpreterV2;
+use crate::interpreters::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+use crate::sql::plans::insert::Insert;
+use crate::sql::plans::insert::InsertInputSource;
+
+pub struct CreateMaterializedViewInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: CreateMaterializedViewPlan,
+}
+
+impl CreateMaterializedViewInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: CreateMaterializedViewPlan) -> Result<Self> {
+        Ok(CreateMaterializedViewInterpreter { ctx, plan })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for CreateMaterializedViewInterpreter {
+    fn name(&self) -> &str {
+        "CreateMaterializedViewInterpreter"
+    }
+
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let plan = &self.plan;
+        let catalog = self.ctx.get_catalog(&plan.catalog)?;
+        if catalog
+            .exists_table(&plan.tenant, &plan.database, &plan.viewname)
+            .await?
+        {
+            return match plan.if_not_exists {
+                true => Ok(PipelineBuildResult::create()),
+                false => Err(ErrorCode::ViewAlreadyExists(format!(
+                    "{}.{} as view Already Exists",
+                    plan.database, plan.viewname
+                ))),
+            };
+        }
+
+        // The rows of the view are kept in a FUSE table.
+        let req = CreateTableReq {
+            if_not_exists: false,
+            name_ident: TableNameIdent {
+                tenant: plan.tenant.clone(),
+                db_name: plan.database.clone(),
+                table_name: plan.viewname.clone(),
+            },
+            table_meta: TableMeta {
+                schema: plan.schema.clone(),
+                engine: "FUSE".to_string(),
+                options: plan.options.clone(),
+                ..Default::default()
+            },
+        };
+        catalog.create_table(req).await?;
+        let view = catalog
+            .get_table(&plan.tenant, &plan.database, &plan.viewname)
+            .await?;
+
+        register_materialized_view(
+            &self.ctx,
+            &(
+                plan.source_catalog.clone(),
+                plan.source_database.clone(),
+                plan.source_table.clone(),
+            ),
+            (
+                plan.catalog.clone(),
+                plan.database.clone(),
+                plan.viewname.clone(),
+            ),
+        )
+        .await?;
+
+        // Populate the view with the rows of the source table at the time of binding,
+        // the insertions committed since the registration are maintained incrementally.
+        let insert_plan = Insert {
+            catalog: plan.catalog.clone(),
+            database: plan.database.clone(),
+            table: plan.viewname.clone(),
+            table_id: view.get_id(),
+            schema: plan.schema.clone(),
+            overwrite: false,
+            source: InsertInputSource::SelectPlan(plan.as_select.clone()),
+        };
+
+        InsertInterpreterV2::try_create(self.ctx.clone(), insert_plan, false)?
+            .execute2()
+            .await
+    }
+}
@@ -0,0 +1,86 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::runtime::GlobalIORuntime;
+use common_exception::Result;
+use common_sql::plans::RefreshMaterializedViewPlan;
+
+use crate::interpreters::common::build_materialized_view_insertion;
+use crate::interpreters::common::set_materialized_view_stale;
+use crate::interpreters::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+pub struct RefreshMaterializedViewInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: RefreshMaterializedViewPlan,
+}
+
+impl RefreshMaterializedViewInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: RefreshMaterializedViewPlan) -> Result<Self> {
+        Ok(RefreshMaterializedViewInterpreter { ctx, plan })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for RefreshMaterializedViewInterpreter {
+    fn name(&self) -> &str {
+        "RefreshMaterializedViewInterpreter"
+    }
+
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let plan = &self.plan;
+        let view = self
+            .ctx
+            .get_table(&plan.catalog, &plan.database, &plan.viewname)
+            .await?;
+
+        // Rebuild the view by overwriting it with the query over the whole source table.
+        let mut build_res = build_materialized_view_insertion(
+            self.ctx.clone(),
+            &plan.catalog,
+            &plan.database,
+            view.as_ref(),
+            true,
+        )
+        .await?
+        .execute2()
+        .await?;
+
+        // The rebuilt view is fresh, whether it failed to be maintained before or not.
+        let ctx = self.ctx.clone();
+        let plan = plan.clone();
+        build_res.main_pipeline.set_on_finished(move |may_error| {
+            if may_error.is_none() {
+                let ctx = ctx.clone();
+                let plan = plan.clone();
+                return GlobalIORuntime::instance().block_on(async move {
+                    set_materialized_view_stale(
+                        &ctx,
+                        &plan.catalog,
+                        &plan.database,
+                        &plan.viewname,
+                        false,
+                    )
+                    .await
+                });
+            }
+            Err(may_error.as_ref().unwrap().clone())
+        });
+        Ok(build_res)
+    }
+}
@@ -0,0 +1,250 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_catalog::table::Table;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::DataSchemaRef;
+use common_expression::Expr;
+use common_expression::RemoteExpr;
+use common_functions::scalars::BUILTIN_FUNCTIONS;
+use common_pipeline_core::Pipeline;
+use common_sql::plans::JoinType;
+use common_sql::plans::MergeIntoPlan;
+use common_sql::plans::Plan;
+use common_storages_fuse::operations::MergeIntoState;
+use common_storages_fuse::FuseTable;
+
+use crate::interpreters::common::invalidate_materialized_views;
+use crate::interpreters::common::TableMutationLock;
+use crate::interpreters::Interpreter;
+use crate::interpreters::SelectInterpreterV2;
+use crate::pipelines::executor::ExecutorSettings;
+use crate::pipelines::executor::PipelineCompleteExecutor;
+use crate::pipelines::processors::transforms::TransformRightJoin;
+use crate::pipelines::processors::HashJoinDesc;
+use crate::pipelines::processors::JoinHashTable;
+use crate::pipelines::processors::RightJoinCompactor;
+use crate::pipelines::processors::SinkBuildHashTable;
+use crate::pipelines::processors::Sinker;
+use crate::pipelines::processors::TransformHashJoinProbe;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+/// interprets MergeIntoPlan
+pub struct MergeIntoInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: MergeIntoPlan,
+}
+
+impl MergeIntoInterpreter {
+    /// Create the MergeIntoInterpreter from MergeIntoPlan
+    pub fn try_create(ctx: Arc<QueryContext>, plan: MergeIntoPlan) -> Result<Self> {
+        Ok(MergeIntoInterpreter { ctx, plan })
+    }
+
+    /// Build the pipelines joining the source with the target table by a right outer join.
+    /// The source is the build side, which is spilled by the hash join if it's too large,
+    /// the blocks of the target table are the probe side.
+    async fn build_join_pipelines(
+        &self,
+        table: &FuseTable,
+        merge_into_state: Arc<MergeIntoState>,
+    ) -> Result<Vec<Pipeline>> {
+        let (s_expr, metadata, bind_context) = if let Plan::Query {
+            s_expr,
+            metadata,
+            bind_context,
+            ..
+        } = self.plan.input.as_ref()
+        {
+            (s_expr, metadata, bind_context)
+        } else {
+            return Err(ErrorCode::Internal(
+                "The source of MERGE INTO must be a query plan",
+            ));
+        };
+
+        // The source is read with its own context, the partitions of the target table are
+        // set to the context of the query.
+        let build_side_context = QueryContext::create_from(self.ctx.clone());
+        let select_interpreter = SelectInterpreterV2::try_create(
+            build_side_context,
+            *bind_context.clone(),
+            *s_expr.clone(),
+            metadata.clone(),
+            false,
+        )?;
+        let build_schema = select_interpreter.schema();
+
+        let info = merge_into_state.info();
+        let hash_join_desc = HashJoinDesc::create_equi_join(
+            JoinType::Right,
+            as_exprs(&info.source_keys),
+            as_exprs(&info.target_keys),
+        )?;
+        let join_state = JoinHashTable::create_join_state(
+            self.ctx.clone(),
+            build_schema,
+            merge_into_state.probe_schema(),
+            hash_join_desc,
+        )?;
+
+        let mut build_res = select_interpreter.build_pipeline().await?;
+        build_res.main_pipeline.add_sink(|input| {
+            Ok(Sinker::<SinkBuildHashTable>::create(
+                input,
+                SinkBuildHashTable::try_create(join_state.clone())?,
+            ))
+        })?;
+
+        let mut probe_pipeline = Pipeline::create();
+        table
+            .add_merge_into_target_source(
+                self.ctx.clone(),
+                merge_into_state.clone(),
+                &mut probe_pipeline,
+            )
+            .await?;
+        let output_schema = merge_into_state.probe_schema();
+        probe_pipeline.add_transform(|input, output| {
+            TransformHashJoinProbe::create(
+                self.ctx.clone(),
+                input,
+                output,
+                join_state.clone(),
+                output_schema.clone(),
+            )
+        })?;
+        probe_pipeline.resize(1)?;
+        probe_pipeline.add_transform(|input, output| {
+            TransformRightJoin::try_create(
+                input,
+                output,
+                RightJoinCompactor::create(join_state.clone()),
+            )
+        })?;
+        table.add_merge_into_join_sink(
+            self.ctx.clone(),
+            merge_into_state.clone(),
+            &mut probe_pipeline,
+        )?;
+
+        let mut pipelines = build_res.sources_pipelines;
+        pipelines.push(build_res.main_pipeline);
+        pipelines.push(probe_pipeline);
+        Ok(pipelines)
+    }
+
+    fn execute_pipelines(&self, mut pipelines: Vec<Pipeline>) -> Result<()> {
+        let settings = self.ctx.get_settings();
+        let max_threads = settings.get_max_threads()? as usize;
+        for pipeline in pipelines.iter_mut() {
+            pipeline.set_max_threads(max_threads);
+        }
+        let query_id = self.ctx.get_id();
+        let executor_settings = ExecutorSettings::try_create(&settings, query_id)?;
+        let executor = PipelineCompleteExecutor::from_pipelines(pipelines, executor_settings)?;
+
+        self.ctx.set_executor(Arc::downgrade(&executor.get_inner()));
+        executor.execute()?;
+        drop(executor);
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for MergeIntoInterpreter {
+    /// Get the name of current interpreter
+    fn name(&self) -> &str {
+        "MergeIntoInterpreter"
+    }
+
+    /// Get the schema of MergeIntoPlan
+    fn schema(&self) -> DataSchemaRef {
+        self.plan.schema()
+    }
+
+    #[tracing::instrument(level = "debug", name = "merge_into_interpreter_execute", skip(self), fields(ctx.id = self.ctx.get_id().as_str()))]
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let catalog_name = self.plan.catalog.as_str();
+        let db_name = self.plan.database.as_str();
+        let tbl_name = self.plan.table.as_str();
+        let mut tbl = self.ctx.get_table(catalog_name, db_name, tbl_name).await?;
+
+        // Serialize the mutations of the table across the cluster.
+        let table_lock = TableMutationLock::try_lock(self.ctx.clone(), tbl.as_ref()).await?;
+        if table_lock.is_some() {
+            // the table may have been mutated while waiting for the lock
+            tbl = self
+                .ctx
+                .get_catalog(catalog_name)?
+                .get_table(self.ctx.get_tenant().as_str(), db_name, tbl_name)
+                .await?;
+        }
+
+        let res = self.merge_into(tbl).await;
+        if let Some(table_lock) = table_lock {
+            table_lock.unlock().await?;
+        }
+        res?;
+
+        Ok(PipelineBuildResult::create())
+    }
+}
+
+impl MergeIntoInterpreter {
+    async fn merge_into(&self, tbl: Arc<dyn Table>) -> Result<()> {
+        // Invalidate the views before the mutation, since they are only maintained on insertion.
+        invalidate_materialized_views(&self.ctx, tbl.as_ref()).await?;
+
+        let table = FuseTable::try_from_table(tbl.as_ref()).map_err(|_| {
+            ErrorCode::Unimplemented(format!(
+                "table {},  of engine type {}, does not support MERGE INTO",
+                tbl.name(),
+                tbl.get_table_info().engine(),
+            ))
+        })?;
+        let merge_into_info = self.plan.merge_into_info(tbl.schema())?;
+        let merge_into_state = table
+            .create_merge_into_state(self.ctx.clone(), merge_into_info)
+            .await?;
+
+        // Join the source with the target table, the rows to be inserted are written
+        // while the joined rows arrive.
+        let pipelines = self
+            .build_join_pipelines(table, merge_into_state.clone())
+            .await?;
+        self.execute_pipelines(pipelines)?;
+
+        // Rewrite the blocks containing the deleted or updated rows and commit.
+        let mut pipeline = Pipeline::create();
+        table.do_merge_into(self.ctx.clone(), merge_into_state, &mut pipeline)?;
+        if !pipeline.pipes.is_empty() {
+            self.execute_pipelines(vec![pipeline])?;
+        }
+
+        Ok(())
+    }
+}
+
+fn as_exprs(remote_exprs: &[RemoteExpr]) -> Vec<Expr> {
+    remote_exprs
+        .iter()
+        .map(|remote_expr| remote_expr.as_expr(&BUILTIN_FUNCTIONS).unwrap())
+        .collect()
+}
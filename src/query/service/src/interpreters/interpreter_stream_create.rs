@@ -0,0 +1,102 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::CreateTableReq;
+use common_meta_app::schema::TableMeta;
+use common_meta_app::schema::TableNameIdent;
+use common_sql::plans::CreateStreamPlan;
+use common_storages_fuse::stream::StreamTable;
+use common_storages_fuse::stream::STREAM_ENGINE;
+use common_storages_fuse::FuseTable;
+use storages_common_table_meta::table::OPT_KEY_STREAM_OFFSET;
+use storages_common_table_meta::table::OPT_KEY_STREAM_SOURCE;
+use storages_common_table_meta::table::OPT_KEY_STREAM_SOURCE_TABLE_ID;
+
+use crate::interpreters::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+pub struct CreateStreamInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: CreateStreamPlan,
+}
+
+impl CreateStreamInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: CreateStreamPlan) -> Result<Self> {
+        Ok(CreateStreamInterpreter { ctx, plan })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for CreateStreamInterpreter {
+    fn name(&self) -> &str {
+        "CreateStreamInterpreter"
+    }
+
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let plan = &self.plan;
+        let source = self
+            .ctx
+            .get_table(&plan.catalog, &plan.table_database, &plan.table_name)
+            .await?;
+        if source.engine() != "FUSE" {
+            return Err(ErrorCode::TableEngineNotSupported(format!(
+                "{}.{} engine is {} that doesn't support stream, only FUSE tables can be tracked by streams",
+                plan.table_database,
+                plan.table_name,
+                source.engine()
+            )));
+        }
+        let fuse_table = FuseTable::try_from_table(source.as_ref())?;
+
+        // The changes are tracked from the current snapshot of the source table.
+        let mut options = BTreeMap::new();
+        options.insert(
+            OPT_KEY_STREAM_SOURCE.to_string(),
+            serde_json::to_string(&(&plan.catalog, &plan.table_database, &plan.table_name))?,
+        );
+        options.insert(
+            OPT_KEY_STREAM_SOURCE_TABLE_ID.to_string(),
+            source.get_id().to_string(),
+        );
+        if let Some(location) = fuse_table.snapshot_loc().await? {
+            options.insert(OPT_KEY_STREAM_OFFSET.to_string(), location);
+        }
+
+        let catalog = self.ctx.get_catalog(&plan.catalog)?;
+        let req = CreateTableReq {
+            if_not_exists: plan.if_not_exists,
+            name_ident: TableNameIdent {
+                tenant: plan.tenant.clone(),
+                db_name: plan.database.clone(),
+                table_name: plan.stream_name.clone(),
+            },
+            table_meta: TableMeta {
+                schema: StreamTable::stream_schema(&source.schema()),
+                engine: STREAM_ENGINE.to_string(),
+                options,
+                ..Default::default()
+            },
+        };
+        catalog.create_table(req).await?;
+
+        Ok(PipelineBuildResult::create())
+    }
+}
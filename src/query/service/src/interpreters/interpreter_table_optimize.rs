@@ -15,10 +15,12 @@
 use std::sync::Arc;
 
 use common_catalog::table::CompactTarget;
+use common_catalog::table::Table;
 use common_exception::Result;
 use common_sql::plans::OptimizeTableAction;
 use common_sql::plans::OptimizeTablePlan;
 
+use crate::interpreters::common::TableMutationLock;
 use crate::interpreters::Interpreter;
 use crate::pipelines::executor::ExecutorSettings;
 use crate::pipelines::executor::PipelineCompleteExecutor;
@@ -46,12 +48,37 @@ impl Interpreter for OptimizeTableInterpreter {
 
     async fn execute2(&self) -> Result<PipelineBuildResult> {
         let plan = &self.plan;
-        let ctx = self.ctx.clone();
         let mut table = self
             .ctx
             .get_table(&plan.catalog, &plan.database, &plan.table)
             .await?;
 
+        // Serialize the mutations of the table across the cluster.
+        let table_lock = TableMutationLock::try_lock(self.ctx.clone(), table.as_ref()).await?;
+        if table_lock.is_some() {
+            // the table may have been mutated while waiting for the lock
+            table = self
+                .ctx
+                .get_catalog(&plan.catalog)?
+                .get_table(self.ctx.get_tenant().as_str(), &plan.database, &plan.table)
+                .await?;
+        }
+
+        let res = self.optimize(table).await;
+        if let Some(table_lock) = table_lock {
+            table_lock.unlock().await?;
+        }
+        res?;
+
+        Ok(PipelineBuildResult::create())
+    }
+}
+
+impl OptimizeTableInterpreter {
+    pub(crate) async fn optimize(&self, mut table: Arc<dyn Table>) -> Result<()> {
+        let plan = &self.plan;
+        let ctx = self.ctx.clone();
+
         let action = &plan.action;
         let do_purge = matches!(
             action,
@@ -81,7 +108,7 @@ impl Interpreter for OptimizeTableInterpreter {
                 )
                 .await?;
 
-            return Ok(PipelineBuildResult::create());
+            return Ok(());
         }
 
         if do_compact_blocks {
@@ -123,6 +150,6 @@ impl Interpreter for OptimizeTableInterpreter {
             table.purge(self.ctx.clone(), keep_latest).await?;
         }
 
-        Ok(PipelineBuildResult::create())
+        Ok(())
     }
 }
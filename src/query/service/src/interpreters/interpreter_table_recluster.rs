@@ -18,6 +18,7 @@ use std::time::SystemTime;
 use common_catalog::plan::PushDownInfo;
 use common_exception::Result;
 
+use crate::interpreters::common::TableMutationLock;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterClusteringHistory;
 use crate::pipelines::executor::ExecutorSettings;
@@ -48,8 +49,6 @@ impl Interpreter for ReclusterTableInterpreter {
     async fn execute2(&self) -> Result<PipelineBuildResult> {
         let plan = &self.plan;
         let ctx = self.ctx.clone();
-        let settings = ctx.get_settings();
-        let tenant = ctx.get_tenant();
         let start = SystemTime::now();
 
         // Build extras via push down scalar
@@ -64,6 +63,32 @@ impl Interpreter for ReclusterTableInterpreter {
             None
         };
 
+        // Serialize the mutations of the table across the cluster.
+        let table = self
+            .ctx
+            .get_table(&plan.catalog, &plan.database, &plan.table)
+            .await?;
+        let table_lock = TableMutationLock::try_lock(ctx.clone(), table.as_ref()).await?;
+
+        let res = self.recluster(extras).await;
+        if let Some(table_lock) = table_lock {
+            table_lock.unlock().await?;
+        }
+        res?;
+
+        InterpreterClusteringHistory::write_log(&ctx, start, &plan.database, &plan.table)?;
+
+        Ok(PipelineBuildResult::create())
+    }
+}
+
+impl ReclusterTableInterpreter {
+    pub(crate) async fn recluster(&self, extras: Option<PushDownInfo>) -> Result<()> {
+        let plan = &self.plan;
+        let ctx = self.ctx.clone();
+        let settings = ctx.get_settings();
+        let tenant = ctx.get_tenant();
+
         loop {
             let table = self
                 .ctx
@@ -98,8 +123,6 @@ impl Interpreter for ReclusterTableInterpreter {
             }
         }
 
-        InterpreterClusteringHistory::write_log(&ctx, start, &plan.database, &plan.table)?;
-
-        Ok(PipelineBuildResult::create())
+        Ok(())
     }
 }
@@ -17,6 +17,7 @@ use std::sync::Arc;
 use common_exception::Result;
 use common_sql::plans::TruncateTablePlan;
 
+use crate::interpreters::common::invalidate_materialized_views;
 use crate::interpreters::Interpreter;
 use crate::pipelines::PipelineBuildResult;
 use crate::sessions::QueryContext;
@@ -45,6 +46,7 @@ impl Interpreter for TruncateTableInterpreter {
         let tbl_name = self.plan.table.as_str();
 
         let tbl = self.ctx.get_table(catalog_name, db_name, tbl_name).await?;
+        invalidate_materialized_views(&self.ctx, tbl.as_ref()).await?;
         tbl.truncate(self.ctx.clone(), self.plan.purge).await?;
         Ok(PipelineBuildResult::create())
     }
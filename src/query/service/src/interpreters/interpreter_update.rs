@@ -0,0 +1,124 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_catalog::table::Table;
+use common_exception::Result;
+use common_expression::DataSchemaRef;
+use common_pipeline_core::Pipeline;
+use common_sql::plans::UpdatePlan;
+
+use crate::interpreters::common::invalidate_materialized_views;
+use crate::interpreters::common::TableMutationLock;
+use crate::interpreters::Interpreter;
+use crate::pipelines::executor::ExecutorSettings;
+use crate::pipelines::executor::PipelineCompleteExecutor;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+use crate::sql::plans::ScalarExpr;
+
+/// interprets UpdatePlan
+pub struct UpdateInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: UpdatePlan,
+}
+
+impl UpdateInterpreter {
+    /// Create the UpdateInterpreter from UpdatePlan
+    pub fn try_create(ctx: Arc<QueryContext>, plan: UpdatePlan) -> Result<Self> {
+        Ok(UpdateInterpreter { ctx, plan })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for UpdateInterpreter {
+    /// Get the name of current interpreter
+    fn name(&self) -> &str {
+        "UpdateInterpreter"
+    }
+
+    /// Get the schema of UpdatePlan
+    fn schema(&self) -> DataSchemaRef {
+        self.plan.schema()
+    }
+
+    #[tracing::instrument(level = "debug", name = "update_interpreter_execute", skip(self), fields(ctx.id = self.ctx.get_id().as_str()))]
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let catalog_name = self.plan.catalog.as_str();
+        let db_name = self.plan.database.as_str();
+        let tbl_name = self.plan.table.as_str();
+        let mut tbl = self.ctx.get_table(catalog_name, db_name, tbl_name).await?;
+
+        // Serialize the mutations of the table across the cluster.
+        let table_lock = TableMutationLock::try_lock(self.ctx.clone(), tbl.as_ref()).await?;
+        if table_lock.is_some() {
+            // the table may have been mutated while waiting for the lock
+            tbl = self
+                .ctx
+                .get_catalog(catalog_name)?
+                .get_table(self.ctx.get_tenant().as_str(), db_name, tbl_name)
+                .await?;
+        }
+
+        let res = self.update(tbl).await;
+        if let Some(table_lock) = table_lock {
+            table_lock.unlock().await?;
+        }
+        res?;
+
+        Ok(PipelineBuildResult::create())
+    }
+}
+
+impl UpdateInterpreter {
+    async fn update(&self, tbl: Arc<dyn Table>) -> Result<()> {
+        // Invalidate the views before the mutation, since they are only maintained on insertion.
+        invalidate_materialized_views(&self.ctx, tbl.as_ref()).await?;
+
+        let (filter, col_indices) = if let Some(scalar) = &self.plan.selection {
+            let filter = scalar.as_expr()?.as_remote_expr();
+            let col_indices = scalar.used_columns().into_iter().collect();
+            (Some(filter), col_indices)
+        } else {
+            (None, vec![])
+        };
+
+        let update_list = self.plan.generate_update_list(tbl.schema())?;
+
+        let mut pipeline = Pipeline::create();
+        tbl.update(
+            self.ctx.clone(),
+            filter,
+            col_indices,
+            update_list,
+            &mut pipeline,
+        )
+        .await?;
+        if !pipeline.pipes.is_empty() {
+            let settings = self.ctx.get_settings();
+            pipeline.set_max_threads(settings.get_max_threads()? as usize);
+            let query_id = self.ctx.get_id();
+            let executor_settings = ExecutorSettings::try_create(&settings, query_id)?;
+            let executor = PipelineCompleteExecutor::try_create(pipeline, executor_settings)?;
+
+            self.ctx.set_executor(Arc::downgrade(&executor.get_inner()));
+            executor.execute()?;
+            drop(executor);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,485 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use chrono::DateTime;
+use chrono::Utc;
+use common_base::base::tokio;
+use common_base::base::tokio::sync::Notify;
+use common_base::base::tokio::sync::Semaphore;
+use common_base::base::tokio::time::sleep;
+use common_base::base::GlobalInstance;
+use common_base::runtime::GlobalIORuntime;
+use common_catalog::catalog::Catalog;
+use common_catalog::catalog_kind::CATALOG_DEFAULT;
+use common_catalog::table::Table;
+use common_config::Config;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::DatabaseType;
+use common_meta_types::AuthInfo;
+use common_meta_types::GrantObject;
+use common_meta_types::UserInfo;
+use common_meta_types::UserPrivilegeSet;
+use common_sql::plans::OptimizeTableAction;
+use common_sql::plans::OptimizeTablePlan;
+use common_sql::plans::ReclusterTablePlan;
+use common_sql::Metadata;
+use common_storages_fuse::FuseTable;
+use common_storages_system::MaintenanceHistoryLogElement;
+use common_storages_system::MaintenanceHistoryQueue;
+use futures::future::select;
+use futures::future::Either;
+use parking_lot::Mutex;
+use parking_lot::RwLock;
+use tracing::info;
+use tracing::warn;
+
+use crate::interpreters::OptimizeTableInterpreter;
+use crate::interpreters::ReclusterTableInterpreter;
+use crate::interpreters::TableMutationLock;
+use crate::sessions::QueryContext;
+use crate::sessions::SessionManager;
+use crate::sessions::SessionType;
+use crate::sessions::TableContext;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MaintenanceAction {
+    Compact,
+    Recluster,
+    Purge,
+}
+
+impl Display for MaintenanceAction {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            MaintenanceAction::Compact => write!(f, "compact"),
+            MaintenanceAction::Recluster => write!(f, "recluster"),
+            MaintenanceAction::Purge => write!(f, "purge"),
+        }
+    }
+}
+
+/// What this node has done to a table, so that an unchanged table is not maintained again.
+struct TableMaintenanceState {
+    /// Snapshot location left by the last compaction or recluster.
+    maintained_snapshot_loc: Option<String>,
+    /// Snapshot location when the table was purged last time.
+    purged_snapshot_loc: Option<String>,
+    last_purge_time: DateTime<Utc>,
+}
+
+/// Keeps the fuse tables of the tenant in shape in the background.
+///
+/// Every round the snapshot of each table is checked, tables with small blocks are compacted,
+/// clustered tables are reclustered and the orphan snapshots beyond the retention period are
+/// purged. A table is only maintained if its mutation lock is free at the moment, so only one
+/// node of the cluster works on a table and user mutations are never blocked behind us.
+/// The activity is recorded in `system.maintenance_history`.
+pub struct MaintenanceService {
+    enabled: bool,
+    tenant: String,
+    interval: Duration,
+    max_concurrency: usize,
+    started_at: DateTime<Utc>,
+    states: Mutex<HashMap<u64, TableMaintenanceState>>,
+    shutdown: Arc<AtomicBool>,
+    shutdown_notify: Arc<Notify>,
+}
+
+impl MaintenanceService {
+    pub fn init(conf: &Config) -> Result<()> {
+        GlobalInstance::set(Self::create(conf));
+
+        Ok(())
+    }
+
+    pub fn create(conf: &Config) -> Arc<MaintenanceService> {
+        Arc::new(MaintenanceService {
+            enabled: conf.query.maintenance_enabled,
+            tenant: conf.query.tenant_id.clone(),
+            interval: Duration::from_secs(conf.query.maintenance_interval_secs),
+            max_concurrency: std::cmp::max(conf.query.maintenance_max_concurrency as usize, 1),
+            started_at: Utc::now(),
+            states: Mutex::new(HashMap::new()),
+            shutdown: Arc::new(AtomicBool::new(false)),
+            shutdown_notify: Arc::new(Notify::new()),
+        })
+    }
+
+    pub fn instance() -> Arc<MaintenanceService> {
+        GlobalInstance::get()
+    }
+
+    /// Start the maintenance rounds, does nothing if the service is not enabled.
+    pub fn start(self: &Arc<Self>) {
+        if !self.enabled {
+            return;
+        }
+
+        info!(
+            "Background maintenance started, interval {:?}, max concurrency {}",
+            self.interval, self.max_concurrency
+        );
+
+        let service = self.clone();
+        tokio::spawn(async move {
+            let mut shutdown_notified = Box::pin(service.shutdown_notify.notified());
+
+            while !service.shutdown.load(Ordering::Relaxed) {
+                match select(shutdown_notified, Box::pin(sleep(service.interval))).await {
+                    Either::Left((_, _)) => {
+                        break;
+                    }
+                    Either::Right((_, new_shutdown_notified)) => {
+                        shutdown_notified = new_shutdown_notified;
+                        if let Err(cause) = service.maintain_tables().await {
+                            warn!("Background maintenance round failure: {:?}", cause);
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    /// Stop scheduling new work, the running jobs are killed with the other sessions.
+    pub fn shutdown(&self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        self.shutdown_notify.notify_waiters();
+    }
+
+    /// Run a round of maintenance over the fuse tables of the tenant.
+    pub async fn maintain_tables(self: &Arc<Self>) -> Result<()> {
+        let ctx = self.create_query_context().await?;
+        let catalog = ctx.get_catalog(CATALOG_DEFAULT)?;
+        let retention_hours = ctx.get_settings().get_retention_period()?;
+
+        let mut tables = vec![];
+        for database in catalog.list_databases(&self.tenant).await? {
+            for table in catalog.list_tables(&self.tenant, database.name()).await? {
+                if FuseTable::try_from_table(table.as_ref()).is_err()
+                    || matches!(table.get_table_info().db_type, DatabaseType::ShareDB(_))
+                {
+                    continue;
+                }
+                tables.push((database.name().to_string(), table));
+            }
+        }
+
+        // Each table is maintained by a task of its own, at most `max_concurrency` at a time.
+        let semaphore = Arc::new(Semaphore::new(self.max_concurrency));
+        let mut handles = Vec::with_capacity(tables.len());
+        for (database, table) in tables {
+            let permit = semaphore.clone().acquire_owned().await.map_err(|e| {
+                ErrorCode::Internal(format!("Background maintenance semaphore closed: {}", e))
+            })?;
+            if self.shutdown.load(Ordering::Relaxed) {
+                break;
+            }
+
+            let service = self.clone();
+            let catalog = catalog.clone();
+            handles.push(tokio::spawn(async move {
+                let _permit = permit;
+                if let Err(cause) = service
+                    .maintain_table(catalog, &database, table.clone(), retention_hours)
+                    .await
+                {
+                    warn!(
+                        "Background maintenance of table {}.{} failure: {:?}",
+                        database,
+                        table.name(),
+                        cause
+                    );
+                }
+            }));
+        }
+
+        for handle in handles {
+            if let Err(cause) = handle.await {
+                warn!("Background maintenance task failure: {:?}", cause);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn maintain_table(
+        self: &Arc<Self>,
+        catalog: Arc<dyn Catalog>,
+        database: &str,
+        table: Arc<dyn Table>,
+        retention_hours: u64,
+    ) -> Result<()> {
+        let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+        let snapshot = match fuse_table.read_table_snapshot().await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(()),
+        };
+        let snapshot_loc = fuse_table.snapshot_loc().await?;
+
+        let mut actions = vec![];
+        let changed = {
+            let mut states = self.states.lock();
+            let state = states
+                .entry(table.get_id())
+                .or_insert_with(|| TableMaintenanceState {
+                    maintained_snapshot_loc: None,
+                    purged_snapshot_loc: None,
+                    last_purge_time: self.started_at,
+                });
+            state.maintained_snapshot_loc != snapshot_loc
+        };
+
+        let mut ctx = None;
+        if changed {
+            // Blocks that do not reach the compact thresholds can be merged together.
+            let summary = &snapshot.summary;
+            let small_blocks = summary
+                .block_count
+                .saturating_sub(summary.perfect_block_count);
+            if small_blocks > 1 {
+                actions.push(MaintenanceAction::Compact);
+            }
+            // Blocks that overlap deep on the cluster key are sorted again.
+            if table.get_table_info().meta.cluster_key().is_some() {
+                let recluster_ctx = self.create_query_context().await?;
+                if fuse_table.need_recluster(recluster_ctx.clone()).await? {
+                    actions.push(MaintenanceAction::Recluster);
+                }
+                ctx = Some(recluster_ctx);
+            }
+        }
+
+        {
+            let mut states = self.states.lock();
+            let state = match states.get_mut(&table.get_id()) {
+                Some(state) => state,
+                None => return Ok(()),
+            };
+            if changed && actions.is_empty() {
+                // Nothing to do with this snapshot, do not check it again.
+                state.maintained_snapshot_loc = snapshot_loc.clone();
+            }
+
+            if snapshot.prev_snapshot_id.is_some() && state.purged_snapshot_loc != snapshot_loc {
+                // The orphan snapshots are all beyond the retention period once the current
+                // one is, for a table that keeps changing purge once per retention period.
+                let retention_boundary =
+                    Utc::now() - chrono::Duration::hours(retention_hours as i64);
+                let snapshot_expired = snapshot
+                    .timestamp
+                    .map(|ts| ts < retention_boundary)
+                    .unwrap_or(true);
+                if snapshot_expired || state.last_purge_time < retention_boundary {
+                    actions.push(MaintenanceAction::Purge);
+                }
+            }
+        }
+
+        if actions.is_empty() {
+            return Ok(());
+        }
+
+        let ctx = match ctx {
+            Some(ctx) => ctx,
+            None => self.create_query_context().await?,
+        };
+        let table_lock =
+            match TableMutationLock::try_lock_nowait(ctx.clone(), table.as_ref()).await? {
+                Some(table_lock) => table_lock,
+                None => {
+                    info!(
+                        "Skip background maintenance of table {}.{}, it is locked by others",
+                        database,
+                        table.name()
+                    );
+                    return Ok(());
+                }
+            };
+
+        let mut purged = false;
+        let mut res = Ok(());
+        for action in actions {
+            // Do not run the next action once the lock is lost, it would commit without the lock.
+            if let Err(cause) = table_lock.check_lost() {
+                res = Err(cause);
+                break;
+            }
+            let start = SystemTime::now();
+            res = self
+                .run_action_blocking(ctx.clone(), database, table.name(), action)
+                .await;
+            // The history is best effort, failing to record it must not keep the table locked.
+            if let Err(cause) = Self::write_log(&ctx, start, database, table.name(), action, &res) {
+                warn!(
+                    "Failed to record background maintenance of table {}.{}: {:?}",
+                    database,
+                    table.name(),
+                    cause
+                );
+            }
+            if res.is_err() {
+                break;
+            }
+            if action == MaintenanceAction::Purge {
+                purged = true;
+            }
+        }
+
+        let unlock_res = table_lock.unlock().await;
+        res?;
+        unlock_res?;
+
+        // Tables are cached by the context, refresh the snapshot location by the catalog.
+        let table = catalog
+            .get_table(&self.tenant, database, table.name())
+            .await?;
+        let snapshot_loc = FuseTable::try_from_table(table.as_ref())?
+            .snapshot_loc()
+            .await?;
+        let mut states = self.states.lock();
+        if let Some(state) = states.get_mut(&table.get_id()) {
+            state.maintained_snapshot_loc = snapshot_loc.clone();
+            if purged {
+                state.purged_snapshot_loc = snapshot_loc;
+                state.last_purge_time = Utc::now();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Run `action` on a blocking thread, the pipelines of the action are executed by
+    /// blocking executors which must not occupy the async workers.
+    async fn run_action_blocking(
+        self: &Arc<Self>,
+        ctx: Arc<QueryContext>,
+        database: &str,
+        table_name: &str,
+        action: MaintenanceAction,
+    ) -> Result<()> {
+        let service = self.clone();
+        let database = database.to_string();
+        let table_name = table_name.to_string();
+        tokio::task::spawn_blocking(move || {
+            GlobalIORuntime::instance().block_on(async move {
+                service
+                    .run_action(ctx, &database, &table_name, action)
+                    .await
+            })
+        })
+        .await
+        .map_err(|e| {
+            ErrorCode::Internal(format!("Background maintenance action panicked: {}", e))
+        })?
+    }
+
+    async fn run_action(
+        &self,
+        ctx: Arc<QueryContext>,
+        database: &str,
+        table_name: &str,
+        action: MaintenanceAction,
+    ) -> Result<()> {
+        match action {
+            MaintenanceAction::Compact | MaintenanceAction::Purge => {
+                let optimize_action = if action == MaintenanceAction::Compact {
+                    OptimizeTableAction::CompactBlocks(None)
+                } else {
+                    OptimizeTableAction::Purge(None)
+                };
+                let plan = OptimizeTablePlan {
+                    catalog: CATALOG_DEFAULT.to_string(),
+                    database: database.to_string(),
+                    table: table_name.to_string(),
+                    action: optimize_action,
+                };
+                let table = ctx
+                    .get_catalog(CATALOG_DEFAULT)?
+                    .get_table(&self.tenant, database, table_name)
+                    .await?;
+                OptimizeTableInterpreter::try_create(ctx, plan)?
+                    .optimize(table)
+                    .await
+            }
+            MaintenanceAction::Recluster => {
+                let plan = ReclusterTablePlan {
+                    tenant: self.tenant.clone(),
+                    catalog: CATALOG_DEFAULT.to_string(),
+                    database: database.to_string(),
+                    table: table_name.to_string(),
+                    is_final: false,
+                    metadata: Arc::new(RwLock::new(Metadata::default())),
+                    push_downs: None,
+                };
+                ReclusterTableInterpreter::try_create(ctx, plan)?
+                    .recluster(None)
+                    .await
+            }
+        }
+    }
+
+    async fn create_query_context(&self) -> Result<Arc<QueryContext>> {
+        let session = SessionManager::instance()
+            .create_session(SessionType::Maintenance)
+            .await?;
+
+        let mut user_info = UserInfo::new("maintenance", "%", AuthInfo::None);
+        user_info.grants.grant_privileges(
+            &GrantObject::Global,
+            UserPrivilegeSet::available_privileges_on_global(),
+        );
+        session.set_authed_user(user_info, None).await?;
+
+        session.create_query_context().await
+    }
+
+    fn write_log(
+        ctx: &QueryContext,
+        start: SystemTime,
+        database: &str,
+        table_name: &str,
+        action: MaintenanceAction,
+        res: &Result<()>,
+    ) -> Result<()> {
+        MaintenanceHistoryQueue::instance()?.append_data(MaintenanceHistoryLogElement {
+            start_time: start
+                .duration_since(UNIX_EPOCH)
+                .expect("Time went backwards")
+                .as_micros() as i64,
+            end_time: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("Time went backwards")
+                .as_micros() as i64,
+            node: ctx.get_cluster().local_id.clone(),
+            database: database.to_string(),
+            table: table_name.to_string(),
+            action: action.to_string(),
+            error: match res {
+                Ok(_) => "".to_string(),
+                Err(cause) => cause.to_string(),
+            },
+        })
+    }
+}
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::VecDeque;
 use std::sync::Arc;
 
 use async_channel::Receiver;
@@ -46,16 +47,23 @@ use common_sql::executor::HashJoin;
 use common_sql::executor::Limit;
 use common_sql::executor::PhysicalPlan;
 use common_sql::executor::Project;
+use common_sql::executor::RecursiveCte;
+use common_sql::executor::RecursiveCteScan;
 use common_sql::executor::Sort;
 use common_sql::executor::TableScan;
 use common_sql::executor::UnionAll;
+use common_sql::executor::Window;
+use common_sql::executor::WindowFunction;
 use common_sql::plans::JoinType;
 use common_sql::ColumnBinding;
 use common_sql::IndexType;
+use parking_lot::Mutex;
 
 use crate::pipelines::processors::port::InputPort;
 use crate::pipelines::processors::transforms::efficiently_memory_final_aggregator;
+use crate::pipelines::processors::transforms::query_spill_dir;
 use crate::pipelines::processors::transforms::HashJoinDesc;
+use crate::pipelines::processors::transforms::RecursiveCteSource;
 use crate::pipelines::processors::transforms::RightSemiAntiJoinCompactor;
 use crate::pipelines::processors::transforms::TransformLeftJoin;
 use crate::pipelines::processors::transforms::TransformMarkJoin;
@@ -64,7 +72,9 @@ use crate::pipelines::processors::transforms::TransformRightJoin;
 use crate::pipelines::processors::transforms::TransformRightSemiAntiJoin;
 use crate::pipelines::processors::AggregatorParams;
 use crate::pipelines::processors::AggregatorTransformParams;
+use crate::pipelines::processors::BlocksSource;
 use crate::pipelines::processors::JoinHashTable;
+use crate::pipelines::processors::LagLeadImpl;
 use crate::pipelines::processors::LeftJoinCompactor;
 use crate::pipelines::processors::MarkJoinCompactor;
 use crate::pipelines::processors::RightJoinCompactor;
@@ -78,6 +88,10 @@ use crate::pipelines::processors::TransformLimit;
 use crate::pipelines::processors::TransformResortAddOn;
 use crate::pipelines::processors::TransformSortMerge;
 use crate::pipelines::processors::TransformSortPartial;
+use crate::pipelines::processors::TransformSortSpill;
+use crate::pipelines::processors::TransformWindow;
+use crate::pipelines::processors::WindowCompactor;
+use crate::pipelines::processors::WindowFunctionImpl;
 use crate::pipelines::Pipeline;
 use crate::pipelines::PipelineBuildResult;
 use crate::sessions::QueryContext;
@@ -125,10 +139,13 @@ impl PipelineBuilder {
             PhysicalPlan::AggregateFinal(aggregate) => self.build_aggregate_final(aggregate),
             PhysicalPlan::Sort(sort) => self.build_sort(sort),
             PhysicalPlan::Limit(limit) => self.build_limit(limit),
+            PhysicalPlan::Window(window) => self.build_window(window),
             PhysicalPlan::HashJoin(join) => self.build_join(join),
             PhysicalPlan::ExchangeSink(sink) => self.build_exchange_sink(sink),
             PhysicalPlan::ExchangeSource(source) => self.build_exchange_source(source),
             PhysicalPlan::UnionAll(union_all) => self.build_union_all(union_all),
+            PhysicalPlan::RecursiveCte(recursive_cte) => self.build_recursive_cte(recursive_cte),
+            PhysicalPlan::RecursiveCteScan(scan) => self.build_recursive_cte_scan(scan),
             PhysicalPlan::DistributedInsertSelect(insert_select) => {
                 self.build_distributed_insert_select(insert_select)
             }
@@ -147,7 +164,6 @@ impl PipelineBuilder {
     fn build_join_state(&mut self, join: &HashJoin) -> Result<Arc<JoinHashTable>> {
         JoinHashTable::create_join_state(
             self.ctx.clone(),
-            &join.build_keys,
             join.build.output_schema()?,
             join.probe.output_schema()?,
             HashJoinDesc::create(join)?,
@@ -429,6 +445,15 @@ impl PipelineBuilder {
             })
             .collect::<Result<Vec<_>>>()?;
 
+        self.build_sort_pipeline(input_schema, sort_desc, sort.limit)
+    }
+
+    fn build_sort_pipeline(
+        &mut self,
+        input_schema: DataSchemaRef,
+        sort_desc: Vec<SortColumnDescription>,
+        limit: Option<usize>,
+    ) -> Result<()> {
         let block_size = self.ctx.get_settings().get_max_block_size()? as usize;
 
         if self.main_pipeline.output_len() == 1 {
@@ -438,28 +463,131 @@ impl PipelineBuilder {
         }
         // Sort
         self.main_pipeline.add_transform(|input, output| {
-            TransformSortPartial::try_create(input, output, sort.limit, sort_desc.clone())
+            TransformSortPartial::try_create(input, output, limit, sort_desc.clone())
         })?;
 
         // Merge
-        self.main_pipeline.add_transform(|input, output| {
-            TransformSortMerge::try_create(
-                input,
-                output,
-                SortMergeCompactor::new(block_size, sort.limit, sort_desc.clone()),
-            )
-        })?;
+        let spilling_threshold = self
+            .ctx
+            .get_settings()
+            .get_sort_spilling_bytes_threshold()? as usize;
+        if limit.is_none() && spilling_threshold > 0 {
+            // The sort with limit only keeps the top rows in memory, no need to spill.
+            let spill_dir = query_spill_dir(&self.ctx.get_id());
+            self.main_pipeline.add_transform(|input, output| {
+                TransformSortSpill::try_create(
+                    input,
+                    output,
+                    input_schema.clone(),
+                    block_size,
+                    spilling_threshold,
+                    spill_dir.clone(),
+                    sort_desc.clone(),
+                )
+            })?;
+        } else {
+            self.main_pipeline.add_transform(|input, output| {
+                TransformSortMerge::try_create(
+                    input,
+                    output,
+                    SortMergeCompactor::new(block_size, limit, sort_desc.clone()),
+                )
+            })?;
+        }
 
         // Concat merge in single thread
         try_add_multi_sort_merge(
             &mut self.main_pipeline,
             input_schema,
             block_size,
-            sort.limit,
+            limit,
             sort_desc,
         )
     }
 
+    fn build_window(&mut self, window: &Window) -> Result<()> {
+        self.build_pipeline(&window.input)?;
+
+        let input_schema = window.input.output_schema()?;
+
+        let partition_by = window
+            .partition_by
+            .iter()
+            .map(|index| input_schema.index_of(&index.to_string()))
+            .collect::<Result<Vec<_>>>()?;
+
+        let order_by = window
+            .order_by
+            .iter()
+            .map(|desc| {
+                let offset = input_schema.index_of(&desc.order_by.to_string())?;
+                Ok(SortColumnDescription {
+                    offset,
+                    asc: desc.asc,
+                    nulls_first: desc.nulls_first,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Rows of the same partition must be adjacent and ordered by the window `ORDER BY`.
+        let sort_desc = partition_by
+            .iter()
+            .map(|offset| SortColumnDescription {
+                offset: *offset,
+                asc: true,
+                nulls_first: false,
+            })
+            .chain(order_by.iter().cloned())
+            .collect::<Vec<_>>();
+
+        if !sort_desc.is_empty() {
+            self.build_sort_pipeline(input_schema, sort_desc, None)?;
+        }
+
+        let func = match &window.func {
+            WindowFunction::Aggregate(agg) => {
+                let params = agg
+                    .sig
+                    .params
+                    .iter()
+                    .map(|p| p.clone().into_scalar())
+                    .collect();
+                WindowFunctionImpl::Aggregate {
+                    func: AggregateFunctionFactory::instance().get(
+                        agg.sig.name.as_str(),
+                        params,
+                        agg.sig.args.clone(),
+                    )?,
+                    arg_offsets: agg.args.clone(),
+                }
+            }
+            WindowFunction::LagLead(lag_lead) => WindowFunctionImpl::LagLead(LagLeadImpl {
+                is_lag: lag_lead.is_lag,
+                arg_offset: lag_lead.arg,
+                offset: lag_lead.offset,
+                default_offset: lag_lead.default,
+                return_type: lag_lead.return_type.clone(),
+            }),
+            WindowFunction::RowNumber => WindowFunctionImpl::RowNumber,
+            WindowFunction::Rank => WindowFunctionImpl::Rank,
+            WindowFunction::DenseRank => WindowFunctionImpl::DenseRank,
+        };
+
+        self.main_pipeline.resize(1)?;
+        self.main_pipeline.add_transform(|input, output| {
+            TransformWindow::try_create(
+                input,
+                output,
+                WindowCompactor::create(
+                    func.clone(),
+                    partition_by.clone(),
+                    order_by.clone(),
+                    window.window_frame.clone(),
+                ),
+            )
+        })
+    }
+
     fn build_limit(&mut self, limit: &Limit) -> Result<()> {
         self.build_pipeline(&limit.input)?;
 
@@ -596,6 +724,30 @@ impl PipelineBuilder {
         Ok(())
     }
 
+    fn build_recursive_cte(&mut self, recursive_cte: &RecursiveCte) -> Result<()> {
+        let max_iterations = self.ctx.get_settings().get_max_recursive_cte_iterations()?;
+        self.main_pipeline.add_source(
+            |output| {
+                RecursiveCteSource::create(
+                    self.ctx.clone(),
+                    output,
+                    recursive_cte.clone(),
+                    max_iterations,
+                )
+            },
+            1,
+        )
+    }
+
+    fn build_recursive_cte_scan(&mut self, scan: &RecursiveCteScan) -> Result<()> {
+        let blocks = self.ctx.get_recursive_cte_table(scan.cte_index);
+        let data_blocks = Arc::new(Mutex::new(VecDeque::from(blocks)));
+        self.main_pipeline.add_source(
+            |output| BlocksSource::create(self.ctx.clone(), output, data_blocks.clone()),
+            1,
+        )
+    }
+
     pub fn build_distributed_insert_select(
         &mut self,
         insert_select: &DistributedInsertSelect,
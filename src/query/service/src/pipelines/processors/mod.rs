@@ -38,6 +38,7 @@ pub use transforms::HashJoinDesc;
 pub use transforms::HashJoinState;
 pub use transforms::HashTable;
 pub use transforms::JoinHashTable;
+pub use transforms::LagLeadImpl;
 pub use transforms::LeftJoinCompactor;
 pub use transforms::MarkJoinCompactor;
 pub use transforms::RightJoinCompactor;
@@ -55,3 +56,7 @@ pub use transforms::TransformLimit;
 pub use transforms::TransformResortAddOn;
 pub use transforms::TransformSortMerge;
 pub use transforms::TransformSortPartial;
+pub use transforms::TransformSortSpill;
+pub use transforms::TransformWindow;
+pub use transforms::WindowCompactor;
+pub use transforms::WindowFunctionImpl;
@@ -38,6 +38,14 @@ impl AggregateInfo {
             overflow: None,
         })
     }
+
+    // The spilled buckets are placed in the bucket -2, they will be split into each bucket when merging.
+    pub fn create_overflow(overflow: OverflowInfo) -> BlockMetaInfoPtr {
+        Box::new(AggregateInfo {
+            bucket: -2,
+            overflow: Some(overflow),
+        })
+    }
 }
 
 #[typetag::serde(name = "aggregate_info")]
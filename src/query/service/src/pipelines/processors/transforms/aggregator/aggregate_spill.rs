@@ -0,0 +1,156 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use common_base::runtime::MemStat;
+use common_base::runtime::GLOBAL_MEM_STAT;
+use common_config::GlobalConfig;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::arrow::deserialize_block;
+use common_expression::arrow::serialize_block;
+use common_expression::DataBlock;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::pipelines::processors::transforms::aggregator::aggregate_info::AggregateInfo;
+use crate::pipelines::processors::transforms::aggregator::aggregate_info::OverflowInfo;
+
+/// The local directory holding the spilled data of the query, it is removed when the query is finished.
+pub fn query_spill_dir(query_id: &str) -> PathBuf {
+    PathBuf::from(&GlobalConfig::instance().query.spill_dir).join(query_id)
+}
+
+/// Writes the two-level buckets of the aggregator to local disk when the memory usage
+/// exceeds the `group_by_spilling_bytes_threshold` setting.
+pub struct AggregateSpiller {
+    spill_dir: PathBuf,
+    bytes_threshold: usize,
+}
+
+impl AggregateSpiller {
+    pub fn create(query_id: &str, bytes_threshold: usize) -> AggregateSpiller {
+        AggregateSpiller {
+            spill_dir: query_spill_dir(query_id),
+            bytes_threshold,
+        }
+    }
+
+    pub fn need_spill(&self) -> bool {
+        let memory_usage = match MemStat::current() {
+            Some(mem_stat) => mem_stat.get_memory_usage(),
+            None => GLOBAL_MEM_STAT.get_memory_usage(),
+        };
+
+        memory_usage >= self.bytes_threshold as i64
+    }
+
+    /// Writes all the bucket blocks into one file, and returns the block carrying the `OverflowInfo` of it.
+    pub fn spill(&self, blocks: Vec<DataBlock>) -> Result<Option<DataBlock>> {
+        if blocks.is_empty() {
+            return Ok(None);
+        }
+
+        let instant = Instant::now();
+        std::fs::create_dir_all(&self.spill_dir)?;
+        let path = self.spill_dir.join(Uuid::new_v4().simple().to_string());
+        let mut file = File::create(&path)?;
+
+        let mut offset = 0;
+        let mut bucket_info = HashMap::with_capacity(blocks.len());
+        for block in blocks {
+            let bucket = match block.get_meta() {
+                Some(meta) => match meta.as_any().downcast_ref::<AggregateInfo>() {
+                    Some(info) if info.bucket >= 0 => info.bucket as usize,
+                    _ => return Err(ErrorCode::Internal("Only two-level blocks can be spilled")),
+                },
+                None => return Err(ErrorCode::Internal("Only two-level blocks can be spilled")),
+            };
+
+            let data = serialize_block(block)?;
+            file.write_all(&data)?;
+            bucket_info.insert(bucket, (offset, data.len()));
+            offset += data.len();
+        }
+        file.sync_data()?;
+
+        info!(
+            "Spill {} aggregate buckets({} bytes) to {:?}, elapsed: {:?}",
+            bucket_info.len(),
+            offset,
+            path,
+            instant.elapsed()
+        );
+
+        Ok(Some(DataBlock::empty_with_meta(
+            AggregateInfo::create_overflow(OverflowInfo {
+                temporary_path: path.to_string_lossy().to_string(),
+                bucket_info,
+            }),
+        )))
+    }
+}
+
+/// Splits the spilled file into one block for each bucket, so that the buckets can be
+/// restored separately when they are merged.
+pub fn split_spilled_buckets(overflow: &OverflowInfo) -> Vec<(isize, DataBlock)> {
+    overflow
+        .bucket_info
+        .iter()
+        .map(|(bucket, range)| {
+            let bucket_overflow = OverflowInfo {
+                temporary_path: overflow.temporary_path.clone(),
+                bucket_info: HashMap::from([(*bucket, *range)]),
+            };
+            let meta = Box::new(AggregateInfo {
+                bucket: *bucket as isize,
+                overflow: Some(bucket_overflow),
+            });
+            (*bucket as isize, DataBlock::empty_with_meta(meta))
+        })
+        .collect()
+}
+
+/// Reads back the spilled buckets if the block refers to the spilled file, otherwise returns the block itself.
+pub fn restore_spilled_block(block: DataBlock) -> Result<Vec<DataBlock>> {
+    let overflow = match block.get_meta() {
+        Some(meta) => match meta.as_any().downcast_ref::<AggregateInfo>() {
+            Some(AggregateInfo {
+                overflow: Some(overflow),
+                ..
+            }) => overflow.clone(),
+            _ => return Ok(vec![block]),
+        },
+        None => return Ok(vec![block]),
+    };
+
+    let mut file = File::open(&overflow.temporary_path)?;
+    let mut blocks = Vec::with_capacity(overflow.bucket_info.len());
+    for (offset, length) in overflow.bucket_info.values() {
+        let mut data = vec![0; *length];
+        file.seek(SeekFrom::Start(*offset as u64))?;
+        file.read_exact(&mut data)?;
+        blocks.push(deserialize_block(&data)?);
+    }
+
+    Ok(blocks)
+}
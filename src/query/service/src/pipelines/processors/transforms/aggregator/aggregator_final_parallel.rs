@@ -33,6 +33,8 @@ use common_hashtable::HashtableLike;
 use tracing::info;
 
 use crate::pipelines::processors::transforms::aggregator::aggregate_info::AggregateInfo;
+use crate::pipelines::processors::transforms::aggregator::aggregate_spill::restore_spilled_block;
+use crate::pipelines::processors::transforms::aggregator::aggregate_spill::split_spilled_buckets;
 use crate::pipelines::processors::transforms::group_by::Area;
 use crate::pipelines::processors::transforms::group_by::GroupColumnsBuilder;
 use crate::pipelines::processors::transforms::group_by::KeysColumnIter;
@@ -76,6 +78,18 @@ where Method: HashMethod + PolymorphicKeysHelper<Method> + Send + 'static
         let mut bucket = -1;
         if let Some(meta_info) = block.get_meta() {
             if let Some(meta_info) = meta_info.as_any().downcast_ref::<AggregateInfo>() {
+                if let Some(overflow) = &meta_info.overflow {
+                    // The spilled buckets are restored lazily when merging each bucket.
+                    for (bucket, spilled_block) in split_spilled_buckets(overflow) {
+                        self.buckets_blocks
+                            .entry(bucket)
+                            .or_default()
+                            .push(spilled_block);
+                    }
+
+                    return Ok(());
+                }
+
                 bucket = meta_info.bucket;
             }
         }
@@ -172,54 +186,57 @@ where Method: HashMethod + PolymorphicKeysHelper<Method> + Send + 'static
             return Ok(vec![]);
         }
         for data_block in blocks {
-            let block = data_block.convert_to_full();
-            // 1.1 and 1.2.
-            let aggregate_function_len = self.params.aggregate_functions.len();
-            let keys_column = block
-                .get_by_offset(aggregate_function_len)
-                .value
-                .as_column()
-                .unwrap();
-            let keys_iter = self.method.keys_iter_from_column(keys_column)?;
-
-            if !HAS_AGG {
-                unsafe {
-                    for key in keys_iter.iter() {
-                        let _ = self.hash_table.insert_and_entry(key);
+            for block in restore_spilled_block(data_block)? {
+                let block = block.convert_to_full();
+                // 1.1 and 1.2.
+                let aggregate_function_len = self.params.aggregate_functions.len();
+                let keys_column = block
+                    .get_by_offset(aggregate_function_len)
+                    .value
+                    .as_column()
+                    .unwrap();
+                let keys_iter = self.method.keys_iter_from_column(keys_column)?;
+
+                if !HAS_AGG {
+                    unsafe {
+                        for key in keys_iter.iter() {
+                            let _ = self.hash_table.insert_and_entry(key);
+                        }
+                    }
+                } else {
+                    // first state places of current block
+                    let places = self.lookup_state(&keys_iter);
+
+                    let states_columns = (0..aggregate_function_len)
+                        .map(|i| block.get_by_offset(i))
+                        .collect::<Vec<_>>();
+                    let mut states_binary_columns = Vec::with_capacity(states_columns.len());
+
+                    for agg in states_columns.iter().take(aggregate_function_len) {
+                        let aggr_column =
+                            agg.value.as_column().unwrap().as_string().ok_or_else(|| {
+                                ErrorCode::IllegalDataType(format!(
+                                    "Aggregation column should be StringType, but got {:?}",
+                                    agg.value
+                                ))
+                            })?;
+                        states_binary_columns.push(aggr_column);
                     }
-                }
-            } else {
-                // first state places of current block
-                let places = self.lookup_state(&keys_iter);
-
-                let states_columns = (0..aggregate_function_len)
-                    .map(|i| block.get_by_offset(i))
-                    .collect::<Vec<_>>();
-                let mut states_binary_columns = Vec::with_capacity(states_columns.len());
-
-                for agg in states_columns.iter().take(aggregate_function_len) {
-                    let aggr_column =
-                        agg.value.as_column().unwrap().as_string().ok_or_else(|| {
-                            ErrorCode::IllegalDataType(format!(
-                                "Aggregation column should be StringType, but got {:?}",
-                                agg.value
-                            ))
-                        })?;
-                    states_binary_columns.push(aggr_column);
-                }
 
-                let aggregate_functions = &self.params.aggregate_functions;
-                let offsets_aggregate_states = &self.params.offsets_aggregate_states;
-                if let Some(temp_place) = self.temp_place {
-                    for (row, place) in places.iter().enumerate() {
-                        for (idx, aggregate_function) in aggregate_functions.iter().enumerate() {
-                            let final_place = place.next(offsets_aggregate_states[idx]);
-                            let state_place = temp_place.next(offsets_aggregate_states[idx]);
-
-                            let mut data =
-                                unsafe { states_binary_columns[idx].index_unchecked(row) };
-                            aggregate_function.deserialize(state_place, &mut data)?;
-                            aggregate_function.merge(final_place, state_place)?;
+                    let aggregate_functions = &self.params.aggregate_functions;
+                    let offsets_aggregate_states = &self.params.offsets_aggregate_states;
+                    if let Some(temp_place) = self.temp_place {
+                        for (row, place) in places.iter().enumerate() {
+                            for (idx, aggregate_function) in aggregate_functions.iter().enumerate()
+                            {
+                                let final_place = place.next(offsets_aggregate_states[idx]);
+                                let state_place = temp_place.next(offsets_aggregate_states[idx]);
+
+                                let mut data =
+                                    unsafe { states_binary_columns[idx].index_unchecked(row) };
+                                aggregate_function.deserialize(state_place, &mut data)?;
+                                aggregate_function.merge(final_place, state_place)?;
+                            }
                         }
                     }
                 }
@@ -34,6 +34,7 @@ use crate::pipelines::processors::transforms::aggregator::aggregate_info::Aggreg
 use crate::pipelines::processors::transforms::aggregator::aggregator_final_parallel::ParallelFinalAggregator;
 use crate::pipelines::processors::transforms::aggregator::PartialAggregator;
 use crate::pipelines::processors::transforms::aggregator::SingleStateAggregator;
+use crate::pipelines::processors::transforms::group_by::Area;
 use crate::pipelines::processors::transforms::group_by::KeysColumnBuilder;
 use crate::pipelines::processors::transforms::group_by::PolymorphicKeysHelper;
 use crate::pipelines::processors::transforms::group_by::TwoLevelHashMethod;
@@ -64,6 +65,14 @@ where Self: Aggregator + Send
             Self::NAME
         )))
     }
+
+    // Take out all the buckets and reset the aggregator, so that it can continue to consume data.
+    fn spill_two_level_block(_agg: &mut Self::TwoLevelAggregator) -> Result<Vec<DataBlock>> {
+        Err(ErrorCode::Unimplemented(format!(
+            "Spilling two level aggregator is unimplemented for {}",
+            Self::NAME
+        )))
+    }
 }
 
 impl<Method> TwoLevelAggregatorLike for PartialAggregator<true, Method>
@@ -211,6 +220,13 @@ where
         agg.states_dropped = true;
         Ok(data_blocks)
     }
+
+    fn spill_two_level_block(agg: &mut Self::TwoLevelAggregator) -> Result<Vec<DataBlock>> {
+        let blocks = Self::convert_two_level_block(agg)?;
+        agg.area = Some(Area::create());
+        agg.states_dropped = false;
+        Ok(blocks)
+    }
 }
 
 impl<Method> TwoLevelAggregatorLike for PartialAggregator<false, Method>
@@ -299,6 +315,13 @@ where
         agg.states_dropped = true;
         Ok(chunks)
     }
+
+    fn spill_two_level_block(agg: &mut Self::TwoLevelAggregator) -> Result<Vec<DataBlock>> {
+        let blocks = Self::convert_two_level_block(agg)?;
+        agg.area = Some(Area::create());
+        agg.states_dropped = false;
+        Ok(blocks)
+    }
 }
 
 impl TwoLevelAggregatorLike for SingleStateAggregator<true> {
@@ -350,3 +373,9 @@ impl<T: TwoLevelAggregatorLike> Aggregator for TwoLevelAggregator<T> {
         T::convert_two_level_block(&mut self.inner)
     }
 }
+
+impl<T: TwoLevelAggregatorLike> TwoLevelAggregator<T> {
+    pub fn spill_two_level_block(&mut self) -> Result<Vec<DataBlock>> {
+        T::spill_two_level_block(&mut self.inner)
+    }
+}
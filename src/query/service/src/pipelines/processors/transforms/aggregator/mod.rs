@@ -13,6 +13,7 @@
 // limitations under the License.
 
 mod aggregate_info;
+mod aggregate_spill;
 mod aggregator_final;
 mod aggregator_final_parallel;
 mod aggregator_params;
@@ -23,6 +24,10 @@ mod utils;
 
 pub use aggregate_info::AggregateInfo;
 pub use aggregate_info::OverflowInfo;
+pub use aggregate_spill::query_spill_dir;
+pub use aggregate_spill::restore_spilled_block;
+pub use aggregate_spill::split_spilled_buckets;
+pub use aggregate_spill::AggregateSpiller;
 pub use aggregator_final::KeysU128FinalAggregator;
 pub use aggregator_final::KeysU16FinalAggregator;
 pub use aggregator_final::KeysU256FinalAggregator;
@@ -93,6 +93,26 @@ impl HashJoinDesc {
         })
     }
 
+    /// Creates the description of an equi-join without other predicates, whose keys are
+    /// evaluated on the blocks of both sides directly, e.g. the join of `MERGE INTO`.
+    pub fn create_equi_join(
+        join_type: JoinType,
+        build_keys: Vec<Expr>,
+        probe_keys: Vec<Expr>,
+    ) -> Result<HashJoinDesc> {
+        Ok(HashJoinDesc {
+            join_type,
+            build_keys,
+            probe_keys,
+            other_predicate: None,
+            marker_join_desc: MarkJoinDesc {
+                has_null: RwLock::new(false),
+            },
+            from_correlated_subquery: false,
+            join_state: JoinState::create()?,
+        })
+    }
+
     fn join_predicate(non_equi_conditions: &[PhysicalScalar]) -> Result<Option<Expr>> {
         if non_equi_conditions.is_empty() {
             return Ok(None);
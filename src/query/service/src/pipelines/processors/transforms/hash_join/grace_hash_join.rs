@@ -0,0 +1,461 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs::File;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::arrow::deserialize_block;
+use common_expression::arrow::serialize_block;
+use common_expression::DataBlock;
+use common_expression::Evaluator;
+use common_expression::HashMethod;
+use common_functions::scalars::BUILTIN_FUNCTIONS;
+use common_hashtable::FastHash;
+use common_hashtable::HashMap;
+use common_hashtable::UnsizedHashMap;
+use common_sql::plans::JoinType;
+use parking_lot::RwLock;
+use tracing::info;
+use uuid::Uuid;
+
+use super::ProbeState;
+use crate::pipelines::processors::transforms::hash_join::desc::HashJoinDesc;
+use crate::pipelines::processors::transforms::hash_join::desc::JoinState;
+use crate::pipelines::processors::transforms::hash_join::desc::MarkJoinDesc;
+use crate::pipelines::processors::transforms::hash_join::row::RowPtr;
+use crate::pipelines::processors::transforms::query_spill_dir;
+use crate::pipelines::processors::FixedKeyHashTable;
+use crate::pipelines::processors::HashJoinState;
+use crate::pipelines::processors::HashTable;
+use crate::pipelines::processors::JoinHashTable;
+use crate::pipelines::processors::SerializerHashTable;
+use crate::sessions::TableContext;
+
+/// The partitions are picked by the highest bits of the key hash, the lowest bits
+/// are left to the hash table of each partition.
+const SPILL_PARTITION_BITS: u64 = 4;
+pub const SPILL_PARTITIONS: usize = 1 << SPILL_PARTITION_BITS;
+/// A partition whose build side still exceeds the threshold is partitioned again with
+/// a different hash seed, at most `MAX_SPILL_LEVEL` times.
+const MAX_SPILL_LEVEL: usize = 3;
+
+/// Picks the partition of a key hash. The hash is remixed with a different seed for each
+/// level of the repartitioning, so that the rows of a large partition are scattered again.
+fn partition_index(hash: u64, level: usize) -> u8 {
+    let hash = match level {
+        0 => hash,
+        _ => {
+            // The finalizer of MurmurHash3.
+            let mut h = hash ^ (level as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+            h ^= h >> 33;
+            h = h.wrapping_mul(0xFF51_AFD7_ED55_8CCD);
+            h ^= h >> 33;
+            h = h.wrapping_mul(0xC4CE_B9FE_1A85_EC53);
+            h ^= h >> 33;
+            h
+        }
+    };
+    (hash >> (64 - SPILL_PARTITION_BITS)) as u8
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum JoinSide {
+    Build,
+    Probe,
+}
+
+struct SpillFile {
+    path: PathBuf,
+    file: File,
+    offset: u64,
+    /// The (offset, length) of the spilled blocks of each partition.
+    partitions: Vec<Vec<(u64, usize)>>,
+}
+
+impl SpillFile {
+    fn create(spill_dir: &PathBuf) -> Result<SpillFile> {
+        let path = spill_dir.join(Uuid::new_v4().simple().to_string());
+        Ok(SpillFile {
+            file: File::create(&path)?,
+            path,
+            offset: 0,
+            partitions: vec![vec![]; SPILL_PARTITIONS],
+        })
+    }
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Partitions both sides of the hash join into local files when the build side exceeds
+/// the `join_spilling_bytes_threshold` setting, the partitions are joined pairwise
+/// after all the probe blocks are spilled.
+pub struct JoinSpiller {
+    build_file: Mutex<SpillFile>,
+    probe_file: Mutex<SpillFile>,
+    next_partition: AtomicUsize,
+}
+
+impl JoinSpiller {
+    pub fn create(query_id: &str) -> Result<JoinSpiller> {
+        let spill_dir = query_spill_dir(query_id);
+        std::fs::create_dir_all(&spill_dir)?;
+        Ok(JoinSpiller {
+            build_file: Mutex::new(SpillFile::create(&spill_dir)?),
+            probe_file: Mutex::new(SpillFile::create(&spill_dir)?),
+            next_partition: AtomicUsize::new(0),
+        })
+    }
+
+    fn spill_file(&self, side: JoinSide) -> &Mutex<SpillFile> {
+        match side {
+            JoinSide::Build => &self.build_file,
+            JoinSide::Probe => &self.probe_file,
+        }
+    }
+
+    /// Appends the partitioned blocks, `blocks[i]` belongs to the partition `i`.
+    pub fn spill(&self, side: JoinSide, blocks: Vec<DataBlock>) -> Result<()> {
+        let mut serialized = Vec::with_capacity(blocks.len());
+        for (partition, block) in blocks.into_iter().enumerate() {
+            if !block.is_empty() {
+                serialized.push((partition, serialize_block(block)?));
+            }
+        }
+
+        let mut spill_file = self.spill_file(side).lock().unwrap();
+        for (partition, data) in serialized {
+            spill_file.file.write_all(&data)?;
+            let offset = spill_file.offset;
+            spill_file.partitions[partition].push((offset, data.len()));
+            spill_file.offset += data.len() as u64;
+        }
+        Ok(())
+    }
+
+    /// Returns the next partition to be joined, `None` if all the partitions are taken.
+    pub fn take_partition(&self) -> Option<usize> {
+        let partition = self.next_partition.fetch_add(1, Ordering::SeqCst);
+        (partition < SPILL_PARTITIONS).then_some(partition)
+    }
+
+    pub fn restore(&self, side: JoinSide, partition: usize) -> Result<Vec<DataBlock>> {
+        let (path, ranges) = {
+            let mut spill_file = self.spill_file(side).lock().unwrap();
+            spill_file.file.flush()?;
+            (
+                spill_file.path.clone(),
+                spill_file.partitions[partition].clone(),
+            )
+        };
+
+        let mut file = File::open(path)?;
+        let mut blocks = Vec::with_capacity(ranges.len());
+        for (offset, length) in ranges {
+            let mut data = vec![0; length];
+            file.seek(SeekFrom::Start(offset))?;
+            file.read_exact(&mut data)?;
+            blocks.push(deserialize_block(&data)?);
+        }
+        Ok(blocks)
+    }
+}
+
+impl JoinHashTable {
+    /// Mark join needs to know whether there are NULLs in the whole build side,
+    /// and cross join has no keys to partition, so they are always joined in memory.
+    pub(crate) fn support_spill(&self) -> bool {
+        !matches!(
+            self.hash_join_desc.join_type,
+            JoinType::Cross | JoinType::LeftMark | JoinType::RightMark
+        )
+    }
+
+    pub(crate) fn spiller(&self) -> Option<Arc<JoinSpiller>> {
+        self.spiller.read().clone()
+    }
+
+    /// Switches to the grace hash join if the build side exceeds the threshold, the blocks
+    /// already kept in memory are moved into the spilled partitions.
+    pub(crate) fn try_spill_build_side(&self, bytes: usize) -> Result<()> {
+        let threshold = self
+            .ctx
+            .get_settings()
+            .get_join_spilling_bytes_threshold()? as usize;
+        if threshold == 0 || !self.support_spill() {
+            return Ok(());
+        }
+
+        let build_bytes = self.build_bytes.fetch_add(bytes, Ordering::SeqCst) + bytes;
+        if build_bytes < threshold {
+            return Ok(());
+        }
+
+        let mut spiller = self.spiller.write();
+        if spiller.is_some() {
+            return Ok(());
+        }
+
+        let new_spiller = JoinSpiller::create(&self.ctx.get_id())?;
+        let mut blocks = std::mem::take(&mut *self.row_space.buffer.write().unwrap());
+        blocks.extend(
+            std::mem::take(&mut *self.row_space.chunks.write().unwrap())
+                .into_iter()
+                .map(|chunk| chunk.data_block),
+        );
+        for block in blocks.iter() {
+            let partitions = self.partition_block(block, JoinSide::Build, 0)?;
+            new_spiller.spill(JoinSide::Build, partitions)?;
+        }
+
+        info!(
+            "Build side of hash join exceeds {} bytes, spill {} blocks into {} partitions",
+            threshold,
+            blocks.len(),
+            SPILL_PARTITIONS
+        );
+        *spiller = Some(Arc::new(new_spiller));
+        Ok(())
+    }
+
+    /// Scatters the block by the hash of the join keys, the keys are encoded by the hash
+    /// method of the hash table, so that the equal keys of both sides are in the same partition.
+    pub(crate) fn partition_block(
+        &self,
+        block: &DataBlock,
+        side: JoinSide,
+        level: usize,
+    ) -> Result<Vec<DataBlock>> {
+        let (block, keys) = match side {
+            JoinSide::Build => (block.clone(), &self.hash_join_desc.build_keys),
+            JoinSide::Probe => (
+                self.nullable_probe_block(block),
+                &self.hash_join_desc.probe_keys,
+            ),
+        };
+        let block = &block;
+        let func_ctx = self.ctx.try_get_function_context()?;
+        let evaluator = Evaluator::new(block, func_ctx, &BUILTIN_FUNCTIONS);
+        let key_columns = keys
+            .iter()
+            .map(|expr| {
+                let return_type = expr.data_type();
+                Ok((
+                    evaluator
+                        .run(expr)
+                        .map_err(|(_, e)| {
+                            ErrorCode::Internal(format!("Invalid expression: {}", e))
+                        })?
+                        .convert_to_full_column(return_type, block.num_rows()),
+                    return_type.clone(),
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        macro_rules! partition_indices {
+            ($method: expr) => {{
+                let keys_state = $method.build_keys_state(&key_columns, block.num_rows())?;
+                $method
+                    .build_keys_iter(&keys_state)?
+                    .take(block.num_rows())
+                    .map(|key| partition_index(key.fast_hash(), level))
+                    .collect::<Vec<_>>()
+            }};
+        }
+
+        let indices = match &*self.hash_table.read() {
+            HashTable::SerializerHashTable(table) => partition_indices!(table.hash_method),
+            HashTable::KeyU8HashTable(table) => partition_indices!(table.hash_method),
+            HashTable::KeyU16HashTable(table) => partition_indices!(table.hash_method),
+            HashTable::KeyU32HashTable(table) => partition_indices!(table.hash_method),
+            HashTable::KeyU64HashTable(table) => partition_indices!(table.hash_method),
+            HashTable::KeyU128HashTable(table) => partition_indices!(table.hash_method),
+            HashTable::KeyU256HashTable(table) => partition_indices!(table.hash_method),
+            HashTable::KeyU512HashTable(table) => partition_indices!(table.hash_method),
+        };
+
+        block.scatter(&indices, SPILL_PARTITIONS)
+    }
+
+    /// Creates an empty hash table with the same join description, to join one partition.
+    fn create_partition_table(&self) -> Result<JoinHashTable> {
+        let hash_table = match &*self.hash_table.read() {
+            HashTable::SerializerHashTable(table) => {
+                HashTable::SerializerHashTable(SerializerHashTable {
+                    hash_table: UnsizedHashMap::<[u8], Vec<RowPtr>>::new(),
+                    hash_method: table.hash_method.clone(),
+                })
+            }
+            HashTable::KeyU8HashTable(table) => HashTable::KeyU8HashTable(FixedKeyHashTable {
+                hash_table: HashMap::new(),
+                hash_method: table.hash_method.clone(),
+            }),
+            HashTable::KeyU16HashTable(table) => HashTable::KeyU16HashTable(FixedKeyHashTable {
+                hash_table: HashMap::new(),
+                hash_method: table.hash_method.clone(),
+            }),
+            HashTable::KeyU32HashTable(table) => HashTable::KeyU32HashTable(FixedKeyHashTable {
+                hash_table: HashMap::new(),
+                hash_method: table.hash_method.clone(),
+            }),
+            HashTable::KeyU64HashTable(table) => HashTable::KeyU64HashTable(FixedKeyHashTable {
+                hash_table: HashMap::new(),
+                hash_method: table.hash_method.clone(),
+            }),
+            HashTable::KeyU128HashTable(table) => HashTable::KeyU128HashTable(FixedKeyHashTable {
+                hash_table: HashMap::new(),
+                hash_method: table.hash_method.clone(),
+            }),
+            HashTable::KeyU256HashTable(table) => HashTable::KeyU256HashTable(FixedKeyHashTable {
+                hash_table: HashMap::new(),
+                hash_method: table.hash_method.clone(),
+            }),
+            HashTable::KeyU512HashTable(table) => HashTable::KeyU512HashTable(FixedKeyHashTable {
+                hash_table: HashMap::new(),
+                hash_method: table.hash_method.clone(),
+            }),
+        };
+
+        // The schemas are already wrapped nullable, wrapping them again is a no-op.
+        let mut partition_table = JoinHashTable::try_create(
+            self.ctx.clone(),
+            hash_table,
+            self.row_space.data_schema.clone(),
+            self.probe_schema.clone(),
+            HashJoinDesc {
+                build_keys: self.hash_join_desc.build_keys.clone(),
+                probe_keys: self.hash_join_desc.probe_keys.clone(),
+                join_type: self.hash_join_desc.join_type.clone(),
+                other_predicate: self.hash_join_desc.other_predicate.clone(),
+                marker_join_desc: MarkJoinDesc {
+                    has_null: RwLock::new(false),
+                },
+                from_correlated_subquery: self.hash_join_desc.from_correlated_subquery,
+                join_state: JoinState::create()?,
+            },
+        )?;
+        partition_table.interrupt = self.interrupt.clone();
+        Ok(partition_table)
+    }
+
+    /// Joins the next spilled partition with a fresh hash table, the results are
+    /// post-processed the same way as the in-memory join does after probing.
+    pub(crate) fn join_spilled_partition(&self) -> Result<Option<Vec<DataBlock>>> {
+        let spiller = match self.spiller() {
+            Some(spiller) => spiller,
+            None => return Ok(None),
+        };
+        let partition = match spiller.take_partition() {
+            Some(partition) => partition,
+            None => return Ok(None),
+        };
+
+        let build_blocks = spiller.restore(JoinSide::Build, partition)?;
+        let probe_blocks = spiller.restore(JoinSide::Probe, partition)?;
+        Ok(Some(self.join_partition(build_blocks, probe_blocks, 0)?))
+    }
+
+    /// Joins the blocks of a partition. If the build side of the partition still exceeds
+    /// the threshold, both sides are partitioned again with a different hash seed, and
+    /// the sub-partitions are joined one by one.
+    fn join_partition(
+        &self,
+        build_blocks: Vec<DataBlock>,
+        probe_blocks: Vec<DataBlock>,
+        level: usize,
+    ) -> Result<Vec<DataBlock>> {
+        if build_blocks.is_empty() && probe_blocks.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let threshold = self
+            .ctx
+            .get_settings()
+            .get_join_spilling_bytes_threshold()? as usize;
+        let build_rows = build_blocks.iter().map(|b| b.num_rows()).sum::<usize>();
+        let build_bytes = build_blocks.iter().map(|b| b.memory_size()).sum::<usize>();
+        if level < MAX_SPILL_LEVEL && build_rows > 1 && build_bytes > threshold {
+            let spiller = JoinSpiller::create(&self.ctx.get_id())?;
+            for block in build_blocks {
+                spiller.spill(
+                    JoinSide::Build,
+                    self.partition_block(&block, JoinSide::Build, level + 1)?,
+                )?;
+            }
+            for block in probe_blocks {
+                spiller.spill(
+                    JoinSide::Probe,
+                    self.partition_block(&block, JoinSide::Probe, level + 1)?,
+                )?;
+            }
+            info!(
+                "Partition of hash join exceeds {} bytes with {} bytes, repartition it at level {}",
+                threshold,
+                build_bytes,
+                level + 1
+            );
+
+            let mut blocks = vec![];
+            while let Some(partition) = spiller.take_partition() {
+                let build_blocks = spiller.restore(JoinSide::Build, partition)?;
+                let probe_blocks = spiller.restore(JoinSide::Probe, partition)?;
+                blocks.extend(self.join_partition(build_blocks, probe_blocks, level + 1)?);
+            }
+            return Ok(blocks);
+        }
+
+        let partition_table = self.create_partition_table()?;
+        for block in build_blocks {
+            partition_table.add_build_block(block)?;
+        }
+        partition_table.finish()?;
+
+        let max_block_size = self.ctx.get_settings().get_max_block_size()? as usize;
+        let mut probe_state = ProbeState::with_capacity(max_block_size);
+        let mut blocks = vec![];
+        for block in probe_blocks {
+            probe_state.clear();
+            blocks.extend(partition_table.probe(&block, &mut probe_state)?);
+        }
+
+        let join_type = &self.hash_join_desc.join_type;
+        if matches!(
+            join_type,
+            JoinType::Left | JoinType::Full | JoinType::Single
+        ) && self.hash_join_desc.other_predicate.is_none()
+        {
+            blocks = partition_table.left_join_blocks(&blocks)?;
+        }
+        if matches!(join_type, JoinType::Right | JoinType::Full) {
+            blocks = partition_table.right_join_blocks(&blocks)?;
+        }
+        if matches!(join_type, JoinType::RightSemi | JoinType::RightAnti) {
+            blocks = partition_table.right_semi_join_blocks(&blocks)?;
+        }
+        Ok(blocks)
+    }
+}
@@ -44,6 +44,21 @@ pub trait HashJoinState: Send + Sync {
     /// Wait until the build phase is finished
     async fn wait_finish(&self) -> Result<()>;
 
+    /// Whether the build side is partitioned to disk
+    fn is_spilled(&self) -> bool;
+
+    /// Attach a probe processor to state
+    fn attach_probe(&self) -> Result<()>;
+
+    /// Detach a probe processor from state
+    fn detach_probe(&self) -> Result<()>;
+
+    /// Wait until all the probe processors are detached
+    async fn wait_probe_finish(&self) -> Result<()>;
+
+    /// Join the next spilled partition, returns `None` if all the partitions are joined
+    fn probe_spilled_partition(&self) -> Result<Option<Vec<DataBlock>>>;
+
     /// Get mark join results
     fn mark_join_blocks(&self) -> Result<Vec<DataBlock>>;
 
@@ -24,6 +24,7 @@ use common_expression::HashMethod;
 
 use super::ProbeState;
 use crate::pipelines::processors::transforms::hash_join::desc::MarkerKind;
+use crate::pipelines::processors::transforms::hash_join::grace_hash_join::JoinSide;
 use crate::pipelines::processors::transforms::hash_join::row::RowPtr;
 use crate::pipelines::processors::HashJoinState;
 use crate::pipelines::processors::HashTable;
@@ -34,6 +35,14 @@ use crate::sql::planner::plans::JoinType;
 #[async_trait::async_trait]
 impl HashJoinState for JoinHashTable {
     fn build(&self, input: DataBlock) -> Result<()> {
+        self.try_spill_build_side(input.memory_size())?;
+        // Hold the read lock, so that the block is not pushed into memory while switching to spill.
+        let spiller = self.spiller.read();
+        if let Some(spiller) = &*spiller {
+            let partitions = self.partition_block(&input, JoinSide::Build, 0)?;
+            return spiller.spill(JoinSide::Build, partitions);
+        }
+
         let mut data_block = input;
         let data_block_size_limit = self.ctx.get_settings().get_max_block_size()? * 16;
         {
@@ -51,6 +60,13 @@ impl HashJoinState for JoinHashTable {
     }
 
     fn probe(&self, input: &DataBlock, probe_state: &mut ProbeState) -> Result<Vec<DataBlock>> {
+        // The probe blocks are joined later by `probe_spilled_partition`.
+        if let Some(spiller) = self.spiller() {
+            let partitions = self.partition_block(input, JoinSide::Probe, 0)?;
+            spiller.spill(JoinSide::Probe, partitions)?;
+            return Ok(vec![]);
+        }
+
         match self.hash_join_desc.join_type {
             JoinType::Inner
             | JoinType::LeftSemi
@@ -265,6 +281,40 @@ impl HashJoinState for JoinHashTable {
         Ok(())
     }
 
+    fn is_spilled(&self) -> bool {
+        self.spiller.read().is_some()
+    }
+
+    fn attach_probe(&self) -> Result<()> {
+        let mut count = self.probe_ref_count.lock().unwrap();
+        *count += 1;
+        Ok(())
+    }
+
+    fn detach_probe(&self) -> Result<()> {
+        let mut count = self.probe_ref_count.lock().unwrap();
+        *count -= 1;
+        if *count == 0 {
+            let mut probe_finished = self.probe_finished.lock().unwrap();
+            *probe_finished = true;
+            self.probe_finished_notify.notify_waiters();
+        }
+        Ok(())
+    }
+
+    async fn wait_probe_finish(&self) -> Result<()> {
+        let notified = self.probe_finished_notify.notified();
+        if !*self.probe_finished.lock().unwrap() {
+            notified.await;
+        }
+
+        Ok(())
+    }
+
+    fn probe_spilled_partition(&self) -> Result<Option<Vec<DataBlock>>> {
+        self.join_spilled_partition()
+    }
+
     fn mark_join_blocks(&self) -> Result<Vec<DataBlock>> {
         let row_ptrs = self.row_ptrs.read();
         let has_null = self.hash_join_desc.marker_join_desc.has_null.read();
@@ -276,6 +326,11 @@ impl HashJoinState for JoinHashTable {
     }
 
     fn right_join_blocks(&self, blocks: &[DataBlock]) -> Result<Vec<DataBlock>> {
+        // The spilled partitions are already post-processed.
+        if self.is_spilled() {
+            return Ok(blocks.to_vec());
+        }
+
         let mut row_state = self.row_state_for_right_join()?;
         let unmatched_build_indexes = self.find_unmatched_build_indexes(&row_state)?;
 
@@ -346,6 +401,11 @@ impl HashJoinState for JoinHashTable {
     }
 
     fn right_semi_join_blocks(&self, blocks: &[DataBlock]) -> Result<Vec<DataBlock>> {
+        // The spilled partitions are already post-processed.
+        if self.is_spilled() {
+            return Ok(blocks.to_vec());
+        }
+
         let mut row_state = self.row_state_for_right_join()?;
         let unmatched_build_indexes = self.find_unmatched_build_indexes(&row_state)?;
         let unmatched_build_block = self.row_space.gather(&unmatched_build_indexes)?;
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
 use std::sync::Mutex;
 
@@ -33,7 +34,6 @@ use common_functions::scalars::BUILTIN_FUNCTIONS;
 use common_hashtable::HashMap;
 use common_hashtable::HashtableKeyable;
 use common_hashtable::UnsizedHashMap;
-use common_sql::executor::PhysicalScalar;
 use common_sql::plans::JoinType;
 use parking_lot::RwLock;
 use primitive_types::U256;
@@ -41,6 +41,7 @@ use primitive_types::U512;
 
 use super::ProbeState;
 use crate::pipelines::processors::transforms::hash_join::desc::HashJoinDesc;
+use crate::pipelines::processors::transforms::hash_join::grace_hash_join::JoinSpiller;
 use crate::pipelines::processors::transforms::hash_join::row::RowPtr;
 use crate::pipelines::processors::transforms::hash_join::row::RowSpace;
 use crate::pipelines::processors::transforms::hash_join::util::build_schema_wrap_nullable;
@@ -82,19 +83,29 @@ pub struct JoinHashTable {
     pub(crate) probe_schema: DataSchemaRef,
     pub(crate) interrupt: Arc<AtomicBool>,
     pub(crate) finished_notify: Arc<Notify>,
+    /// The bytes of the blocks received by the build side
+    pub(crate) build_bytes: AtomicUsize,
+    /// Set when the build side exceeds `join_spilling_bytes_threshold`, then both sides
+    /// are partitioned into local files and joined partition by partition.
+    pub(crate) spiller: RwLock<Option<Arc<JoinSpiller>>>,
+    /// Reference count of the probe processors, the spilled partitions are joined after
+    /// all of them have spilled their probe blocks.
+    pub(crate) probe_ref_count: Mutex<usize>,
+    pub(crate) probe_finished: Mutex<bool>,
+    pub(crate) probe_finished_notify: Arc<Notify>,
 }
 
 impl JoinHashTable {
     pub fn create_join_state(
         ctx: Arc<QueryContext>,
-        build_keys: &[PhysicalScalar],
         build_schema: DataSchemaRef,
         probe_schema: DataSchemaRef,
         hash_join_desc: HashJoinDesc,
     ) -> Result<Arc<JoinHashTable>> {
-        let hash_key_types = build_keys
+        let hash_key_types = hash_join_desc
+            .build_keys
             .iter()
-            .map(|expr| expr.data_type())
+            .map(|expr| expr.data_type().clone())
             .collect::<Vec<_>>();
         let method = DataBlock::choose_hash_method_with_types(&hash_key_types)?;
         Ok(match method {
@@ -211,32 +222,42 @@ impl JoinHashTable {
             probe_schema: probe_data_schema,
             finished_notify: Arc::new(Notify::new()),
             interrupt: Arc::new(AtomicBool::new(false)),
+            build_bytes: AtomicUsize::new(0),
+            spiller: RwLock::new(None),
+            probe_ref_count: Mutex::new(0),
+            probe_finished: Mutex::new(false),
+            probe_finished_notify: Arc::new(Notify::new()),
         })
     }
 
+    /// The columns of the probe side are wrapped nullable for right join and full join.
+    pub(crate) fn nullable_probe_block(&self, input: &DataBlock) -> DataBlock {
+        if !matches!(
+            self.hash_join_desc.join_type,
+            JoinType::Right | JoinType::Full
+        ) {
+            return input.clone();
+        }
+        let nullable_columns = input
+            .columns()
+            .iter()
+            .map(|c| {
+                let mut validity = MutableBitmap::new();
+                validity.extend_constant(input.num_rows(), true);
+                let validity: Bitmap = validity.into();
+                Self::set_validity(c, validity.len(), &validity)
+            })
+            .collect::<Vec<_>>();
+        DataBlock::new(nullable_columns, input.num_rows())
+    }
+
     pub(crate) fn probe_join(
         &self,
         input: &DataBlock,
         probe_state: &mut ProbeState,
     ) -> Result<Vec<DataBlock>> {
         let func_ctx = self.ctx.try_get_function_context()?;
-        let mut input = (*input).clone();
-        if matches!(
-            self.hash_join_desc.join_type,
-            JoinType::Right | JoinType::Full
-        ) {
-            let nullable_columns = input
-                .columns()
-                .iter()
-                .map(|c| {
-                    let mut validity = MutableBitmap::new();
-                    validity.extend_constant(input.num_rows(), true);
-                    let validity: Bitmap = validity.into();
-                    Self::set_validity(c, validity.len(), &validity)
-                })
-                .collect::<Vec<_>>();
-            input = DataBlock::new(nullable_columns, input.num_rows());
-        }
+        let input = self.nullable_probe_block(input);
         let evaluator = Evaluator::new(&input, func_ctx, &BUILTIN_FUNCTIONS);
 
         let probe_keys = self
@@ -14,6 +14,7 @@
 
 mod common;
 mod desc;
+mod grace_hash_join;
 mod hash_join_state;
 mod hash_join_state_impl;
 mod join_hash_table;
@@ -26,10 +26,13 @@ mod transform_mark_join;
 
 mod transform_convert_grouping;
 mod transform_merge_block;
+mod transform_recursive_cte;
 mod transform_resort_addon;
 mod transform_right_join;
 mod transform_right_semi_anti_join;
+mod transform_window;
 
+pub use aggregator::query_spill_dir;
 pub use aggregator::AggregatorParams;
 pub use aggregator::AggregatorTransformParams;
 use common_pipeline_transforms::processors::transforms::transform;
@@ -37,6 +40,7 @@ use common_pipeline_transforms::processors::transforms::transform_block_compact;
 use common_pipeline_transforms::processors::transforms::transform_compact;
 use common_pipeline_transforms::processors::transforms::transform_sort_merge;
 use common_pipeline_transforms::processors::transforms::transform_sort_partial;
+use common_pipeline_transforms::processors::transforms::transform_sort_spill;
 pub use hash_join::FixedKeyHashTable;
 pub use hash_join::HashJoinDesc;
 pub use hash_join::HashJoinState;
@@ -62,6 +66,7 @@ pub use transform_limit::TransformLimit;
 pub use transform_mark_join::MarkJoinCompactor;
 pub use transform_mark_join::TransformMarkJoin;
 pub use transform_merge_block::TransformMergeBlock;
+pub use transform_recursive_cte::RecursiveCteSource;
 pub use transform_resort_addon::TransformResortAddOn;
 pub use transform_right_join::RightJoinCompactor;
 pub use transform_right_join::TransformRightJoin;
@@ -70,3 +75,8 @@ pub use transform_right_semi_anti_join::TransformRightSemiAntiJoin;
 pub use transform_sort_merge::SortMergeCompactor;
 pub use transform_sort_merge::TransformSortMerge;
 pub use transform_sort_partial::TransformSortPartial;
+pub use transform_sort_spill::TransformSortSpill;
+pub use transform_window::LagLeadImpl;
+pub use transform_window::TransformWindow;
+pub use transform_window::WindowCompactor;
+pub use transform_window::WindowFunctionImpl;
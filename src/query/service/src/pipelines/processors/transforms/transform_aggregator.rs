@@ -263,12 +263,26 @@ impl<TAggregator: Aggregator + TwoLevelAggregatorLike + 'static> AggregatorTrans
     ) -> Result<ProcessorPtr> {
         let settings = ctx.get_settings();
         let two_level_threshold = settings.get_group_by_two_level_threshold()? as usize;
+        let spilling_bytes_threshold = settings.get_group_by_spilling_bytes_threshold()? as usize;
+
+        // The spilled files are only visible to the local node, so spilling is disabled in cluster mode.
+        let spiller = match TAggregator::SUPPORT_TWO_LEVEL
+            && spilling_bytes_threshold != 0
+            && ctx.get_cluster().is_empty()
+        {
+            true => Some(AggregateSpiller::create(
+                &ctx.get_id(),
+                spilling_bytes_threshold,
+            )),
+            false => None,
+        };
 
         let transformer = AggregatorTransform::<TAggregator>::ConsumeData(ConsumeState {
             inner,
             input_port: transform_params.transform_input_port,
             output_port: transform_params.transform_output_port,
             two_level_threshold,
+            spiller,
             input_data_block: None,
         });
 
@@ -298,7 +312,8 @@ impl<TAggregator: Aggregator + TwoLevelAggregatorLike + 'static> AggregatorTrans
                     inner: s.inner,
                     is_generated: false,
                     output_port: s.output_port,
-                    output_data_block: vec![],
+                    // The spilled blocks are sent ahead of the in-memory buckets.
+                    output_data_block: s.spilled_blocks,
                 }))
             }
             _ => Err(ErrorCode::Internal("")),
@@ -312,6 +327,8 @@ impl<TAggregator: Aggregator + TwoLevelAggregatorLike + 'static> AggregatorTrans
                     inner: s.inner.convert_two_level()?,
                     input_port: s.input_port,
                     output_port: s.output_port,
+                    spiller: s.spiller,
+                    spilled_blocks: vec![],
                     input_data_block: None,
                 },
             )),
@@ -358,8 +375,13 @@ impl<TAggregator: Aggregator + TwoLevelAggregatorLike + 'static> AggregatorTrans
         if let AggregatorTransform::ConsumeData(state) = self {
             if TAggregator::SUPPORT_TWO_LEVEL {
                 let cardinality = state.inner.get_state_cardinality();
+                let need_spill = match &state.spiller {
+                    Some(spiller) => cardinality > 0 && spiller.need_spill(),
+                    None => false,
+                };
 
-                if cardinality >= state.two_level_threshold {
+                // Only the two-level buckets can be spilled.
+                if cardinality >= state.two_level_threshold || need_spill {
                     let mut temp_state = AggregatorTransform::Finished;
                     std::mem::swap(self, &mut temp_state);
                     temp_state = temp_state.convert_to_two_level_consume()?;
@@ -491,6 +513,7 @@ impl<TAggregator: Aggregator + TwoLevelAggregatorLike + 'static> AggregatorTrans
 struct ConsumeState<TAggregator: Aggregator> {
     inner: TAggregator,
     two_level_threshold: usize,
+    spiller: Option<AggregateSpiller>,
 
     input_port: Arc<InputPort>,
     output_port: Arc<OutputPort>,
@@ -509,6 +532,8 @@ impl<TAggregator: Aggregator> ConsumeState<TAggregator> {
 
 struct TwoLevelConsumeState<TAggregator: Aggregator + TwoLevelAggregatorLike> {
     inner: TwoLevelAggregator<TAggregator>,
+    spiller: Option<AggregateSpiller>,
+    spilled_blocks: Vec<DataBlock>,
 
     input_port: Arc<InputPort>,
     output_port: Arc<OutputPort>,
@@ -521,6 +546,15 @@ impl<TAggregator: Aggregator + TwoLevelAggregatorLike> TwoLevelConsumeState<TAgg
             self.inner.consume(input_data)?;
         }
 
+        if let Some(spiller) = &self.spiller {
+            if spiller.need_spill() {
+                let blocks = self.inner.spill_two_level_block()?;
+                if let Some(spilled_block) = spiller.spill(blocks)? {
+                    self.spilled_blocks.push(spilled_block);
+                }
+            }
+        }
+
         Ok(())
     }
 }
@@ -536,7 +570,7 @@ impl<TAggregator: Aggregator> GenerateState<TAggregator> {
     pub fn generate(&mut self) -> Result<()> {
         if !self.is_generated {
             self.is_generated = true;
-            self.output_data_block = self.inner.generate()?;
+            self.output_data_block.extend(self.inner.generate()?);
         }
 
         Ok(())
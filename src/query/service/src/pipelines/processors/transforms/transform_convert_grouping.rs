@@ -35,6 +35,7 @@ use serde::Deserializer;
 use serde::Serialize;
 use serde::Serializer;
 
+use crate::pipelines::processors::transforms::aggregator::split_spilled_buckets;
 use crate::pipelines::processors::transforms::aggregator::AggregateInfo;
 use crate::pipelines::processors::transforms::aggregator::BucketAggregator;
 use crate::pipelines::processors::transforms::group_by::KeysColumnIter;
@@ -300,9 +301,11 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send + 'static> Proces
 
         if all_port_prepared_data {
             // current working bucket is process completed.
-            if self.working_bucket == 0 && self.buckets_blocks.contains_key(&-1) {
+            if self.working_bucket == 0
+                && (self.buckets_blocks.contains_key(&-1) || self.buckets_blocks.contains_key(&-2))
+            {
                 // all single level data block
-                if self.buckets_blocks.len() == 1 {
+                if self.buckets_blocks.len() == 1 && self.buckets_blocks.contains_key(&-1) {
                     self.working_bucket = 256;
 
                     if let Some(bucket_blocks) = self.buckets_blocks.remove(&-1) {
@@ -314,11 +317,16 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send + 'static> Proces
                     return Ok(Event::NeedConsume);
                 }
 
-                // need convert to two level data block
+                // need convert to two level data block or restore spilled data block
                 self.working_bucket += 1;
                 return Ok(Event::Sync);
             }
 
+            // The buckets restored from the spilled data may be missing in all the inputs.
+            if let Some(bucket) = self.buckets_blocks.keys().filter(|b| **b >= 0).min() {
+                min_bucket = std::cmp::min(*bucket, min_bucket);
+            }
+
             if min_bucket == MAX_BUCKET_NUM {
                 self.output.finish();
 
@@ -354,16 +362,14 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send + 'static> Proces
                     if let Some(meta) = data_block.get_meta() {
                         if let Some(meta) = meta.as_any().downcast_ref::<AggregateInfo>() {
                             let overflow = meta.overflow.as_ref().unwrap();
-                            for (bucket_id, (_offset, _length)) in &overflow.bucket_info {
-                                // DataBlock
-                                // DataBlock::empty_with_meta()
-
-                                match self.buckets_blocks.entry(*bucket_id as isize) {
+                            // The spilled data is restored by MergeBucketTransform bucket by bucket.
+                            for (bucket, block) in split_spilled_buckets(overflow) {
+                                match self.buckets_blocks.entry(bucket) {
                                     Entry::Vacant(v) => {
-                                        v.insert(vec![]);
+                                        v.insert(vec![block]);
                                     }
-                                    Entry::Occupied(_v) => {
-                                        // v.get_mut().push()
+                                    Entry::Occupied(mut v) => {
+                                        v.get_mut().push(block);
                                     }
                                 };
                             }
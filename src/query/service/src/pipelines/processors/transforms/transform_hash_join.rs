@@ -61,6 +61,10 @@ impl Sink for SinkBuildHashTable {
 enum HashJoinStep {
     Build,
     Probe,
+    /// Wait for all the probe processors to spill their probe blocks.
+    FinishSpill,
+    /// Join the spilled partitions one by one.
+    ProbeSpilled,
 }
 
 pub struct TransformHashJoinProbe {
@@ -72,6 +76,8 @@ pub struct TransformHashJoinProbe {
     step: HashJoinStep,
     join_state: Arc<dyn HashJoinState>,
     probe_state: ProbeState,
+    probe_attached: bool,
+    spilled_finished: bool,
 }
 
 impl TransformHashJoinProbe {
@@ -83,6 +89,7 @@ impl TransformHashJoinProbe {
         _output_schema: DataSchemaRef,
     ) -> Result<ProcessorPtr> {
         let default_block_size = ctx.get_settings().get_max_block_size()?;
+        join_state.attach_probe()?;
         Ok(ProcessorPtr::create(Box::new(TransformHashJoinProbe {
             input_data: None,
             output_data_blocks: VecDeque::new(),
@@ -91,9 +98,26 @@ impl TransformHashJoinProbe {
             step: HashJoinStep::Build,
             join_state,
             probe_state: ProbeState::with_capacity(default_block_size as usize),
+            probe_attached: true,
+            spilled_finished: false,
         })))
     }
 
+    fn detach_probe(&mut self) -> Result<()> {
+        if self.probe_attached {
+            self.probe_attached = false;
+            self.join_state.detach_probe()?;
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<Event> {
+        self.detach_probe()?;
+        self.input_port.finish();
+        self.output_port.finish();
+        Ok(Event::Finished)
+    }
+
     fn probe(&mut self, block: &DataBlock) -> Result<()> {
         self.probe_state.clear();
         self.output_data_blocks
@@ -114,11 +138,10 @@ impl Processor for TransformHashJoinProbe {
 
     fn event(&mut self) -> Result<Event> {
         match self.step {
-            HashJoinStep::Build => Ok(Event::Async),
+            HashJoinStep::Build | HashJoinStep::FinishSpill => Ok(Event::Async),
             HashJoinStep::Probe => {
                 if self.output_port.is_finished() {
-                    self.input_port.finish();
-                    return Ok(Event::Finished);
+                    return self.finish();
                 }
 
                 if !self.output_port.can_push() {
@@ -143,13 +166,37 @@ impl Processor for TransformHashJoinProbe {
                 }
 
                 if self.input_port.is_finished() {
-                    self.output_port.finish();
-                    return Ok(Event::Finished);
+                    if self.join_state.is_spilled() {
+                        self.step = HashJoinStep::FinishSpill;
+                        return Ok(Event::Async);
+                    }
+                    return self.finish();
                 }
 
                 self.input_port.set_need_data();
                 Ok(Event::NeedData)
             }
+            HashJoinStep::ProbeSpilled => {
+                if self.output_port.is_finished() {
+                    return self.finish();
+                }
+
+                if !self.output_port.can_push() {
+                    return Ok(Event::NeedConsume);
+                }
+
+                if !self.output_data_blocks.is_empty() {
+                    let data = self.output_data_blocks.pop_front().unwrap();
+                    self.output_port.push_data(Ok(data));
+                    return Ok(Event::NeedConsume);
+                }
+
+                if self.spilled_finished {
+                    return self.finish();
+                }
+
+                Ok(Event::Sync)
+            }
         }
     }
 
@@ -159,7 +206,7 @@ impl Processor for TransformHashJoinProbe {
 
     fn process(&mut self) -> Result<()> {
         match self.step {
-            HashJoinStep::Build => Ok(()),
+            HashJoinStep::Build | HashJoinStep::FinishSpill => Ok(()),
             HashJoinStep::Probe => {
                 if let Some(data) = self.input_data.take() {
                     let data = data.convert_to_full();
@@ -167,13 +214,30 @@ impl Processor for TransformHashJoinProbe {
                 }
                 Ok(())
             }
+            HashJoinStep::ProbeSpilled => {
+                match self.join_state.probe_spilled_partition()? {
+                    Some(blocks) => self
+                        .output_data_blocks
+                        .extend(blocks.into_iter().filter(|b| !b.is_empty())),
+                    None => self.spilled_finished = true,
+                }
+                Ok(())
+            }
         }
     }
 
     async fn async_process(&mut self) -> Result<()> {
-        if let HashJoinStep::Build = &self.step {
-            self.join_state.wait_finish().await?;
-            self.step = HashJoinStep::Probe;
+        match &self.step {
+            HashJoinStep::Build => {
+                self.join_state.wait_finish().await?;
+                self.step = HashJoinStep::Probe;
+            }
+            HashJoinStep::FinishSpill => {
+                self.detach_probe()?;
+                self.join_state.wait_probe_finish().await?;
+                self.step = HashJoinStep::ProbeSpilled;
+            }
+            _ => {}
         }
 
         Ok(())
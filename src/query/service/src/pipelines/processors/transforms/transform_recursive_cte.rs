@@ -0,0 +1,137 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::DataBlock;
+use common_sql::executor::PhysicalPlan;
+use common_sql::executor::RecursiveCte;
+
+use crate::pipelines::executor::ExecutorSettings;
+use crate::pipelines::executor::PipelinePullingExecutor;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::ProcessorPtr;
+use crate::pipelines::processors::SyncSource;
+use crate::pipelines::processors::SyncSourcer;
+use crate::pipelines::PipelineBuilder;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+/// Evaluates a recursive CTE.
+///
+/// The anchor member is executed first. Then the recursive member is executed
+/// repeatedly, with `RecursiveCteScan` reading the rows produced by the previous
+/// iteration, until an iteration produces no rows.
+///
+/// The rows of the previous iteration are kept by the source itself, and are only set into
+/// the context created to run the recursive member, so the sources of a recursive CTE
+/// referenced more than once by a query don't share their working tables.
+pub struct RecursiveCteSource {
+    ctx: Arc<QueryContext>,
+    plan: RecursiveCte,
+    max_iterations: u64,
+
+    iterations: u64,
+    anchor_executed: bool,
+    finished: bool,
+    working_table: Vec<DataBlock>,
+    output_blocks: VecDeque<DataBlock>,
+}
+
+impl RecursiveCteSource {
+    pub fn create(
+        ctx: Arc<QueryContext>,
+        output: Arc<OutputPort>,
+        plan: RecursiveCte,
+        max_iterations: u64,
+    ) -> Result<ProcessorPtr> {
+        SyncSourcer::create(ctx.clone(), output, RecursiveCteSource {
+            ctx,
+            plan,
+            max_iterations,
+            iterations: 0,
+            anchor_executed: false,
+            finished: false,
+            working_table: vec![],
+            output_blocks: VecDeque::new(),
+        })
+    }
+
+    fn execute(&self, plan: &PhysicalPlan, projections: &[usize]) -> Result<Vec<DataBlock>> {
+        let ctx = QueryContext::create_from(self.ctx.clone());
+        ctx.set_recursive_cte_table(self.plan.cte_index, self.working_table.clone());
+        let mut build_res = PipelineBuilder::create(ctx.clone()).finalize(plan)?;
+
+        let settings = ctx.get_settings();
+        build_res.set_max_threads(settings.get_max_threads()? as usize);
+        let settings = ExecutorSettings::try_create(&settings, ctx.get_id())?;
+
+        let mut executor = PipelinePullingExecutor::from_pipelines(build_res, settings)?;
+        executor.start();
+
+        let mut blocks = vec![];
+        while let Some(block) = executor.pull_data()? {
+            if block.num_rows() == 0 {
+                continue;
+            }
+            let columns = projections
+                .iter()
+                .map(|offset| block.get_by_offset(*offset).clone())
+                .collect();
+            blocks.push(DataBlock::new(columns, block.num_rows()));
+        }
+        Ok(blocks)
+    }
+
+    fn next_iteration(&mut self) -> Result<Vec<DataBlock>> {
+        if !self.anchor_executed {
+            self.anchor_executed = true;
+            return self.execute(&self.plan.anchor, &self.plan.anchor_projections);
+        }
+
+        if self.iterations >= self.max_iterations {
+            return Err(ErrorCode::Overflow(format!(
+                "Recursive CTE exceeds the maximum number of iterations {}, please check the terminating condition or increase max_recursive_cte_iterations",
+                self.max_iterations
+            )));
+        }
+        self.iterations += 1;
+        self.execute(&self.plan.recursive, &self.plan.recursive_projections)
+    }
+}
+
+impl SyncSource for RecursiveCteSource {
+    const NAME: &'static str = "RecursiveCteSource";
+
+    fn generate(&mut self) -> Result<Option<DataBlock>> {
+        loop {
+            if let Some(block) = self.output_blocks.pop_front() {
+                return Ok(Some(block));
+            }
+            if self.finished {
+                return Ok(None);
+            }
+
+            let blocks = self.next_iteration()?;
+            if blocks.is_empty() {
+                self.finished = true;
+            }
+            self.working_table = blocks.clone();
+            self.output_blocks.extend(blocks);
+        }
+    }
+}
@@ -0,0 +1,500 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ops::Range;
+
+use bumpalo::Bump;
+use common_exception::Result;
+use common_expression::types::number::NumberScalar;
+use common_expression::types::number::UInt64Type;
+use common_expression::types::DataType;
+use common_expression::types::NumberDataType;
+use common_expression::utils::FromData;
+use common_expression::BlockEntry;
+use common_expression::Column;
+use common_expression::ColumnBuilder;
+use common_expression::DataBlock;
+use common_expression::ScalarRef;
+use common_expression::SortColumnDescription;
+use common_expression::Value;
+use common_functions::aggregates::AggregateFunctionRef;
+use common_functions::aggregates::StateAddr;
+use common_sql::plans::WindowFuncFrame;
+use common_sql::plans::WindowFuncFrameBound;
+use common_sql::plans::WindowFuncFrameUnits;
+
+use crate::pipelines::processors::transforms::Compactor;
+use crate::pipelines::processors::TransformCompact;
+
+#[derive(Clone)]
+pub enum WindowFunctionImpl {
+    Aggregate {
+        func: AggregateFunctionRef,
+        arg_offsets: Vec<usize>,
+    },
+    LagLead(LagLeadImpl),
+    RowNumber,
+    Rank,
+    DenseRank,
+}
+
+#[derive(Clone)]
+pub struct LagLeadImpl {
+    pub is_lag: bool,
+    pub arg_offset: usize,
+    pub offset: u64,
+    pub default_offset: Option<usize>,
+    pub return_type: DataType,
+}
+
+/// Compute a window function over the input, which must be sorted by
+/// `partition_by` and then by `order_by`, and append the result as the last column.
+pub struct WindowCompactor {
+    func: WindowFunctionImpl,
+    partition_by: Vec<usize>,
+    order_by: Vec<SortColumnDescription>,
+    frame: WindowFuncFrame,
+}
+
+impl WindowCompactor {
+    pub fn create(
+        func: WindowFunctionImpl,
+        partition_by: Vec<usize>,
+        order_by: Vec<SortColumnDescription>,
+        frame: WindowFuncFrame,
+    ) -> Self {
+        WindowCompactor {
+            func,
+            partition_by,
+            order_by,
+            frame,
+        }
+    }
+
+    fn row_equals(columns: &[Column], lhs: usize, rhs: usize) -> bool {
+        columns
+            .iter()
+            .all(|column| column.index(lhs) == column.index(rhs))
+    }
+
+    /// Split `range` into consecutive ranges of rows having equal values in `columns`.
+    fn split_ranges(columns: &[Column], range: Range<usize>) -> Vec<Range<usize>> {
+        let mut ranges = vec![];
+        let mut start = range.start;
+        for row in range.start + 1..range.end {
+            if !Self::row_equals(columns, start, row) {
+                ranges.push(start..row);
+                start = row;
+            }
+        }
+        if start < range.end {
+            ranges.push(start..range.end);
+        }
+        ranges
+    }
+
+    fn has_range_offset(&self) -> bool {
+        let is_offset = |bound: &WindowFuncFrameBound| {
+            matches!(
+                bound,
+                WindowFuncFrameBound::Preceding(Some(_)) | WindowFuncFrameBound::Following(Some(_))
+            )
+        };
+        self.frame.units == WindowFuncFrameUnits::Range
+            && (is_offset(&self.frame.start_bound) || is_offset(&self.frame.end_bound))
+    }
+
+    /// Returns the position of the first row (if `inclusive` is false) or the row after the
+    /// last row (if `inclusive` is true) whose distance from the current row is `offset`,
+    /// the distance is measured by the value of the single `ORDER BY` column.
+    fn range_position(
+        &self,
+        keys: &[Option<f64>],
+        current: f64,
+        offset: f64,
+        inclusive: bool,
+    ) -> usize {
+        let desc = &self.order_by[0];
+        let direction = if desc.asc { 1.0 } else { -1.0 };
+        keys.partition_point(|key| match key {
+            None => desc.nulls_first,
+            Some(key) if inclusive => (key - current) * direction <= offset,
+            Some(key) => (key - current) * direction < offset,
+        })
+    }
+
+    /// Returns the inclusive frame `[start, end]` of `row`, frame is empty if `start > end`.
+    /// `keys` holds the values of the `ORDER BY` column of the partition for the RANGE
+    /// frame with offsets, and is empty otherwise.
+    fn frame_of_row(
+        &self,
+        row: usize,
+        partition: &Range<usize>,
+        peers: &Range<usize>,
+        keys: &[Option<f64>],
+    ) -> (isize, isize) {
+        let (first, last) = (partition.start as isize, partition.end as isize - 1);
+        let current = keys.get(row - partition.start).copied().flatten();
+        let is_range = self.frame.units == WindowFuncFrameUnits::Range;
+        let row = row as isize;
+
+        let start = match self.frame.start_bound {
+            WindowFuncFrameBound::Preceding(None) => first,
+            WindowFuncFrameBound::Following(None) => last + 1,
+            WindowFuncFrameBound::CurrentRow if is_range => peers.start as isize,
+            WindowFuncFrameBound::CurrentRow => row,
+            WindowFuncFrameBound::Preceding(Some(n)) if is_range => match current {
+                Some(v) => first + self.range_position(keys, v, -(n as f64), false) as isize,
+                None => peers.start as isize,
+            },
+            WindowFuncFrameBound::Following(Some(n)) if is_range => match current {
+                Some(v) => first + self.range_position(keys, v, n as f64, false) as isize,
+                None => peers.start as isize,
+            },
+            WindowFuncFrameBound::Preceding(Some(n)) => (row - n as isize).max(first),
+            WindowFuncFrameBound::Following(Some(n)) => row + n as isize,
+        };
+
+        let end = match self.frame.end_bound {
+            WindowFuncFrameBound::Following(None) => last,
+            WindowFuncFrameBound::Preceding(None) => first - 1,
+            WindowFuncFrameBound::CurrentRow if is_range => peers.end as isize - 1,
+            WindowFuncFrameBound::CurrentRow => row,
+            WindowFuncFrameBound::Preceding(Some(n)) if is_range => match current {
+                Some(v) => first + self.range_position(keys, v, -(n as f64), true) as isize - 1,
+                None => peers.end as isize - 1,
+            },
+            WindowFuncFrameBound::Following(Some(n)) if is_range => match current {
+                Some(v) => first + self.range_position(keys, v, n as f64, true) as isize - 1,
+                None => peers.end as isize - 1,
+            },
+            WindowFuncFrameBound::Preceding(Some(n)) => row - n as isize,
+            WindowFuncFrameBound::Following(Some(n)) => (row + n as isize).min(last),
+        };
+
+        (start, end)
+    }
+
+    fn compute_ranking(
+        &self,
+        order_by: &[Column],
+        partitions: &[Range<usize>],
+        num_rows: usize,
+    ) -> Column {
+        let mut values = Vec::with_capacity(num_rows);
+        for partition in partitions {
+            let peers = Self::split_ranges(order_by, partition.clone());
+            let mut row_number = 1;
+            for (group, peer) in peers.iter().enumerate() {
+                let rank = row_number;
+                for _ in peer.clone() {
+                    let value = match &self.func {
+                        WindowFunctionImpl::RowNumber => row_number,
+                        WindowFunctionImpl::Rank => rank,
+                        _ => group as u64 + 1,
+                    };
+                    values.push(value);
+                    row_number += 1;
+                }
+            }
+        }
+        UInt64Type::from_data(values)
+    }
+
+    fn compute_aggregate(
+        &self,
+        func: &AggregateFunctionRef,
+        args: &[Column],
+        order_by: &[Column],
+        partitions: &[Range<usize>],
+        num_rows: usize,
+    ) -> Result<Column> {
+        let arena = Bump::new();
+        let layout = func.state_layout();
+        // `acc_place` keeps the state of the growing frame, `place` is reset for every row.
+        let acc_place: StateAddr = arena.alloc_layout(layout).into();
+        let place: StateAddr = arena.alloc_layout(layout).into();
+        func.init_state(acc_place);
+        func.init_state(place);
+        let reset = |place: StateAddr| {
+            if func.need_manual_drop_state() {
+                unsafe { func.drop_state(place) }
+            }
+            func.init_state(place);
+        };
+
+        let mut builder = ColumnBuilder::with_capacity(&func.return_type()?, num_rows);
+        // Frames starting at UNBOUNDED PRECEDING only grow, so the state is accumulated
+        // incrementally within a partition instead of re-accumulating every frame from scratch.
+        // Other frames are answered from a segment tree built over the partition.
+        // `merge_result` may consume the state (e.g. the distinct combinator), so the result
+        // is computed from a copy merged into `place`.
+        let incremental = self.frame.start_bound == WindowFuncFrameBound::Preceding(None);
+
+        let has_range_offset = self.has_range_offset();
+
+        for partition in partitions {
+            let peers = Self::split_ranges(order_by, partition.clone());
+            let keys = if has_range_offset {
+                partition
+                    .clone()
+                    .map(|row| number_to_f64(order_by[0].index(row).unwrap()))
+                    .collect()
+            } else {
+                vec![]
+            };
+            reset(acc_place);
+            let mut accumulated = partition.start as isize - 1;
+            let tree = if incremental {
+                None
+            } else {
+                Some(SegmentTree::try_create(func, args, partition.clone())?)
+            };
+
+            for peer in peers.iter() {
+                for row in peer.clone() {
+                    let (start, end) = self.frame_of_row(row, partition, peer, &keys);
+                    reset(place);
+                    match &tree {
+                        None => {
+                            while accumulated < end {
+                                accumulated += 1;
+                                func.accumulate_row(acc_place, args, accumulated as usize)?;
+                            }
+                            func.merge(place, acc_place)?;
+                        }
+                        Some(tree) => {
+                            let start = start.max(partition.start as isize);
+                            let end = end.min(partition.end as isize - 1);
+                            if start <= end {
+                                tree.merge_range(place, start as usize..end as usize + 1)?;
+                            }
+                        }
+                    }
+                    func.merge_result(place, &mut builder)?;
+                }
+            }
+        }
+
+        if func.need_manual_drop_state() {
+            unsafe {
+                func.drop_state(acc_place);
+                func.drop_state(place);
+            }
+        }
+
+        Ok(builder.build())
+    }
+
+    /// `LAG` and `LEAD` return the argument of the row at `offset` before or after the
+    /// current row in the partition, or the default value if there is no such row.
+    fn compute_lag_lead(
+        &self,
+        lag_lead: &LagLeadImpl,
+        arg: &Column,
+        default: Option<&Column>,
+        partitions: &[Range<usize>],
+        num_rows: usize,
+    ) -> Column {
+        let offset = lag_lead.offset as usize;
+        let mut builder = ColumnBuilder::with_capacity(&lag_lead.return_type, num_rows);
+        for partition in partitions {
+            for row in partition.clone() {
+                let target = if lag_lead.is_lag {
+                    row.checked_sub(offset)
+                        .filter(|target| *target >= partition.start)
+                } else {
+                    row.checked_add(offset)
+                        .filter(|target| *target < partition.end)
+                };
+                match (target, default) {
+                    (Some(target), _) => builder.push(arg.index(target).unwrap()),
+                    (None, Some(default)) => builder.push(default.index(row).unwrap()),
+                    (None, None) => builder.push_default(),
+                }
+            }
+        }
+        builder.build()
+    }
+}
+
+impl Compactor for WindowCompactor {
+    fn name() -> &'static str {
+        "WindowTransform"
+    }
+
+    fn compact_final(&self, blocks: &[DataBlock]) -> Result<Vec<DataBlock>> {
+        if blocks.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut block = DataBlock::concat(blocks)?.convert_to_full();
+        let num_rows = block.num_rows();
+        if num_rows == 0 {
+            return Ok(vec![]);
+        }
+
+        let get_columns = |offsets: &[usize]| -> Vec<Column> {
+            offsets
+                .iter()
+                .map(|offset| {
+                    block
+                        .get_by_offset(*offset)
+                        .value
+                        .as_column()
+                        .unwrap()
+                        .clone()
+                })
+                .collect()
+        };
+
+        let partition_by = get_columns(&self.partition_by);
+        let order_by_offsets = self
+            .order_by
+            .iter()
+            .map(|desc| desc.offset)
+            .collect::<Vec<_>>();
+        let order_by = get_columns(&order_by_offsets);
+        let partitions = Self::split_ranges(&partition_by, 0..num_rows);
+
+        let (data_type, column) = match &self.func {
+            WindowFunctionImpl::Aggregate { func, arg_offsets } => {
+                let args = get_columns(arg_offsets);
+                let column =
+                    self.compute_aggregate(func, &args, &order_by, &partitions, num_rows)?;
+                (func.return_type()?, column)
+            }
+            WindowFunctionImpl::LagLead(lag_lead) => {
+                let arg = get_columns(&[lag_lead.arg_offset]).remove(0);
+                let default = lag_lead
+                    .default_offset
+                    .map(|offset| get_columns(&[offset]).remove(0));
+                let column =
+                    self.compute_lag_lead(lag_lead, &arg, default.as_ref(), &partitions, num_rows);
+                (lag_lead.return_type.clone(), column)
+            }
+            _ => (
+                DataType::Number(NumberDataType::UInt64),
+                self.compute_ranking(&order_by, &partitions, num_rows),
+            ),
+        };
+
+        block.add_column(BlockEntry {
+            data_type,
+            value: Value::Column(column),
+        });
+        Ok(vec![block])
+    }
+}
+
+/// A segment tree of aggregate states over the rows of one partition, so that the state of
+/// any frame is merged from `O(log n)` nodes instead of accumulating every row of the frame.
+/// Nodes are merged from left to right, which keeps the row order for order-sensitive
+/// aggregates.
+struct SegmentTree<'a> {
+    func: &'a AggregateFunctionRef,
+    _arena: Bump,
+    // `states[1]` is the root, the children of node `i` are `2 * i` and `2 * i + 1`,
+    // the leaves start at `size`.
+    states: Vec<StateAddr>,
+    size: usize,
+    offset: usize,
+}
+
+impl<'a> SegmentTree<'a> {
+    fn try_create(
+        func: &'a AggregateFunctionRef,
+        args: &[Column],
+        partition: Range<usize>,
+    ) -> Result<Self> {
+        let arena = Bump::new();
+        let layout = func.state_layout();
+        let size = partition.len().next_power_of_two();
+        let states: Vec<StateAddr> = (0..2 * size)
+            .map(|_| {
+                let place: StateAddr = arena.alloc_layout(layout).into();
+                func.init_state(place);
+                place
+            })
+            .collect();
+
+        for (leaf, row) in partition.clone().enumerate() {
+            func.accumulate_row(states[size + leaf], args, row)?;
+        }
+        for node in (1..size).rev() {
+            func.merge(states[node], states[2 * node])?;
+            func.merge(states[node], states[2 * node + 1])?;
+        }
+
+        Ok(SegmentTree {
+            func,
+            _arena: arena,
+            states,
+            size,
+            offset: partition.start,
+        })
+    }
+
+    /// Merge the states of the rows in `range` into `place`.
+    fn merge_range(&self, place: StateAddr, range: Range<usize>) -> Result<()> {
+        let range = range.start - self.offset..range.end - self.offset;
+        self.merge_node(place, 1, 0..self.size, &range)
+    }
+
+    fn merge_node(
+        &self,
+        place: StateAddr,
+        node: usize,
+        covered: Range<usize>,
+        range: &Range<usize>,
+    ) -> Result<()> {
+        if covered.end <= range.start || range.end <= covered.start {
+            return Ok(());
+        }
+        if range.start <= covered.start && covered.end <= range.end {
+            return self.func.merge(place, self.states[node]);
+        }
+        let mid = covered.start + covered.len() / 2;
+        self.merge_node(place, 2 * node, covered.start..mid, range)?;
+        self.merge_node(place, 2 * node + 1, mid..covered.end, range)
+    }
+}
+
+impl Drop for SegmentTree<'_> {
+    fn drop(&mut self) {
+        if self.func.need_manual_drop_state() {
+            for place in &self.states {
+                unsafe { self.func.drop_state(*place) }
+            }
+        }
+    }
+}
+
+fn number_to_f64(scalar: ScalarRef) -> Option<f64> {
+    match scalar {
+        ScalarRef::Number(NumberScalar::UInt8(v)) => Some(v as f64),
+        ScalarRef::Number(NumberScalar::UInt16(v)) => Some(v as f64),
+        ScalarRef::Number(NumberScalar::UInt32(v)) => Some(v as f64),
+        ScalarRef::Number(NumberScalar::UInt64(v)) => Some(v as f64),
+        ScalarRef::Number(NumberScalar::Int8(v)) => Some(v as f64),
+        ScalarRef::Number(NumberScalar::Int16(v)) => Some(v as f64),
+        ScalarRef::Number(NumberScalar::Int32(v)) => Some(v as f64),
+        ScalarRef::Number(NumberScalar::Int64(v)) => Some(v as f64),
+        ScalarRef::Number(NumberScalar::Float32(v)) => Some(v.into_inner() as f64),
+        ScalarRef::Number(NumberScalar::Float64(v)) => Some(v.into_inner()),
+        _ => None,
+    }
+}
+
+pub type TransformWindow = TransformCompact<WindowCompactor>;
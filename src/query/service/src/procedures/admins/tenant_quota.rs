@@ -17,6 +17,7 @@ use std::sync::Arc;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_expression::types::number::UInt32Type;
+use common_expression::types::number::UInt64Type;
 use common_expression::types::DataType;
 use common_expression::types::NumberDataType;
 use common_expression::types::ValueType;
@@ -52,7 +53,7 @@ impl OneBlockProcedure for TenantQuotaProcedure {
 
     fn features(&self) -> ProcedureFeatures {
         ProcedureFeatures::default()
-            .variadic_arguments(0, 5)
+            .variadic_arguments(0, 8)
             .management_mode_required(true)
     }
 
@@ -62,6 +63,9 @@ impl OneBlockProcedure for TenantQuotaProcedure {
     /// max_tables_per_database: u32
     /// max_stages: u32
     /// max_files_per_stage: u32
+    /// max_storage_in_bytes: u64
+    /// max_storage_per_database_in_bytes: u64
+    /// max_rows_per_table: u64
     async fn all_data(&self, ctx: Arc<QueryContext>, args: Vec<String>) -> Result<DataBlock> {
         let mut tenant = ctx.get_tenant();
         if !args.is_empty() {
@@ -93,6 +97,15 @@ impl OneBlockProcedure for TenantQuotaProcedure {
         if let Some(max_files_per_stage) = args.get(4) {
             quota.max_files_per_stage = max_files_per_stage.parse::<u32>()?
         };
+        if let Some(max_storage) = args.get(5) {
+            quota.max_storage_in_bytes = max_storage.parse::<u64>()?;
+        };
+        if let Some(max_storage_per_database) = args.get(6) {
+            quota.max_storage_per_database_in_bytes = max_storage_per_database.parse::<u64>()?;
+        };
+        if let Some(max_rows_per_table) = args.get(7) {
+            quota.max_rows_per_table = max_rows_per_table.parse::<u64>()?;
+        };
 
         quota_api.set_quota(&quota, Some(res.seq)).await?;
 
@@ -111,6 +124,18 @@ impl OneBlockProcedure for TenantQuotaProcedure {
                 "max_files_per_stage",
                 DataType::Number(NumberDataType::UInt32),
             ),
+            DataField::new(
+                "max_storage_in_bytes",
+                DataType::Number(NumberDataType::UInt64),
+            ),
+            DataField::new(
+                "max_storage_per_database_in_bytes",
+                DataType::Number(NumberDataType::UInt64),
+            ),
+            DataField::new(
+                "max_rows_per_table",
+                DataType::Number(NumberDataType::UInt64),
+            ),
         ])
     }
 }
@@ -135,6 +160,20 @@ impl TenantQuotaProcedure {
                     data_type: DataType::Number(NumberDataType::UInt32),
                     value: Value::Scalar(UInt32Type::upcast_scalar(quota.max_files_per_stage)),
                 },
+                BlockEntry {
+                    data_type: DataType::Number(NumberDataType::UInt64),
+                    value: Value::Scalar(UInt64Type::upcast_scalar(quota.max_storage_in_bytes)),
+                },
+                BlockEntry {
+                    data_type: DataType::Number(NumberDataType::UInt64),
+                    value: Value::Scalar(UInt64Type::upcast_scalar(
+                        quota.max_storage_per_database_in_bytes,
+                    )),
+                },
+                BlockEntry {
+                    data_type: DataType::Number(NumberDataType::UInt64),
+                    value: Value::Scalar(UInt64Type::upcast_scalar(quota.max_rows_per_table)),
+                },
             ],
             1,
         ))
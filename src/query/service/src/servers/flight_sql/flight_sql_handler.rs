@@ -0,0 +1,82 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use common_arrow::arrow_format::flight::service::flight_service_server::FlightServiceServer;
+use common_base::base::tokio;
+use common_base::base::tokio::net::TcpListener;
+use common_base::base::tokio::sync::Notify;
+use common_config::Config;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::Server;
+
+use crate::servers::flight_sql::flight_sql_service::FlightSqlServiceImpl;
+use crate::servers::Server as DatabendQueryServer;
+
+pub struct FlightSQLHandler {
+    config: Config,
+    abort_notify: Arc<Notify>,
+}
+
+impl FlightSQLHandler {
+    pub fn create(config: Config) -> Result<Box<dyn DatabendQueryServer>> {
+        Ok(Box::new(Self {
+            config,
+            abort_notify: Arc::new(Notify::new()),
+        }))
+    }
+
+    async fn listener_tcp(listening: SocketAddr) -> Result<(TcpListenerStream, SocketAddr)> {
+        let listener = TcpListener::bind(listening).await.map_err(|e| {
+            ErrorCode::TokioError(format!("{{{}:{}}} {}", listening.ip(), listening.port(), e))
+        })?;
+        let listener_addr = listener.local_addr()?;
+        Ok((TcpListenerStream::new(listener), listener_addr))
+    }
+
+    fn shutdown_notify(&self) -> impl Future<Output = ()> + 'static {
+        let notified = self.abort_notify.clone();
+        async move {
+            notified.notified().await;
+        }
+    }
+
+    pub async fn start_with_incoming(&mut self, listener_stream: TcpListenerStream) -> Result<()> {
+        let flight_sql_service = FlightSqlServiceImpl::create(&self.config)?;
+        let server = Server::builder()
+            .add_service(FlightServiceServer::new(flight_sql_service))
+            .serve_with_incoming_shutdown(listener_stream, self.shutdown_notify());
+
+        tokio::spawn(server);
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl DatabendQueryServer for FlightSQLHandler {
+    async fn shutdown(&mut self, _graceful: bool) {
+        self.abort_notify.notify_waiters();
+    }
+
+    async fn start(&mut self, listening: SocketAddr) -> Result<SocketAddr> {
+        let (listener_stream, listener_addr) = Self::listener_tcp(listening).await?;
+        self.start_with_incoming(listener_stream).await?;
+        Ok(listener_addr)
+    }
+}
@@ -0,0 +1,911 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use chrono_tz::Tz;
+use common_arrow::arrow::io::flight::default_ipc_fields;
+use common_arrow::arrow::io::flight::deserialize_batch;
+use common_arrow::arrow::io::flight::deserialize_schemas;
+use common_arrow::arrow::io::flight::serialize_batch;
+use common_arrow::arrow::io::flight::serialize_schema;
+use common_arrow::arrow::io::flight::serialize_schema_to_info;
+use common_arrow::arrow::io::ipc::write::WriteOptions;
+use common_arrow::arrow::io::ipc::IpcField;
+use common_arrow::arrow_format::flight::data::Action;
+use common_arrow::arrow_format::flight::data::ActionType;
+use common_arrow::arrow_format::flight::data::Criteria;
+use common_arrow::arrow_format::flight::data::Empty;
+use common_arrow::arrow_format::flight::data::FlightData;
+use common_arrow::arrow_format::flight::data::FlightDescriptor;
+use common_arrow::arrow_format::flight::data::FlightEndpoint;
+use common_arrow::arrow_format::flight::data::FlightInfo;
+use common_arrow::arrow_format::flight::data::HandshakeRequest;
+use common_arrow::arrow_format::flight::data::HandshakeResponse;
+use common_arrow::arrow_format::flight::data::PutResult;
+use common_arrow::arrow_format::flight::data::Result as FlightResult;
+use common_arrow::arrow_format::flight::data::SchemaResult;
+use common_arrow::arrow_format::flight::data::Ticket;
+use common_arrow::arrow_format::flight::service::flight_service_server::FlightService;
+use common_base::base::tokio;
+use common_catalog::catalog_kind::CATALOG_DEFAULT;
+use common_catalog::table::AppendMode;
+use common_catalog::table_context::PlaceholderValue;
+use common_config::Config;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::types::date::date_to_string;
+use common_expression::types::timestamp::timestamp_to_string;
+use common_expression::types::NumberScalar;
+use common_expression::DataBlock;
+use common_expression::DataField;
+use common_expression::DataSchema;
+use common_expression::DataSchemaRefExt;
+use common_expression::ScalarRef;
+use common_expression::SendableDataBlockStream;
+use common_meta_types::GrantObject;
+use common_meta_types::UserPrivilegeType;
+use common_pipeline_core::processors::port::OutputPort;
+use common_pipeline_core::Pipe;
+use common_pipeline_sources::processors::sources::StreamSource;
+use common_sql::count_placeholders;
+use common_sql::plans::Plan;
+use common_sql::Planner;
+use futures::StreamExt;
+use parking_lot::Mutex;
+use prost::Message;
+use tokio_stream::Stream;
+use tonic::Request;
+use tonic::Response as RawResponse;
+use tonic::Status;
+use tonic::Streaming;
+use tracing::info;
+
+use crate::auth::AuthMgr;
+use crate::auth::Credential;
+use crate::interpreters::append2table;
+use crate::interpreters::InterpreterFactory;
+use crate::pipelines::executor::ExecutorSettings;
+use crate::pipelines::executor::PipelineCompleteExecutor;
+use crate::pipelines::processors::TransformCastSchema;
+use crate::pipelines::PipelineBuildResult;
+use crate::servers::flight_sql::protocol::decode_message;
+use crate::servers::flight_sql::protocol::ActionClosePreparedStatementRequest;
+use crate::servers::flight_sql::protocol::ActionCreatePreparedStatementRequest;
+use crate::servers::flight_sql::protocol::ActionCreatePreparedStatementResult;
+use crate::servers::flight_sql::protocol::CommandGetDbSchemas;
+use crate::servers::flight_sql::protocol::CommandGetTables;
+use crate::servers::flight_sql::protocol::CommandStatementIngest;
+use crate::servers::flight_sql::protocol::DoPutUpdateResult;
+use crate::servers::flight_sql::protocol::FlightSqlCommand;
+use crate::servers::flight_sql::protocol::FlightSqlMessage;
+use crate::servers::flight_sql::protocol::FlightSqlTicket;
+use crate::servers::flight_sql::protocol::TicketStatementQuery;
+use crate::sessions::PreparedStatement;
+use crate::sessions::QueryContext;
+use crate::sessions::Session;
+use crate::sessions::SessionManager;
+use crate::sessions::SessionType;
+use crate::sessions::TableContext;
+
+pub type FlightSqlStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send + 'static>>;
+
+type Response<T> = Result<RawResponse<T>, Status>;
+type StreamReq<T> = Request<Streaming<T>>;
+
+/// Sessions without any request for this long are released.
+const SESSION_IDLE_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+const ACTION_CREATE_PREPARED_STATEMENT: &str = "CreatePreparedStatement";
+const ACTION_CLOSE_PREPARED_STATEMENT: &str = "ClosePreparedStatement";
+
+const TABLE_TYPE: &str = "if(engine = 'VIEW', 'VIEW', 'TABLE')";
+
+struct FlightSqlSession {
+    session: Arc<Session>,
+    last_access: Instant,
+    // The parameters bound by `do_put` to the prepared statements, keyed by the statement id.
+    parameters: HashMap<u32, Vec<PlaceholderValue>>,
+}
+
+/// Arrow Flight SQL front end for the clients.
+///
+/// A client authenticates in `handshake` with basic auth or a JWT and gets a bearer token
+/// identifying its session for the following requests. Queries and metadata commands are
+/// planned in `get_flight_info`, the SQL is carried in the ticket and executed in `do_get`,
+/// the result blocks are streamed as arrow record batches. `do_put` executes updates and
+/// ingests the record batches into a table.
+///
+/// Prepared statements are created and closed by the actions and kept in the session, they
+/// are planned with their `?` placeholders like the ones of MySQL. The parameters of a query
+/// are bound by a `do_put` of one row before `get_flight_info`, the parameters of an update
+/// are sent in its `do_put` and the update is executed once per row.
+pub struct FlightSqlServiceImpl {
+    auth_manager: Arc<AuthMgr>,
+    sessions: Mutex<HashMap<String, FlightSqlSession>>,
+}
+
+impl FlightSqlServiceImpl {
+    pub fn create(config: &Config) -> Result<Self> {
+        Ok(FlightSqlServiceImpl {
+            auth_manager: AuthMgr::create(config)?,
+            sessions: Mutex::new(HashMap::new()),
+        })
+    }
+
+    async fn authenticate(
+        &self,
+        authorization: Option<String>,
+        remote_addr: Option<SocketAddr>,
+    ) -> Result<String> {
+        let authorization = authorization
+            .ok_or_else(|| ErrorCode::AuthenticateFailure("No authorization header"))?;
+        let hostname = remote_addr.map(|addr| addr.ip().to_string());
+
+        let credential = if let Some(basic) = authorization.strip_prefix("Basic ") {
+            let decoded = base64::decode(basic).map_err(|cause| {
+                ErrorCode::AuthenticateFailure(format!("Invalid basic authorization: {}", cause))
+            })?;
+            let decoded = String::from_utf8(decoded)?;
+            let (name, password) = decoded.split_once(':').ok_or_else(|| {
+                ErrorCode::AuthenticateFailure("Invalid basic authorization: missing password")
+            })?;
+            Credential::Password {
+                name: name.to_string(),
+                password: Some(password.as_bytes().to_vec()),
+                hostname,
+            }
+        } else if let Some(token) = authorization.strip_prefix("Bearer ") {
+            Credential::Jwt {
+                token: token.to_string(),
+                hostname,
+            }
+        } else {
+            return Err(ErrorCode::AuthenticateFailure(
+                "Unsupported authorization, expects Basic or Bearer",
+            ));
+        };
+
+        let session = SessionManager::instance()
+            .create_session(SessionType::FlightSQL)
+            .await?;
+        self.auth_manager.auth(session.clone(), &credential).await?;
+
+        let token = uuid::Uuid::new_v4().to_string();
+        let mut sessions = self.sessions.lock();
+        sessions.retain(|_, session| session.last_access.elapsed() < SESSION_IDLE_TIMEOUT);
+        sessions.insert(token.clone(), FlightSqlSession {
+            session,
+            last_access: Instant::now(),
+            parameters: HashMap::new(),
+        });
+
+        info!("Flight SQL session authenticated: {:?}", remote_addr);
+        Ok(token)
+    }
+
+    fn get_session<T>(&self, request: &Request<T>) -> Result<(String, Arc<Session>), Status> {
+        let token = request
+            .metadata()
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or_else(|| Status::unauthenticated("No bearer token, handshake first"))?;
+
+        match self.sessions.lock().get_mut(token) {
+            Some(session) => {
+                session.last_access = Instant::now();
+                Ok((token.to_string(), session.session.clone()))
+            }
+            None => Err(Status::unauthenticated("Invalid or expired bearer token")),
+        }
+    }
+
+    fn bind_parameters(&self, token: &str, id: u32, parameters: Vec<PlaceholderValue>) {
+        if let Some(session) = self.sessions.lock().get_mut(token) {
+            session.parameters.insert(id, parameters);
+        }
+    }
+
+    fn bound_parameters(&self, token: &str, id: u32) -> Vec<PlaceholderValue> {
+        self.sessions
+            .lock()
+            .get(token)
+            .and_then(|session| session.parameters.get(&id).cloned())
+            .unwrap_or_default()
+    }
+
+    fn close_prepared_statement(&self, token: &str, session: &Arc<Session>, handle: &[u8]) {
+        if let Some(id) = statement_id(handle) {
+            session.remove_prepared_statement(id);
+            if let Some(session) = self.sessions.lock().get_mut(token) {
+                session.parameters.remove(&id);
+            }
+        }
+    }
+
+    fn prepared_statement(
+        session: &Arc<Session>,
+        handle: &[u8],
+    ) -> Result<(u32, Arc<PreparedStatement>)> {
+        statement_id(handle)
+            .and_then(|id| {
+                session
+                    .get_prepared_statement(id)
+                    .map(|statement| (id, statement))
+            })
+            .ok_or_else(|| ErrorCode::BadArguments("Unknown or closed prepared statement"))
+    }
+
+    /// Plan a command of `get_flight_info`, returns the plan and the ticket executing it.
+    async fn plan_command(
+        &self,
+        token: &str,
+        session: &Arc<Session>,
+        command: FlightSqlCommand,
+    ) -> Result<(Plan, Vec<u8>)> {
+        let query = match command {
+            FlightSqlCommand::PreparedStatementQuery(command) => {
+                let (_, plan, _) = self
+                    .plan_prepared_query(token, session, &command.prepared_statement_handle)
+                    .await?;
+                return Ok((plan, command.encode_any()));
+            }
+            FlightSqlCommand::StatementQuery(command) => command.query,
+            FlightSqlCommand::GetCatalogs(_) => {
+                "SELECT name AS catalog_name FROM system.catalogs ORDER BY catalog_name".to_string()
+            }
+            FlightSqlCommand::GetDbSchemas(command) => Self::db_schemas_query(&command),
+            FlightSqlCommand::GetTables(command) => Self::tables_query(&command)?,
+            FlightSqlCommand::GetTableTypes(_) => {
+                "SELECT 'TABLE' AS table_type UNION ALL SELECT 'VIEW' AS table_type".to_string()
+            }
+            FlightSqlCommand::StatementUpdate(_)
+            | FlightSqlCommand::PreparedStatementUpdate(_)
+            | FlightSqlCommand::StatementIngest(_) => {
+                return Err(ErrorCode::BadArguments(
+                    "Updates and ingestion should be sent by do_put",
+                ));
+            }
+        };
+
+        let (_, plan) = Self::plan_sql(session, &query).await?;
+        let ticket = TicketStatementQuery {
+            statement_handle: query.into_bytes(),
+        };
+        Ok((plan, ticket.encode_any()))
+    }
+
+    fn db_schemas_query(command: &CommandGetDbSchemas) -> String {
+        let mut filters = vec![];
+        if let Some(catalog) = &command.catalog {
+            filters.push(format!("catalog = {}", quote(catalog)));
+        }
+        if let Some(pattern) = &command.db_schema_filter_pattern {
+            filters.push(format!("name LIKE {}", quote(pattern)));
+        }
+
+        format!(
+            "SELECT catalog AS catalog_name, name AS db_schema_name FROM system.databases{} \
+             ORDER BY catalog_name, db_schema_name",
+            where_clause(filters)
+        )
+    }
+
+    fn tables_query(command: &CommandGetTables) -> Result<String> {
+        if command.include_schema {
+            return Err(ErrorCode::Unimplemented(
+                "Flight SQL GetTables with include_schema is not supported",
+            ));
+        }
+
+        let mut filters = vec![];
+        if let Some(catalog) = &command.catalog {
+            filters.push(format!("catalog = {}", quote(catalog)));
+        }
+        if let Some(pattern) = &command.db_schema_filter_pattern {
+            filters.push(format!("database LIKE {}", quote(pattern)));
+        }
+        if let Some(pattern) = &command.table_name_filter_pattern {
+            filters.push(format!("name LIKE {}", quote(pattern)));
+        }
+        if !command.table_types.is_empty() {
+            let table_types = command
+                .table_types
+                .iter()
+                .map(|table_type| quote(table_type))
+                .collect::<Vec<_>>();
+            filters.push(format!("{} IN ({})", TABLE_TYPE, table_types.join(", ")));
+        }
+
+        Ok(format!(
+            "SELECT catalog AS catalog_name, database AS db_schema_name, name AS table_name, \
+             {} AS table_type FROM system.tables{} \
+             ORDER BY catalog_name, db_schema_name, table_name",
+            TABLE_TYPE,
+            where_clause(filters)
+        ))
+    }
+
+    async fn plan_sql(session: &Arc<Session>, query: &str) -> Result<(Arc<QueryContext>, Plan)> {
+        let ctx = session.create_query_context().await?;
+        let mut planner = Planner::new(ctx.clone());
+        let (plan, _, _) = planner.plan_sql(query).await?;
+        Ok((ctx, plan))
+    }
+
+    async fn plan_prepared(
+        session: &Arc<Session>,
+        statement: &PreparedStatement,
+        parameters: Vec<PlaceholderValue>,
+    ) -> Result<(Arc<QueryContext>, Plan)> {
+        check_parameters(statement, &parameters)?;
+        let ctx = session.create_query_context().await?;
+        ctx.attach_placeholders(parameters);
+        let plan = statement.plan(ctx.clone()).await?;
+        Ok((ctx, plan))
+    }
+
+    /// Plan a prepared query with the parameters bound to it.
+    async fn plan_prepared_query(
+        &self,
+        token: &str,
+        session: &Arc<Session>,
+        handle: &[u8],
+    ) -> Result<(Arc<QueryContext>, Plan, String)> {
+        let (id, statement) = Self::prepared_statement(session, handle)?;
+        let parameters = self.bound_parameters(token, id);
+        let (ctx, plan) = Self::plan_prepared(session, &statement, parameters).await?;
+        Ok((ctx, plan, statement.query().to_string()))
+    }
+
+    async fn flight_info(
+        &self,
+        token: &str,
+        session: &Arc<Session>,
+        descriptor: FlightDescriptor,
+    ) -> Result<FlightInfo> {
+        let command = FlightSqlCommand::decode(&descriptor.cmd)?;
+        let (plan, ticket) = self.plan_command(token, session, command).await?;
+
+        Ok(FlightInfo {
+            schema: serialize_schema_to_info(&plan.schema().to_arrow(), None)?,
+            flight_descriptor: Some(descriptor),
+            endpoint: vec![FlightEndpoint {
+                ticket: Some(Ticket { ticket }),
+                location: vec![],
+            }],
+            total_records: -1,
+            total_bytes: -1,
+        })
+    }
+
+    async fn ticket_query(
+        &self,
+        token: &str,
+        session: &Arc<Session>,
+        ticket: Ticket,
+    ) -> Result<FlightSqlStream<FlightData>> {
+        let (ctx, plan, query) = match FlightSqlTicket::decode(&ticket.ticket)? {
+            FlightSqlTicket::StatementQuery(ticket) => {
+                let query = String::from_utf8(ticket.statement_handle)
+                    .map_err(|_| ErrorCode::BadArguments("Invalid statement handle"))?;
+                let (ctx, plan) = Self::plan_sql(session, &query).await?;
+                (ctx, plan, query)
+            }
+            FlightSqlTicket::PreparedStatementQuery(command) => {
+                self.plan_prepared_query(token, session, &command.prepared_statement_handle)
+                    .await?
+            }
+        };
+
+        Self::execute_query(ctx, &plan, &query).await
+    }
+
+    async fn execute_query(
+        ctx: Arc<QueryContext>,
+        plan: &Plan,
+        query: &str,
+    ) -> Result<FlightSqlStream<FlightData>> {
+        ctx.attach_query_str(plan.to_string(), query);
+
+        let interpreter = InterpreterFactory::get(ctx.clone(), plan).await?;
+        let mut data_stream = interpreter.execute(ctx.clone()).await?;
+
+        let arrow_schema = plan.schema().to_arrow();
+        let ipc_fields = default_ipc_fields(&arrow_schema.fields);
+        let schema = serialize_schema(&arrow_schema, Some(&ipc_fields));
+        let options = WriteOptions { compression: None };
+
+        let stream = async_stream::stream! {
+            // The context lives until all the blocks are sent.
+            let _ctx = ctx;
+            yield Ok(schema);
+
+            while let Some(block) = data_stream.next().await {
+                let data = block.and_then(|block| {
+                    if block.is_empty() {
+                        return Ok(None);
+                    }
+                    block_to_flight_data(block, &ipc_fields, &options).map(Some)
+                });
+                match data {
+                    Ok(Some(data)) => yield Ok(data),
+                    Ok(None) => continue,
+                    Err(cause) => {
+                        yield Err(Status::from(cause));
+                        break;
+                    }
+                }
+            }
+        };
+        Ok(Box::pin(stream))
+    }
+
+    async fn execute_update(ctx: Arc<QueryContext>, plan: &Plan, query: &str) -> Result<i64> {
+        ctx.attach_query_str(plan.to_string(), query);
+
+        let interpreter = InterpreterFactory::get(ctx.clone(), plan).await?;
+        let mut data_stream = interpreter.execute(ctx.clone()).await?;
+        while let Some(block) = data_stream.next().await {
+            block?;
+        }
+
+        Ok(ctx.get_write_progress_value().rows as i64)
+    }
+
+    async fn ingest(
+        session: Arc<Session>,
+        command: CommandStatementIngest,
+        header: FlightData,
+        mut input: Streaming<FlightData>,
+    ) -> Result<i64> {
+        if command.temporary {
+            return Err(ErrorCode::Unimplemented(
+                "Flight SQL ingestion into temporary tables is not supported",
+            ));
+        }
+
+        let ctx = session.create_query_context().await?;
+        let catalog = command
+            .catalog
+            .unwrap_or_else(|| CATALOG_DEFAULT.to_string());
+        let database = command.schema.unwrap_or_else(|| ctx.get_current_database());
+        session
+            .validate_privilege(
+                &GrantObject::Table(catalog.clone(), database.clone(), command.table.clone()),
+                UserPrivilegeType::Insert,
+            )
+            .await?;
+        let table = ctx.get_table(&catalog, &database, &command.table).await?;
+
+        // The first message carries the schema of the record batches.
+        let (arrow_schema, ipc_schema) = deserialize_schemas(&header.data_header)?;
+        let input_schema =
+            DataSchemaRefExt::create(arrow_schema.fields.iter().map(DataField::from).collect());
+        let table_schema = table.schema();
+        let insert_schema = DataSchemaRefExt::create(
+            arrow_schema
+                .fields
+                .iter()
+                .map(|field| Ok(DataField::from(table_schema.field_with_name(&field.name)?)))
+                .collect::<Result<Vec<_>>>()?,
+        );
+
+        let source_schema = input_schema.clone();
+        let blocks = async_stream::try_stream! {
+            while let Some(data) = input.message().await? {
+                let chunk = deserialize_batch(
+                    &data,
+                    &arrow_schema.fields,
+                    &ipc_schema,
+                    &Default::default(),
+                )?;
+                yield DataBlock::from_arrow_chunk(&chunk, &source_schema)?;
+            }
+        };
+
+        let output = OutputPort::create();
+        let source = StreamSource::create(
+            ctx.clone(),
+            Some(Box::pin(blocks) as SendableDataBlockStream),
+            output.clone(),
+        )?;
+        let mut build_res = PipelineBuildResult::create();
+        build_res.main_pipeline.add_pipe(Pipe::SimplePipe {
+            inputs_port: vec![],
+            outputs_port: vec![output],
+            processors: vec![source],
+        });
+
+        if input_schema != insert_schema {
+            let func_ctx = ctx.try_get_function_context()?;
+            build_res.main_pipeline.add_transform(|input, output| {
+                TransformCastSchema::try_create(
+                    input,
+                    output,
+                    input_schema.clone(),
+                    insert_schema.clone(),
+                    func_ctx,
+                )
+            })?;
+        }
+
+        append2table(
+            ctx.clone(),
+            table,
+            insert_schema,
+            &mut build_res,
+            false,
+            true,
+            AppendMode::Normal,
+        )?;
+
+        let settings = ctx.get_settings();
+        let mut pipeline = build_res.main_pipeline;
+        pipeline.set_max_threads(settings.get_max_threads()? as usize);
+        let executor_settings = ExecutorSettings::try_create(&settings, ctx.get_id())?;
+        let executor = PipelineCompleteExecutor::try_create(pipeline, executor_settings)?;
+        ctx.set_executor(Arc::downgrade(&executor.get_inner()));
+        // The executor blocks until all the record batches are ingested, keep it off the
+        // async workers serving the requests.
+        tokio::task::spawn_blocking(move || executor.execute())
+            .await
+            .map_err(|cause| {
+                ErrorCode::TokioError(format!("Flight SQL ingestion panicked: {}", cause))
+            })??;
+
+        Ok(ctx.get_write_progress_value().rows as i64)
+    }
+
+    /// Decode the rows of parameters sent as record batches after the header of `do_put`.
+    async fn parameters(
+        session: &Arc<Session>,
+        header: &FlightData,
+        mut input: Streaming<FlightData>,
+    ) -> Result<Vec<Vec<PlaceholderValue>>> {
+        if header.data_header.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let tz = session.get_format_settings()?.timezone;
+        let (arrow_schema, ipc_schema) = deserialize_schemas(&header.data_header)?;
+        let schema = DataSchema::new(arrow_schema.fields.iter().map(DataField::from).collect());
+
+        let mut rows = vec![];
+        while let Some(data) = input.message().await? {
+            let chunk = deserialize_batch(
+                &data,
+                &arrow_schema.fields,
+                &ipc_schema,
+                &Default::default(),
+            )?;
+            let block = DataBlock::from_arrow_chunk(&chunk, &schema)?;
+            for row in 0..block.num_rows() {
+                let parameters = (0..block.num_columns())
+                    .map(|column| {
+                        let entry = block.get_by_offset(column);
+                        placeholder_value(entry.value.as_ref().index(row).unwrap(), tz)
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                rows.push(parameters);
+            }
+        }
+        Ok(rows)
+    }
+
+    async fn put(
+        &self,
+        token: &str,
+        session: Arc<Session>,
+        mut input: Streaming<FlightData>,
+    ) -> Result<PutResult> {
+        let header = input
+            .message()
+            .await?
+            .ok_or_else(|| ErrorCode::BadArguments("Empty do_put stream"))?;
+        let descriptor = header
+            .flight_descriptor
+            .as_ref()
+            .ok_or_else(|| ErrorCode::BadArguments("Missing flight descriptor in do_put"))?;
+
+        match FlightSqlCommand::decode(&descriptor.cmd)? {
+            FlightSqlCommand::StatementUpdate(command) => {
+                let (ctx, plan) = Self::plan_sql(&session, &command.query).await?;
+                let record_count = Self::execute_update(ctx, &plan, &command.query).await?;
+                Ok(update_result(record_count))
+            }
+            FlightSqlCommand::PreparedStatementUpdate(command) => {
+                let (_, statement) =
+                    Self::prepared_statement(&session, &command.prepared_statement_handle)?;
+                let mut rows = Self::parameters(&session, &header, input).await?;
+                // A statement without parameters may be sent without any record batch.
+                if rows.is_empty() {
+                    rows.push(vec![]);
+                }
+
+                let mut record_count = 0;
+                for parameters in rows {
+                    let (ctx, plan) = Self::plan_prepared(&session, &statement, parameters).await?;
+                    record_count += Self::execute_update(ctx, &plan, statement.query()).await?;
+                }
+                Ok(update_result(record_count))
+            }
+            FlightSqlCommand::PreparedStatementQuery(command) => {
+                let (id, statement) =
+                    Self::prepared_statement(&session, &command.prepared_statement_handle)?;
+                let mut rows = Self::parameters(&session, &header, input).await?;
+                if rows.len() > 1 {
+                    return Err(ErrorCode::BadArguments(
+                        "Only one row of parameters can be bound to a prepared query",
+                    ));
+                }
+
+                let parameters = rows.pop().unwrap_or_default();
+                check_parameters(&statement, &parameters)?;
+                self.bind_parameters(token, id, parameters);
+                Ok(PutResult::default())
+            }
+            FlightSqlCommand::StatementIngest(command) => {
+                let record_count = Self::ingest(session, command, header, input).await?;
+                Ok(update_result(record_count))
+            }
+            _ => Err(ErrorCode::BadArguments(
+                "Queries and metadata commands should be sent by get_flight_info",
+            )),
+        }
+    }
+
+    async fn action(&self, token: &str, session: Arc<Session>, action: Action) -> Result<Vec<u8>> {
+        match action.r#type.as_str() {
+            ACTION_CREATE_PREPARED_STATEMENT => {
+                let request: ActionCreatePreparedStatementRequest = decode_message(&action.body)?;
+                let param_count = count_placeholders(&request.query)?;
+
+                // Bind the placeholders with NULL to describe the result columns.
+                let ctx = session.create_query_context().await?;
+                ctx.attach_placeholders(vec![PlaceholderValue::Null; param_count]);
+                let mut planner = Planner::new(ctx);
+                let (plan, _, _) = planner.plan_sql(&request.query).await?;
+                let schema = if plan.has_result_set() {
+                    plan.schema()
+                } else {
+                    Arc::new(DataSchema::empty())
+                };
+
+                let statement = PreparedStatement::create(request.query, param_count);
+                let id = session.add_prepared_statement(statement);
+                // The types of the parameters are only known once they're bound.
+                let result = ActionCreatePreparedStatementResult {
+                    prepared_statement_handle: id.to_string().into_bytes(),
+                    dataset_schema: serialize_schema_to_info(&schema.to_arrow(), None)?,
+                    parameter_schema: vec![],
+                };
+                Ok(result.encode_any())
+            }
+            ACTION_CLOSE_PREPARED_STATEMENT => {
+                let request: ActionClosePreparedStatementRequest = decode_message(&action.body)?;
+                self.close_prepared_statement(token, &session, &request.prepared_statement_handle);
+                Ok(vec![])
+            }
+            other => Err(ErrorCode::Unimplemented(format!(
+                "Unimplemented flight sql action: {}",
+                other
+            ))),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl FlightService for FlightSqlServiceImpl {
+    type HandshakeStream = FlightSqlStream<HandshakeResponse>;
+
+    async fn handshake(
+        &self,
+        request: StreamReq<HandshakeRequest>,
+    ) -> Response<Self::HandshakeStream> {
+        let authorization = request
+            .metadata()
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string());
+        let token = self
+            .authenticate(authorization, request.remote_addr())
+            .await
+            .map_err(|cause| Status::unauthenticated(cause.message()))?;
+
+        let output = HandshakeResponse {
+            protocol_version: 0,
+            payload: token.as_bytes().to_vec(),
+        };
+        let mut response = RawResponse::new(
+            Box::pin(tokio_stream::once(Ok(output))) as FlightSqlStream<HandshakeResponse>
+        );
+        let authorization = format!("Bearer {}", token)
+            .parse()
+            .map_err(|_| Status::internal("Invalid bearer token"))?;
+        response
+            .metadata_mut()
+            .insert("authorization", authorization);
+        Ok(response)
+    }
+
+    type ListFlightsStream = FlightSqlStream<FlightInfo>;
+
+    async fn list_flights(&self, _: Request<Criteria>) -> Response<Self::ListFlightsStream> {
+        Err(Status::unimplemented(
+            "Flight SQL does not implement list_flights.",
+        ))
+    }
+
+    async fn get_flight_info(&self, request: Request<FlightDescriptor>) -> Response<FlightInfo> {
+        let (token, session) = self.get_session(&request)?;
+        let info = self
+            .flight_info(&token, &session, request.into_inner())
+            .await?;
+        Ok(RawResponse::new(info))
+    }
+
+    async fn get_schema(&self, request: Request<FlightDescriptor>) -> Response<SchemaResult> {
+        let (token, session) = self.get_session(&request)?;
+        let info = self
+            .flight_info(&token, &session, request.into_inner())
+            .await?;
+        Ok(RawResponse::new(SchemaResult {
+            schema: info.schema,
+        }))
+    }
+
+    type DoGetStream = FlightSqlStream<FlightData>;
+
+    async fn do_get(&self, request: Request<Ticket>) -> Response<Self::DoGetStream> {
+        let (token, session) = self.get_session(&request)?;
+        let stream = self
+            .ticket_query(&token, &session, request.into_inner())
+            .await?;
+        Ok(RawResponse::new(stream))
+    }
+
+    type DoPutStream = FlightSqlStream<PutResult>;
+
+    async fn do_put(&self, request: StreamReq<FlightData>) -> Response<Self::DoPutStream> {
+        let (token, session) = self.get_session(&request)?;
+        let result = self.put(&token, session, request.into_inner()).await?;
+        Ok(RawResponse::new(
+            Box::pin(tokio_stream::once(Ok(result))) as FlightSqlStream<PutResult>
+        ))
+    }
+
+    type DoExchangeStream = FlightSqlStream<FlightData>;
+
+    async fn do_exchange(&self, _: StreamReq<FlightData>) -> Response<Self::DoExchangeStream> {
+        Err(Status::unimplemented(
+            "Flight SQL does not implement do_exchange.",
+        ))
+    }
+
+    type DoActionStream = FlightSqlStream<FlightResult>;
+
+    async fn do_action(&self, request: Request<Action>) -> Response<Self::DoActionStream> {
+        let (token, session) = self.get_session(&request)?;
+        let body = self.action(&token, session, request.into_inner()).await?;
+
+        Ok(RawResponse::new(
+            Box::pin(tokio_stream::once(Ok(FlightResult { body })))
+                as FlightSqlStream<FlightResult>,
+        ))
+    }
+
+    type ListActionsStream = FlightSqlStream<ActionType>;
+
+    async fn list_actions(&self, _: Request<Empty>) -> Response<Self::ListActionsStream> {
+        let actions = vec![
+            Ok(ActionType {
+                r#type: ACTION_CREATE_PREPARED_STATEMENT.to_string(),
+                description: "Creates a reusable prepared statement resource on the server."
+                    .to_string(),
+            }),
+            Ok(ActionType {
+                r#type: ACTION_CLOSE_PREPARED_STATEMENT.to_string(),
+                description: "Closes a reusable prepared statement resource on the server."
+                    .to_string(),
+            }),
+        ];
+        Ok(RawResponse::new(
+            Box::pin(tokio_stream::iter(actions)) as FlightSqlStream<ActionType>
+        ))
+    }
+}
+
+fn block_to_flight_data(
+    block: DataBlock,
+    ipc_fields: &[IpcField],
+    options: &WriteOptions,
+) -> Result<FlightData> {
+    let chunk = block.try_into()?;
+    let (dicts, values) = serialize_batch(&chunk, ipc_fields, options)?;
+
+    if !dicts.is_empty() {
+        return Err(ErrorCode::Unimplemented(
+            "DatabendQuery does not implement dicts.",
+        ));
+    }
+
+    Ok(values)
+}
+
+fn update_result(record_count: i64) -> PutResult {
+    PutResult {
+        app_metadata: DoPutUpdateResult { record_count }.encode_to_vec(),
+    }
+}
+
+/// The handle of a prepared statement is the id of the statement in the session.
+fn statement_id(handle: &[u8]) -> Option<u32> {
+    std::str::from_utf8(handle).ok()?.parse().ok()
+}
+
+fn check_parameters(statement: &PreparedStatement, parameters: &[PlaceholderValue]) -> Result<()> {
+    if parameters.len() != statement.param_count() {
+        return Err(ErrorCode::BadArguments(format!(
+            "Prepared statement expects {} parameters, but got {}",
+            statement.param_count(),
+            parameters.len()
+        )));
+    }
+    Ok(())
+}
+
+fn placeholder_value(value: ScalarRef, tz: Tz) -> Result<PlaceholderValue> {
+    match value {
+        ScalarRef::Null => Ok(PlaceholderValue::Null),
+        ScalarRef::Number(NumberScalar::Int8(v)) => Ok(PlaceholderValue::Int(v as i64)),
+        ScalarRef::Number(NumberScalar::Int16(v)) => Ok(PlaceholderValue::Int(v as i64)),
+        ScalarRef::Number(NumberScalar::Int32(v)) => Ok(PlaceholderValue::Int(v as i64)),
+        ScalarRef::Number(NumberScalar::Int64(v)) => Ok(PlaceholderValue::Int(v)),
+        ScalarRef::Number(NumberScalar::UInt8(v)) => Ok(PlaceholderValue::UInt(v as u64)),
+        ScalarRef::Number(NumberScalar::UInt16(v)) => Ok(PlaceholderValue::UInt(v as u64)),
+        ScalarRef::Number(NumberScalar::UInt32(v)) => Ok(PlaceholderValue::UInt(v as u64)),
+        ScalarRef::Number(NumberScalar::UInt64(v)) => Ok(PlaceholderValue::UInt(v)),
+        ScalarRef::Number(NumberScalar::Float32(v)) => {
+            Ok(PlaceholderValue::Float(v.into_inner() as f64))
+        }
+        ScalarRef::Number(NumberScalar::Float64(v)) => Ok(PlaceholderValue::Float(v.into_inner())),
+        ScalarRef::String(v) => Ok(PlaceholderValue::String(String::from_utf8(v.to_vec())?)),
+        ScalarRef::Date(v) => Ok(PlaceholderValue::Date(date_to_string(v, tz).to_string())),
+        ScalarRef::Timestamp(v) => Ok(PlaceholderValue::Timestamp(
+            timestamp_to_string(v, tz).to_string(),
+        )),
+        _ => Err(ErrorCode::Unimplemented(
+            "Only numbers, strings, dates and timestamps are supported as parameters",
+        )),
+    }
+}
+
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+fn where_clause(filters: Vec<String>) -> String {
+    if filters.is_empty() {
+        String::new()
+    } else {
+        format!(" WHERE {}", filters.join(" AND "))
+    }
+}
@@ -0,0 +1,280 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Messages of the Arrow Flight SQL protocol, `arrow.flight.protocol.sql` in `FlightSql.proto`.
+//!
+//! Commands are carried as `google.protobuf.Any` in the descriptors, tickets and action bodies.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use prost::Message;
+
+const TYPE_URL_PREFIX: &str = "type.googleapis.com/arrow.flight.protocol.sql.";
+
+#[derive(Clone, PartialEq, Message)]
+pub struct Any {
+    #[prost(string, tag = "1")]
+    pub type_url: String,
+    #[prost(bytes = "vec", tag = "2")]
+    pub value: Vec<u8>,
+}
+
+pub trait FlightSqlMessage: Message + Default + Sized {
+    const NAME: &'static str;
+
+    fn as_any(&self) -> Any {
+        Any {
+            type_url: format!("{}{}", TYPE_URL_PREFIX, Self::NAME),
+            value: self.encode_to_vec(),
+        }
+    }
+
+    fn encode_any(&self) -> Vec<u8> {
+        self.as_any().encode_to_vec()
+    }
+
+    fn decode_any(any: &Any) -> Result<Self> {
+        Self::decode(any.value.as_slice()).map_err(|cause| {
+            ErrorCode::BadBytes(format!(
+                "Cannot decode flight sql message {}, cause {}",
+                Self::NAME,
+                cause
+            ))
+        })
+    }
+}
+
+macro_rules! flight_sql_messages {
+    ($($message:ident),*) => {
+        $(
+            impl FlightSqlMessage for $message {
+                const NAME: &'static str = stringify!($message);
+            }
+        )*
+    };
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CommandGetCatalogs {}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CommandGetDbSchemas {
+    #[prost(string, optional, tag = "1")]
+    pub catalog: Option<String>,
+    #[prost(string, optional, tag = "2")]
+    pub db_schema_filter_pattern: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CommandGetTables {
+    #[prost(string, optional, tag = "1")]
+    pub catalog: Option<String>,
+    #[prost(string, optional, tag = "2")]
+    pub db_schema_filter_pattern: Option<String>,
+    #[prost(string, optional, tag = "3")]
+    pub table_name_filter_pattern: Option<String>,
+    #[prost(string, repeated, tag = "4")]
+    pub table_types: Vec<String>,
+    #[prost(bool, tag = "5")]
+    pub include_schema: bool,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CommandGetTableTypes {}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CommandStatementQuery {
+    #[prost(string, tag = "1")]
+    pub query: String,
+    #[prost(bytes = "vec", optional, tag = "2")]
+    pub transaction_id: Option<Vec<u8>>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct TicketStatementQuery {
+    #[prost(bytes = "vec", tag = "1")]
+    pub statement_handle: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CommandPreparedStatementQuery {
+    #[prost(bytes = "vec", tag = "1")]
+    pub prepared_statement_handle: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CommandStatementUpdate {
+    #[prost(string, tag = "1")]
+    pub query: String,
+    #[prost(bytes = "vec", optional, tag = "2")]
+    pub transaction_id: Option<Vec<u8>>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CommandPreparedStatementUpdate {
+    #[prost(bytes = "vec", tag = "1")]
+    pub prepared_statement_handle: Vec<u8>,
+}
+
+/// Bulk ingestion into an existing table, the data follows in the `do_put` stream.
+#[derive(Clone, PartialEq, Message)]
+pub struct CommandStatementIngest {
+    #[prost(string, tag = "2")]
+    pub table: String,
+    #[prost(string, optional, tag = "3")]
+    pub schema: Option<String>,
+    #[prost(string, optional, tag = "4")]
+    pub catalog: Option<String>,
+    #[prost(bool, tag = "5")]
+    pub temporary: bool,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct DoPutUpdateResult {
+    #[prost(int64, tag = "1")]
+    pub record_count: i64,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ActionCreatePreparedStatementRequest {
+    #[prost(string, tag = "1")]
+    pub query: String,
+    #[prost(bytes = "vec", optional, tag = "2")]
+    pub transaction_id: Option<Vec<u8>>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ActionCreatePreparedStatementResult {
+    #[prost(bytes = "vec", tag = "1")]
+    pub prepared_statement_handle: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub dataset_schema: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub parameter_schema: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ActionClosePreparedStatementRequest {
+    #[prost(bytes = "vec", tag = "1")]
+    pub prepared_statement_handle: Vec<u8>,
+}
+
+flight_sql_messages!(
+    CommandGetCatalogs,
+    CommandGetDbSchemas,
+    CommandGetTables,
+    CommandGetTableTypes,
+    CommandStatementQuery,
+    TicketStatementQuery,
+    CommandPreparedStatementQuery,
+    CommandStatementUpdate,
+    CommandPreparedStatementUpdate,
+    CommandStatementIngest,
+    DoPutUpdateResult,
+    ActionCreatePreparedStatementRequest,
+    ActionCreatePreparedStatementResult,
+    ActionClosePreparedStatementRequest
+);
+
+/// The commands accepted in the flight descriptors.
+pub enum FlightSqlCommand {
+    GetCatalogs(CommandGetCatalogs),
+    GetDbSchemas(CommandGetDbSchemas),
+    GetTables(CommandGetTables),
+    GetTableTypes(CommandGetTableTypes),
+    StatementQuery(CommandStatementQuery),
+    PreparedStatementQuery(CommandPreparedStatementQuery),
+    StatementUpdate(CommandStatementUpdate),
+    PreparedStatementUpdate(CommandPreparedStatementUpdate),
+    StatementIngest(CommandStatementIngest),
+}
+
+impl FlightSqlCommand {
+    pub fn decode(bytes: &[u8]) -> Result<FlightSqlCommand> {
+        let any = decode_any(bytes)?;
+        let name = any
+            .type_url
+            .strip_prefix(TYPE_URL_PREFIX)
+            .unwrap_or(any.type_url.as_str());
+
+        match name {
+            CommandGetCatalogs::NAME => Ok(Self::GetCatalogs(FlightSqlMessage::decode_any(&any)?)),
+            CommandGetDbSchemas::NAME => {
+                Ok(Self::GetDbSchemas(FlightSqlMessage::decode_any(&any)?))
+            }
+            CommandGetTables::NAME => Ok(Self::GetTables(FlightSqlMessage::decode_any(&any)?)),
+            CommandGetTableTypes::NAME => {
+                Ok(Self::GetTableTypes(FlightSqlMessage::decode_any(&any)?))
+            }
+            CommandStatementQuery::NAME => {
+                Ok(Self::StatementQuery(FlightSqlMessage::decode_any(&any)?))
+            }
+            CommandPreparedStatementQuery::NAME => Ok(Self::PreparedStatementQuery(
+                FlightSqlMessage::decode_any(&any)?,
+            )),
+            CommandStatementUpdate::NAME => {
+                Ok(Self::StatementUpdate(FlightSqlMessage::decode_any(&any)?))
+            }
+            CommandPreparedStatementUpdate::NAME => Ok(Self::PreparedStatementUpdate(
+                FlightSqlMessage::decode_any(&any)?,
+            )),
+            CommandStatementIngest::NAME => {
+                Ok(Self::StatementIngest(FlightSqlMessage::decode_any(&any)?))
+            }
+            _ => Err(ErrorCode::Unimplemented(format!(
+                "Unimplemented flight sql command: {}",
+                any.type_url
+            ))),
+        }
+    }
+}
+
+/// The tickets returned by `get_flight_info`.
+pub enum FlightSqlTicket {
+    StatementQuery(TicketStatementQuery),
+    PreparedStatementQuery(CommandPreparedStatementQuery),
+}
+
+impl FlightSqlTicket {
+    pub fn decode(bytes: &[u8]) -> Result<FlightSqlTicket> {
+        let any = decode_any(bytes)?;
+        let name = any
+            .type_url
+            .strip_prefix(TYPE_URL_PREFIX)
+            .unwrap_or(any.type_url.as_str());
+
+        match name {
+            TicketStatementQuery::NAME => {
+                Ok(Self::StatementQuery(FlightSqlMessage::decode_any(&any)?))
+            }
+            CommandPreparedStatementQuery::NAME => Ok(Self::PreparedStatementQuery(
+                FlightSqlMessage::decode_any(&any)?,
+            )),
+            _ => Err(ErrorCode::BadArguments(format!(
+                "Invalid flight sql ticket: {}",
+                any.type_url
+            ))),
+        }
+    }
+}
+
+pub fn decode_any(bytes: &[u8]) -> Result<Any> {
+    Any::decode(bytes).map_err(|cause| {
+        ErrorCode::BadBytes(format!("Cannot decode flight sql command, cause {}", cause))
+    })
+}
+
+pub fn decode_message<T: FlightSqlMessage>(bytes: &[u8]) -> Result<T> {
+    T::decode_any(&decode_any(bytes)?)
+}
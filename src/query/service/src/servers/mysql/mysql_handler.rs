@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fs::File;
 use std::future::Future;
+use std::io::BufReader;
 use std::net::SocketAddr;
 use std::sync::Arc;
 
@@ -21,6 +23,7 @@ use common_base::base::tokio::net::TcpStream;
 use common_base::base::tokio::task::JoinHandle;
 use common_base::runtime::Runtime;
 use common_base::runtime::TrySpawn;
+use common_config::Config;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use futures::future::AbortHandle;
@@ -28,6 +31,10 @@ use futures::future::AbortRegistration;
 use futures::future::Abortable;
 use futures::StreamExt;
 use opensrv_mysql::*;
+use rustls::Certificate;
+use rustls::PrivateKey;
+use rustls::ServerConfig;
+use rustls_pemfile::Item;
 use tokio_stream::wrappers::TcpListenerStream;
 use tracing::error;
 use tracing::info;
@@ -41,15 +48,17 @@ use crate::sessions::SessionManager;
 use crate::sessions::SessionType;
 
 pub struct MySQLHandler {
+    config: Config,
     abort_handle: AbortHandle,
     abort_registration: Option<AbortRegistration>,
     join_handle: Option<JoinHandle<()>>,
 }
 
 impl MySQLHandler {
-    pub fn create() -> Result<Box<dyn Server>> {
+    pub fn create(config: Config) -> Result<Box<dyn Server>> {
         let (abort_handle, registration) = AbortHandle::new_pair();
         Ok(Box::new(MySQLHandler {
+            config,
             abort_handle,
             abort_registration: Some(registration),
             join_handle: None,
@@ -66,20 +75,59 @@ impl MySQLHandler {
         Ok((TcpListenerStream::new(listener), listener_addr))
     }
 
-    fn listen_loop(&self, stream: ListeningStream, rt: Arc<Runtime>) -> impl Future<Output = ()> {
+    fn build_tls(cert_path: &str, key_path: &str) -> Result<Arc<ServerConfig>> {
+        let certs = rustls_pemfile::certs(&mut BufReader::new(File::open(cert_path)?))?
+            .into_iter()
+            .map(Certificate)
+            .collect::<Vec<_>>();
+
+        let key = rustls_pemfile::read_all(&mut BufReader::new(File::open(key_path)?))?
+            .into_iter()
+            .find_map(|item| match item {
+                Item::RSAKey(key) | Item::PKCS8Key(key) | Item::ECKey(key) => Some(key),
+                _ => None,
+            })
+            .ok_or_else(|| {
+                ErrorCode::TLSConfigurationFailure(format!("No private key found in {key_path}"))
+            })?;
+
+        let config = ServerConfig::builder()
+            .with_safe_defaults()
+            .with_no_client_auth()
+            .with_single_cert(certs, PrivateKey(key))
+            .map_err(|e| {
+                ErrorCode::TLSConfigurationFailure(format!(
+                    "Cannot build TLS config for MySQL handler: {e}"
+                ))
+            })?;
+        Ok(Arc::new(config))
+    }
+
+    fn listen_loop(
+        &self,
+        stream: ListeningStream,
+        rt: Arc<Runtime>,
+        tls: Option<Arc<ServerConfig>>,
+    ) -> impl Future<Output = ()> {
         stream.for_each(move |accept_socket| {
             let executor = rt.clone();
             let sessions = SessionManager::instance();
+            let tls = tls.clone();
             async move {
                 match accept_socket {
                     Err(error) => error!("Broken session connection: {}", error),
-                    Ok(socket) => MySQLHandler::accept_socket(sessions, executor, socket),
+                    Ok(socket) => MySQLHandler::accept_socket(sessions, executor, socket, tls),
                 };
             }
         })
     }
 
-    fn accept_socket(sessions: Arc<SessionManager>, executor: Arc<Runtime>, socket: TcpStream) {
+    fn accept_socket(
+        sessions: Arc<SessionManager>,
+        executor: Arc<Runtime>,
+        socket: TcpStream,
+        tls: Option<Arc<ServerConfig>>,
+    ) {
         executor.spawn(async move {
             match sessions.create_session(SessionType::MySQL).await {
                 Err(error) => {
@@ -88,7 +136,7 @@ impl MySQLHandler {
                 }
                 Ok(session) => {
                     info!("MySQL connection coming: {:?}", socket.peer_addr());
-                    if let Err(error) = MySQLConnection::run_on_stream(session, socket) {
+                    if let Err(error) = MySQLConnection::run_on_stream(session, socket, tls) {
                         error!("Unexpected error occurred during query: {:?}", error);
                     };
                 }
@@ -134,13 +182,26 @@ impl Server for MySQLHandler {
         match self.abort_registration.take() {
             None => Err(ErrorCode::Internal("MySQLHandler already running.")),
             Some(registration) => {
+                let query_config = &self.config.query;
+                let tls = if query_config.mysql_handler_tls_server_key.is_empty()
+                    || query_config.mysql_handler_tls_server_cert.is_empty()
+                {
+                    None
+                } else {
+                    info!("MySQL Handler TLS enabled");
+                    Some(Self::build_tls(
+                        &query_config.mysql_handler_tls_server_cert,
+                        &query_config.mysql_handler_tls_server_key,
+                    )?)
+                };
+
                 let rejected_rt = Arc::new(Runtime::with_worker_threads(
                     1,
                     Some("mysql-handler".to_string()),
                 )?);
                 let (stream, listener) = Self::listener_tcp(listening).await?;
                 let stream = Abortable::new(stream, registration);
-                self.join_handle = Some(tokio::spawn(self.listen_loop(stream, rejected_rt)));
+                self.join_handle = Some(tokio::spawn(self.listen_loop(stream, rejected_rt, tls)));
                 Ok(listener)
             }
         }
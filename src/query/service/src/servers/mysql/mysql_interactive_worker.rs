@@ -20,13 +20,17 @@ use common_base::base::convert_byte_size;
 use common_base::base::convert_number_size;
 use common_base::base::tokio::io::AsyncWrite;
 use common_base::runtime::TrySpawn;
+use common_catalog::table_context::PlaceholderValue;
 use common_config::DATABEND_COMMIT_VERSION;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_exception::ToErrorCode;
 use common_expression::DataBlock;
+use common_expression::DataSchema;
 use common_expression::DataSchemaRef;
 use common_expression::SendableDataBlockStream;
+use common_meta_types::UserOptionFlag;
+use common_sql::count_placeholders;
 use common_sql::plans::Plan;
 use common_sql::Planner;
 use common_users::CertifiedInfo;
@@ -34,11 +38,16 @@ use common_users::UserApiProvider;
 use futures_util::StreamExt;
 use metrics::histogram;
 use opensrv_mysql::AsyncMysqlShim;
+use opensrv_mysql::Column;
+use opensrv_mysql::ColumnFlags;
+use opensrv_mysql::ColumnType;
 use opensrv_mysql::ErrorKind;
 use opensrv_mysql::InitWriter;
 use opensrv_mysql::ParamParser;
+use opensrv_mysql::ParamValue;
 use opensrv_mysql::QueryResultWriter;
 use opensrv_mysql::StatementMetaWriter;
+use opensrv_mysql::ValueInner;
 use rand::RngCore;
 use tracing::error;
 use tracing::info;
@@ -47,39 +56,19 @@ use tracing::Instrument;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterFactory;
 use crate::interpreters::InterpreterQueryLog;
+use crate::servers::mysql::writers::convert_schema;
 use crate::servers::mysql::writers::DFInitResultWriter;
 use crate::servers::mysql::writers::DFQueryResultWriter;
 use crate::servers::mysql::writers::ProgressReporter;
 use crate::servers::mysql::writers::QueryResult;
 use crate::servers::mysql::MySQLFederated;
 use crate::servers::mysql::MYSQL_VERSION;
+use crate::sessions::PreparedStatement;
 use crate::sessions::QueryContext;
 use crate::sessions::Session;
 use crate::sessions::TableContext;
 use crate::stream::DataBlockStream;
 
-fn has_result_set_by_plan(plan: &Plan) -> bool {
-    matches!(
-        plan,
-        Plan::Query { .. }
-            | Plan::Explain { .. }
-            | Plan::ExplainAst { .. }
-            | Plan::ExplainSyntax { .. }
-            | Plan::Call(_)
-            | Plan::ShowCreateDatabase(_)
-            | Plan::ShowCreateTable(_)
-            | Plan::ShowRoles(_)
-            | Plan::DescShare(_)
-            | Plan::ShowShares(_)
-            | Plan::ShowObjectGrantPrivileges(_)
-            | Plan::ShowGrantTenantsOfShare(_)
-            | Plan::DescribeTable(_)
-            | Plan::ShowGrants(_)
-            | Plan::ListStage(_)
-            | Plan::Presign(_)
-    )
-}
-
 struct InteractiveWorkerBase<W: AsyncWrite + Send + Unpin> {
     session: Arc<Session>,
     generic_hold: PhantomData<W>,
@@ -90,6 +79,7 @@ pub struct InteractiveWorker<W: AsyncWrite + Send + Unpin> {
     version: String,
     salt: [u8; 20],
     client_addr: String,
+    is_tls: bool,
 }
 
 #[async_trait::async_trait]
@@ -133,7 +123,7 @@ impl<W: AsyncWrite + Send + Sync + Unpin> AsyncMysqlShim<W> for InteractiveWorke
         let client_addr = self.client_addr.clone();
         let info = CertifiedInfo::create(&username, auth_data, &client_addr);
 
-        let authenticate = self.base.authenticate(salt, info);
+        let authenticate = self.base.authenticate(salt, info, self.is_tls);
         match authenticate.await {
             Ok(res) => res,
             Err(failure) => {
@@ -262,7 +252,7 @@ impl<W: AsyncWrite + Send + Sync + Unpin> AsyncMysqlShim<W> for InteractiveWorke
 }
 
 impl<W: AsyncWrite + Send + Unpin> InteractiveWorkerBase<W> {
-    async fn authenticate(&self, salt: &[u8], info: CertifiedInfo) -> Result<bool> {
+    async fn authenticate(&self, salt: &[u8], info: CertifiedInfo, is_tls: bool) -> Result<bool> {
         let user_name = &info.user_name;
         let client_ip = info.user_client_address.split(':').collect::<Vec<_>>()[0];
 
@@ -271,6 +261,13 @@ impl<W: AsyncWrite + Send + Unpin> InteractiveWorkerBase<W> {
             .get_user_with_client_ip(&ctx.get_tenant(), user_name, client_ip)
             .await?;
 
+        if !is_tls && user_info.has_option_flag(UserOptionFlag::RequireTls) {
+            return Err(ErrorCode::AuthenticateFailure(format!(
+                "User {} requires a TLS connection",
+                user_name
+            )));
+        }
+
         let authed = user_info.auth_info.auth_mysql(&info.user_password, salt)?;
         if authed {
             self.session.set_authed_user(user_info, None).await?;
@@ -278,32 +275,107 @@ impl<W: AsyncWrite + Send + Unpin> InteractiveWorkerBase<W> {
         Ok(authed)
     }
 
-    async fn do_prepare(&mut self, _: &str, writer: StatementMetaWriter<'_, W>) -> Result<()> {
-        writer
-            .error(
-                ErrorKind::ER_UNKNOWN_ERROR,
-                "Prepare is not support in Databend.".as_bytes(),
-            )
-            .await?;
+    async fn do_prepare(&mut self, query: &str, writer: StatementMetaWriter<'_, W>) -> Result<()> {
+        match self.prepare_statement(query).await {
+            Ok((id, params, columns)) => writer.reply(id, &params, &columns).await?,
+            Err(error) => {
+                error!("OnPrepare Error: {:?}", error);
+                writer
+                    .error(ErrorKind::ER_UNKNOWN_ERROR, error.to_string().as_bytes())
+                    .await?
+            }
+        }
         Ok(())
     }
 
+    async fn prepare_statement(&mut self, query: &str) -> Result<(u32, Vec<Column>, Vec<Column>)> {
+        let param_count = count_placeholders(query)?;
+        let schema = match self.federated_server_command_check(query) {
+            Some((schema, _)) => schema,
+            None => {
+                // Bind the placeholders with NULL to describe the result columns.
+                let context = self.session.create_query_context().await?;
+                context.attach_placeholders(vec![PlaceholderValue::Null; param_count]);
+                let mut planner = Planner::new(context);
+                let (plan, _, _) = planner.plan_sql(query).await?;
+                if plan.has_result_set() {
+                    plan.schema()
+                } else {
+                    Arc::new(DataSchema::empty())
+                }
+            }
+        };
+
+        let columns = convert_schema(&schema)?;
+        let params = (0..param_count)
+            .map(|_| Column {
+                table: "".to_string(),
+                column: "?".to_string(),
+                coltype: ColumnType::MYSQL_TYPE_VAR_STRING,
+                colflags: ColumnFlags::empty(),
+            })
+            .collect::<Vec<_>>();
+
+        let statement = PreparedStatement::create(query.to_string(), param_count);
+        let id = self.session.add_prepared_statement(statement);
+        Ok((id, params, columns))
+    }
+
     async fn do_execute(
         &mut self,
-        _: u32,
-        _: ParamParser<'_>,
+        id: u32,
+        params: ParamParser<'_>,
         writer: QueryResultWriter<'_, W>,
     ) -> Result<()> {
-        writer
-            .error(
-                ErrorKind::ER_UNKNOWN_ERROR,
-                "Execute is not support in Databend.".as_bytes(),
-            )
-            .await?;
-        Ok(())
+        let mut writer = DFQueryResultWriter::create_binary(writer);
+        let format = self.session.get_format_settings()?;
+        let (statement, params) = match self.bind_statement(id, params) {
+            Ok(bound) => bound,
+            Err(error) => return writer.write(Err(error), &format).await,
+        };
+
+        let instant = Instant::now();
+        let query_result = self.do_query_prepared(&statement, params).await;
+        let mut write_result = writer.write(query_result, &format).await;
+
+        if let Err(cause) = write_result {
+            let suffix = format!("(while in query {})", statement.query());
+            write_result = Err(cause.add_message_back(suffix));
+        }
+
+        histogram!(
+            super::mysql_metrics::METRIC_MYSQL_PROCESSOR_REQUEST_DURATION,
+            instant.elapsed()
+        );
+
+        write_result
     }
 
-    async fn do_close(&mut self, _: u32) {}
+    fn bind_statement(
+        &self,
+        id: u32,
+        params: ParamParser<'_>,
+    ) -> Result<(Arc<PreparedStatement>, Vec<PlaceholderValue>)> {
+        let statement = self.session.get_prepared_statement(id).ok_or_else(|| {
+            ErrorCode::BadArguments(format!("Unknown prepared statement handler ({})", id))
+        })?;
+        let params = params
+            .into_iter()
+            .map(convert_param)
+            .collect::<Result<Vec<_>>>()?;
+        if params.len() != statement.param_count() {
+            return Err(ErrorCode::BadArguments(format!(
+                "Prepared statement expects {} parameters, but got {}",
+                statement.param_count(),
+                params.len()
+            )));
+        }
+        Ok((statement, params))
+    }
+
+    async fn do_close(&mut self, id: u32) {
+        self.session.remove_prepared_statement(id);
+    }
 
     // Check the query is a federated or driver setup command.
     // Here we fake some values for the command which Databend not supported.
@@ -338,28 +410,52 @@ impl<W: AsyncWrite + Send + Unpin> InteractiveWorkerBase<W> {
 
                 let mut planner = Planner::new(context.clone());
                 let (plan, _, _) = planner.plan_sql(query).await?;
+                Self::exec_plan(context, &plan, query).await
+            }
+        }
+    }
 
-                context.attach_query_str(plan.to_string(), query);
-                let interpreter = InterpreterFactory::get(context.clone(), &plan).await;
-                let has_result_set = has_result_set_by_plan(&plan);
-
-                match interpreter {
-                    Ok(interpreter) => {
-                        let (blocks, extra_info) =
-                            Self::exec_query(interpreter.clone(), &context).await?;
-                        let schema = interpreter.schema();
-                        Ok(QueryResult::create(
-                            blocks,
-                            extra_info,
-                            has_result_set,
-                            schema,
-                        ))
-                    }
-                    Err(e) => {
-                        InterpreterQueryLog::fail_to_start(context, e.clone());
-                        Err(e)
-                    }
-                }
+    #[tracing::instrument(level = "debug", skip(self, statement, params))]
+    async fn do_query_prepared(
+        &mut self,
+        statement: &PreparedStatement,
+        params: Vec<PlaceholderValue>,
+    ) -> Result<QueryResult> {
+        let query = statement.query();
+        if self.federated_server_command_check(query).is_some() {
+            return self.do_query(query).await;
+        }
+
+        info!("Prepared query: {}", query);
+        let context = self.session.create_query_context().await?;
+        context.attach_placeholders(params);
+        let plan = statement.plan(context.clone()).await?;
+        Self::exec_plan(context, &plan, query).await
+    }
+
+    async fn exec_plan(
+        context: Arc<QueryContext>,
+        plan: &Plan,
+        query: &str,
+    ) -> Result<QueryResult> {
+        context.attach_query_str(plan.to_string(), query);
+        let interpreter = InterpreterFactory::get(context.clone(), plan).await;
+        let has_result_set = plan.has_result_set();
+
+        match interpreter {
+            Ok(interpreter) => {
+                let (blocks, extra_info) = Self::exec_query(interpreter.clone(), &context).await?;
+                let schema = interpreter.schema();
+                Ok(QueryResult::create(
+                    blocks,
+                    extra_info,
+                    has_result_set,
+                    schema,
+                ))
+            }
+            Err(e) => {
+                InterpreterQueryLog::fail_to_start(context, e.clone());
+                Err(e)
             }
         }
     }
@@ -441,8 +537,77 @@ impl<W: AsyncWrite + Send + Unpin> InteractiveWorker<W> {
             salt: scramble,
             version: format!("{}-{}", MYSQL_VERSION, *DATABEND_COMMIT_VERSION),
             client_addr,
+            is_tls: false,
         }
     }
+
+    pub fn set_tls(&mut self, is_tls: bool) {
+        self.is_tls = is_tls;
+    }
+}
+
+fn convert_param(param: ParamValue<'_>) -> Result<PlaceholderValue> {
+    match param.value.into_inner() {
+        ValueInner::NULL => Ok(PlaceholderValue::Null),
+        ValueInner::Int(v) => Ok(PlaceholderValue::Int(v)),
+        ValueInner::UInt(v) => Ok(PlaceholderValue::UInt(v)),
+        ValueInner::Double(v) => Ok(PlaceholderValue::Float(v)),
+        ValueInner::Bytes(v) => Ok(PlaceholderValue::String(String::from_utf8(v.to_vec())?)),
+        ValueInner::Date(v) => decode_binary_datetime(v, true),
+        ValueInner::Datetime(v) => decode_binary_datetime(v, false),
+        ValueInner::Time(v) => decode_binary_time(v).map(PlaceholderValue::String),
+    }
+}
+
+/// https://dev.mysql.com/doc/internals/en/binary-protocol-value.html
+/// year(2), month(1), day(1) [, hour(1), minute(1), second(1) [, microsecond(4)]]
+fn decode_binary_datetime(bytes: &[u8], is_date: bool) -> Result<PlaceholderValue> {
+    if !matches!(bytes.len(), 4 | 7 | 11) {
+        return Err(ErrorCode::BadBytes(format!(
+            "Invalid binary datetime parameter of {} bytes",
+            bytes.len()
+        )));
+    }
+
+    let year = u16::from_le_bytes([bytes[0], bytes[1]]);
+    let date = format!("{:04}-{:02}-{:02}", year, bytes[2], bytes[3]);
+    if is_date && bytes.len() == 4 {
+        return Ok(PlaceholderValue::Date(date));
+    }
+
+    let mut timestamp = match bytes.len() {
+        4 => format!("{} 00:00:00", date),
+        _ => format!("{} {:02}:{:02}:{:02}", date, bytes[4], bytes[5], bytes[6]),
+    };
+    if bytes.len() == 11 {
+        let micros = u32::from_le_bytes([bytes[7], bytes[8], bytes[9], bytes[10]]);
+        timestamp.push_str(&format!(".{:06}", micros));
+    }
+    Ok(PlaceholderValue::Timestamp(timestamp))
+}
+
+/// https://dev.mysql.com/doc/internals/en/binary-protocol-value.html
+/// is_negative(1), days(4), hour(1), minute(1), second(1) [, microsecond(4)]
+fn decode_binary_time(bytes: &[u8]) -> Result<String> {
+    if bytes.is_empty() {
+        return Ok("00:00:00".to_string());
+    }
+    if !matches!(bytes.len(), 8 | 12) {
+        return Err(ErrorCode::BadBytes(format!(
+            "Invalid binary time parameter of {} bytes",
+            bytes.len()
+        )));
+    }
+
+    let sign = if bytes[0] == 1 { "-" } else { "" };
+    let days = u32::from_le_bytes([bytes[1], bytes[2], bytes[3], bytes[4]]);
+    let hours = days * 24 + bytes[5] as u32;
+    let mut time = format!("{}{:02}:{:02}:{:02}", sign, hours, bytes[6], bytes[7]);
+    if bytes.len() == 12 {
+        let micros = u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]);
+        time.push_str(&format!(".{:06}", micros));
+    }
+    Ok(time)
 }
 
 struct ContextProgressReporter {
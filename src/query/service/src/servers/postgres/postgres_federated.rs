@@ -0,0 +1,293 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_config::DATABEND_COMMIT_VERSION;
+use common_expression::types::StringType;
+use common_expression::utils::FromData;
+use common_expression::DataBlock;
+use common_expression::DataSchema;
+use common_expression::DataSchemaRef;
+use common_expression::TableDataType;
+use common_expression::TableField;
+use common_expression::TableSchemaRef;
+use common_expression::TableSchemaRefExt;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::servers::federated_helper::FederatedHelper;
+use crate::servers::postgres::postgres_types::pg_type_len;
+use crate::servers::postgres::postgres_types::pg_type_oid_sql;
+use crate::servers::postgres::postgres_types::PG_TYPES;
+use crate::servers::postgres::POSTGRES_VERSION;
+use crate::servers::postgres::POSTGRES_VERSION_NUM;
+
+// The oid of the pg_catalog namespace and of its owner in PostgreSQL.
+const PG_CATALOG_NAMESPACE_OID: u32 = 11;
+const PG_CATALOG_OWNER_OID: u32 = 10;
+
+static CATALOG_TABLE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(pg_catalog\.)?(pg_type|pg_namespace|pg_class|pg_attribute)\b").unwrap()
+});
+
+static WITH_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)^WITH\s+").unwrap());
+
+pub struct PostgresFederated {
+    postgres_version: String,
+    databend_version: String,
+}
+
+impl PostgresFederated {
+    pub fn create() -> Self {
+        PostgresFederated {
+            postgres_version: POSTGRES_VERSION.to_string(),
+            databend_version: DATABEND_COMMIT_VERSION.to_string(),
+        }
+    }
+
+    // Build block with a single string column.
+    // Format:
+    // |name|
+    // |value|
+    fn single_value_block(name: &str, value: &str) -> Option<(TableSchemaRef, DataBlock)> {
+        let schema = TableSchemaRefExt::create(vec![TableField::new(name, TableDataType::String)]);
+        let block = DataBlock::new_from_columns(vec![StringType::from_data(vec![
+            value.as_bytes().to_vec(),
+        ])]);
+        Some((schema, block))
+    }
+
+    // Check SHOW variable, the values are what the drivers expect from a postgres server.
+    fn federated_show_variable_check(&self, query: &str) -> Option<(TableSchemaRef, DataBlock)> {
+        let rules: Vec<(&str, Option<(TableSchemaRef, DataBlock)>)> = vec![
+            (
+                "(?i)^(SHOW TRANSACTION ISOLATION LEVEL(.*))",
+                Self::single_value_block("transaction_isolation", "read committed"),
+            ),
+            (
+                "(?i)^(SHOW server_version_num(.*))",
+                Self::single_value_block("server_version_num", POSTGRES_VERSION_NUM),
+            ),
+            (
+                "(?i)^(SHOW server_version(.*))",
+                Self::single_value_block("server_version", &self.postgres_version),
+            ),
+            (
+                "(?i)^(SHOW standard_conforming_strings(.*))",
+                Self::single_value_block("standard_conforming_strings", "on"),
+            ),
+            (
+                "(?i)^(SHOW search_path(.*))",
+                Self::single_value_block("search_path", "\"$user\", public"),
+            ),
+            (
+                "(?i)^(SHOW client_encoding(.*))",
+                Self::single_value_block("client_encoding", "UTF8"),
+            ),
+            (
+                "(?i)^(SHOW DateStyle(.*))",
+                Self::single_value_block("DateStyle", "ISO, MDY"),
+            ),
+        ];
+        FederatedHelper::block_match_rule(query, rules)
+    }
+
+    // Check for SET, transaction and the catalog probes of the drivers and tools,
+    // this is the final check of the federated query.
+    fn federated_mixed_check(&self, query: &str) -> Option<(TableSchemaRef, DataBlock)> {
+        let rules: Vec<(&str, Option<(TableSchemaRef, DataBlock)>)> = vec![
+            (
+                r"(?i)^(SELECT (pg_catalog\.)?VERSION\(\s*\))",
+                Self::single_value_block(
+                    "version",
+                    &format!(
+                        "PostgreSQL {} (Databend Query {})",
+                        self.postgres_version, self.databend_version
+                    ),
+                ),
+            ),
+            (
+                r"(?i)^(SELECT (pg_catalog\.)?current_setting\('server_version_num'\))",
+                Self::single_value_block("current_setting", POSTGRES_VERSION_NUM),
+            ),
+            (
+                r"(?i)^(SELECT (pg_catalog\.)?current_setting\('server_version'\))",
+                Self::single_value_block("current_setting", &self.postgres_version),
+            ),
+            (
+                // pg_dump.
+                r"(?i)^(SELECT (pg_catalog\.)?set_config\((.*))",
+                Self::single_value_block("set_config", ""),
+            ),
+            // Txn.
+            ("(?i)^(BEGIN(.*))", None),
+            ("(?i)^(START TRANSACTION(.*))", None),
+            ("(?i)^(ROLLBACK(.*))", None),
+            ("(?i)^(COMMIT(.*))", None),
+            ("(?i)^(END(.*))", None),
+            ("(?i)^(ABORT(.*))", None),
+            ("(?i)^(DISCARD ALL)", None),
+            ("(?i)^(DEALLOCATE(.*))", None),
+            // Set, by psql, pgjdbc, npgsql and the Grafana datasource.
+            ("(?i)^(SET extra_float_digits(.*))", None),
+            ("(?i)^(SET application_name(.*))", None),
+            ("(?i)^(SET client_encoding(.*))", None),
+            ("(?i)^(SET client_min_messages(.*))", None),
+            ("(?i)^(SET DateStyle(.*))", None),
+            ("(?i)^(SET intervalstyle(.*))", None),
+            ("(?i)^(SET search_path(.*))", None),
+            ("(?i)^(SET statement_timeout(.*))", None),
+            ("(?i)^(SET lock_timeout(.*))", None),
+            ("(?i)^(SET idle_in_transaction_session_timeout(.*))", None),
+            ("(?i)^(SET standard_conforming_strings(.*))", None),
+            ("(?i)^(SET SESSION CHARACTERISTICS(.*))", None),
+            ("(?i)^(SET TRANSACTION(.*))", None),
+            ("(?i)^(SET LOCAL(.*))", None),
+            ("(?i)^(SET SESSION(.*))", None),
+            // Catalog probes Databend has nothing to answer with, they return nothing.
+            // pg_type, pg_namespace, pg_class and pg_attribute are answered by `catalog_query`.
+            ("(?i)^(SELECT(.*)FROM (pg_catalog\\.)?pg_proc(.*))", None),
+            (
+                "(?i)^(SELECT(.*)FROM (pg_catalog\\.)?pg_database(.*))",
+                None,
+            ),
+            (
+                "(?i)^(SELECT(.*)FROM (pg_catalog\\.)?pg_settings(.*))",
+                None,
+            ),
+            (
+                "(?i)^(SELECT(.*)FROM (pg_catalog\\.)?pg_extension(.*))",
+                None,
+            ),
+            ("(?i)^(SELECT(.*)FROM (pg_catalog\\.)?pg_roles(.*))", None),
+        ];
+
+        FederatedHelper::block_match_rule(query, rules)
+    }
+
+    // The pg_catalog tables the drivers and the BI tools read the schema from, as views over
+    // the system tables. The oids of the tables and the namespaces are the crc32 of the names,
+    // the oids of the types are the ones the handler sends in the row descriptions.
+    fn catalog_tables() -> Vec<(&'static str, String)> {
+        let pg_namespace = format!(
+            "SELECT CAST({0} AS INT UNSIGNED) AS oid, 'pg_catalog' AS nspname, \
+             CAST({1} AS INT UNSIGNED) AS nspowner \
+             UNION ALL \
+             SELECT crc32(name), name, CAST({1} AS INT UNSIGNED) FROM system.databases",
+            PG_CATALOG_NAMESPACE_OID, PG_CATALOG_OWNER_OID
+        );
+
+        let pg_class = format!(
+            "SELECT crc32(concat(database, '.', name)) AS oid, name AS relname, \
+             crc32(database) AS relnamespace, CAST(0 AS INT UNSIGNED) AS reltype, \
+             CAST({0} AS INT UNSIGNED) AS relowner, if(engine = 'VIEW', 'v', 'r') AS relkind, \
+             'p' AS relpersistence, false AS relhasindex, false AS relispartition \
+             FROM system.tables",
+            PG_CATALOG_OWNER_OID
+        );
+
+        let pg_attribute = format!(
+            "SELECT crc32(concat(database, '.', `table`)) AS attrelid, name AS attname, \
+             CAST({0} AS INT UNSIGNED) AS atttypid, \
+             CAST(row_number() OVER (PARTITION BY database, `table`) AS SMALLINT) AS attnum, \
+             CAST(-1 AS INT) AS atttypmod, is_nullable = 'NO' AS attnotnull, \
+             default_kind != '' AS atthasdef, false AS attisdropped \
+             FROM system.columns",
+            pg_type_oid_sql("type")
+        );
+
+        let pg_type = PG_TYPES
+            .iter()
+            .map(|(oid, name)| {
+                format!(
+                    "SELECT CAST({0} AS INT UNSIGNED) AS oid, '{1}' AS typname, \
+                     CAST({2} AS INT UNSIGNED) AS typnamespace, \
+                     CAST({3} AS INT UNSIGNED) AS typowner, CAST({4} AS SMALLINT) AS typlen, \
+                     'b' AS typtype, CAST(0 AS INT UNSIGNED) AS typrelid, \
+                     CAST(0 AS INT UNSIGNED) AS typelem, CAST(0 AS INT UNSIGNED) AS typbasetype, \
+                     false AS typnotnull, CAST(-1 AS INT) AS typtypmod",
+                    oid,
+                    name,
+                    PG_CATALOG_NAMESPACE_OID,
+                    PG_CATALOG_OWNER_OID,
+                    pg_type_len(*oid)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(" UNION ALL ");
+
+        vec![
+            ("pg_namespace", pg_namespace),
+            ("pg_class", pg_class),
+            ("pg_attribute", pg_attribute),
+            ("pg_type", pg_type),
+        ]
+    }
+
+    // Rewrite a query over pg_type, pg_namespace, pg_class or pg_attribute into a query over
+    // the system tables, the tables it reads are defined in a WITH clause put in front of it.
+    // Return None if the query reads none of them.
+    pub fn catalog_query(&self, query: &str) -> Option<String> {
+        let query = query.trim().trim_end_matches(';');
+        let head = query.get(..6).unwrap_or_default();
+        if !head.eq_ignore_ascii_case("SELECT") && !WITH_REGEX.is_match(query) {
+            return None;
+        }
+
+        let mut used = vec![];
+        for captures in CATALOG_TABLE_REGEX.captures_iter(query) {
+            let name = captures[2].to_lowercase();
+            if !used.contains(&name) {
+                used.push(name);
+            }
+        }
+        if used.is_empty() {
+            return None;
+        }
+
+        let ctes = Self::catalog_tables()
+            .into_iter()
+            .filter(|(name, _)| used.iter().any(|used| used == name))
+            .map(|(name, definition)| format!("{} AS ({})", name, definition))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let query = CATALOG_TABLE_REGEX.replace_all(query, |captures: &regex::Captures| {
+            captures[2].to_lowercase()
+        });
+
+        Some(match WITH_REGEX.find(&query) {
+            Some(with) => format!("WITH {}, {}", ctes, &query[with.end()..]),
+            None => format!("WITH {} {}", ctes, query),
+        })
+    }
+
+    // Check the query is a federated or driver setup command.
+    // Here we fake some values for the command which Databend not supported.
+    pub fn check(&self, query: &str) -> Option<(DataSchemaRef, DataBlock)> {
+        let query = query.trim();
+
+        // First to check the show variables.
+        let show_variable = self
+            .federated_show_variable_check(query)
+            .map(|(schema, chunk)| (Arc::new(DataSchema::from(schema)), chunk));
+        if show_variable.is_some() {
+            return show_variable;
+        }
+
+        // Last check.
+        self.federated_mixed_check(query)
+            .map(|(schema, chunk)| (Arc::new(DataSchema::from(schema)), chunk))
+    }
+}
@@ -0,0 +1,975 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_base::base::tokio::io::AsyncRead;
+use common_base::base::tokio::io::AsyncWrite;
+use common_base::runtime::TrySpawn;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_exception::ToErrorCode;
+use common_expression::DataBlock;
+use common_expression::DataSchema;
+use common_expression::DataSchemaRef;
+use common_expression::ScalarRef;
+use common_expression::SendableDataBlockStream;
+use common_formats::field_encoder::FieldEncoderRowBased;
+use common_formats::field_encoder::FieldEncoderValues;
+use common_io::prelude::FormatSettings;
+use common_meta_types::AuthInfo;
+use common_meta_types::PasswordHashMethod;
+use common_meta_types::ScramSha256Secret;
+use common_meta_types::UserOptionFlag;
+use common_sql::plans::Plan;
+use common_sql::Planner;
+use common_users::UserApiProvider;
+use futures_util::StreamExt;
+use rand::RngCore;
+use tracing::error;
+use tracing::info;
+use tracing::warn;
+use tracing::Instrument;
+
+use crate::interpreters::InterpreterFactory;
+use crate::interpreters::InterpreterQueryLog;
+use crate::servers::postgres::postgres_protocol::decode_password;
+use crate::servers::postgres::postgres_protocol::decode_sasl_initial_response;
+use crate::servers::postgres::postgres_protocol::BackendMessage;
+use crate::servers::postgres::postgres_protocol::FieldDescription;
+use crate::servers::postgres::postgres_protocol::FrontendMessage;
+use crate::servers::postgres::postgres_protocol::PostgresProtocol;
+use crate::servers::postgres::postgres_protocol::StartupMessage;
+use crate::servers::postgres::postgres_types::decode_binary_param;
+use crate::servers::postgres::postgres_types::encode_binary;
+use crate::servers::postgres::postgres_types::number_literal;
+use crate::servers::postgres::postgres_types::pg_type_is_numeric;
+use crate::servers::postgres::postgres_types::pg_type_len;
+use crate::servers::postgres::postgres_types::pg_type_oid;
+use crate::servers::postgres::postgres_types::quote_literal;
+use crate::servers::postgres::postgres_types::FORMAT_BINARY;
+use crate::servers::postgres::postgres_types::FORMAT_TEXT;
+use crate::servers::postgres::postgres_types::PG_TYPE_TEXT;
+use crate::servers::postgres::PostgresFederated;
+use crate::servers::postgres::POSTGRES_VERSION;
+use crate::sessions::QueryContext;
+use crate::sessions::Session;
+use crate::sessions::TableContext;
+use crate::stream::DataBlockStream;
+
+// The result rows are flushed to the client once this many bytes are buffered: 100KB.
+const DEFAULT_RESULT_SET_WRITE_BUFFER_SIZE: usize = 100 * 1024;
+
+const SQLSTATE_INVALID_AUTHORIZATION: &str = "28000";
+const SQLSTATE_INVALID_PASSWORD: &str = "28P01";
+const SQLSTATE_INVALID_CATALOG_NAME: &str = "3D000";
+const SQLSTATE_PROTOCOL_VIOLATION: &str = "08P01";
+const SQLSTATE_ADMIN_SHUTDOWN: &str = "57P01";
+const SQLSTATE_QUERY_CANCELED: &str = "57014";
+
+const SCRAM_SHA_256: &str = "SCRAM-SHA-256";
+const SCRAM_NONCE_LEN: usize = 18;
+
+struct PreparedStatement {
+    query: String,
+    param_types: Vec<i32>,
+}
+
+struct Portal {
+    query: String,
+    result_formats: Vec<i16>,
+}
+
+struct QueryResult {
+    schema: DataSchemaRef,
+    blocks: SendableDataBlockStream,
+    has_result_set: bool,
+    command: String,
+    context: Option<Arc<QueryContext>>,
+}
+
+pub struct InteractiveWorker {
+    session: Arc<Session>,
+    client_addr: String,
+    statements: HashMap<String, PreparedStatement>,
+    portals: HashMap<String, Portal>,
+    // After an error of the extended query protocol, the messages are discarded until Sync.
+    extended_query_failed: bool,
+}
+
+impl InteractiveWorker {
+    pub fn create(session: Arc<Session>, client_addr: String) -> InteractiveWorker {
+        InteractiveWorker {
+            session,
+            client_addr,
+            statements: HashMap::new(),
+            portals: HashMap::new(),
+            extended_query_failed: false,
+        }
+    }
+
+    pub async fn run<R, W>(mut self, protocol: &mut PostgresProtocol<R, W>) -> Result<()>
+    where
+        R: AsyncRead + Unpin + Send,
+        W: AsyncWrite + Unpin + Send,
+    {
+        if !self.startup(protocol).await? {
+            return Ok(());
+        }
+
+        while let Some(message) = protocol.read_message().await? {
+            if self.session.is_aborting() {
+                let cause = ErrorCode::AbortedSession(
+                    "Aborting this connection. because we are try aborting server.",
+                );
+                write_error(protocol, &cause, "");
+                protocol.flush().await?;
+                return Err(cause);
+            }
+
+            match message {
+                FrontendMessage::Query(query) => {
+                    self.on_query(protocol, &query).await?;
+                    protocol.write(BackendMessage::ReadyForQuery);
+                    protocol.flush().await?;
+                }
+                FrontendMessage::Sync => {
+                    self.extended_query_failed = false;
+                    protocol.write(BackendMessage::ReadyForQuery);
+                    protocol.flush().await?;
+                }
+                FrontendMessage::Flush => protocol.flush().await?,
+                FrontendMessage::Terminate => break,
+                FrontendMessage::Password(_) => {
+                    return Err(ErrorCode::BadBytes(
+                        "Unexpected postgres password message after the authentication",
+                    ));
+                }
+                message => {
+                    if self.extended_query_failed {
+                        continue;
+                    }
+
+                    if let Err(cause) = self.on_extended_query(protocol, message).await {
+                        self.extended_query_failed = true;
+                        write_error(protocol, &cause, "");
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Returns false if the connection should be closed.
+    async fn startup<R, W>(&mut self, protocol: &mut PostgresProtocol<R, W>) -> Result<bool>
+    where
+        R: AsyncRead + Unpin + Send,
+        W: AsyncWrite + Unpin + Send,
+    {
+        let params = loop {
+            match protocol.read_startup().await? {
+                // Encryption is not supported, the client goes on without it or disconnects.
+                StartupMessage::SslRequest | StartupMessage::GssEncRequest => {
+                    protocol.write_byte(b'N').await?
+                }
+                // The backend key data is never sent, so there is nothing to cancel.
+                StartupMessage::CancelRequest { .. } => return Ok(false),
+                StartupMessage::Startup { params } => break params,
+            }
+        };
+
+        let user_name = match params.get("user") {
+            Some(user_name) => user_name.clone(),
+            None => {
+                protocol.write(BackendMessage::ErrorResponse {
+                    code: SQLSTATE_INVALID_AUTHORIZATION,
+                    message: "no PostgreSQL user name specified in startup packet",
+                });
+                protocol.flush().await?;
+                return Ok(false);
+            }
+        };
+
+        if let Err(cause) = self.authenticate(protocol, &user_name).await {
+            error!(
+                "PostgreSQL handler authenticate failed, \
+                    user_name: {}, \
+                    client_address: {}, \
+                    failure_cause: {}",
+                user_name, self.client_addr, cause
+            );
+            protocol.write(BackendMessage::ErrorResponse {
+                code: SQLSTATE_INVALID_PASSWORD,
+                message: &format!("password authentication failed for user \"{}\"", user_name),
+            });
+            protocol.flush().await?;
+            return Ok(false);
+        }
+
+        if let Some(database) = params.get("database").filter(|db| !db.is_empty()) {
+            let init_query = format!("USE `{}`", database);
+            if let Err(cause) = self.do_query(&init_query).await {
+                protocol.write(BackendMessage::ErrorResponse {
+                    code: SQLSTATE_INVALID_CATALOG_NAME,
+                    message: &cause.message(),
+                });
+                protocol.flush().await?;
+                return Ok(false);
+            }
+        }
+
+        let format = self.session.get_format_settings()?;
+        protocol.write(BackendMessage::AuthenticationOk);
+        for (name, value) in [
+            ("server_version", POSTGRES_VERSION),
+            ("server_encoding", "UTF8"),
+            ("client_encoding", "UTF8"),
+            ("DateStyle", "ISO, MDY"),
+            ("TimeZone", format.timezone.name()),
+            ("integer_datetimes", "on"),
+            ("standard_conforming_strings", "on"),
+            (
+                "application_name",
+                params.get("application_name").map_or("", |v| v.as_str()),
+            ),
+        ] {
+            protocol.write(BackendMessage::ParameterStatus(name, value));
+        }
+        protocol.write(BackendMessage::ReadyForQuery);
+        protocol.flush().await?;
+        Ok(true)
+    }
+
+    async fn authenticate<R, W>(
+        &mut self,
+        protocol: &mut PostgresProtocol<R, W>,
+        user_name: &str,
+    ) -> Result<()>
+    where
+        R: AsyncRead + Unpin + Send,
+        W: AsyncWrite + Unpin + Send,
+    {
+        let client_ip = self.client_addr.split(':').collect::<Vec<_>>()[0];
+
+        let ctx = self.session.create_query_context().await?;
+        let user_info = UserApiProvider::instance()
+            .get_user_with_client_ip(&ctx.get_tenant(), user_name, client_ip)
+            .await?;
+
+        // The PostgreSQL handler does not support TLS yet.
+        if user_info.has_option_flag(UserOptionFlag::RequireTls) {
+            return Err(ErrorCode::AuthenticateFailure(format!(
+                "User {} requires a TLS connection",
+                user_name
+            )));
+        }
+
+        match &user_info.auth_info {
+            // The users without password are trusted like the postgres `trust` method.
+            AuthInfo::None => {}
+            AuthInfo::Password {
+                hash_value,
+                hash_method: PasswordHashMethod::ScramSha256,
+            } => {
+                let secret = ScramSha256Secret::parse(hash_value)?;
+                authenticate_scram(protocol, &secret).await?;
+            }
+            // Only the hashes of the other passwords are stored, the client sends the
+            // cleartext password to check against them.
+            auth_info => {
+                protocol.write(BackendMessage::AuthenticationCleartextPassword);
+                protocol.flush().await?;
+
+                let password = decode_password(&read_password_message(protocol).await?);
+                if !auth_info.auth_postgres(&password)? {
+                    return Err(ErrorCode::AuthenticateFailure("wrong password"));
+                }
+            }
+        }
+
+        self.session.set_authed_user(user_info, None).await
+    }
+
+    async fn on_query<R, W>(
+        &mut self,
+        protocol: &mut PostgresProtocol<R, W>,
+        query: &str,
+    ) -> Result<()>
+    where
+        R: AsyncRead + Unpin + Send,
+        W: AsyncWrite + Unpin + Send,
+    {
+        let statements = split_statements(query);
+        if statements.is_empty() {
+            protocol.write(BackendMessage::EmptyQueryResponse);
+            return Ok(());
+        }
+
+        for statement in statements {
+            let result = match self.do_query(statement).await {
+                Ok(result) => result,
+                Err(cause) => {
+                    write_error(protocol, &cause, statement);
+                    return Ok(());
+                }
+            };
+
+            if result.has_result_set {
+                protocol.write(BackendMessage::RowDescription(&field_descriptions(
+                    &result.schema,
+                    &[],
+                )));
+            }
+
+            // Errors in the result stream end the simple query, the connection goes on.
+            let format = self.session.get_format_settings()?;
+            if let Err(cause) = write_result(protocol, result, &[], &format).await {
+                write_error(protocol, &cause, statement);
+                return Ok(());
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn on_extended_query<R, W>(
+        &mut self,
+        protocol: &mut PostgresProtocol<R, W>,
+        message: FrontendMessage,
+    ) -> Result<()>
+    where
+        R: AsyncRead + Unpin + Send,
+        W: AsyncWrite + Unpin + Send,
+    {
+        match message {
+            FrontendMessage::Parse {
+                name,
+                query,
+                mut param_types,
+            } => {
+                let num_params = max_placeholder(&query);
+                if param_types.len() < num_params {
+                    param_types.resize(num_params, 0);
+                }
+                self.statements
+                    .insert(name, PreparedStatement { query, param_types });
+                protocol.write(BackendMessage::ParseComplete);
+            }
+            FrontendMessage::Bind {
+                portal,
+                statement,
+                param_formats,
+                params,
+                result_formats,
+            } => {
+                let statement = self.get_statement(&statement)?;
+                let query = bind_params(statement, &param_formats, &params)?;
+                self.portals.insert(portal, Portal {
+                    query,
+                    result_formats,
+                });
+                protocol.write(BackendMessage::BindComplete);
+            }
+            FrontendMessage::Describe { kind: b'S', name } => {
+                let statement = self.get_statement(&name)?;
+                let param_types = statement
+                    .param_types
+                    .iter()
+                    .map(|ty| if *ty == 0 { PG_TYPE_TEXT } else { *ty })
+                    .collect::<Vec<_>>();
+                // The parameters are unknown until bound, describe with nulls instead.
+                let nulls = vec![None; param_types.len()];
+                let query = bind_params(statement, &[], &nulls)?;
+
+                protocol.write(BackendMessage::ParameterDescription(&param_types));
+                match self.describe_query(&query).await? {
+                    Some(schema) => protocol.write(BackendMessage::RowDescription(
+                        &field_descriptions(&schema, &[]),
+                    )),
+                    None => protocol.write(BackendMessage::NoData),
+                }
+            }
+            FrontendMessage::Describe { kind: _, name } => {
+                let portal = self.get_portal(&name)?;
+                let result_formats = portal.result_formats.clone();
+                let query = portal.query.clone();
+                match self.describe_query(&query).await? {
+                    Some(schema) => protocol.write(BackendMessage::RowDescription(
+                        &field_descriptions(&schema, &result_formats),
+                    )),
+                    None => protocol.write(BackendMessage::NoData),
+                }
+            }
+            FrontendMessage::Execute {
+                portal,
+                max_rows: _,
+            } => {
+                // All the rows are sent at once, a portal is never suspended.
+                let portal = self.get_portal(&portal)?;
+                let result_formats = portal.result_formats.clone();
+                let query = portal.query.clone();
+                if query.trim().is_empty() {
+                    protocol.write(BackendMessage::EmptyQueryResponse);
+                    return Ok(());
+                }
+
+                let result = self.do_query(&query).await?;
+                let format = self.session.get_format_settings()?;
+                write_result(protocol, result, &result_formats, &format).await?;
+            }
+            FrontendMessage::Close { kind, name } => {
+                if kind == b'S' {
+                    self.statements.remove(&name);
+                } else {
+                    self.portals.remove(&name);
+                }
+                protocol.write(BackendMessage::CloseComplete);
+            }
+            _ => unreachable!("not a message of the extended query protocol"),
+        }
+
+        Ok(())
+    }
+
+    fn get_statement(&self, name: &str) -> Result<&PreparedStatement> {
+        self.statements.get(name).ok_or_else(|| {
+            ErrorCode::BadArguments(format!("prepared statement \"{}\" does not exist", name))
+        })
+    }
+
+    fn get_portal(&self, name: &str) -> Result<&Portal> {
+        self.portals
+            .get(name)
+            .ok_or_else(|| ErrorCode::BadArguments(format!("portal \"{}\" does not exist", name)))
+    }
+
+    // Check the query is a federated or driver setup command.
+    // Here we fake some values for the command which Databend not supported.
+    fn federated_server_command_check(&self, query: &str) -> Option<(DataSchemaRef, DataBlock)> {
+        // INSERT don't need postgres federated check
+        if query.len() > 6 && query[..6].eq_ignore_ascii_case("INSERT") {
+            return None;
+        }
+        let federated = PostgresFederated::create();
+        federated.check(query)
+    }
+
+    // The schema of the result set, None if the query has no result set.
+    async fn describe_query(&self, query: &str) -> Result<Option<DataSchemaRef>> {
+        if let Some((schema, _)) = self.federated_server_command_check(query) {
+            return Ok(Some(schema).filter(|schema| !schema.fields().is_empty()));
+        }
+
+        // The catalog probes are described as nothing if the rewritten query can not be planned.
+        let catalog_query = PostgresFederated::create().catalog_query(query);
+        let context = self.session.create_query_context().await?;
+        let mut planner = Planner::new(context.clone());
+        let planned = planner
+            .plan_sql(catalog_query.as_deref().unwrap_or(query))
+            .await;
+        let (plan, _, _) = match planned {
+            Err(cause) if catalog_query.is_some() => {
+                warn!("Cannot plan the catalog query {}: {:?}", query, cause);
+                return Ok(None);
+            }
+            planned => planned?,
+        };
+        Ok(Some(plan.schema()).filter(|_| plan.has_result_set()))
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn do_query(&self, query: &str) -> Result<QueryResult> {
+        match self.federated_server_command_check(query) {
+            Some((schema, data_block)) => {
+                info!("Federated query: {}", query);
+                if data_block.num_rows() > 0 {
+                    info!("Federated response: {:?}", data_block);
+                }
+                Ok(Self::federated_result(query, schema, data_block))
+            }
+            None => match PostgresFederated::create().catalog_query(query) {
+                // The catalog probes return nothing if the rewritten query can not be run.
+                Some(catalog_query) => {
+                    info!("Catalog query: {}", query);
+                    match self.execute_query(&catalog_query).await {
+                        Ok(result) => Ok(result),
+                        Err(cause) => {
+                            warn!("Cannot run the catalog query {}: {:?}", query, cause);
+                            let schema = Arc::new(DataSchema::empty());
+                            Ok(Self::federated_result(query, schema, DataBlock::empty()))
+                        }
+                    }
+                }
+                None => {
+                    info!("Normal query: {}", query);
+                    self.execute_query(query).await
+                }
+            },
+        }
+    }
+
+    fn federated_result(query: &str, schema: DataSchemaRef, data_block: DataBlock) -> QueryResult {
+        QueryResult {
+            has_result_set: !schema.fields().is_empty(),
+            schema,
+            blocks: DataBlockStream::create(None, vec![data_block]).boxed(),
+            command: command_of_query(query),
+            context: None,
+        }
+    }
+
+    async fn execute_query(&self, query: &str) -> Result<QueryResult> {
+        let context = self.session.create_query_context().await?;
+
+        let mut planner = Planner::new(context.clone());
+        let (plan, _, _) = planner.plan_sql(query).await?;
+
+        context.attach_query_str(plan.to_string(), query);
+        let interpreter = match InterpreterFactory::get(context.clone(), &plan).await {
+            Ok(interpreter) => interpreter,
+            Err(e) => {
+                InterpreterQueryLog::fail_to_start(context, e.clone());
+                return Err(e);
+            }
+        };
+
+        let query_result = context.try_spawn({
+            let ctx = context.clone();
+            let interpreter = interpreter.clone();
+            async move { interpreter.execute(ctx).await }.in_current_span()
+        })?;
+        let blocks = query_result.await.map_err_to_code(
+            ErrorCode::TokioError,
+            || "Cannot join handle from context's runtime",
+        )??;
+
+        Ok(QueryResult {
+            schema: interpreter.schema(),
+            blocks,
+            has_result_set: plan.has_result_set(),
+            command: command_of_plan(&plan, query),
+            context: Some(context),
+        })
+    }
+}
+
+/// Sends the rows and the command completion of the result, the row description is
+/// sent by the caller.
+async fn write_result<R, W>(
+    protocol: &mut PostgresProtocol<R, W>,
+    mut result: QueryResult,
+    result_formats: &[i16],
+    format: &FormatSettings,
+) -> Result<()>
+where
+    R: AsyncRead + Unpin + Send,
+    W: AsyncWrite + Unpin + Send,
+{
+    let encoder = FieldEncoderValues::create_for_postgres_handler(format.timezone);
+
+    let mut num_rows = 0;
+    let mut buf = Vec::new();
+    let mut ranges = Vec::new();
+    while let Some(block) = result.blocks.next().await {
+        let block = block?;
+        if !result.has_result_set {
+            // For statements without result sets, we still need to pull the stream
+            // because errors may occur in the stream.
+            continue;
+        }
+
+        let columns = block
+            .convert_to_full()
+            .columns()
+            .iter()
+            .map(|column| column.value.clone().into_column().unwrap())
+            .collect::<Vec<_>>();
+
+        for row_index in 0..block.num_rows() {
+            buf.clear();
+            ranges.clear();
+            for (col_index, column) in columns.iter().enumerate() {
+                let start = buf.len();
+                match unsafe { column.index_unchecked(row_index) } {
+                    ScalarRef::Null => {
+                        ranges.push(None);
+                        continue;
+                    }
+                    value if result_format(result_formats, col_index) == FORMAT_BINARY => {
+                        encode_binary(value, &mut buf)?
+                    }
+                    _ => encoder.write_field(column, row_index, &mut buf, true),
+                }
+                ranges.push(Some(start..buf.len()));
+            }
+
+            let values = ranges
+                .iter()
+                .map(|range| range.clone().map(|range| &buf[range]))
+                .collect::<Vec<_>>();
+            protocol.write(BackendMessage::DataRow(&values));
+            num_rows += 1;
+
+            if protocol.buffered_bytes() >= DEFAULT_RESULT_SET_WRITE_BUFFER_SIZE {
+                protocol.flush().await?;
+            }
+        }
+    }
+
+    let tag = match result.command.as_str() {
+        _ if result.has_result_set => format!("SELECT {}", num_rows),
+        "INSERT" => format!(
+            "INSERT 0 {}",
+            result
+                .context
+                .as_ref()
+                .map_or(0, |context| context.get_write_progress_value().rows)
+        ),
+        "UPDATE" | "DELETE" => format!("{} 0", result.command),
+        command => command.to_string(),
+    };
+    protocol.write(BackendMessage::CommandComplete(&tag));
+    Ok(())
+}
+
+async fn read_password_message<R, W>(protocol: &mut PostgresProtocol<R, W>) -> Result<Vec<u8>>
+where
+    R: AsyncRead + Unpin + Send,
+    W: AsyncWrite + Unpin + Send,
+{
+    match protocol.read_message().await? {
+        Some(FrontendMessage::Password(body)) => Ok(body),
+        _ => Err(ErrorCode::AuthenticateFailure(
+            "expected a password message",
+        )),
+    }
+}
+
+// The SASL exchange of SCRAM-SHA-256 (RFC 5802, RFC 7677). Channel binding is not supported,
+// the connections are never encrypted.
+async fn authenticate_scram<R, W>(
+    protocol: &mut PostgresProtocol<R, W>,
+    secret: &ScramSha256Secret,
+) -> Result<()>
+where
+    R: AsyncRead + Unpin + Send,
+    W: AsyncWrite + Unpin + Send,
+{
+    protocol.write(BackendMessage::AuthenticationSasl(&[SCRAM_SHA_256]));
+    protocol.flush().await?;
+
+    let (mechanism, client_first) =
+        decode_sasl_initial_response(&read_password_message(protocol).await?)?;
+    if mechanism != SCRAM_SHA_256 {
+        return Err(ErrorCode::AuthenticateFailure(format!(
+            "unsupported SASL mechanism {}",
+            mechanism
+        )));
+    }
+
+    // The GS2 header is followed by the bare message, the user name in it is ignored like
+    // postgres does, the one of the startup message is used.
+    let client_first = String::from_utf8(client_first)?;
+    let gs2_header = client_first
+        .get(..3)
+        .filter(|header| *header == "n,," || *header == "y,,")
+        .ok_or_else(|| ErrorCode::AuthenticateFailure("SCRAM channel binding is not supported"))?;
+    let client_first_bare = &client_first[3..];
+
+    let mut server_nonce = vec![0; SCRAM_NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut server_nonce);
+    let nonce = format!(
+        "{}{}",
+        scram_attribute(client_first_bare, "r")?,
+        base64::encode(server_nonce)
+    );
+    let server_first = format!(
+        "r={},s={},i={}",
+        nonce,
+        base64::encode(&secret.salt),
+        secret.iterations
+    );
+    protocol.write(BackendMessage::AuthenticationSaslContinue(
+        server_first.as_bytes(),
+    ));
+    protocol.flush().await?;
+
+    let client_final = String::from_utf8(read_password_message(protocol).await?)?;
+    let (client_final_without_proof, proof) = client_final
+        .rsplit_once(",p=")
+        .ok_or_else(|| ErrorCode::AuthenticateFailure("missing SCRAM client proof"))?;
+    if scram_attribute(client_final_without_proof, "c")? != base64::encode(gs2_header)
+        || scram_attribute(client_final_without_proof, "r")? != nonce
+    {
+        return Err(ErrorCode::AuthenticateFailure(
+            "SCRAM channel binding or nonce mismatch",
+        ));
+    }
+
+    let proof = base64::decode(proof)
+        .map_err(|_| ErrorCode::AuthenticateFailure("invalid SCRAM client proof"))?;
+    let auth_message = format!(
+        "{},{},{}",
+        client_first_bare, server_first, client_final_without_proof
+    );
+    if !secret.verify_client_proof(auth_message.as_bytes(), &proof) {
+        return Err(ErrorCode::AuthenticateFailure("wrong password"));
+    }
+
+    let server_final = format!(
+        "v={}",
+        base64::encode(secret.server_signature(auth_message.as_bytes()))
+    );
+    protocol.write(BackendMessage::AuthenticationSaslFinal(
+        server_final.as_bytes(),
+    ));
+    Ok(())
+}
+
+fn scram_attribute<'a>(message: &'a str, name: &str) -> Result<&'a str> {
+    message
+        .split(',')
+        .find_map(|attribute| attribute.strip_prefix(name)?.strip_prefix('='))
+        .ok_or_else(|| ErrorCode::AuthenticateFailure(format!("missing SCRAM attribute {}", name)))
+}
+
+fn write_error<R, W>(protocol: &mut PostgresProtocol<R, W>, cause: &ErrorCode, query: &str)
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let code = match cause.code() {
+        ErrorCode::ABORTED_SESSION => SQLSTATE_ADMIN_SHUTDOWN,
+        ErrorCode::ABORTED_QUERY => SQLSTATE_QUERY_CANCELED,
+        code => sqlstate_of_error(code),
+    };
+    if code != SQLSTATE_ADMIN_SHUTDOWN && code != SQLSTATE_QUERY_CANCELED {
+        error!("OnQuery Error: {:?} (while in query {})", cause, query);
+    }
+
+    protocol.write(BackendMessage::ErrorResponse {
+        code,
+        message: &cause.message(),
+    });
+}
+
+fn sqlstate_of_error(code: u16) -> &'static str {
+    match code {
+        ErrorCode::SYNTAX_EXCEPTION => "42601",
+        ErrorCode::SEMANTIC_ERROR => "42000",
+        ErrorCode::UNKNOWN_DATABASE => SQLSTATE_INVALID_CATALOG_NAME,
+        ErrorCode::UNKNOWN_TABLE => "42P01",
+        ErrorCode::UNKNOWN_COLUMN => "42703",
+        ErrorCode::UNKNOWN_FUNCTION => "42883",
+        ErrorCode::DATABASE_ALREADY_EXISTS => "42P04",
+        ErrorCode::TABLE_ALREADY_EXISTS => "42P07",
+        ErrorCode::PERMISSION_DENIED => "42501",
+        ErrorCode::AUTHENTICATE_FAILURE => SQLSTATE_INVALID_PASSWORD,
+        ErrorCode::UNIMPLEMENTED => "0A000",
+        ErrorCode::BAD_BYTES => SQLSTATE_PROTOCOL_VIOLATION,
+        _ => "XX000",
+    }
+}
+
+fn field_descriptions(schema: &DataSchemaRef, result_formats: &[i16]) -> Vec<FieldDescription> {
+    schema
+        .fields()
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let type_oid = pg_type_oid(field.data_type());
+            FieldDescription {
+                name: field.name().to_string(),
+                type_oid,
+                type_len: pg_type_len(type_oid),
+                format: result_format(result_formats, index),
+            }
+        })
+        .collect()
+}
+
+// No format means text for all, a single format applies to all.
+fn result_format(formats: &[i16], index: usize) -> i16 {
+    match formats.len() {
+        0 => FORMAT_TEXT,
+        1 => formats[0],
+        _ => formats.get(index).copied().unwrap_or(FORMAT_TEXT),
+    }
+}
+
+/// Puts the parameters into the query as SQL literals.
+fn bind_params(
+    statement: &PreparedStatement,
+    param_formats: &[i16],
+    params: &[Option<Vec<u8>>],
+) -> Result<String> {
+    if params.len() != statement.param_types.len() {
+        return Err(ErrorCode::BadArguments(format!(
+            "bind message supplies {} parameters, but prepared statement requires {}",
+            params.len(),
+            statement.param_types.len()
+        )));
+    }
+
+    let literals = params
+        .iter()
+        .enumerate()
+        .map(|(index, param)| {
+            let type_oid = statement.param_types[index];
+            match param {
+                None => Ok("NULL".to_string()),
+                Some(value) if result_format(param_formats, index) == FORMAT_BINARY => {
+                    decode_binary_param(type_oid, value)
+                }
+                Some(value) => {
+                    let value = String::from_utf8(value.clone())?;
+                    if pg_type_is_numeric(type_oid) {
+                        number_literal(&value)
+                    } else {
+                        Ok(quote_literal(&value))
+                    }
+                }
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut query = String::with_capacity(statement.query.len());
+    let mut last = 0;
+    for (start, end, index) in placeholders(&statement.query) {
+        query.push_str(&statement.query[last..start]);
+        query.push_str(&literals[index - 1]);
+        last = end;
+    }
+    query.push_str(&statement.query[last..]);
+    Ok(query)
+}
+
+// The command tag of the statements without result set.
+fn command_of_plan(plan: &Plan, query: &str) -> String {
+    match plan {
+        Plan::Insert(_) => "INSERT".to_string(),
+        Plan::Update(_) => "UPDATE".to_string(),
+        Plan::Delete(_) => "DELETE".to_string(),
+        _ => command_of_query(query),
+    }
+}
+
+// The leading keywords of the query, e.g. `CREATE TABLE`.
+fn command_of_query(query: &str) -> String {
+    let words = query
+        .split_whitespace()
+        .map(|word| word.to_uppercase())
+        .collect::<Vec<_>>();
+    match words.first().map(|word| word.as_str()) {
+        Some("CREATE" | "DROP" | "ALTER") => words[..words.len().min(2)].join(" "),
+        Some(word) => word.trim_end_matches(';').to_string(),
+        None => String::new(),
+    }
+}
+
+// The (byte index, char) outside of the quotes and comments of the query.
+fn unquoted_chars(query: &str) -> Vec<(usize, char)> {
+    let mut result = vec![];
+    let mut chars = query.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\'' | '"' | '`' => {
+                while let Some((_, next)) = chars.next() {
+                    if next == '\\' {
+                        chars.next();
+                    } else if next == c {
+                        // The doubled quote is an escaped quote.
+                        if chars.peek().map(|(_, c)| *c) != Some(c) {
+                            break;
+                        }
+                        chars.next();
+                    }
+                }
+            }
+            '-' if chars.peek().map(|(_, c)| *c) == Some('-') => {
+                for (_, next) in chars.by_ref() {
+                    if next == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek().map(|(_, c)| *c) == Some('*') => {
+                chars.next();
+                let mut prev = ' ';
+                for (_, next) in chars.by_ref() {
+                    if prev == '*' && next == '/' {
+                        break;
+                    }
+                    prev = next;
+                }
+            }
+            c => result.push((index, c)),
+        }
+    }
+    result
+}
+
+/// Splits the statements of a simple query, the empty statements are skipped.
+fn split_statements(query: &str) -> Vec<&str> {
+    let mut statements = vec![];
+    let mut start = 0;
+    for (index, c) in unquoted_chars(query) {
+        if c == ';' {
+            statements.push(&query[start..index]);
+            start = index + 1;
+        }
+    }
+    statements.push(&query[start..]);
+    statements
+        .into_iter()
+        .map(|statement| statement.trim())
+        .filter(|statement| !statement.is_empty())
+        .collect()
+}
+
+// The (start, end, number) of the placeholders `$1`, `$2`... of the query.
+fn placeholders(query: &str) -> Vec<(usize, usize, usize)> {
+    let chars = unquoted_chars(query);
+    let mut result = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let (start, c) = chars[i];
+        i += 1;
+        if c != '$' {
+            continue;
+        }
+
+        let mut end = start + 1;
+        while i < chars.len() && chars[i].0 == end && chars[i].1.is_ascii_digit() {
+            end += 1;
+            i += 1;
+        }
+        if let Ok(number) = query[start + 1..end].parse::<usize>() {
+            if number > 0 {
+                result.push((start, end, number));
+            }
+        }
+    }
+    result
+}
+
+fn max_placeholder(query: &str) -> usize {
+    placeholders(query)
+        .into_iter()
+        .map(|(_, _, number)| number)
+        .max()
+        .unwrap_or(0)
+}
@@ -0,0 +1,480 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_base::base::tokio::io::AsyncRead;
+use common_base::base::tokio::io::AsyncReadExt;
+use common_base::base::tokio::io::AsyncWrite;
+use common_base::base::tokio::io::AsyncWriteExt;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+// The frontend messages larger than this are rejected, same as the postgres server.
+const MAX_MESSAGE_LENGTH: usize = 1024 * 1024 * 1024;
+
+const PROTOCOL_VERSION_3: i32 = 196608;
+const CANCEL_REQUEST_CODE: i32 = 80877102;
+const SSL_REQUEST_CODE: i32 = 80877103;
+const GSSENC_REQUEST_CODE: i32 = 80877104;
+
+/// The first message of a connection, it has no message type byte.
+pub enum StartupMessage {
+    Startup { params: HashMap<String, String> },
+    SslRequest,
+    GssEncRequest,
+    CancelRequest { process_id: i32, secret_key: i32 },
+}
+
+pub enum FrontendMessage {
+    Query(String),
+    Parse {
+        name: String,
+        query: String,
+        param_types: Vec<i32>,
+    },
+    Bind {
+        portal: String,
+        statement: String,
+        param_formats: Vec<i16>,
+        params: Vec<Option<Vec<u8>>>,
+        result_formats: Vec<i16>,
+    },
+    Describe {
+        kind: u8,
+        name: String,
+    },
+    Execute {
+        portal: String,
+        max_rows: i32,
+    },
+    Close {
+        kind: u8,
+        name: String,
+    },
+    /// The body of a PasswordMessage, SASLInitialResponse or SASLResponse, which share the
+    /// message type, the kind depends on the step of the authentication.
+    Password(Vec<u8>),
+    Sync,
+    Flush,
+    Terminate,
+}
+
+pub struct FieldDescription {
+    pub name: String,
+    pub type_oid: i32,
+    pub type_len: i16,
+    pub format: i16,
+}
+
+pub enum BackendMessage<'a> {
+    AuthenticationOk,
+    AuthenticationCleartextPassword,
+    AuthenticationSasl(&'a [&'a str]),
+    AuthenticationSaslContinue(&'a [u8]),
+    AuthenticationSaslFinal(&'a [u8]),
+    ParameterStatus(&'a str, &'a str),
+    BackendKeyData { process_id: i32, secret_key: i32 },
+    ReadyForQuery,
+    RowDescription(&'a [FieldDescription]),
+    DataRow(&'a [Option<&'a [u8]>]),
+    CommandComplete(&'a str),
+    EmptyQueryResponse,
+    ParseComplete,
+    BindComplete,
+    CloseComplete,
+    NoData,
+    PortalSuspended,
+    ParameterDescription(&'a [i32]),
+    ErrorResponse { code: &'a str, message: &'a str },
+}
+
+/// Reads the frontend messages and buffers the backend messages of a connection,
+/// the buffered messages are sent by `flush`.
+pub struct PostgresProtocol<R, W> {
+    reader: R,
+    writer: W,
+    buf: Vec<u8>,
+}
+
+impl<R: AsyncRead + Unpin, W: AsyncWrite + Unpin> PostgresProtocol<R, W> {
+    pub fn create(reader: R, writer: W) -> Self {
+        PostgresProtocol {
+            reader,
+            writer,
+            buf: Vec::new(),
+        }
+    }
+
+    pub async fn read_startup(&mut self) -> Result<StartupMessage> {
+        let len = self.read_length().await?;
+        if len < 4 {
+            return Err(ErrorCode::BadBytes(
+                "Invalid postgres startup packet length",
+            ));
+        }
+
+        let mut body = vec![0; len - 4];
+        self.reader.read_exact(&mut body).await?;
+        let mut body = Body::new(&body);
+
+        match body.i32()? {
+            SSL_REQUEST_CODE => Ok(StartupMessage::SslRequest),
+            GSSENC_REQUEST_CODE => Ok(StartupMessage::GssEncRequest),
+            CANCEL_REQUEST_CODE => Ok(StartupMessage::CancelRequest {
+                process_id: body.i32()?,
+                secret_key: body.i32()?,
+            }),
+            PROTOCOL_VERSION_3 => {
+                let mut params = HashMap::new();
+                loop {
+                    let name = body.cstr()?;
+                    if name.is_empty() {
+                        break;
+                    }
+                    params.insert(name, body.cstr()?);
+                }
+                Ok(StartupMessage::Startup { params })
+            }
+            version => Err(ErrorCode::BadBytes(format!(
+                "Unsupported postgres protocol version {}.{}",
+                version >> 16,
+                version & 0xffff
+            ))),
+        }
+    }
+
+    /// Returns None if the client closed the connection.
+    pub async fn read_message(&mut self) -> Result<Option<FrontendMessage>> {
+        let tag = match self.reader.read_u8().await {
+            Ok(tag) => tag,
+            Err(cause) if cause.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(cause) => return Err(cause.into()),
+        };
+
+        let len = self.read_length().await?;
+        if len < 4 {
+            return Err(ErrorCode::BadBytes("Invalid postgres message length"));
+        }
+        let mut body = vec![0; len - 4];
+        self.reader.read_exact(&mut body).await?;
+        let mut body = Body::new(&body);
+
+        let message = match tag {
+            b'Q' => FrontendMessage::Query(body.cstr()?),
+            b'P' => {
+                let name = body.cstr()?;
+                let query = body.cstr()?;
+                let param_types = (0..body.i16()?)
+                    .map(|_| body.i32())
+                    .collect::<Result<Vec<_>>>()?;
+                FrontendMessage::Parse {
+                    name,
+                    query,
+                    param_types,
+                }
+            }
+            b'B' => {
+                let portal = body.cstr()?;
+                let statement = body.cstr()?;
+                let param_formats = (0..body.i16()?)
+                    .map(|_| body.i16())
+                    .collect::<Result<Vec<_>>>()?;
+                let params = (0..body.i16()?)
+                    .map(|_| match body.i32()? {
+                        len if len < 0 => Ok(None),
+                        len => body.bytes(len as usize).map(|v| Some(v.to_vec())),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                let result_formats = (0..body.i16()?)
+                    .map(|_| body.i16())
+                    .collect::<Result<Vec<_>>>()?;
+                FrontendMessage::Bind {
+                    portal,
+                    statement,
+                    param_formats,
+                    params,
+                    result_formats,
+                }
+            }
+            b'D' => FrontendMessage::Describe {
+                kind: body.u8()?,
+                name: body.cstr()?,
+            },
+            b'E' => FrontendMessage::Execute {
+                portal: body.cstr()?,
+                max_rows: body.i32()?,
+            },
+            b'C' => FrontendMessage::Close {
+                kind: body.u8()?,
+                name: body.cstr()?,
+            },
+            b'p' => FrontendMessage::Password(body.rest().to_vec()),
+            b'S' => FrontendMessage::Sync,
+            b'H' => FrontendMessage::Flush,
+            b'X' => FrontendMessage::Terminate,
+            other => {
+                return Err(ErrorCode::BadBytes(format!(
+                    "Unsupported postgres message type '{}'",
+                    other as char
+                )));
+            }
+        };
+
+        Ok(Some(message))
+    }
+
+    /// Answers the SSL and GSSAPI encryption requests, the single byte has no message frame.
+    pub async fn write_byte(&mut self, byte: u8) -> Result<()> {
+        self.writer.write_all(&[byte]).await?;
+        self.writer.flush().await?;
+        Ok(())
+    }
+
+    pub fn write(&mut self, message: BackendMessage) {
+        let buf = &mut self.buf;
+        let start = match message {
+            BackendMessage::AuthenticationOk => {
+                let start = begin(buf, b'R');
+                put_i32(buf, 0);
+                start
+            }
+            BackendMessage::AuthenticationCleartextPassword => {
+                let start = begin(buf, b'R');
+                put_i32(buf, 3);
+                start
+            }
+            BackendMessage::AuthenticationSasl(mechanisms) => {
+                let start = begin(buf, b'R');
+                put_i32(buf, 10);
+                for mechanism in mechanisms {
+                    put_cstr(buf, mechanism);
+                }
+                buf.push(0);
+                start
+            }
+            BackendMessage::AuthenticationSaslContinue(data) => {
+                let start = begin(buf, b'R');
+                put_i32(buf, 11);
+                buf.extend_from_slice(data);
+                start
+            }
+            BackendMessage::AuthenticationSaslFinal(data) => {
+                let start = begin(buf, b'R');
+                put_i32(buf, 12);
+                buf.extend_from_slice(data);
+                start
+            }
+            BackendMessage::ParameterStatus(name, value) => {
+                let start = begin(buf, b'S');
+                put_cstr(buf, name);
+                put_cstr(buf, value);
+                start
+            }
+            BackendMessage::BackendKeyData {
+                process_id,
+                secret_key,
+            } => {
+                let start = begin(buf, b'K');
+                put_i32(buf, process_id);
+                put_i32(buf, secret_key);
+                start
+            }
+            BackendMessage::ReadyForQuery => {
+                let start = begin(buf, b'Z');
+                // Always idle, transactions are not supported.
+                buf.push(b'I');
+                start
+            }
+            BackendMessage::RowDescription(fields) => {
+                let start = begin(buf, b'T');
+                put_i16(buf, fields.len() as i16);
+                for field in fields {
+                    put_cstr(buf, &field.name);
+                    // Table oid and column attribute number.
+                    put_i32(buf, 0);
+                    put_i16(buf, 0);
+                    put_i32(buf, field.type_oid);
+                    put_i16(buf, field.type_len);
+                    // Type modifier.
+                    put_i32(buf, -1);
+                    put_i16(buf, field.format);
+                }
+                start
+            }
+            BackendMessage::DataRow(values) => {
+                let start = begin(buf, b'D');
+                put_i16(buf, values.len() as i16);
+                for value in values {
+                    match value {
+                        None => put_i32(buf, -1),
+                        Some(value) => {
+                            put_i32(buf, value.len() as i32);
+                            buf.extend_from_slice(value);
+                        }
+                    }
+                }
+                start
+            }
+            BackendMessage::CommandComplete(tag) => {
+                let start = begin(buf, b'C');
+                put_cstr(buf, tag);
+                start
+            }
+            BackendMessage::EmptyQueryResponse => begin(buf, b'I'),
+            BackendMessage::ParseComplete => begin(buf, b'1'),
+            BackendMessage::BindComplete => begin(buf, b'2'),
+            BackendMessage::CloseComplete => begin(buf, b'3'),
+            BackendMessage::NoData => begin(buf, b'n'),
+            BackendMessage::PortalSuspended => begin(buf, b's'),
+            BackendMessage::ParameterDescription(types) => {
+                let start = begin(buf, b't');
+                put_i16(buf, types.len() as i16);
+                for type_oid in types {
+                    put_i32(buf, *type_oid);
+                }
+                start
+            }
+            BackendMessage::ErrorResponse { code, message } => {
+                let start = begin(buf, b'E');
+                for (field, value) in [
+                    (b'S', "ERROR"),
+                    (b'V', "ERROR"),
+                    (b'C', code),
+                    (b'M', message),
+                ] {
+                    buf.push(field);
+                    put_cstr(buf, value);
+                }
+                buf.push(0);
+                start
+            }
+        };
+
+        // The length counts itself but not the message type.
+        let len = (buf.len() - start) as i32;
+        buf[start..start + 4].copy_from_slice(&len.to_be_bytes());
+    }
+
+    pub fn buffered_bytes(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub async fn flush(&mut self) -> Result<()> {
+        if !self.buf.is_empty() {
+            self.writer.write_all(&self.buf).await?;
+            self.buf.clear();
+        }
+        self.writer.flush().await?;
+        Ok(())
+    }
+
+    async fn read_length(&mut self) -> Result<usize> {
+        let len = self.reader.read_i32().await?;
+        if len < 0 || len as usize > MAX_MESSAGE_LENGTH {
+            return Err(ErrorCode::BadBytes(format!(
+                "Invalid postgres message length {}",
+                len
+            )));
+        }
+        Ok(len as usize)
+    }
+}
+
+// Writes the message type and reserves the length, returns the position of the length.
+fn begin(buf: &mut Vec<u8>, tag: u8) -> usize {
+    buf.push(tag);
+    let start = buf.len();
+    put_i32(buf, 0);
+    start
+}
+
+/// The password of a PasswordMessage, a null terminated string.
+pub fn decode_password(body: &[u8]) -> Vec<u8> {
+    body.strip_suffix(&[0]).unwrap_or(body).to_vec()
+}
+
+/// The mechanism and the initial client response of a SASLInitialResponse.
+pub fn decode_sasl_initial_response(body: &[u8]) -> Result<(String, Vec<u8>)> {
+    let mut body = Body::new(body);
+    let mechanism = body.cstr()?;
+    let len = body.i32()?;
+    let data = if len < 0 {
+        vec![]
+    } else {
+        body.bytes(len as usize)?.to_vec()
+    };
+    Ok((mechanism, data))
+}
+
+fn put_i16(buf: &mut Vec<u8>, v: i16) {
+    buf.extend_from_slice(&v.to_be_bytes());
+}
+
+fn put_i32(buf: &mut Vec<u8>, v: i32) {
+    buf.extend_from_slice(&v.to_be_bytes());
+}
+
+fn put_cstr(buf: &mut Vec<u8>, v: &str) {
+    buf.extend_from_slice(v.as_bytes());
+    buf.push(0);
+}
+
+struct Body<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Body<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Body { data }
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.data.len() < len {
+            return Err(ErrorCode::BadBytes("Unexpected end of postgres message"));
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn i16(&mut self) -> Result<i16> {
+        let bytes = self.bytes(2)?;
+        Ok(i16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn i32(&mut self) -> Result<i32> {
+        let bytes = self.bytes(4)?;
+        Ok(i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn cstr(&mut self) -> Result<String> {
+        let end = self
+            .data
+            .iter()
+            .position(|b| *b == 0)
+            .ok_or_else(|| ErrorCode::BadBytes("Unterminated string in postgres message"))?;
+        let value = String::from_utf8(self.data[..end].to_vec())?;
+        self.data = &self.data[end + 1..];
+        Ok(value)
+    }
+
+    fn rest(&mut self) -> &'a [u8] {
+        std::mem::take(&mut self.data)
+    }
+}
@@ -0,0 +1,268 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::types::number::NumberScalar;
+use common_expression::types::DataType;
+use common_expression::types::NumberDataType;
+use common_expression::ScalarRef;
+use common_expression::TableDataType;
+
+// The oids of the types in pg_catalog.pg_type.
+pub const PG_TYPE_BOOL: i32 = 16;
+pub const PG_TYPE_INT8: i32 = 20;
+pub const PG_TYPE_INT2: i32 = 21;
+pub const PG_TYPE_INT4: i32 = 23;
+pub const PG_TYPE_TEXT: i32 = 25;
+pub const PG_TYPE_JSON: i32 = 114;
+pub const PG_TYPE_FLOAT4: i32 = 700;
+pub const PG_TYPE_FLOAT8: i32 = 701;
+pub const PG_TYPE_VARCHAR: i32 = 1043;
+pub const PG_TYPE_DATE: i32 = 1082;
+pub const PG_TYPE_TIMESTAMP: i32 = 1114;
+pub const PG_TYPE_NUMERIC: i32 = 1700;
+
+/// The types listed in pg_catalog.pg_type, the oids and the names.
+pub const PG_TYPES: &[(i32, &str)] = &[
+    (PG_TYPE_BOOL, "bool"),
+    (PG_TYPE_INT8, "int8"),
+    (PG_TYPE_INT2, "int2"),
+    (PG_TYPE_INT4, "int4"),
+    (PG_TYPE_TEXT, "text"),
+    (PG_TYPE_JSON, "json"),
+    (PG_TYPE_FLOAT4, "float4"),
+    (PG_TYPE_FLOAT8, "float8"),
+    (PG_TYPE_VARCHAR, "varchar"),
+    (PG_TYPE_DATE, "date"),
+    (PG_TYPE_TIMESTAMP, "timestamp"),
+    (PG_TYPE_NUMERIC, "numeric"),
+];
+
+pub const FORMAT_TEXT: i16 = 0;
+pub const FORMAT_BINARY: i16 = 1;
+
+// Postgres counts the dates and timestamps of the binary format from 2000-01-01.
+const PG_EPOCH_DAYS: i32 = 10957;
+const PG_EPOCH_MICROS: i64 = 946_684_800_000_000;
+
+/// Maps the data type to the oid of the postgres type holding its values. The unsigned
+/// integers take the next wider signed type, and the nested types are sent as text.
+pub fn pg_type_oid(data_type: &DataType) -> i32 {
+    match data_type.remove_nullable() {
+        DataType::Boolean => PG_TYPE_BOOL,
+        DataType::String => PG_TYPE_TEXT,
+        DataType::Number(num_ty) => match num_ty {
+            NumberDataType::Int8 | NumberDataType::Int16 | NumberDataType::UInt8 => PG_TYPE_INT2,
+            NumberDataType::Int32 | NumberDataType::UInt16 => PG_TYPE_INT4,
+            NumberDataType::Int64 | NumberDataType::UInt32 => PG_TYPE_INT8,
+            NumberDataType::UInt64 => PG_TYPE_NUMERIC,
+            NumberDataType::Float32 => PG_TYPE_FLOAT4,
+            NumberDataType::Float64 => PG_TYPE_FLOAT8,
+        },
+        DataType::Decimal(_) => PG_TYPE_NUMERIC,
+        DataType::Date => PG_TYPE_DATE,
+        DataType::Timestamp => PG_TYPE_TIMESTAMP,
+        DataType::Variant => PG_TYPE_JSON,
+        _ => PG_TYPE_TEXT,
+    }
+}
+
+/// The SQL expression mapping the type names of system.columns to the type oids, the same
+/// as `pg_type_oid` does for the data types.
+pub fn pg_type_oid_sql(type_name: &str) -> String {
+    let mut types = vec![
+        TableDataType::Boolean,
+        TableDataType::String,
+        TableDataType::Date,
+        TableDataType::Timestamp,
+        TableDataType::Variant,
+    ];
+    for num_ty in [
+        NumberDataType::UInt8,
+        NumberDataType::UInt16,
+        NumberDataType::UInt32,
+        NumberDataType::UInt64,
+        NumberDataType::Int8,
+        NumberDataType::Int16,
+        NumberDataType::Int32,
+        NumberDataType::Int64,
+        NumberDataType::Float32,
+        NumberDataType::Float64,
+    ] {
+        types.push(TableDataType::Number(num_ty));
+    }
+
+    let type_name = format!(
+        "if({0} LIKE '% NULL', substr({0}, 1, length({0}) - 5), {0})",
+        type_name
+    );
+    let mut branches = types
+        .iter()
+        .map(|ty| {
+            format!(
+                "{} = '{}', {}",
+                type_name,
+                ty.sql_name(),
+                pg_type_oid(&DataType::from(ty))
+            )
+        })
+        .collect::<Vec<_>>();
+    branches.push(format!(
+        "{} LIKE 'DECIMAL(%', {}",
+        type_name, PG_TYPE_NUMERIC
+    ));
+    format!("multiIf({}, {})", branches.join(", "), PG_TYPE_TEXT)
+}
+
+/// The size of the type in pg_catalog.pg_type, -1 for the variable length types.
+pub fn pg_type_len(type_oid: i32) -> i16 {
+    match type_oid {
+        PG_TYPE_BOOL => 1,
+        PG_TYPE_INT2 => 2,
+        PG_TYPE_INT4 | PG_TYPE_FLOAT4 | PG_TYPE_DATE => 4,
+        PG_TYPE_INT8 | PG_TYPE_FLOAT8 | PG_TYPE_TIMESTAMP => 8,
+        _ => -1,
+    }
+}
+
+/// Whether the value of a parameter with the type is put into the SQL as a number.
+pub fn pg_type_is_numeric(type_oid: i32) -> bool {
+    matches!(
+        type_oid,
+        PG_TYPE_INT2
+            | PG_TYPE_INT4
+            | PG_TYPE_INT8
+            | PG_TYPE_FLOAT4
+            | PG_TYPE_FLOAT8
+            | PG_TYPE_NUMERIC
+    )
+}
+
+/// Encodes the value in the binary format of the type given by `pg_type_oid`.
+pub fn encode_binary(value: ScalarRef, buf: &mut Vec<u8>) -> Result<()> {
+    match value {
+        ScalarRef::Boolean(v) => buf.push(v as u8),
+        ScalarRef::Number(number) => match number {
+            NumberScalar::Int8(v) => buf.extend_from_slice(&(v as i16).to_be_bytes()),
+            NumberScalar::Int16(v) => buf.extend_from_slice(&v.to_be_bytes()),
+            NumberScalar::Int32(v) => buf.extend_from_slice(&v.to_be_bytes()),
+            NumberScalar::Int64(v) => buf.extend_from_slice(&v.to_be_bytes()),
+            NumberScalar::UInt8(v) => buf.extend_from_slice(&(v as i16).to_be_bytes()),
+            NumberScalar::UInt16(v) => buf.extend_from_slice(&(v as i32).to_be_bytes()),
+            NumberScalar::UInt32(v) => buf.extend_from_slice(&(v as i64).to_be_bytes()),
+            NumberScalar::Float32(v) => buf.extend_from_slice(&v.0.to_be_bytes()),
+            NumberScalar::Float64(v) => buf.extend_from_slice(&v.0.to_be_bytes()),
+            NumberScalar::UInt64(_) => {
+                return Err(ErrorCode::Unimplemented(
+                    "The binary format of numeric is not supported, use the text format",
+                ));
+            }
+        },
+        ScalarRef::String(v) | ScalarRef::Variant(v) => buf.extend_from_slice(v),
+        ScalarRef::Date(v) => buf.extend_from_slice(&(v - PG_EPOCH_DAYS).to_be_bytes()),
+        ScalarRef::Timestamp(v) => buf.extend_from_slice(&(v - PG_EPOCH_MICROS).to_be_bytes()),
+        other => {
+            return Err(ErrorCode::Unimplemented(format!(
+                "The binary format of {:?} is not supported, use the text format",
+                other
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Decodes a parameter sent in the binary format into its SQL literal.
+pub fn decode_binary_param(type_oid: i32, value: &[u8]) -> Result<String> {
+    let invalid = || {
+        ErrorCode::BadBytes(format!(
+            "Invalid binary parameter of type oid {}, {} bytes",
+            type_oid,
+            value.len()
+        ))
+    };
+
+    match type_oid {
+        PG_TYPE_BOOL => match value {
+            [v] => Ok(if *v != 0 { "TRUE" } else { "FALSE" }.to_string()),
+            _ => Err(invalid()),
+        },
+        PG_TYPE_INT2 => {
+            let v = i16::from_be_bytes(value.try_into().map_err(|_| invalid())?);
+            Ok(format!("({})", v))
+        }
+        PG_TYPE_INT4 => {
+            let v = i32::from_be_bytes(value.try_into().map_err(|_| invalid())?);
+            Ok(format!("({})", v))
+        }
+        PG_TYPE_INT8 => {
+            let v = i64::from_be_bytes(value.try_into().map_err(|_| invalid())?);
+            Ok(format!("({})", v))
+        }
+        PG_TYPE_FLOAT4 => {
+            let v = f32::from_be_bytes(value.try_into().map_err(|_| invalid())?);
+            float_literal(v as f64)
+        }
+        PG_TYPE_FLOAT8 => {
+            let v = f64::from_be_bytes(value.try_into().map_err(|_| invalid())?);
+            float_literal(v)
+        }
+        PG_TYPE_TEXT | PG_TYPE_VARCHAR | PG_TYPE_JSON => {
+            Ok(quote_literal(&String::from_utf8(value.to_vec())?))
+        }
+        _ => Err(ErrorCode::Unimplemented(format!(
+            "The binary format of parameters with type oid {} is not supported",
+            type_oid
+        ))),
+    }
+}
+
+/// Converts a number parameter sent in the text format into its SQL literal.
+///
+/// The literals of numbers are parenthesized, so that a negative number can't be joined with
+/// the SQL before it, e.g. `1 -$1` into the comment `1 --1`.
+pub fn number_literal(value: &str) -> Result<String> {
+    // Decimals are kept as they are to not lose their precision.
+    let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    let unsigned = value.strip_prefix(&['+', '-'][..]).unwrap_or(value);
+    let is_decimal = match unsigned.split_once('.') {
+        Some((integral, fractional)) => is_digits(integral) && is_digits(fractional),
+        None => is_digits(unsigned),
+    };
+    if is_decimal {
+        return Ok(format!("({})", value));
+    }
+
+    match value.parse::<f64>() {
+        Ok(v) => float_literal(v),
+        Err(_) => Err(ErrorCode::BadArguments(format!(
+            "Invalid number parameter: {}",
+            value
+        ))),
+    }
+}
+
+fn float_literal(value: f64) -> Result<String> {
+    if !value.is_finite() {
+        return Err(ErrorCode::BadArguments(format!(
+            "Number parameter must be finite, got {}",
+            value
+        )));
+    }
+    Ok(format!("({})", value))
+}
+
+pub fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
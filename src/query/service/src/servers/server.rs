@@ -29,6 +29,7 @@ use tracing::error;
 use tracing::info;
 
 use crate::clusters::ClusterDiscovery;
+use crate::maintenance::MaintenanceService;
 use crate::sessions::SessionManager;
 
 pub type ListeningStream = Abortable<TcpListenerStream>;
@@ -62,6 +63,7 @@ impl ShutdownHandle {
     }
 
     pub async fn shutdown(&mut self, mut signal: SignalStream) {
+        MaintenanceService::instance().shutdown();
         self.shutdown_services(true).await;
         ClusterDiscovery::instance()
             .unregister_to_metastore(&mut signal)
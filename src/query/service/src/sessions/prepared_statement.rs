@@ -0,0 +1,138 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use common_catalog::table_context::PlaceholderValue;
+use common_exception::Result;
+use common_expression::types::DataType;
+use common_meta_app::schema::TableIdent;
+use common_sql::plans::Plan;
+use common_sql::Planner;
+use parking_lot::Mutex;
+
+use crate::sessions::query_ctx_shared::DatabaseAndTable;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+/// A statement prepared in the session, e.g. by COM_STMT_PREPARE of MySQL.
+///
+/// The statement is bound on its first execution with the `?` placeholders kept in the plan,
+/// the next executions only replace the placeholders with their parameters and optimize the
+/// plan. It's bound again once the types of the parameters, the current database or the
+/// versions of the tables it reads are changed.
+pub struct PreparedStatement {
+    query: String,
+    param_count: usize,
+    bound: Mutex<Option<BoundStatement>>,
+    // False if the plan of the statement can't be reused, e.g. DDL statements.
+    reusable: AtomicBool,
+}
+
+#[derive(Clone)]
+struct BoundStatement {
+    database: String,
+    param_types: Vec<DataType>,
+    tables: Vec<(DatabaseAndTable, TableIdent)>,
+    plan: Plan,
+}
+
+impl PreparedStatement {
+    pub fn create(query: String, param_count: usize) -> PreparedStatement {
+        PreparedStatement {
+            query,
+            param_count,
+            bound: Mutex::new(None),
+            reusable: AtomicBool::new(true),
+        }
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn param_count(&self) -> usize {
+        self.param_count
+    }
+
+    /// Plan the statement with the parameters attached to the context.
+    pub async fn plan(&self, ctx: Arc<QueryContext>) -> Result<Plan> {
+        if !self.reusable.load(Ordering::Relaxed) {
+            return self.plan_sql(ctx).await;
+        }
+
+        let database = ctx.get_current_database();
+        let param_types = ctx
+            .get_placeholders()
+            .iter()
+            .map(PlaceholderValue::data_type)
+            .collect::<Vec<_>>();
+
+        let bound = self.bound.lock().clone();
+        if let Some(bound) = bound {
+            if bound.database == database
+                && bound.param_types == param_types
+                && Self::tables_unchanged(&ctx, &bound.tables).await?
+            {
+                return Planner::new(ctx).plan_prepared(&bound.plan);
+            }
+        }
+
+        let mut planner = Planner::new(ctx.clone());
+        // Placeholders in the positions requiring constants (e.g. the arguments of table
+        // functions) fail the binding, these statements are planned with the parameters directly.
+        let plan = match planner.bind_prepared(&self.query).await {
+            Ok(Some(plan)) => plan,
+            Ok(None) => {
+                self.reusable.store(false, Ordering::Relaxed);
+                return self.plan_sql(ctx).await;
+            }
+            Err(_) => return self.plan_sql(ctx).await,
+        };
+
+        let tables = ctx
+            .get_tables_refs_by_name()
+            .into_iter()
+            .map(|(name, table)| (name, table.get_table_info().ident))
+            .collect();
+        *self.bound.lock() = Some(BoundStatement {
+            database,
+            param_types,
+            tables,
+            plan: plan.clone(),
+        });
+        planner.plan_prepared(&plan)
+    }
+
+    async fn plan_sql(&self, ctx: Arc<QueryContext>) -> Result<Plan> {
+        let mut planner = Planner::new(ctx);
+        let (plan, _, _) = planner.plan_sql(&self.query).await?;
+        Ok(plan)
+    }
+
+    async fn tables_unchanged(
+        ctx: &Arc<QueryContext>,
+        tables: &[(DatabaseAndTable, TableIdent)],
+    ) -> Result<bool> {
+        for ((catalog, database, name), ident) in tables {
+            let table = ctx.get_table(catalog, database, name).await?;
+            if &table.get_table_info().ident != ident {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::collections::VecDeque;
 use std::future::Future;
 use std::net::SocketAddr;
@@ -32,7 +33,9 @@ use common_catalog::plan::DataSourcePlan;
 use common_catalog::plan::PartInfoPtr;
 use common_catalog::plan::Partitions;
 use common_catalog::plan::StageTableInfo;
+use common_catalog::table_context::PlaceholderValue;
 use common_catalog::table_context::StageAttachment;
+use common_config::GlobalConfig;
 use common_config::DATABEND_COMMIT_VERSION;
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -41,13 +44,16 @@ use common_expression::FunctionContext;
 use common_expression::Scalar;
 use common_io::prelude::FormatSettings;
 use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::UpsertTableOptionReq;
 use common_meta_types::RoleInfo;
+use common_meta_types::TenantQuota;
 use common_meta_types::UserInfo;
 use common_settings::Settings;
 use common_storage::DataOperator;
 use common_storage::StorageMetrics;
 use common_storages_fuse::TableContext;
 use common_storages_stage::StageTable;
+use common_users::UserApiProvider;
 use parking_lot::RwLock;
 use tracing::debug;
 
@@ -57,6 +63,7 @@ use crate::catalogs::Catalog;
 use crate::clusters::Cluster;
 use crate::pipelines::executor::PipelineExecutor;
 use crate::sessions::query_affect::QueryAffect;
+use crate::sessions::query_ctx_shared::DatabaseAndTable;
 use crate::sessions::ProcessInfo;
 use crate::sessions::QueryContextShared;
 use crate::sessions::Session;
@@ -69,11 +76,20 @@ pub struct QueryContext {
     partition_queue: Arc<RwLock<VecDeque<PartInfoPtr>>>,
     shared: Arc<QueryContextShared>,
     fragment_id: Arc<AtomicUsize>,
+    /// Rows produced by the latest iteration of each recursive CTE, keyed by CTE index.
+    /// They are private to the context, a recursive CTE referenced more than once is
+    /// evaluated by several sources, each running its recursive member in its own context.
+    recursive_cte_tables: Arc<RwLock<HashMap<usize, Vec<DataBlock>>>>,
 }
 
 impl QueryContext {
+    /// Create a context sharing the query with `other`, the working tables of the recursive
+    /// CTEs of `other` are copied, so that setting them in the new context doesn't
+    /// affect `other`.
     pub fn create_from(other: Arc<QueryContext>) -> Arc<QueryContext> {
-        QueryContext::create_from_shared(other.shared.clone())
+        let ctx = QueryContext::create_from_shared(other.shared.clone());
+        *ctx.recursive_cte_tables.write() = other.recursive_cte_tables.read().clone();
+        ctx
     }
 
     pub fn create_from_shared(shared: Arc<QueryContextShared>) -> Arc<QueryContext> {
@@ -84,6 +100,7 @@ impl QueryContext {
             version: format!("DatabendQuery {}", *DATABEND_COMMIT_VERSION),
             shared,
             fragment_id: Arc::new(AtomicUsize::new(0)),
+            recursive_cte_tables: Arc::new(RwLock::new(HashMap::new())),
         })
     }
 
@@ -143,6 +160,23 @@ impl QueryContext {
         self.shared.get_auth_manager()
     }
 
+    // Create a context for the statements issued by this query itself, such as the
+    // maintenance of materialized views. It shares the session and cluster of this query,
+    // but caches its own tables and collects its own commits.
+    pub fn create_derived(&self) -> Result<Arc<QueryContext>> {
+        let config = GlobalConfig::instance();
+        let shared = QueryContextShared::try_create(
+            &config,
+            self.get_current_session(),
+            self.get_cluster(),
+        )?;
+        Ok(QueryContext::create_from_shared(shared))
+    }
+
+    pub fn pin_table(&self, catalog: &str, database: &str, table: &str, table_ref: Arc<dyn Table>) {
+        self.shared.pin_table(catalog, database, table, table_ref)
+    }
+
     // Get the current session.
     pub fn get_current_session(&self) -> Arc<Session> {
         self.shared.session.clone()
@@ -188,6 +222,23 @@ impl QueryContext {
         self.shared.attach_stage(attachment);
     }
 
+    pub fn attach_placeholders(&self, values: Vec<PlaceholderValue>) {
+        self.shared.attach_placeholders(values);
+    }
+
+    pub fn get_tables_refs_by_name(&self) -> Vec<(DatabaseAndTable, Arc<dyn Table>)> {
+        self.shared.get_tables_refs_by_name()
+    }
+
+    pub fn set_recursive_cte_table(&self, cte_index: usize, blocks: Vec<DataBlock>) {
+        self.recursive_cte_tables.write().insert(cte_index, blocks);
+    }
+
+    pub fn get_recursive_cte_table(&self, cte_index: usize) -> Vec<DataBlock> {
+        let tables = self.recursive_cte_tables.read();
+        tables.get(&cte_index).cloned().unwrap_or_default()
+    }
+
     pub fn get_created_time(&self) -> SystemTime {
         self.shared.created_time
     }
@@ -369,10 +420,29 @@ impl TableContext for QueryContext {
         SessionManager::instance().processes_info()
     }
 
+    // Get the quota of the current tenant.
+    async fn get_tenant_quota(&self) -> Result<TenantQuota> {
+        let tenant = self.get_tenant();
+        let quota_api = UserApiProvider::instance().get_tenant_quota_api_client(&tenant)?;
+        Ok(quota_api.get_quota(None).await?.data)
+    }
+
     // Get Stage Attachment.
     fn get_stage_attachment(&self) -> Option<StageAttachment> {
         self.shared.get_stage_attachment()
     }
+
+    fn get_placeholders(&self) -> Vec<PlaceholderValue> {
+        self.shared.get_placeholders()
+    }
+
+    fn consume_stream(&self, offset: UpsertTableOptionReq) {
+        self.shared.consume_stream(offset)
+    }
+
+    fn get_consumed_streams(&self) -> Vec<UpsertTableOptionReq> {
+        self.shared.get_consumed_streams()
+    }
 }
 
 impl TrySpawn for QueryContext {
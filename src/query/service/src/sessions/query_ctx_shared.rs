@@ -22,11 +22,13 @@ use std::time::SystemTime;
 
 use common_base::base::Progress;
 use common_base::runtime::Runtime;
+use common_catalog::table_context::PlaceholderValue;
 use common_catalog::table_context::StageAttachment;
 use common_config::Config;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_expression::DataBlock;
+use common_meta_app::schema::UpsertTableOptionReq;
 use common_meta_types::RoleInfo;
 use common_meta_types::UserInfo;
 use common_settings::Settings;
@@ -34,17 +36,19 @@ use common_storage::DataOperator;
 use common_storage::StorageMetrics;
 use parking_lot::Mutex;
 use parking_lot::RwLock;
+use tracing::warn;
 use uuid::Uuid;
 
 use crate::auth::AuthMgr;
 use crate::catalogs::CatalogManager;
 use crate::clusters::Cluster;
 use crate::pipelines::executor::PipelineExecutor;
+use crate::pipelines::processors::transforms::query_spill_dir;
 use crate::sessions::query_affect::QueryAffect;
 use crate::sessions::Session;
 use crate::storages::Table;
 
-type DatabaseAndTable = (String, String, String);
+pub type DatabaseAndTable = (String, String, String);
 
 /// Data that needs to be shared in a query context.
 /// This is very useful, for example, for queries:
@@ -78,6 +82,10 @@ pub struct QueryContextShared {
     pub(in crate::sessions) executor: Arc<RwLock<Weak<PipelineExecutor>>>,
     pub(in crate::sessions) precommit_blocks: Arc<RwLock<Vec<DataBlock>>>,
     pub(in crate::sessions) stage_attachment: Arc<RwLock<Option<StageAttachment>>>,
+    /// Parameters of the prepared statement run by the query
+    pub(in crate::sessions) placeholders: Arc<RwLock<Vec<PlaceholderValue>>>,
+    /// New offsets of the streams read by the query, keyed by stream table id
+    pub(in crate::sessions) consumed_streams: Arc<RwLock<HashMap<u64, UpsertTableOptionReq>>>,
     pub(in crate::sessions) created_time: SystemTime,
 }
 
@@ -107,6 +115,8 @@ impl QueryContextShared {
             executor: Arc::new(RwLock::new(Weak::new())),
             precommit_blocks: Arc::new(RwLock::new(vec![])),
             stage_attachment: Arc::new(RwLock::new(None)),
+            placeholders: Arc::new(RwLock::new(vec![])),
+            consumed_streams: Arc::new(RwLock::new(HashMap::new())),
             created_time: SystemTime::now(),
         }))
     }
@@ -192,6 +202,12 @@ impl QueryContextShared {
         self.session.apply_changed_settings(changed_settings)
     }
 
+    /// Resolve the table to `table_ref` in this query, instead of the one in catalog.
+    pub fn pin_table(&self, catalog: &str, database: &str, table: &str, table_ref: Arc<dyn Table>) {
+        let table_meta_key = (catalog.to_string(), database.to_string(), table.to_string());
+        self.tables_refs.lock().insert(table_meta_key, table_ref);
+    }
+
     pub async fn get_table(
         &self,
         catalog: &str,
@@ -317,11 +333,53 @@ impl QueryContextShared {
         *stage_attachment = Some(attachment);
     }
 
+    pub fn get_placeholders(&self) -> Vec<PlaceholderValue> {
+        self.placeholders.read().clone()
+    }
+
+    pub fn attach_placeholders(&self, values: Vec<PlaceholderValue>) {
+        let mut placeholders = self.placeholders.write();
+        *placeholders = values;
+    }
+
+    /// The tables resolved in the query, with the catalog and database they are resolved in.
+    pub fn get_tables_refs_by_name(&self) -> Vec<(DatabaseAndTable, Arc<dyn Table>)> {
+        let tables = self.tables_refs.lock();
+        tables
+            .iter()
+            .map(|(key, table)| (key.clone(), table.clone()))
+            .collect()
+    }
+
+    pub fn consume_stream(&self, offset: UpsertTableOptionReq) {
+        let mut streams = self.consumed_streams.write();
+        streams.insert(offset.table_id, offset);
+    }
+
+    pub fn get_consumed_streams(&self) -> Vec<UpsertTableOptionReq> {
+        self.consumed_streams.read().values().cloned().collect()
+    }
+
     pub fn get_created_time(&self) -> SystemTime {
         self.created_time
     }
 }
 
+impl Drop for QueryContextShared {
+    fn drop(&mut self) {
+        // Clean up the data spilled by the query.
+        let spill_dir = query_spill_dir(self.init_query_id.read().as_str());
+        if spill_dir.exists() {
+            if let Err(cause) = std::fs::remove_dir_all(&spill_dir) {
+                warn!(
+                    "Cannot remove the spill dir {:?}, cause: {:?}",
+                    spill_dir, cause
+                );
+            }
+        }
+    }
+}
+
 pub fn short_sql(query: &str) -> String {
     use unicode_segmentation::UnicodeSegmentation;
     let query = query.trim_start();
@@ -32,6 +32,7 @@ use parking_lot::RwLock;
 
 use crate::clusters::ClusterDiscovery;
 use crate::servers::http::v1::HttpQueryManager;
+use crate::sessions::PreparedStatement;
 use crate::sessions::QueryContext;
 use crate::sessions::QueryContextShared;
 use crate::sessions::SessionContext;
@@ -173,6 +174,18 @@ impl Session {
         self.session_ctx.get_current_database()
     }
 
+    pub fn add_prepared_statement(self: &Arc<Self>, statement: PreparedStatement) -> u32 {
+        self.session_ctx.add_prepared_statement(statement)
+    }
+
+    pub fn get_prepared_statement(self: &Arc<Self>, id: u32) -> Option<Arc<PreparedStatement>> {
+        self.session_ctx.get_prepared_statement(id)
+    }
+
+    pub fn remove_prepared_statement(self: &Arc<Self>, id: u32) {
+        self.session_ctx.remove_prepared_statement(id)
+    }
+
     pub fn get_current_catalog(self: &Arc<Self>) -> String {
         self.session_ctx.get_current_catalog()
     }
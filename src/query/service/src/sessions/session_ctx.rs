@@ -12,8 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU32;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::Weak;
@@ -26,6 +28,7 @@ use common_settings::Settings;
 use futures::channel::oneshot::Sender;
 use parking_lot::RwLock;
 
+use crate::sessions::PreparedStatement;
 use crate::sessions::QueryContextShared;
 
 pub struct SessionContext {
@@ -51,6 +54,9 @@ pub struct SessionContext {
     client_host: RwLock<Option<SocketAddr>>,
     io_shutdown_tx: RwLock<Option<Sender<Sender<()>>>>,
     query_context_shared: RwLock<Weak<QueryContextShared>>,
+    // The statements prepared in the session, keyed by the statement id.
+    prepared_statements: RwLock<HashMap<u32, Arc<PreparedStatement>>>,
+    next_statement_id: AtomicU32,
 }
 
 impl SessionContext {
@@ -67,6 +73,8 @@ impl SessionContext {
             current_database: RwLock::new("default".to_string()),
             io_shutdown_tx: Default::default(),
             query_context_shared: Default::default(),
+            prepared_statements: Default::default(),
+            next_statement_id: Default::default(),
         }))
     }
 
@@ -212,4 +220,21 @@ impl SessionContext {
         let mut lock = self.query_context_shared.write();
         *lock = ctx
     }
+
+    pub fn add_prepared_statement(&self, statement: PreparedStatement) -> u32 {
+        let id = self.next_statement_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let mut prepared_statements = self.prepared_statements.write();
+        prepared_statements.insert(id, Arc::new(statement));
+        id
+    }
+
+    pub fn get_prepared_statement(&self, id: u32) -> Option<Arc<PreparedStatement>> {
+        let prepared_statements = self.prepared_statements.read();
+        prepared_statements.get(&id).cloned()
+    }
+
+    pub fn remove_prepared_statement(&self, id: u32) {
+        let mut prepared_statements = self.prepared_statements.write();
+        prepared_statements.remove(&id);
+    }
 }
@@ -18,21 +18,27 @@ use std::fmt;
 pub enum SessionType {
     Clickhouse,
     MySQL,
+    PostgreSQL,
     HTTPQuery,
     HTTPStreamingLoad,
     ClickHouseHttpHandler,
     FlightRPC,
+    FlightSQL,
     HTTPAPI(String),
     Dummy,
     Fuzz,
     Local,
+    Maintenance,
 }
 
 impl SessionType {
     pub fn is_user_session(&self) -> bool {
         !matches!(
             self,
-            SessionType::HTTPAPI(_) | SessionType::Dummy | SessionType::Fuzz
+            SessionType::HTTPAPI(_)
+                | SessionType::Dummy
+                | SessionType::Fuzz
+                | SessionType::Maintenance
         )
     }
 }
@@ -43,13 +49,16 @@ impl fmt::Display for SessionType {
             SessionType::ClickHouseHttpHandler => "ClickhouseHTTPHandler".to_string(),
             SessionType::Clickhouse => "Clickhouse".to_string(),
             SessionType::MySQL => "MySQL".to_string(),
+            SessionType::PostgreSQL => "PostgreSQL".to_string(),
             SessionType::HTTPQuery => "HTTPQuery".to_string(),
             SessionType::HTTPStreamingLoad => "HTTPStreamingLoad".to_string(),
             SessionType::Dummy => "Dummy".to_string(),
             SessionType::FlightRPC => "FlightRPC".to_string(),
+            SessionType::FlightSQL => "FlightSQL".to_string(),
             SessionType::HTTPAPI(usage) => format!("HTTPAPI({})", usage),
             SessionType::Fuzz => "Fuzz".to_string(),
             SessionType::Local => "Local".to_string(),
+            SessionType::Maintenance => "Maintenance".to_string(),
         };
         write!(f, "{}", name)
     }
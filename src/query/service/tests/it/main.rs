@@ -18,6 +18,7 @@ mod auth;
 mod catalogs;
 mod clusters;
 mod configs;
+mod maintenance;
 mod metrics;
 mod pipelines;
 mod servers;
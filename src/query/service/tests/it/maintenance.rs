@@ -0,0 +1,338 @@
+//  Copyright 2023 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::base::tokio;
+use common_catalog::catalog_kind::CATALOG_DEFAULT;
+use common_config::Config;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_management::TableLockApi;
+use common_storage::StorageFsConfig;
+use common_storage::StorageParams;
+use common_storages_fuse::FuseTable;
+use common_users::UserApiProvider;
+use databend_query::interpreters::TableMutationLock;
+use databend_query::maintenance::MaintenanceService;
+use databend_query::sessions::QueryContext;
+use databend_query::sessions::TableContext;
+use databend_query::storages::fuse::FUSE_TBL_SNAPSHOT_PREFIX;
+use databend_query::storages::Table;
+use tempfile::TempDir;
+use walkdir::WalkDir;
+
+use crate::storages::fuse::table_test_fixture::execute_command;
+use crate::storages::fuse::table_test_fixture::execute_query;
+use crate::storages::fuse::table_test_fixture::expects_ok;
+use crate::tests::ConfigBuilder;
+use crate::tests::TestGuard;
+
+/// Each case runs in a tenant of its own, since the global settings and the
+/// maintenance history are shared by the cases.
+struct MaintenanceFixture {
+    tmp_dir: TempDir,
+    _guard: TestGuard,
+    conf: Config,
+    ctx: Arc<QueryContext>,
+}
+
+impl MaintenanceFixture {
+    async fn new(tenant: &str, max_concurrency: u64) -> Result<MaintenanceFixture> {
+        let tmp_dir = TempDir::new().unwrap();
+        let mut conf = ConfigBuilder::create().config();
+        conf.query.tenant_id = tenant.to_string();
+        conf.query.maintenance_max_concurrency = max_concurrency;
+        conf.storage.params = StorageParams::Fs(StorageFsConfig {
+            root: tmp_dir.path().to_str().unwrap().to_string(),
+        });
+
+        let (_guard, ctx) =
+            crate::tests::create_query_context_with_config(conf.clone(), None).await?;
+        execute_command(ctx.clone(), &format!("create database {}", tenant)).await?;
+
+        Ok(MaintenanceFixture {
+            tmp_dir,
+            _guard,
+            conf,
+            ctx,
+        })
+    }
+
+    /// The database of the case is named after the tenant.
+    fn database(&self) -> String {
+        self.conf.query.tenant_id.clone()
+    }
+
+    /// Create `table` with `inserts` snapshots of one block each.
+    async fn create_table(&self, table: &str, cluster_by: bool, inserts: usize) -> Result<()> {
+        let cluster_by = if cluster_by { " cluster by(a)" } else { "" };
+        let qry = format!(
+            "create table {}.{}(a int){}",
+            self.database(),
+            table,
+            cluster_by
+        );
+        execute_command(self.ctx.clone(), &qry).await?;
+
+        let qry = format!("insert into {}.{} values(1),(9)", self.database(), table);
+        for _ in 0..inserts {
+            execute_command(self.ctx.clone(), &qry).await?;
+        }
+        Ok(())
+    }
+
+    async fn latest_table(&self, table: &str) -> Result<Arc<dyn Table>> {
+        self.ctx
+            .get_catalog(CATALOG_DEFAULT)?
+            .get_table(&self.ctx.get_tenant(), &self.database(), table)
+            .await
+    }
+
+    async fn snapshot_loc(&self, table: &str) -> Result<Option<String>> {
+        let table = self.latest_table(table).await?;
+        FuseTable::try_from_table(table.as_ref())?
+            .snapshot_loc()
+            .await
+    }
+
+    /// `(block_count, row_count)` of the current snapshot.
+    async fn block_and_row_count(&self, table: &str) -> Result<(u64, u64)> {
+        let table = self.latest_table(table).await?;
+        let snapshot = FuseTable::try_from_table(table.as_ref())?
+            .read_table_snapshot()
+            .await?
+            .unwrap();
+        Ok((snapshot.summary.block_count, snapshot.summary.row_count))
+    }
+
+    /// Snapshot files in the storage, including the orphan ones.
+    fn snapshot_file_count(&self) -> usize {
+        WalkDir::new(self.tmp_dir.path())
+            .into_iter()
+            .map(|entry| entry.unwrap())
+            .filter(|entry| {
+                entry.file_type().is_file()
+                    && entry
+                        .path()
+                        .parent()
+                        .and_then(|dir| dir.file_name())
+                        .map(|dir| dir == FUSE_TBL_SNAPSHOT_PREFIX)
+                        .unwrap_or(false)
+            })
+            .count()
+    }
+
+    async fn maintain_tables(&self) -> Result<()> {
+        MaintenanceService::create(&self.conf)
+            .maintain_tables()
+            .await
+    }
+
+    /// Check the number of successful `action`s recorded in `system.maintenance_history`.
+    async fn expects_history(&self, case_name: &str, action: &str, count: u64) -> Result<()> {
+        let qry = format!(
+            "select count(*) from system.maintenance_history \
+             where database = '{}' and action = '{}' and error = ''",
+            self.database(),
+            action
+        );
+        let row = format!("| {:<8} |", format!("{}_u64", count));
+        let expected = vec![
+            "+----------+",
+            "| Column 0 |",
+            "+----------+",
+            row.as_str(),
+            "+----------+",
+        ];
+        expects_ok(
+            case_name,
+            execute_query(self.ctx.clone(), &qry).await,
+            expected,
+        )
+        .await
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_maintenance_compact_small_blocks() -> Result<()> {
+    let fixture = MaintenanceFixture::new("maintenance_compact", 2).await?;
+    fixture.create_table("t", false, 5).await?;
+    assert_eq!(fixture.block_and_row_count("t").await?, (5, 10));
+
+    fixture.maintain_tables().await?;
+
+    // Blocks are so tiny, they are compacted together.
+    let (block_count, row_count) = fixture.block_and_row_count("t").await?;
+    assert!(block_count < 5);
+    assert_eq!(row_count, 10);
+    fixture.expects_history("compact", "compact", 1).await?;
+
+    // The orphan snapshots are kept within the retention period.
+    fixture.expects_history("no purge", "purge", 0).await?;
+    assert!(fixture.snapshot_file_count() > 1);
+
+    // A table is not maintained again until it changes.
+    let snapshot_loc = fixture.snapshot_loc("t").await?;
+    fixture.maintain_tables().await?;
+    assert_eq!(fixture.snapshot_loc("t").await?, snapshot_loc);
+    fixture.expects_history("unchanged", "compact", 1).await?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_maintenance_purge_after_retention() -> Result<()> {
+    let fixture = MaintenanceFixture::new("maintenance_purge", 2).await?;
+    fixture.create_table("t", false, 3).await?;
+    assert_eq!(fixture.snapshot_file_count(), 3);
+
+    // Every orphan snapshot is beyond a retention period of 0 hours.
+    fixture.ctx.get_settings().set_settings(
+        "retention_period".to_string(),
+        "0".to_string(),
+        true,
+    )?;
+
+    fixture.maintain_tables().await?;
+
+    fixture.expects_history("compact", "compact", 1).await?;
+    fixture.expects_history("purge", "purge", 1).await?;
+    // Only the snapshot left by the compaction is kept.
+    assert_eq!(fixture.snapshot_file_count(), 1);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_maintenance_recluster() -> Result<()> {
+    let fixture = MaintenanceFixture::new("maintenance_recluster", 2).await?;
+    // All the blocks cover the same range of the cluster key.
+    fixture.create_table("t", true, 3).await?;
+    let table = fixture.latest_table("t").await?;
+    assert!(
+        FuseTable::try_from_table(table.as_ref())?
+            .need_recluster(fixture.ctx.clone())
+            .await?
+    );
+
+    fixture.maintain_tables().await?;
+
+    fixture.expects_history("recluster", "recluster", 1).await?;
+    let table = fixture.latest_table("t").await?;
+    assert!(
+        !FuseTable::try_from_table(table.as_ref())?
+            .need_recluster(fixture.ctx.clone())
+            .await?
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_maintenance_skip_locked_table() -> Result<()> {
+    let fixture = MaintenanceFixture::new("maintenance_locked", 2).await?;
+    fixture.create_table("t", false, 3).await?;
+    let snapshot_loc = fixture.snapshot_loc("t").await?;
+
+    // A user mutation holds the lock of the table.
+    let table = fixture.latest_table("t").await?;
+    let table_lock = TableMutationLock::try_lock(fixture.ctx.clone(), table.as_ref())
+        .await?
+        .unwrap();
+
+    fixture.maintain_tables().await?;
+    assert_eq!(fixture.snapshot_loc("t").await?, snapshot_loc);
+    fixture.expects_history("locked", "compact", 0).await?;
+
+    // The table is maintained by the next round once the lock is released.
+    table_lock.unlock().await?;
+    fixture.maintain_tables().await?;
+    assert_ne!(fixture.snapshot_loc("t").await?, snapshot_loc);
+    fixture.expects_history("unlocked", "compact", 1).await?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_table_lock_lost() -> Result<()> {
+    let fixture = MaintenanceFixture::new("maintenance_lock_lost", 2).await?;
+    fixture.create_table("t", false, 1).await?;
+    fixture.ctx.get_settings().set_settings(
+        "table_lock_expire_secs".to_string(),
+        "1".to_string(),
+        false,
+    )?;
+
+    let table = fixture.latest_table("t").await?;
+    let table_lock = TableMutationLock::try_lock(fixture.ctx.clone(), table.as_ref())
+        .await?
+        .unwrap();
+    table_lock.check_lost()?;
+
+    // The revision is gone behind the back of the holder, as if it expired.
+    let lock_api =
+        UserApiProvider::instance().get_table_lock_api_client(&fixture.ctx.get_tenant())?;
+    let revs = lock_api.list_lock_revisions(table.get_id()).await?;
+    assert_eq!(revs.len(), 1);
+    lock_api
+        .delete_lock_revision(table.get_id(), revs[0].0)
+        .await?;
+
+    // The heartbeat fails, the lock is lost and the query is aborted, so that it does not commit.
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+    assert!(table_lock.check_lost().is_err());
+    assert!(fixture.ctx.get_aborting().load(Ordering::Acquire));
+
+    let err = table_lock.unlock().await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::TABLE_LOCK_EXPIRED);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_maintenance_max_concurrency() -> Result<()> {
+    let fixture = MaintenanceFixture::new("maintenance_concurrency", 1).await?;
+    for table in ["t1", "t2", "t3"] {
+        fixture.create_table(table, false, 3).await?;
+    }
+
+    fixture.maintain_tables().await?;
+    fixture.expects_history("compact", "compact", 3).await?;
+
+    // With a concurrency of 1, the tables are maintained one after another.
+    let qry = format!(
+        "select count(*) from system.maintenance_history a, system.maintenance_history b \
+         where a.database = '{db}' and b.database = '{db}' and a.table < b.table \
+         and a.start_time < b.end_time and b.start_time < a.end_time",
+        db = fixture.database()
+    );
+    let expected = vec![
+        "+----------+",
+        "| Column 0 |",
+        "+----------+",
+        "| 0_u64    |",
+        "+----------+",
+    ];
+    expects_ok(
+        "no overlapping maintenance",
+        execute_query(fixture.ctx.clone(), &qry).await,
+        expected,
+    )
+    .await?;
+
+    Ok(())
+}
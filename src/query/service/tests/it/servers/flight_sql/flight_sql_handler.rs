@@ -0,0 +1,514 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::net::SocketAddr;
+
+use common_arrow::arrow::array::Array;
+use common_arrow::arrow::array::BinaryArray;
+use common_arrow::arrow::array::Int32Array;
+use common_arrow::arrow::chunk::Chunk;
+use common_arrow::arrow::datatypes::DataType;
+use common_arrow::arrow::datatypes::Field;
+use common_arrow::arrow::datatypes::Schema;
+use common_arrow::arrow::io::flight::default_ipc_fields;
+use common_arrow::arrow::io::flight::deserialize_batch;
+use common_arrow::arrow::io::flight::deserialize_schemas;
+use common_arrow::arrow::io::flight::serialize_batch;
+use common_arrow::arrow::io::flight::serialize_schema;
+use common_arrow::arrow::io::ipc::write::WriteOptions;
+use common_arrow::arrow_format::flight::data::flight_descriptor::DescriptorType;
+use common_arrow::arrow_format::flight::data::Action;
+use common_arrow::arrow_format::flight::data::Empty;
+use common_arrow::arrow_format::flight::data::FlightData;
+use common_arrow::arrow_format::flight::data::FlightDescriptor;
+use common_arrow::arrow_format::flight::data::HandshakeRequest;
+use common_arrow::arrow_format::flight::service::flight_service_client::FlightServiceClient;
+use common_base::base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use databend_query::servers::flight_sql::protocol::decode_message;
+use databend_query::servers::flight_sql::protocol::ActionClosePreparedStatementRequest;
+use databend_query::servers::flight_sql::protocol::ActionCreatePreparedStatementRequest;
+use databend_query::servers::flight_sql::protocol::ActionCreatePreparedStatementResult;
+use databend_query::servers::flight_sql::protocol::CommandGetCatalogs;
+use databend_query::servers::flight_sql::protocol::CommandGetTables;
+use databend_query::servers::flight_sql::protocol::CommandPreparedStatementQuery;
+use databend_query::servers::flight_sql::protocol::CommandPreparedStatementUpdate;
+use databend_query::servers::flight_sql::protocol::CommandStatementIngest;
+use databend_query::servers::flight_sql::protocol::CommandStatementQuery;
+use databend_query::servers::flight_sql::protocol::CommandStatementUpdate;
+use databend_query::servers::flight_sql::protocol::DoPutUpdateResult;
+use databend_query::servers::flight_sql::protocol::FlightSqlMessage;
+use databend_query::servers::FlightSQLHandler;
+use futures::StreamExt;
+use prost::Message;
+use tonic::metadata::MetadataValue;
+use tonic::transport::Channel;
+use tonic::Code;
+use tonic::Request;
+
+use crate::tests::ConfigBuilder;
+use crate::tests::TestGlobalServices;
+
+type Client = FlightServiceClient<Channel>;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_handshake() -> Result<()> {
+    let config = ConfigBuilder::create().build();
+    let _guard = TestGlobalServices::setup(config.clone()).await?;
+    let mut handler = FlightSQLHandler::create(config)?;
+    let listening = handler.start("127.0.0.1:0".parse::<SocketAddr>()?).await?;
+    let mut client = create_client(listening).await?;
+
+    // Unknown users and missing authorization are rejected.
+    let status = handshake(&mut client, Some("Basic bm9fdXNlcjo="))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::Unauthenticated);
+    let status = handshake(&mut client, None).await.unwrap_err();
+    assert_eq!(status.code(), Code::Unauthenticated);
+
+    // The requests without a valid bearer token are rejected.
+    let mut request = Request::new(statement_descriptor("SELECT 1"));
+    set_token(&mut request, "not_exists_token");
+    let status = client.get_flight_info(request).await.unwrap_err();
+    assert_eq!(status.code(), Code::Unauthenticated);
+
+    let token = handshake(&mut client, Some(&root_authorization())).await?;
+    assert!(!token.is_empty());
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_statement_query() -> Result<()> {
+    let config = ConfigBuilder::create().build();
+    let _guard = TestGlobalServices::setup(config.clone()).await?;
+    let mut handler = FlightSQLHandler::create(config)?;
+    let listening = handler.start("127.0.0.1:0".parse::<SocketAddr>()?).await?;
+    let mut client = create_client(listening).await?;
+    let token = handshake(&mut client, Some(&root_authorization())).await?;
+
+    let (schema, chunks) = query(
+        &mut client,
+        &token,
+        statement_descriptor("SELECT number::INT32 AS a FROM numbers(3) ORDER BY a"),
+    )
+    .await?;
+    assert_eq!(schema.fields.len(), 1);
+    assert_eq!(schema.fields[0].name, "a");
+    assert_eq!(int32_column(&chunks), vec![0, 1, 2]);
+
+    // The planning errors are returned by get_flight_info.
+    let mut request = Request::new(statement_descriptor("SELECT * FROM not_exists_table"));
+    set_token(&mut request, &token);
+    assert!(client.get_flight_info(request).await.is_err());
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_metadata_commands() -> Result<()> {
+    let config = ConfigBuilder::create().build();
+    let _guard = TestGlobalServices::setup(config.clone()).await?;
+    let mut handler = FlightSQLHandler::create(config)?;
+    let listening = handler.start("127.0.0.1:0".parse::<SocketAddr>()?).await?;
+    let mut client = create_client(listening).await?;
+    let token = handshake(&mut client, Some(&root_authorization())).await?;
+
+    update(&mut client, &token, "CREATE TABLE default.t1(a INT)").await?;
+
+    let (_, chunks) = query(
+        &mut client,
+        &token,
+        command_descriptor(&CommandGetCatalogs {}),
+    )
+    .await?;
+    assert!(string_column(&chunks, 0).contains(&"default".to_string()));
+
+    let command = CommandGetTables {
+        catalog: None,
+        db_schema_filter_pattern: Some("default".to_string()),
+        table_name_filter_pattern: Some("t1".to_string()),
+        table_types: vec!["TABLE".to_string()],
+        include_schema: false,
+    };
+    let (schema, chunks) = query(&mut client, &token, command_descriptor(&command)).await?;
+    let names = schema
+        .fields
+        .iter()
+        .map(|field| field.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec![
+        "catalog_name",
+        "db_schema_name",
+        "table_name",
+        "table_type"
+    ]);
+    assert_eq!(string_column(&chunks, 2), vec!["t1".to_string()]);
+    assert_eq!(string_column(&chunks, 3), vec!["TABLE".to_string()]);
+
+    // Views are filtered out by the table types.
+    let command = CommandGetTables {
+        table_types: vec!["VIEW".to_string()],
+        ..command
+    };
+    let (_, chunks) = query(&mut client, &token, command_descriptor(&command)).await?;
+    assert!(string_column(&chunks, 2).is_empty());
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_ingest() -> Result<()> {
+    let config = ConfigBuilder::create().build();
+    let _guard = TestGlobalServices::setup(config.clone()).await?;
+    let mut handler = FlightSQLHandler::create(config)?;
+    let listening = handler.start("127.0.0.1:0".parse::<SocketAddr>()?).await?;
+    let mut client = create_client(listening).await?;
+    let token = handshake(&mut client, Some(&root_authorization())).await?;
+
+    update(
+        &mut client,
+        &token,
+        "CREATE TABLE default.t2(a INT, b STRING)",
+    )
+    .await?;
+
+    // Only a subset of the columns is sent, the others are filled by the defaults.
+    let schema = Schema::from(vec![Field::new("a", DataType::Int32, false)]);
+    let ipc_fields = default_ipc_fields(&schema.fields);
+    let mut header = serialize_schema(&schema, Some(&ipc_fields));
+    header.flight_descriptor = Some(command_descriptor(&CommandStatementIngest {
+        table: "t2".to_string(),
+        schema: Some("default".to_string()),
+        catalog: None,
+        temporary: false,
+    }));
+
+    let mut messages = vec![header];
+    let options = WriteOptions { compression: None };
+    for values in [vec![1, 2], vec![3]] {
+        let chunk = Chunk::new(vec![Int32Array::from_vec(values).boxed()]);
+        let (_, data) = serialize_batch(&chunk, &ipc_fields, &options)?;
+        messages.push(data);
+    }
+
+    let mut request = Request::new(tokio_stream::iter(messages));
+    set_token(&mut request, &token);
+    let mut results = client.do_put(request).await?.into_inner();
+    let result = results
+        .next()
+        .await
+        .ok_or_else(|| ErrorCode::Internal("No do_put result"))??;
+    let result = DoPutUpdateResult::decode(result.app_metadata.as_slice())
+        .map_err(|cause| ErrorCode::BadBytes(cause.to_string()))?;
+    assert_eq!(result.record_count, 3);
+
+    let (_, chunks) = query(
+        &mut client,
+        &token,
+        statement_descriptor("SELECT a FROM default.t2 ORDER BY a"),
+    )
+    .await?;
+    assert_eq!(int32_column(&chunks), vec![1, 2, 3]);
+
+    // Ingesting into an unknown table fails.
+    let mut header = serialize_schema(&schema, Some(&ipc_fields));
+    header.flight_descriptor = Some(command_descriptor(&CommandStatementIngest {
+        table: "not_exists_table".to_string(),
+        schema: Some("default".to_string()),
+        catalog: None,
+        temporary: false,
+    }));
+    let mut request = Request::new(tokio_stream::iter(vec![header]));
+    set_token(&mut request, &token);
+    assert!(client.do_put(request).await.is_err());
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_prepared_statement() -> Result<()> {
+    let config = ConfigBuilder::create().build();
+    let _guard = TestGlobalServices::setup(config.clone()).await?;
+    let mut handler = FlightSQLHandler::create(config)?;
+    let listening = handler.start("127.0.0.1:0".parse::<SocketAddr>()?).await?;
+    let mut client = create_client(listening).await?;
+    let token = handshake(&mut client, Some(&root_authorization())).await?;
+
+    let mut request = Request::new(Empty {});
+    set_token(&mut request, &token);
+    let actions = client
+        .list_actions(request)
+        .await?
+        .into_inner()
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(actions.len(), 2);
+
+    // A statement without parameters is executed by its handle.
+    let handle = prepare(
+        &mut client,
+        &token,
+        "SELECT number::INT32 AS a FROM numbers(2)",
+    )
+    .await?;
+    let descriptor = command_descriptor(&CommandPreparedStatementQuery {
+        prepared_statement_handle: handle,
+    });
+    let (_, chunks) = query(&mut client, &token, descriptor).await?;
+    assert_eq!(int32_column(&chunks), vec![0, 1]);
+
+    // The parameters of a query are bound by do_put before it's executed.
+    let handle = prepare(
+        &mut client,
+        &token,
+        "SELECT number::INT32 AS a FROM numbers(10) WHERE number > ? ORDER BY a",
+    )
+    .await?;
+    let descriptor = command_descriptor(&CommandPreparedStatementQuery {
+        prepared_statement_handle: handle.clone(),
+    });
+    let mut request = Request::new(descriptor.clone());
+    set_token(&mut request, &token);
+    assert!(client.get_flight_info(request).await.is_err());
+    for (param, expected) in [(6, vec![7, 8, 9]), (8, vec![9])] {
+        put_parameters(&mut client, &token, descriptor.clone(), vec![param]).await?;
+        let (_, chunks) = query(&mut client, &token, descriptor.clone()).await?;
+        assert_eq!(int32_column(&chunks), expected);
+    }
+
+    // The closed statements are not found anymore.
+    do_action(
+        &mut client,
+        &token,
+        "ClosePreparedStatement",
+        &ActionClosePreparedStatementRequest {
+            prepared_statement_handle: handle,
+        },
+    )
+    .await?;
+    let mut request = Request::new(descriptor);
+    set_token(&mut request, &token);
+    assert!(client.get_flight_info(request).await.is_err());
+
+    // An update is executed once per row of parameters.
+    update(&mut client, &token, "CREATE TABLE default.t3(a INT)").await?;
+    let handle = prepare(&mut client, &token, "INSERT INTO default.t3 VALUES (?)").await?;
+    let descriptor = command_descriptor(&CommandPreparedStatementUpdate {
+        prepared_statement_handle: handle,
+    });
+    let result = put_parameters(&mut client, &token, descriptor, vec![1, 2, 3]).await?;
+    let result = DoPutUpdateResult::decode(result.as_slice())
+        .map_err(|cause| ErrorCode::BadBytes(cause.to_string()))?;
+    assert_eq!(result.record_count, 3);
+
+    let (_, chunks) = query(
+        &mut client,
+        &token,
+        statement_descriptor("SELECT a FROM default.t3 ORDER BY a"),
+    )
+    .await?;
+    assert_eq!(int32_column(&chunks), vec![1, 2, 3]);
+    Ok(())
+}
+
+async fn create_client(listening: SocketAddr) -> Result<Client> {
+    FlightServiceClient::connect(format!("http://{}", listening))
+        .await
+        .map_err(|cause| ErrorCode::CannotConnectNode(cause.to_string()))
+}
+
+fn root_authorization() -> String {
+    format!("Basic {}", base64::encode("root:"))
+}
+
+async fn handshake(
+    client: &mut Client,
+    authorization: Option<&str>,
+) -> std::result::Result<String, tonic::Status> {
+    let mut request = Request::new(tokio_stream::once(HandshakeRequest {
+        protocol_version: 0,
+        payload: vec![],
+    }));
+    if let Some(authorization) = authorization {
+        let value = MetadataValue::try_from(authorization).unwrap();
+        request.metadata_mut().insert("authorization", value);
+    }
+
+    let response = client.handshake(request).await?;
+    let authorization = response
+        .metadata()
+        .get("authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|value| value.to_string())
+        .unwrap();
+    Ok(authorization)
+}
+
+fn set_token<T>(request: &mut Request<T>, token: &str) {
+    let value = MetadataValue::try_from(format!("Bearer {}", token)).unwrap();
+    request.metadata_mut().insert("authorization", value);
+}
+
+fn command_descriptor<T: FlightSqlMessage>(command: &T) -> FlightDescriptor {
+    FlightDescriptor {
+        r#type: DescriptorType::Cmd as i32,
+        cmd: command.encode_any(),
+        path: vec![],
+    }
+}
+
+fn statement_descriptor(query: &str) -> FlightDescriptor {
+    command_descriptor(&CommandStatementQuery {
+        query: query.to_string(),
+        transaction_id: None,
+    })
+}
+
+async fn update(client: &mut Client, token: &str, query: &str) -> Result<()> {
+    let mut data = FlightData::default();
+    data.flight_descriptor = Some(command_descriptor(&CommandStatementUpdate {
+        query: query.to_string(),
+        transaction_id: None,
+    }));
+
+    let mut request = Request::new(tokio_stream::once(data));
+    set_token(&mut request, token);
+    let mut results = client.do_put(request).await?.into_inner();
+    while let Some(result) = results.next().await {
+        result?;
+    }
+    Ok(())
+}
+
+async fn do_action<T: FlightSqlMessage>(
+    client: &mut Client,
+    token: &str,
+    r#type: &str,
+    body: &T,
+) -> Result<Vec<u8>> {
+    let mut request = Request::new(Action {
+        r#type: r#type.to_string(),
+        body: body.encode_any(),
+    });
+    set_token(&mut request, token);
+    let mut results = client.do_action(request).await?.into_inner();
+    let result = results
+        .next()
+        .await
+        .ok_or_else(|| ErrorCode::Internal("No do_action result"))??;
+    Ok(result.body)
+}
+
+/// Creates a prepared statement, returns its handle.
+async fn prepare(client: &mut Client, token: &str, query: &str) -> Result<Vec<u8>> {
+    let body = do_action(
+        client,
+        token,
+        "CreatePreparedStatement",
+        &ActionCreatePreparedStatementRequest {
+            query: query.to_string(),
+            transaction_id: None,
+        },
+    )
+    .await?;
+    let result: ActionCreatePreparedStatementResult = decode_message(&body)?;
+    assert!(!result.dataset_schema.is_empty());
+    Ok(result.prepared_statement_handle)
+}
+
+/// Sends the rows of an INT parameter by do_put, returns the app metadata of the result.
+async fn put_parameters(
+    client: &mut Client,
+    token: &str,
+    descriptor: FlightDescriptor,
+    values: Vec<i32>,
+) -> Result<Vec<u8>> {
+    let schema = Schema::from(vec![Field::new("p", DataType::Int32, false)]);
+    let ipc_fields = default_ipc_fields(&schema.fields);
+    let mut header = serialize_schema(&schema, Some(&ipc_fields));
+    header.flight_descriptor = Some(descriptor);
+    let chunk = Chunk::new(vec![Int32Array::from_vec(values).boxed()]);
+    let options = WriteOptions { compression: None };
+    let (_, data) = serialize_batch(&chunk, &ipc_fields, &options)?;
+
+    let mut request = Request::new(tokio_stream::iter(vec![header, data]));
+    set_token(&mut request, token);
+    let mut results = client.do_put(request).await?.into_inner();
+    let result = results
+        .next()
+        .await
+        .ok_or_else(|| ErrorCode::Internal("No do_put result"))??;
+    Ok(result.app_metadata)
+}
+
+/// Runs the descriptor by get_flight_info and do_get, returns the schema and the record batches.
+async fn query(
+    client: &mut Client,
+    token: &str,
+    descriptor: FlightDescriptor,
+) -> Result<(Schema, Vec<Chunk<Box<dyn Array>>>)> {
+    let mut request = Request::new(descriptor);
+    set_token(&mut request, token);
+    let info = client.get_flight_info(request).await?.into_inner();
+    let ticket = info.endpoint[0].ticket.clone().unwrap();
+
+    let mut request = Request::new(ticket);
+    set_token(&mut request, token);
+    let mut stream = client.do_get(request).await?.into_inner();
+
+    let header = stream
+        .next()
+        .await
+        .ok_or_else(|| ErrorCode::Internal("No schema in do_get"))??;
+    let (schema, ipc_schema) = deserialize_schemas(&header.data_header)?;
+    let mut chunks = vec![];
+    while let Some(data) = stream.next().await {
+        chunks.push(deserialize_batch(
+            &data?,
+            &schema.fields,
+            &ipc_schema,
+            &Default::default(),
+        )?);
+    }
+    Ok((schema, chunks))
+}
+
+fn int32_column(chunks: &[Chunk<Box<dyn Array>>]) -> Vec<i32> {
+    chunks
+        .iter()
+        .flat_map(|chunk| {
+            let array = chunk.arrays()[0]
+                .as_any()
+                .downcast_ref::<Int32Array>()
+                .unwrap();
+            array.values().to_vec()
+        })
+        .collect()
+}
+
+fn string_column(chunks: &[Chunk<Box<dyn Array>>], index: usize) -> Vec<String> {
+    chunks
+        .iter()
+        .flat_map(|chunk| {
+            let array = chunk.arrays()[index]
+                .as_any()
+                .downcast_ref::<BinaryArray<i64>>()
+                .unwrap();
+            array
+                .values_iter()
+                .map(|value| String::from_utf8_lossy(value).to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
@@ -27,17 +27,21 @@ use mysql_async::prelude::FromRow;
 use mysql_async::prelude::Queryable;
 use mysql_async::FromRowError;
 use mysql_async::Row;
+use mysql_async::SslOpts;
 use tokio::sync::Barrier;
 
+use crate::tests::tls_constants::TEST_TLS_SERVER_CERT;
+use crate::tests::tls_constants::TEST_TLS_SERVER_KEY;
 use crate::tests::ConfigBuilder;
 use crate::tests::TestGlobalServices;
 
 #[tokio::test(flavor = "current_thread")]
 async fn test_generic_code_with_on_query() -> Result<()> {
     // Setup
-    let _guard = TestGlobalServices::setup(ConfigBuilder::create().build()).await?;
+    let config = ConfigBuilder::create().build();
+    let _guard = TestGlobalServices::setup(config.clone()).await?;
 
-    let mut handler = MySQLHandler::create()?;
+    let mut handler = MySQLHandler::create(config)?;
 
     let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
     let runnable_server = handler.start(listening).await?;
@@ -49,12 +53,174 @@ async fn test_generic_code_with_on_query() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn test_generic_code_with_tls() -> Result<()> {
+    let config = ConfigBuilder::create()
+        .mysql_handler_tls_server_cert(TEST_TLS_SERVER_CERT)
+        .mysql_handler_tls_server_key(TEST_TLS_SERVER_KEY)
+        .build();
+    let _guard = TestGlobalServices::setup(config.clone()).await?;
+
+    let mut handler = MySQLHandler::create(config)?;
+
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let runnable_server = handler.start(listening).await?;
+
+    let ssl_opts = SslOpts::default()
+        .with_danger_accept_invalid_certs(true)
+        .with_danger_skip_domain_validation(true);
+    let opts = mysql_async::OptsBuilder::default()
+        .ip_or_hostname("127.0.0.1")
+        .tcp_port(runnable_server.port())
+        .user(Some("root"))
+        .ssl_opts(Some(ssl_opts));
+    let mut connection = mysql_async::Conn::new(opts)
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "TLS connection failed")?;
+
+    let result = connection.query_iter("SELECT 1, 2, 3;").await;
+    assert!(result.is_ok());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_require_tls_user() -> Result<()> {
+    let config = ConfigBuilder::create()
+        .mysql_handler_tls_server_cert(TEST_TLS_SERVER_CERT)
+        .mysql_handler_tls_server_key(TEST_TLS_SERVER_KEY)
+        .build();
+    let _guard = TestGlobalServices::setup(config.clone()).await?;
+
+    let mut handler = MySQLHandler::create(config)?;
+
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let runnable_server = handler.start(listening).await?;
+
+    // root@localhost is not required to use TLS.
+    let mut connection = create_connection(runnable_server.port()).await?;
+    connection
+        .query_drop("CREATE USER 'tls_user' IDENTIFIED BY 'password' WITH REQUIRETLS")
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Create user failed")?;
+
+    let opts = mysql_async::OptsBuilder::default()
+        .ip_or_hostname("127.0.0.1")
+        .tcp_port(runnable_server.port())
+        .user(Some("tls_user"))
+        .pass(Some("password"));
+
+    let plaintext = mysql_async::Conn::new(opts.clone()).await;
+    assert!(plaintext.is_err());
+
+    let ssl_opts = SslOpts::default()
+        .with_danger_accept_invalid_certs(true)
+        .with_danger_skip_domain_validation(true);
+    let mut connection = mysql_async::Conn::new(opts.ssl_opts(Some(ssl_opts)))
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "TLS connection failed")?;
+    let result = connection.query_iter("SELECT 1, 2, 3;").await;
+    assert!(result.is_ok());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_prepared_statement_with_params() -> Result<()> {
+    let config = ConfigBuilder::create().build();
+    let _guard = TestGlobalServices::setup(config.clone()).await?;
+
+    let mut handler = MySQLHandler::create(config)?;
+
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let runnable_server = handler.start(listening).await?;
+    let mut connection = create_connection(runnable_server.port()).await?;
+
+    let statement = connection
+        .prep("SELECT ? + 1, ?")
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Prepare failed")?;
+    assert_eq!(statement.num_params(), 2);
+
+    for value in [1u64, 41] {
+        let row: Option<(u64, String)> = connection
+            .exec_first(&statement, (value, "databend"))
+            .await
+            .map_err_to_code(ErrorCode::UnknownException, || "Execute failed")?;
+        assert_eq!(row, Some((value + 1, "databend".to_string())));
+    }
+
+    connection
+        .close(statement)
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Close failed")?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_prepared_statement_with_table() -> Result<()> {
+    let config = ConfigBuilder::create().build();
+    let _guard = TestGlobalServices::setup(config.clone()).await?;
+
+    let mut handler = MySQLHandler::create(config)?;
+
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let runnable_server = handler.start(listening).await?;
+    let mut connection = create_connection(runnable_server.port()).await?;
+
+    connection
+        .query_drop("CREATE TABLE prepared_t(a INT, b VARCHAR)")
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Create table failed")?;
+
+    let insert = connection
+        .prep("INSERT INTO prepared_t VALUES (?, ?), (?, ?)")
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Prepare failed")?;
+    let select = connection
+        .prep("SELECT count(*) FROM prepared_t WHERE a > ?")
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Prepare failed")?;
+
+    connection
+        .exec_drop(&insert, (1, "a", 2, "b"))
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Execute failed")?;
+    let count: Option<u64> = connection
+        .exec_first(&select, (0,))
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Execute failed")?;
+    assert_eq!(count, Some(2));
+
+    // The bound plan is reused with new parameters, and bound again with the new
+    // version of the table or new parameter types.
+    connection
+        .exec_drop(&insert, (3, "c", 4, "d"))
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Execute failed")?;
+    for (param, expected) in [(0.0, 4u64), (1.5, 3), (4.0, 0)] {
+        let count: Option<u64> = connection
+            .exec_first(&select, (param,))
+            .await
+            .map_err_to_code(ErrorCode::UnknownException, || "Execute failed")?;
+        assert_eq!(count, Some(expected));
+    }
+    let count: Option<u64> = connection
+        .exec_first(&select, (2,))
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Execute failed")?;
+    assert_eq!(count, Some(2));
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn test_rejected_session_with_sequence() -> Result<()> {
-    let _guard =
-        TestGlobalServices::setup(ConfigBuilder::create().max_active_sessions(1).build()).await?;
+    let config = ConfigBuilder::create().max_active_sessions(1).build();
+    let _guard = TestGlobalServices::setup(config.clone()).await?;
 
-    let mut handler = MySQLHandler::create()?;
+    let mut handler = MySQLHandler::create(config)?;
 
     let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
     let listening = handler.start(listening).await?;
@@ -117,10 +283,10 @@ async fn test_rejected_session_with_parallel() -> Result<()> {
     }
 
     // Setup
-    let _guard =
-        TestGlobalServices::setup(ConfigBuilder::create().max_active_sessions(1).build()).await?;
+    let config = ConfigBuilder::create().max_active_sessions(1).build();
+    let _guard = TestGlobalServices::setup(config.clone()).await?;
 
-    let mut handler = MySQLHandler::create()?;
+    let mut handler = MySQLHandler::create(config)?;
 
     let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
     let listening = handler.start(listening).await?;
@@ -0,0 +1,147 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use common_expression::block_debug::assert_blocks_eq;
+use databend_query::servers::PostgresFederated;
+
+#[test]
+fn test_postgres_federated() -> Result<()> {
+    let federated = PostgresFederated::create();
+
+    //
+    {
+        let query = "select 1";
+        let result = federated.check(query);
+        assert!(result.is_none());
+    }
+
+    // select version()
+    {
+        let query = "select version()";
+        let result = federated.check(query);
+        assert!(result.is_some());
+
+        if let Some((_, block)) = result {
+            assert!(!block.is_empty())
+        }
+
+        let query = "select versiona";
+        let result = federated.check(query);
+        assert!(result.is_none());
+    }
+
+    // show variables
+    {
+        let query = "SHOW TRANSACTION ISOLATION LEVEL";
+        let result = federated.check(query);
+        assert!(result.is_some());
+
+        if let Some((_, block)) = result {
+            let expect = vec![
+                "+------------------+",
+                "| Column 0         |",
+                "+------------------+",
+                "| \"read committed\" |",
+                "+------------------+",
+            ];
+
+            assert_blocks_eq(expect, &[block]);
+        }
+
+        let query = "show server_version_num";
+        let result = federated.check(query);
+        assert!(result.is_some());
+
+        if let Some((_, block)) = result {
+            let expect = vec![
+                "+----------+",
+                "| Column 0 |",
+                "+----------+",
+                "| \"140000\" |",
+                "+----------+",
+            ];
+
+            assert_blocks_eq(expect, &[block]);
+        }
+    }
+
+    // driver setup and catalog probes
+    {
+        for query in [
+            "SET extra_float_digits = 3",
+            "SET application_name = 'PostgreSQL JDBC Driver'",
+            "BEGIN",
+            "COMMIT",
+        ] {
+            let result = federated.check(query);
+            assert!(result.is_some(), "{}", query);
+
+            if let Some((schema, block)) = result {
+                assert!(schema.fields().is_empty());
+                assert!(block.is_empty());
+            }
+        }
+    }
+
+    // Catalog tables, read from the system tables.
+    {
+        for query in [
+            "SELECT relname FROM pg_catalog.pg_class",
+            "select * from pg_attribute a join pg_type t on a.atttypid = t.oid",
+            "WITH t AS (SELECT 1) SELECT nspname FROM pg_namespace",
+            "SELECT typinput='array_in'::regproc, typtype FROM pg_catalog.pg_type LEFT JOIN (select ns.oid as nspoid, ns.nspname, r.r from pg_namespace as ns join ( select s.r, (current_schemas(false))[s.r] as nspname from generate_series(1, array_upper(current_schemas(false), 1)) as s(r) ) as r using ( nspname ) ) as sp ON sp.nspoid = typnamespace WHERE typname = $1 ORDER BY sp.r, pg_type.oid DESC LIMIT 1",
+        ] {
+            assert!(federated.check(query).is_none(), "{}", query);
+            assert!(federated.catalog_query(query).is_some(), "{}", query);
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_postgres_catalog_query() -> Result<()> {
+    let federated = PostgresFederated::create();
+
+    assert!(federated.catalog_query("select 1").is_none());
+    assert!(
+        federated
+            .catalog_query("select * from pg_classes")
+            .is_none()
+    );
+    assert!(
+        federated
+            .catalog_query("insert into t select * from pg_class")
+            .is_none()
+    );
+
+    let query = federated
+        .catalog_query("SELECT relname FROM pg_catalog.pg_class WHERE relkind = 'r';")
+        .unwrap();
+    assert!(query.starts_with("WITH pg_class AS (SELECT "));
+    assert!(query.contains("FROM system.tables"));
+    assert!(query.ends_with(" SELECT relname FROM pg_class WHERE relkind = 'r'"));
+    assert!(!query.contains("pg_attribute AS"));
+
+    let query = federated
+        .catalog_query("with t as (select 1) select * from PG_ATTRIBUTE, pg_type")
+        .unwrap();
+    assert!(query.starts_with("WITH pg_attribute AS (SELECT "));
+    assert!(query.contains("), pg_type AS (SELECT "));
+    assert!(query.contains("FROM system.columns"));
+    assert!(query.ends_with(", t as (select 1) select * from pg_attribute, pg_type"));
+
+    Ok(())
+}
@@ -0,0 +1,422 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::net::SocketAddr;
+
+use common_base::base::tokio;
+use common_base::base::tokio::io::AsyncReadExt;
+use common_base::base::tokio::io::AsyncWriteExt;
+use common_base::base::tokio::net::TcpStream;
+use common_exception::Result;
+use databend_query::servers::PostgresHandler;
+use hmac::Hmac;
+use hmac::Mac;
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::tests::ConfigBuilder;
+use crate::tests::TestGlobalServices;
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_simple_query() -> Result<()> {
+    let _guard = TestGlobalServices::setup(ConfigBuilder::create().build()).await?;
+
+    let mut handler = PostgresHandler::create()?;
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let listening = handler.start(listening).await?;
+    let mut stream = create_connection(listening.port()).await?;
+
+    send_message(
+        &mut stream,
+        b'Q',
+        &cstr("SELECT 1 + 1 AS a, 'b' AS b; SET extra_float_digits = 3"),
+    )
+    .await?;
+    let messages = read_until_ready(&mut stream).await?;
+    let tags = messages.iter().map(|(tag, _)| *tag).collect::<Vec<_>>();
+    assert_eq!(tags, vec![b'T', b'D', b'C', b'C', b'Z']);
+    assert_eq!(data_row(&messages[1].1), vec![
+        Some(b"2".to_vec()),
+        Some(b"b".to_vec())
+    ]);
+    assert_eq!(messages[2].1, cstr("SELECT 1"));
+    assert_eq!(messages[3].1, cstr("SET"));
+
+    // The errors are sent to the client, the connection goes on.
+    send_message(&mut stream, b'Q', &cstr("SELECT * FROM not_exists_table")).await?;
+    let messages = read_until_ready(&mut stream).await?;
+    let tags = messages.iter().map(|(tag, _)| *tag).collect::<Vec<_>>();
+    assert_eq!(tags, vec![b'E', b'Z']);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_catalog_query() -> Result<()> {
+    let _guard = TestGlobalServices::setup(ConfigBuilder::create().build()).await?;
+
+    let mut handler = PostgresHandler::create()?;
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let listening = handler.start(listening).await?;
+    let mut stream = create_connection(listening.port()).await?;
+
+    send_message(
+        &mut stream,
+        b'Q',
+        &cstr("CREATE TABLE catalog_t(a INT, b VARCHAR NULL, c DOUBLE)"),
+    )
+    .await?;
+    read_until_ready(&mut stream).await?;
+
+    send_message(
+        &mut stream,
+        b'Q',
+        &cstr(
+            "SELECT n.nspname, a.attname, t.typname FROM pg_catalog.pg_attribute a \
+             JOIN pg_catalog.pg_class c ON a.attrelid = c.oid \
+             JOIN pg_catalog.pg_namespace n ON c.relnamespace = n.oid \
+             JOIN pg_catalog.pg_type t ON a.atttypid = t.oid \
+             WHERE c.relname = 'catalog_t' ORDER BY a.attnum",
+        ),
+    )
+    .await?;
+    let messages = read_until_ready(&mut stream).await?;
+    let tags = messages.iter().map(|(tag, _)| *tag).collect::<Vec<_>>();
+    assert_eq!(tags, vec![b'T', b'D', b'D', b'D', b'C', b'Z']);
+    let rows = messages[1..4]
+        .iter()
+        .map(|(_, body)| data_row(body))
+        .collect::<Vec<_>>();
+    assert_eq!(rows, vec![
+        vec![
+            Some(b"default".to_vec()),
+            Some(b"a".to_vec()),
+            Some(b"int4".to_vec())
+        ],
+        vec![
+            Some(b"default".to_vec()),
+            Some(b"b".to_vec()),
+            Some(b"text".to_vec())
+        ],
+        vec![
+            Some(b"default".to_vec()),
+            Some(b"c".to_vec()),
+            Some(b"float8".to_vec())
+        ],
+    ]);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_extended_query() -> Result<()> {
+    let _guard = TestGlobalServices::setup(ConfigBuilder::create().build()).await?;
+
+    let mut handler = PostgresHandler::create()?;
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let listening = handler.start(listening).await?;
+    let mut stream = create_connection(listening.port()).await?;
+
+    // Parse with an int4 parameter.
+    let mut parse = cstr("");
+    parse.extend(cstr("SELECT $1 + 1 AS a, 'it''s $1' AS b"));
+    parse.extend(1i16.to_be_bytes());
+    parse.extend(23i32.to_be_bytes());
+    send_message(&mut stream, b'P', &parse).await?;
+
+    // Bind the text parameter 41, the results in text.
+    let mut bind = cstr("");
+    bind.extend(cstr(""));
+    bind.extend(0i16.to_be_bytes());
+    bind.extend(1i16.to_be_bytes());
+    bind.extend(2i32.to_be_bytes());
+    bind.extend(b"41");
+    bind.extend(0i16.to_be_bytes());
+    send_message(&mut stream, b'B', &bind).await?;
+
+    let mut describe = vec![b'P'];
+    describe.extend(cstr(""));
+    send_message(&mut stream, b'D', &describe).await?;
+
+    let mut execute = cstr("");
+    execute.extend(0i32.to_be_bytes());
+    send_message(&mut stream, b'E', &execute).await?;
+    send_message(&mut stream, b'S', &[]).await?;
+
+    let messages = read_until_ready(&mut stream).await?;
+    let tags = messages.iter().map(|(tag, _)| *tag).collect::<Vec<_>>();
+    assert_eq!(tags, vec![b'1', b'2', b'T', b'D', b'C', b'Z']);
+    assert_eq!(data_row(&messages[3].1), vec![
+        Some(b"42".to_vec()),
+        Some(b"it's $1".to_vec())
+    ]);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_extended_query_number_params() -> Result<()> {
+    let _guard = TestGlobalServices::setup(ConfigBuilder::create().build()).await?;
+
+    let mut handler = PostgresHandler::create()?;
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let listening = handler.start(listening).await?;
+    let mut stream = create_connection(listening.port()).await?;
+
+    // A negative parameter after `-` is not turned into a `--` comment.
+    let query = "SELECT (1 -$1)::INT32 AS a";
+    for (type_oid, format, param) in [
+        (23, 0, b"-1".to_vec()),
+        (23, 1, (-1i32).to_be_bytes().to_vec()),
+        (701, 0, b"-1.0".to_vec()),
+        (701, 1, (-1f64).to_be_bytes().to_vec()),
+    ] {
+        let messages = extended_query(&mut stream, query, type_oid, format, &param).await?;
+        let tags = messages.iter().map(|(tag, _)| *tag).collect::<Vec<_>>();
+        assert_eq!(tags, vec![b'1', b'2', b'T', b'D', b'C', b'Z']);
+        assert_eq!(data_row(&messages[3].1)[0].as_deref(), Some(&b"2"[..]));
+    }
+
+    // Non-finite numbers and text that isn't a number are rejected.
+    for (type_oid, format, param) in [
+        (701, 0, b"NaN".to_vec()),
+        (701, 0, b"-inf".to_vec()),
+        (701, 1, f64::NAN.to_be_bytes().to_vec()),
+        (23, 0, b"1; DROP TABLE t".to_vec()),
+    ] {
+        let messages = extended_query(&mut stream, query, type_oid, format, &param).await?;
+        let tags = messages.iter().map(|(tag, _)| *tag).collect::<Vec<_>>();
+        assert_eq!(tags, vec![b'1', b'E', b'Z']);
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_scram_sha256_authentication() -> Result<()> {
+    let _guard = TestGlobalServices::setup(ConfigBuilder::create().build()).await?;
+
+    let mut handler = PostgresHandler::create()?;
+    let listening = handler.start("127.0.0.1:0".parse::<SocketAddr>()?).await?;
+    let mut stream = create_connection(listening.port()).await?;
+    send_message(
+        &mut stream,
+        b'Q',
+        &cstr("CREATE USER u_scram IDENTIFIED WITH scram_sha256_password BY 'pencil'"),
+    )
+    .await?;
+    let messages = read_until_ready(&mut stream).await?;
+    assert_eq!(messages[0].0, b'C');
+
+    // The right password is accepted and the server proves it knows the secret.
+    let mut stream = start_connection(listening.port(), "u_scram").await?;
+    let server_signature = scram_exchange(&mut stream, "pencil").await?;
+    let messages = read_until_ready(&mut stream).await?;
+    let mut final_message = 12i32.to_be_bytes().to_vec();
+    final_message.extend(format!("v={}", base64::encode(server_signature)).as_bytes());
+    assert_eq!(messages[0], (b'R', final_message));
+    assert_eq!(messages[1], (b'R', 0i32.to_be_bytes().to_vec()));
+    assert_eq!(messages.last().unwrap().0, b'Z');
+
+    // The wrong password is rejected.
+    let mut stream = start_connection(listening.port(), "u_scram").await?;
+    scram_exchange(&mut stream, "pencil2").await?;
+    let (tag, _) = read_message(&mut stream).await?;
+    assert_eq!(tag, b'E');
+
+    Ok(())
+}
+
+// Sends the startup message of the user.
+/// Parse, bind and execute the query with a parameter of the type and format, until Sync.
+async fn extended_query(
+    stream: &mut TcpStream,
+    query: &str,
+    type_oid: i32,
+    format: i16,
+    param: &[u8],
+) -> Result<Vec<(u8, Vec<u8>)>> {
+    let mut parse = cstr("");
+    parse.extend(cstr(query));
+    parse.extend(1i16.to_be_bytes());
+    parse.extend(type_oid.to_be_bytes());
+    send_message(stream, b'P', &parse).await?;
+
+    let mut bind = cstr("");
+    bind.extend(cstr(""));
+    bind.extend(1i16.to_be_bytes());
+    bind.extend(format.to_be_bytes());
+    bind.extend(1i16.to_be_bytes());
+    bind.extend((param.len() as i32).to_be_bytes());
+    bind.extend(param);
+    bind.extend(0i16.to_be_bytes());
+    send_message(stream, b'B', &bind).await?;
+
+    let mut describe = vec![b'P'];
+    describe.extend(cstr(""));
+    send_message(stream, b'D', &describe).await?;
+
+    let mut execute = cstr("");
+    execute.extend(0i32.to_be_bytes());
+    send_message(stream, b'E', &execute).await?;
+    send_message(stream, b'S', &[]).await?;
+
+    read_until_ready(stream).await
+}
+
+async fn start_connection(port: u16, user: &str) -> Result<TcpStream> {
+    let mut stream = TcpStream::connect(format!("127.0.0.1:{}", port)).await?;
+
+    let mut startup = 196608i32.to_be_bytes().to_vec();
+    startup.extend(cstr("user"));
+    startup.extend(cstr(user));
+    startup.push(0);
+    stream
+        .write_all(&(startup.len() as i32 + 4).to_be_bytes())
+        .await?;
+    stream.write_all(&startup).await?;
+    Ok(stream)
+}
+
+// The client side of SCRAM-SHA-256 until the client final message, returns the expected
+// server signature.
+async fn scram_exchange(stream: &mut TcpStream, password: &str) -> Result<Vec<u8>> {
+    let (tag, body) = read_message(stream).await?;
+    let mut sasl = 10i32.to_be_bytes().to_vec();
+    sasl.extend(cstr("SCRAM-SHA-256"));
+    sasl.push(0);
+    assert_eq!((tag, body), (b'R', sasl));
+
+    let client_first_bare = "n=,r=fyko+d2lbbFgONRv9qkxdawL";
+    let client_first = format!("n,,{}", client_first_bare);
+    let mut initial_response = cstr("SCRAM-SHA-256");
+    initial_response.extend((client_first.len() as i32).to_be_bytes());
+    initial_response.extend(client_first.as_bytes());
+    send_message(stream, b'p', &initial_response).await?;
+
+    let (tag, body) = read_message(stream).await?;
+    assert_eq!(tag, b'R');
+    assert_eq!(body[..4], 11i32.to_be_bytes());
+    let server_first = String::from_utf8(body[4..].to_vec()).unwrap();
+    let attribute = |name: &str| {
+        server_first
+            .split(',')
+            .find_map(|attribute| attribute.strip_prefix(name))
+            .unwrap()
+            .to_string()
+    };
+    let nonce = attribute("r=");
+    let salt = base64::decode(attribute("s=")).unwrap();
+    let iterations = attribute("i=").parse::<u32>().unwrap();
+    assert!(nonce.starts_with("fyko+d2lbbFgONRv9qkxdawL"));
+
+    // Hi(), PBKDF2 with HMAC-SHA-256.
+    let mut block = salt.clone();
+    block.extend(1u32.to_be_bytes());
+    let mut u = hmac_sha256(password.as_bytes(), &block);
+    let mut salted_password = u.clone();
+    for _ in 1..iterations {
+        u = hmac_sha256(password.as_bytes(), &u);
+        salted_password
+            .iter_mut()
+            .zip(&u)
+            .for_each(|(s, u)| *s ^= u);
+    }
+
+    let client_final_without_proof = format!("c=biws,r={}", nonce);
+    let auth_message = format!(
+        "{},{},{}",
+        client_first_bare, server_first, client_final_without_proof
+    );
+    let client_key = hmac_sha256(&salted_password, b"Client Key");
+    let stored_key = Sha256::digest(&client_key);
+    let client_signature = hmac_sha256(&stored_key, auth_message.as_bytes());
+    let proof = client_key
+        .iter()
+        .zip(&client_signature)
+        .map(|(k, s)| k ^ s)
+        .collect::<Vec<_>>();
+    let client_final = format!("{},p={}", client_final_without_proof, base64::encode(proof));
+    send_message(stream, b'p', client_final.as_bytes()).await?;
+
+    let server_key = hmac_sha256(&salted_password, b"Server Key");
+    Ok(hmac_sha256(&server_key, auth_message.as_bytes()))
+}
+
+fn hmac_sha256(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key).unwrap();
+    Mac::update(&mut mac, message);
+    mac.finalize().into_bytes().to_vec()
+}
+
+async fn create_connection(port: u16) -> Result<TcpStream> {
+    let mut stream = start_connection(port, "root").await?;
+
+    let messages = read_until_ready(&mut stream).await?;
+    // AuthenticationOk, the parameters and ReadyForQuery.
+    assert_eq!(messages[0], (b'R', 0i32.to_be_bytes().to_vec()));
+    assert_eq!(messages.last().unwrap().0, b'Z');
+    Ok(stream)
+}
+
+async fn send_message(stream: &mut TcpStream, tag: u8, body: &[u8]) -> Result<()> {
+    stream.write_all(&[tag]).await?;
+    stream
+        .write_all(&(body.len() as i32 + 4).to_be_bytes())
+        .await?;
+    stream.write_all(body).await?;
+    Ok(())
+}
+
+async fn read_message(stream: &mut TcpStream) -> Result<(u8, Vec<u8>)> {
+    let tag = stream.read_u8().await?;
+    let len = stream.read_i32().await?;
+    let mut body = vec![0; len as usize - 4];
+    stream.read_exact(&mut body).await?;
+    Ok((tag, body))
+}
+
+async fn read_until_ready(stream: &mut TcpStream) -> Result<Vec<(u8, Vec<u8>)>> {
+    let mut messages = vec![];
+    loop {
+        let (tag, body) = read_message(stream).await?;
+        messages.push((tag, body));
+        if tag == b'Z' {
+            return Ok(messages);
+        }
+    }
+}
+
+fn data_row(body: &[u8]) -> Vec<Option<Vec<u8>>> {
+    let num_values = i16::from_be_bytes([body[0], body[1]]);
+    let mut pos = 2;
+    let mut values = vec![];
+    for _ in 0..num_values {
+        let len = i32::from_be_bytes(body[pos..pos + 4].try_into().unwrap());
+        pos += 4;
+        if len < 0 {
+            values.push(None);
+        } else {
+            values.push(Some(body[pos..pos + len as usize].to_vec()));
+            pos += len as usize;
+        }
+    }
+    values
+}
+
+fn cstr(value: &str) -> Vec<u8> {
+    let mut bytes = value.as_bytes().to_vec();
+    bytes.push(0);
+    bytes
+}
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use common_base::base::tokio;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use databend_query::sessions::SessionManager;
 use databend_query::sessions::SessionType;
@@ -35,5 +36,16 @@ async fn test_session_setting() -> Result<()> {
         assert_eq!(actual, expect);
     }
 
+    // The table lock expires in at least one second.
+    {
+        let settings = session.get_settings();
+        let res =
+            settings.set_settings("table_lock_expire_secs".to_string(), "0".to_string(), false);
+        assert_eq!(res.unwrap_err().code(), ErrorCode::WRONG_VALUE_FOR_VARIABLE);
+
+        settings.set_settings("table_lock_expire_secs".to_string(), "1".to_string(), false)?;
+        assert_eq!(settings.get_table_lock_expire_secs()?, 1);
+    }
+
     Ok(())
 }
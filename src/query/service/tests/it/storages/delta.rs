@@ -0,0 +1,159 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+
+use chrono::TimeZone;
+use chrono::Utc;
+use common_base::base::tokio;
+use common_catalog::plan::PushDownInfo;
+use common_catalog::table::NavigationPoint;
+use common_catalog::table::Table;
+use common_catalog::table_context::TableContext;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
+use common_sql::executor::table_read_plan::ToReadDataSourcePlan;
+use common_sql::parse_to_remote_string_exprs;
+use common_storage::StorageFsConfig;
+use common_storage::StorageParams;
+use common_storages_delta::DeltaTable;
+use common_storages_delta::DELTA_ENGINE;
+use common_storages_delta::OPT_KEY_PARTITION_COLUMNS;
+use databend_query::stream::ReadDataBlockStream;
+use futures::TryStreamExt;
+
+/// The delta table under `tests/data`, partitioned by `region`,
+/// the latest version 3 has three data files of 5 rows.
+const TABLE_DIR: &str = "../../../tests/data/delta/sales";
+
+async fn create_delta_table() -> Result<Arc<dyn Table>> {
+    let root = std::fs::canonicalize(TABLE_DIR).unwrap();
+    let storage_params = StorageParams::Fs(StorageFsConfig {
+        root: root.to_str().unwrap().to_string(),
+    });
+    let (schema, partition_columns) = DeltaTable::read_schema(&storage_params).await?;
+    let table = DeltaTable::try_create(TableInfo {
+        desc: "'default'.'sales'".into(),
+        name: "sales".into(),
+        meta: TableMeta {
+            schema,
+            engine: DELTA_ENGINE.to_string(),
+            storage_params: Some(storage_params),
+            options: [(
+                OPT_KEY_PARTITION_COLUMNS.to_string(),
+                partition_columns.join(","),
+            )]
+            .into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    })?;
+    Ok(table.into())
+}
+
+async fn count_rows(ctx: Arc<dyn TableContext>, table: &dyn Table) -> Result<usize> {
+    let plan = table.read_plan(ctx.clone(), None).await?;
+    let stream = table.read_data_block_stream(ctx, &plan).await?;
+    let blocks = stream.try_collect::<Vec<_>>().await?;
+    Ok(blocks.iter().map(|b| b.num_rows()).sum())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_delta_table_read() -> Result<()> {
+    let (_guard, ctx) = crate::tests::create_query_context().await?;
+    let table = create_delta_table().await?;
+    assert_eq!(table.engine(), DELTA_ENGINE);
+
+    let (stats, _) = table.read_partitions(ctx.clone(), None).await?;
+    assert_eq!(stats.partitions_total, 3);
+    assert_eq!(stats.partitions_scanned, 3);
+    assert_eq!(count_rows(ctx.clone(), table.as_ref()).await?, 5);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_delta_table_pruning() -> Result<()> {
+    let (_guard, ctx) = crate::tests::create_query_context().await?;
+    let table = create_delta_table().await?;
+
+    let cases = [
+        // pruned by the partition values.
+        ("region = 'europe'", 1),
+        ("region = 'africa'", 0),
+        ("region is null", 1),
+        // pruned by the statistics of the data files.
+        ("id > 4", 1),
+        ("amount < 15", 1),
+        ("id > 0", 3),
+    ];
+    for (filter, expected) in cases {
+        let push_downs = PushDownInfo {
+            filters: parse_to_remote_string_exprs(ctx.clone(), table.clone(), false, filter)?,
+            ..Default::default()
+        };
+        let (stats, parts) = table.read_partitions(ctx.clone(), Some(push_downs)).await?;
+        assert_eq!(stats.partitions_total, 3, "filter: {}", filter);
+        assert_eq!(stats.partitions_scanned, expected, "filter: {}", filter);
+        assert_eq!(parts.len(), expected, "filter: {}", filter);
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_delta_table_navigate_to() -> Result<()> {
+    let (_guard, ctx) = crate::tests::create_query_context().await?;
+    let table = create_delta_table().await?;
+
+    // version 1 is loaded from the checkpoint.
+    let v1 = table
+        .navigate_to(&NavigationPoint::SnapshotID("1".to_string()))
+        .await?;
+    let (stats, _) = v1.read_partitions(ctx.clone(), None).await?;
+    assert_eq!(stats.partitions_total, 2);
+    assert_eq!(count_rows(ctx.clone(), v1.as_ref()).await?, 4);
+
+    // 2023-03-15 is between the commits of version 2 and 3.
+    let time_point = Utc.timestamp_millis_opt(1678838400000).unwrap();
+    let v2 = table
+        .navigate_to(&NavigationPoint::TimePoint(time_point))
+        .await?;
+    assert_eq!(
+        v2.get_table_info().meta.engine_options.get("version"),
+        Some(&"2".to_string())
+    );
+    let (stats, _) = v2.read_partitions(ctx.clone(), None).await?;
+    assert_eq!(stats.partitions_total, 2);
+
+    // the commit of version 0 is cleaned up, version 4 doesn't exist yet.
+    for id in ["0", "4", "abc"] {
+        let err = match table
+            .navigate_to(&NavigationPoint::SnapshotID(id.to_string()))
+            .await
+        {
+            Ok(_) => panic!("version {} should not be found", id),
+            Err(e) => e,
+        };
+        assert_eq!(
+            err.code(),
+            ErrorCode::TABLE_HISTORICAL_DATA_NOT_FOUND,
+            "version: {}",
+            id
+        );
+    }
+
+    Ok(())
+}
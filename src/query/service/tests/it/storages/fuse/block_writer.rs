@@ -87,6 +87,8 @@ impl<'a> BlockWriter<'a> {
             location,
             Some(bloom_filter_index_location),
             bloom_filter_index_size,
+            None,
+            0,
             Compression::Lz4Raw,
         );
         Ok(block_meta)
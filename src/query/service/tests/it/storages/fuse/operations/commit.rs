@@ -26,6 +26,7 @@ use common_catalog::plan::DataSourcePlan;
 use common_catalog::plan::PartInfoPtr;
 use common_catalog::plan::Partitions;
 use common_catalog::table::Table;
+use common_catalog::table_context::PlaceholderValue;
 use common_catalog::table_context::ProcessInfo;
 use common_catalog::table_context::StageAttachment;
 use common_catalog::table_context::TableContext;
@@ -65,6 +66,7 @@ use common_meta_app::schema::UpsertTableOptionReply;
 use common_meta_app::schema::UpsertTableOptionReq;
 use common_meta_types::MetaId;
 use common_meta_types::RoleInfo;
+use common_meta_types::TenantQuota;
 use common_meta_types::UserInfo;
 use common_settings::Settings;
 use common_storage::DataOperator;
@@ -459,9 +461,25 @@ impl TableContext for CtxDelegation {
         todo!()
     }
 
+    async fn get_tenant_quota(&self) -> Result<TenantQuota> {
+        self.ctx.get_tenant_quota().await
+    }
+
     fn get_stage_attachment(&self) -> Option<StageAttachment> {
         todo!()
     }
+
+    fn get_placeholders(&self) -> Vec<PlaceholderValue> {
+        todo!()
+    }
+
+    fn consume_stream(&self, _offset: UpsertTableOptionReq) {
+        todo!()
+    }
+
+    fn get_consumed_streams(&self) -> Vec<UpsertTableOptionReq> {
+        vec![]
+    }
 }
 
 #[derive(Clone)]
@@ -22,6 +22,7 @@ mod navigate;
 mod optimize;
 mod purge_drop;
 mod purge_truncate;
+mod quota;
 mod read_plan;
 mod table_analyze;
 mod truncate;
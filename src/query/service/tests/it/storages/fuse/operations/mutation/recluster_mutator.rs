@@ -65,6 +65,8 @@ async fn test_recluster_mutator_block_select() -> Result<()> {
             location.clone(),
             None,
             0,
+            None,
+            0,
             meta::Compression::Lz4Raw,
         ));
         let segment = SegmentInfo::new(vec![test_block_meta], Statistics::default());
@@ -124,6 +126,7 @@ async fn test_recluster_mutator_block_select() -> Result<()> {
         schema,
         &None,
         segments_location,
+        vec![],
     )
     .await?;
     let mut blocks_map: BTreeMap<i32, Vec<(usize, Arc<BlockMeta>)>> = BTreeMap::new();
@@ -0,0 +1,198 @@
+//  Copyright 2023 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use common_base::base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::AuthInfo;
+use common_meta_types::GrantObject;
+use common_meta_types::PasswordHashMethod;
+use common_meta_types::TenantQuota;
+use common_meta_types::UserInfo;
+use common_meta_types::UserOptionFlag;
+use common_meta_types::UserPrivilegeSet;
+use common_users::UserApiProvider;
+use databend_query::sessions::TableContext;
+
+use crate::storages::fuse::table_test_fixture::execute_command;
+use crate::storages::fuse::table_test_fixture::execute_query;
+use crate::storages::fuse::table_test_fixture::expects_err;
+use crate::storages::fuse::table_test_fixture::expects_ok;
+use crate::storages::fuse::table_test_fixture::TestFixture;
+use crate::tests::ConfigBuilder;
+
+async fn set_quota(tenant: &str, quota: TenantQuota) -> Result<()> {
+    let quota_api = UserApiProvider::instance().get_tenant_quota_api_client(tenant)?;
+    let res = quota_api.get_quota(None).await?;
+    quota_api.set_quota(&quota, Some(res.seq)).await?;
+    Ok(())
+}
+
+fn tenant_setting_user() -> UserInfo {
+    let mut user_info = UserInfo::new("root", "127.0.0.1", AuthInfo::Password {
+        hash_method: PasswordHashMethod::Sha256,
+        hash_value: Vec::from("pass"),
+    });
+    user_info.grants.grant_privileges(
+        &GrantObject::Global,
+        UserPrivilegeSet::available_privileges_on_global(),
+    );
+    user_info
+        .option
+        .set_option_flag(UserOptionFlag::TenantSetting);
+    user_info
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fuse_table_quota_exceeded() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+    let tenant = ctx.get_tenant();
+    let db = fixture.default_db_name();
+
+    execute_command(ctx.clone(), &format!("create table {}.t(a int)", db)).await?;
+    execute_command(ctx.clone(), &format!("insert into {}.t values(1)", db)).await?;
+
+    let insert = format!("insert into {}.t values(2),(3)", db);
+    let count = format!("select count(*) from {}.t", db);
+
+    // Rows per table.
+    set_quota(&tenant, TenantQuota {
+        max_rows_per_table: 2,
+        ..Default::default()
+    })
+    .await?;
+    expects_err(
+        "max_rows_per_table",
+        ErrorCode::STORAGE_QUOTA_EXCEEDED,
+        execute_command(ctx.clone(), &insert).await,
+    );
+
+    // Storage per database.
+    set_quota(&tenant, TenantQuota {
+        max_storage_per_database_in_bytes: 1,
+        ..Default::default()
+    })
+    .await?;
+    expects_err(
+        "max_storage_per_database_in_bytes",
+        ErrorCode::STORAGE_QUOTA_EXCEEDED,
+        execute_command(ctx.clone(), &insert).await,
+    );
+
+    // Storage of the tenant.
+    set_quota(&tenant, TenantQuota {
+        max_storage_in_bytes: 1,
+        ..Default::default()
+    })
+    .await?;
+    expects_err(
+        "max_storage_in_bytes",
+        ErrorCode::STORAGE_QUOTA_EXCEEDED,
+        execute_command(ctx.clone(), &insert).await,
+    );
+
+    // Nothing was committed by the rejected inserts.
+    let expected = vec![
+        "+----------+",
+        "| Column 0 |",
+        "+----------+",
+        "| 1_u64    |",
+        "+----------+",
+    ];
+    expects_ok(
+        "rejected inserts",
+        execute_query(ctx.clone(), &count).await,
+        expected,
+    )
+    .await?;
+
+    // Without quotas the insert goes through.
+    set_quota(&tenant, TenantQuota::default()).await?;
+    execute_command(ctx.clone(), &insert).await?;
+    let expected = vec![
+        "+----------+",
+        "| Column 0 |",
+        "+----------+",
+        "| 3_u64    |",
+        "+----------+",
+    ];
+    expects_ok(
+        "accepted insert",
+        execute_query(ctx.clone(), &count).await,
+        expected,
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_tenant_quota_requires_management_mode() -> Result<()> {
+    // Even with the TENANTSETTING flag, a tenant can't raise its own quota.
+    let (_guard, ctx) = crate::tests::create_query_context_with_config(
+        ConfigBuilder::create().config(),
+        Some(tenant_setting_user()),
+    )
+    .await?;
+    let tenant = ctx.get_tenant();
+
+    set_quota(&tenant, TenantQuota {
+        max_storage_in_bytes: 1,
+        ..Default::default()
+    })
+    .await?;
+
+    let call = format!("call admin$tenant_quota('{}', 0, 0, 0, 0, 0, 0, 0)", tenant);
+    expects_err(
+        "call tenant_quota out of management mode",
+        ErrorCode::MANAGEMENT_MODE_PERMISSION_DENIED,
+        execute_command(ctx.clone(), &call).await,
+    );
+
+    let quota_api = UserApiProvider::instance().get_tenant_quota_api_client(&tenant)?;
+    assert_eq!(
+        quota_api.get_quota(None).await?.data.max_storage_in_bytes,
+        1
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_tenant_quota_in_management_mode() -> Result<()> {
+    let (_guard, ctx) = crate::tests::create_query_context_with_config(
+        ConfigBuilder::create().with_management_mode().config(),
+        Some(tenant_setting_user()),
+    )
+    .await?;
+    let tenant = ctx.get_tenant();
+
+    let call = format!(
+        "call admin$tenant_quota('{}', 0, 0, 0, 0, 100, 10, 5)",
+        tenant
+    );
+    execute_command(ctx.clone(), &call).await?;
+
+    let quota_api = UserApiProvider::instance().get_tenant_quota_api_client(&tenant)?;
+    let quota = quota_api.get_quota(None).await?.data;
+    assert_eq!(quota, TenantQuota {
+        max_storage_in_bytes: 100,
+        max_storage_per_database_in_bytes: 10,
+        max_rows_per_table: 5,
+        ..Default::default()
+    });
+
+    Ok(())
+}
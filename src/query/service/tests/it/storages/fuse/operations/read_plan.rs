@@ -91,6 +91,8 @@ fn test_to_partitions() -> Result<()> {
         location,
         bloom_filter_location,
         bloom_filter_size,
+        None,
+        0,
         meta::Compression::Lz4Raw,
     ));
 
@@ -59,7 +59,7 @@ async fn apply_block_pruning(
 ) -> Result<Vec<Arc<BlockMeta>>> {
     let ctx: Arc<dyn TableContext> = ctx;
     let segment_locs = table_snapshot.segments.clone();
-    BlockPruner::prune(&ctx, op, schema, push_down, segment_locs)
+    BlockPruner::prune(&ctx, op, schema, push_down, segment_locs, vec![])
         .await
         .map(|v| v.into_iter().map(|(_, v)| v).collect())
 }
@@ -31,6 +31,9 @@ use common_expression::FunctionContext;
 use common_expression::Literal;
 use common_expression::RawExpr;
 use common_expression::Scalar;
+use common_expression::TableDataType;
+use common_expression::TableField;
+use common_expression::TableSchemaRefExt;
 use common_functions::aggregates::eval_aggr;
 use common_functions::scalars::BUILTIN_FUNCTIONS;
 use common_sql::evaluator::BlockOperator;
@@ -64,7 +67,12 @@ fn test_ft_stats_block_stats() -> common_exception::Result<()> {
         StringType::from_data(vec!["aa", "aa", "bb"]),
     ]);
 
-    let r = gen_columns_statistics(&block, None)?;
+    let schema = TableSchemaRefExt::create(vec![
+        TableField::new("a", TableDataType::Number(NumberDataType::Int32)),
+        TableField::new("b", TableDataType::String),
+    ]);
+
+    let r = gen_columns_statistics(&block, None, &schema)?;
     assert_eq!(2, r.len());
     let col_stats = r.get(&0).unwrap();
     assert_eq!(col_stats.min, Scalar::Number(NumberScalar::Int32(1)));
@@ -86,7 +94,11 @@ fn test_ft_stats_block_stats_with_column_distinct_count() -> common_exception::R
     let mut column_distinct_count = HashMap::new();
     column_distinct_count.insert(0, 3);
     column_distinct_count.insert(1, 2);
-    let r = gen_columns_statistics(&block, Some(column_distinct_count))?;
+    let schema = TableSchemaRefExt::create(vec![
+        TableField::new("a", TableDataType::Number(NumberDataType::Int32)),
+        TableField::new("b", TableDataType::String),
+    ]);
+    let r = gen_columns_statistics(&block, Some(column_distinct_count), &schema)?;
     assert_eq!(2, r.len());
     let col_stats = r.get(&0).unwrap();
     assert_eq!(col_stats.min, Scalar::Number(NumberScalar::Int32(1)));
@@ -111,8 +123,15 @@ fn test_ft_tuple_stats_block_stats() -> common_exception::Result<()> {
     };
 
     let block = DataBlock::new_from_columns(vec![column]);
+    let schema = TableSchemaRefExt::create(vec![TableField::new("t", TableDataType::Tuple {
+        fields_name: vec!["a".to_string(), "b".to_string()],
+        fields_type: vec![
+            TableDataType::Number(NumberDataType::Int32),
+            TableDataType::Number(NumberDataType::Int32),
+        ],
+    })]);
 
-    let r = gen_columns_statistics(&block, None)?;
+    let r = gen_columns_statistics(&block, None, &schema)?;
     assert_eq!(2, r.len());
     let col0_stats = r.get(&0).unwrap();
     assert_eq!(col0_stats.min, Scalar::Number(NumberScalar::Int32(1)));
@@ -130,11 +149,11 @@ fn test_ft_stats_col_stats_reduce() -> common_exception::Result<()> {
     let rows_per_block = 3;
     let val_start_with = 1;
 
-    let (_, blocks) =
+    let (schema, blocks) =
         TestFixture::gen_sample_blocks_ex(num_of_blocks, rows_per_block, val_start_with);
     let col_stats = blocks
         .iter()
-        .map(|b| gen_columns_statistics(&b.clone().unwrap(), None))
+        .map(|b| gen_columns_statistics(&b.clone().unwrap(), None, &schema))
         .collect::<common_exception::Result<Vec<_>>>()?;
     let r = reducers::reduce_block_statistics(&col_stats, None);
     assert!(r.is_ok());
@@ -215,9 +234,9 @@ async fn test_accumulator() -> common_exception::Result<()> {
     let loc_generator = TableMetaLocationGenerator::with_prefix("/".to_owned());
     for item in blocks {
         let block = item?;
-        let col_stats = gen_columns_statistics(&block, None)?;
+        let col_stats = gen_columns_statistics(&block, None, &schema)?;
         let block_statistics =
-            BlockStatistics::from(&block, "does_not_matter".to_owned(), None, None)?;
+            BlockStatistics::from(&block, "does_not_matter".to_owned(), None, None, &schema)?;
         let block_writer = BlockWriter::new(&operator, &loc_generator);
         let block_meta = block_writer
             .write(FuseStorageFormat::Parquet, &schema, block, col_stats, None)
@@ -418,7 +437,8 @@ fn test_ft_stats_block_stats_string_columns_trimming_using_eval() -> common_exce
         let max_expr = max_col.0.index(0).unwrap();
 
         // generate the statistics of column
-        let stats_of_columns = gen_columns_statistics(&block, None).unwrap();
+        let schema = TableSchemaRefExt::create(vec![TableField::new("a", TableDataType::String)]);
+        let stats_of_columns = gen_columns_statistics(&block, None, &schema).unwrap();
 
         // check if the max value (untrimmed) is in degenerated condition:
         // - the length of string value is larger or equal than STRING_PREFIX_LEN
@@ -518,6 +538,8 @@ fn test_reduce_block_meta() -> common_exception::Result<()> {
             location.clone(),
             None,
             bloom_filter_index_size,
+            None,
+            0,
             Compression::Lz4Raw,
         );
         blocks.push(block_meta);
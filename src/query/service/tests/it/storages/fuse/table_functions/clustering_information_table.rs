@@ -24,6 +24,7 @@ use common_expression::Scalar;
 use common_expression::SendableDataBlockStream;
 use common_sql::executor::table_read_plan::ToReadDataSourcePlan;
 use common_storages_fuse::table_functions::ClusteringInformationTable;
+use common_storages_fuse::FuseTable;
 use databend_query::sessions::QueryContext;
 use databend_query::stream::ReadDataBlockStream;
 use databend_query::table_functions::TableArgs;
@@ -108,6 +109,54 @@ async fn test_clustering_information_table_read() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_need_recluster() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+
+    // no block.
+    let table = fixture.latest_default_table().await?;
+    assert!(
+        !FuseTable::try_from_table(table.as_ref())?
+            .need_recluster(ctx.clone())
+            .await?
+    );
+
+    // a single block is not overlapped by others.
+    let qry = format!("insert into {}.{} values(1, (2, 3)),(3, (4, 6))", db, tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let table = fixture.latest_default_table().await?;
+    assert!(
+        !FuseTable::try_from_table(table.as_ref())?
+            .need_recluster(ctx.clone())
+            .await?
+    );
+
+    // the block [2, 2] lies in the block [1, 3].
+    let qry = format!("insert into {}.{} values(2, (1, 1))", db, tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let table = fixture.latest_default_table().await?;
+    assert!(
+        FuseTable::try_from_table(table.as_ref())?
+            .need_recluster(ctx.clone())
+            .await?
+    );
+
+    let qry = format!("alter table {}.{} recluster final", db, tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let table = fixture.latest_default_table().await?;
+    assert!(
+        !FuseTable::try_from_table(table.as_ref())?
+            .need_recluster(ctx.clone())
+            .await?
+    );
+
+    Ok(())
+}
+
 async fn test_drive_clustering_information(
     tbl_args: TableArgs,
     ctx: Arc<QueryContext>,
@@ -15,10 +15,14 @@
 use common_exception::Result;
 use common_expression::types::number::Float64Type;
 use common_expression::types::number::Int64Type;
+use common_expression::types::NumberDataType;
 use common_expression::Column;
 use common_expression::DataBlock;
 use common_expression::FromData;
 use common_expression::Scalar;
+use common_expression::TableDataType;
+use common_expression::TableField;
+use common_expression::TableSchemaRefExt;
 use databend_query::storages::fuse::statistics::gen_columns_statistics;
 
 fn gen_sample_block() -> (DataBlock, Vec<Column>) {
@@ -62,7 +66,22 @@ fn gen_sample_block() -> (DataBlock, Vec<Column>) {
 #[test]
 fn test_column_statistic() -> Result<()> {
     let (sample_block, sample_cols) = gen_sample_block();
-    let col_stats = gen_columns_statistics(&sample_block, None)?;
+    let int64 = TableDataType::Number(NumberDataType::Int64);
+    let float64 = TableDataType::Number(NumberDataType::Float64);
+    let b = TableDataType::Tuple {
+        fields_name: vec!["c".to_string(), "d".to_string()],
+        fields_type: vec![int64.clone(), float64.clone()],
+    };
+    let a = TableDataType::Tuple {
+        fields_name: vec!["b".to_string(), "e".to_string()],
+        fields_type: vec![b, float64.clone()],
+    };
+    let schema = TableSchemaRefExt::create(vec![
+        TableField::new("a", a),
+        TableField::new("f", int64),
+        TableField::new("g", float64),
+    ]);
+    let col_stats = gen_columns_statistics(&sample_block, None, &schema)?;
 
     assert_eq!(5, col_stats.len());
 
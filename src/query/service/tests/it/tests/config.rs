@@ -95,6 +95,16 @@ impl ConfigBuilder {
         self
     }
 
+    pub fn mysql_handler_tls_server_key(mut self, value: impl Into<String>) -> ConfigBuilder {
+        self.conf.query.mysql_handler_tls_server_key = value.into();
+        self
+    }
+
+    pub fn mysql_handler_tls_server_cert(mut self, value: impl Into<String>) -> ConfigBuilder {
+        self.conf.query.mysql_handler_tls_server_cert = value.into();
+        self
+    }
+
     pub fn http_handler_tls_server_key(mut self, value: impl Into<String>) -> ConfigBuilder {
         self.conf.query.http_handler_tls_server_key = value.into();
         self
@@ -170,6 +170,17 @@ impl Settings {
                 desc: "The retention_period in hours. By default the value is 12 hours.",
                 possible_values: None,
             },
+            // table_lock_expire_secs
+            SettingValue {
+                default_value: UserSettingValue::UInt64(5),
+                user_setting: UserSetting::create(
+                    "table_lock_expire_secs",
+                    UserSettingValue::UInt64(5),
+                ),
+                level: ScopeLevel::Session,
+                desc: "The seconds a table lock expires in if it is not extended, at least 1, default value: 5.",
+                possible_values: None,
+            },
             // max_storage_io_requests
             SettingValue {
                 default_value: UserSettingValue::UInt64(default_max_storage_io_requests),
@@ -274,6 +285,46 @@ impl Settings {
                 desc: "The threshold of keys to open two-level aggregation, default value: 10000.",
                 possible_values: None,
             },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(0),
+                user_setting: UserSetting::create(
+                    "group_by_spilling_bytes_threshold",
+                    UserSettingValue::UInt64(0),
+                ),
+                level: ScopeLevel::Session,
+                desc: "The memory usage in bytes to spill the two-level aggregation to disk, 0 means never spill, default value: 0.",
+                possible_values: None,
+            },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(0),
+                user_setting: UserSetting::create(
+                    "join_spilling_bytes_threshold",
+                    UserSettingValue::UInt64(0),
+                ),
+                level: ScopeLevel::Session,
+                desc: "The build side size in bytes to partition the hash join to disk, 0 means never spill, default value: 0.",
+                possible_values: None,
+            },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(0),
+                user_setting: UserSetting::create(
+                    "sort_spilling_bytes_threshold",
+                    UserSettingValue::UInt64(0),
+                ),
+                level: ScopeLevel::Session,
+                desc: "The memory budget in bytes of each sort processor to spill sorted runs to disk, 0 means never spill, default value: 0.",
+                possible_values: None,
+            },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(1000),
+                user_setting: UserSetting::create(
+                    "max_recursive_cte_iterations",
+                    UserSettingValue::UInt64(1000),
+                ),
+                level: ScopeLevel::Session,
+                desc: "The maximum number of iterations to evaluate the recursive member of a recursive CTE, default value: 1000.",
+                possible_values: None,
+            },
             SettingValue {
                 default_value: UserSettingValue::UInt64(3),
                 user_setting: UserSetting::create("max_inlist_to_or", UserSettingValue::UInt64(3)),
@@ -405,6 +456,16 @@ impl Settings {
                 desc: "If enable distributed eval index, default value: 1",
                 possible_values: None,
             },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(1),
+                user_setting: UserSetting::create(
+                    "enable_aggregating_index_scan",
+                    UserSettingValue::UInt64(1),
+                ),
+                level: ScopeLevel::Session,
+                desc: "If enable answering aggregations from materialized views, default value: 1",
+                possible_values: None,
+            },
             SettingValue {
                 default_value: UserSettingValue::UInt64(0),
                 user_setting: UserSetting::create(
@@ -542,6 +603,11 @@ impl Settings {
         self.try_get_u64(key)
     }
 
+    pub fn get_table_lock_expire_secs(&self) -> Result<u64> {
+        let key = "table_lock_expire_secs";
+        self.try_get_u64(key)
+    }
+
     pub fn get_max_storage_io_requests(&self) -> Result<u64> {
         let key = "max_storage_io_requests";
         self.try_get_u64(key)
@@ -627,6 +693,54 @@ impl Settings {
         self.try_set_u64(key, val, false)
     }
 
+    // Get group by spilling bytes threshold
+    pub fn get_group_by_spilling_bytes_threshold(&self) -> Result<u64> {
+        let key = "group_by_spilling_bytes_threshold";
+        self.try_get_u64(key)
+    }
+
+    // Set group by spilling bytes threshold
+    pub fn set_group_by_spilling_bytes_threshold(&self, val: u64) -> Result<()> {
+        let key = "group_by_spilling_bytes_threshold";
+        self.try_set_u64(key, val, false)
+    }
+
+    // Get join spilling bytes threshold
+    pub fn get_join_spilling_bytes_threshold(&self) -> Result<u64> {
+        let key = "join_spilling_bytes_threshold";
+        self.try_get_u64(key)
+    }
+
+    // Set join spilling bytes threshold
+    pub fn set_join_spilling_bytes_threshold(&self, val: u64) -> Result<()> {
+        let key = "join_spilling_bytes_threshold";
+        self.try_set_u64(key, val, false)
+    }
+
+    // Get sort spilling bytes threshold
+    pub fn get_sort_spilling_bytes_threshold(&self) -> Result<u64> {
+        let key = "sort_spilling_bytes_threshold";
+        self.try_get_u64(key)
+    }
+
+    // Set sort spilling bytes threshold
+    pub fn set_sort_spilling_bytes_threshold(&self, val: u64) -> Result<()> {
+        let key = "sort_spilling_bytes_threshold";
+        self.try_set_u64(key, val, false)
+    }
+
+    // Get max recursive cte iterations
+    pub fn get_max_recursive_cte_iterations(&self) -> Result<u64> {
+        let key = "max_recursive_cte_iterations";
+        self.try_get_u64(key)
+    }
+
+    // Set max recursive cte iterations
+    pub fn set_max_recursive_cte_iterations(&self, val: u64) -> Result<()> {
+        let key = "max_recursive_cte_iterations";
+        self.try_set_u64(key, val, false)
+    }
+
     pub fn get_max_inlist_to_or(&self) -> Result<u64> {
         let key = "max_inlist_to_or";
         self.try_get_u64(key)
@@ -704,6 +818,12 @@ impl Settings {
         self.try_set_u64(KEY, v, false)
     }
 
+    pub fn get_enable_aggregating_index_scan(&self) -> Result<bool> {
+        static KEY: &str = "enable_aggregating_index_scan";
+        let v = self.try_get_u64(KEY)?;
+        Ok(v != 0)
+    }
+
     pub fn get_enable_cbo(&self) -> Result<bool> {
         static KEY: &str = "enable_cbo";
         let v = self.try_get_u64(KEY)?;
@@ -812,6 +932,22 @@ impl Settings {
         Ok(val)
     }
 
+    // Check the lower bound of the u64 settings which can't be zero.
+    fn check_min_value(key: &str, val: u64) -> Result<()> {
+        let min_value = match key {
+            // The table lock heartbeat extends the lease every third of it.
+            "table_lock_expire_secs" => 1,
+            _ => return Ok(()),
+        };
+        if val < min_value {
+            return Err(ErrorCode::WrongValueForVariable(format!(
+                "Variable {:?} can't be set to {}, the minimum value is {}",
+                key, val, min_value
+            )));
+        }
+        Ok(())
+    }
+
     // Get u64 value, we don't get from the metasrv.
     fn try_get_u64(&self, key: &str) -> Result<u64> {
         let setting = self.check_and_get_setting_value(key)?;
@@ -983,6 +1119,7 @@ impl Settings {
         match setting.user_setting.value {
             UserSettingValue::UInt64(_) => {
                 let u64_val = val.parse::<u64>()?;
+                Self::check_min_value(&key, u64_val)?;
                 self.try_set_u64(&key, u64_val, is_global)?
             }
             UserSettingValue::String(_) => {
@@ -31,9 +31,11 @@ use super::HashJoin;
 use super::Limit;
 use super::PhysicalPlan;
 use super::Project;
+use super::RecursiveCte;
 use super::Sort;
 use super::TableScan;
 use super::UnionAll;
+use super::Window;
 use crate::executor::explain::PlanStatsInfo;
 use crate::executor::FragmentKind;
 use crate::planner::MetadataRef;
@@ -56,9 +58,14 @@ fn to_format_tree(plan: &PhysicalPlan, metadata: &MetadataRef) -> Result<FormatT
         PhysicalPlan::AggregateFinal(plan) => aggregate_final_to_format_tree(plan, metadata),
         PhysicalPlan::Sort(plan) => sort_to_format_tree(plan, metadata),
         PhysicalPlan::Limit(plan) => limit_to_format_tree(plan, metadata),
+        PhysicalPlan::Window(plan) => window_to_format_tree(plan, metadata),
         PhysicalPlan::HashJoin(plan) => hash_join_to_format_tree(plan, metadata),
         PhysicalPlan::Exchange(plan) => exchange_to_format_tree(plan, metadata),
         PhysicalPlan::UnionAll(plan) => union_all_to_format_tree(plan, metadata),
+        PhysicalPlan::RecursiveCte(plan) => recursive_cte_to_format_tree(plan, metadata),
+        PhysicalPlan::RecursiveCteScan(_) => {
+            Ok(FormatTreeNode::new("RecursiveCteScan".to_string()))
+        }
         PhysicalPlan::ExchangeSource(_)
         | PhysicalPlan::ExchangeSink(_)
         | PhysicalPlan::DistributedInsertSelect(_) => {
@@ -376,6 +383,57 @@ fn sort_to_format_tree(plan: &Sort, metadata: &MetadataRef) -> Result<FormatTree
     Ok(FormatTreeNode::with_children("Sort".to_string(), children))
 }
 
+fn window_to_format_tree(plan: &Window, metadata: &MetadataRef) -> Result<FormatTreeNode<String>> {
+    let column_name = |index| match metadata.read().column(index).clone() {
+        ColumnEntry::BaseTableColumn { column_name, .. } => column_name,
+        ColumnEntry::DerivedColumn { alias, .. } => alias,
+    };
+
+    let partition_by = plan
+        .partition_by
+        .iter()
+        .map(|index| column_name(*index))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let order_by = plan
+        .order_by
+        .iter()
+        .map(|sort_key| {
+            format!(
+                "{} {} {}",
+                column_name(sort_key.order_by),
+                if sort_key.asc { "ASC" } else { "DESC" },
+                if sort_key.nulls_first {
+                    "NULLS FIRST"
+                } else {
+                    "NULLS LAST"
+                }
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let mut children = vec![
+        FormatTreeNode::new(format!("window function: {}", column_name(plan.index))),
+        FormatTreeNode::new(format!("partition by: [{partition_by}]")),
+        FormatTreeNode::new(format!("order by: [{order_by}]")),
+        FormatTreeNode::new(format!("frame: {}", plan.window_frame)),
+    ];
+
+    if let Some(info) = &plan.stat_info {
+        let items = plan_stats_info_to_format_tree(info);
+        children.extend(items);
+    }
+
+    children.push(to_format_tree(&plan.input, metadata)?);
+
+    Ok(FormatTreeNode::with_children(
+        "Window".to_string(),
+        children,
+    ))
+}
+
 fn limit_to_format_tree(plan: &Limit, metadata: &MetadataRef) -> Result<FormatTreeNode<String>> {
     let mut children = vec![
         FormatTreeNode::new(format!(
@@ -490,6 +548,34 @@ fn union_all_to_format_tree(
     ))
 }
 
+fn recursive_cte_to_format_tree(
+    plan: &RecursiveCte,
+    metadata: &MetadataRef,
+) -> Result<FormatTreeNode<String>> {
+    let mut children = vec![];
+
+    if let Some(info) = &plan.stat_info {
+        let items = plan_stats_info_to_format_tree(info);
+        children.extend(items);
+    }
+
+    children.extend(vec![
+        FormatTreeNode::with_children("anchor".to_string(), vec![to_format_tree(
+            &plan.anchor,
+            metadata,
+        )?]),
+        FormatTreeNode::with_children("recursive".to_string(), vec![to_format_tree(
+            &plan.recursive,
+            metadata,
+        )?]),
+    ]);
+
+    Ok(FormatTreeNode::with_children(
+        "RecursiveCte".to_string(),
+        children,
+    ))
+}
+
 fn plan_stats_info_to_format_tree(info: &PlanStatsInfo) -> Vec<FormatTreeNode<String>> {
     vec![FormatTreeNode::new(format!(
         "estimated rows: {0:.2}",
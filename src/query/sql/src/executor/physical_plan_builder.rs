@@ -26,6 +26,7 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_expression::types::DataType;
 use common_expression::DataBlock;
+use common_expression::DataField;
 use common_expression::DataSchemaRef;
 use common_expression::DataSchemaRefExt;
 use common_expression::RemoteExpr;
@@ -39,9 +40,14 @@ use super::AggregatePartial;
 use super::Exchange as PhysicalExchange;
 use super::Filter;
 use super::HashJoin;
+use super::LagLeadFunctionDesc;
 use super::Limit;
+use super::RecursiveCte;
+use super::RecursiveCteScan;
 use super::Sort;
 use super::TableScan;
+use super::Window;
+use super::WindowFunction;
 use crate::executor::explain::PlanStatsInfo;
 use crate::executor::table_read_plan::ToReadDataSourcePlan;
 use crate::executor::EvalScalar;
@@ -59,6 +65,7 @@ use crate::plans::Exchange;
 use crate::plans::RelOperator;
 use crate::plans::Scalar;
 use crate::plans::Scan;
+use crate::plans::WindowFuncType;
 use crate::ColumnEntry;
 use crate::IndexType;
 use crate::Metadata;
@@ -519,6 +526,153 @@ impl PhysicalPlanBuilder {
                     stat_info: Some(stat_info),
                 }))
             }
+            RelOperator::RecursiveCte(cte) => {
+                let anchor = self.build(&cte.anchor).await?;
+                let recursive = self.build(&cte.recursive).await?;
+                let anchor_schema = anchor.output_schema()?;
+                let recursive_schema = recursive.output_schema()?;
+                let anchor_projections = cte
+                    .anchor_columns
+                    .iter()
+                    .map(|column| anchor_schema.index_of(&column.index.to_string()))
+                    .collect::<Result<Vec<_>>>()?;
+                let recursive_projections = cte
+                    .recursive_columns
+                    .iter()
+                    .map(|column| recursive_schema.index_of(&column.index.to_string()))
+                    .collect::<Result<Vec<_>>>()?;
+                let fields = cte
+                    .output_columns
+                    .iter()
+                    .zip(cte.anchor_columns.iter())
+                    .map(|(index, column)| {
+                        DataField::new(&index.to_string(), *column.data_type.clone())
+                    })
+                    .collect::<Vec<_>>();
+                Ok(PhysicalPlan::RecursiveCte(RecursiveCte {
+                    cte_index: cte.cte_index,
+                    anchor: Box::new(anchor),
+                    recursive: Box::new(recursive),
+                    anchor_projections,
+                    recursive_projections,
+                    output_schema: DataSchemaRefExt::create(fields),
+
+                    stat_info: Some(stat_info),
+                }))
+            }
+            RelOperator::RecursiveCteScan(scan) => {
+                let metadata = self.metadata.read();
+                let fields = scan
+                    .columns
+                    .iter()
+                    .map(|index| match metadata.column(*index) {
+                        ColumnEntry::DerivedColumn { data_type, .. } => {
+                            Ok(DataField::new(&index.to_string(), data_type.clone()))
+                        }
+                        ColumnEntry::BaseTableColumn { .. } => Err(ErrorCode::Internal(
+                            "RecursiveCteScan must output derived columns",
+                        )),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(PhysicalPlan::RecursiveCteScan(RecursiveCteScan {
+                    cte_index: scan.cte_index,
+                    output_schema: DataSchemaRefExt::create(fields),
+                }))
+            }
+            RelOperator::Window(window) => {
+                let input = self.build(s_expr.child(0)?).await?;
+                let input_schema = input.output_schema()?;
+
+                let func = match &window.function {
+                    WindowFuncType::Aggregate(agg) => {
+                        WindowFunction::Aggregate(AggregateFunctionDesc {
+                            sig: AggregateFunctionSignature {
+                                name: agg.func_name.clone(),
+                                args: agg.args.iter().map(|s| s.data_type()).collect(),
+                                params: agg.params.clone(),
+                                return_type: *agg.return_type.clone(),
+                            },
+                            output_column: window.index,
+                            args: agg
+                                .args
+                                .iter()
+                                .map(|arg| {
+                                    if let Scalar::BoundColumnRef(col) = arg {
+                                        input_schema.index_of(&col.column.index.to_string())
+                                    } else {
+                                        Err(ErrorCode::Internal(
+                                            "Window function argument must be a BoundColumnRef"
+                                                .to_string(),
+                                        ))
+                                    }
+                                })
+                                .collect::<Result<_>>()?,
+                            arg_indices: agg
+                                .args
+                                .iter()
+                                .map(|arg| {
+                                    if let Scalar::BoundColumnRef(col) = arg {
+                                        Ok(col.column.index)
+                                    } else {
+                                        Err(ErrorCode::Internal(
+                                            "Window function argument must be a BoundColumnRef"
+                                                .to_string(),
+                                        ))
+                                    }
+                                })
+                                .collect::<Result<_>>()?,
+                        })
+                    }
+                    WindowFuncType::LagLead(lag_lead) => {
+                        let offset_of = |arg: &Scalar| {
+                            if let Scalar::BoundColumnRef(col) = arg {
+                                input_schema.index_of(&col.column.index.to_string())
+                            } else {
+                                Err(ErrorCode::Internal(
+                                    "Window function argument must be a BoundColumnRef".to_string(),
+                                ))
+                            }
+                        };
+                        WindowFunction::LagLead(LagLeadFunctionDesc {
+                            is_lag: lag_lead.is_lag,
+                            arg: offset_of(&lag_lead.arg)?,
+                            offset: lag_lead.offset,
+                            default: lag_lead
+                                .default
+                                .as_ref()
+                                .map(|default| offset_of(default))
+                                .transpose()?,
+                            return_type: *lag_lead.return_type.clone(),
+                        })
+                    }
+                    WindowFuncType::RowNumber => WindowFunction::RowNumber,
+                    WindowFuncType::Rank => WindowFunction::Rank,
+                    WindowFuncType::DenseRank => WindowFunction::DenseRank,
+                };
+
+                // null is the largest value in databend, smallest in hive
+                let default_nulls_first =
+                    !self.ctx.get_settings().get_sql_dialect()?.is_null_biggest();
+
+                Ok(PhysicalPlan::Window(Window {
+                    input: Box::new(input),
+                    index: window.index,
+                    func,
+                    partition_by: window.partition_by.iter().map(|v| v.index).collect(),
+                    order_by: window
+                        .order_by
+                        .iter()
+                        .map(|v| SortDesc {
+                            asc: v.asc.unwrap_or(true),
+                            nulls_first: v.nulls_first.unwrap_or(default_nulls_first),
+                            order_by: v.order_by_item.index,
+                        })
+                        .collect(),
+                    window_frame: window.frame.clone(),
+
+                    stat_info: Some(stat_info),
+                }))
+            }
             _ => Err(ErrorCode::Internal(format!(
                 "Unsupported physical plan: {:?}",
                 s_expr.plan()
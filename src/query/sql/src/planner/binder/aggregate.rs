@@ -38,11 +38,15 @@ use crate::plans::CastExpr;
 use crate::plans::ComparisonExpr;
 use crate::plans::EvalScalar;
 use crate::plans::FunctionCall;
+use crate::plans::LagLeadFunction;
 use crate::plans::NotExpr;
 use crate::plans::OrExpr;
 use crate::plans::Scalar;
 use crate::plans::ScalarExpr;
 use crate::plans::ScalarItem;
+use crate::plans::WindowFunc;
+use crate::plans::WindowFuncType;
+use crate::plans::WindowOrderBy;
 use crate::BindContext;
 use crate::MetadataRef;
 
@@ -91,7 +95,7 @@ impl<'a> AggregateRewriter<'a> {
     pub fn visit(&mut self, scalar: &Scalar) -> Result<Scalar> {
         match scalar {
             Scalar::BoundColumnRef(_) => Ok(scalar.clone()),
-            Scalar::ConstantExpr(_) => Ok(scalar.clone()),
+            Scalar::ConstantExpr(_) | Scalar::PlaceholderExpr(_) => Ok(scalar.clone()),
             Scalar::AndExpr(scalar) => Ok(AndExpr {
                 left: Box::new(self.visit(&scalar.left)?),
                 right: Box::new(self.visit(&scalar.right)?),
@@ -141,6 +145,63 @@ impl<'a> AggregateRewriter<'a> {
             Scalar::SubqueryExpr(_) => Ok(scalar.clone()),
 
             Scalar::AggregateFunction(agg_func) => self.replace_aggregate_function(agg_func),
+
+            // The aggregate function of a window function will be computed by `Window`, but
+            // there may be aggregate functions in its arguments, partition by or order by items.
+            Scalar::WindowFunction(window) => {
+                let func = match &window.func {
+                    WindowFuncType::Aggregate(agg) => {
+                        WindowFuncType::Aggregate(AggregateFunction {
+                            display_name: agg.display_name.clone(),
+                            func_name: agg.func_name.clone(),
+                            distinct: agg.distinct,
+                            params: agg.params.clone(),
+                            args: agg
+                                .args
+                                .iter()
+                                .map(|arg| self.visit(arg))
+                                .collect::<Result<Vec<_>>>()?,
+                            return_type: agg.return_type.clone(),
+                        })
+                    }
+                    WindowFuncType::LagLead(lag_lead) => WindowFuncType::LagLead(LagLeadFunction {
+                        is_lag: lag_lead.is_lag,
+                        arg: Box::new(self.visit(&lag_lead.arg)?),
+                        offset: lag_lead.offset,
+                        default: match &lag_lead.default {
+                            None => None,
+                            Some(default) => Some(Box::new(self.visit(default)?)),
+                        },
+                        return_type: lag_lead.return_type.clone(),
+                    }),
+                    func => func.clone(),
+                };
+                let partition_by = window
+                    .partition_by
+                    .iter()
+                    .map(|partition| self.visit(partition))
+                    .collect::<Result<Vec<_>>>()?;
+                let order_by = window
+                    .order_by
+                    .iter()
+                    .map(|order| {
+                        Ok(WindowOrderBy {
+                            expr: self.visit(&order.expr)?,
+                            asc: order.asc,
+                            nulls_first: order.nulls_first,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(WindowFunc {
+                    display_name: window.display_name.clone(),
+                    func,
+                    partition_by,
+                    order_by,
+                    frame: window.frame.clone(),
+                    return_type: window.return_type.clone(),
+                }
+                .into())
+            }
         }
     }
 
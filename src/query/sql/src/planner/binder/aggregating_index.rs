@@ -0,0 +1,293 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+
+use common_ast::ast::Expr;
+use common_ast::ast::Literal;
+use common_ast::ast::SelectTarget;
+use common_ast::ast::SetExpr;
+use common_ast::ast::Statement;
+use common_ast::parser::parse_sql;
+use common_ast::parser::tokenize_sql;
+use common_ast::Backtrace;
+use common_catalog::table::Table;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use storages_common_table_meta::table::OPT_KEY_MATERIALIZED_VIEWS;
+use storages_common_table_meta::table::OPT_KEY_MATERIALIZED_VIEW_QUERY;
+use storages_common_table_meta::table::OPT_KEY_MATERIALIZED_VIEW_SOURCE;
+use storages_common_table_meta::table::OPT_KEY_MATERIALIZED_VIEW_STALE;
+use tracing::warn;
+
+use crate::binder::split_conjunctions;
+use crate::binder::Binder;
+use crate::plans::Scalar;
+use crate::AggregatingIndex;
+use crate::BindContext;
+use crate::ColumnEntry;
+use crate::IndexType;
+use crate::ScalarBinder;
+use crate::ScalarExpr;
+use crate::TableEntry;
+
+/// The aggregate functions which can be computed again from their partial results.
+const REAGGREGATABLE_FUNCTIONS: [&str; 4] = ["count", "sum", "min", "max"];
+
+impl Binder {
+    /// Resolve the materialized views of the table `table_index` as aggregating indexes,
+    /// so that the optimizer can answer the aggregations over the table with them.
+    ///
+    /// `bind_context` is the context of the table, the queries of the views are bound
+    /// against its columns.
+    pub(super) async fn bind_aggregating_indexes(
+        &self,
+        bind_context: &BindContext,
+        table_index: IndexType,
+    ) -> Result<()> {
+        if !self
+            .ctx
+            .get_settings()
+            .get_enable_aggregating_index_scan()?
+        {
+            return Ok(());
+        }
+
+        let table_entry = self.metadata.read().table(table_index).clone();
+        let views: Vec<(String, String, String)> = match table_entry
+            .table()
+            .options()
+            .get(OPT_KEY_MATERIALIZED_VIEWS)
+        {
+            Some(views) => serde_json::from_str(views)?,
+            None => return Ok(()),
+        };
+
+        for (catalog, database, name) in views {
+            let view = match self.ctx.get_table(&catalog, &database, &name).await {
+                Ok(view) => view,
+                // The view has been dropped.
+                Err(e) if e.code() == ErrorCode::UNKNOWN_TABLE => continue,
+                Err(e) => return Err(e),
+            };
+            if let Some(index) = self
+                .bind_aggregating_index(bind_context, &table_entry, catalog, database, view)
+                .await?
+            {
+                self.metadata
+                    .write()
+                    .add_aggregating_index(table_index, index);
+            }
+        }
+        Ok(())
+    }
+
+    /// Bind the query of the materialized view `view`. Only the views in form of
+    /// `SELECT <keys and aggregates> FROM <table> [WHERE ...] GROUP BY <columns>` are
+    /// aggregating indexes, return `None` for the others.
+    async fn bind_aggregating_index(
+        &self,
+        bind_context: &BindContext,
+        table_entry: &TableEntry,
+        catalog: String,
+        database: String,
+        view: Arc<dyn Table>,
+    ) -> Result<Option<AggregatingIndex>> {
+        // The view may be defined on another table of the same name, which has been replaced.
+        match view.options().get(OPT_KEY_MATERIALIZED_VIEW_SOURCE) {
+            Some(source)
+                if serde_json::from_str::<(String, String, String)>(source)?
+                    == (
+                        table_entry.catalog().to_string(),
+                        table_entry.database().to_string(),
+                        table_entry.name().to_string(),
+                    ) => {}
+            _ => return Ok(None),
+        }
+        // The view failed to be maintained, its rows are not the aggregations of the table.
+        if view.options().contains_key(OPT_KEY_MATERIALIZED_VIEW_STALE) {
+            return Ok(None);
+        }
+        let query = match view.options().get(OPT_KEY_MATERIALIZED_VIEW_QUERY) {
+            Some(query) => query.clone(),
+            None => return Ok(None),
+        };
+
+        // A view failed to be parsed is not an index, but must not fail the query on the table.
+        let sql_dialect = self.ctx.get_settings().get_sql_dialect()?;
+        let backtrace = Backtrace::new();
+        let tokens = tokenize_sql(&query);
+        let stmt = match &tokens {
+            Ok(tokens) => parse_sql(tokens, sql_dialect, &backtrace).map(|(stmt, _)| stmt),
+            Err(e) => Err(e.clone()),
+        };
+        let stmt = match stmt {
+            Ok(stmt) => stmt,
+            Err(e) => {
+                warn!(
+                    "skip aggregating index {}.{}, failed to parse its query: {}",
+                    database,
+                    view.name(),
+                    e
+                );
+                return Ok(None);
+            }
+        };
+        let select = match &stmt {
+            Statement::Query(query)
+                if query.with.is_none()
+                    && query.order_by.is_empty()
+                    && query.limit.is_empty()
+                    && query.offset.is_none() =>
+            {
+                match &query.body {
+                    SetExpr::Select(select)
+                        if !select.distinct
+                            && select.having.is_none()
+                            && select.from.len() == 1 =>
+                    {
+                        select
+                    }
+                    _ => return Ok(None),
+                }
+            }
+            _ => return Ok(None),
+        };
+
+        let table_index = table_entry.index();
+        let mut keys = Vec::with_capacity(select.group_by.len());
+        for expr in select.group_by.iter() {
+            match self
+                .bind_table_column(bind_context, table_index, expr)
+                .await
+            {
+                Some(column) => keys.push(column),
+                None => return Ok(None),
+            }
+        }
+
+        let mut group_items = vec![];
+        let mut aggregate_functions = vec![];
+        for (field, target) in select.select_list.iter().enumerate() {
+            let expr = match target {
+                SelectTarget::AliasedExpr { expr, .. } => expr.as_ref(),
+                SelectTarget::QualifiedName { .. } => return Ok(None),
+            };
+            match expr {
+                Expr::FunctionCall {
+                    distinct: false,
+                    name,
+                    args,
+                    params,
+                    window: None,
+                    ..
+                } if params.is_empty() => {
+                    let func_name = name.name.to_lowercase();
+                    if !REAGGREGATABLE_FUNCTIONS.contains(&func_name.as_str()) {
+                        return Ok(None);
+                    }
+                    let arg = match args.as_slice() {
+                        [] if func_name == "count" => None,
+                        [Expr::Literal { lit, .. }]
+                            if func_name == "count" && *lit != Literal::Null =>
+                        {
+                            None
+                        }
+                        [arg] => match self.bind_table_column(bind_context, table_index, arg).await
+                        {
+                            Some(column) => Some(column),
+                            None => return Ok(None),
+                        },
+                        _ => return Ok(None),
+                    };
+                    aggregate_functions.push((func_name, arg, field));
+                }
+                _ => match self
+                    .bind_table_column(bind_context, table_index, expr)
+                    .await
+                {
+                    Some(column) if keys.contains(&column) => group_items.push((column, field)),
+                    _ => return Ok(None),
+                },
+            }
+        }
+
+        let mut predicates = vec![];
+        if let Some(selection) = &select.selection {
+            let mut scalar_binder = ScalarBinder::new(
+                bind_context,
+                self.ctx.clone(),
+                &self.name_resolution_ctx,
+                self.metadata.clone(),
+                &[],
+            );
+            let scalar = match scalar_binder.bind(selection).await {
+                Ok((scalar, _)) => scalar,
+                Err(_) => return Ok(None),
+            };
+            let columns = scalar.used_columns();
+            if columns.is_empty()
+                || !columns
+                    .iter()
+                    .all(|column| self.is_table_column(table_index, *column))
+            {
+                return Ok(None);
+            }
+            predicates = split_conjunctions(&scalar);
+        }
+
+        Ok(Some(AggregatingIndex {
+            catalog,
+            database,
+            table: view,
+            group_items,
+            aggregate_functions,
+            predicates,
+        }))
+    }
+
+    /// Bind `expr` as a column of the table `table_index`, return `None` if it's not.
+    async fn bind_table_column(
+        &self,
+        bind_context: &BindContext,
+        table_index: IndexType,
+        expr: &Expr<'_>,
+    ) -> Option<IndexType> {
+        let mut scalar_binder = ScalarBinder::new(
+            bind_context,
+            self.ctx.clone(),
+            &self.name_resolution_ctx,
+            self.metadata.clone(),
+            &[],
+        );
+        match scalar_binder.bind(expr).await {
+            Ok((Scalar::BoundColumnRef(column), _))
+                if self.is_table_column(table_index, column.column.index) =>
+            {
+                Some(column.column.index)
+            }
+            _ => None,
+        }
+    }
+
+    fn is_table_column(&self, table_index: IndexType, column: IndexType) -> bool {
+        matches!(
+            self.metadata.read().column(column),
+            ColumnEntry::BaseTableColumn {
+                table_index: index,
+                path_indices: None,
+                ..
+            } if *index == table_index
+        )
+    }
+}
@@ -26,6 +26,7 @@ use common_expression::DataSchemaRefExt;
 use dashmap::DashMap;
 
 use super::AggregateInfo;
+use super::WindowInfo;
 use crate::normalize_identifier;
 use crate::optimizer::SExpr;
 use crate::plans::Scalar;
@@ -89,6 +90,8 @@ pub struct BindContext {
 
     pub aggregate_info: AggregateInfo,
 
+    pub window_info: WindowInfo,
+
     /// True if there is aggregation in current context, which means
     /// non-grouping columns cannot be referenced outside aggregation
     /// functions, otherwise a grouping error will be raised.
@@ -110,6 +113,7 @@ impl BindContext {
             parent: None,
             columns: Vec::new(),
             aggregate_info: AggregateInfo::default(),
+            window_info: WindowInfo::default(),
             in_grouping: false,
             ctes_map: Box::new(DashMap::new()),
         }
@@ -120,6 +124,7 @@ impl BindContext {
             parent: Some(parent.clone()),
             columns: vec![],
             aggregate_info: Default::default(),
+            window_info: Default::default(),
             in_grouping: false,
             ctes_map: parent.ctes_map.clone(),
         }
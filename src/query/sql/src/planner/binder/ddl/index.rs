@@ -0,0 +1,111 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use common_ast::ast::CreateIndexStmt;
+use common_ast::ast::DropIndexStmt;
+use common_ast::ast::IndexType;
+use common_exception::Result;
+use common_meta_app::schema::SkippingIndexMeta;
+use common_meta_app::schema::SkippingIndexType;
+
+use crate::binder::Binder;
+use crate::planner::semantic::normalize_identifier;
+use crate::plans::CreateIndexPlan;
+use crate::plans::DropIndexPlan;
+use crate::plans::Plan;
+
+impl<'a> Binder {
+    pub(in crate::planner::binder) async fn bind_create_index(
+        &mut self,
+        stmt: &CreateIndexStmt<'a>,
+    ) -> Result<Plan> {
+        let CreateIndexStmt {
+            if_not_exists,
+            index,
+            index_type,
+            catalog,
+            database,
+            table,
+            expr,
+        } = stmt;
+
+        let tenant = self.ctx.get_tenant();
+        let catalog = catalog
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_catalog());
+        let database = database
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_database());
+        let table = normalize_identifier(table, &self.name_resolution_ctx).name;
+        let index = normalize_identifier(index, &self.name_resolution_ctx).name;
+
+        let index_type = match index_type {
+            IndexType::MinMax => SkippingIndexType::MinMax,
+            IndexType::Set(max_size) => SkippingIndexType::Set(*max_size),
+            IndexType::NgramBloomFilter(n) => SkippingIndexType::NgramBloomFilter(*n),
+        };
+
+        // The expression is stored as sql text like the cluster key, it is
+        // validated against the table schema while creating the index.
+        let plan = CreateIndexPlan {
+            if_not_exists: *if_not_exists,
+            tenant,
+            catalog,
+            database,
+            table,
+            index,
+            meta: SkippingIndexMeta {
+                index_type,
+                expr: expr.to_string(),
+            },
+        };
+        Ok(Plan::CreateIndex(Box::new(plan)))
+    }
+
+    pub(in crate::planner::binder) async fn bind_drop_index(
+        &mut self,
+        stmt: &DropIndexStmt<'a>,
+    ) -> Result<Plan> {
+        let DropIndexStmt {
+            if_exists,
+            index,
+            catalog,
+            database,
+            table,
+        } = stmt;
+
+        let tenant = self.ctx.get_tenant();
+        let catalog = catalog
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_catalog());
+        let database = database
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_database());
+        let table = normalize_identifier(table, &self.name_resolution_ctx).name;
+        let index = normalize_identifier(index, &self.name_resolution_ctx).name;
+
+        let plan = DropIndexPlan {
+            if_exists: *if_exists,
+            tenant,
+            catalog,
+            database,
+            table,
+            index,
+        };
+        Ok(Plan::DropIndex(Box::new(plan)))
+    }
+}
@@ -19,6 +19,7 @@ use std::sync::Arc;
 use common_ast::ast::AlterTableAction;
 use common_ast::ast::AlterTableStmt;
 use common_ast::ast::AnalyzeTableStmt;
+use common_ast::ast::ColumnDefinition;
 use common_ast::ast::CompactTarget;
 use common_ast::ast::CreateTableSource;
 use common_ast::ast::CreateTableStmt;
@@ -58,6 +59,8 @@ use common_expression::TableField;
 use common_expression::TableSchemaRef;
 use common_expression::TableSchemaRefExt;
 use common_storage::DataOperator;
+use common_storages_delta::DeltaTable;
+use common_storages_delta::OPT_KEY_PARTITION_COLUMNS;
 use common_storages_view::view_table::QUERY;
 use common_storages_view::view_table::VIEW_ENGINE;
 use storages_common_table_meta::table::is_reserved_opt_key;
@@ -75,17 +78,20 @@ use crate::optimizer::OptimizerContext;
 use crate::planner::semantic::normalize_identifier;
 use crate::planner::semantic::IdentifierNormalizer;
 use crate::planner::semantic::TypeChecker;
+use crate::plans::AddTableColumnPlan;
 use crate::plans::AlterTableClusterKeyPlan;
 use crate::plans::AnalyzeTablePlan;
 use crate::plans::CreateTablePlanV2;
 use crate::plans::DescribeTablePlan;
 use crate::plans::DropTableClusterKeyPlan;
+use crate::plans::DropTableColumnPlan;
 use crate::plans::DropTablePlan;
 use crate::plans::ExistsTablePlan;
 use crate::plans::OptimizeTableAction;
 use crate::plans::OptimizeTablePlan;
 use crate::plans::Plan;
 use crate::plans::ReclusterTablePlan;
+use crate::plans::RenameTableColumnPlan;
 use crate::plans::RenameTableEntity;
 use crate::plans::RenameTablePlan;
 use crate::plans::RevertTablePlan;
@@ -346,6 +352,10 @@ impl<'a> Binder {
                     part_prefix: uri.part_prefix.clone(),
                     connection: uri.connection.clone(),
                 };
+                // The location of a DELTA table is the directory of the table.
+                if engine == Engine::Delta && !uri.path.ends_with('/') {
+                    uri.path.push('/');
+                }
                 let (sp, _) = parse_uri_location(&mut uri)?;
 
                 // create a temporary op to check if params is correct
@@ -368,6 +378,12 @@ impl<'a> Binder {
             options.insert("TRANSIENT".to_owned(), "T".to_owned());
         }
 
+        if engine == Engine::Delta && (source.is_some() || as_query.is_some()) {
+            return Err(ErrorCode::BadArguments(
+                "Incorrect CREATE query: DELTA table is read-only and its columns are read from the transaction log",
+            ));
+        }
+
         // Build table schema
         let (schema, field_default_exprs, field_comments) = match (&source, &as_query) {
             (Some(source), None) => {
@@ -414,6 +430,22 @@ impl<'a> Binder {
                 Self::validate_create_table_schema(&source_schema)?;
                 (source_schema, source_default_exprs, source_coments)
             }
+            (None, None) if engine == Engine::Delta => {
+                // The schema of a DELTA table is read from its transaction log.
+                let sp = storage_params.as_ref().ok_or_else(|| {
+                    ErrorCode::BadArguments(
+                        "Incorrect CREATE query: DELTA table requires the location of the table",
+                    )
+                })?;
+                let (schema, partition_columns) = DeltaTable::read_schema(sp).await?;
+                if !partition_columns.is_empty() {
+                    options.insert(
+                        OPT_KEY_PARTITION_COLUMNS.to_owned(),
+                        partition_columns.join(","),
+                    );
+                }
+                (schema, vec![], vec![])
+            }
             _ => Err(ErrorCode::BadArguments(
                 "Incorrect CREATE query: required list of column descriptions or AS section or SELECT..",
             ))?,
@@ -591,6 +623,42 @@ impl<'a> Binder {
                     entities,
                 })))
             }
+            AlterTableAction::AddColumn { column } => {
+                let (field, default_expr, comment) = self.analyze_column_definition(column).await?;
+                Ok(Plan::AddTableColumn(Box::new(AddTableColumnPlan {
+                    tenant,
+                    catalog,
+                    database,
+                    table,
+                    field: field.with_default_expr(default_expr),
+                    comment,
+                })))
+            }
+            AlterTableAction::DropColumn { column } => {
+                let column = normalize_identifier(column, &self.name_resolution_ctx).name;
+                Ok(Plan::DropTableColumn(Box::new(DropTableColumnPlan {
+                    tenant,
+                    catalog,
+                    database,
+                    table,
+                    column,
+                })))
+            }
+            AlterTableAction::RenameColumn {
+                old_column,
+                new_column,
+            } => {
+                let old_column = normalize_identifier(old_column, &self.name_resolution_ctx).name;
+                let new_column = normalize_identifier(new_column, &self.name_resolution_ctx).name;
+                Ok(Plan::RenameTableColumn(Box::new(RenameTableColumnPlan {
+                    tenant,
+                    catalog,
+                    database,
+                    table,
+                    old_column,
+                    new_column,
+                })))
+            }
             AlterTableAction::AlterTableClusterKey { cluster_by } => {
                 let schema = self
                     .ctx
@@ -863,37 +931,17 @@ impl<'a> Binder {
         &self,
         source: &CreateTableSource<'a>,
     ) -> Result<(TableSchemaRef, Vec<Option<String>>, Vec<String>)> {
-        let bind_context = BindContext::new();
         match source {
             CreateTableSource::Columns(columns) => {
-                let mut scalar_binder = ScalarBinder::new(
-                    &bind_context,
-                    self.ctx.clone(),
-                    &self.name_resolution_ctx,
-                    self.metadata.clone(),
-                    &[],
-                );
                 let mut fields = Vec::with_capacity(columns.len());
                 let mut fields_default_expr = Vec::with_capacity(columns.len());
                 let mut fields_comments = Vec::with_capacity(columns.len());
                 for column in columns.iter() {
-                    let name = normalize_identifier(&column.name, &self.name_resolution_ctx).name;
-                    let schema_data_type = TypeChecker::resolve_type_name(&column.data_type)?;
-
-                    fields.push(TableField::new(&name, schema_data_type.clone()));
-                    fields_default_expr.push({
-                        if let Some(default_expr) = &column.default_expr {
-                            let (_expr, expr_type) = scalar_binder.bind(default_expr).await?;
-                            let data_type = DataType::from(&schema_data_type);
-                            if common_super_type(data_type.clone(), expr_type.clone()).is_none() {
-                                return Err(ErrorCode::SemanticError(format!("column {name} is of type {} but default expression is of type {}", data_type, expr_type)));
-                            }
-                            Some(default_expr.to_string())
-                        } else {
-                            None
-                        }
-                    });
-                    fields_comments.push(column.comment.clone().unwrap_or_default());
+                    let (field, default_expr, comment) =
+                        self.analyze_column_definition(column).await?;
+                    fields.push(field);
+                    fields_default_expr.push(default_expr);
+                    fields_comments.push(comment);
                 }
                 let schema = TableSchemaRefExt::create(fields);
                 Self::validate_create_table_schema(&schema)?;
@@ -927,8 +975,47 @@ impl<'a> Binder {
         }
     }
 
+    /// Analyze the column definition, returns the field, the default expression and the comment of the column.
+    async fn analyze_column_definition(
+        &self,
+        column: &ColumnDefinition<'a>,
+    ) -> Result<(TableField, Option<String>, String)> {
+        let bind_context = BindContext::new();
+        let mut scalar_binder = ScalarBinder::new(
+            &bind_context,
+            self.ctx.clone(),
+            &self.name_resolution_ctx,
+            self.metadata.clone(),
+            &[],
+        );
+        let name = normalize_identifier(&column.name, &self.name_resolution_ctx).name;
+        let schema_data_type = TypeChecker::resolve_type_name(&column.data_type)?;
+
+        let default_expr = if let Some(default_expr) = &column.default_expr {
+            let (_expr, expr_type) = scalar_binder.bind(default_expr).await?;
+            let data_type = DataType::from(&schema_data_type);
+            if common_super_type(data_type.clone(), expr_type.clone()).is_none() {
+                return Err(ErrorCode::SemanticError(format!(
+                    "column {name} is of type {} but default expression is of type {}",
+                    data_type, expr_type
+                )));
+            }
+            Some(default_expr.to_string())
+        } else {
+            None
+        };
+
+        Ok((
+            TableField::new(&name, schema_data_type),
+            default_expr,
+            column.comment.clone().unwrap_or_default(),
+        ))
+    }
+
     /// Validate the schema of the table to be created.
-    fn validate_create_table_schema(schema: &TableSchemaRef) -> Result<()> {
+    pub(in crate::planner::binder) fn validate_create_table_schema(
+        schema: &TableSchemaRef,
+    ) -> Result<()> {
         // Check if there are duplicated column names
         let mut name_set = HashSet::new();
         for field in schema.fields() {
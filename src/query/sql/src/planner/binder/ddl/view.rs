@@ -12,17 +12,41 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
 use common_ast::ast::AlterViewStmt;
+use common_ast::ast::CreateMaterializedViewStmt;
 use common_ast::ast::CreateViewStmt;
 use common_ast::ast::DropViewStmt;
+use common_ast::ast::Identifier;
+use common_ast::ast::RefreshMaterializedViewStmt;
+use common_ast::ast::Statement;
+use common_ast::ast::TableReference;
+use common_ast::walk_table_reference_mut;
+use common_ast::VisitorMut;
+use common_exception::ErrorCode;
 use common_exception::Result;
+use common_expression::infer_schema_type;
+use common_expression::TableField;
+use common_expression::TableSchemaRefExt;
+use storages_common_table_meta::table::OPT_KEY_DATABASE_ID;
+use storages_common_table_meta::table::OPT_KEY_MATERIALIZED_VIEW_QUERY;
+use storages_common_table_meta::table::OPT_KEY_MATERIALIZED_VIEW_SOURCE;
 
 use crate::binder::Binder;
+use crate::optimizer::optimize;
+use crate::optimizer::OptimizerConfig;
+use crate::optimizer::OptimizerContext;
 use crate::planner::semantic::normalize_identifier;
+use crate::planner::semantic::NameResolutionContext;
 use crate::plans::AlterViewPlan;
+use crate::plans::CreateMaterializedViewPlan;
 use crate::plans::CreateViewPlan;
 use crate::plans::DropViewPlan;
 use crate::plans::Plan;
+use crate::plans::RefreshMaterializedViewPlan;
+use crate::BindContext;
 
 impl<'a> Binder {
     pub(in crate::planner::binder) async fn bind_create_view(
@@ -124,4 +148,194 @@ impl<'a> Binder {
         };
         Ok(Plan::DropView(Box::new(plan)))
     }
+
+    pub(in crate::planner::binder) async fn bind_create_materialized_view(
+        &mut self,
+        stmt: &CreateMaterializedViewStmt<'a>,
+    ) -> Result<Plan> {
+        let CreateMaterializedViewStmt {
+            if_not_exists,
+            catalog,
+            database,
+            view,
+            query,
+        } = stmt;
+
+        let tenant = self.ctx.get_tenant();
+        let catalog = catalog
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_catalog());
+        let database = database
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_database());
+        let viewname = normalize_identifier(view, &self.name_resolution_ctx).name;
+
+        // The query is kept with the view and re-planned on each insertion into
+        // the source table, qualify its tables so it reads the same tables later.
+        let mut query = query.clone();
+        let mut sources = MaterializedViewSources {
+            name_resolution_ctx: &self.name_resolution_ctx,
+            current_catalog: self.ctx.get_current_catalog(),
+            current_database: self.ctx.get_current_database(),
+            ctes: query
+                .with
+                .iter()
+                .flat_map(|with| with.ctes.iter())
+                .map(|cte| normalize_identifier(&cte.alias.name, &self.name_resolution_ctx).name)
+                .collect(),
+            tables: vec![],
+        };
+        sources.visit_query(&mut query);
+        let (source_catalog, source_database, source_table) = match sources.tables.as_slice() {
+            [source] => source.clone(),
+            _ => {
+                return Err(ErrorCode::SemanticError(
+                    "The query of a materialized view must read from exactly one table",
+                ));
+            }
+        };
+        let source = self
+            .ctx
+            .get_table(&source_catalog, &source_database, &source_table)
+            .await?;
+        if source.engine() != "FUSE" {
+            return Err(ErrorCode::SemanticError(format!(
+                "Materialized view can only be created on FUSE tables, but {}.{} is {}",
+                source_database,
+                source_table,
+                source.engine()
+            )));
+        }
+
+        let init_bind_context = BindContext::new();
+        let (_, bind_context) = self.bind_query(&init_bind_context, &query).await?;
+        let fields = bind_context
+            .columns
+            .iter()
+            .map(|column_binding| {
+                Ok(TableField::new(
+                    &column_binding.column_name,
+                    infer_schema_type(&column_binding.data_type)?,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let schema = TableSchemaRefExt::create(fields);
+        Self::validate_create_table_schema(&schema)?;
+
+        let db = self
+            .ctx
+            .get_catalog(&catalog)?
+            .get_database(&tenant, &database)
+            .await?;
+        let mut options = BTreeMap::new();
+        options.insert(
+            OPT_KEY_DATABASE_ID.to_owned(),
+            db.get_db_info().ident.db_id.to_string(),
+        );
+        options.insert(
+            OPT_KEY_MATERIALIZED_VIEW_QUERY.to_owned(),
+            query.to_string(),
+        );
+        options.insert(
+            OPT_KEY_MATERIALIZED_VIEW_SOURCE.to_owned(),
+            serde_json::to_string(&(&source_catalog, &source_database, &source_table))?,
+        );
+
+        let stmt = Statement::Query(query);
+        let select_plan = self.bind_statement(&BindContext::new(), &stmt).await?;
+        let opt_ctx = Arc::new(OptimizerContext::new(OptimizerConfig::default()));
+        let as_select = Box::new(optimize(self.ctx.clone(), opt_ctx, select_plan)?);
+
+        let plan = CreateMaterializedViewPlan {
+            if_not_exists: *if_not_exists,
+            tenant,
+            catalog,
+            database,
+            viewname,
+            schema,
+            options,
+            source_catalog,
+            source_database,
+            source_table,
+            as_select,
+        };
+        Ok(Plan::CreateMaterializedView(Box::new(plan)))
+    }
+
+    pub(in crate::planner::binder) async fn bind_refresh_materialized_view(
+        &mut self,
+        stmt: &RefreshMaterializedViewStmt<'a>,
+    ) -> Result<Plan> {
+        let RefreshMaterializedViewStmt {
+            catalog,
+            database,
+            view,
+        } = stmt;
+
+        let tenant = self.ctx.get_tenant();
+        let catalog = catalog
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_catalog());
+        let database = database
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_database());
+        let viewname = normalize_identifier(view, &self.name_resolution_ctx).name;
+
+        let plan = RefreshMaterializedViewPlan {
+            tenant,
+            catalog,
+            database,
+            viewname,
+        };
+        Ok(Plan::RefreshMaterializedView(Box::new(plan)))
+    }
+}
+
+/// Collects the tables read by the query of a materialized view, and qualifies
+/// the unqualified ones with the current database.
+struct MaterializedViewSources<'a> {
+    name_resolution_ctx: &'a NameResolutionContext,
+    current_catalog: String,
+    current_database: String,
+    ctes: Vec<String>,
+    /// `(catalog, database, table)` of the tables read by the query.
+    tables: Vec<(String, String, String)>,
+}
+
+impl<'a> VisitorMut for MaterializedViewSources<'a> {
+    fn visit_table_reference(&mut self, table_ref: &mut TableReference<'_>) {
+        if let TableReference::Table {
+            catalog,
+            database,
+            table,
+            ..
+        } = table_ref
+        {
+            let table_name = normalize_identifier(table, self.name_resolution_ctx).name;
+            if database.is_some() || !self.ctes.contains(&table_name) {
+                let catalog_name = catalog
+                    .as_ref()
+                    .map(|ident| normalize_identifier(ident, self.name_resolution_ctx).name)
+                    .unwrap_or_else(|| self.current_catalog.clone());
+                let database_name = match database {
+                    Some(ident) => normalize_identifier(ident, self.name_resolution_ctx).name,
+                    None => {
+                        *database = Some(Identifier {
+                            name: self.current_database.clone(),
+                            quote: Some('`'),
+                            span: table.span.clone(),
+                        });
+                        self.current_database.clone()
+                    }
+                };
+                self.tables.push((catalog_name, database_name, table_name));
+            }
+        }
+
+        walk_table_reference_mut(self, table_ref);
+    }
 }
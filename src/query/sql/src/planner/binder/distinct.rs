@@ -20,6 +20,7 @@ use crate::binder::Binder;
 use crate::binder::ColumnBinding;
 use crate::optimizer::SExpr;
 use crate::planner::semantic::GroupingChecker;
+use crate::planner::semantic::WindowChecker;
 use crate::plans::Aggregate;
 use crate::plans::AggregateMode;
 use crate::plans::BoundColumnRef;
@@ -47,6 +48,13 @@ impl Binder {
                         scalar,
                         index: item.index,
                     })
+                } else if !bind_context.window_info.window_functions.is_empty() {
+                    let mut window_checker = WindowChecker::new(bind_context);
+                    let scalar = window_checker.resolve(&item.scalar)?;
+                    Ok(ScalarItem {
+                        scalar,
+                        index: item.index,
+                    })
                 } else {
                     Ok(item)
                 }
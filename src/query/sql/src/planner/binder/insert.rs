@@ -108,6 +108,10 @@ impl<'a> Binder {
                 let select_plan = self.bind_statement(bind_context, &statement).await?;
                 let opt_ctx = Arc::new(OptimizerContext::new(OptimizerConfig {
                     enable_distributed_optimization: !self.ctx.get_cluster().is_empty(),
+                    enable_aggregating_index: self
+                        .ctx
+                        .get_settings()
+                        .get_enable_aggregating_index_scan()?,
                 }));
                 let optimized_plan = optimize(self.ctx.clone(), opt_ctx, select_plan)?;
                 Ok(InsertInputSource::SelectPlan(Box::new(optimized_plan)))
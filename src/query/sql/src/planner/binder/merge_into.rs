@@ -0,0 +1,427 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_ast::ast::Expr;
+use common_ast::ast::Identifier;
+use common_ast::ast::MatchOperation;
+use common_ast::ast::MergeIntoStmt;
+use common_ast::ast::MergeOption;
+use common_ast::ast::TableReference;
+use common_ast::parser::parse_expr;
+use common_ast::parser::tokenize_sql;
+use common_ast::Backtrace;
+use common_ast::Dialect;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::type_check::common_super_type;
+use common_expression::types::DataType;
+use common_expression::TableSchemaRef;
+
+use crate::binder::split_conjunctions;
+use crate::binder::split_equivalent_predicate;
+use crate::binder::wrap_cast_if_needed;
+use crate::binder::Binder;
+use crate::binder::ScalarBinder;
+use crate::binder::Visibility;
+use crate::normalize_identifier;
+use crate::optimizer::optimize;
+use crate::optimizer::ColumnSet;
+use crate::optimizer::OptimizerConfig;
+use crate::optimizer::OptimizerContext;
+use crate::plans::MatchedClausePlan;
+use crate::plans::MergeIntoPlan;
+use crate::plans::Plan;
+use crate::plans::Scalar;
+use crate::plans::UnmatchedClausePlan;
+use crate::BindContext;
+use crate::IndexType;
+use crate::ScalarExpr;
+
+impl<'a> Binder {
+    pub(in crate::planner::binder) async fn bind_merge_into(
+        &mut self,
+        bind_context: &BindContext,
+        stmt: &MergeIntoStmt<'a>,
+    ) -> Result<Plan> {
+        let MergeIntoStmt {
+            table,
+            source,
+            join_expr,
+            merge_options,
+        } = stmt;
+
+        let (catalog_name, database_name, table_name) = if let TableReference::Table {
+            catalog,
+            database,
+            table,
+            ..
+        } = table
+        {
+            (
+                catalog
+                    .as_ref()
+                    .map_or_else(|| self.ctx.get_current_catalog(), |i| i.name.clone()),
+                database
+                    .as_ref()
+                    .map_or_else(|| self.ctx.get_current_database(), |i| i.name.clone()),
+                normalize_identifier(table, &self.name_resolution_ctx).name,
+            )
+        } else {
+            return Err(ErrorCode::Internal(
+                "should not happen, parser should have report error already",
+            ));
+        };
+
+        let target_table = self
+            .ctx
+            .get_table(&catalog_name, &database_name, &table_name)
+            .await?;
+        let table_id = target_table.get_id();
+        let schema = target_table.schema();
+
+        let (_, target_context) = self.bind_table_reference(bind_context, table).await?;
+        let mut target_columns = HashMap::new();
+        for column in target_context.columns.iter() {
+            if column.visibility == Visibility::Visible {
+                target_columns.insert(column.index, schema.index_of(&column.column_name)?);
+            }
+        }
+
+        let (source_expr, source_context) = self.bind_table_reference(bind_context, source).await?;
+        let source_column_set = source_context
+            .columns
+            .iter()
+            .filter(|column| column.visibility == Visibility::Visible)
+            .map(|column| column.index)
+            .collect::<ColumnSet>();
+        let target_column_set = target_columns.keys().cloned().collect::<ColumnSet>();
+        let joined_column_set = target_column_set
+            .union(&source_column_set)
+            .cloned()
+            .collect::<ColumnSet>();
+
+        // The expressions of the `WHEN` clauses can see the columns of both the target and the source.
+        let mut joined_context = BindContext::with_parent(Box::new(bind_context.clone()));
+        for column in target_context
+            .columns
+            .iter()
+            .chain(source_context.columns.iter())
+        {
+            joined_context.add_column_binding(column.clone());
+        }
+        let mut scalar_binder = ScalarBinder::new(
+            &joined_context,
+            self.ctx.clone(),
+            &self.name_resolution_ctx,
+            self.metadata.clone(),
+            &[],
+        );
+
+        // The source is joined with the target table by a right outer join, the columns of
+        // the target table are nullable in the ON clause, the same as in a `RIGHT JOIN`.
+        let mut on_context = BindContext::with_parent(Box::new(bind_context.clone()));
+        for column in target_context.columns.iter() {
+            let mut nullable_column = column.clone();
+            nullable_column.data_type = Box::new(column.data_type.wrap_nullable());
+            on_context.add_column_binding(nullable_column);
+        }
+        for column in source_context.columns.iter() {
+            on_context.add_column_binding(column.clone());
+        }
+        let mut on_binder = ScalarBinder::new(
+            &on_context,
+            self.ctx.clone(),
+            &self.name_resolution_ctx,
+            self.metadata.clone(),
+            &[],
+        );
+        let (join_scalar, _) = on_binder.bind(join_expr).await?;
+        let mut join_keys = vec![];
+        for predicate in split_conjunctions(&join_scalar) {
+            let (left, right) = split_equivalent_predicate(&predicate).ok_or_else(|| {
+                ErrorCode::SemanticError(
+                    "The ON clause of MERGE INTO only supports conjunctions of equalities",
+                )
+            })?;
+            let left_columns = left.used_columns();
+            let right_columns = right.used_columns();
+            let is_key = |columns: &ColumnSet, set: &ColumnSet| {
+                !columns.is_empty() && columns.is_subset(set)
+            };
+            let (target_key, source_key) = if is_key(&left_columns, &target_column_set)
+                && is_key(&right_columns, &source_column_set)
+            {
+                (left, right)
+            } else if is_key(&left_columns, &source_column_set)
+                && is_key(&right_columns, &target_column_set)
+            {
+                (right, left)
+            } else {
+                return Err(ErrorCode::SemanticError(format!(
+                    "The equality in the ON clause of MERGE INTO must compare the target table with the source, but got {}",
+                    join_expr
+                )));
+            };
+            let target_type = target_key.data_type();
+            let source_type = source_key.data_type();
+            let common_type = common_super_type(target_type.clone(), source_type.clone())
+                .ok_or_else(|| {
+                    ErrorCode::SemanticError(format!(
+                        "Cannot compare {} with {} in the ON clause of MERGE INTO",
+                        target_type, source_type
+                    ))
+                })?;
+            join_keys.push((
+                wrap_cast_if_needed(&target_key, &common_type),
+                wrap_cast_if_needed(&source_key, &common_type),
+            ));
+        }
+
+        let mut matched_clauses = vec![];
+        let mut unmatched_clauses = vec![];
+        for merge_option in merge_options.iter() {
+            match merge_option {
+                MergeOption::Match(clause) => {
+                    let condition = match &clause.selection {
+                        Some(expr) => Some(
+                            self.bind_merge_expr(&mut scalar_binder, expr, &joined_column_set)
+                                .await?,
+                        ),
+                        None => None,
+                    };
+                    let update_list = match &clause.operation {
+                        MatchOperation::Update { update_list } => {
+                            let mut update_columns = HashMap::with_capacity(update_list.len());
+                            for update_expr in update_list {
+                                let col_name = normalize_identifier(
+                                    &update_expr.name,
+                                    &self.name_resolution_ctx,
+                                )
+                                .name;
+                                let index = schema.index_of(&col_name)?;
+                                if update_columns.contains_key(&index) {
+                                    return Err(ErrorCode::BadArguments(format!(
+                                        "Multiple assignments in the single statement to column `{}`",
+                                        col_name
+                                    )));
+                                }
+                                let scalar = self
+                                    .bind_merge_expr(
+                                        &mut scalar_binder,
+                                        &update_expr.expr,
+                                        &joined_column_set,
+                                    )
+                                    .await?;
+                                let data_type = DataType::from(schema.field(index).data_type());
+                                update_columns
+                                    .insert(index, wrap_cast_if_needed(&scalar, &data_type));
+                            }
+                            Some(update_columns)
+                        }
+                        MatchOperation::Delete => None,
+                    };
+                    matched_clauses.push(MatchedClausePlan {
+                        condition,
+                        update_list,
+                    });
+                }
+                MergeOption::Unmatch(clause) => {
+                    // There is no row of the target table for the unmatched rows.
+                    let condition = match &clause.selection {
+                        Some(expr) => Some(
+                            self.bind_merge_expr(&mut scalar_binder, expr, &source_column_set)
+                                .await?,
+                        ),
+                        None => None,
+                    };
+                    let values = self
+                        .bind_merge_insert_values(
+                            &mut scalar_binder,
+                            schema.clone(),
+                            &clause.columns,
+                            &clause.values,
+                            &source_column_set,
+                        )
+                        .await?;
+                    unmatched_clauses.push(UnmatchedClausePlan { condition, values });
+                }
+            }
+        }
+
+        // Only the source columns used by the merge expressions are read.
+        let mut used_source_columns = ColumnSet::new();
+        for (_, source_key) in join_keys.iter() {
+            used_source_columns.extend(source_key.used_columns());
+        }
+        for clause in matched_clauses.iter() {
+            if let Some(condition) = &clause.condition {
+                used_source_columns.extend(condition.used_columns());
+            }
+            if let Some(update_list) = &clause.update_list {
+                for scalar in update_list.values() {
+                    used_source_columns.extend(scalar.used_columns());
+                }
+            }
+        }
+        for clause in unmatched_clauses.iter() {
+            if let Some(condition) = &clause.condition {
+                used_source_columns.extend(condition.used_columns());
+            }
+            for scalar in clause.values.iter().flatten() {
+                used_source_columns.extend(scalar.used_columns());
+            }
+        }
+        let mut input_context = source_context.clone();
+        input_context.columns = source_context
+            .columns
+            .into_iter()
+            .filter(|column| {
+                column.visibility == Visibility::Visible
+                    && used_source_columns.contains(&column.index)
+            })
+            .collect();
+        let source_columns = input_context
+            .columns
+            .iter()
+            .map(|column| column.index)
+            .collect::<Vec<IndexType>>();
+
+        let input = Plan::Query {
+            s_expr: Box::new(source_expr),
+            metadata: self.metadata.clone(),
+            bind_context: Box::new(input_context),
+            rewrite_kind: None,
+            ignore_result: false,
+        };
+        let opt_ctx = Arc::new(OptimizerContext::new(OptimizerConfig {
+            enable_distributed_optimization: !self.ctx.get_cluster().is_empty(),
+            enable_aggregating_index: self
+                .ctx
+                .get_settings()
+                .get_enable_aggregating_index_scan()?,
+        }));
+        let input = optimize(self.ctx.clone(), opt_ctx, input)?;
+
+        let plan = MergeIntoPlan {
+            catalog: catalog_name,
+            database: database_name,
+            table: table_name,
+            table_id,
+            input: Box::new(input),
+            target_columns,
+            source_columns,
+            join_keys,
+            matched_clauses,
+            unmatched_clauses,
+        };
+        Ok(Plan::MergeInto(Box::new(plan)))
+    }
+
+    async fn bind_merge_expr(
+        &self,
+        scalar_binder: &mut ScalarBinder<'_>,
+        expr: &Expr<'a>,
+        visible_columns: &ColumnSet,
+    ) -> Result<Scalar> {
+        let (scalar, _) = scalar_binder.bind(expr).await?;
+        if !scalar.used_columns().is_subset(visible_columns) {
+            return Err(ErrorCode::SemanticError(format!(
+                "Expression {} references columns that are not available in this clause of MERGE INTO",
+                expr
+            )));
+        }
+        Ok(scalar)
+    }
+
+    /// Bind the values of the inserted rows, the columns not specified are filled with
+    /// their default expressions, `None` means the default value of the data type.
+    async fn bind_merge_insert_values(
+        &self,
+        scalar_binder: &mut ScalarBinder<'_>,
+        schema: TableSchemaRef,
+        columns: &Option<Vec<Identifier<'a>>>,
+        values: &[Expr<'a>],
+        visible_columns: &ColumnSet,
+    ) -> Result<Vec<Option<Scalar>>> {
+        let positions = match columns {
+            Some(columns) => {
+                let mut positions = Vec::with_capacity(columns.len());
+                for column in columns {
+                    let col_name = normalize_identifier(column, &self.name_resolution_ctx).name;
+                    let position = schema.index_of(&col_name)?;
+                    if positions.contains(&position) {
+                        return Err(ErrorCode::BadArguments(format!(
+                            "Column `{}` is specified more than once",
+                            col_name
+                        )));
+                    }
+                    positions.push(position);
+                }
+                positions
+            }
+            None => (0..schema.num_fields()).collect(),
+        };
+        if positions.len() != values.len() {
+            return Err(ErrorCode::BadArguments(format!(
+                "Number of columns does not match number of values, expected {} but got {}",
+                positions.len(),
+                values.len()
+            )));
+        }
+
+        let mut row = vec![None; schema.num_fields()];
+        for (position, expr) in positions.into_iter().zip(values.iter()) {
+            let scalar = self
+                .bind_merge_expr(scalar_binder, expr, visible_columns)
+                .await?;
+            let data_type = DataType::from(schema.field(position).data_type());
+            row[position] = Some(wrap_cast_if_needed(&scalar, &data_type));
+        }
+
+        let mut default_binder = ScalarBinder::new(
+            &BindContext::new(),
+            self.ctx.clone(),
+            &self.name_resolution_ctx,
+            self.metadata.clone(),
+            &[],
+        );
+        let mut result = Vec::with_capacity(row.len());
+        for (field, value) in schema.fields().iter().zip(row.into_iter()) {
+            let scalar = match value {
+                Some(scalar) => scalar,
+                None => {
+                    let data_type = DataType::from(field.data_type());
+                    match field.default_expr() {
+                        Some(default_expr) => {
+                            let tokens = tokenize_sql(default_expr)?;
+                            let backtrace = Backtrace::new();
+                            let ast = parse_expr(&tokens, Dialect::PostgreSQL, &backtrace)?;
+                            let (scalar, _) = default_binder.bind(&ast).await?;
+                            wrap_cast_if_needed(&scalar, &data_type)
+                        }
+                        None => {
+                            result.push(None);
+                            continue;
+                        }
+                    }
+                }
+            };
+            result.push(Some(scalar));
+        }
+        Ok(result)
+    }
+}
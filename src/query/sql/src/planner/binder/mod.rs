@@ -13,6 +13,7 @@
 // limitations under the License.
 
 mod aggregate;
+mod aggregating_index;
 mod bind_context;
 #[allow(clippy::module_inception)]
 mod binder;
@@ -28,8 +29,10 @@ mod join;
 mod kill;
 mod limit;
 mod location;
+mod merge_into;
 mod presign;
 mod project;
+mod recursive_cte;
 mod scalar;
 mod scalar_common;
 mod scalar_visitor;
@@ -39,6 +42,7 @@ mod show;
 mod sort;
 mod table;
 mod update;
+mod window;
 
 pub use aggregate::AggregateInfo;
 pub use bind_context::*;
@@ -47,3 +51,4 @@ pub use builders::*;
 pub use location::parse_uri_location;
 pub use scalar::ScalarBinder;
 pub use scalar_common::*;
+pub use window::WindowInfo;
@@ -33,6 +33,7 @@ use crate::planner::binder::ColumnBinding;
 use crate::planner::semantic::compare_table_name;
 use crate::planner::semantic::normalize_identifier;
 use crate::planner::semantic::GroupingChecker;
+use crate::planner::semantic::WindowChecker;
 use crate::plans::BoundColumnRef;
 use crate::plans::EvalScalar;
 use crate::plans::Scalar;
@@ -115,6 +116,13 @@ impl<'a> Binder {
                         scalar,
                         index: item.index,
                     })
+                } else if !bind_context.window_info.window_functions.is_empty() {
+                    let mut window_checker = WindowChecker::new(bind_context);
+                    let scalar = window_checker.resolve(&item.scalar)?;
+                    Ok(ScalarItem {
+                        scalar,
+                        index: item.index,
+                    })
                 } else {
                     Ok(item.clone())
                 }
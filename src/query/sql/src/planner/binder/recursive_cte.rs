@@ -0,0 +1,233 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_ast::ast::SetExpr;
+use common_ast::ast::SetOperator;
+use common_ast::ast::CTE;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::binder::CteInfo;
+use crate::binder::Visibility;
+use crate::optimizer::SExpr;
+use crate::planner::binder::BindContext;
+use crate::planner::binder::Binder;
+use crate::plans::BoundColumnRef;
+use crate::plans::CastExpr;
+use crate::plans::EvalScalar;
+use crate::plans::RecursiveCte;
+use crate::plans::RecursiveCteScan;
+use crate::plans::RelOperator;
+use crate::plans::ScalarItem;
+use crate::ColumnBinding;
+use crate::IndexType;
+
+impl Binder {
+    /// Bind a CTE declared in `WITH RECURSIVE`.
+    ///
+    /// The query of the CTE must be `anchor UNION ALL recursive`, where only the
+    /// recursive member references the CTE itself. Returns `None` if the CTE doesn't
+    /// reference itself, then it can be bound as a plain CTE.
+    pub(super) async fn bind_recursive_cte(
+        &mut self,
+        bind_context: &BindContext,
+        cte: &CTE<'_>,
+    ) -> Result<Option<CteInfo>> {
+        let table_name = cte.alias.name.name.clone();
+        let set_operation = match &cte.query.body {
+            SetExpr::SetOperation(set_operation) if set_operation.op == SetOperator::Union => {
+                set_operation
+            }
+            _ => return Ok(None),
+        };
+
+        // Bind the anchor member, it must not reference the CTE itself.
+        let (anchor_expr, anchor_context) = self
+            .bind_set_expr(bind_context, &set_operation.left, &[])
+            .await?;
+
+        let mut columns_alias: Vec<String> =
+            cte.alias.columns.iter().map(|c| c.name.clone()).collect();
+        if columns_alias.len() > anchor_context.columns.len() {
+            return Err(ErrorCode::SemanticError(format!(
+                "recursive cte {table_name} has {} columns available but {} columns specified",
+                anchor_context.columns.len(),
+                columns_alias.len()
+            )));
+        }
+        for column in anchor_context.columns.iter().skip(columns_alias.len()) {
+            columns_alias.push(column.column_name.clone());
+        }
+
+        // The rows of the previous iteration are read by a `RecursiveCteScan`, whose
+        // columns have the types of the anchor member.
+        let mut scan_context = BindContext::new();
+        for (column, name) in anchor_context.columns.iter().zip(columns_alias.iter()) {
+            let index = self
+                .metadata
+                .write()
+                .add_derived_column(name.clone(), *column.data_type.clone());
+            scan_context.add_column_binding(ColumnBinding {
+                database_name: None,
+                table_name: None,
+                column_name: name.clone(),
+                index,
+                data_type: column.data_type.clone(),
+                visibility: Visibility::Visible,
+            });
+        }
+        let scan_columns: Vec<IndexType> = scan_context.columns.iter().map(|c| c.index).collect();
+        // Columns of the working table are unique in metadata, use the first one as the
+        // identifier of the CTE.
+        let cte_index = scan_columns[0];
+        let scan = RecursiveCteScan {
+            cte_index,
+            columns: scan_columns.clone(),
+        };
+        bind_context.ctes_map.insert(table_name.clone(), CteInfo {
+            columns_alias: columns_alias.clone(),
+            s_expr: SExpr::create_leaf(scan.into()),
+            bind_context: scan_context,
+        });
+
+        // Bind the recursive member, which can reference the CTE through the scan above.
+        let recursive = self
+            .bind_set_expr(bind_context, &set_operation.right, &[])
+            .await;
+        bind_context.ctes_map.remove(&table_name);
+        let (mut recursive_expr, recursive_context) = recursive?;
+
+        match count_recursive_cte_scan(&recursive_expr, cte_index) {
+            0 => return Ok(None),
+            1 => {}
+            _ => {
+                return Err(ErrorCode::SemanticError(format!(
+                    "recursive reference to cte {table_name} must not appear more than once"
+                )));
+            }
+        }
+        if !set_operation.all {
+            return Err(ErrorCode::SemanticError(format!(
+                "recursive cte {table_name} must use UNION ALL to combine its anchor and recursive members"
+            )));
+        }
+        if !cte.query.order_by.is_empty()
+            || !cte.query.limit.is_empty()
+            || cte.query.offset.is_some()
+        {
+            return Err(ErrorCode::SemanticError(format!(
+                "ORDER BY, LIMIT and OFFSET are not supported in recursive cte {table_name}"
+            )));
+        }
+        if anchor_context.columns.len() != recursive_context.columns.len() {
+            return Err(ErrorCode::SemanticError(format!(
+                "anchor and recursive members of cte {table_name} must have the same number of columns"
+            )));
+        }
+
+        // Cast the outputs of the recursive member to the types of the anchor member.
+        let mut scalar_items = vec![];
+        let mut recursive_columns = Vec::with_capacity(recursive_context.columns.len());
+        for (anchor_col, recursive_col) in anchor_context
+            .columns
+            .iter()
+            .zip(recursive_context.columns.iter())
+        {
+            if anchor_col.data_type == recursive_col.data_type {
+                recursive_columns.push(recursive_col.clone());
+                continue;
+            }
+            let index = self.metadata.write().add_derived_column(
+                recursive_col.column_name.clone(),
+                *anchor_col.data_type.clone(),
+            );
+            scalar_items.push(ScalarItem {
+                scalar: CastExpr {
+                    argument: Box::new(
+                        BoundColumnRef {
+                            column: recursive_col.clone(),
+                        }
+                        .into(),
+                    ),
+                    from_type: recursive_col.data_type.clone(),
+                    target_type: anchor_col.data_type.clone(),
+                }
+                .into(),
+                index,
+            });
+            recursive_columns.push(ColumnBinding {
+                database_name: None,
+                table_name: None,
+                column_name: recursive_col.column_name.clone(),
+                index,
+                data_type: anchor_col.data_type.clone(),
+                visibility: Visibility::Visible,
+            });
+        }
+        if !scalar_items.is_empty() {
+            recursive_expr = SExpr::create_unary(
+                EvalScalar {
+                    items: scalar_items,
+                }
+                .into(),
+                recursive_expr,
+            );
+        }
+
+        let mut output_context = BindContext::new();
+        for (column, name) in anchor_context.columns.iter().zip(columns_alias.iter()) {
+            let index = self
+                .metadata
+                .write()
+                .add_derived_column(name.clone(), *column.data_type.clone());
+            output_context.add_column_binding(ColumnBinding {
+                database_name: None,
+                table_name: None,
+                column_name: name.clone(),
+                index,
+                data_type: column.data_type.clone(),
+                visibility: Visibility::Visible,
+            });
+        }
+
+        let recursive_cte = RecursiveCte {
+            cte_index,
+            anchor: Box::new(anchor_expr),
+            recursive: Box::new(recursive_expr),
+            anchor_columns: anchor_context.columns.clone(),
+            recursive_columns,
+            scan_columns,
+            output_columns: output_context.columns.iter().map(|c| c.index).collect(),
+        };
+
+        Ok(Some(CteInfo {
+            columns_alias,
+            s_expr: SExpr::create_leaf(recursive_cte.into()),
+            bind_context: output_context,
+        }))
+    }
+}
+
+fn count_recursive_cte_scan(s_expr: &SExpr, cte_index: IndexType) -> usize {
+    let count = match s_expr.plan() {
+        RelOperator::RecursiveCteScan(scan) if scan.cte_index == cte_index => 1,
+        _ => 0,
+    };
+    count
+        + s_expr
+            .children()
+            .iter()
+            .map(|child| count_recursive_cte_scan(child, cte_index))
+            .sum::<usize>()
+}
@@ -19,9 +19,12 @@ use crate::plans::AndExpr;
 use crate::plans::CastExpr;
 use crate::plans::ComparisonExpr;
 use crate::plans::FunctionCall;
+use crate::plans::LagLeadFunction;
 use crate::plans::NotExpr;
 use crate::plans::OrExpr;
 use crate::plans::Scalar;
+use crate::plans::WindowFunc;
+use crate::plans::WindowFuncType;
 
 /// Controls how the visitor recursion should proceed.
 pub enum Recursion<V: ScalarVisitor> {
@@ -58,6 +61,39 @@ pub trait ScalarVisitor: Sized {
                                         stack.push(RecursionProcessing::Call(arg));
                                     }
                                 }
+                                Scalar::WindowFunction(WindowFunc {
+                                    func,
+                                    partition_by,
+                                    order_by,
+                                    ..
+                                }) => {
+                                    if let WindowFuncType::Aggregate(AggregateFunction {
+                                        args,
+                                        ..
+                                    }) = func
+                                    {
+                                        for arg in args {
+                                            stack.push(RecursionProcessing::Call(arg));
+                                        }
+                                    }
+                                    if let WindowFuncType::LagLead(LagLeadFunction {
+                                        arg,
+                                        default,
+                                        ..
+                                    }) = func
+                                    {
+                                        stack.push(RecursionProcessing::Call(arg));
+                                        if let Some(default) = default {
+                                            stack.push(RecursionProcessing::Call(default));
+                                        }
+                                    }
+                                    for partition in partition_by {
+                                        stack.push(RecursionProcessing::Call(partition));
+                                    }
+                                    for order in order_by {
+                                        stack.push(RecursionProcessing::Call(&order.expr));
+                                    }
+                                }
                                 Scalar::ComparisonExpr(ComparisonExpr { left, right, .. }) => {
                                     stack.push(RecursionProcessing::Call(left));
                                     stack.push(RecursionProcessing::Call(right));
@@ -78,7 +114,9 @@ pub trait ScalarVisitor: Sized {
                                         stack.push(RecursionProcessing::Call(arg));
                                     }
                                 }
-                                Scalar::BoundColumnRef(_) | Scalar::ConstantExpr(_) => {}
+                                Scalar::BoundColumnRef(_)
+                                | Scalar::ConstantExpr(_)
+                                | Scalar::PlaceholderExpr(_) => {}
                                 Scalar::CastExpr(CastExpr { argument, .. }) => {
                                     stack.push(RecursionProcessing::Call(argument))
                                 }
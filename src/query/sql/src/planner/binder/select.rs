@@ -31,6 +31,7 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_expression::type_check::common_super_type;
 use common_expression::types::DataType;
+use common_expression::types::NumberDataType;
 
 use crate::binder::join::JoinConditions;
 use crate::binder::scalar_common::split_conjunctions;
@@ -44,10 +45,16 @@ use crate::plans::BoundColumnRef;
 use crate::plans::CastExpr;
 use crate::plans::EvalScalar;
 use crate::plans::Filter;
+use crate::plans::FunctionCall;
 use crate::plans::JoinType;
 use crate::plans::Scalar;
 use crate::plans::ScalarItem;
 use crate::plans::UnionAll;
+use crate::plans::Window;
+use crate::plans::WindowFuncFrame;
+use crate::plans::WindowFuncFrameBound;
+use crate::plans::WindowFuncFrameUnits;
+use crate::plans::WindowFuncType;
 use crate::ColumnBinding;
 use crate::IndexType;
 
@@ -139,6 +146,8 @@ impl<'a> Binder {
                 .await?;
         }
 
+        s_expr = self.bind_window(&mut from_context, &scalar_items, s_expr)?;
+
         if stmt.distinct {
             s_expr = self.bind_distinct(&from_context, &projections, &mut scalar_items, s_expr)?;
         }
@@ -202,6 +211,12 @@ impl<'a> Binder {
                         "duplicate cte {table_name}"
                     )));
                 }
+                if with.recursive {
+                    if let Some(cte_info) = self.bind_recursive_cte(bind_context, cte).await? {
+                        bind_context.ctes_map.insert(table_name, cte_info);
+                        continue;
+                    }
+                }
                 let (s_expr, cte_bind_context) = self.bind_query(bind_context, &cte.query).await?;
                 let cte_info = CteInfo {
                     columns_alias: cte.alias.columns.iter().map(|c| c.name.clone()).collect(),
@@ -321,13 +336,27 @@ impl<'a> Binder {
             }
         }
         match (op, all) {
-            (SetOperator::Intersect, false) => {
+            (SetOperator::Intersect, _) => {
                 // Transfer Intersect to Semi join
-                self.bind_intersect(left_bind_context, right_bind_context, left_expr, right_expr)
+                self.bind_intersect(
+                    left_bind_context,
+                    right_bind_context,
+                    coercion_types,
+                    left_expr,
+                    right_expr,
+                    *all,
+                )
             }
-            (SetOperator::Except, false) => {
+            (SetOperator::Except, _) => {
                 // Transfer Except to Anti join
-                self.bind_except(left_bind_context, right_bind_context, left_expr, right_expr)
+                self.bind_except(
+                    left_bind_context,
+                    right_bind_context,
+                    coercion_types,
+                    left_expr,
+                    right_expr,
+                    *all,
+                )
             }
             (SetOperator::Union, true) => self.bind_union(
                 left_bind_context,
@@ -345,9 +374,6 @@ impl<'a> Binder {
                 right_expr,
                 true,
             ),
-            _ => Err(ErrorCode::Unimplemented(
-                "Unsupported query type, currently, databend only support intersect distinct and except distinct",
-            )),
         }
     }
 
@@ -386,15 +412,19 @@ impl<'a> Binder {
         &mut self,
         left_context: BindContext,
         right_context: BindContext,
+        coercion_types: Vec<DataType>,
         left_expr: SExpr,
         right_expr: SExpr,
+        all: bool,
     ) -> Result<(SExpr, BindContext)> {
         self.bind_intersect_or_except(
             left_context,
             right_context,
+            coercion_types,
             left_expr,
             right_expr,
             JoinType::LeftSemi,
+            all,
         )
     }
 
@@ -402,52 +432,88 @@ impl<'a> Binder {
         &mut self,
         left_context: BindContext,
         right_context: BindContext,
+        coercion_types: Vec<DataType>,
         left_expr: SExpr,
         right_expr: SExpr,
+        all: bool,
     ) -> Result<(SExpr, BindContext)> {
         self.bind_intersect_or_except(
             left_context,
             right_context,
+            coercion_types,
             left_expr,
             right_expr,
             JoinType::LeftAnti,
+            all,
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn bind_intersect_or_except(
         &mut self,
         left_context: BindContext,
         right_context: BindContext,
+        coercion_types: Vec<DataType>,
         left_expr: SExpr,
         right_expr: SExpr,
         join_type: JoinType,
+        all: bool,
     ) -> Result<(SExpr, BindContext)> {
-        let left_expr = self.bind_distinct(
-            &left_context,
-            left_context.all_column_bindings(),
-            &mut HashMap::new(),
-            left_expr,
-        )?;
-        let mut left_conditions = Vec::with_capacity(left_context.columns.len());
-        let mut right_conditions = Vec::with_capacity(right_context.columns.len());
-        assert_eq!(left_context.columns.len(), right_context.columns.len());
-        for (left_column, right_column) in left_context
-            .columns
-            .iter()
-            .zip(right_context.columns.iter())
-        {
+        let mut left_conditions = Vec::with_capacity(left_context.columns.len() + 1);
+        let mut right_conditions = Vec::with_capacity(right_context.columns.len() + 1);
+        let (left_expr, right_expr) = if all {
+            // Number the duplicated rows on both sides, so that the n-th duplicate of a row
+            // can only match the n-th duplicate on the other side. Then INTERSECT ALL keeps
+            // min(m, n) duplicates and EXCEPT ALL keeps max(m - n, 0) duplicates.
+            let (left_expr, left_row_number) = self.bind_row_number(&left_context, left_expr)?;
+            let (right_expr, right_row_number) =
+                self.bind_row_number(&right_context, right_expr)?;
             left_conditions.push(
                 BoundColumnRef {
-                    column: left_column.clone(),
+                    column: left_row_number,
                 }
                 .into(),
             );
             right_conditions.push(
                 BoundColumnRef {
-                    column: right_column.clone(),
+                    column: right_row_number,
                 }
                 .into(),
             );
+            (left_expr, right_expr)
+        } else {
+            let left_expr = self.bind_distinct(
+                &left_context,
+                left_context.all_column_bindings(),
+                &mut HashMap::new(),
+                left_expr,
+            )?;
+            (left_expr, right_expr)
+        };
+
+        assert_eq!(left_context.columns.len(), right_context.columns.len());
+        for ((left_column, right_column), data_type) in left_context
+            .columns
+            .iter()
+            .zip(right_context.columns.iter())
+            .zip(coercion_types.iter())
+        {
+            let left = coerce_column(left_column, data_type);
+            let right = coerce_column(right_column, data_type);
+            // Set operations treat NULLs as equal, but NULLs never match in a hash join.
+            // Compare the nullability and the non-null values separately.
+            if data_type.is_nullable() {
+                left_conditions.push(is_not_null(left.clone()));
+                right_conditions.push(is_not_null(right.clone()));
+                left_conditions.push(assume_not_null(left, data_type));
+                right_conditions.push(assume_not_null(right, data_type));
+            } else if data_type.is_nullable_or_null() {
+                left_conditions.push(is_not_null(left));
+                right_conditions.push(is_not_null(right));
+            } else {
+                left_conditions.push(left);
+                right_conditions.push(right);
+            }
         }
         let join_conditions = JoinConditions {
             left_conditions,
@@ -459,6 +525,52 @@ impl<'a> Binder {
         Ok((s_expr, left_context))
     }
 
+    /// Number the rows which have the same values in all the columns of `bind_context`,
+    /// starting from 1.
+    fn bind_row_number(
+        &mut self,
+        bind_context: &BindContext,
+        child: SExpr,
+    ) -> Result<(SExpr, ColumnBinding)> {
+        let data_type = DataType::Number(NumberDataType::UInt64);
+        let index = self
+            .metadata
+            .write()
+            .add_derived_column("row_number".to_string(), data_type.clone());
+        let partition_by = bind_context
+            .columns
+            .iter()
+            .map(|column| ScalarItem {
+                scalar: BoundColumnRef {
+                    column: column.clone(),
+                }
+                .into(),
+                index: column.index,
+            })
+            .collect();
+        let window = Window {
+            index,
+            function: WindowFuncType::RowNumber,
+            arguments: vec![],
+            partition_by,
+            order_by: vec![],
+            frame: WindowFuncFrame {
+                units: WindowFuncFrameUnits::Rows,
+                start_bound: WindowFuncFrameBound::Preceding(None),
+                end_bound: WindowFuncFrameBound::Following(None),
+            },
+        };
+        let column = ColumnBinding {
+            database_name: None,
+            table_name: None,
+            column_name: "row_number".to_string(),
+            index,
+            data_type: Box::new(data_type),
+            visibility: Visibility::Visible,
+        };
+        Ok((SExpr::create_unary(window.into(), child), column))
+    }
+
     #[allow(clippy::type_complexity)]
     fn coercion_union_type(
         &self,
@@ -557,3 +669,39 @@ impl<'a> Binder {
         Ok((new_bind_context, pairs, left_expr, right_expr))
     }
 }
+
+fn coerce_column(column: &ColumnBinding, data_type: &DataType) -> Scalar {
+    let scalar: Scalar = BoundColumnRef {
+        column: column.clone(),
+    }
+    .into();
+    if column.data_type.as_ref() == data_type {
+        return scalar;
+    }
+    CastExpr {
+        argument: Box::new(scalar),
+        from_type: column.data_type.clone(),
+        target_type: Box::new(data_type.clone()),
+    }
+    .into()
+}
+
+fn is_not_null(scalar: Scalar) -> Scalar {
+    FunctionCall {
+        params: vec![],
+        arguments: vec![scalar],
+        func_name: "is_not_null".to_string(),
+        return_type: Box::new(DataType::Boolean),
+    }
+    .into()
+}
+
+fn assume_not_null(scalar: Scalar, data_type: &DataType) -> Scalar {
+    FunctionCall {
+        params: vec![],
+        arguments: vec![scalar],
+        func_name: "assume_not_null".to_string(),
+        return_type: Box::new(data_type.remove_nullable()),
+    }
+    .into()
+}
@@ -55,6 +55,7 @@ use common_pipeline_sources::processors::sources::input_formats::InputContext;
 use common_storages_stage::get_first_file;
 use common_storages_stage::StageTable;
 use common_storages_view::view_table::QUERY;
+use storages_common_table_meta::table::OPT_KEY_MATERIALIZED_VIEW_STALE;
 
 use crate::binder::copy::parse_stage_location_v2;
 use crate::binder::location::parse_uri_location;
@@ -164,6 +165,15 @@ impl<'a> Binder {
                         &navigation_point,
                     )
                     .await?;
+                if table_meta
+                    .options()
+                    .contains_key(OPT_KEY_MATERIALIZED_VIEW_STALE)
+                {
+                    return Err(ErrorCode::StaleMaterializedView(format!(
+                        "materialized view {}.{} is stale, run `REFRESH MATERIALIZED VIEW {}.{}` to rebuild it",
+                        database, table_name, database, table_name
+                    )));
+                }
                 match table_meta.engine() {
                     "VIEW" => {
                         let query = table_meta
@@ -211,6 +221,11 @@ impl<'a> Binder {
                         let (s_expr, mut bind_context) = self
                             .bind_base_table(bind_context, database.as_str(), table_index)
                             .await?;
+                        // Time travel reads the history of the table, which the views do not keep.
+                        if navigation_point.is_none() {
+                            self.bind_aggregating_indexes(&bind_context, table_index)
+                                .await?;
+                        }
                         if let Some(alias) = alias {
                             bind_context.apply_table_alias(alias, &self.name_resolution_ctx)?;
                         }
@@ -0,0 +1,304 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_exception::Result;
+
+use crate::binder::Binder;
+use crate::binder::ColumnBinding;
+use crate::binder::Visibility;
+use crate::optimizer::SExpr;
+use crate::planner::semantic::GroupingChecker;
+use crate::plans::AggregateFunction;
+use crate::plans::BoundColumnRef;
+use crate::plans::EvalScalar;
+use crate::plans::LagLeadFunction;
+use crate::plans::Scalar;
+use crate::plans::ScalarExpr;
+use crate::plans::ScalarItem;
+use crate::plans::Window;
+use crate::plans::WindowFunc;
+use crate::plans::WindowFuncFrame;
+use crate::plans::WindowFuncType;
+use crate::plans::WindowOrderByInfo;
+use crate::BindContext;
+use crate::IndexType;
+use crate::MetadataRef;
+
+#[derive(Default, Clone, PartialEq, Eq, Debug)]
+pub struct WindowInfo {
+    /// Window functions
+    pub window_functions: Vec<WindowFunctionInfo>,
+
+    /// Arguments, partition by items and order by items of window functions, which
+    /// will be evaluated before window functions.
+    pub window_items: Vec<ScalarItem>,
+
+    /// Mapping: (window function display name) -> (index of window func in `window_functions`)
+    /// This is used to find a window function in current context.
+    pub window_functions_map: HashMap<String, usize>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct WindowFunctionInfo {
+    /// The output column index of the window function
+    pub index: IndexType,
+    /// Window function, the arguments of aggregate window functions have been
+    /// replaced with BoundColumnRef
+    pub func: WindowFuncType,
+    pub arguments: Vec<ScalarItem>,
+    pub partition_by_items: Vec<ScalarItem>,
+    pub order_by_items: Vec<WindowOrderByInfo>,
+    pub frame: WindowFuncFrame,
+}
+
+pub(super) struct WindowRewriter<'a> {
+    pub bind_context: &'a mut BindContext,
+    pub metadata: MetadataRef,
+}
+
+impl<'a> WindowRewriter<'a> {
+    pub fn new(bind_context: &'a mut BindContext, metadata: MetadataRef) -> Self {
+        Self {
+            bind_context,
+            metadata,
+        }
+    }
+
+    /// Find the window functions in the scalar expression and add them into `WindowInfo`.
+    pub fn visit(&mut self, scalar: &Scalar) -> Result<()> {
+        match scalar {
+            Scalar::BoundColumnRef(_)
+            | Scalar::ConstantExpr(_)
+            | Scalar::PlaceholderExpr(_)
+            | Scalar::AggregateFunction(_)
+            | Scalar::SubqueryExpr(_) => Ok(()),
+            Scalar::AndExpr(scalar) => {
+                self.visit(&scalar.left)?;
+                self.visit(&scalar.right)
+            }
+            Scalar::OrExpr(scalar) => {
+                self.visit(&scalar.left)?;
+                self.visit(&scalar.right)
+            }
+            Scalar::NotExpr(scalar) => self.visit(&scalar.argument),
+            Scalar::ComparisonExpr(scalar) => {
+                self.visit(&scalar.left)?;
+                self.visit(&scalar.right)
+            }
+            Scalar::FunctionCall(func) => {
+                for arg in func.arguments.iter() {
+                    self.visit(arg)?;
+                }
+                Ok(())
+            }
+            Scalar::CastExpr(cast) => self.visit(&cast.argument),
+            Scalar::WindowFunction(window) => self.register_window_function(window),
+        }
+    }
+
+    /// Replace the arguments, partition by items and order by items of window function
+    /// with BoundColumnRef, and add the replaced window function into `WindowInfo`.
+    fn register_window_function(&mut self, window: &WindowFunc) -> Result<()> {
+        if self
+            .bind_context
+            .window_info
+            .window_functions_map
+            .contains_key(&window.display_name)
+        {
+            // The window function is duplicated
+            return Ok(());
+        }
+
+        let func_name = window.func.func_name();
+
+        let (func, arguments) = match &window.func {
+            WindowFuncType::Aggregate(agg) => {
+                let mut replaced_args = Vec::with_capacity(agg.args.len());
+                let mut arguments = Vec::with_capacity(agg.args.len());
+                for (i, arg) in agg.args.iter().enumerate() {
+                    let item = self.replace_window_item(arg, format!("{func_name}_arg_{i}"))?;
+                    replaced_args.push(self.column_ref(&item));
+                    arguments.push(item);
+                }
+                let replaced_agg = AggregateFunction {
+                    display_name: agg.display_name.clone(),
+                    func_name: agg.func_name.clone(),
+                    distinct: agg.distinct,
+                    params: agg.params.clone(),
+                    args: replaced_args,
+                    return_type: agg.return_type.clone(),
+                };
+                (WindowFuncType::Aggregate(replaced_agg), arguments)
+            }
+            WindowFuncType::LagLead(lag_lead) => {
+                let mut arguments =
+                    vec![self.replace_window_item(&lag_lead.arg, format!("{func_name}_arg_0"))?];
+                let default = match &lag_lead.default {
+                    None => None,
+                    Some(default) => {
+                        let item =
+                            self.replace_window_item(default, format!("{func_name}_arg_1"))?;
+                        arguments.push(item);
+                        Some(Box::new(self.column_ref(&arguments[1])))
+                    }
+                };
+                let replaced_lag_lead = LagLeadFunction {
+                    is_lag: lag_lead.is_lag,
+                    arg: Box::new(self.column_ref(&arguments[0])),
+                    offset: lag_lead.offset,
+                    default,
+                    return_type: lag_lead.return_type.clone(),
+                };
+                (WindowFuncType::LagLead(replaced_lag_lead), arguments)
+            }
+            func => (func.clone(), vec![]),
+        };
+
+        let mut partition_by_items = Vec::with_capacity(window.partition_by.len());
+        for (i, partition) in window.partition_by.iter().enumerate() {
+            partition_by_items
+                .push(self.replace_window_item(partition, format!("{func_name}_part_{i}"))?);
+        }
+
+        let mut order_by_items = Vec::with_capacity(window.order_by.len());
+        for (i, order) in window.order_by.iter().enumerate() {
+            order_by_items.push(WindowOrderByInfo {
+                order_by_item: self
+                    .replace_window_item(&order.expr, format!("{func_name}_order_{i}"))?,
+                asc: order.asc,
+                nulls_first: order.nulls_first,
+            });
+        }
+
+        let index = self
+            .metadata
+            .write()
+            .add_derived_column(window.display_name.clone(), *window.return_type.clone());
+
+        let window_info = &mut self.bind_context.window_info;
+        window_info.window_functions.push(WindowFunctionInfo {
+            index,
+            func,
+            arguments,
+            partition_by_items,
+            order_by_items,
+            frame: window.frame.clone(),
+        });
+        window_info.window_functions_map.insert(
+            window.display_name.clone(),
+            window_info.window_functions.len() - 1,
+        );
+
+        Ok(())
+    }
+
+    /// Resolve the input of window function in current context. If it's not a column reference,
+    /// a derived column will be generated and evaluated before window functions.
+    fn replace_window_item(&mut self, scalar: &Scalar, name: String) -> Result<ScalarItem> {
+        let scalar = if self.bind_context.in_grouping {
+            let mut grouping_checker = GroupingChecker::new(self.bind_context);
+            grouping_checker.resolve(scalar, None)?
+        } else {
+            scalar.clone()
+        };
+
+        if let Scalar::BoundColumnRef(column_ref) = &scalar {
+            return Ok(ScalarItem {
+                index: column_ref.column.index,
+                scalar,
+            });
+        }
+
+        let index = self
+            .metadata
+            .write()
+            .add_derived_column(name, scalar.data_type());
+        let item = ScalarItem { scalar, index };
+        self.bind_context
+            .window_info
+            .window_items
+            .push(item.clone());
+        Ok(item)
+    }
+
+    fn column_ref(&self, item: &ScalarItem) -> Scalar {
+        if let Scalar::BoundColumnRef(_) = &item.scalar {
+            return item.scalar.clone();
+        }
+        BoundColumnRef {
+            column: ColumnBinding {
+                database_name: None,
+                table_name: None,
+                // Since window arguments can not be referenced, the name is only for debug
+                column_name: format!("window_arg_{}", item.index),
+                index: item.index,
+                data_type: Box::new(item.scalar.data_type()),
+                visibility: Visibility::Visible,
+            },
+        }
+        .into()
+    }
+}
+
+impl Binder {
+    /// Analyze window functions in select clause and order by clause, and build `Window`
+    /// operators for them.
+    /// The arguments, partition by items and order by items are evaluated by an `EvalScalar`
+    /// in advance, then each window function is computed by a `Window` operator, whose output
+    /// column will be referenced by the projection.
+    pub(super) fn bind_window(
+        &mut self,
+        bind_context: &mut BindContext,
+        scalar_items: &HashMap<IndexType, ScalarItem>,
+        child: SExpr,
+    ) -> Result<SExpr> {
+        // Visit the items in the order of index, so that the window functions are
+        // registered in the order they appear in select clause and order by clause.
+        let mut items = scalar_items.values().collect::<Vec<_>>();
+        items.sort_by_key(|item| item.index);
+        for item in items {
+            let mut rewriter = WindowRewriter::new(bind_context, self.metadata.clone());
+            rewriter.visit(&item.scalar)?;
+        }
+
+        let window_info = &bind_context.window_info;
+        if window_info.window_functions.is_empty() {
+            return Ok(child);
+        }
+
+        let mut new_expr = child;
+        if !window_info.window_items.is_empty() {
+            let eval_scalar = EvalScalar {
+                items: window_info.window_items.clone(),
+            };
+            new_expr = SExpr::create_unary(eval_scalar.into(), new_expr);
+        }
+
+        for window_func in window_info.window_functions.iter() {
+            let window_plan = Window {
+                index: window_func.index,
+                function: window_func.func.clone(),
+                arguments: window_func.arguments.clone(),
+                partition_by: window_func.partition_by_items.clone(),
+                order_by: window_func.order_by_items.clone(),
+                frame: window_func.frame.clone(),
+            };
+            new_expr = SExpr::create_unary(window_plan.into(), new_expr);
+        }
+
+        Ok(new_expr)
+    }
+}
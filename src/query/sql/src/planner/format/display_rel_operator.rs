@@ -76,6 +76,9 @@ impl Display for FormatContext {
                 RelOperator::UnionAll(_) => write!(f, "Union"),
                 RelOperator::Pattern(_) => write!(f, "Pattern"),
                 RelOperator::DummyTableScan(_) => write!(f, "DummyTableScan"),
+                RelOperator::Window(_) => write!(f, "Window"),
+                RelOperator::RecursiveCte(_) => write!(f, "RecursiveCte"),
+                RelOperator::RecursiveCteScan(_) => write!(f, "RecursiveCteScan"),
             },
             Self::Text(text) => write!(f, "{}", text),
         }
@@ -98,6 +101,7 @@ pub fn format_scalar(_metadata: &MetadataRef, scalar: &Scalar) -> String {
             }
         }
         Scalar::ConstantExpr(constant) => constant.value.to_string(),
+        Scalar::PlaceholderExpr(placeholder) => format!("?{}", placeholder.index),
         Scalar::AndExpr(and) => format!(
             "({}) AND ({})",
             format_scalar(_metadata, &and.left),
@@ -116,6 +120,7 @@ pub fn format_scalar(_metadata: &MetadataRef, scalar: &Scalar) -> String {
             format_scalar(_metadata, &comp.right)
         ),
         Scalar::AggregateFunction(agg) => agg.display_name.clone(),
+        Scalar::WindowFunction(window) => window.display_name.clone(),
         Scalar::FunctionCall(func) => {
             format!(
                 "{}({})",
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::fmt::Debug;
@@ -26,6 +27,8 @@ use common_expression::TableDataType;
 use common_expression::TableField;
 use parking_lot::RwLock;
 
+use crate::plans::Scalar;
+
 /// Planner use [`usize`] as it's index type.
 ///
 /// This type will be used across the whole planner.
@@ -56,9 +59,33 @@ pub type MetadataRef = Arc<RwLock<Metadata>>;
 pub struct Metadata {
     tables: Vec<TableEntry>,
     columns: Vec<ColumnEntry>,
+    /// Aggregating indexes of the tables, keyed by table index.
+    aggregating_indexes: HashMap<IndexType, Vec<AggregatingIndex>>,
+    /// Keep the `?` placeholders of a prepared statement in the plan instead of
+    /// binding them to the parameters, see `Planner::bind_prepared`.
+    keep_placeholders: bool,
+    /// Number of the placeholders of the statement before the expressions bound with
+    /// the metadata, e.g. the placeholders in the previous rows of `INSERT ... VALUES`.
+    placeholder_offset: usize,
 }
 
 impl Metadata {
+    pub fn keep_placeholders(&self) -> bool {
+        self.keep_placeholders
+    }
+
+    pub fn set_keep_placeholders(&mut self, keep_placeholders: bool) {
+        self.keep_placeholders = keep_placeholders;
+    }
+
+    pub fn placeholder_offset(&self) -> usize {
+        self.placeholder_offset
+    }
+
+    pub fn set_placeholder_offset(&mut self, placeholder_offset: usize) {
+        self.placeholder_offset = placeholder_offset;
+    }
+
     pub fn table(&self, index: IndexType) -> &TableEntry {
         self.tables.get(index).expect("metadata must contain table")
     }
@@ -152,8 +179,8 @@ impl Metadata {
             fields.push_back((vec![i], field.clone()));
         }
 
-        // build leaf index in DFS order for primitive columns.
-        let mut leaf_index = 0;
+        // the leaf index of primitive columns is the column id, which is the id
+        // of the leaf column in DFS order when the column was added.
         while !fields.is_empty() {
             let (indices, field) = fields.pop_front().unwrap();
             let path_indices = if indices.len() > 1 {
@@ -175,6 +202,13 @@ impl Metadata {
                     None,
                 );
 
+                let mut inner_column_ids = Vec::with_capacity(fields_type.len());
+                let mut next_column_id = field.column_id();
+                for inner_field_type in fields_type.iter() {
+                    inner_column_ids.push(next_column_id);
+                    next_column_id += inner_field_type.num_leaf_columns();
+                }
+
                 let mut i = fields_type.len();
                 for (inner_field_name, inner_field_type) in
                     fields_name.iter().rev().zip(fields_type.iter().rev())
@@ -184,7 +218,8 @@ impl Metadata {
                     inner_indices.push(i);
                     // create tuple inner field
                     let inner_name = format!("{}:{}", field.name(), inner_field_name);
-                    let inner_field = TableField::new(&inner_name, inner_field_type.clone());
+                    let inner_field = TableField::new(&inner_name, inner_field_type.clone())
+                        .with_column_id(inner_column_ids[i]);
                     fields.push_front((inner_indices, inner_field));
                 }
             } else {
@@ -193,14 +228,31 @@ impl Metadata {
                     field.data_type().clone(),
                     table_index,
                     path_indices,
-                    Some(leaf_index),
+                    Some(field.column_id() as IndexType),
                 );
-                leaf_index += 1;
             }
         }
         table_index
     }
 
+    pub fn add_aggregating_index(&mut self, table_index: IndexType, index: AggregatingIndex) {
+        self.aggregating_indexes
+            .entry(table_index)
+            .or_default()
+            .push(index);
+    }
+
+    pub fn aggregating_indexes(&self, table_index: IndexType) -> &[AggregatingIndex] {
+        self.aggregating_indexes
+            .get(&table_index)
+            .map(|indexes| indexes.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn has_aggregating_indexes(&self) -> bool {
+        !self.aggregating_indexes.is_empty()
+    }
+
     /// find_smallest_column in given indices.
     pub fn find_smallest_column(&self, indices: &[IndexType]) -> IndexType {
         let mut smallest_index = indices.iter().min().expect("indices must be valid");
@@ -312,6 +364,38 @@ impl TableEntry {
     }
 }
 
+/// A materialized view aggregating a table of the query, which can answer the
+/// aggregations over the table with less rows to read.
+///
+/// The group keys, aggregate arguments and predicates of the view are bound
+/// against the columns of the aggregated table.
+#[derive(Clone)]
+pub struct AggregatingIndex {
+    pub catalog: String,
+    pub database: String,
+    pub table: Arc<dyn Table>,
+
+    /// `(column, field)` pairs, the group key `column` is stored in `field` of the view.
+    pub group_items: Vec<(IndexType, usize)>,
+    /// `(function name, argument column, field)` tuples, `count(*)` has no argument.
+    pub aggregate_functions: Vec<(String, Option<IndexType>, usize)>,
+    /// Conjunctive predicates of the `WHERE` clause of the view.
+    pub predicates: Vec<Scalar>,
+}
+
+impl Debug for AggregatingIndex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AggregatingIndex")
+            .field("catalog", &self.catalog)
+            .field("database", &self.database)
+            .field("name", &self.table.name())
+            .field("group_items", &self.group_items)
+            .field("aggregate_functions", &self.aggregate_functions)
+            .field("predicates", &self.predicates)
+            .finish()
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum ColumnEntry {
     /// Column from base table, for example `SELECT t.a, t.b FROM t`.
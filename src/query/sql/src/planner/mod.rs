@@ -14,6 +14,7 @@
 
 mod format;
 mod metadata;
+mod placeholder;
 #[allow(clippy::module_inception)]
 mod planner;
 mod semantic;
@@ -33,6 +34,7 @@ pub use binder::Visibility;
 pub use expression_parser::parse_exprs;
 pub use expression_parser::parse_to_remote_string_exprs;
 pub use metadata::*;
+pub use placeholder::count_placeholders;
 pub use planner::Planner;
 pub use plans::ScalarExpr;
 pub use semantic::normalize_identifier;
@@ -0,0 +1,417 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::collections::HashMap;
+
+use common_exception::Result;
+use common_expression::types::DataType;
+use common_functions::aggregates::AggregateFunctionFactory;
+
+use crate::optimizer::SExpr;
+use crate::plans::Aggregate;
+use crate::plans::AggregateFunction;
+use crate::plans::AggregateMode;
+use crate::plans::BoundColumnRef;
+use crate::plans::EvalScalar;
+use crate::plans::Filter;
+use crate::plans::RelOperator;
+use crate::plans::Scalar;
+use crate::plans::ScalarItem;
+use crate::plans::Scan;
+use crate::plans::Statistics;
+use crate::AggregatingIndex;
+use crate::ColumnBinding;
+use crate::ColumnEntry;
+use crate::IndexType;
+use crate::Metadata;
+use crate::MetadataRef;
+use crate::ScalarExpr;
+use crate::Visibility;
+
+/// Rewrite the aggregations over a table to read its aggregating indexes, i.e. the
+/// materialized views which group the table by a superset of the group keys.
+///
+/// The views are maintained incrementally, thus a group may be split into several
+/// rows of a view. The aggregations are computed again over the view:
+///
+/// ```text
+///  Aggregate: sum(b), count(*)      Aggregate: sum(s), sum(c)
+///     group by: a                      group by: a
+///         |                                  |
+///   Filter: a > 1 and c = 0   =>      Filter: a > 1
+///         |                                  |
+///     Scan: t                     EvalScalar: a := mv.a
+///                                            |
+///                                        Scan: mv
+/// ```
+///
+/// where `mv` is `SELECT a, sum(b) AS s, count(*) AS c FROM t WHERE c = 0 GROUP BY a, d`.
+pub struct AggregatingIndexRewriter {
+    metadata: MetadataRef,
+}
+
+/// How an aggregation is answered by an aggregating index.
+struct IndexMatch {
+    /// `(column, field)` pairs, the input `column` of the aggregation is read from `field`.
+    columns: Vec<(IndexType, usize)>,
+    /// The rewritten aggregate functions, the argument is the field of the view.
+    aggregate_functions: Vec<(ScalarItem, usize)>,
+    /// The predicates not implied by the view.
+    predicates: Vec<Scalar>,
+}
+
+impl AggregatingIndexRewriter {
+    pub fn new(metadata: MetadataRef) -> Self {
+        Self { metadata }
+    }
+
+    pub fn rewrite(&self, s_expr: &SExpr) -> Result<SExpr> {
+        if !self.metadata.read().has_aggregating_indexes() {
+            return Ok(s_expr.clone());
+        }
+        self.rewrite_impl(s_expr)
+    }
+
+    fn rewrite_impl(&self, s_expr: &SExpr) -> Result<SExpr> {
+        if let RelOperator::Aggregate(aggregate) = s_expr.plan() {
+            if let Some(result) = self.try_rewrite_aggregate(aggregate, s_expr.child(0)?)? {
+                return Ok(result);
+            }
+        }
+
+        let children = s_expr
+            .children()
+            .iter()
+            .map(|child| self.rewrite_impl(child))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(s_expr.replace_children(children))
+    }
+
+    /// Match `Aggregate -> [EvalScalar] -> [Filter] -> Scan`, and rewrite it with the
+    /// smallest aggregating index that can answer the aggregation.
+    fn try_rewrite_aggregate(&self, aggregate: &Aggregate, input: &SExpr) -> Result<Option<SExpr>> {
+        if aggregate.mode != AggregateMode::Initial || aggregate.from_distinct {
+            return Ok(None);
+        }
+
+        // The columns renamed by the `EvalScalar`, mapping to the columns of the table.
+        let mut renamed = HashMap::new();
+        let mut input = input;
+        if let RelOperator::EvalScalar(eval_scalar) = input.plan() {
+            for item in eval_scalar.items.iter() {
+                match &item.scalar {
+                    Scalar::BoundColumnRef(column) => {
+                        renamed.insert(item.index, column.column.index);
+                    }
+                    _ => return Ok(None),
+                }
+            }
+            input = input.child(0)?;
+        }
+        let predicates = match input.plan() {
+            RelOperator::Filter(filter) if !filter.is_having => {
+                input = input.child(0)?;
+                filter.predicates.clone()
+            }
+            _ => vec![],
+        };
+        let scan = match input.plan() {
+            RelOperator::Scan(scan)
+                if scan.push_down_predicates.is_none()
+                    && scan.limit.is_none()
+                    && scan.order_by.is_none()
+                    && scan.prewhere.is_none() =>
+            {
+                scan
+            }
+            _ => return Ok(None),
+        };
+        let table_column = |index: IndexType| {
+            let column = renamed.get(&index).cloned().unwrap_or(index);
+            scan.columns.contains(&column).then_some(column)
+        };
+
+        // `(output, column)` pairs of the group keys.
+        let mut group_items = Vec::with_capacity(aggregate.group_items.len());
+        for item in aggregate.group_items.iter() {
+            match &item.scalar {
+                Scalar::BoundColumnRef(column) => match table_column(column.column.index) {
+                    Some(column) => group_items.push((item.index, column)),
+                    None => return Ok(None),
+                },
+                _ => return Ok(None),
+            }
+        }
+
+        // `(function, argument column)` pairs of the aggregate functions.
+        let mut aggregate_functions = Vec::with_capacity(aggregate.aggregate_functions.len());
+        for item in aggregate.aggregate_functions.iter() {
+            let function = match &item.scalar {
+                Scalar::AggregateFunction(function)
+                    if !function.distinct && function.params.is_empty() =>
+                {
+                    function
+                }
+                _ => return Ok(None),
+            };
+            let arg = match function.args.as_slice() {
+                [] => None,
+                [Scalar::BoundColumnRef(column)] => match table_column(column.column.index) {
+                    Some(column) => Some(column),
+                    None => return Ok(None),
+                },
+                _ => return Ok(None),
+            };
+            aggregate_functions.push((item, function, arg));
+        }
+
+        let mut best: Option<(u64, AggregatingIndex, IndexMatch)> = None;
+        {
+            let metadata = self.metadata.read();
+            for index in metadata.aggregating_indexes(scan.table_index) {
+                let index_match = match Self::match_index(
+                    index,
+                    &group_items,
+                    &aggregate_functions,
+                    &predicates,
+                    &metadata,
+                )? {
+                    Some(index_match) => index_match,
+                    None => continue,
+                };
+                let num_rows = index
+                    .table
+                    .table_statistics()?
+                    .and_then(|statistics| statistics.num_rows)
+                    .unwrap_or(u64::MAX);
+                if best.as_ref().map_or(true, |(rows, _, _)| num_rows < *rows) {
+                    best = Some((num_rows, index.clone(), index_match));
+                }
+            }
+        }
+
+        match best {
+            Some((_, index, index_match)) => Ok(Some(self.build_index_scan(
+                aggregate,
+                &index,
+                index_match,
+            )?)),
+            None => Ok(None),
+        }
+    }
+
+    fn match_index(
+        index: &AggregatingIndex,
+        group_items: &[(IndexType, IndexType)],
+        aggregate_functions: &[(&ScalarItem, &AggregateFunction, Option<IndexType>)],
+        predicates: &[Scalar],
+        metadata: &Metadata,
+    ) -> Result<Option<IndexMatch>> {
+        let view_schema = index.table.schema();
+        let field_type = |field: usize| DataType::from(view_schema.field(field).data_type());
+        let column_type = |column: IndexType| match metadata.column(column) {
+            ColumnEntry::BaseTableColumn { data_type, .. } => DataType::from(data_type),
+            ColumnEntry::DerivedColumn { data_type, .. } => data_type.clone(),
+        };
+        // The field storing the group key `column`.
+        let key_field = |column: IndexType| {
+            index
+                .group_items
+                .iter()
+                .find(|(key, field)| *key == column && field_type(*field) == column_type(column))
+                .map(|(_, field)| *field)
+        };
+
+        // The rows filtered out by the view must be filtered out by the query too,
+        // the remaining predicates are evaluated over the group keys of the view.
+        if !index
+            .predicates
+            .iter()
+            .all(|predicate| predicates.contains(predicate))
+        {
+            return Ok(None);
+        }
+        let predicates = predicates
+            .iter()
+            .filter(|predicate| !index.predicates.contains(predicate))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let mut columns = Vec::new();
+        for (output, column) in group_items.iter() {
+            match key_field(*column) {
+                Some(field) => columns.push((*output, field)),
+                None => return Ok(None),
+            }
+        }
+        for predicate in predicates.iter() {
+            for column in predicate.used_columns() {
+                match key_field(column) {
+                    Some(field) => {
+                        if !columns.iter().any(|(output, _)| *output == column) {
+                            columns.push((column, field));
+                        }
+                    }
+                    None => return Ok(None),
+                }
+            }
+        }
+
+        let mut functions = Vec::with_capacity(aggregate_functions.len());
+        for (item, function, arg) in aggregate_functions.iter() {
+            let name = function.func_name.to_lowercase();
+            let field = match index
+                .aggregate_functions
+                .iter()
+                .find(|(index_name, index_arg, _)| *index_name == name && index_arg == arg)
+            {
+                Some((_, _, field)) => *field,
+                None => return Ok(None),
+            };
+            // The count of an empty input is 0, but the sum of the counts is NULL.
+            if name == "count" && group_items.is_empty() {
+                return Ok(None);
+            }
+            let func_name = match name.as_str() {
+                "count" => "sum".to_string(),
+                name => name.to_string(),
+            };
+            let return_type = AggregateFunctionFactory::instance()
+                .get(&func_name, vec![], vec![field_type(field)])?
+                .return_type()?;
+            if return_type != *function.return_type {
+                return Ok(None);
+            }
+            functions.push((
+                ScalarItem {
+                    scalar: AggregateFunction {
+                        func_name,
+                        args: vec![],
+                        ..(*function).clone()
+                    }
+                    .into(),
+                    index: item.index,
+                },
+                field,
+            ));
+        }
+
+        Ok(Some(IndexMatch {
+            columns,
+            aggregate_functions: functions,
+            predicates,
+        }))
+    }
+
+    fn build_index_scan(
+        &self,
+        aggregate: &Aggregate,
+        index: &AggregatingIndex,
+        index_match: IndexMatch,
+    ) -> Result<SExpr> {
+        let mut metadata = self.metadata.write();
+        let table_index = metadata.add_table(
+            index.catalog.clone(),
+            index.database.clone(),
+            index.table.clone(),
+            None,
+        );
+        let columns = metadata.columns_by_table_index(table_index);
+        // The columns of the fields of the view, in order of the fields.
+        let field_columns = columns
+            .iter()
+            .filter_map(|column| match column {
+                ColumnEntry::BaseTableColumn {
+                    column_index,
+                    column_name,
+                    data_type,
+                    path_indices: None,
+                    ..
+                } => Some(BoundColumnRef {
+                    column: ColumnBinding {
+                        database_name: Some(index.database.clone()),
+                        table_name: Some(index.table.name().to_string()),
+                        column_name: column_name.clone(),
+                        index: *column_index,
+                        data_type: Box::new(DataType::from(data_type)),
+                        visibility: Visibility::Visible,
+                    },
+                }),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        drop(metadata);
+
+        let mut result = SExpr::create_leaf(
+            Scan {
+                table_index,
+                columns: columns.iter().map(|column| column.index()).collect(),
+                push_down_predicates: None,
+                limit: None,
+                order_by: None,
+                prewhere: None,
+                statistics: Statistics {
+                    statistics: index.table.table_statistics()?,
+                    col_stats: HashMap::new(),
+                    is_accurate: index.table.engine().to_lowercase() == "fuse",
+                },
+            }
+            .into(),
+        );
+        if !index_match.columns.is_empty() {
+            result = SExpr::create_unary(
+                EvalScalar {
+                    items: index_match
+                        .columns
+                        .iter()
+                        .map(|(output, field)| ScalarItem {
+                            scalar: field_columns[*field].clone().into(),
+                            index: *output,
+                        })
+                        .collect(),
+                }
+                .into(),
+                result,
+            );
+        }
+        if !index_match.predicates.is_empty() {
+            result = SExpr::create_unary(
+                Filter {
+                    predicates: index_match.predicates,
+                    is_having: false,
+                }
+                .into(),
+                result,
+            );
+        }
+
+        let aggregate_functions = index_match
+            .aggregate_functions
+            .into_iter()
+            .map(|(mut item, field)| {
+                if let Scalar::AggregateFunction(function) = &mut item.scalar {
+                    function.args = vec![field_columns[field].clone().into()];
+                }
+                item
+            })
+            .collect();
+        Ok(SExpr::create_unary(
+            Aggregate {
+                aggregate_functions,
+                ..aggregate.clone()
+            }
+            .into(),
+            result,
+        ))
+    }
+}
@@ -687,7 +687,7 @@ impl SubqueryRewriter {
                 }
                 Ok(scalar.clone())
             }
-            Scalar::ConstantExpr(_) => Ok(scalar.clone()),
+            Scalar::ConstantExpr(_) | Scalar::PlaceholderExpr(_) => Ok(scalar.clone()),
             Scalar::AndExpr(and_expr) => {
                 let left = self.flatten_scalar(&and_expr.left, correlated_columns)?;
                 let right = self.flatten_scalar(&and_expr.right, correlated_columns)?;
@@ -243,7 +243,31 @@ impl UnusedColumnPruner {
                 ))
             }
 
-            RelOperator::DummyTableScan(_) => Ok(expr.clone()),
+            RelOperator::Window(p) => {
+                if !required.contains(&p.index) {
+                    // Eliminate unneccessary `Window`
+                    return self.keep_required_columns(expr.child(0)?, required);
+                }
+                // The arguments, partition by and order by items come from its EvalScalar child,
+                // so we only include the column indexes here.
+                p.arguments.iter().for_each(|item| {
+                    required.insert(item.index);
+                });
+                p.partition_by.iter().for_each(|item| {
+                    required.insert(item.index);
+                });
+                p.order_by.iter().for_each(|info| {
+                    required.insert(info.order_by_item.index);
+                });
+                Ok(SExpr::create_unary(
+                    RelOperator::Window(p.clone()),
+                    self.keep_required_columns(expr.child(0)?, required)?,
+                ))
+            }
+
+            RelOperator::DummyTableScan(_)
+            | RelOperator::RecursiveCte(_)
+            | RelOperator::RecursiveCteScan(_) => Ok(expr.clone()),
 
             _ => Err(ErrorCode::Internal(
                 "Attempting to prune columns of a physical plan is not allowed",
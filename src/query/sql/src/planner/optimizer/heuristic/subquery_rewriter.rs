@@ -125,12 +125,14 @@ impl SubqueryRewriter {
                 self.rewrite(s_expr.child(1)?)?,
             )),
 
-            RelOperator::Limit(_) | RelOperator::Sort(_) => Ok(SExpr::create_unary(
-                s_expr.plan().clone(),
-                self.rewrite(s_expr.child(0)?)?,
-            )),
+            RelOperator::Limit(_) | RelOperator::Sort(_) | RelOperator::Window(_) => Ok(
+                SExpr::create_unary(s_expr.plan().clone(), self.rewrite(s_expr.child(0)?)?),
+            ),
 
-            RelOperator::DummyTableScan(_) | RelOperator::Scan(_) => Ok(s_expr.clone()),
+            RelOperator::DummyTableScan(_)
+            | RelOperator::Scan(_)
+            | RelOperator::RecursiveCte(_)
+            | RelOperator::RecursiveCteScan(_) => Ok(s_expr.clone()),
 
             _ => Err(ErrorCode::Internal("Invalid plan type")),
         }
@@ -147,7 +149,9 @@ impl SubqueryRewriter {
         match scalar {
             Scalar::BoundColumnRef(_) => Ok((scalar.clone(), s_expr.clone())),
 
-            Scalar::ConstantExpr(_) => Ok((scalar.clone(), s_expr.clone())),
+            Scalar::ConstantExpr(_) | Scalar::PlaceholderExpr(_) => {
+                Ok((scalar.clone(), s_expr.clone()))
+            }
 
             Scalar::AndExpr(expr) => {
                 // Notice that the conjunctions has been flattened in binder, if we encounter
@@ -207,7 +211,9 @@ impl SubqueryRewriter {
                 ))
             }
 
-            Scalar::AggregateFunction(_) => Ok((scalar.clone(), s_expr.clone())),
+            Scalar::AggregateFunction(_) | Scalar::WindowFunction(_) => {
+                Ok((scalar.clone(), s_expr.clone()))
+            }
 
             Scalar::FunctionCall(func) => {
                 let mut args = vec![];
@@ -529,7 +535,7 @@ pub fn check_child_expr_in_subquery(
 ) -> Result<(Scalar, bool)> {
     match child_expr {
         Scalar::BoundColumnRef(_) => Ok((child_expr.clone(), op != &ComparisonOp::Equal)),
-        Scalar::ConstantExpr(_) => Ok((child_expr.clone(), true)),
+        Scalar::ConstantExpr(_) | Scalar::PlaceholderExpr(_) => Ok((child_expr.clone(), true)),
         Scalar::CastExpr(cast) => {
             let arg = &cast.argument;
             let (_, is_non_equi_condition) = check_child_expr_in_subquery(arg, op)?;
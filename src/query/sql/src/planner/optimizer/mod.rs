@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod aggregating_index;
 mod cascades;
 mod cost;
 mod distributed;
@@ -28,6 +29,7 @@ mod rule;
 mod s_expr;
 mod util;
 
+pub use aggregating_index::AggregatingIndexRewriter;
 pub use heuristic::HeuristicOptimizer;
 pub use heuristic::DEFAULT_REWRITE_RULES;
 pub use m_expr::MExpr;
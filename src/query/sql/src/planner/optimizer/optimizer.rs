@@ -27,18 +27,23 @@ use crate::optimizer::cascades::CascadesOptimizer;
 use crate::optimizer::distributed::optimize_distributed_query;
 use crate::optimizer::heuristic::RuleList;
 use crate::optimizer::util::contains_local_table_scan;
+use crate::optimizer::util::contains_recursive_cte;
+use crate::optimizer::AggregatingIndexRewriter;
 use crate::optimizer::HeuristicOptimizer;
 use crate::optimizer::SExpr;
 use crate::optimizer::DEFAULT_REWRITE_RULES;
 use crate::plans::CopyPlanV2;
 use crate::plans::Plan;
+use crate::plans::RelOperator;
 use crate::BindContext;
+use crate::ColumnBinding;
 use crate::IndexType;
 use crate::MetadataRef;
 
 #[derive(Debug, Clone, Default)]
 pub struct OptimizerConfig {
     pub enable_distributed_optimization: bool,
+    pub enable_aggregating_index: bool,
 }
 
 #[derive(Debug)]
@@ -145,16 +150,24 @@ pub fn optimize_query(
 
     let contains_local_table_scan = contains_local_table_scan(&s_expr, &metadata);
 
-    let mut heuristic = HeuristicOptimizer::new(ctx.clone(), bind_context, metadata, rules);
+    let s_expr = if opt_ctx.config.enable_aggregating_index {
+        AggregatingIndexRewriter::new(metadata.clone()).rewrite(&s_expr)?
+    } else {
+        s_expr
+    };
+
+    let mut heuristic = HeuristicOptimizer::new(ctx.clone(), bind_context, metadata.clone(), rules);
     let mut result = heuristic.optimize(s_expr)?;
+    result = optimize_recursive_cte(ctx.clone(), metadata, &result)?;
 
     let mut cascades = CascadesOptimizer::create(ctx.clone())?;
     result = cascades.optimize(result)?;
 
     // So far, we don't have ability to execute distributed query
     // with reading data from local tales(e.g. system tables).
-    let enable_distributed_query =
-        opt_ctx.config.enable_distributed_optimization && !contains_local_table_scan;
+    let enable_distributed_query = opt_ctx.config.enable_distributed_optimization
+        && !contains_local_table_scan
+        && !contains_recursive_cte(&result);
     if enable_distributed_query {
         result = optimize_distributed_query(ctx.clone(), &result)?;
     }
@@ -162,6 +175,41 @@ pub fn optimize_query(
     Ok(result)
 }
 
+/// The members of a recursive CTE are planned as standalone queries, optimize them separately.
+fn optimize_recursive_cte(
+    ctx: Arc<dyn TableContext>,
+    metadata: MetadataRef,
+    s_expr: &SExpr,
+) -> Result<SExpr> {
+    if let RelOperator::RecursiveCte(cte) = s_expr.plan() {
+        let optimize_member = |member: &SExpr, columns: &[ColumnBinding]| -> Result<SExpr> {
+            let mut bind_context = BindContext::new();
+            for column in columns {
+                bind_context.add_column_binding(column.clone());
+            }
+            optimize_query(
+                ctx.clone(),
+                Arc::new(OptimizerContext::new(OptimizerConfig::default())),
+                metadata.clone(),
+                Box::new(bind_context),
+                member.clone(),
+            )
+        };
+
+        let mut cte = cte.clone();
+        cte.anchor = Box::new(optimize_member(&cte.anchor, &cte.anchor_columns)?);
+        cte.recursive = Box::new(optimize_member(&cte.recursive, &cte.recursive_columns)?);
+        return Ok(SExpr::create_leaf(cte.into()));
+    }
+
+    let children = s_expr
+        .children()
+        .iter()
+        .map(|child| optimize_recursive_cte(ctx.clone(), metadata.clone(), child))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(s_expr.replace_children(children))
+}
+
 // TODO(leiysky): reuse the optimization logic with `optimize_query`
 fn get_optimized_memo(
     ctx: Arc<dyn TableContext>,
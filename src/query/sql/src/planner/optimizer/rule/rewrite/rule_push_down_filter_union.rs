@@ -149,6 +149,7 @@ fn replace_column_binding(
             Ok(Scalar::BoundColumnRef(column))
         }
         constant_expr @ Scalar::ConstantExpr(_) => Ok(constant_expr),
+        placeholder @ Scalar::PlaceholderExpr(_) => Ok(placeholder),
         Scalar::AndExpr(expr) => Ok(Scalar::AndExpr(AndExpr {
             left: Box::new(replace_column_binding(index_pairs, *expr.left)?),
             right: Box::new(replace_column_binding(index_pairs, *expr.right)?),
@@ -196,6 +197,9 @@ fn replace_column_binding(
             from_type: expr.from_type,
             target_type: expr.target_type,
         })),
+        Scalar::WindowFunction(_) => Err(ErrorCode::Unimplemented(
+            "replace_column_binding: don't support window function",
+        )),
         Scalar::SubqueryExpr(_) => Err(ErrorCode::Unimplemented(
             "replace_column_binding: don't support subquery",
         )),
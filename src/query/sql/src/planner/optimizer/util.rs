@@ -28,3 +28,9 @@ pub fn contains_local_table_scan(s_expr: &SExpr, metadata: &MetadataRef) -> bool
             false
         }
 }
+
+/// Check if a query contains a recursive CTE, which can only be evaluated on the local node.
+pub fn contains_recursive_cte(s_expr: &SExpr) -> bool {
+    s_expr.children().iter().any(contains_recursive_cte)
+        || matches!(s_expr.plan(), RelOperator::RecursiveCte(_))
+}
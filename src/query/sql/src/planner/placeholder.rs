@@ -0,0 +1,263 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_ast::parser::token::Token;
+use common_ast::parser::token::TokenKind;
+use common_ast::parser::token::Tokenizer;
+use common_catalog::table_context::PlaceholderValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::types::number::F64;
+use common_expression::types::DataType;
+use common_expression::types::NumberDataType;
+use common_expression::Literal;
+
+use crate::optimizer::SExpr;
+use crate::plans::CastExpr;
+use crate::plans::ConstantExpr;
+use crate::plans::Exchange;
+use crate::plans::PlaceholderExpr;
+use crate::plans::RelOperator;
+use crate::plans::Scalar;
+use crate::plans::ScalarItem;
+use crate::plans::WindowFuncType;
+
+/// Returns the number of `?` placeholders in the SQL, placeholders inside
+/// string literals and comments are not counted.
+pub fn count_placeholders(sql: &str) -> Result<usize> {
+    let mut count = 0;
+    for token in Tokenizer::new(sql) {
+        if token?.kind == TokenKind::Placeholder {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// Returns the position of the placeholder starting the span among the placeholders of its SQL.
+pub fn placeholder_index(span: &[Token]) -> Result<usize> {
+    let placeholder = span
+        .first()
+        .ok_or_else(|| ErrorCode::Internal("Span of placeholder is empty"))?;
+    let mut index = 0;
+    for token in Tokenizer::new(placeholder.source) {
+        let token = token?;
+        if token.span.start >= placeholder.span.start {
+            break;
+        }
+        if token.kind == TokenKind::Placeholder {
+            index += 1;
+        }
+    }
+    Ok(index)
+}
+
+/// Builds the constant scalar of a parameter bound to a placeholder.
+pub fn placeholder_scalar(value: &PlaceholderValue) -> Scalar {
+    let (literal, data_type) = match value {
+        PlaceholderValue::Null => (Literal::Null, DataType::Null),
+        PlaceholderValue::Int(v) => (Literal::Int64(*v), DataType::Number(NumberDataType::Int64)),
+        PlaceholderValue::UInt(v) => (
+            Literal::UInt64(*v),
+            DataType::Number(NumberDataType::UInt64),
+        ),
+        PlaceholderValue::Float(v) => (
+            Literal::Float64(F64::from(*v)),
+            DataType::Number(NumberDataType::Float64),
+        ),
+        PlaceholderValue::String(v)
+        | PlaceholderValue::Date(v)
+        | PlaceholderValue::Timestamp(v) => {
+            (Literal::String(v.as_bytes().to_vec()), DataType::String)
+        }
+    };
+    let constant: Scalar = ConstantExpr {
+        value: literal,
+        data_type: Box::new(data_type.clone()),
+    }
+    .into();
+
+    match value {
+        PlaceholderValue::Date(_) | PlaceholderValue::Timestamp(_) => CastExpr {
+            argument: Box::new(constant),
+            from_type: Box::new(data_type),
+            target_type: Box::new(value.data_type()),
+        }
+        .into(),
+        _ => constant,
+    }
+}
+
+/// Replaces the placeholders kept in the plan of a prepared statement with the parameters
+/// of an execution, the parameters must have the types the plan was bound with.
+pub struct PlaceholderRewriter<'a> {
+    values: &'a [PlaceholderValue],
+}
+
+impl<'a> PlaceholderRewriter<'a> {
+    pub fn new(values: &'a [PlaceholderValue]) -> Self {
+        Self { values }
+    }
+
+    pub fn rewrite(&self, s_expr: &SExpr) -> Result<SExpr> {
+        let plan = match s_expr.plan().clone() {
+            RelOperator::Scan(mut plan) => {
+                if let Some(predicates) = plan.push_down_predicates.as_mut() {
+                    self.rewrite_scalars(predicates)?;
+                }
+                if let Some(prewhere) = plan.prewhere.as_mut() {
+                    self.rewrite_scalars(&mut prewhere.predicates)?;
+                }
+                plan.into()
+            }
+            RelOperator::Join(mut plan) => {
+                self.rewrite_scalars(&mut plan.left_conditions)?;
+                self.rewrite_scalars(&mut plan.right_conditions)?;
+                self.rewrite_scalars(&mut plan.non_equi_conditions)?;
+                plan.into()
+            }
+            RelOperator::EvalScalar(mut plan) => {
+                self.rewrite_items(&mut plan.items)?;
+                plan.into()
+            }
+            RelOperator::Filter(mut plan) => {
+                self.rewrite_scalars(&mut plan.predicates)?;
+                plan.into()
+            }
+            RelOperator::Aggregate(mut plan) => {
+                self.rewrite_items(&mut plan.group_items)?;
+                self.rewrite_items(&mut plan.aggregate_functions)?;
+                plan.into()
+            }
+            RelOperator::Window(mut plan) => {
+                self.rewrite_window_func(&mut plan.function)?;
+                self.rewrite_items(&mut plan.arguments)?;
+                self.rewrite_items(&mut plan.partition_by)?;
+                for order_by in plan.order_by.iter_mut() {
+                    self.rewrite_scalar(&mut order_by.order_by_item.scalar)?;
+                }
+                plan.into()
+            }
+            RelOperator::Exchange(Exchange::Hash(mut scalars)) => {
+                self.rewrite_scalars(&mut scalars)?;
+                Exchange::Hash(scalars).into()
+            }
+            RelOperator::RecursiveCte(mut plan) => {
+                plan.anchor = Box::new(self.rewrite(&plan.anchor)?);
+                plan.recursive = Box::new(self.rewrite(&plan.recursive)?);
+                plan.into()
+            }
+            plan @ (RelOperator::Sort(_)
+            | RelOperator::Limit(_)
+            | RelOperator::Exchange(_)
+            | RelOperator::UnionAll(_)
+            | RelOperator::DummyTableScan(_)
+            | RelOperator::RecursiveCteScan(_)
+            | RelOperator::Pattern(_)) => plan,
+        };
+
+        let children = s_expr
+            .children()
+            .iter()
+            .map(|child| self.rewrite(child))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(SExpr::create(plan, children, s_expr.original_group(), None))
+    }
+
+    fn rewrite_items(&self, items: &mut [ScalarItem]) -> Result<()> {
+        for item in items.iter_mut() {
+            self.rewrite_scalar(&mut item.scalar)?;
+        }
+        Ok(())
+    }
+
+    fn rewrite_scalars(&self, scalars: &mut [Scalar]) -> Result<()> {
+        for scalar in scalars.iter_mut() {
+            self.rewrite_scalar(scalar)?;
+        }
+        Ok(())
+    }
+
+    fn rewrite_window_func(&self, func: &mut WindowFuncType) -> Result<()> {
+        match func {
+            WindowFuncType::Aggregate(agg) => self.rewrite_scalars(&mut agg.args),
+            WindowFuncType::LagLead(lag_lead) => {
+                self.rewrite_scalar(&mut lag_lead.arg)?;
+                if let Some(default) = lag_lead.default.as_mut() {
+                    self.rewrite_scalar(default)?;
+                }
+                Ok(())
+            }
+            WindowFuncType::RowNumber | WindowFuncType::Rank | WindowFuncType::DenseRank => Ok(()),
+        }
+    }
+
+    fn rewrite_scalar(&self, scalar: &mut Scalar) -> Result<()> {
+        match scalar {
+            Scalar::PlaceholderExpr(placeholder) => {
+                let bound = self.bind(placeholder)?;
+                *scalar = bound;
+            }
+            Scalar::BoundColumnRef(_) | Scalar::ConstantExpr(_) => {}
+            Scalar::AndExpr(expr) => {
+                self.rewrite_scalar(&mut expr.left)?;
+                self.rewrite_scalar(&mut expr.right)?;
+            }
+            Scalar::OrExpr(expr) => {
+                self.rewrite_scalar(&mut expr.left)?;
+                self.rewrite_scalar(&mut expr.right)?;
+            }
+            Scalar::NotExpr(expr) => self.rewrite_scalar(&mut expr.argument)?,
+            Scalar::ComparisonExpr(expr) => {
+                self.rewrite_scalar(&mut expr.left)?;
+                self.rewrite_scalar(&mut expr.right)?;
+            }
+            Scalar::AggregateFunction(agg) => self.rewrite_scalars(&mut agg.args)?,
+            Scalar::WindowFunction(window) => {
+                self.rewrite_window_func(&mut window.func)?;
+                self.rewrite_scalars(&mut window.partition_by)?;
+                for order_by in window.order_by.iter_mut() {
+                    self.rewrite_scalar(&mut order_by.expr)?;
+                }
+            }
+            Scalar::FunctionCall(func) => self.rewrite_scalars(&mut func.arguments)?,
+            Scalar::CastExpr(cast) => self.rewrite_scalar(&mut cast.argument)?,
+            Scalar::SubqueryExpr(subquery) => {
+                subquery.subquery = Box::new(self.rewrite(&subquery.subquery)?);
+                if let Some(child_expr) = subquery.child_expr.as_mut() {
+                    self.rewrite_scalar(child_expr)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn bind(&self, placeholder: &PlaceholderExpr) -> Result<Scalar> {
+        let value = self.values.get(placeholder.index).ok_or_else(|| {
+            ErrorCode::BadArguments(format!(
+                "Missing parameter for placeholder {} of prepared statement",
+                placeholder.index + 1
+            ))
+        })?;
+        if value.data_type() != *placeholder.data_type {
+            return Err(ErrorCode::Internal(format!(
+                "Parameter {} of prepared statement is bound as {}, but got {}",
+                placeholder.index + 1,
+                placeholder.data_type,
+                value.data_type()
+            )));
+        }
+        Ok(placeholder_scalar(value))
+    }
+}
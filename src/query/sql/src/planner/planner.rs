@@ -27,6 +27,8 @@ use parking_lot::RwLock;
 use crate::optimizer::optimize;
 use crate::optimizer::OptimizerConfig;
 use crate::optimizer::OptimizerContext;
+use crate::planner::placeholder::PlaceholderRewriter;
+use crate::plans::InsertInputSource;
 use crate::plans::Plan;
 use crate::Binder;
 use crate::Metadata;
@@ -46,6 +48,58 @@ impl Planner {
     }
 
     pub async fn plan_sql(&mut self, sql: &str) -> Result<(Plan, MetadataRef, Option<String>)> {
+        let (plan, metadata, format) = self.bind_sql(sql, false).await?;
+        let optimized_plan = self.optimize(plan)?;
+        Ok((optimized_plan, metadata, format))
+    }
+
+    /// Bind the SQL of a prepared statement, the `?` placeholders are kept in the plan
+    /// so that it can be cached and planned for each execution with [`Planner::plan_prepared`].
+    ///
+    /// Returns `None` if the plan of the statement can't be reused.
+    pub async fn bind_prepared(&mut self, sql: &str) -> Result<Option<Plan>> {
+        let (plan, _, _) = self.bind_sql(sql, true).await?;
+        Ok(match &plan {
+            Plan::Query { .. } => Some(plan),
+            // The values are parsed with the parameters attached to the context on execution.
+            Plan::Insert(insert) if matches!(insert.source, InsertInputSource::Values(_)) => {
+                Some(plan)
+            }
+            _ => None,
+        })
+    }
+
+    /// Plan a statement bound by [`Planner::bind_prepared`] with the parameters attached to the context.
+    pub fn plan_prepared(&mut self, plan: &Plan) -> Result<Plan> {
+        match plan {
+            Plan::Query {
+                s_expr,
+                metadata,
+                bind_context,
+                rewrite_kind,
+                ignore_result,
+            } => {
+                let placeholders = self.ctx.get_placeholders();
+                let s_expr = PlaceholderRewriter::new(&placeholders).rewrite(s_expr)?;
+                // The optimizers add columns to the metadata, each execution works on its own copy.
+                let metadata = Arc::new(RwLock::new(metadata.read().clone()));
+                self.optimize(Plan::Query {
+                    s_expr: Box::new(s_expr),
+                    metadata,
+                    bind_context: bind_context.clone(),
+                    rewrite_kind: rewrite_kind.clone(),
+                    ignore_result: *ignore_result,
+                })
+            }
+            _ => Ok(plan.clone()),
+        }
+    }
+
+    async fn bind_sql(
+        &mut self,
+        sql: &str,
+        keep_placeholders: bool,
+    ) -> Result<(Plan, MetadataRef, Option<String>)> {
         let settings = self.ctx.get_settings();
         let sql_dialect = settings.get_sql_dialect()?;
 
@@ -80,6 +134,7 @@ impl Planner {
 
                 // Step 3: Bind AST with catalog, and generate a pure logical SExpr
                 let metadata = Arc::new(RwLock::new(Metadata::default()));
+                metadata.write().set_keep_placeholders(keep_placeholders);
                 let name_resolution_ctx = NameResolutionContext::try_from(settings.as_ref())?;
                 let binder = Binder::new(
                     self.ctx.clone(),
@@ -89,13 +144,7 @@ impl Planner {
                 );
                 let plan = binder.bind(&stmt).await?;
 
-                // Step 4: Optimize the SExpr with optimizers, and generate optimized physical SExpr
-                let opt_ctx = Arc::new(OptimizerContext::new(OptimizerConfig {
-                    enable_distributed_optimization: !self.ctx.get_cluster().is_empty(),
-                }));
-                let optimized_plan = optimize(self.ctx.clone(), opt_ctx, plan)?;
-
-                Ok((optimized_plan, metadata.clone(), format))
+                Ok((plan, metadata.clone(), format))
             }
             .await;
 
@@ -125,4 +174,14 @@ impl Planner {
             }
         }
     }
+
+    /// Optimize the SExpr with optimizers, and generate optimized physical SExpr
+    fn optimize(&self, plan: Plan) -> Result<Plan> {
+        let settings = self.ctx.get_settings();
+        let opt_ctx = Arc::new(OptimizerContext::new(OptimizerConfig {
+            enable_distributed_optimization: !self.ctx.get_cluster().is_empty(),
+            enable_aggregating_index: settings.get_enable_aggregating_index_scan()?,
+        }));
+        optimize(self.ctx.clone(), opt_ctx, plan)
+    }
 }
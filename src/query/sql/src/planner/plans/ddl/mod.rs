@@ -14,7 +14,9 @@
 mod account;
 mod catalog;
 mod database;
+mod index;
 mod stage;
+mod stream;
 mod table;
 mod udf;
 mod view;
@@ -22,7 +24,9 @@ mod view;
 pub use account::*;
 pub use catalog::*;
 pub use database::*;
+pub use index::*;
 pub use stage::*;
+pub use stream::*;
 pub use table::*;
 pub use udf::*;
 pub use view::*;
@@ -15,6 +15,10 @@ use std::sync::Arc;
 
 use common_expression::DataSchema;
 use common_expression::DataSchemaRef;
+use common_expression::TableSchemaRef;
+
+use crate::plans::Plan;
+use crate::plans::TableOptions;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct CreateViewPlan {
@@ -61,3 +65,42 @@ impl DropViewPlan {
         Arc::new(DataSchema::empty())
     }
 }
+
+/// Create a materialized view, whose rows are stored in a FUSE table and
+/// maintained on each insertion into the source table.
+#[derive(Clone, Debug)]
+pub struct CreateMaterializedViewPlan {
+    pub if_not_exists: bool,
+    pub tenant: String,
+    pub catalog: String,
+    pub database: String,
+    pub viewname: String,
+    pub schema: TableSchemaRef,
+    pub options: TableOptions,
+
+    pub source_catalog: String,
+    pub source_database: String,
+    pub source_table: String,
+    /// The query of the view, used to populate the view on creation.
+    pub as_select: Box<Plan>,
+}
+
+impl CreateMaterializedViewPlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::empty())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RefreshMaterializedViewPlan {
+    pub tenant: String,
+    pub catalog: String,
+    pub database: String,
+    pub viewname: String,
+}
+
+impl RefreshMaterializedViewPlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::empty())
+    }
+}
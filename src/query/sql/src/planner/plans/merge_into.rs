@@ -0,0 +1,176 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_catalog::plan::MatchedAction;
+use common_catalog::plan::MergeIntoInfo;
+use common_catalog::plan::UnmatchedAction;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::types::DataType;
+use common_expression::DataSchema;
+use common_expression::DataSchemaRef;
+use common_expression::RemoteExpr;
+use common_expression::TableSchemaRef;
+use common_meta_types::MetaId;
+
+use crate::plans::Plan;
+use crate::plans::Scalar;
+use crate::IndexType;
+
+#[derive(Clone, Debug)]
+pub struct MatchedClausePlan {
+    pub condition: Option<Scalar>,
+    /// The new values of the updated columns, `None` means the rows are deleted.
+    pub update_list: Option<HashMap<usize, Scalar>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct UnmatchedClausePlan {
+    pub condition: Option<Scalar>,
+    /// One value for each column of the target table, `None` means the default value of
+    /// the data type.
+    pub values: Vec<Option<Scalar>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct MergeIntoPlan {
+    pub catalog: String,
+    pub database: String,
+    pub table: String,
+    pub table_id: MetaId,
+    /// The query producing the rows of the source.
+    pub input: Box<Plan>,
+    /// Maps the column indexes of the target table to the positions in the table schema.
+    pub target_columns: HashMap<IndexType, usize>,
+    /// The column indexes of the source, in the order of the output of `input`.
+    pub source_columns: Vec<IndexType>,
+    /// Equi-join keys, pairs of the target expression and the source expression.
+    pub join_keys: Vec<(Scalar, Scalar)>,
+    pub matched_clauses: Vec<MatchedClausePlan>,
+    pub unmatched_clauses: Vec<UnmatchedClausePlan>,
+}
+
+impl MergeIntoPlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::empty())
+    }
+
+    /// Lower the bound expressions into expressions over the blocks the storage works on.
+    pub fn merge_into_info(&self, schema: TableSchemaRef) -> Result<MergeIntoInfo> {
+        let target_layout = &self.target_columns;
+        let source_layout = self
+            .source_columns
+            .iter()
+            .enumerate()
+            .map(|(pos, index)| (*index, pos))
+            .collect::<HashMap<_, _>>();
+        // The joined block is the columns of the target table followed by the columns of the source.
+        let mut joined_layout = target_layout.clone();
+        joined_layout.extend(
+            source_layout
+                .iter()
+                .map(|(index, pos)| (*index, pos + schema.num_fields())),
+        );
+
+        let mut target_keys = Vec::with_capacity(self.join_keys.len());
+        let mut source_keys = Vec::with_capacity(self.join_keys.len());
+        for (target_key, source_key) in self.join_keys.iter() {
+            target_keys.push(lower_scalar(target_key, target_layout)?);
+            source_keys.push(lower_scalar(source_key, &source_layout)?);
+        }
+
+        let matched_actions = self
+            .matched_clauses
+            .iter()
+            .map(|clause| {
+                let condition = clause
+                    .condition
+                    .as_ref()
+                    .map(|scalar| lower_scalar(scalar, &joined_layout))
+                    .transpose()?;
+                let update_list = match &clause.update_list {
+                    Some(update_list) => {
+                        let mut update_list = update_list
+                            .iter()
+                            .map(|(pos, scalar)| Ok((*pos, lower_scalar(scalar, &joined_layout)?)))
+                            .collect::<Result<Vec<_>>>()?;
+                        update_list.sort_by_key(|(pos, _)| *pos);
+                        Some(update_list)
+                    }
+                    None => None,
+                };
+                Ok(MatchedAction {
+                    condition,
+                    update_list,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let unmatched_actions = self
+            .unmatched_clauses
+            .iter()
+            .map(|clause| {
+                let condition = clause
+                    .condition
+                    .as_ref()
+                    .map(|scalar| lower_scalar(scalar, &source_layout))
+                    .transpose()?;
+                let values = clause
+                    .values
+                    .iter()
+                    .zip(schema.fields().iter())
+                    .map(|(value, field)| match value {
+                        Some(scalar) => lower_scalar(scalar, &source_layout),
+                        None => {
+                            let data_type = DataType::from(field.data_type());
+                            Ok(RemoteExpr::Constant {
+                                span: None,
+                                scalar: data_type.default_value(),
+                                data_type,
+                            })
+                        }
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(UnmatchedAction { condition, values })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(MergeIntoInfo {
+            target_keys,
+            source_keys,
+            matched_actions,
+            unmatched_actions,
+        })
+    }
+}
+
+fn lower_scalar(scalar: &Scalar, layout: &HashMap<IndexType, usize>) -> Result<RemoteExpr> {
+    let expr = scalar.as_expr_with_col_index()?;
+    if let Some(index) = expr
+        .column_refs()
+        .keys()
+        .find(|index| !layout.contains_key(index))
+    {
+        return Err(ErrorCode::Internal(format!(
+            "Column {} is not available in the expression {:?}",
+            index, scalar
+        )));
+    }
+    Ok(expr
+        .project_column_ref(|index| layout[index])
+        .as_remote_expr())
+}
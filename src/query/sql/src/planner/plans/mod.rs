@@ -26,11 +26,13 @@ mod join;
 mod kill;
 mod limit;
 mod list;
+mod merge_into;
 mod operator;
 mod pattern;
 mod plan;
 mod presign;
 mod recluster_table;
+mod recursive_cte;
 mod revert_table;
 mod scalar;
 mod scan;
@@ -39,6 +41,7 @@ pub mod share;
 mod sort;
 mod union_all;
 mod update;
+mod window;
 
 pub use aggregate::*;
 pub use call::CallPlan;
@@ -55,6 +58,7 @@ pub use join::*;
 pub use kill::KillPlan;
 pub use limit::*;
 pub use list::ListPlan;
+pub use merge_into::*;
 pub use operator::*;
 pub use pattern::PatternPlan;
 pub use plan::Plan::*;
@@ -62,6 +66,8 @@ pub use plan::RewriteKind::*;
 pub use plan::*;
 pub use presign::*;
 pub use recluster_table::ReclusterTablePlan;
+pub use recursive_cte::RecursiveCte;
+pub use recursive_cte::RecursiveCteScan;
 pub use revert_table::RevertTablePlan;
 pub use scalar::*;
 pub use scan::*;
@@ -70,3 +76,4 @@ pub use share::*;
 pub use sort::*;
 pub use union_all::UnionAll;
 pub use update::UpdatePlan;
+pub use window::*;
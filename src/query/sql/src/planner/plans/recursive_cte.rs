@@ -0,0 +1,168 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::sync::Arc;
+
+use common_catalog::table_context::TableContext;
+use common_exception::Result;
+
+use crate::optimizer::ColumnSet;
+use crate::optimizer::Distribution;
+use crate::optimizer::PhysicalProperty;
+use crate::optimizer::RelExpr;
+use crate::optimizer::RelationalProperty;
+use crate::optimizer::RequiredProperty;
+use crate::optimizer::SExpr;
+use crate::optimizer::Statistics;
+use crate::plans::Operator;
+use crate::plans::RelOp;
+use crate::ColumnBinding;
+use crate::IndexType;
+
+/// `RecursiveCte` evaluates a `WITH RECURSIVE` common table expression.
+///
+/// The anchor member is evaluated once, then the recursive member is evaluated
+/// repeatedly against the rows produced by the previous iteration (read through
+/// `RecursiveCteScan`) until an iteration produces no rows.
+///
+/// Both members are planned as standalone queries, so the operator is a leaf
+/// of the enclosing plan.
+#[derive(Clone, Debug)]
+pub struct RecursiveCte {
+    pub cte_index: IndexType,
+    pub anchor: Box<SExpr>,
+    pub recursive: Box<SExpr>,
+    /// Output columns of the anchor member, in CTE column order
+    pub anchor_columns: Vec<ColumnBinding>,
+    /// Output columns of the recursive member, in CTE column order
+    pub recursive_columns: Vec<ColumnBinding>,
+    /// Columns of the `RecursiveCteScan` inside the recursive member
+    pub scan_columns: Vec<IndexType>,
+    /// Columns produced by this operator
+    pub output_columns: Vec<IndexType>,
+}
+
+impl PartialEq for RecursiveCte {
+    fn eq(&self, other: &Self) -> bool {
+        self.cte_index == other.cte_index && self.output_columns == other.output_columns
+    }
+}
+
+impl Eq for RecursiveCte {}
+
+impl Hash for RecursiveCte {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.cte_index.hash(state);
+        self.output_columns.hash(state);
+    }
+}
+
+impl RecursiveCte {
+    pub fn used_columns(&self) -> Result<ColumnSet> {
+        Ok(self.output_columns.iter().cloned().collect())
+    }
+}
+
+impl Operator for RecursiveCte {
+    fn rel_op(&self) -> RelOp {
+        RelOp::RecursiveCte
+    }
+
+    fn derive_relational_prop<'a>(&self, _rel_expr: &RelExpr<'a>) -> Result<RelationalProperty> {
+        let anchor_prop = RelExpr::with_s_expr(&self.anchor).derive_relational_prop()?;
+        let output_columns: ColumnSet = self.output_columns.iter().cloned().collect();
+
+        Ok(RelationalProperty {
+            output_columns: output_columns.clone(),
+            outer_columns: ColumnSet::new(),
+            used_columns: output_columns,
+            // The number of iterations is unknown, use the anchor as a lower bound.
+            cardinality: anchor_prop.cardinality,
+            statistics: Statistics {
+                precise_cardinality: None,
+                column_stats: Default::default(),
+                is_accurate: false,
+            },
+        })
+    }
+
+    fn derive_physical_prop<'a>(&self, _rel_expr: &RelExpr<'a>) -> Result<PhysicalProperty> {
+        Ok(PhysicalProperty {
+            distribution: Distribution::Serial,
+        })
+    }
+
+    fn compute_required_prop_child<'a>(
+        &self,
+        _ctx: Arc<dyn TableContext>,
+        _rel_expr: &RelExpr<'a>,
+        _child_index: usize,
+        required: &RequiredProperty,
+    ) -> Result<RequiredProperty> {
+        Ok(required.clone())
+    }
+}
+
+/// `RecursiveCteScan` reads the rows produced by the previous iteration of
+/// the `RecursiveCte` identified by `cte_index`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RecursiveCteScan {
+    pub cte_index: IndexType,
+    pub columns: Vec<IndexType>,
+}
+
+impl RecursiveCteScan {
+    pub fn used_columns(&self) -> Result<ColumnSet> {
+        Ok(self.columns.iter().cloned().collect())
+    }
+}
+
+impl Operator for RecursiveCteScan {
+    fn rel_op(&self) -> RelOp {
+        RelOp::RecursiveCteScan
+    }
+
+    fn derive_relational_prop<'a>(&self, _rel_expr: &RelExpr<'a>) -> Result<RelationalProperty> {
+        let columns: ColumnSet = self.columns.iter().cloned().collect();
+        Ok(RelationalProperty {
+            output_columns: columns.clone(),
+            outer_columns: ColumnSet::new(),
+            used_columns: columns,
+            cardinality: 1.0,
+            statistics: Statistics {
+                precise_cardinality: None,
+                column_stats: Default::default(),
+                is_accurate: false,
+            },
+        })
+    }
+
+    fn derive_physical_prop<'a>(&self, _rel_expr: &RelExpr<'a>) -> Result<PhysicalProperty> {
+        Ok(PhysicalProperty {
+            distribution: Distribution::Serial,
+        })
+    }
+
+    fn compute_required_prop_child<'a>(
+        &self,
+        _ctx: Arc<dyn TableContext>,
+        _rel_expr: &RelExpr<'a>,
+        _child_index: usize,
+        required: &RequiredProperty,
+    ) -> Result<RequiredProperty> {
+        Ok(required.clone())
+    }
+}
@@ -43,11 +43,13 @@ pub trait ScalarExpr {
 pub enum Scalar {
     BoundColumnRef(BoundColumnRef),
     ConstantExpr(ConstantExpr),
+    PlaceholderExpr(PlaceholderExpr),
     AndExpr(AndExpr),
     OrExpr(OrExpr),
     NotExpr(NotExpr),
     ComparisonExpr(ComparisonExpr),
     AggregateFunction(AggregateFunction),
+    WindowFunction(WindowFunc),
     FunctionCall(FunctionCall),
     // TODO(leiysky): maybe we don't need this variant any more
     // after making functions static typed?
@@ -60,11 +62,13 @@ impl ScalarExpr for Scalar {
         match self {
             Scalar::BoundColumnRef(scalar) => scalar.data_type(),
             Scalar::ConstantExpr(scalar) => scalar.data_type(),
+            Scalar::PlaceholderExpr(scalar) => scalar.data_type(),
             Scalar::AndExpr(scalar) => scalar.data_type(),
             Scalar::OrExpr(scalar) => scalar.data_type(),
             Scalar::NotExpr(scalar) => scalar.data_type(),
             Scalar::ComparisonExpr(scalar) => scalar.data_type(),
             Scalar::AggregateFunction(scalar) => scalar.data_type(),
+            Scalar::WindowFunction(scalar) => scalar.data_type(),
             Scalar::FunctionCall(scalar) => scalar.data_type(),
             Scalar::CastExpr(scalar) => scalar.data_type(),
             Scalar::SubqueryExpr(scalar) => scalar.data_type(),
@@ -75,11 +79,13 @@ impl ScalarExpr for Scalar {
         match self {
             Scalar::BoundColumnRef(scalar) => scalar.used_columns(),
             Scalar::ConstantExpr(scalar) => scalar.used_columns(),
+            Scalar::PlaceholderExpr(scalar) => scalar.used_columns(),
             Scalar::AndExpr(scalar) => scalar.used_columns(),
             Scalar::OrExpr(scalar) => scalar.used_columns(),
             Scalar::NotExpr(scalar) => scalar.used_columns(),
             Scalar::ComparisonExpr(scalar) => scalar.used_columns(),
             Scalar::AggregateFunction(scalar) => scalar.used_columns(),
+            Scalar::WindowFunction(scalar) => scalar.used_columns(),
             Scalar::FunctionCall(scalar) => scalar.used_columns(),
             Scalar::CastExpr(scalar) => scalar.used_columns(),
             Scalar::SubqueryExpr(scalar) => scalar.used_columns(),
@@ -125,6 +131,25 @@ impl TryFrom<Scalar> for ConstantExpr {
     }
 }
 
+impl From<PlaceholderExpr> for Scalar {
+    fn from(v: PlaceholderExpr) -> Self {
+        Self::PlaceholderExpr(v)
+    }
+}
+
+impl TryFrom<Scalar> for PlaceholderExpr {
+    type Error = ErrorCode;
+    fn try_from(value: Scalar) -> Result<Self> {
+        if let Scalar::PlaceholderExpr(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::Internal(
+                "Cannot downcast Scalar to PlaceholderExpr",
+            ))
+        }
+    }
+}
+
 impl From<AndExpr> for Scalar {
     fn from(v: AndExpr) -> Self {
         Self::AndExpr(v)
@@ -214,6 +239,23 @@ impl TryFrom<Scalar> for AggregateFunction {
     }
 }
 
+impl From<WindowFunc> for Scalar {
+    fn from(v: WindowFunc) -> Self {
+        Self::WindowFunction(v)
+    }
+}
+
+impl TryFrom<Scalar> for WindowFunc {
+    type Error = ErrorCode;
+    fn try_from(value: Scalar) -> Result<Self> {
+        if let Scalar::WindowFunction(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::Internal("Cannot downcast Scalar to WindowFunc"))
+        }
+    }
+}
+
 impl From<FunctionCall> for Scalar {
     fn from(v: FunctionCall) -> Self {
         Self::FunctionCall(v)
@@ -301,6 +343,26 @@ impl ScalarExpr for ConstantExpr {
     }
 }
 
+/// `?` placeholder of a prepared statement. It is kept in the plan cached for the statement
+/// and replaced by the parameter of each execution before optimizing, see `Planner::bind_prepared`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct PlaceholderExpr {
+    /// The position of the placeholder in the statement, starting from 0.
+    pub index: usize,
+
+    pub data_type: Box<DataType>,
+}
+
+impl ScalarExpr for PlaceholderExpr {
+    fn data_type(&self) -> DataType {
+        *self.data_type.clone()
+    }
+
+    fn used_columns(&self) -> ColumnSet {
+        ColumnSet::new()
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct AndExpr {
     pub left: Box<Scalar>,
@@ -450,6 +512,150 @@ impl ScalarExpr for AggregateFunction {
     }
 }
 
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct WindowFunc {
+    pub display_name: String,
+
+    pub func: WindowFuncType,
+    pub partition_by: Vec<Scalar>,
+    pub order_by: Vec<WindowOrderBy>,
+    pub frame: WindowFuncFrame,
+    pub return_type: Box<DataType>,
+}
+
+impl ScalarExpr for WindowFunc {
+    fn data_type(&self) -> DataType {
+        *self.return_type.clone()
+    }
+
+    fn used_columns(&self) -> ColumnSet {
+        let mut result = match &self.func {
+            WindowFuncType::Aggregate(agg) => agg.used_columns(),
+            WindowFuncType::LagLead(lag_lead) => lag_lead.used_columns(),
+            WindowFuncType::RowNumber | WindowFuncType::Rank | WindowFuncType::DenseRank => {
+                ColumnSet::new()
+            }
+        };
+        for scalar in self.partition_by.iter() {
+            result = result.union(&scalar.used_columns()).cloned().collect();
+        }
+        for order in self.order_by.iter() {
+            result = result.union(&order.expr.used_columns()).cloned().collect();
+        }
+        result
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum WindowFuncType {
+    Aggregate(AggregateFunction),
+    LagLead(LagLeadFunction),
+    RowNumber,
+    Rank,
+    DenseRank,
+}
+
+impl WindowFuncType {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "row_number" => Some(WindowFuncType::RowNumber),
+            "rank" => Some(WindowFuncType::Rank),
+            "dense_rank" => Some(WindowFuncType::DenseRank),
+            _ => None,
+        }
+    }
+
+    pub fn func_name(&self) -> String {
+        match self {
+            WindowFuncType::Aggregate(agg) => agg.func_name.clone(),
+            WindowFuncType::LagLead(lag_lead) if lag_lead.is_lag => "lag".to_string(),
+            WindowFuncType::LagLead(_) => "lead".to_string(),
+            WindowFuncType::RowNumber => "row_number".to_string(),
+            WindowFuncType::Rank => "rank".to_string(),
+            WindowFuncType::DenseRank => "dense_rank".to_string(),
+        }
+    }
+}
+
+/// `LAG(arg [, offset [, default]])` or `LEAD(arg [, offset [, default]])`
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct LagLeadFunction {
+    /// Is `LAG` or `LEAD`
+    pub is_lag: bool,
+    pub arg: Box<Scalar>,
+    /// Number of rows to look backward (`LAG`) or forward (`LEAD`)
+    pub offset: u64,
+    /// Value returned if the row doesn't exist, `NULL` if not specified
+    pub default: Option<Box<Scalar>>,
+    pub return_type: Box<DataType>,
+}
+
+impl LagLeadFunction {
+    pub fn used_columns(&self) -> ColumnSet {
+        let mut result = self.arg.used_columns();
+        if let Some(default) = &self.default {
+            result = result.union(&default.used_columns()).cloned().collect();
+        }
+        result
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct WindowOrderBy {
+    pub expr: Scalar,
+    // Optional `ASC` or `DESC`
+    pub asc: Option<bool>,
+    // Optional `NULLS FIRST` or `NULLS LAST`
+    pub nulls_first: Option<bool>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, serde::Serialize, serde::Deserialize)]
+pub enum WindowFuncFrameUnits {
+    Rows,
+    Range,
+}
+
+/// Bound of a window frame, `None` offset means `UNBOUNDED`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, serde::Serialize, serde::Deserialize)]
+pub enum WindowFuncFrameBound {
+    CurrentRow,
+    Preceding(Option<usize>),
+    Following(Option<usize>),
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug, serde::Serialize, serde::Deserialize)]
+pub struct WindowFuncFrame {
+    pub units: WindowFuncFrameUnits,
+    pub start_bound: WindowFuncFrameBound,
+    pub end_bound: WindowFuncFrameBound,
+}
+
+impl std::fmt::Display for WindowFuncFrameBound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WindowFuncFrameBound::CurrentRow => write!(f, "CURRENT ROW"),
+            WindowFuncFrameBound::Preceding(None) => write!(f, "UNBOUNDED PRECEDING"),
+            WindowFuncFrameBound::Preceding(Some(n)) => write!(f, "{n} PRECEDING"),
+            WindowFuncFrameBound::Following(None) => write!(f, "UNBOUNDED FOLLOWING"),
+            WindowFuncFrameBound::Following(Some(n)) => write!(f, "{n} FOLLOWING"),
+        }
+    }
+}
+
+impl std::fmt::Display for WindowFuncFrame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let units = match self.units {
+            WindowFuncFrameUnits::Rows => "ROWS",
+            WindowFuncFrameUnits::Range => "RANGE",
+        };
+        write!(
+            f,
+            "{units} BETWEEN {} AND {}",
+            self.start_bound, self.end_bound
+        )
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct FunctionCall {
     pub params: Vec<usize>,
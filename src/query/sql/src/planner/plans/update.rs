@@ -13,7 +13,18 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::sync::Arc;
 
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::type_check;
+use common_expression::types::DataType;
+use common_expression::DataSchema;
+use common_expression::DataSchemaRef;
+use common_expression::RawExpr;
+use common_expression::RemoteExpr;
+use common_expression::TableSchemaRef;
+use common_functions::scalars::BUILTIN_FUNCTIONS;
 use common_meta_types::MetaId;
 
 use crate::plans::Scalar;
@@ -27,3 +38,53 @@ pub struct UpdatePlan {
     pub update_list: HashMap<usize, Scalar>,
     pub selection: Option<Scalar>,
 }
+
+impl UpdatePlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::empty())
+    }
+
+    /// Generate the expressions computing the new values of the updated columns.
+    /// Rows not matching the selection keep their original values.
+    pub fn generate_update_list(
+        &self,
+        schema: TableSchemaRef,
+    ) -> Result<Vec<(usize, RemoteExpr<String>)>> {
+        let mut update_list = self
+            .update_list
+            .iter()
+            .map(|(index, scalar)| {
+                let field = schema.field(*index);
+                let data_type = DataType::from(field.data_type());
+                let mut raw_expr = RawExpr::Cast {
+                    span: None,
+                    is_try: false,
+                    expr: Box::new(scalar.as_raw_expr()),
+                    dest_type: data_type.clone(),
+                };
+
+                if let Some(selection) = &self.selection {
+                    raw_expr = RawExpr::FunctionCall {
+                        span: None,
+                        name: "if".to_string(),
+                        params: vec![],
+                        args: vec![selection.as_raw_expr(), raw_expr, RawExpr::ColumnRef {
+                            span: None,
+                            id: field.name().clone(),
+                            data_type,
+                        }],
+                    };
+                }
+
+                let expr = type_check::check(&raw_expr, &BUILTIN_FUNCTIONS).map_err(|(_, e)| {
+                    ErrorCode::Internal(format!(
+                        "Failed to type check the update expression: {raw_expr:?}, error: {e}",
+                    ))
+                })?;
+                Ok((*index, expr.as_remote_expr()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        update_list.sort_by_key(|(index, _)| *index);
+        Ok(update_list)
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_catalog::table_context::TableContext;
+use common_exception::Result;
+
+use crate::optimizer::ColumnSet;
+use crate::optimizer::Distribution;
+use crate::optimizer::PhysicalProperty;
+use crate::optimizer::RelExpr;
+use crate::optimizer::RelationalProperty;
+use crate::optimizer::RequiredProperty;
+use crate::plans::Operator;
+use crate::plans::RelOp;
+use crate::plans::ScalarItem;
+use crate::plans::WindowFuncFrame;
+use crate::plans::WindowFuncType;
+use crate::IndexType;
+use crate::ScalarExpr;
+
+/// Evaluate a window function over the partitions of its input,
+/// the result is appended to the input as a new column.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Window {
+    // The output column index of the window function
+    pub index: IndexType,
+    // Window function, the arguments of aggregate window functions are column references
+    pub function: WindowFuncType,
+    // Arguments of the window function
+    pub arguments: Vec<ScalarItem>,
+    // Partition by items of the window, such as: partition by col1, col2;
+    pub partition_by: Vec<ScalarItem>,
+    // Order by items of the window, such as: order by col1 asc, col2 desc;
+    pub order_by: Vec<WindowOrderByInfo>,
+    pub frame: WindowFuncFrame,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WindowOrderByInfo {
+    pub order_by_item: ScalarItem,
+    pub asc: Option<bool>,
+    pub nulls_first: Option<bool>,
+}
+
+impl Window {
+    pub fn used_columns(&self) -> Result<ColumnSet> {
+        let mut used_columns = ColumnSet::new();
+        used_columns.insert(self.index);
+        for arg in self.arguments.iter() {
+            used_columns.insert(arg.index);
+            used_columns.extend(arg.scalar.used_columns())
+        }
+        for item in self.partition_by.iter() {
+            used_columns.insert(item.index);
+            used_columns.extend(item.scalar.used_columns())
+        }
+        for info in self.order_by.iter() {
+            used_columns.insert(info.order_by_item.index);
+            used_columns.extend(info.order_by_item.scalar.used_columns())
+        }
+        Ok(used_columns)
+    }
+}
+
+impl Operator for Window {
+    fn rel_op(&self) -> RelOp {
+        RelOp::Window
+    }
+
+    fn derive_physical_prop(&self, rel_expr: &RelExpr) -> Result<PhysicalProperty> {
+        rel_expr.derive_physical_prop_child(0)
+    }
+
+    fn compute_required_prop_child(
+        &self,
+        _ctx: Arc<dyn TableContext>,
+        _rel_expr: &RelExpr,
+        _child_index: usize,
+        required: &RequiredProperty,
+    ) -> Result<RequiredProperty> {
+        // Rows of a partition must be processed together.
+        let mut required = required.clone();
+        required.distribution = Distribution::Serial;
+        Ok(required)
+    }
+
+    fn derive_relational_prop(&self, rel_expr: &RelExpr) -> Result<RelationalProperty> {
+        let input_prop = rel_expr.derive_relational_prop_child(0)?;
+
+        // Derive output columns
+        let mut output_columns = input_prop.output_columns.clone();
+        output_columns.insert(self.index);
+
+        // Derive outer columns
+        let outer_columns = input_prop
+            .outer_columns
+            .difference(&output_columns)
+            .cloned()
+            .collect();
+
+        // Derive used columns
+        let mut used_columns = self.used_columns()?;
+        used_columns.extend(input_prop.used_columns);
+
+        // A window function produces exactly one row for each input row
+        Ok(RelationalProperty {
+            output_columns,
+            outer_columns,
+            used_columns,
+            cardinality: input_prop.cardinality,
+            statistics: input_prop.statistics,
+        })
+    }
+}
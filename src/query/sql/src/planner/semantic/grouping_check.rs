@@ -74,7 +74,7 @@ impl<'a> GroupingChecker<'a> {
                 err_msg = span.map_or(err_msg.clone(), |span| span.display_error(err_msg.clone()));
                 Err(ErrorCode::SemanticError(err_msg))
             }
-            Scalar::ConstantExpr(_) => Ok(scalar.clone()),
+            Scalar::ConstantExpr(_) | Scalar::PlaceholderExpr(_) => Ok(scalar.clone()),
             Scalar::AndExpr(scalar) => Ok(AndExpr {
                 left: Box::new(self.resolve(&scalar.left, span)?),
                 right: Box::new(self.resolve(&scalar.right, span)?),
@@ -147,6 +147,35 @@ impl<'a> GroupingChecker<'a> {
                 }
                 Err(ErrorCode::Internal("Invalid aggregate function"))
             }
+
+            Scalar::WindowFunction(window) => {
+                if let Some(column) = self
+                    .bind_context
+                    .window_info
+                    .window_functions_map
+                    .get(&window.display_name)
+                {
+                    let window_func = &self.bind_context.window_info.window_functions[*column];
+                    let column_binding = ColumnBinding {
+                        database_name: None,
+                        table_name: None,
+                        column_name: window.display_name.clone(),
+                        index: window_func.index,
+                        data_type: window.return_type.clone(),
+                        visibility: Visibility::Visible,
+                    };
+                    return Ok(BoundColumnRef {
+                        column: column_binding,
+                    }
+                    .into());
+                }
+                let mut err_msg = format!(
+                    "window function \"{}\" is not allowed in this context",
+                    &window.display_name
+                );
+                err_msg = span.map_or(err_msg.clone(), |span| span.display_error(err_msg.clone()));
+                Err(ErrorCode::SemanticError(err_msg))
+            }
         }
     }
 }
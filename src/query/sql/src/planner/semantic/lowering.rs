@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_expression::type_check;
 use common_expression::Expr;
@@ -19,6 +20,7 @@ use common_expression::RawExpr;
 use common_functions::scalars::BUILTIN_FUNCTIONS;
 
 use crate::plans::Scalar;
+use crate::IndexType;
 use crate::ScalarExpr;
 
 const DUMMY_NAME: &str = "DUMMY";
@@ -37,6 +39,11 @@ impl Scalar {
                 span: None,
                 lit: constant.value.clone(),
             },
+            Scalar::PlaceholderExpr(placeholder) => RawExpr::ColumnRef {
+                span: None,
+                id: DUMMY_NAME.to_string(),
+                data_type: *placeholder.data_type.clone(),
+            },
             Scalar::AndExpr(expr) => RawExpr::FunctionCall {
                 span: None,
                 name: "and".to_string(),
@@ -66,6 +73,11 @@ impl Scalar {
                 id: agg.display_name.clone(),
                 data_type: *agg.return_type.clone(),
             },
+            Scalar::WindowFunction(window) => RawExpr::ColumnRef {
+                span: None,
+                id: window.display_name.clone(),
+                data_type: *window.return_type.clone(),
+            },
             Scalar::FunctionCall(func) => RawExpr::FunctionCall {
                 span: None,
                 name: func.func_name.clone(),
@@ -98,4 +110,88 @@ impl Scalar {
         })?;
         Ok(expr)
     }
+
+    /// Lowering `Scalar` into `RawExpr` whose column references are the indexes of the columns.
+    /// Aggregate functions, window functions, subqueries and unbound placeholders are not supported.
+    pub fn as_raw_expr_with_col_index(&self) -> Result<RawExpr<IndexType>> {
+        Ok(match self {
+            Scalar::BoundColumnRef(column_ref) => RawExpr::ColumnRef {
+                span: None,
+                id: column_ref.column.index,
+                data_type: *column_ref.column.data_type.clone(),
+            },
+            Scalar::ConstantExpr(constant) => RawExpr::Literal {
+                span: None,
+                lit: constant.value.clone(),
+            },
+            Scalar::AndExpr(expr) => RawExpr::FunctionCall {
+                span: None,
+                name: "and".to_string(),
+                params: vec![],
+                args: vec![
+                    expr.left.as_raw_expr_with_col_index()?,
+                    expr.right.as_raw_expr_with_col_index()?,
+                ],
+            },
+            Scalar::OrExpr(expr) => RawExpr::FunctionCall {
+                span: None,
+                name: "or".to_string(),
+                params: vec![],
+                args: vec![
+                    expr.left.as_raw_expr_with_col_index()?,
+                    expr.right.as_raw_expr_with_col_index()?,
+                ],
+            },
+            Scalar::NotExpr(expr) => RawExpr::FunctionCall {
+                span: None,
+                name: "not".to_string(),
+                params: vec![],
+                args: vec![expr.argument.as_raw_expr_with_col_index()?],
+            },
+            Scalar::ComparisonExpr(expr) => RawExpr::FunctionCall {
+                span: None,
+                name: expr.op.to_func_name().to_string(),
+                params: vec![],
+                args: vec![
+                    expr.left.as_raw_expr_with_col_index()?,
+                    expr.right.as_raw_expr_with_col_index()?,
+                ],
+            },
+            Scalar::FunctionCall(func) => RawExpr::FunctionCall {
+                span: None,
+                name: func.func_name.clone(),
+                params: func.params.clone(),
+                args: func
+                    .arguments
+                    .iter()
+                    .map(Scalar::as_raw_expr_with_col_index)
+                    .collect::<Result<_>>()?,
+            },
+            Scalar::CastExpr(cast) => RawExpr::Cast {
+                span: None,
+                is_try: cast.target_type.is_nullable(),
+                expr: Box::new(cast.argument.as_raw_expr_with_col_index()?),
+                dest_type: *cast.target_type.clone(),
+            },
+            Scalar::AggregateFunction(_)
+            | Scalar::WindowFunction(_)
+            | Scalar::SubqueryExpr(_)
+            | Scalar::PlaceholderExpr(_) => {
+                return Err(ErrorCode::SemanticError(format!(
+                    "Unsupported expression: {:?}",
+                    self
+                )));
+            }
+        })
+    }
+
+    pub fn as_expr_with_col_index(&self) -> Result<Expr<IndexType>> {
+        let raw_expr = self.as_raw_expr_with_col_index()?;
+        let expr = type_check::check(&raw_expr, &BUILTIN_FUNCTIONS).map_err(|(_, e)| {
+            ErrorCode::Internal(format!(
+                "Failed to type check the expression: {raw_expr:?}, error: {e}",
+            ))
+        })?;
+        Ok(expr)
+    }
 }
@@ -16,6 +16,7 @@ mod grouping_check;
 mod lowering;
 mod name_resolution;
 mod type_check;
+mod window_check;
 
 pub use grouping_check::GroupingChecker;
 pub use name_resolution::compare_table_name;
@@ -24,3 +25,4 @@ pub use name_resolution::IdentifierNormalizer;
 pub use name_resolution::NameResolutionContext;
 pub use type_check::validate_function_arg;
 pub use type_check::TypeChecker;
+pub use window_check::WindowChecker;
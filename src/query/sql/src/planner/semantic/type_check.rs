@@ -28,6 +28,10 @@ use common_ast::ast::SubqueryModifier;
 use common_ast::ast::TrimWhere;
 use common_ast::ast::TypeName;
 use common_ast::ast::UnaryOperator;
+use common_ast::ast::WindowFrame;
+use common_ast::ast::WindowFrameBound;
+use common_ast::ast::WindowFrameUnits;
+use common_ast::ast::WindowSpec;
 use common_ast::parser::parse_expr;
 use common_ast::parser::token::Token;
 use common_ast::parser::tokenize_sql;
@@ -41,6 +45,8 @@ use common_expression::infer_schema_type;
 use common_expression::type_check;
 use common_expression::type_check::check_literal;
 use common_expression::type_check::common_super_type;
+use common_expression::types::decimal::DecimalDataType;
+use common_expression::types::decimal::DecimalSize;
 use common_expression::types::number::F64;
 use common_expression::types::DataType;
 use common_expression::types::NumberDataType;
@@ -61,6 +67,8 @@ use crate::binder::NameResolutionResult;
 use crate::optimizer::RelExpr;
 use crate::planner::binder::wrap_cast_if_needed;
 use crate::planner::metadata::optimize_remove_count_args;
+use crate::planner::placeholder::placeholder_index;
+use crate::planner::placeholder::placeholder_scalar;
 use crate::plans::AggregateFunction;
 use crate::plans::AndExpr;
 use crate::plans::BoundColumnRef;
@@ -69,11 +77,19 @@ use crate::plans::ComparisonExpr;
 use crate::plans::ComparisonOp;
 use crate::plans::ConstantExpr;
 use crate::plans::FunctionCall;
+use crate::plans::LagLeadFunction;
 use crate::plans::NotExpr;
 use crate::plans::OrExpr;
+use crate::plans::PlaceholderExpr;
 use crate::plans::Scalar;
 use crate::plans::SubqueryExpr;
 use crate::plans::SubqueryType;
+use crate::plans::WindowFunc;
+use crate::plans::WindowFuncFrame;
+use crate::plans::WindowFuncFrameBound;
+use crate::plans::WindowFuncFrameUnits;
+use crate::plans::WindowFuncType;
+use crate::plans::WindowOrderBy;
 use crate::BindContext;
 use crate::ColumnBinding;
 use crate::ColumnEntry;
@@ -305,6 +321,7 @@ impl<'a> TypeChecker<'a> {
                                 },
                                 args: args.iter().copied().cloned().collect(),
                                 params: vec![],
+                                window: None,
                             },
                             None,
                         )
@@ -555,6 +572,7 @@ impl<'a> TypeChecker<'a> {
                                 },
                                 args: vec![*operand.clone(), c.clone()],
                                 params: vec![],
+                                window: None,
                             };
                             arguments.push(equal_expr)
                         }
@@ -620,10 +638,52 @@ impl<'a> TypeChecker<'a> {
                 name,
                 args,
                 params,
-                ..
+                window,
             } => {
                 let func_name = name.name.to_lowercase();
                 let func_name = func_name.as_str();
+
+                if let Some(window) = window {
+                    // Window function
+                    let func = if let Some(func) = WindowFuncType::from_name(func_name) {
+                        if !args.is_empty() || !params.is_empty() {
+                            return Err(ErrorCode::SemanticError(span.display_error(format!(
+                                "window function {func_name} doesn't accept any arguments"
+                            ))));
+                        }
+                        func
+                    } else if func_name == "lag" || func_name == "lead" {
+                        if !params.is_empty() {
+                            return Err(ErrorCode::SemanticError(span.display_error(format!(
+                                "window function {func_name} doesn't accept any parameters"
+                            ))));
+                        }
+                        self.resolve_lag_lead(span, func_name, args).await?
+                    } else if AggregateFunctionFactory::instance().contains(func_name) {
+                        let args: Vec<&Expr> = args.iter().collect();
+                        let box (agg, _) = self
+                            .resolve_aggregate_function(
+                                span, expr, func_name, *distinct, params, &args,
+                            )
+                            .await?;
+                        WindowFuncType::Aggregate(agg)
+                    } else {
+                        return Err(ErrorCode::SemanticError(span.display_error(format!(
+                            "function {func_name} is not a window function"
+                        ))));
+                    };
+                    return self.resolve_window(span, expr, func, window).await;
+                }
+
+                if WindowFuncType::from_name(func_name).is_some()
+                    || func_name == "lag"
+                    || func_name == "lead"
+                {
+                    return Err(ErrorCode::SemanticError(span.display_error(format!(
+                        "window function {func_name} requires an OVER clause"
+                    ))));
+                }
+
                 if !is_builtin_function(func_name)
                     && !Self::all_rewritable_scalar_function().contains(&func_name)
                 {
@@ -633,75 +693,10 @@ impl<'a> TypeChecker<'a> {
                 let args: Vec<&Expr> = args.iter().collect();
 
                 if AggregateFunctionFactory::instance().contains(func_name) {
-                    if self.in_aggregate_function {
-                        // Reset the state
-                        self.in_aggregate_function = false;
-                        return Err(ErrorCode::SemanticError(expr.span().display_error(
-                            "aggregate function calls cannot be nested".to_string(),
-                        )));
-                    }
-
-                    // Check aggregate function
-                    let params = params
-                        .iter()
-                        .map(|literal| {
-                            self.resolve_literal(literal, None)
-                                .map(|box (value, _)| value)
-                        })
-                        .collect::<Result<Vec<_>>>()?;
-
-                    let scalar_params = params
-                        .iter()
-                        .map(|param| check_literal(param).0)
-                        .collect::<Vec<_>>();
-
-                    self.in_aggregate_function = true;
-                    let mut arguments = vec![];
-                    let mut arg_types = vec![];
-                    for arg in args.iter() {
-                        let box (argument, arg_type) = self.resolve(arg, None).await?;
-                        arguments.push(argument);
-                        arg_types.push(arg_type);
-                    }
-                    self.in_aggregate_function = false;
-
-                    // Rewrite `xxx(distinct)` to `xxx_distinct(...)`
-                    let (func_name, distinct) =
-                        if func_name.eq_ignore_ascii_case("count") && *distinct {
-                            ("count_distinct", false)
-                        } else {
-                            (func_name, *distinct)
-                        };
-
-                    let func_name = if distinct {
-                        format!("{}_distinct", func_name)
-                    } else {
-                        func_name.to_string()
-                    };
-
-                    let agg_func = AggregateFunctionFactory::instance()
-                        .get(&func_name, scalar_params, arg_types)
-                        .map_err(|e| ErrorCode::SemanticError(span.display_error(e.message())))?;
-
-                    let args = if optimize_remove_count_args(&func_name, distinct, args.as_slice())
-                    {
-                        vec![]
-                    } else {
-                        arguments
-                    };
-
-                    Box::new((
-                        AggregateFunction {
-                            display_name: format!("{:#}", expr),
-                            func_name,
-                            distinct: false,
-                            params,
-                            args,
-                            return_type: Box::new(agg_func.return_type()?),
-                        }
-                        .into(),
-                        agg_func.return_type()?,
-                    ))
+                    let box (agg, data_type) = self
+                        .resolve_aggregate_function(span, expr, func_name, *distinct, params, &args)
+                        .await?;
+                    Box::new((agg.into(), data_type))
                 } else {
                     // Scalar function
                     let params = params
@@ -719,21 +714,24 @@ impl<'a> TypeChecker<'a> {
                 }
             }
 
-            Expr::CountAll { .. } => {
+            Expr::CountAll { span, window } => {
                 let agg_func = AggregateCountFunction::try_create("", vec![], vec![])?;
+                let agg = AggregateFunction {
+                    display_name: format!("{:#}", expr),
+                    func_name: "count".to_string(),
+                    distinct: false,
+                    params: vec![],
+                    args: vec![],
+                    return_type: Box::new(agg_func.return_type()?),
+                };
 
-                Box::new((
-                    AggregateFunction {
-                        display_name: format!("{:#}", expr),
-                        func_name: "count".to_string(),
-                        distinct: false,
-                        params: vec![],
-                        args: vec![],
-                        return_type: Box::new(agg_func.return_type()?),
-                    }
-                    .into(),
-                    agg_func.return_type()?,
-                ))
+                if let Some(window) = window {
+                    return self
+                        .resolve_window(span, expr, WindowFuncType::Aggregate(agg), window)
+                        .await;
+                }
+
+                Box::new((agg.into(), agg_func.return_type()?))
             }
 
             Expr::Exists { subquery, not, .. } => {
@@ -919,6 +917,31 @@ impl<'a> TypeChecker<'a> {
             }
 
             Expr::Tuple { span, exprs, .. } => self.resolve_tuple(span, exprs).await?,
+
+            Expr::Placeholder { span } => {
+                let index = self.metadata.read().placeholder_offset() + placeholder_index(span)?;
+                let value = self
+                    .ctx
+                    .get_placeholders()
+                    .get(index)
+                    .cloned()
+                    .ok_or_else(|| {
+                        ErrorCode::SemanticError(span.display_error(
+                            "Placeholder `?` is only allowed in prepared statements".to_string(),
+                        ))
+                    })?;
+                let data_type = value.data_type();
+                let scalar = if self.metadata.read().keep_placeholders() {
+                    PlaceholderExpr {
+                        index,
+                        data_type: Box::new(data_type.clone()),
+                    }
+                    .into()
+                } else {
+                    placeholder_scalar(&value)
+                };
+                Box::new((scalar, data_type))
+            }
         };
 
         Ok(Box::new(self.post_resolve(&scalar, &data_type)?))
@@ -1340,6 +1363,283 @@ impl<'a> TypeChecker<'a> {
         }
     }
 
+    /// Resolve an aggregate function call, e.g. `sum(a)`, `count(distinct a)`.
+    async fn resolve_aggregate_function(
+        &mut self,
+        span: &[Token<'_>],
+        expr: &Expr<'_>,
+        func_name: &str,
+        distinct: bool,
+        params: &[Literal],
+        args: &[&Expr<'_>],
+    ) -> Result<Box<(AggregateFunction, DataType)>> {
+        if self.in_aggregate_function {
+            // Reset the state
+            self.in_aggregate_function = false;
+            return Err(ErrorCode::SemanticError(expr.span().display_error(
+                "aggregate function calls cannot be nested".to_string(),
+            )));
+        }
+
+        // Check aggregate function
+        let params = params
+            .iter()
+            .map(|literal| {
+                self.resolve_literal(literal, None)
+                    .map(|box (value, _)| value)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let scalar_params = params
+            .iter()
+            .map(|param| check_literal(param).0)
+            .collect::<Vec<_>>();
+
+        self.in_aggregate_function = true;
+        let mut arguments = vec![];
+        let mut arg_types = vec![];
+        for arg in args.iter() {
+            let box (argument, arg_type) = self.resolve(arg, None).await?;
+            arguments.push(argument);
+            arg_types.push(arg_type);
+        }
+        self.in_aggregate_function = false;
+
+        // Rewrite `xxx(distinct)` to `xxx_distinct(...)`
+        let (func_name, distinct) = if func_name.eq_ignore_ascii_case("count") && distinct {
+            ("count_distinct", false)
+        } else {
+            (func_name, distinct)
+        };
+
+        let func_name = if distinct {
+            format!("{}_distinct", func_name)
+        } else {
+            func_name.to_string()
+        };
+
+        let agg_func = AggregateFunctionFactory::instance()
+            .get(&func_name, scalar_params, arg_types)
+            .map_err(|e| ErrorCode::SemanticError(span.display_error(e.message())))?;
+
+        let args = if optimize_remove_count_args(&func_name, distinct, args) {
+            vec![]
+        } else {
+            arguments
+        };
+
+        let return_type = agg_func.return_type()?;
+        Ok(Box::new((
+            AggregateFunction {
+                display_name: format!("{:#}", expr),
+                func_name,
+                distinct: false,
+                params,
+                args,
+                return_type: Box::new(return_type.clone()),
+            },
+            return_type,
+        )))
+    }
+
+    /// Resolve the `OVER` clause of a window function, the partition by and
+    /// order by expressions are resolved within current context.
+    async fn resolve_window(
+        &mut self,
+        span: &[Token<'_>],
+        expr: &Expr<'_>,
+        func: WindowFuncType,
+        window: &WindowSpec<'_>,
+    ) -> Result<Box<(Scalar, DataType)>> {
+        if self.in_aggregate_function {
+            return Err(ErrorCode::SemanticError(span.display_error(
+                "window function calls cannot be nested in aggregate function".to_string(),
+            )));
+        }
+
+        let mut partition_by = Vec::with_capacity(window.partition_by.len());
+        for p in window.partition_by.iter() {
+            let box (scalar, _) = self.resolve(p, None).await?;
+            partition_by.push(scalar);
+        }
+
+        let mut order_by = Vec::with_capacity(window.order_by.len());
+        for o in window.order_by.iter() {
+            let box (scalar, _) = self.resolve(&o.expr, None).await?;
+            order_by.push(WindowOrderBy {
+                expr: scalar,
+                asc: o.asc,
+                nulls_first: o.nulls_first,
+            });
+        }
+
+        let frame = self.resolve_window_frame(span, &func, &order_by, &window.window_frame)?;
+
+        let data_type = match &func {
+            WindowFuncType::Aggregate(agg) => *agg.return_type.clone(),
+            WindowFuncType::LagLead(lag_lead) => *lag_lead.return_type.clone(),
+            WindowFuncType::RowNumber | WindowFuncType::Rank | WindowFuncType::DenseRank => {
+                DataType::Number(NumberDataType::UInt64)
+            }
+        };
+
+        let window_func = WindowFunc {
+            display_name: format!("{:#}", expr),
+            func,
+            partition_by,
+            order_by,
+            frame,
+            return_type: Box::new(data_type.clone()),
+        };
+
+        Ok(Box::new((window_func.into(), data_type)))
+    }
+
+    /// Resolve the arguments of `LAG` and `LEAD`, the result is nullable since the
+    /// row at the offset may not exist, and the default value is casted to it.
+    async fn resolve_lag_lead(
+        &mut self,
+        span: &[Token<'_>],
+        func_name: &str,
+        args: &[Expr<'_>],
+    ) -> Result<WindowFuncType> {
+        if args.is_empty() || args.len() > 3 {
+            return Err(ErrorCode::SemanticError(span.display_error(format!(
+                "window function {func_name} expects 1 to 3 arguments, but got {}",
+                args.len()
+            ))));
+        }
+
+        let box (arg, arg_type) = self.resolve(&args[0], None).await?;
+        let offset = match args.get(1) {
+            None => 1,
+            Some(Expr::Literal {
+                lit: Literal::Integer(n),
+                ..
+            }) => *n,
+            Some(expr) => {
+                return Err(ErrorCode::SemanticError(expr.span().display_error(format!(
+                    "offset of window function {func_name} must be a non-negative integer literal"
+                ))));
+            }
+        };
+
+        let return_type = arg_type.wrap_nullable();
+        let default = match args.get(2) {
+            None => None,
+            Some(expr) => {
+                let box (default, _) = self.resolve(expr, None).await?;
+                Some(Box::new(wrap_cast_if_needed(&default, &return_type)))
+            }
+        };
+
+        Ok(WindowFuncType::LagLead(LagLeadFunction {
+            is_lag: func_name == "lag",
+            arg: Box::new(arg),
+            offset,
+            default,
+            return_type: Box::new(return_type),
+        }))
+    }
+
+    fn resolve_window_frame(
+        &mut self,
+        span: &[Token<'_>],
+        func: &WindowFuncType,
+        order_by: &[WindowOrderBy],
+        frame: &Option<WindowFrame<'_>>,
+    ) -> Result<WindowFuncFrame> {
+        let frame = match (func, frame) {
+            (WindowFuncType::Aggregate(_), Some(frame)) => frame,
+            (_, Some(_)) => {
+                return Err(ErrorCode::SemanticError(span.display_error(format!(
+                    "window function {} doesn't support window frame",
+                    func.func_name()
+                ))));
+            }
+            (WindowFuncType::Aggregate(_), None) if !order_by.is_empty() => {
+                // `RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW` is the default frame
+                // if there is an `ORDER BY` clause.
+                return Ok(WindowFuncFrame {
+                    units: WindowFuncFrameUnits::Range,
+                    start_bound: WindowFuncFrameBound::Preceding(None),
+                    end_bound: WindowFuncFrameBound::CurrentRow,
+                });
+            }
+            _ => {
+                // The whole partition is the default frame.
+                return Ok(WindowFuncFrame {
+                    units: WindowFuncFrameUnits::Rows,
+                    start_bound: WindowFuncFrameBound::Preceding(None),
+                    end_bound: WindowFuncFrameBound::Following(None),
+                });
+            }
+        };
+
+        let units = match frame.units {
+            WindowFrameUnits::Rows => WindowFuncFrameUnits::Rows,
+            WindowFrameUnits::Range => WindowFuncFrameUnits::Range,
+        };
+        let start_bound = self.resolve_window_frame_bound(&frame.start_bound)?;
+        let end_bound = self.resolve_window_frame_bound(&frame.end_bound)?;
+
+        if matches!(start_bound, WindowFuncFrameBound::Following(None))
+            || matches!(end_bound, WindowFuncFrameBound::Preceding(None))
+        {
+            return Err(ErrorCode::SemanticError(span.display_error(
+                "invalid window frame, frame start cannot be UNBOUNDED FOLLOWING and frame end cannot be UNBOUNDED PRECEDING".to_string(),
+            )));
+        }
+
+        let has_offset = |bound: &WindowFuncFrameBound| {
+            matches!(
+                bound,
+                WindowFuncFrameBound::Preceding(Some(_)) | WindowFuncFrameBound::Following(Some(_))
+            )
+        };
+        if units == WindowFuncFrameUnits::Range
+            && (has_offset(&start_bound) || has_offset(&end_bound))
+            && (order_by.len() != 1 || !order_by[0].expr.data_type().remove_nullable().is_numeric())
+        {
+            return Err(ErrorCode::SemanticError(
+                span.display_error(
+                    "RANGE frame with offset requires exactly one numeric ORDER BY expression"
+                        .to_string(),
+                ),
+            ));
+        }
+
+        Ok(WindowFuncFrame {
+            units,
+            start_bound,
+            end_bound,
+        })
+    }
+
+    fn resolve_window_frame_bound(
+        &mut self,
+        bound: &WindowFrameBound<'_>,
+    ) -> Result<WindowFuncFrameBound> {
+        let offset = |expr: &Option<Box<Expr<'_>>>| -> Result<Option<usize>> {
+            match expr.as_deref() {
+                None => Ok(None),
+                Some(Expr::Literal {
+                    lit: Literal::Integer(n),
+                    ..
+                }) => Ok(Some(*n as usize)),
+                Some(expr) => Err(ErrorCode::SemanticError(expr.span().display_error(
+                    "frame offset must be a non-negative integer literal".to_string(),
+                ))),
+            }
+        };
+
+        Ok(match bound {
+            WindowFrameBound::CurrentRow => WindowFuncFrameBound::CurrentRow,
+            WindowFrameBound::Preceding(expr) => WindowFuncFrameBound::Preceding(offset(expr)?),
+            WindowFrameBound::Following(expr) => WindowFuncFrameBound::Following(offset(expr)?),
+        })
+    }
+
     pub async fn resolve_subquery(
         &mut self,
         typ: SubqueryType,
@@ -1568,6 +1868,7 @@ impl<'a> TypeChecker<'a> {
                             },
                             args: vec![arg_x.clone()],
                             params: vec![],
+                            window: None,
                         },
                         None,
                     )
@@ -1605,6 +1906,7 @@ impl<'a> TypeChecker<'a> {
                         },
                         args: vec![(*arg).clone()],
                         params: vec![],
+                        window: None,
                     };
 
                     new_args.push(is_not_null_expr);
@@ -2176,6 +2478,7 @@ impl<'a> TypeChecker<'a> {
                     name,
                     args,
                     params,
+                    window,
                 } => Ok(Expr::FunctionCall {
                     span,
                     distinct: *distinct,
@@ -2185,6 +2488,7 @@ impl<'a> TypeChecker<'a> {
                         .map(|arg| self.clone_expr_with_replacement(arg, replacement_fn))
                         .collect::<Result<Vec<Expr>>>()?,
                     params: params.clone(),
+                    window: window.clone(),
                 }),
                 Expr::Case {
                     span,
@@ -2285,6 +2589,13 @@ impl<'a> TypeChecker<'a> {
             TypeName::Int64 => TableDataType::Number(NumberDataType::Int64),
             TypeName::Float32 => TableDataType::Number(NumberDataType::Float32),
             TypeName::Float64 => TableDataType::Number(NumberDataType::Float64),
+            TypeName::Decimal { precision, scale } => TableDataType::Decimal(
+                DecimalDataType::from_size(DecimalSize {
+                    precision: *precision,
+                    scale: *scale,
+                })
+                .map_err(ErrorCode::BadArguments)?,
+            ),
             TypeName::String => TableDataType::String,
             TypeName::Timestamp => TableDataType::Timestamp,
             TypeName::Date => TableDataType::Date,
@@ -17,6 +17,7 @@ use std::collections::HashSet;
 use common_ast::ast::Expr;
 use common_ast::ast::Identifier;
 use common_ast::ast::Literal;
+use common_ast::ast::WindowSpec;
 use common_ast::parser::token::Token;
 use common_ast::walk_expr;
 use common_ast::Visitor;
@@ -86,6 +87,7 @@ impl<'ast> Visitor<'ast> for UDFValidator {
         name: &'ast Identifier<'ast>,
         args: &'ast [Expr<'ast>],
         _params: &'ast [Literal],
+        window: &'ast Option<WindowSpec<'ast>>,
     ) {
         let name = name.to_string();
         if !is_builtin_function(&name) && self.name.eq_ignore_ascii_case(&name) {
@@ -96,5 +98,8 @@ impl<'ast> Visitor<'ast> for UDFValidator {
         for arg in args {
             walk_expr(self, arg);
         }
+        if let Some(window) = window {
+            self.visit_window_spec(window);
+        }
     }
 }
@@ -34,6 +34,7 @@ use common_expression::TableSchemaRef;
 use common_expression::Value;
 use common_functions::scalars::BUILTIN_FUNCTIONS;
 use storages_common_table_meta::meta::V2BloomBlock;
+use storages_common_table_meta::meta::V3BloomBlock;
 use storages_common_table_meta::meta::Versioned;
 
 use crate::filters::Filter;
@@ -55,9 +56,9 @@ use crate::SupportedType;
 ///         | "Bob"   |  30   |
 ///         +---------+-------+
 /// ```
-/// We will create table of filters as follows:
+/// We will create table of filters as follows, suppose the column ids of name and age are 0 and 1:
 /// ```
-///         +---Bloom(name)--+--Bloom(age)--+
+///         +---Bloom(0)-----+--Bloom(1)----+
 ///         |  123456789abcd |  ac2345bcd   |
 ///         +----------------+--------------+
 /// ```
@@ -151,7 +152,7 @@ impl BlockFilter {
                     column_distinct_count.insert(i, len);
                 }
 
-                let filter_name = Self::build_filter_column_name(source_schema.field(i).name());
+                let filter_name = Self::build_filter_column_name(version, source_schema.field(i));
                 filter_fields.push(TableField::new(&filter_name, TableDataType::String));
                 // create filter column
                 let serialized_bytes = filter.to_bytes()?;
@@ -231,13 +232,23 @@ impl BlockFilter {
     }
 
     /// For every applicable column, we will create a filter.
-    /// The filter will be stored with field name 'Bloom(column_name)'
-    pub fn build_filter_column_name(column_name: &str) -> String {
-        format!("Bloom({})", column_name)
+    /// The filter will be stored with field name 'Bloom(column_id)'.
+    ///
+    /// The index of version 3 or before stores the filter with field name 'Bloom(column_name)'.
+    pub fn build_filter_column_name(version: u64, field: &TableField) -> String {
+        if version <= V3BloomBlock::VERSION {
+            format!("Bloom({})", field.name())
+        } else {
+            format!("Bloom({})", field.column_id())
+        }
     }
 
     fn find(&self, column_name: &str, target: &Scalar, ty: &DataType) -> Result<FilterEvalResult> {
-        let filter_column = &Self::build_filter_column_name(column_name);
+        let filter_column = &match self.source_schema.field_with_name(column_name) {
+            Ok(field) => Self::build_filter_column_name(self.version, field),
+            // The column is not a field of the source table, e.g. inner field of a tuple.
+            Err(_) => return Ok(FilterEvalResult::Uncertain),
+        };
 
         if !self.filter_schema.has_field(filter_column)
             || !Xor8Filter::is_supported_type(ty)
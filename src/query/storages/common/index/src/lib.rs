@@ -16,12 +16,17 @@ mod bloom;
 pub mod filters;
 pub mod index_min_max;
 pub mod range_filter;
+mod skipping;
 
 pub use bloom::BlockFilter;
 pub use bloom::FilterEvalResult;
 use common_expression::types::DataType;
 pub use index_min_max::*;
 pub use range_filter::*;
+pub use skipping::build_block_skipping_index;
+pub use skipping::eval_block_skipping_index;
+pub use skipping::is_skipping_index_applicable;
+pub use skipping::SkippingIndex;
 
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum IndexSchemaVersion {
@@ -34,7 +39,11 @@ pub trait SupportedType {
         let inner_type = data_type.remove_nullable();
         matches!(
             inner_type,
-            DataType::Number(_) | DataType::Date | DataType::Timestamp | DataType::String
+            DataType::Number(_)
+                | DataType::Decimal(_)
+                | DataType::Date
+                | DataType::Timestamp
+                | DataType::String
         )
     }
 }
@@ -93,8 +93,8 @@ impl RangeFilter {
             .column_refs()
             .into_iter()
             .map(|(name, ty)| {
-                let offset = self.schema.index_of(&name)?;
-                let domain = statistics_to_domain(stats.get(&(offset as u32)), &ty);
+                let column_id = self.schema.field_with_name(&name)?.column_id();
+                let domain = statistics_to_domain(stats.get(&column_id), &ty);
                 Ok((name, domain))
             })
             .collect::<Result<_>>()?;
@@ -146,6 +146,11 @@ fn statistics_to_domain(stat: Option<&ColumnStatistics>, data_type: &DataType) -
             min: DateType::try_downcast_scalar(&stat.min.as_ref()).unwrap(),
             max: DateType::try_downcast_scalar(&stat.max.as_ref()).unwrap(),
         }),
+        DataType::Decimal(_) => stat
+            .min
+            .as_ref()
+            .domain(data_type)
+            .merge(&stat.max.as_ref().domain(data_type)),
         DataType::Nullable(ty) => {
             let domain = statistics_to_domain(Some(stat), ty);
             Domain::Nullable(NullableDomain {
@@ -0,0 +1,441 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::types::nullable::NullableDomain;
+use common_expression::types::DataType;
+use common_expression::ConstantFolder;
+use common_expression::DataBlock;
+use common_expression::Domain;
+use common_expression::Evaluator;
+use common_expression::Expr;
+use common_expression::FunctionContext;
+use common_expression::Scalar;
+use common_expression::ScalarRef;
+use common_expression::TableSchema;
+use common_functions::scalars::BUILTIN_FUNCTIONS;
+use common_meta_app::schema::SkippingIndexMeta;
+use common_meta_app::schema::SkippingIndexType;
+use storages_common_table_meta::meta::BlockSkippingIndex;
+use storages_common_table_meta::meta::SkippingIndexData;
+use storages_common_table_meta::meta::SkippingIndexEntry;
+
+use crate::filters::Filter;
+use crate::filters::FilterBuilder;
+use crate::filters::Xor8Builder;
+use crate::filters::Xor8Filter;
+use crate::FilterEvalResult;
+use crate::SupportedType;
+
+/// A data skipping index declared on an expression of a table.
+#[derive(Clone, Debug)]
+pub struct SkippingIndex {
+    pub name: String,
+    pub meta: SkippingIndexMeta,
+    /// The indexed expression, which refers the columns by name.
+    pub expr: Expr<String>,
+}
+
+impl SkippingIndex {
+    pub fn create(name: &str, meta: &SkippingIndexMeta, expr: Expr<String>) -> Result<Self> {
+        let data_type = expr.data_type().remove_nullable();
+        let supported = match meta.index_type {
+            SkippingIndexType::MinMax | SkippingIndexType::Set(_) => {
+                SkippingIndexes::is_supported_type(&data_type)
+            }
+            SkippingIndexType::NgramBloomFilter(_) => data_type == DataType::String,
+        };
+        if !supported {
+            return Err(ErrorCode::UnsupportedIndex(format!(
+                "Index type {} is not supported on expression {} of type {}",
+                meta.index_type, meta.expr, data_type
+            )));
+        }
+
+        match meta.index_type {
+            SkippingIndexType::Set(0) | SkippingIndexType::NgramBloomFilter(0) => {
+                Err(ErrorCode::UnsupportedIndex(format!(
+                    "The parameter of index type {} must be greater than 0",
+                    meta.index_type
+                )))
+            }
+            _ => Ok(Self {
+                name: name.to_string(),
+                meta: meta.clone(),
+                expr,
+            }),
+        }
+    }
+
+    /// The definition of the index, which is kept along with the index data of each block.
+    pub fn definition(&self) -> String {
+        format!("{} {}", self.meta.index_type, self.meta.expr)
+    }
+
+    fn build(
+        &self,
+        func_ctx: FunctionContext,
+        schema: &TableSchema,
+        block: &DataBlock,
+    ) -> Result<SkippingIndexData> {
+        for name in self.expr.column_refs().keys() {
+            schema.index_of(name)?;
+        }
+        let expr = self
+            .expr
+            .project_column_ref(|name| schema.index_of(name).unwrap());
+        let evaluator = Evaluator::new(block, func_ctx, &BUILTIN_FUNCTIONS);
+        let column = evaluator
+            .run(&expr)?
+            .convert_to_full_column(expr.data_type(), block.num_rows());
+
+        let data = match self.meta.index_type {
+            SkippingIndexType::MinMax => {
+                let mut min = Scalar::Null;
+                let mut max = Scalar::Null;
+                let mut has_null = false;
+                for value in column.iter() {
+                    if value.is_null() {
+                        has_null = true;
+                        continue;
+                    }
+                    if min.is_null() || value < min.as_ref() {
+                        min = value.to_owned();
+                    }
+                    if max.is_null() || value > max.as_ref() {
+                        max = value.to_owned();
+                    }
+                }
+                SkippingIndexData::MinMax { min, max, has_null }
+            }
+            SkippingIndexType::Set(max_size) => {
+                let mut values = BTreeSet::new();
+                for value in column.iter().filter(|v| !v.is_null()) {
+                    values.insert(value.to_owned());
+                    if values.len() as u64 > max_size {
+                        break;
+                    }
+                }
+                if values.len() as u64 > max_size {
+                    SkippingIndexData::Set(None)
+                } else {
+                    SkippingIndexData::Set(Some(values.into_iter().collect()))
+                }
+            }
+            SkippingIndexType::NgramBloomFilter(n) => {
+                let mut builder = Xor8Builder::create();
+                let mut is_empty = true;
+                for value in column.iter() {
+                    if let ScalarRef::String(s) = value {
+                        for ngram in ngrams(s, n as usize) {
+                            builder.add_key(&ngram);
+                            is_empty = false;
+                        }
+                    }
+                }
+                // Values shorter than `n` contribute no ngram, an empty filter
+                // means no pattern of at least `n` chars can match.
+                if is_empty {
+                    SkippingIndexData::NgramBloomFilter(vec![])
+                } else {
+                    SkippingIndexData::NgramBloomFilter(builder.build()?.to_bytes()?)
+                }
+            }
+        };
+        Ok(data)
+    }
+}
+
+struct SkippingIndexes;
+
+impl SupportedType for SkippingIndexes {}
+
+/// Build the skipping indexes of a block.
+pub fn build_block_skipping_index(
+    func_ctx: FunctionContext,
+    schema: &TableSchema,
+    indexes: &[SkippingIndex],
+    block: &DataBlock,
+) -> Result<BlockSkippingIndex> {
+    let mut block_index = BlockSkippingIndex::default();
+    for index in indexes {
+        let data = index.build(func_ctx, schema, block)?;
+        block_index
+            .indexes
+            .insert(index.name.clone(), SkippingIndexEntry {
+                definition: index.definition(),
+                data,
+            });
+    }
+    Ok(block_index)
+}
+
+/// Apply the predicate expression to the skipping indexes of a block.
+///
+/// The sub expressions which are indexed are replaced by what the index knows about them:
+/// the range of values for a minmax index, or constant `false` for a comparison that the
+/// set or ngram bloom filter index is sure to fail. Then the expression is folded, if it
+/// is folded to constant `false`, the block can be skipped.
+#[tracing::instrument(level = "debug", name = "block_skipping_index_eval", skip_all)]
+pub fn eval_block_skipping_index(
+    func_ctx: FunctionContext,
+    indexes: &[SkippingIndex],
+    block_index: &BlockSkippingIndex,
+    expr: &Expr<String>,
+) -> Result<FilterEvalResult> {
+    let mut applicable = Vec::with_capacity(indexes.len());
+    for index in indexes {
+        if let Some(entry) = block_index.indexes.get(&index.name) {
+            if entry.definition == index.definition() {
+                applicable.push((index.expr.sql_display(), index, &entry.data));
+            }
+        }
+    }
+
+    let mut virtual_domains = HashMap::new();
+    let mut expr = expr.clone();
+    rewrite_expr(&mut expr, &applicable, &mut virtual_domains)?;
+
+    let input_domains = expr
+        .column_refs()
+        .into_iter()
+        .map(|(name, ty)| {
+            let domain = virtual_domains
+                .remove(&name)
+                .unwrap_or_else(|| Domain::full(&ty));
+            (name, domain)
+        })
+        .collect();
+
+    let folder = ConstantFolder::new(input_domains, func_ctx, &BUILTIN_FUNCTIONS);
+    let (new_expr, _) = folder.fold(&expr);
+
+    match new_expr {
+        Expr::Constant {
+            scalar: Scalar::Boolean(false),
+            ..
+        } => Ok(FilterEvalResult::MustFalse),
+        _ => Ok(FilterEvalResult::Uncertain),
+    }
+}
+
+/// Whether any sub expression of the predicate is indexed.
+pub fn is_skipping_index_applicable(indexes: &[SkippingIndex], expr: &Expr<String>) -> bool {
+    let indexed = indexes
+        .iter()
+        .map(|index| index.expr.sql_display())
+        .collect::<Vec<_>>();
+
+    fn walk(expr: &Expr<String>, indexed: &[String]) -> bool {
+        if indexed.contains(&expr.sql_display()) {
+            return true;
+        }
+        match expr {
+            Expr::Cast { expr, .. } => walk(expr, indexed),
+            Expr::FunctionCall { args, .. } => args.iter().any(|arg| walk(arg, indexed)),
+            _ => false,
+        }
+    }
+    walk(expr, &indexed)
+}
+
+type ApplicableIndex<'a> = (String, &'a SkippingIndex, &'a SkippingIndexData);
+
+fn rewrite_expr(
+    expr: &mut Expr<String>,
+    indexes: &[ApplicableIndex],
+    virtual_domains: &mut HashMap<String, Domain>,
+) -> Result<()> {
+    if let Some(new_expr) = rewrite_comparison(expr, indexes)? {
+        *expr = new_expr;
+        return Ok(());
+    }
+
+    // Indexed expressions with min and max values, e.g. `to_date(ts)`, are replaced
+    // by virtual columns whose domains are the ranges of the values.
+    let display = expr.sql_display();
+    for (d, index, data) in indexes {
+        if d != &display {
+            continue;
+        }
+        if let SkippingIndexData::MinMax { min, max, has_null } = data {
+            let data_type = expr.data_type().clone();
+            let column = format!("__skipping_index_{}", index.name);
+            virtual_domains.insert(
+                column.clone(),
+                min_max_domain(&data_type, min, max, *has_null),
+            );
+            *expr = Expr::ColumnRef {
+                span: None,
+                id: column,
+                data_type,
+            };
+            return Ok(());
+        }
+    }
+
+    match expr {
+        Expr::Cast { expr, .. } => rewrite_expr(expr, indexes, virtual_domains)?,
+        Expr::FunctionCall { args, .. } => {
+            for arg in args.iter_mut() {
+                rewrite_expr(arg, indexes, virtual_domains)?;
+            }
+        }
+        _ => (),
+    }
+    Ok(())
+}
+
+// Comparisons with a constant, e.g. `<indexed> = <constant>` or `<indexed> LIKE <constant>`,
+// are rewritten to `false` if the index is sure that no value matches.
+fn rewrite_comparison(
+    expr: &Expr<String>,
+    indexes: &[ApplicableIndex],
+) -> Result<Option<Expr<String>>> {
+    if let Expr::FunctionCall {
+        span,
+        function,
+        args,
+        return_type,
+        ..
+    } = expr
+    {
+        let name = function.signature.name.as_str();
+        let (arg, scalar) = match args.as_slice() {
+            [arg, Expr::Constant { scalar, .. }] if name == "eq" || name == "like" => (arg, scalar),
+            [Expr::Constant { scalar, .. }, arg] if name == "eq" => (arg, scalar),
+            _ => return Ok(None),
+        };
+
+        let display = arg.sql_display();
+        for (_, index, data) in indexes.iter().filter(|(d, _, _)| d == &display) {
+            if must_not_match(index, data, name, scalar)? {
+                return Ok(Some(Expr::Constant {
+                    span: span.clone(),
+                    scalar: Scalar::Boolean(false),
+                    data_type: return_type.clone(),
+                }));
+            }
+        }
+    }
+    Ok(None)
+}
+
+fn must_not_match(
+    index: &SkippingIndex,
+    data: &SkippingIndexData,
+    function: &str,
+    target: &Scalar,
+) -> Result<bool> {
+    if target.is_null() {
+        return Ok(false);
+    }
+
+    match (data, function) {
+        (SkippingIndexData::Set(Some(values)), "eq") => Ok(values.binary_search(target).is_err()),
+        (SkippingIndexData::NgramBloomFilter(bytes), "eq" | "like") => {
+            let n = match index.meta.index_type {
+                SkippingIndexType::NgramBloomFilter(n) => n as usize,
+                _ => return Ok(false),
+            };
+            let target = match target {
+                Scalar::String(s) => s,
+                _ => return Ok(false),
+            };
+            let fragments = if function == "like" {
+                like_fragments(target)
+            } else {
+                vec![target.clone()]
+            };
+
+            let required = fragments
+                .iter()
+                .flat_map(|fragment| ngrams(fragment, n))
+                .collect::<Vec<_>>();
+            if required.is_empty() {
+                return Ok(false);
+            }
+            if bytes.is_empty() {
+                return Ok(true);
+            }
+
+            let (filter, _) = Xor8Filter::from_bytes(bytes)?;
+            Ok(required.iter().any(|ngram| !filter.contains(ngram)))
+        }
+        _ => Ok(false),
+    }
+}
+
+fn min_max_domain(data_type: &DataType, min: &Scalar, max: &Scalar, has_null: bool) -> Domain {
+    if min.is_null() || max.is_null() {
+        return match data_type {
+            DataType::Nullable(_) => Domain::Nullable(NullableDomain {
+                has_null: true,
+                value: None,
+            }),
+            _ => Domain::full(data_type),
+        };
+    }
+
+    let inner_type = data_type.remove_nullable();
+    let domain = min
+        .as_ref()
+        .domain(&inner_type)
+        .merge(&max.as_ref().domain(&inner_type));
+    match data_type {
+        DataType::Nullable(_) => Domain::Nullable(NullableDomain {
+            has_null,
+            value: Some(Box::new(domain)),
+        }),
+        _ => domain,
+    }
+}
+
+/// The ngrams of `n` chars of the string.
+fn ngrams(s: &[u8], n: usize) -> Vec<String> {
+    let chars = String::from_utf8_lossy(s).chars().collect::<Vec<_>>();
+    chars
+        .windows(n)
+        .map(|window| window.iter().collect::<String>())
+        .collect()
+}
+
+/// The literal fragments of a LIKE pattern, which every matched string must contain.
+fn like_fragments(pattern: &[u8]) -> Vec<Vec<u8>> {
+    let mut fragments = vec![];
+    let mut fragment = vec![];
+    let mut iter = pattern.iter();
+    while let Some(c) = iter.next() {
+        match c {
+            b'\\' => {
+                if let Some(c) = iter.next() {
+                    fragment.push(*c);
+                }
+            }
+            b'%' | b'_' => {
+                if !fragment.is_empty() {
+                    fragments.push(std::mem::take(&mut fragment));
+                }
+            }
+            _ => fragment.push(*c),
+        }
+    }
+    if !fragment.is_empty() {
+        fragments.push(fragment);
+    }
+    fragments
+}
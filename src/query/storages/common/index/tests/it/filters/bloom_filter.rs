@@ -115,6 +115,57 @@ fn test_bloom_filter() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_bloom_filter_after_rename_column() -> Result<()> {
+    let schema = Arc::new(TableSchema::new(vec![
+        TableField::new("a", TableDataType::Number(NumberDataType::UInt8)),
+        TableField::new("b", TableDataType::String),
+    ]));
+    let block = DataBlock::new_from_columns(vec![
+        UInt8Type::from_data(vec![2, 3]),
+        StringType::from_data(vec!["b", "c"]),
+    ]);
+
+    let index = BlockFilter::try_create(
+        FunctionContext::default(),
+        schema.clone(),
+        LatestBloom::VERSION,
+        &[&block],
+    )?;
+
+    // the filters are found by the column ids, which are not changed by renaming.
+    let mut renamed_schema = schema.as_ref().clone();
+    renamed_schema.rename_column("b", "c")?;
+    renamed_schema.rename_column("a", "b")?;
+    let index = BlockFilter::from_filter_block(
+        FunctionContext::default(),
+        Arc::new(renamed_schema),
+        index.filter_schema,
+        index.filter_block,
+        LatestBloom::VERSION,
+    )?;
+
+    assert_eq!(
+        FilterEvalResult::MustFalse,
+        eval_index(&index, "c", Scalar::String(b"a".to_vec()), DataType::String)
+    );
+    assert_eq!(
+        FilterEvalResult::Uncertain,
+        eval_index(&index, "c", Scalar::String(b"b".to_vec()), DataType::String)
+    );
+    assert_eq!(
+        FilterEvalResult::Uncertain,
+        eval_index(
+            &index,
+            "b",
+            Scalar::Number(NumberScalar::UInt8(2)),
+            DataType::Number(NumberDataType::UInt8)
+        )
+    );
+
+    Ok(())
+}
+
 fn eval_index(index: &BlockFilter, col_name: &str, val: Scalar, ty: DataType) -> FilterEvalResult {
     index
         .eval(
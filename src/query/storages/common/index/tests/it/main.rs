@@ -13,3 +13,4 @@
 // limitations under the License.
 
 mod filters;
+mod skipping_index;
@@ -0,0 +1,235 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use common_exception::Result;
+use common_expression::type_check::check_function;
+use common_expression::types::number::NumberScalar;
+use common_expression::types::number::UInt8Type;
+use common_expression::types::DataType;
+use common_expression::types::NumberDataType;
+use common_expression::types::StringType;
+use common_expression::DataBlock;
+use common_expression::Expr;
+use common_expression::FromData;
+use common_expression::FunctionContext;
+use common_expression::Scalar;
+use common_expression::TableDataType;
+use common_expression::TableField;
+use common_expression::TableSchema;
+use common_functions::scalars::BUILTIN_FUNCTIONS;
+use common_meta_app::schema::SkippingIndexMeta;
+use common_meta_app::schema::SkippingIndexType;
+use storages_common_index::build_block_skipping_index;
+use storages_common_index::eval_block_skipping_index;
+use storages_common_index::FilterEvalResult;
+use storages_common_index::SkippingIndex;
+
+#[test]
+fn test_skipping_index() -> Result<()> {
+    let schema = TableSchema::new(vec![
+        TableField::new("a", TableDataType::Number(NumberDataType::UInt8)),
+        TableField::new("s", TableDataType::String),
+    ]);
+    let block = DataBlock::new_from_columns(vec![
+        UInt8Type::from_data(vec![1, 2, 3]),
+        StringType::from_data(vec!["hello world", "databend", "foo"]),
+    ]);
+
+    let a_plus_one = function("plus", &[column("a", uint8_type()), uint8(1)]);
+    let indexes = vec![
+        index(
+            "i_minmax",
+            SkippingIndexType::MinMax,
+            "a + 1",
+            a_plus_one.clone(),
+        )?,
+        index(
+            "i_set",
+            SkippingIndexType::Set(10),
+            "a",
+            column("a", uint8_type()),
+        )?,
+        index(
+            "i_small_set",
+            SkippingIndexType::Set(2),
+            "a + 1",
+            a_plus_one.clone(),
+        )?,
+        index(
+            "i_ngram",
+            SkippingIndexType::NgramBloomFilter(3),
+            "s",
+            column("s", DataType::String),
+        )?,
+    ];
+    let block_index =
+        build_block_skipping_index(FunctionContext::default(), &schema, &indexes, &block)?;
+
+    let eval = |expr: Expr<String>| {
+        eval_block_skipping_index(FunctionContext::default(), &indexes, &block_index, &expr)
+            .unwrap()
+    };
+
+    // minmax: a + 1 is in [2, 4]
+    assert_eq!(
+        FilterEvalResult::MustFalse,
+        eval(function("gt", &[a_plus_one.clone(), uint16(4)]))
+    );
+    assert_eq!(
+        FilterEvalResult::Uncertain,
+        eval(function("gt", &[a_plus_one.clone(), uint16(3)]))
+    );
+
+    // set
+    assert_eq!(
+        FilterEvalResult::MustFalse,
+        eval(function("eq", &[column("a", uint8_type()), uint8(5)]))
+    );
+    assert_eq!(
+        FilterEvalResult::Uncertain,
+        eval(function("eq", &[column("a", uint8_type()), uint8(2)]))
+    );
+    // the set of more than 2 values is not kept, only the minmax index of `a + 1` applies.
+    assert_eq!(
+        FilterEvalResult::Uncertain,
+        eval(function("eq", &[a_plus_one, uint16(3)]))
+    );
+
+    // ngram bloom filter
+    assert_eq!(
+        FilterEvalResult::Uncertain,
+        eval(function("like", &[
+            column("s", DataType::String),
+            string("%data%")
+        ]))
+    );
+    assert_eq!(
+        FilterEvalResult::MustFalse,
+        eval(function("like", &[
+            column("s", DataType::String),
+            string("%xyz%")
+        ]))
+    );
+    // patterns shorter than the ngrams can not be checked.
+    assert_eq!(
+        FilterEvalResult::Uncertain,
+        eval(function("like", &[
+            column("s", DataType::String),
+            string("%xy%")
+        ]))
+    );
+    assert_eq!(
+        FilterEvalResult::MustFalse,
+        eval(function("eq", &[
+            column("s", DataType::String),
+            string("bar")
+        ]))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_skipping_index_definition_changed() -> Result<()> {
+    let schema = TableSchema::new(vec![TableField::new(
+        "a",
+        TableDataType::Number(NumberDataType::UInt8),
+    )]);
+    let block = DataBlock::new_from_columns(vec![UInt8Type::from_data(vec![1, 2, 3])]);
+
+    let a = column("a", uint8_type());
+    let old = vec![index("i", SkippingIndexType::Set(10), "a", a.clone())?];
+    let block_index =
+        build_block_skipping_index(FunctionContext::default(), &schema, &old, &block)?;
+
+    // the index is created again with the same name, the data of the block is ignored.
+    let new = vec![index("i", SkippingIndexType::Set(20), "a", a.clone())?];
+    let expr = function("eq", &[a, uint8(5)]);
+    assert_eq!(
+        FilterEvalResult::MustFalse,
+        eval_block_skipping_index(FunctionContext::default(), &old, &block_index, &expr)?
+    );
+    assert_eq!(
+        FilterEvalResult::Uncertain,
+        eval_block_skipping_index(FunctionContext::default(), &new, &block_index, &expr)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_skipping_index_unsupported_type() {
+    let expr = column("a", uint8_type());
+    assert!(
+        index(
+            "i",
+            SkippingIndexType::NgramBloomFilter(3),
+            "a",
+            expr.clone()
+        )
+        .is_err()
+    );
+    assert!(index("i", SkippingIndexType::Set(0), "a", expr).is_err());
+}
+
+fn index(
+    name: &str,
+    index_type: SkippingIndexType,
+    definition: &str,
+    expr: Expr<String>,
+) -> Result<SkippingIndex> {
+    let meta = SkippingIndexMeta {
+        index_type,
+        expr: definition.to_string(),
+    };
+    SkippingIndex::create(name, &meta, expr)
+}
+
+fn uint8_type() -> DataType {
+    DataType::Number(NumberDataType::UInt8)
+}
+
+fn column(name: &str, data_type: DataType) -> Expr<String> {
+    Expr::ColumnRef {
+        span: None,
+        id: name.to_string(),
+        data_type,
+    }
+}
+
+fn constant(scalar: Scalar, data_type: DataType) -> Expr<String> {
+    Expr::Constant {
+        span: None,
+        scalar,
+        data_type,
+    }
+}
+
+fn uint8(v: u8) -> Expr<String> {
+    constant(Scalar::Number(NumberScalar::UInt8(v)), uint8_type())
+}
+
+fn uint16(v: u16) -> Expr<String> {
+    constant(
+        Scalar::Number(NumberScalar::UInt16(v)),
+        DataType::Number(NumberDataType::UInt16),
+    )
+}
+
+fn string(v: &str) -> Expr<String> {
+    constant(Scalar::String(v.as_bytes().to_vec()), DataType::String)
+}
+
+fn function(name: &str, args: &[Expr<String>]) -> Expr<String> {
+    check_function(None, name, &[], args, &BUILTIN_FUNCTIONS).unwrap()
+}
@@ -16,7 +16,10 @@ pub use v0::ColumnMeta;
 pub use v1::TableSnapshotStatistics;
 pub use v2::BlockFilter;
 pub use v2::BlockMeta;
+pub use v2::BlockSkippingIndex;
 pub use v2::SegmentInfo;
+pub use v2::SkippingIndexData;
+pub use v2::SkippingIndexEntry;
 pub use v2::TableSnapshot;
 pub use v2::TableSnapshotLite;
 
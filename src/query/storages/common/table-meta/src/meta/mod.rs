@@ -37,4 +37,5 @@ pub use versions::SegmentInfoVersion;
 pub use versions::SnapshotVersion;
 pub use versions::TableSnapshotStatisticsVersion;
 pub use versions::V2BloomBlock;
+pub use versions::V3BloomBlock;
 pub use versions::Versioned;
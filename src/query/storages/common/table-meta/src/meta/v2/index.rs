@@ -12,8 +12,13 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+use std::collections::BTreeMap;
+
 use common_expression::DataBlock;
+use common_expression::Scalar;
 use common_expression::TableSchemaRef;
+use serde::Deserialize;
+use serde::Serialize;
 
 /// Filter data of a DataBlock, which itself is also a DataBlock.
 ///
@@ -22,3 +27,36 @@ pub struct BlockFilter {
     pub filter_schema: TableSchemaRef,
     pub filter_block: DataBlock,
 }
+
+/// Data skipping indexes of a DataBlock, keyed by index name.
+///
+/// Unlike [BlockFilter], which is built on every column, skipping indexes are declared
+/// by users on expressions of the table, and are all loaded at once.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct BlockSkippingIndex {
+    pub indexes: BTreeMap<String, SkippingIndexEntry>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SkippingIndexEntry {
+    /// The definition the index is built with, e.g. `set(100) to_date(ts)`.
+    ///
+    /// An index may be dropped and created again with the same name, the data of a block
+    /// is used only if the definition is unchanged.
+    pub definition: String,
+    pub data: SkippingIndexData,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum SkippingIndexData {
+    /// Min and max non-null values of the expression, `Scalar::Null` if all the values are null.
+    MinMax {
+        min: Scalar,
+        max: Scalar,
+        has_null: bool,
+    },
+    /// Distinct non-null values of the expression, None if there are more values than the limit.
+    Set(Option<Vec<Scalar>>),
+    /// Serialized xor8 filter of the ngrams of the expression.
+    NgramBloomFilter(Vec<u8>),
+}
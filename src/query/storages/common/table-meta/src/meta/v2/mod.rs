@@ -17,6 +17,9 @@ mod segment;
 mod snapshot;
 
 pub use index::BlockFilter;
+pub use index::BlockSkippingIndex;
+pub use index::SkippingIndexData;
+pub use index::SkippingIndexEntry;
 pub use segment::BlockMeta;
 pub use segment::SegmentInfo;
 pub use snapshot::TableSnapshot;
@@ -58,6 +58,13 @@ pub struct BlockMeta {
     #[serde(default)]
     pub bloom_filter_index_size: u64,
 
+    /// location of data skipping indexes, if the table has any
+    #[serde(default)]
+    pub skipping_index_location: Option<Location>,
+
+    #[serde(default)]
+    pub skipping_index_size: u64,
+
     /// Compression algo used to compress the columns of blocks
     ///
     /// If not specified, the legacy algo `Lz4` will be used.
@@ -79,6 +86,8 @@ impl BlockMeta {
         location: Location,
         bloom_filter_index_location: Option<Location>,
         bloom_filter_index_size: u64,
+        skipping_index_location: Option<Location>,
+        skipping_index_size: u64,
         compression: Compression,
     ) -> Self {
         Self {
@@ -91,6 +100,8 @@ impl BlockMeta {
             location,
             bloom_filter_index_location,
             bloom_filter_index_size,
+            skipping_index_location,
+            skipping_index_size,
             compression,
         }
     }
@@ -166,6 +177,8 @@ impl BlockMeta {
             location: (s.location.path.clone(), 0),
             bloom_filter_index_location: None,
             bloom_filter_index_size: 0,
+            skipping_index_location: None,
+            skipping_index_size: 0,
             compression: Compression::Lz4,
         }
     }
@@ -190,6 +203,8 @@ impl BlockMeta {
             location: s.location.clone(),
             bloom_filter_index_location: s.bloom_filter_index_location.clone(),
             bloom_filter_index_size: s.bloom_filter_index_size,
+            skipping_index_location: None,
+            skipping_index_size: 0,
             compression: s.compression,
         }
     }
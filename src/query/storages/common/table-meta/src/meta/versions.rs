@@ -97,16 +97,21 @@ impl Versioned<2> for DataBlock {}
 
 pub struct V0BloomBlock {}
 pub struct V2BloomBlock {}
+pub struct V3BloomBlock {}
 
 impl Versioned<0> for V0BloomBlock {}
 impl Versioned<2> for V2BloomBlock {}
+impl Versioned<3> for V3BloomBlock {}
 
-impl Versioned<3> for BlockFilter {}
+impl Versioned<4> for BlockFilter {}
+
+impl Versioned<0> for v2::BlockSkippingIndex {}
 
 pub enum BlockBloomFilterIndexVersion {
     V0(PhantomData<V0BloomBlock>),
     V2(PhantomData<V2BloomBlock>),
-    V3(PhantomData<v2::BlockFilter>),
+    V3(PhantomData<V3BloomBlock>),
+    V4(PhantomData<v2::BlockFilter>),
 }
 
 mod converters {
@@ -173,6 +178,10 @@ mod converters {
                 3 => Ok(BlockBloomFilterIndexVersion::V3(ver_eq::<_, 3>(
                     PhantomData,
                 ))),
+                // version 4 names the filter columns by the column ids instead of the column names
+                4 => Ok(BlockBloomFilterIndexVersion::V4(ver_eq::<_, 4>(
+                    PhantomData,
+                ))),
                 _ => Err(ErrorCode::Internal(format!(
                     "unknown block bloom filer index version {value}, versions supported: 1"
                 ))),
@@ -20,6 +20,22 @@ pub const OPT_KEY_DATABASE_ID: &str = "database_id";
 pub const OPT_KEY_SNAPSHOT_LOCATION: &str = "snapshot_location";
 pub const OPT_KEY_STORAGE_FORMAT: &str = "storage_format";
 pub const OPT_KEY_TABLE_COMPRESSION: &str = "compression";
+/// The query of a materialized view, kept by the table that stores the view's rows
+pub const OPT_KEY_MATERIALIZED_VIEW_QUERY: &str = "materialized_view_query";
+/// The table that a materialized view reads, as a json array of catalog, database and table
+pub const OPT_KEY_MATERIALIZED_VIEW_SOURCE: &str = "materialized_view_source";
+/// The materialized views maintained on insertion, kept by the source table of the views
+/// as a json array of catalog, database and view triples
+pub const OPT_KEY_MATERIALIZED_VIEWS: &str = "materialized_views";
+/// Set on a materialized view which failed to be maintained, until it is refreshed
+pub const OPT_KEY_MATERIALIZED_VIEW_STALE: &str = "materialized_view_stale";
+/// The table that a stream tracks, as a json array of catalog, database and table
+pub const OPT_KEY_STREAM_SOURCE: &str = "stream_source";
+/// The id of the table that a stream tracks, a recreated table is not tracked any more
+pub const OPT_KEY_STREAM_SOURCE_TABLE_ID: &str = "stream_source_table_id";
+/// The snapshot location of the source table up to which the changes have been consumed,
+/// absent if the source table had no snapshot yet
+pub const OPT_KEY_STREAM_OFFSET: &str = "stream_offset";
 
 /// Legacy table snapshot location key
 ///
@@ -38,6 +54,13 @@ pub static RESERVED_TABLE_OPTION_KEYS: Lazy<HashSet<&'static str>> = Lazy::new(|
     let mut r = HashSet::new();
     r.insert(OPT_KEY_DATABASE_ID);
     r.insert(OPT_KEY_LEGACY_SNAPSHOT_LOC);
+    r.insert(OPT_KEY_MATERIALIZED_VIEW_QUERY);
+    r.insert(OPT_KEY_MATERIALIZED_VIEW_SOURCE);
+    r.insert(OPT_KEY_MATERIALIZED_VIEWS);
+    r.insert(OPT_KEY_MATERIALIZED_VIEW_STALE);
+    r.insert(OPT_KEY_STREAM_SOURCE);
+    r.insert(OPT_KEY_STREAM_SOURCE_TABLE_ID);
+    r.insert(OPT_KEY_STREAM_OFFSET);
     r
 });
 
@@ -46,6 +69,13 @@ pub static INTERNAL_TABLE_OPTION_KEYS: Lazy<HashSet<&'static str>> = Lazy::new(|
     let mut r = HashSet::new();
     r.insert(OPT_KEY_LEGACY_SNAPSHOT_LOC);
     r.insert(OPT_KEY_DATABASE_ID);
+    r.insert(OPT_KEY_MATERIALIZED_VIEW_QUERY);
+    r.insert(OPT_KEY_MATERIALIZED_VIEW_SOURCE);
+    r.insert(OPT_KEY_MATERIALIZED_VIEWS);
+    r.insert(OPT_KEY_MATERIALIZED_VIEW_STALE);
+    r.insert(OPT_KEY_STREAM_SOURCE);
+    r.insert(OPT_KEY_STREAM_SOURCE_TABLE_ID);
+    r.insert(OPT_KEY_STREAM_OFFSET);
     r
 });
 
@@ -0,0 +1,122 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! The actions recorded in the transaction log of Delta Lake,
+//! see <https://github.com/delta-io/delta/blob/master/PROTOCOL.md#actions>.
+
+use std::collections::HashMap;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use serde::Deserialize;
+
+/// Column mapping (reader version 2) and table features (reader version 3)
+/// change the way to read the data files, which are not supported yet.
+pub const MAX_READER_VERSION: i64 = 1;
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Protocol {
+    pub min_reader_version: i64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Format {
+    pub provider: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Metadata {
+    pub format: Format,
+    pub schema_string: String,
+    #[serde(default)]
+    pub partition_columns: Vec<String>,
+}
+
+/// A data file added to the table.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Add {
+    /// The uri encoded path relative to the table directory.
+    pub path: String,
+    /// The values of the partition columns, serialized as strings.
+    #[serde(default)]
+    pub partition_values: HashMap<String, Option<String>>,
+    #[serde(default)]
+    pub size: i64,
+    /// The statistics of the file, serialized as a json object.
+    pub stats: Option<String>,
+}
+
+/// A data file removed from the table.
+#[derive(Deserialize, Debug, Clone)]
+pub struct Remove {
+    pub path: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct CommitInfo {
+    /// The time of the commit, in milliseconds since the epoch.
+    pub timestamp: Option<i64>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Action {
+    Protocol(Protocol),
+    Metadata(Metadata),
+    Add(Add),
+    Remove(Remove),
+    CommitInfo(CommitInfo),
+}
+
+/// A line of a commit file, which holds exactly one action.
+/// The actions unrelated to reading, such as `txn` and `cdc`, are ignored.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ActionLine {
+    protocol: Option<Protocol>,
+    meta_data: Option<Metadata>,
+    add: Option<Add>,
+    remove: Option<Remove>,
+    commit_info: Option<CommitInfo>,
+}
+
+/// Parse the actions of a commit file, which is newline-delimited json.
+pub fn parse_commit(path: &str, data: &[u8]) -> Result<Vec<Action>> {
+    let mut actions = vec![];
+    for line in data.split(|b| *b == b'\n') {
+        if line.iter().all(|b| b.is_ascii_whitespace()) {
+            continue;
+        }
+        let line: ActionLine = serde_json::from_slice(line).map_err(|e| {
+            ErrorCode::BadBytes(format!("invalid delta commit file {}: {}", path, e))
+        })?;
+        if let Some(protocol) = line.protocol {
+            actions.push(Action::Protocol(protocol));
+        }
+        if let Some(metadata) = line.meta_data {
+            actions.push(Action::Metadata(metadata));
+        }
+        if let Some(add) = line.add {
+            actions.push(Action::Add(add));
+        }
+        if let Some(remove) = line.remove {
+            actions.push(Action::Remove(remove));
+        }
+        if let Some(commit_info) = line.commit_info {
+            actions.push(Action::CommitInfo(commit_info));
+        }
+    }
+    Ok(actions)
+}
@@ -0,0 +1,206 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! The checkpoints of the transaction log, which are parquet files holding
+//! the reconciled actions of the table at some version.
+
+use std::collections::HashMap;
+use std::io::Cursor;
+
+use common_arrow::arrow::array::Array;
+use common_arrow::arrow::array::ListArray;
+use common_arrow::arrow::array::MapArray;
+use common_arrow::arrow::array::PrimitiveArray;
+use common_arrow::arrow::array::StructArray;
+use common_arrow::arrow::array::Utf8Array;
+use common_arrow::arrow::io::parquet::read::infer_schema;
+use common_arrow::arrow::io::parquet::read::read_columns;
+use common_arrow::arrow::io::parquet::read::read_metadata;
+use common_arrow::arrow::io::parquet::read::to_deserializer;
+use common_arrow::arrow::io::parquet::read::RowGroupDeserializer;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::action::Action;
+use crate::action::Add;
+use crate::action::Format;
+use crate::action::Metadata;
+use crate::action::Protocol;
+
+/// The columns of the actions needed to rebuild the active files of the table,
+/// the `remove` actions in checkpoints are only tombstones of the removed files.
+const ACTION_COLUMNS: [&str; 3] = ["protocol", "metaData", "add"];
+
+/// Read the actions in a part of a checkpoint.
+pub fn parse_checkpoint(path: &str, data: Vec<u8>) -> Result<Vec<Action>> {
+    let mut reader = Cursor::new(data);
+    let file_meta = read_metadata(&mut reader)
+        .map_err(|e| ErrorCode::BadBytes(format!("invalid delta checkpoint {}: {}", path, e)))?;
+    let fields = infer_schema(&file_meta)?
+        .fields
+        .into_iter()
+        .filter(|f| ACTION_COLUMNS.contains(&f.name.as_str()))
+        .collect::<Vec<_>>();
+
+    let mut actions = vec![];
+    for row_group in file_meta.row_groups.iter() {
+        let mut columns = Vec::with_capacity(fields.len());
+        for field in fields.iter() {
+            let chunks = read_columns(&mut reader, row_group.columns(), &field.name)?;
+            columns.push(to_deserializer(
+                chunks,
+                field.clone(),
+                row_group.num_rows(),
+                None,
+                None,
+            )?);
+        }
+        for chunk in RowGroupDeserializer::new(columns, row_group.num_rows(), None) {
+            let chunk = chunk?;
+            for (field, array) in fields.iter().zip(chunk.arrays()) {
+                let array = as_struct(array.as_ref()).ok_or_else(|| {
+                    ErrorCode::BadBytes(format!(
+                        "invalid delta checkpoint {}: column {} is not a struct",
+                        path, field.name
+                    ))
+                })?;
+                for row in 0..array.len() {
+                    if let Some(action) = parse_action(&field.name, array, row) {
+                        actions.push(action);
+                    }
+                }
+            }
+        }
+    }
+    Ok(actions)
+}
+
+/// Each row holds only one action, the columns of the other actions are null.
+fn parse_action(column: &str, array: &StructArray, row: usize) -> Option<Action> {
+    if !array.is_valid(row) {
+        return None;
+    }
+    let action = match column {
+        "protocol" => Action::Protocol(Protocol {
+            min_reader_version: get_i64(array, "minReaderVersion", row)?,
+        }),
+        "metaData" => {
+            let provider = child(array, "format")
+                .and_then(as_struct)
+                .and_then(|format| get_str(format, "provider", row))
+                .unwrap_or_else(|| "parquet".to_string());
+            Action::Metadata(Metadata {
+                format: Format { provider },
+                schema_string: get_str(array, "schemaString", row)?,
+                partition_columns: child(array, "partitionColumns")
+                    .map(|a| string_list(a, row))
+                    .unwrap_or_default(),
+            })
+        }
+        "add" => Action::Add(Add {
+            path: get_str(array, "path", row)?,
+            partition_values: child(array, "partitionValues")
+                .map(|a| string_map(a, row))
+                .unwrap_or_default(),
+            size: get_i64(array, "size", row).unwrap_or_default(),
+            stats: get_str(array, "stats", row),
+        }),
+        _ => return None,
+    };
+    Some(action)
+}
+
+fn as_struct(array: &dyn Array) -> Option<&StructArray> {
+    array.as_any().downcast_ref::<StructArray>()
+}
+
+fn child<'a>(array: &'a StructArray, name: &str) -> Option<&'a dyn Array> {
+    array
+        .fields()
+        .iter()
+        .position(|f| f.name == name)
+        .map(|idx| array.values()[idx].as_ref())
+}
+
+fn str_value(array: &dyn Array, row: usize) -> Option<String> {
+    if !array.is_valid(row) {
+        return None;
+    }
+    let array = array.as_any();
+    if let Some(array) = array.downcast_ref::<Utf8Array<i32>>() {
+        return Some(array.value(row).to_string());
+    }
+    array
+        .downcast_ref::<Utf8Array<i64>>()
+        .map(|array| array.value(row).to_string())
+}
+
+fn get_str(array: &StructArray, name: &str, row: usize) -> Option<String> {
+    str_value(child(array, name)?, row)
+}
+
+fn get_i64(array: &StructArray, name: &str, row: usize) -> Option<i64> {
+    let array = child(array, name)?;
+    if !array.is_valid(row) {
+        return None;
+    }
+    let array = array.as_any();
+    if let Some(array) = array.downcast_ref::<PrimitiveArray<i64>>() {
+        return Some(array.value(row));
+    }
+    array
+        .downcast_ref::<PrimitiveArray<i32>>()
+        .map(|array| array.value(row) as i64)
+}
+
+/// The values of a list, or the entries of a map, in the given row.
+/// Maps may also be read as lists of key-value structs.
+fn nested_values(array: &dyn Array, row: usize) -> Option<Box<dyn Array>> {
+    if !array.is_valid(row) {
+        return None;
+    }
+    let array = array.as_any();
+    if let Some(array) = array.downcast_ref::<MapArray>() {
+        return Some(array.value(row));
+    }
+    array
+        .downcast_ref::<ListArray<i32>>()
+        .map(|array| array.value(row))
+}
+
+fn string_list(array: &dyn Array, row: usize) -> Vec<String> {
+    match nested_values(array, row) {
+        Some(values) => (0..values.len())
+            .filter_map(|idx| str_value(values.as_ref(), idx))
+            .collect(),
+        None => vec![],
+    }
+}
+
+fn string_map(array: &dyn Array, row: usize) -> HashMap<String, Option<String>> {
+    let mut map = HashMap::new();
+    let entries = match nested_values(array, row) {
+        Some(entries) => entries,
+        None => return map,
+    };
+    if let Some(entries) = as_struct(entries.as_ref()) {
+        if let [keys, values] = entries.values() {
+            for idx in 0..entries.len() {
+                if let Some(key) = str_value(keys.as_ref(), idx) {
+                    map.insert(key, str_value(values.as_ref(), idx));
+                }
+            }
+        }
+    }
+    map
+}
@@ -0,0 +1,283 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Replay the transaction log in `_delta_log/` to derive the state of the table,
+//! see <https://github.com/delta-io/delta/blob/master/PROTOCOL.md#delta-log-entries>.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::io::ErrorKind;
+
+use chrono::DateTime;
+use chrono::Utc;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::TableSchema;
+use futures::TryStreamExt;
+use opendal::Operator;
+
+use crate::action::parse_commit;
+use crate::action::Action;
+use crate::action::Add;
+use crate::action::Metadata;
+use crate::action::Protocol;
+use crate::action::MAX_READER_VERSION;
+use crate::checkpoint::parse_checkpoint;
+use crate::schema::StructType;
+
+const LOG_DIR: &str = "_delta_log/";
+
+fn commit_path(version: u64) -> String {
+    format!("{}{:020}.json", LOG_DIR, version)
+}
+
+/// The state of the table at a version.
+pub struct DeltaSnapshot {
+    pub version: u64,
+    pub metadata: Metadata,
+    /// The active data files, by their paths.
+    pub files: BTreeMap<String, Add>,
+}
+
+impl DeltaSnapshot {
+    pub fn table_schema(&self) -> Result<TableSchema> {
+        StructType::parse(&self.metadata.schema_string)?.to_table_schema()
+    }
+
+    /// The partition columns, lowercased as the columns of the table schema.
+    pub fn partition_columns(&self) -> Vec<String> {
+        self.metadata
+            .partition_columns
+            .iter()
+            .map(|c| c.to_lowercase())
+            .collect()
+    }
+}
+
+/// The files in the log directory.
+#[derive(Default)]
+struct LogFiles {
+    commits: BTreeSet<u64>,
+    /// The paths of the complete checkpoints, which may be split into multiple parts.
+    checkpoints: BTreeMap<u64, Vec<String>>,
+}
+
+impl LogFiles {
+    fn latest_version(&self) -> Option<u64> {
+        let commit = self.commits.iter().next_back();
+        let checkpoint = self.checkpoints.keys().next_back();
+        commit.max(checkpoint).copied()
+    }
+}
+
+/// The replayed actions, only the latest protocol and metadata take effect.
+#[derive(Default)]
+struct LogReplay {
+    protocol: Option<Protocol>,
+    metadata: Option<Metadata>,
+    files: BTreeMap<String, Add>,
+}
+
+impl LogReplay {
+    fn apply(&mut self, actions: Vec<Action>) {
+        for action in actions {
+            match action {
+                Action::Protocol(protocol) => self.protocol = Some(protocol),
+                Action::Metadata(metadata) => self.metadata = Some(metadata),
+                Action::Add(add) => {
+                    self.files.insert(add.path.clone(), add);
+                }
+                Action::Remove(remove) => {
+                    self.files.remove(&remove.path);
+                }
+                Action::CommitInfo(_) => {}
+            }
+        }
+    }
+
+    fn finish(self, version: u64) -> Result<DeltaSnapshot> {
+        let missing = || {
+            ErrorCode::BadBytes(format!(
+                "delta log at version {} has no protocol or metadata",
+                version
+            ))
+        };
+        let protocol = self.protocol.ok_or_else(missing)?;
+        if protocol.min_reader_version > MAX_READER_VERSION {
+            return Err(ErrorCode::Unimplemented(format!(
+                "delta reader version {} is not supported yet",
+                protocol.min_reader_version
+            )));
+        }
+        let metadata = self.metadata.ok_or_else(missing)?;
+        if !metadata.format.provider.eq_ignore_ascii_case("parquet") {
+            return Err(ErrorCode::Unimplemented(format!(
+                "delta data file format {} is not supported yet",
+                metadata.format.provider
+            )));
+        }
+        Ok(DeltaSnapshot {
+            version,
+            metadata,
+            files: self.files,
+        })
+    }
+}
+
+/// The transaction log of a table, the root of the operator is the directory of the table.
+pub struct DeltaLog {
+    operator: Operator,
+}
+
+impl DeltaLog {
+    pub fn new(operator: Operator) -> Self {
+        DeltaLog { operator }
+    }
+
+    /// Derive the state of the table at the given version, or the latest version if it's `None`.
+    ///
+    /// The replay starts from the latest checkpoint no later than the version,
+    /// then applies the following commits one by one.
+    pub async fn snapshot(&self, version: Option<u64>) -> Result<DeltaSnapshot> {
+        let log_files = self.list_files().await?;
+        let latest = log_files.latest_version().ok_or_else(|| {
+            ErrorCode::UnknownTable("no delta log found, the location is not a delta table")
+        })?;
+        let version = match version {
+            Some(v) if v > latest => {
+                return Err(ErrorCode::TableHistoricalDataNotFound(format!(
+                    "No historical data found at given point: version {}, the latest version is {}",
+                    v, latest
+                )));
+            }
+            Some(v) => v,
+            None => latest,
+        };
+
+        let mut replay = LogReplay::default();
+        let start = match log_files.checkpoints.range(..=version).next_back() {
+            Some((checkpoint, parts)) => {
+                for path in parts {
+                    let data = self.operator.object(path).read().await?;
+                    replay.apply(parse_checkpoint(path, data)?);
+                }
+                checkpoint + 1
+            }
+            None => 0,
+        };
+        for v in start..=version {
+            if !log_files.commits.contains(&v) {
+                return Err(ErrorCode::TableHistoricalDataNotFound(format!(
+                    "delta commit of version {} is missing, it may have been cleaned up",
+                    v
+                )));
+            }
+            let path = commit_path(v);
+            let data = self.operator.object(&path).read().await?;
+            replay.apply(parse_commit(&path, &data)?);
+        }
+        replay.finish(version)
+    }
+
+    /// Find the latest version committed no later than the time point,
+    /// by the timestamps in the `commitInfo` of the commits, which are increasing.
+    pub async fn version_at(&self, time_point: &DateTime<Utc>) -> Result<u64> {
+        let millis = time_point.timestamp_millis();
+        let commits = self
+            .list_files()
+            .await?
+            .commits
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        // Binary search the number of the commits no later than the time point.
+        let (mut low, mut high) = (0, commits.len());
+        while low < high {
+            let mid = (low + high) / 2;
+            if self.commit_timestamp(commits[mid]).await? <= millis {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        match low {
+            0 => Err(ErrorCode::TableHistoricalDataNotFound(format!(
+                "No historical data found at given point: {}",
+                time_point
+            ))),
+            n => Ok(commits[n - 1]),
+        }
+    }
+
+    async fn commit_timestamp(&self, version: u64) -> Result<i64> {
+        let path = commit_path(version);
+        let data = self.operator.object(&path).read().await?;
+        parse_commit(&path, &data)?
+            .into_iter()
+            .find_map(|action| match action {
+                Action::CommitInfo(info) => info.timestamp,
+                _ => None,
+            })
+            .ok_or_else(|| {
+                ErrorCode::Unimplemented(format!(
+                    "delta commit {} has no timestamp, time travel by time is not supported",
+                    path
+                ))
+            })
+    }
+
+    /// The commits are named as `<version>.json` and the checkpoints as
+    /// `<version>.checkpoint.parquet` or `<version>.checkpoint.<part>.<parts>.parquet`,
+    /// where the versions are padded to 20 digits.
+    async fn list_files(&self) -> Result<LogFiles> {
+        let mut lister = match self.operator.object(LOG_DIR).list().await {
+            Ok(lister) => lister,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(LogFiles::default()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut log_files = LogFiles::default();
+        // Version to the number of parts and the paths of the parts found.
+        let mut checkpoint_parts: BTreeMap<u64, (usize, Vec<String>)> = BTreeMap::new();
+        while let Some(de) = lister.try_next().await? {
+            let (version, suffix) = match de.name().split_once('.') {
+                Some((v, suffix)) if v.len() == 20 && v.bytes().all(|b| b.is_ascii_digit()) => {
+                    (v.parse::<u64>()?, suffix)
+                }
+                _ => continue,
+            };
+            let path = format!("{}{}", LOG_DIR, de.name());
+            if suffix == "json" {
+                log_files.commits.insert(version);
+            } else if suffix == "checkpoint.parquet" {
+                checkpoint_parts.insert(version, (1, vec![path]));
+            } else if let Some(parts) = suffix
+                .strip_prefix("checkpoint.")
+                .and_then(|s| s.strip_suffix(".parquet"))
+                .and_then(|s| s.split_once('.'))
+                .and_then(|(_, parts)| parts.parse::<usize>().ok())
+            {
+                let entry = checkpoint_parts
+                    .entry(version)
+                    .or_insert_with(|| (parts, vec![]));
+                entry.1.push(path);
+            }
+        }
+        log_files.checkpoints = checkpoint_parts
+            .into_iter()
+            .filter(|(_, (parts, paths))| *parts == paths.len())
+            .map(|(version, (_, paths))| (version, paths))
+            .collect();
+        Ok(log_files)
+    }
+}
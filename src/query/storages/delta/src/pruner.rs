@@ -0,0 +1,169 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_catalog::table_context::TableContext;
+use common_exception::Result;
+use common_expression::Expr;
+use common_expression::Scalar;
+use common_expression::TableDataType;
+use common_expression::TableSchemaRef;
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use storages_common_pruner::RangePruner;
+use storages_common_pruner::RangePrunerCreator;
+use storages_common_table_meta::meta::ColumnStatistics;
+use storages_common_table_meta::meta::StatisticsOfColumns;
+
+use crate::action::Add;
+use crate::value::parse_scalar;
+
+/// The length of the prefixes of strings kept in the statistics by default,
+/// a truncated maximum value is not an upper bound any more.
+const STRING_PREFIX_LENGTH: usize = 32;
+
+/// The statistics of a data file, see <https://github.com/delta-io/delta/blob/master/PROTOCOL.md#per-file-statistics>.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FileStatistics {
+    num_records: u64,
+    #[serde(default)]
+    min_values: HashMap<String, JsonValue>,
+    #[serde(default)]
+    max_values: HashMap<String, JsonValue>,
+    #[serde(default)]
+    null_count: HashMap<String, JsonValue>,
+}
+
+/// Prunes the data files by their partition values and the statistics
+/// recorded in the log, before reading any of them.
+pub struct DataFilePruner {
+    range_pruner: Arc<dyn RangePruner + Send + Sync>,
+    /// Column name to the column id and type of the top level columns.
+    columns: HashMap<String, (u32, TableDataType)>,
+    /// The column ids of the partition columns, in the order of the partition values.
+    partition_column_ids: Vec<u32>,
+}
+
+impl DataFilePruner {
+    pub fn try_create(
+        ctx: &Arc<dyn TableContext>,
+        filters: Option<&[Expr<String>]>,
+        table_schema: &TableSchemaRef,
+        partition_column_ids: Vec<u32>,
+    ) -> Result<Self> {
+        let range_pruner = RangePrunerCreator::try_create(ctx, filters, table_schema)?;
+        let columns = table_schema
+            .fields()
+            .iter()
+            .map(|f| (f.name().clone(), (f.column_id(), f.data_type().clone())))
+            .collect();
+        Ok(DataFilePruner {
+            range_pruner,
+            columns,
+            partition_column_ids,
+        })
+    }
+
+    pub fn should_keep(&self, file: &Add, partition_values: &[Scalar]) -> bool {
+        let stats = self.file_statistics(file, partition_values);
+        self.range_pruner.should_keep(&stats)
+    }
+
+    fn file_statistics(&self, file: &Add, partition_values: &[Scalar]) -> StatisticsOfColumns {
+        let mut stats = StatisticsOfColumns::new();
+        // Files without valid statistics are only pruned by the partition values.
+        let file_stats = file
+            .stats
+            .as_ref()
+            .and_then(|s| serde_json::from_str::<FileStatistics>(s).ok());
+        let num_records = file_stats.as_ref().map(|s| s.num_records).unwrap_or(1);
+
+        if let Some(file_stats) = &file_stats {
+            for (name, null_count) in file_stats.null_count.iter() {
+                // The statistics of nested columns are json objects, which are skipped.
+                let (column_id, ty) = match (self.columns.get(&name.to_lowercase()), null_count) {
+                    (Some(column), JsonValue::Number(_)) => column,
+                    _ => continue,
+                };
+                let null_count = match null_count.as_u64() {
+                    Some(n) => n,
+                    None => continue,
+                };
+                if ty.is_nullable() && null_count == num_records {
+                    stats.insert(*column_id, null_statistics(null_count));
+                    continue;
+                }
+                let min = file_stats
+                    .min_values
+                    .get(name)
+                    .and_then(|v| decode_stat(ty, v, false));
+                let max = file_stats
+                    .max_values
+                    .get(name)
+                    .and_then(|v| decode_stat(ty, v, true));
+                if let (Some(min), Some(max)) = (min, max) {
+                    stats.insert(*column_id, column_statistics(min, max, null_count));
+                }
+            }
+        }
+
+        // All the rows of a file share the same partition values.
+        for (column_id, value) in self.partition_column_ids.iter().zip(partition_values) {
+            let column_stats = match value {
+                Scalar::Null => null_statistics(num_records),
+                v => column_statistics(v.clone(), v.clone(), 0),
+            };
+            stats.insert(*column_id, column_stats);
+        }
+        stats
+    }
+}
+
+fn column_statistics(min: Scalar, max: Scalar, null_count: u64) -> ColumnStatistics {
+    ColumnStatistics {
+        min,
+        max,
+        null_count,
+        in_memory_size: 0,
+        distinct_of_values: None,
+    }
+}
+
+fn null_statistics(null_count: u64) -> ColumnStatistics {
+    column_statistics(Scalar::Null, Scalar::Null, null_count)
+}
+
+/// Decode a minimum or maximum value in the statistics, `None` if it's not a valid bound.
+fn decode_stat(ty: &TableDataType, value: &JsonValue, is_max: bool) -> Option<Scalar> {
+    let text = match (ty.remove_nullable(), value) {
+        (TableDataType::String, JsonValue::String(s)) => {
+            if is_max && s.chars().count() >= STRING_PREFIX_LENGTH {
+                return None;
+            }
+            s.clone()
+        }
+        // The decimals are parsed as floats in json, which may lose precision.
+        (TableDataType::Decimal(_), _) => return None,
+        (_, JsonValue::String(s)) => s.clone(),
+        (_, JsonValue::Number(n)) => n.to_string(),
+        _ => return None,
+    };
+    match parse_scalar(ty, &text)? {
+        // The timestamps are truncated to milliseconds.
+        Scalar::Timestamp(ts) if is_max => Some(Scalar::Timestamp(ts + 999)),
+        scalar => Some(scalar),
+    }
+}
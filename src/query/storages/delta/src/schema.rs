@@ -0,0 +1,163 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! The schema of a Delta table, serialized as the json of a spark struct type,
+//! see <https://github.com/delta-io/delta/blob/master/PROTOCOL.md#schema-serialization-format>.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::types::decimal::DecimalDataType;
+use common_expression::types::decimal::DecimalSize;
+use common_expression::types::NumberDataType;
+use common_expression::TableDataType;
+use common_expression::TableField;
+use common_expression::TableSchema;
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct StructType {
+    pub fields: Vec<StructField>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct StructField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub field_type: DeltaType,
+    pub nullable: bool,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ArrayType {
+    pub element_type: Box<DeltaType>,
+    pub contains_null: bool,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum DeltaType {
+    Primitive(String),
+    Struct(StructType),
+    Array(ArrayType),
+    /// Maps are only recognized to report that they are not supported.
+    Map {
+        #[serde(rename = "keyType")]
+        #[allow(dead_code)]
+        key_type: Box<DeltaType>,
+    },
+}
+
+impl StructType {
+    pub fn parse(schema_string: &str) -> Result<StructType> {
+        serde_json::from_str(schema_string)
+            .map_err(|e| ErrorCode::BadBytes(format!("invalid delta table schema: {}", e)))
+    }
+
+    /// The names of the columns are lowercased, as the unquoted identifiers in queries.
+    pub fn to_table_schema(&self) -> Result<TableSchema> {
+        let fields = self
+            .fields
+            .iter()
+            .map(|f| {
+                Ok(TableField::new(
+                    &f.name.to_lowercase(),
+                    f.to_table_data_type()?,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(TableSchema::new(fields))
+    }
+}
+
+impl StructField {
+    fn to_table_data_type(&self) -> Result<TableDataType> {
+        Ok(wrap_nullable(
+            self.field_type.to_table_data_type()?,
+            self.nullable,
+        ))
+    }
+}
+
+fn wrap_nullable(ty: TableDataType, nullable: bool) -> TableDataType {
+    if nullable {
+        TableDataType::Nullable(Box::new(ty))
+    } else {
+        ty
+    }
+}
+
+impl DeltaType {
+    pub fn to_table_data_type(&self) -> Result<TableDataType> {
+        match self {
+            DeltaType::Primitive(name) => primitive_to_table_data_type(name),
+            DeltaType::Struct(StructType { fields }) => {
+                let fields_name = fields.iter().map(|f| f.name.to_lowercase()).collect();
+                let fields_type = fields
+                    .iter()
+                    .map(|f| f.to_table_data_type())
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(TableDataType::Tuple {
+                    fields_name,
+                    fields_type,
+                })
+            }
+            DeltaType::Array(ArrayType {
+                element_type,
+                contains_null,
+            }) => {
+                let ty = wrap_nullable(element_type.to_table_data_type()?, *contains_null);
+                Ok(TableDataType::Array(Box::new(ty)))
+            }
+            DeltaType::Map { .. } => Err(ErrorCode::Unimplemented(
+                "delta type map is not supported yet",
+            )),
+        }
+    }
+}
+
+fn primitive_to_table_data_type(name: &str) -> Result<TableDataType> {
+    let ty = match name {
+        "boolean" => TableDataType::Boolean,
+        "byte" => TableDataType::Number(NumberDataType::Int8),
+        "short" => TableDataType::Number(NumberDataType::Int16),
+        "integer" => TableDataType::Number(NumberDataType::Int32),
+        "long" => TableDataType::Number(NumberDataType::Int64),
+        "float" => TableDataType::Number(NumberDataType::Float32),
+        "double" => TableDataType::Number(NumberDataType::Float64),
+        "date" => TableDataType::Date,
+        "timestamp" | "timestamp_ntz" => TableDataType::Timestamp,
+        "string" | "binary" => TableDataType::String,
+        _ => {
+            if let Some(args) = name
+                .strip_prefix("decimal(")
+                .and_then(|s| s.strip_suffix(')'))
+            {
+                let mut args = args.split(',').map(|s| s.trim().parse::<u8>());
+                if let (Some(Ok(precision)), Some(Ok(scale)), None) =
+                    (args.next(), args.next(), args.next())
+                {
+                    let size = DecimalSize { precision, scale };
+                    return DecimalDataType::from_size(size)
+                        .map(TableDataType::Decimal)
+                        .map_err(ErrorCode::BadBytes);
+                }
+            }
+            return Err(ErrorCode::Unimplemented(format!(
+                "delta type {} is not supported yet",
+                name
+            )));
+        }
+    };
+    Ok(ty)
+}
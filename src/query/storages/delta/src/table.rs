@@ -0,0 +1,492 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_arrow::arrow::datatypes::Schema as ArrowSchema;
+use common_arrow::arrow::io::parquet::read::read_metadata_async;
+use common_arrow::arrow::io::parquet::write::to_parquet_schema;
+use common_arrow::parquet::metadata::FileMetaData;
+use common_arrow::parquet::metadata::SchemaDescriptor;
+use common_catalog::catalog::StorageDescription;
+use common_catalog::plan::DataSourcePlan;
+use common_catalog::plan::PartInfoPtr;
+use common_catalog::plan::PartStatistics;
+use common_catalog::plan::Partitions;
+use common_catalog::plan::PartitionsShuffleKind;
+use common_catalog::plan::Projection;
+use common_catalog::plan::PushDownInfo;
+use common_catalog::table::NavigationPoint;
+use common_catalog::table::Table;
+use common_catalog::table_context::TableContext;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::DataField;
+use common_expression::DataSchema;
+use common_expression::Scalar;
+use common_expression::TableField;
+use common_expression::TableSchema;
+use common_expression::TableSchemaRef;
+use common_functions::scalars::BUILTIN_FUNCTIONS;
+use common_meta_app::schema::TableInfo;
+use common_pipeline_core::Pipeline;
+use common_storage::init_operator;
+use common_storage::StorageParams;
+use common_storages_parquet::ColumnMeta;
+use common_storages_parquet::ParquetReader;
+use common_storages_parquet::ParquetRowGroupPart;
+use common_storages_parquet::ParquetSource;
+use opendal::Operator;
+
+use crate::action::Add;
+use crate::log::DeltaLog;
+use crate::log::DeltaSnapshot;
+use crate::pruner::DataFilePruner;
+use crate::value::parse_scalar;
+
+pub const DELTA_ENGINE: &str = "DELTA";
+
+/// The partition columns of the table, separated by commas.
+pub const OPT_KEY_PARTITION_COLUMNS: &str = "partition_columns";
+
+/// The version to read, the latest version if absent.
+const OPT_KEY_VERSION: &str = "version";
+
+/// A read-only table over the directory of a Delta Lake table,
+/// the data files are read with the parquet reader.
+///
+/// The schema and the partition columns are kept when the table is created,
+/// the data files are derived by replaying the log when the table is read.
+pub struct DeltaTable {
+    table_info: TableInfo,
+    version: Option<u64>,
+    operator: Operator,
+}
+
+impl DeltaTable {
+    pub fn try_create(table_info: TableInfo) -> Result<Box<dyn Table>> {
+        let version =
+            match table_info.meta.engine_options.get(OPT_KEY_VERSION) {
+                Some(v) => Some(v.parse::<u64>().map_err(|_| {
+                    ErrorCode::Internal(format!("invalid delta table version {}", v))
+                })?),
+                None => None,
+            };
+        let operator = match &table_info.meta.storage_params {
+            Some(sp) => init_operator(sp)?,
+            None => {
+                return Err(ErrorCode::Internal(
+                    "Delta engine table missing storage params",
+                ));
+            }
+        };
+        Ok(Box::new(DeltaTable {
+            table_info,
+            version,
+            operator,
+        }))
+    }
+
+    pub fn description() -> StorageDescription {
+        StorageDescription {
+            engine_name: DELTA_ENGINE.to_string(),
+            comment: "DELTA Storage Engine".to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Read the schema and the partition columns of the latest version of the table,
+    /// the root of the storage is the directory of the table.
+    pub async fn read_schema(
+        storage_params: &StorageParams,
+    ) -> Result<(TableSchemaRef, Vec<String>)> {
+        let operator = init_operator(storage_params)?;
+        let snapshot = DeltaLog::new(operator).snapshot(None).await?;
+        Ok((
+            Arc::new(snapshot.table_schema()?),
+            snapshot.partition_columns(),
+        ))
+    }
+
+    fn partition_columns(&self) -> Vec<String> {
+        match self.table_info.meta.options.get(OPT_KEY_PARTITION_COLUMNS) {
+            Some(columns) if !columns.is_empty() => {
+                columns.split(',').map(|c| c.to_string()).collect()
+            }
+            _ => vec![],
+        }
+    }
+
+    /// The schema of the table changes along with the log,
+    /// but the columns of a table can't be changed after it's created.
+    fn check_schema(&self, snapshot: &DeltaSnapshot) -> Result<()> {
+        if snapshot.table_schema()?.fields() != self.table_info.schema().fields()
+            || snapshot.partition_columns() != self.partition_columns()
+        {
+            return Err(ErrorCode::TableSchemaMismatch(format!(
+                "the schema of delta table {} at version {} is different from the table, please recreate the table",
+                self.table_info.desc, snapshot.version
+            )));
+        }
+        Ok(())
+    }
+
+    /// The partition columns are not stored in the data files,
+    /// which are read with the schema of the other columns.
+    fn data_arrow_schema(&self) -> ArrowSchema {
+        let partition_columns = self.partition_columns();
+        let fields = self
+            .table_info
+            .schema()
+            .fields()
+            .iter()
+            .filter(|f| !partition_columns.contains(f.name()))
+            .cloned()
+            .collect();
+        TableSchema::new(fields).to_arrow()
+    }
+
+    fn partition_fields(&self, table_schema: &TableSchema) -> Vec<TableField> {
+        let partition_columns = self.partition_columns();
+        table_schema
+            .fields()
+            .iter()
+            .filter(|f| partition_columns.contains(f.name()))
+            .cloned()
+            .collect()
+    }
+
+    #[tracing::instrument(level = "info", skip_all, fields(table = %self.table_info.desc))]
+    async fn do_read_partitions(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        push_downs: Option<PushDownInfo>,
+    ) -> Result<(PartStatistics, Partitions)> {
+        let snapshot = DeltaLog::new(self.operator.clone())
+            .snapshot(self.version)
+            .await?;
+        self.check_schema(&snapshot)?;
+
+        let table_schema = self.table_info.schema();
+        let partition_fields = self.partition_fields(&table_schema);
+        let filters = push_downs.as_ref().map(|extra| {
+            extra
+                .filters
+                .iter()
+                .map(|f| f.as_expr(&BUILTIN_FUNCTIONS).unwrap())
+                .collect::<Vec<_>>()
+        });
+        let file_pruner = DataFilePruner::try_create(
+            &ctx,
+            filters.as_deref(),
+            &table_schema,
+            partition_fields.iter().map(|f| f.column_id()).collect(),
+        )?;
+
+        // Prune the data files by the partition values and the statistics in the log.
+        let mut data_files = vec![];
+        for file in snapshot.files.values() {
+            let partition_values = partition_values(file, &partition_fields)?;
+            if file_pruner.should_keep(file, &partition_values) {
+                data_files.push((file, partition_values));
+            }
+        }
+        tracing::info!(
+            "prune delta version {}: {} of {} data files are kept",
+            snapshot.version,
+            data_files.len(),
+            snapshot.files.len()
+        );
+
+        let data_arrow_schema = self.data_arrow_schema();
+        let data_descriptor = to_parquet_schema(&data_arrow_schema)?;
+        let projection = PushDownInfo::projection_of_push_downs(&table_schema, &push_downs);
+        let projection = data_projection(&table_schema, &partition_fields, &projection);
+        let (_, _, _, columns_to_read) =
+            ParquetReader::do_projection(&data_arrow_schema, &projection)?;
+
+        let mut read_rows = 0;
+        let mut read_bytes = 0;
+        let mut partitions: Vec<PartInfoPtr> = vec![];
+        for (file, partition_values) in data_files.iter() {
+            let location = data_file_location(&file.path)?;
+            let mut reader = self.operator.object(&location).reader().await?;
+            let file_meta = read_metadata_async(&mut reader).await?;
+            check_file_schema(&location, &file_meta, &data_descriptor)?;
+
+            for rg in file_meta.row_groups.iter() {
+                let mut column_metas = HashMap::with_capacity(columns_to_read.len());
+                for index in &columns_to_read {
+                    let c = &rg.columns()[*index];
+                    let (offset, length) = c.byte_range();
+                    column_metas.insert(*index, ColumnMeta {
+                        offset,
+                        length,
+                        compression: c.compression().into(),
+                    });
+                }
+                read_rows += rg.num_rows();
+                read_bytes += rg.total_byte_size();
+                partitions.push(ParquetRowGroupPart::create(
+                    location.clone(),
+                    rg.num_rows(),
+                    column_metas,
+                    partition_values.clone(),
+                ));
+            }
+        }
+
+        Ok((
+            PartStatistics::new_exact(
+                read_rows,
+                read_bytes,
+                data_files.len(),
+                snapshot.files.len(),
+            ),
+            Partitions::create(PartitionsShuffleKind::Mod, partitions),
+        ))
+    }
+
+    fn do_read_data(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        plan: &DataSourcePlan,
+        pipeline: &mut Pipeline,
+    ) -> Result<()> {
+        let table_schema = plan.source_info.schema();
+        let partition_fields = self.partition_fields(&table_schema);
+        let projection = PushDownInfo::projection_of_push_downs(&table_schema, &plan.push_downs);
+        let output_schema = Arc::new(DataSchema::from(&projection.project_schema(&table_schema)));
+        let reader = ParquetReader::create(
+            self.operator.clone(),
+            self.data_arrow_schema(),
+            data_projection(&table_schema, &partition_fields, &projection),
+        )?;
+        let partition_fields = partition_fields
+            .iter()
+            .map(DataField::from)
+            .collect::<Vec<_>>();
+
+        let max_threads = ctx.get_settings().get_max_threads()? as usize;
+        let max_io_requests = ctx.get_settings().get_max_storage_io_requests()? as usize;
+        let max_io_requests = std::cmp::max(max_threads, max_io_requests);
+        pipeline.add_source(
+            |output| {
+                ParquetSource::create(
+                    ctx.clone(),
+                    output,
+                    output_schema.clone(),
+                    reader.clone(),
+                    Arc::new(None),
+                    Arc::new(None),
+                    partition_fields.clone(),
+                )
+            },
+            max_io_requests,
+        )?;
+
+        pipeline.resize(std::cmp::min(max_threads, max_io_requests))
+    }
+}
+
+/// Map the projection on the table to the columns stored in the data files.
+fn data_projection(
+    table_schema: &TableSchema,
+    partition_fields: &[TableField],
+    projection: &Projection,
+) -> Projection {
+    let mut next = 0;
+    let data_indices = table_schema
+        .fields()
+        .iter()
+        .map(|f| {
+            if partition_fields.iter().any(|p| p.name() == f.name()) {
+                None
+            } else {
+                next += 1;
+                Some(next - 1)
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let projection = match projection {
+        Projection::Columns(indices) => Projection::Columns(
+            indices
+                .iter()
+                .filter_map(|idx| data_indices[*idx])
+                .collect(),
+        ),
+        Projection::InnerColumns(path_indices) => Projection::InnerColumns(
+            path_indices
+                .iter()
+                .filter_map(|(key, path)| {
+                    let mut path = path.clone();
+                    path[0] = data_indices[path[0]]?;
+                    Some((*key, path))
+                })
+                .collect(),
+        ),
+    };
+    // Read one column at least to know the number of rows,
+    // if only the partition columns are needed.
+    if projection.is_empty() {
+        Projection::Columns(vec![0])
+    } else {
+        projection
+    }
+}
+
+/// The values of the partition columns of a data file, an empty string stands for null.
+fn partition_values(file: &Add, partition_fields: &[TableField]) -> Result<Vec<Scalar>> {
+    let values = file
+        .partition_values
+        .iter()
+        .map(|(k, v)| (k.to_lowercase(), v))
+        .collect::<HashMap<_, _>>();
+    partition_fields
+        .iter()
+        .map(|field| {
+            let ty = field.data_type();
+            match values.get(field.name()) {
+                Some(Some(text)) if !text.is_empty() => parse_scalar(ty, text).ok_or_else(|| {
+                    ErrorCode::BadBytes(format!(
+                        "invalid value {} of partition column {} in delta data file {}",
+                        text,
+                        field.name(),
+                        file.path
+                    ))
+                }),
+                _ if ty.is_nullable() => Ok(Scalar::Null),
+                _ => Err(ErrorCode::BadBytes(format!(
+                    "missing value of partition column {} in delta data file {}",
+                    field.name(),
+                    file.path
+                ))),
+            }
+        })
+        .collect()
+}
+
+/// The paths of the data files are uri encoded and relative to the directory of the table.
+fn data_file_location(path: &str) -> Result<String> {
+    if path.contains("://") {
+        return Err(ErrorCode::Unimplemented(format!(
+            "delta data file with absolute path {} is not supported yet",
+            path
+        )));
+    }
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok());
+        match (bytes[i], hex.and_then(|h| u8::from_str_radix(h, 16).ok())) {
+            (b'%', Some(b)) => {
+                decoded.push(b);
+                i += 3;
+            }
+            (b, _) => {
+                decoded.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded)
+        .map_err(|_| ErrorCode::BadBytes(format!("invalid delta data file path {}", path)))
+}
+
+/// The data files are read with the schema of the table,
+/// make sure that the columns are stored in the same layout.
+fn check_file_schema(
+    location: &str,
+    file_meta: &FileMetaData,
+    expected: &SchemaDescriptor,
+) -> Result<()> {
+    let columns = file_meta.schema().columns();
+    let matched = columns.len() == expected.columns().len()
+        && columns.iter().zip(expected.columns()).all(|(c, e)| {
+            c.path_in_schema[0].to_lowercase() == e.path_in_schema[0]
+                && c.descriptor.primitive_type.physical_type
+                    == e.descriptor.primitive_type.physical_type
+        });
+    if !matched {
+        return Err(ErrorCode::Unimplemented(format!(
+            "delta data file {} is stored in a layout different from the table schema, which is not supported yet",
+            location
+        )));
+    }
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl Table for DeltaTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    fn benefit_column_prune(&self) -> bool {
+        true
+    }
+
+    async fn read_partitions(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        push_downs: Option<PushDownInfo>,
+    ) -> Result<(PartStatistics, Partitions)> {
+        self.do_read_partitions(ctx, push_downs).await
+    }
+
+    fn read_data(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        plan: &DataSourcePlan,
+        pipeline: &mut Pipeline,
+    ) -> Result<()> {
+        self.do_read_data(ctx, plan, pipeline)
+    }
+
+    /// The snapshot id is the version of the table.
+    async fn navigate_to(&self, point: &NavigationPoint) -> Result<Arc<dyn Table>> {
+        let log = DeltaLog::new(self.operator.clone());
+        let version = match point {
+            NavigationPoint::SnapshotID(id) => id.parse::<u64>().map_err(|_| {
+                ErrorCode::TableHistoricalDataNotFound(format!(
+                    "invalid delta table version {}",
+                    id
+                ))
+            })?,
+            NavigationPoint::TimePoint(time_point) => log.version_at(time_point).await?,
+        };
+        let snapshot = log.snapshot(Some(version)).await?;
+
+        let mut table_info = self.table_info.clone();
+        table_info.meta.schema = Arc::new(snapshot.table_schema()?);
+        table_info.meta.options.insert(
+            OPT_KEY_PARTITION_COLUMNS.to_string(),
+            snapshot.partition_columns().join(","),
+        );
+        table_info
+            .meta
+            .engine_options
+            .insert(OPT_KEY_VERSION.to_string(), version.to_string());
+        Ok(DeltaTable::try_create(table_info)?.into())
+    }
+}
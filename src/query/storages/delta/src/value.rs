@@ -0,0 +1,87 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use chrono::DateTime;
+use chrono::Datelike;
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use common_expression::types::decimal::DecimalDataType;
+use common_expression::types::decimal::DecimalScalar;
+use common_expression::types::number::F32;
+use common_expression::types::number::F64;
+use common_expression::types::NumberDataType;
+use common_expression::types::NumberScalar;
+use common_expression::Scalar;
+use common_expression::TableDataType;
+
+/// The days from 0001-01-01 to 1970-01-01.
+const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+/// Parse a value serialized as a string in the log, such as the values of the
+/// partition columns, see <https://github.com/delta-io/delta/blob/master/PROTOCOL.md#partition-value-serialization>.
+pub fn parse_scalar(ty: &TableDataType, text: &str) -> Option<Scalar> {
+    let scalar = match ty.remove_nullable() {
+        TableDataType::Boolean => Scalar::Boolean(text.parse().ok()?),
+        TableDataType::Number(NumberDataType::Int8) => {
+            Scalar::Number(NumberScalar::Int8(text.parse().ok()?))
+        }
+        TableDataType::Number(NumberDataType::Int16) => {
+            Scalar::Number(NumberScalar::Int16(text.parse().ok()?))
+        }
+        TableDataType::Number(NumberDataType::Int32) => {
+            Scalar::Number(NumberScalar::Int32(text.parse().ok()?))
+        }
+        TableDataType::Number(NumberDataType::Int64) => {
+            Scalar::Number(NumberScalar::Int64(text.parse().ok()?))
+        }
+        TableDataType::Number(NumberDataType::Float32) => {
+            Scalar::Number(NumberScalar::Float32(F32::from(text.parse::<f32>().ok()?)))
+        }
+        TableDataType::Number(NumberDataType::Float64) => {
+            Scalar::Number(NumberScalar::Float64(F64::from(text.parse::<f64>().ok()?)))
+        }
+        TableDataType::String => Scalar::String(text.as_bytes().to_vec()),
+        TableDataType::Date => Scalar::Date(parse_date(text)?),
+        TableDataType::Timestamp => Scalar::Timestamp(parse_timestamp(text)?),
+        TableDataType::Decimal(DecimalDataType::Decimal128(size)) => Scalar::Decimal(
+            DecimalScalar::Decimal128(parse_decimal(text, size.scale)?, size),
+        ),
+        _ => return None,
+    };
+    Some(scalar)
+}
+
+fn parse_date(text: &str) -> Option<i32> {
+    let date = NaiveDate::parse_from_str(text, "%Y-%m-%d").ok()?;
+    Some(date.num_days_from_ce() - UNIX_EPOCH_DAYS_FROM_CE)
+}
+
+/// Timestamps are in microseconds, serialized in ISO 8601 in the statistics,
+/// or as `yyyy-MM-dd HH:mm:ss[.SSSSSS]` in UTC in the partition values.
+fn parse_timestamp(text: &str) -> Option<i64> {
+    if let Ok(t) = DateTime::parse_from_rfc3339(text) {
+        return Some(t.timestamp_micros());
+    }
+    let t = NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f").ok()?;
+    Some(t.timestamp_micros())
+}
+
+/// Parse a decimal in plain notation into its unscaled value.
+fn parse_decimal(text: &str, scale: u8) -> Option<i128> {
+    let (int_part, frac_part) = text.split_once('.').unwrap_or((text, ""));
+    if frac_part.len() > scale as usize || !frac_part.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let digits = format!("{}{:0<width$}", int_part, frac_part, width = scale as usize);
+    digits.parse::<i128>().ok()
+}
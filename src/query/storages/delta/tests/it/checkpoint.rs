@@ -0,0 +1,70 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use common_storages_delta::action::Action;
+use common_storages_delta::checkpoint::parse_checkpoint;
+
+use crate::TABLE_DIR;
+
+const CHECKPOINT: &str = "_delta_log/00000000000000000001.checkpoint.parquet";
+
+#[test]
+fn test_parse_checkpoint() -> Result<()> {
+    let data = std::fs::read(format!("{}/{}", TABLE_DIR, CHECKPOINT)).unwrap();
+    let actions = parse_checkpoint(CHECKPOINT, data)?;
+    assert_eq!(actions.len(), 4);
+
+    match &actions[0] {
+        Action::Protocol(protocol) => assert_eq!(protocol.min_reader_version, 1),
+        other => panic!("unexpected action {:?}", other),
+    }
+    match &actions[1] {
+        Action::Metadata(metadata) => {
+            assert_eq!(metadata.format.provider, "parquet");
+            assert_eq!(metadata.partition_columns, vec!["region".to_string()]);
+            assert!(metadata.schema_string.contains("\"amount\""));
+        }
+        other => panic!("unexpected action {:?}", other),
+    }
+
+    let adds = actions
+        .iter()
+        .filter_map(|action| match action {
+            Action::Add(add) => Some(add),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(adds.len(), 2);
+    assert_eq!(adds[0].path, "region=asia/part-00000.parquet");
+    assert_eq!(
+        adds[0].partition_values.get("region"),
+        Some(&Some("asia".to_string()))
+    );
+    assert_eq!(adds[1].path, "region=europe/part-00001.parquet");
+    assert_eq!(
+        adds[1].partition_values.get("region"),
+        Some(&Some("europe".to_string()))
+    );
+    assert!(adds.iter().all(|add| add.size > 0));
+    assert!(adds[1].stats.as_ref().unwrap().contains("\"numRecords\":2"));
+    Ok(())
+}
+
+#[test]
+fn test_parse_invalid_checkpoint() -> Result<()> {
+    let err = parse_checkpoint(CHECKPOINT, b"not a parquet file".to_vec()).unwrap_err();
+    assert!(err.message().contains("invalid delta checkpoint"));
+    Ok(())
+}
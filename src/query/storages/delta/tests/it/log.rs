@@ -0,0 +1,114 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::TimeZone;
+use chrono::Utc;
+use common_base::base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_storages_delta::log::DeltaLog;
+use common_storages_delta::log::DeltaSnapshot;
+
+use crate::fixture_operator;
+
+fn paths(snapshot: &DeltaSnapshot) -> Vec<&str> {
+    snapshot.files.keys().map(|k| k.as_str()).collect()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_replay_latest() -> Result<()> {
+    let log = DeltaLog::new(fixture_operator()?);
+    let snapshot = log.snapshot(None).await?;
+    assert_eq!(snapshot.version, 3);
+    // The file removed by version 2 is not active any more.
+    assert_eq!(paths(&snapshot), vec![
+        "region=__HIVE_DEFAULT_PARTITION__/part-00003.parquet",
+        "region=asia/part-00002.parquet",
+        "region=europe/part-00001.parquet",
+    ]);
+    assert_eq!(snapshot.partition_columns(), vec!["region".to_string()]);
+
+    let schema = snapshot.table_schema()?;
+    let names = schema
+        .fields()
+        .iter()
+        .map(|f| f.name().as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["id", "name", "amount", "region"]);
+
+    let file = &snapshot.files["region=__HIVE_DEFAULT_PARTITION__/part-00003.parquet"];
+    assert_eq!(file.partition_values.get("region"), Some(&None));
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_replay_from_checkpoint() -> Result<()> {
+    let log = DeltaLog::new(fixture_operator()?);
+
+    // The commit of version 0 is cleaned up, version 1 is only readable from the checkpoint.
+    let snapshot = log.snapshot(Some(1)).await?;
+    assert_eq!(snapshot.version, 1);
+    assert_eq!(paths(&snapshot), vec![
+        "region=asia/part-00000.parquet",
+        "region=europe/part-00001.parquet",
+    ]);
+
+    // The commits after the checkpoint are applied on it.
+    let snapshot = log.snapshot(Some(2)).await?;
+    assert_eq!(paths(&snapshot), vec![
+        "region=asia/part-00002.parquet",
+        "region=europe/part-00001.parquet",
+    ]);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_replay_unavailable_versions() -> Result<()> {
+    let log = DeltaLog::new(fixture_operator()?);
+
+    let err = log.snapshot(Some(0)).await.err().unwrap();
+    assert_eq!(err.code(), ErrorCode::TABLE_HISTORICAL_DATA_NOT_FOUND);
+    assert!(err.message().contains("version 0 is missing"));
+
+    let err = log.snapshot(Some(4)).await.err().unwrap();
+    assert_eq!(err.code(), ErrorCode::TABLE_HISTORICAL_DATA_NOT_FOUND);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_version_at() -> Result<()> {
+    let log = DeltaLog::new(fixture_operator()?);
+
+    // The commits 1, 2 and 3 are committed at 2023-02-01, 2023-03-01 and 2023-04-01.
+    let cases = [
+        // 2023-02-01, the time of version 1 itself
+        (1675209600000, 1),
+        // 2023-02-15
+        (1676419200000, 1),
+        // 2023-03-15
+        (1678838400000, 2),
+        // 2024-01-01
+        (1704067200000, 3),
+    ];
+    for (millis, version) in cases {
+        let time_point = Utc.timestamp_millis_opt(millis).unwrap();
+        assert_eq!(log.version_at(&time_point).await?, version);
+    }
+
+    // 2023-01-15, the only commit before it is cleaned up
+    let time_point = Utc.timestamp_millis_opt(1673740800000).unwrap();
+    let err = log.version_at(&time_point).await.err().unwrap();
+    assert_eq!(err.code(), ErrorCode::TABLE_HISTORICAL_DATA_NOT_FOUND);
+    Ok(())
+}
@@ -0,0 +1,41 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use common_storage::init_operator;
+use common_storage::StorageFsConfig;
+use common_storage::StorageParams;
+use opendal::Operator;
+
+mod checkpoint;
+mod log;
+
+/// The delta table under `tests/data`, partitioned by `region`:
+///
+/// - version 0: add `asia/part-00000`, the commit is cleaned up.
+/// - version 1: add `europe/part-00001`, with a checkpoint.
+/// - version 2: replace `asia/part-00000` with `asia/part-00002`.
+/// - version 3: add `__HIVE_DEFAULT_PARTITION__/part-00003`, of which `region` is null.
+pub const TABLE_DIR: &str = "../../../../tests/data/delta/sales";
+
+pub fn fixture_storage_params() -> StorageParams {
+    let root = std::fs::canonicalize(TABLE_DIR).unwrap();
+    StorageParams::Fs(StorageFsConfig {
+        root: root.to_str().unwrap().to_string(),
+    })
+}
+
+pub fn fixture_operator() -> Result<Operator> {
+    init_operator(&fixture_storage_params())
+}
@@ -19,12 +19,15 @@ use common_config::Config;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_app::schema::TableInfo;
+use common_storages_delta::DeltaTable;
 use common_storages_memory::MemoryTable;
 use common_storages_null::NullTable;
 use common_storages_random::RandomTable;
 use common_storages_view::view_table::ViewTable;
 use dashmap::DashMap;
 
+use crate::fuse::stream::StreamTable;
+use crate::fuse::stream::STREAM_ENGINE;
 use crate::fuse::FuseTable;
 use crate::Table;
 
@@ -90,6 +93,12 @@ impl StorageFactory {
             descriptor: Arc::new(FuseTable::description),
         });
 
+        // Register STREAM table engine.
+        creators.insert(STREAM_ENGINE.to_string(), Storage {
+            creator: Arc::new(StreamTable::try_create),
+            descriptor: Arc::new(StreamTable::description),
+        });
+
         // Register View table engine
         creators.insert("VIEW".to_string(), Storage {
             creator: Arc::new(ViewTable::try_create),
@@ -102,6 +111,12 @@ impl StorageFactory {
             descriptor: Arc::new(RandomTable::description),
         });
 
+        // Register DELTA table engine
+        creators.insert("DELTA".to_string(), Storage {
+            creator: Arc::new(DeltaTable::try_create),
+            descriptor: Arc::new(DeltaTable::description),
+        });
+
         StorageFactory { storages: creators }
     }
 
@@ -36,9 +36,12 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_expression::BlockCompactThresholds;
 use common_expression::DataBlock;
+use common_expression::Expr;
 // use common_sql::ExpressionParser;
 use common_expression::RemoteExpr;
+use common_expression::TableField;
 use common_meta_app::schema::DatabaseType;
+use common_meta_app::schema::SkippingIndexMeta;
 use common_meta_app::schema::TableInfo;
 use common_sharing::create_share_table_operator;
 use common_sql::parse_exprs;
@@ -51,6 +54,7 @@ use common_storage::StorageMetrics;
 use common_storage::StorageMetricsLayer;
 use opendal::layers::CacheLayer;
 use opendal::Operator;
+use storages_common_index::SkippingIndex;
 use storages_common_table_meta::caches::LoadParams;
 use storages_common_table_meta::meta::ClusterKey;
 use storages_common_table_meta::meta::ColumnStatistics as FuseColumnStatistics;
@@ -91,6 +95,10 @@ pub struct FuseTable {
 
     pub(crate) operator: Operator,
     pub(crate) data_metrics: Arc<StorageMetrics>,
+
+    /// If set, only the segments appended by these operation logs are visible,
+    /// see [FuseTable::appended_table].
+    pub(crate) appended_entries: Option<Arc<Vec<AppendOperationLogEntry>>>,
 }
 
 impl FuseTable {
@@ -152,6 +160,7 @@ impl FuseTable {
             data_metrics,
             storage_format: FuseStorageFormat::from_str(storage_format.as_str())?,
             table_compression: table_compression.as_str().try_into()?,
+            appended_entries: None,
         }))
     }
 
@@ -279,6 +288,41 @@ impl FuseTable {
     pub fn transient(&self) -> bool {
         self.table_info.meta.options.contains_key("TRANSIENT")
     }
+
+    /// Parse the skipping indexes defined on the table.
+    pub fn skipping_indexes(&self, ctx: Arc<dyn TableContext>) -> Result<Vec<SkippingIndex>> {
+        let indexes = &self.table_info.meta.skipping_indexes;
+        if indexes.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let table_meta = Arc::new(self.clone());
+        indexes
+            .iter()
+            .map(|(name, meta)| {
+                let expr =
+                    self.parse_skipping_index_expr(ctx.clone(), table_meta.clone(), &meta.expr)?;
+                SkippingIndex::create(name, meta, expr)
+            })
+            .collect()
+    }
+
+    pub(crate) fn parse_skipping_index_expr(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        table_meta: Arc<dyn Table>,
+        sql: &str,
+    ) -> Result<Expr<String>> {
+        let mut exprs = parse_exprs(ctx, table_meta.clone(), false, sql)?;
+        if exprs.len() != 1 {
+            return Err(ErrorCode::UnsupportedIndex(format!(
+                "skipping index must be defined on exactly one expression, got '{}'",
+                sql
+            )));
+        }
+        let expr = exprs.remove(0);
+        Ok(expr.project_column_ref(|index| table_meta.schema().field(*index).name().to_string()))
+    }
 }
 
 #[async_trait::async_trait]
@@ -422,6 +466,48 @@ impl Table for FuseTable {
         .await
     }
 
+    async fn add_column(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        field: &TableField,
+        comment: &str,
+    ) -> Result<()> {
+        self.do_add_column(ctx, field, comment).await
+    }
+
+    async fn drop_column(&self, ctx: Arc<dyn TableContext>, column: &str) -> Result<()> {
+        self.do_drop_column(ctx, column).await
+    }
+
+    async fn rename_column(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        old_column: &str,
+        new_column: &str,
+    ) -> Result<()> {
+        self.do_rename_column(ctx, old_column, new_column).await
+    }
+
+    async fn create_skipping_index(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        name: &str,
+        meta: SkippingIndexMeta,
+        if_not_exists: bool,
+    ) -> Result<()> {
+        self.do_create_skipping_index(ctx, name, meta, if_not_exists)
+            .await
+    }
+
+    async fn drop_skipping_index(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        name: &str,
+        if_exists: bool,
+    ) -> Result<()> {
+        self.do_drop_skipping_index(ctx, name, if_exists).await
+    }
+
     #[tracing::instrument(level = "debug", name = "fuse_table_read_partitions", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
     async fn read_partitions(
         &self,
@@ -482,6 +568,16 @@ impl Table for FuseTable {
     }
 
     fn table_statistics(&self) -> Result<Option<TableStatistics>> {
+        if let Some(entries) = &self.appended_entries {
+            let s = Self::appended_summary(entries)?;
+            return Ok(Some(TableStatistics {
+                num_rows: Some(s.row_count),
+                data_size: Some(s.uncompressed_byte_size),
+                data_size_compressed: Some(s.compressed_byte_size),
+                index_size: Some(s.index_size),
+            }));
+        }
+
         let s = &self.table_info.meta.statistics;
         Ok(Some(TableStatistics {
             num_rows: Some(s.number_of_rows),
@@ -492,6 +588,15 @@ impl Table for FuseTable {
     }
 
     async fn column_statistics_provider(&self) -> Result<Box<dyn ColumnStatisticsProvider>> {
+        if let Some(entries) = &self.appended_entries {
+            let s = Self::appended_summary(entries)?;
+            return Ok(Box::new(FuseTableColumnStatisticsProvider {
+                column_stats: s.col_stats,
+                row_count: s.row_count,
+                column_distinct_values: None,
+            }));
+        }
+
         let provider = if let Some(snapshot) = self.read_table_snapshot().await? {
             let stats = &snapshot.summary.col_stats;
             let table_statistics = self.read_table_snapshot_statistics(Some(&snapshot)).await?;
@@ -537,6 +642,18 @@ impl Table for FuseTable {
         self.do_delete(ctx, filter, col_indices, pipeline).await
     }
 
+    async fn update(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        filter: Option<RemoteExpr<String>>,
+        col_indices: Vec<usize>,
+        update_list: Vec<(usize, RemoteExpr<String>)>,
+        pipeline: &mut Pipeline,
+    ) -> Result<()> {
+        self.do_update(ctx, filter, col_indices, update_list, pipeline)
+            .await
+    }
+
     fn get_block_compact_thresholds(&self) -> BlockCompactThresholds {
         let max_rows_per_block = self.get_option(FUSE_OPT_KEY_ROW_PER_BLOCK, DEFAULT_ROW_PER_BLOCK);
         let min_rows_per_block = (max_rows_per_block as f64 * 0.8) as usize;
@@ -17,6 +17,7 @@ use std::marker::PhantomData;
 use common_exception::Result;
 use common_expression::DataBlock;
 use storages_common_table_meta::meta::BlockFilter;
+use storages_common_table_meta::meta::BlockSkippingIndex;
 use storages_common_table_meta::meta::Location;
 use storages_common_table_meta::meta::SegmentInfo;
 use storages_common_table_meta::meta::SnapshotVersion;
@@ -29,6 +30,7 @@ use crate::constants::FUSE_TBL_SEGMENT_PREFIX;
 use crate::constants::FUSE_TBL_SNAPSHOT_PREFIX;
 use crate::constants::FUSE_TBL_SNAPSHOT_STATISTICS_PREFIX;
 use crate::FUSE_TBL_LAST_SNAPSHOT_HINT;
+use crate::FUSE_TBL_SKIPPING_INDEX_PREFIX;
 use crate::FUSE_TBL_XOR_BLOOM_INDEX_PREFIX;
 
 static SNAPSHOT_V0: SnapshotVersion = SnapshotVersion::V0(PhantomData);
@@ -91,6 +93,19 @@ impl TableMetaLocationGenerator {
         )
     }
 
+    pub fn block_skipping_index_location(&self, block_id: &Uuid) -> Location {
+        (
+            format!(
+                "{}/{}/{}_v{}.json",
+                &self.prefix,
+                FUSE_TBL_SKIPPING_INDEX_PREFIX,
+                block_id.as_simple(),
+                BlockSkippingIndex::VERSION,
+            ),
+            BlockSkippingIndex::VERSION,
+        )
+    }
+
     pub fn gen_segment_info_location(&self) -> String {
         let segment_uuid = Uuid::new_v4().simple().to_string();
         format!(
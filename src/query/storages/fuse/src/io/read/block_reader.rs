@@ -28,6 +28,7 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_expression::DataField;
 use common_expression::DataSchema;
+use common_expression::Scalar;
 use common_expression::TableSchemaRef;
 use common_storage::ColumnLeaf;
 use common_storage::ColumnLeaves;
@@ -46,8 +47,14 @@ pub struct BlockReader {
     pub(crate) operator: Operator,
     pub(crate) projection: Projection,
     pub(crate) projected_schema: TableSchemaRef,
+    // The `leaf_ids` of the column leaves are the column ids of the leaf columns.
     pub(crate) column_leaves: ColumnLeaves,
     pub(crate) parquet_schema_descriptor: SchemaDescriptor,
+    // Column id of the leaf column -> index of the leaf column in `parquet_schema_descriptor`.
+    pub(crate) leaf_column_positions: HashMap<usize, usize>,
+    // Default values of the projected columns, used to fill the columns which
+    // are added after the block was written.
+    pub(crate) default_vals: Vec<Scalar>,
 }
 
 pub struct OwnerMemory {
@@ -111,21 +118,44 @@ where Self: 'static
 }
 
 impl BlockReader {
+    /// Create a block reader, `field_default_vals` are the default values of the fields of `schema`.
     pub fn create(
         operator: Operator,
         schema: TableSchemaRef,
         projection: Projection,
+        field_default_vals: Vec<Scalar>,
     ) -> Result<Arc<BlockReader>> {
-        let projected_schema = match projection {
-            Projection::Columns(ref indices) => TableSchemaRef::new(schema.project(indices)),
-            Projection::InnerColumns(ref path_indices) => {
-                Arc::new(schema.inner_project(path_indices))
-            }
+        let (projected_schema, default_vals) = match projection {
+            Projection::Columns(ref indices) => (
+                TableSchemaRef::new(schema.project(indices)),
+                indices
+                    .iter()
+                    .map(|index| field_default_vals[*index].clone())
+                    .collect(),
+            ),
+            Projection::InnerColumns(ref path_indices) => (
+                Arc::new(schema.inner_project(path_indices)),
+                path_indices
+                    .values()
+                    .map(|path| Self::inner_default_value(&field_default_vals[path[0]], &path[1..]))
+                    .collect(),
+            ),
         };
 
         let arrow_schema = schema.to_arrow();
         let parquet_schema_descriptor = to_parquet_schema(&arrow_schema)?;
-        let column_leaves = ColumnLeaves::new_from_schema(&arrow_schema);
+        let leaf_column_ids = schema
+            .to_leaf_column_ids()
+            .into_iter()
+            .map(|column_id| column_id as usize)
+            .collect::<Vec<_>>();
+        let column_leaves =
+            ColumnLeaves::new_from_schema_with_leaf_ids(&arrow_schema, &leaf_column_ids);
+        let leaf_column_positions = leaf_column_ids
+            .into_iter()
+            .enumerate()
+            .map(|(position, column_id)| (column_id, position))
+            .collect();
 
         Ok(Arc::new(BlockReader {
             operator,
@@ -133,9 +163,32 @@ impl BlockReader {
             projected_schema,
             parquet_schema_descriptor,
             column_leaves,
+            leaf_column_positions,
+            default_vals,
         }))
     }
 
+    fn inner_default_value(default_val: &Scalar, path: &[usize]) -> Scalar {
+        match (default_val, path.split_first()) {
+            (Scalar::Tuple(vals), Some((index, path))) => {
+                Self::inner_default_value(&vals[*index], path)
+            }
+            _ => default_val.clone(),
+        }
+    }
+
+    /// Returns true if the block contains all the leaf columns of the column,
+    /// otherwise the column is added after the block was written.
+    pub(crate) fn block_contains_column<T>(
+        column: &ColumnLeaf,
+        columns_meta: &HashMap<usize, T>,
+    ) -> bool {
+        column
+            .leaf_ids
+            .iter()
+            .all(|leaf_id| columns_meta.contains_key(leaf_id))
+    }
+
     pub fn support_blocking_api(&self) -> bool {
         self.operator.metadata().can_blocking()
     }
@@ -282,7 +335,11 @@ impl BlockReader {
 
         let mut ranges = vec![];
         for index in indices.keys() {
-            let column_meta = &columns_meta[index];
+            // The columns added after the block was written are not in the block.
+            let column_meta = match columns_meta.get(index) {
+                None => continue,
+                Some(column_meta) => column_meta,
+            };
             ranges.push((
                 *index,
                 column_meta.offset..(column_meta.offset + column_meta.len),
@@ -311,7 +368,10 @@ impl BlockReader {
 
         let mut ranges = vec![];
         for index in indices.keys() {
-            let column_meta = &part.columns_meta[index];
+            let column_meta = match part.columns_meta.get(index) {
+                None => continue,
+                Some(column_meta) => column_meta,
+            };
             ranges.push((
                 *index,
                 column_meta.offset..(column_meta.offset + column_meta.len),
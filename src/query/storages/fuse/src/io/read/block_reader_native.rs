@@ -17,12 +17,15 @@ use std::io::BufReader;
 use std::time::Instant;
 
 use common_arrow::arrow::array::Array;
-use common_arrow::arrow::chunk::Chunk;
 use common_arrow::native::read::reader::PaReader;
 use common_arrow::native::read::PaReadBuf;
 use common_catalog::plan::PartInfoPtr;
 use common_exception::Result;
+use common_expression::BlockEntry;
+use common_expression::Column;
+use common_expression::ColumnBuilder;
 use common_expression::DataBlock;
+use common_expression::Value;
 use opendal::Object;
 
 use crate::fuse_part::FusePartInfo;
@@ -52,7 +55,11 @@ impl BlockReader {
         let mut join_handlers = Vec::with_capacity(indices.len());
 
         for (index, field) in indices {
-            let column_meta = &part.columns_meta[&index];
+            // The columns added after the block was written are not in the block.
+            let column_meta = match part.columns_meta.get(&index) {
+                None => continue,
+                Some(column_meta) => column_meta,
+            };
             join_handlers.push(Self::read_native_columns_data(
                 self.operator.object(&part.location),
                 index,
@@ -112,7 +119,10 @@ impl BlockReader {
         let mut results = Vec::with_capacity(indices.len());
 
         for (index, field) in indices {
-            let column_meta = &part.columns_meta[&index];
+            let column_meta = match part.columns_meta.get(&index) {
+                None => continue,
+                Some(column_meta) => column_meta,
+            };
 
             let op = self.operator.clone();
 
@@ -149,21 +159,33 @@ impl BlockReader {
         Ok((index, fuse_reader))
     }
 
-    pub fn build_block(&self, chunks: Vec<(usize, Box<dyn Array>)>) -> Result<DataBlock> {
-        let mut results = Vec::with_capacity(chunks.len());
+    /// Build a block from the arrays of the leaf columns, the columns added after the
+    /// block was written have no arrays and are filled with the default values.
+    pub fn build_block(
+        &self,
+        chunks: Vec<(usize, Box<dyn Array>)>,
+        num_rows: usize,
+    ) -> Result<DataBlock> {
         let mut chunk_map: HashMap<usize, Box<dyn Array>> = chunks.into_iter().collect();
         let columns = self.projection.project_column_leaves(&self.column_leaves)?;
-        for column in &columns {
-            let indices = &column.leaf_ids;
-
-            for index in indices {
-                if let Some(array) = chunk_map.remove(index) {
-                    results.push(array);
-                    break;
-                }
-            }
+        let data_fields = self.data_fields();
+        let mut entries = Vec::with_capacity(columns.len());
+        for (i, column) in columns.iter().enumerate() {
+            let data_type = data_fields[i].data_type().clone();
+            let array = column
+                .leaf_ids
+                .iter()
+                .find_map(|index| chunk_map.remove(index));
+            let column = match array {
+                Some(array) => Column::from_arrow(array.as_ref(), &data_type),
+                None => ColumnBuilder::repeat(&self.default_vals[i].as_ref(), num_rows, &data_type)
+                    .build(),
+            };
+            entries.push(BlockEntry {
+                data_type,
+                value: Value::Column(column),
+            });
         }
-        let chunk = Chunk::new(results);
-        DataBlock::from_arrow_chunk(&chunk, &self.data_schema())
+        Ok(DataBlock::new(entries, num_rows))
     }
 }
@@ -16,7 +16,6 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Instant;
 
-use common_arrow::arrow::chunk::Chunk;
 use common_arrow::arrow::datatypes::Field;
 use common_arrow::arrow::io::parquet::read::column_iter_to_arrays;
 use common_arrow::arrow::io::parquet::read::ArrayIter;
@@ -27,7 +26,11 @@ use common_arrow::parquet::read::PageReader;
 use common_catalog::plan::PartInfoPtr;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_expression::BlockEntry;
+use common_expression::Column;
+use common_expression::ColumnBuilder;
 use common_expression::DataBlock;
+use common_expression::Value;
 use storages_common_table_meta::meta::BlockMeta;
 use storages_common_table_meta::meta::ColumnMeta;
 use storages_common_table_meta::meta::Compression;
@@ -126,6 +129,11 @@ impl BlockReader {
         let columns = self.projection.project_column_leaves(&self.column_leaves)?;
 
         for column in &columns {
+            if !Self::block_contains_column(column, columns_meta) {
+                columns_array_iter.push(None);
+                continue;
+            }
+
             let field = column.field.clone();
             let indices = &column.leaf_ids;
             let mut column_metas = Vec::with_capacity(indices.len());
@@ -134,13 +142,14 @@ impl BlockReader {
             for index in indices {
                 let column_read = <&[u8]>::clone(&chunk_map[index]);
                 let column_meta = &columns_meta[index];
-                let column_descriptor = &self.parquet_schema_descriptor.columns()[*index];
+                let column_descriptor =
+                    &self.parquet_schema_descriptor.columns()[self.leaf_column_positions[index]];
                 column_metas.push(column_meta);
                 column_chunks.push(column_read);
                 column_descriptors.push(column_descriptor);
             }
 
-            columns_array_iter.push(Self::chunks_to_parquet_array_iter(
+            columns_array_iter.push(Some(Self::chunks_to_parquet_array_iter(
                 column_metas,
                 column_chunks,
                 num_rows,
@@ -150,18 +159,30 @@ impl BlockReader {
                 uncompressed_buffer
                     .clone()
                     .unwrap_or_else(|| UncompressedBuffer::new(0)),
-            )?);
+            )?));
         }
 
-        let mut arrays = Vec::with_capacity(columns_array_iter.len());
-        for mut column_array_iter in columns_array_iter.into_iter() {
-            let array = column_array_iter.next().unwrap()?;
-            arrays.push(array);
-            drop(column_array_iter);
+        let data_fields = self.data_fields();
+        let mut entries = Vec::with_capacity(columns_array_iter.len());
+        for (i, column_array_iter) in columns_array_iter.into_iter().enumerate() {
+            let data_type = data_fields[i].data_type().clone();
+            let column = match column_array_iter {
+                Some(mut column_array_iter) => {
+                    let array = column_array_iter.next().unwrap()?;
+                    drop(column_array_iter);
+                    Column::from_arrow(array.as_ref(), &data_type)
+                }
+                // The column is added after the block was written, fill it with the default value.
+                None => ColumnBuilder::repeat(&self.default_vals[i].as_ref(), num_rows, &data_type)
+                    .build(),
+            };
+            entries.push(BlockEntry {
+                data_type,
+                value: Value::Column(column),
+            });
         }
 
-        let chunk = Chunk::try_new(arrays)?;
-        DataBlock::from_arrow_chunk(&chunk, &self.data_schema())
+        Ok(DataBlock::new(entries, num_rows))
     }
 
     fn chunks_to_parquet_array_iter<'a>(
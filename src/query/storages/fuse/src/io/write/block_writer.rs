@@ -41,7 +41,7 @@ pub fn write_block(
         FuseStorageFormat::Parquet => {
             let result =
                 blocks_to_parquet(schema, vec![block], buf, write_settings.table_compression)?;
-            let meta = util::column_metas(&result.1)?;
+            let meta = util::column_metas(&result.1, schema)?;
             Ok((result.0, meta))
         }
         FuseStorageFormat::Native => {
@@ -64,10 +64,10 @@ pub fn write_block(
             let metas = writer
                 .metas
                 .iter()
-                .enumerate()
-                .map(|(idx, meta)| {
+                .zip(schema.to_leaf_column_ids())
+                .map(|(meta, column_id)| {
                     (
-                        idx as ColumnId,
+                        column_id,
                         ColumnMeta::new(meta.offset, meta.length, meta.num_values),
                     )
                 })
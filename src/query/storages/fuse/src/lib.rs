@@ -24,6 +24,7 @@ pub mod io;
 pub mod operations;
 pub mod pruning;
 pub mod statistics;
+pub mod stream;
 pub mod table_functions;
 
 mod metrics;
@@ -0,0 +1,209 @@
+//  Copyright 2023 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::sync::Arc;
+
+use common_catalog::table::Table;
+use common_catalog::table_context::TableContext;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::types::DataType;
+use common_expression::DataBlock;
+use common_expression::Evaluator;
+use common_expression::Expr;
+use common_expression::Scalar;
+use common_expression::TableField;
+use common_functions::scalars::BUILTIN_FUNCTIONS;
+use common_meta_app::schema::TableMeta;
+use common_sql::parse_exprs;
+use storages_common_table_meta::meta::Statistics as FuseStatistics;
+use storages_common_table_meta::meta::TableSnapshot;
+use uuid::Uuid;
+
+use crate::FuseTable;
+
+impl FuseTable {
+    pub async fn do_add_column(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        field: &TableField,
+        comment: &str,
+    ) -> Result<()> {
+        let mut new_table_meta = self.get_table_info().meta.clone();
+        let mut schema = new_table_meta.schema.as_ref().clone();
+        // Tables created by legacy versions may have no field comments.
+        if new_table_meta.field_comments.len() == schema.num_fields() {
+            new_table_meta.field_comments.push(comment.to_string());
+        }
+        schema.add_column(field)?;
+        // Blocks written before the column is added are filled with the default value, which
+        // is evaluated once here so that a non-deterministic default (e.g. `now()`) reads the
+        // same for every read of the old rows.
+        let column_id = schema.field_with_name(field.name())?.column_id();
+        let default_value = self.eval_default_value(ctx.clone(), field)?;
+        new_table_meta
+            .column_default_values
+            .insert(column_id, default_value);
+        new_table_meta.schema = Arc::new(schema);
+
+        self.commit_table_meta(ctx, new_table_meta).await
+    }
+
+    fn eval_default_value(&self, ctx: Arc<dyn TableContext>, field: &TableField) -> Result<Scalar> {
+        let data_type = DataType::from(field.data_type());
+        let default_expr = match field.default_expr() {
+            Some(default_expr) => default_expr,
+            None => return Ok(data_type.default_value()),
+        };
+
+        let mut exprs = parse_exprs(ctx.clone(), Arc::new(self.clone()), false, default_expr)?;
+        let expr = Expr::Cast {
+            span: None,
+            is_try: false,
+            expr: Box::new(exprs.remove(0)),
+            dest_type: data_type,
+        };
+        let func_ctx = ctx.try_get_function_context()?;
+        let dummy_block = DataBlock::new(vec![], 1);
+        let evaluator = Evaluator::new(&dummy_block, func_ctx, &BUILTIN_FUNCTIONS);
+        let value = evaluator.run(&expr).map_err(|(_, e)| {
+            ErrorCode::Internal(format!(
+                "eval default expression of column {} failed: {}.",
+                field.name(),
+                e
+            ))
+        })?;
+        Ok(value.as_ref().index(0).unwrap().to_owned())
+    }
+
+    pub async fn do_drop_column(&self, ctx: Arc<dyn TableContext>, column: &str) -> Result<()> {
+        self.check_cluster_key_columns(ctx.clone(), column)?;
+        self.check_skipping_index_columns(ctx.clone(), column)?;
+
+        let mut new_table_meta = self.get_table_info().meta.clone();
+        let mut schema = new_table_meta.schema.as_ref().clone();
+        let index = schema.index_of(column)?;
+        let column_id = schema.field(index).column_id();
+        schema.drop_column(column)?;
+        new_table_meta.column_default_values.remove(&column_id);
+        if index < new_table_meta.field_comments.len() {
+            new_table_meta.field_comments.remove(index);
+        }
+        new_table_meta.schema = Arc::new(schema);
+
+        self.commit_table_meta(ctx, new_table_meta).await
+    }
+
+    pub async fn do_rename_column(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        old_column: &str,
+        new_column: &str,
+    ) -> Result<()> {
+        self.check_cluster_key_columns(ctx.clone(), old_column)?;
+        self.check_skipping_index_columns(ctx.clone(), old_column)?;
+
+        let mut new_table_meta = self.get_table_info().meta.clone();
+        let mut schema = new_table_meta.schema.as_ref().clone();
+        schema.rename_column(old_column, new_column)?;
+        new_table_meta.schema = Arc::new(schema);
+
+        self.commit_table_meta(ctx, new_table_meta).await
+    }
+
+    // The cluster key is stored as an expression string which refers the columns by name,
+    // so the columns used by the cluster key can not be dropped or renamed.
+    fn check_cluster_key_columns(&self, ctx: Arc<dyn TableContext>, column: &str) -> Result<()> {
+        if let Some((_, order)) = &self.cluster_key_meta {
+            let schema = self.schema();
+            let cluster_keys = parse_exprs(ctx, Arc::new(self.clone()), true, order)?;
+            for cluster_key in cluster_keys.iter() {
+                if cluster_key
+                    .column_refs()
+                    .keys()
+                    .any(|index| schema.field(*index).name() == column)
+                {
+                    return Err(ErrorCode::UnsupportedEngineParams(format!(
+                        "Column \"{}\" is used by the cluster key {}",
+                        column, order
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Same as the cluster key, the skipping indexes refer the columns by name.
+    fn check_skipping_index_columns(&self, ctx: Arc<dyn TableContext>, column: &str) -> Result<()> {
+        let table_meta = Arc::new(self.clone());
+        for (name, meta) in self.table_info.meta.skipping_indexes.iter() {
+            let expr =
+                self.parse_skipping_index_expr(ctx.clone(), table_meta.clone(), &meta.expr)?;
+            if expr.column_refs().contains_key(column) {
+                return Err(ErrorCode::UnsupportedIndex(format!(
+                    "Column \"{}\" is used by the skipping index {}",
+                    column, name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    // Commit the new table meta with a new snapshot which carries the new schema,
+    // the data of the table is untouched.
+    pub(crate) async fn commit_table_meta(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        new_table_meta: TableMeta,
+    ) -> Result<()> {
+        let schema = new_table_meta.schema.as_ref().clone();
+
+        let prev = self.read_table_snapshot().await?;
+        let prev_version = self.snapshot_format_version().await?;
+        let prev_timestamp = prev.as_ref().and_then(|v| v.timestamp);
+        let prev_snapshot_id = prev.as_ref().map(|v| (v.snapshot_id, prev_version));
+        let prev_statistics_location = prev
+            .as_ref()
+            .and_then(|v| v.table_statistics_location.clone());
+        let (summary, segments) = if let Some(v) = prev {
+            (v.summary.clone(), v.segments.clone())
+        } else {
+            (FuseStatistics::default(), vec![])
+        };
+
+        let new_snapshot = TableSnapshot::new(
+            Uuid::new_v4(),
+            &prev_timestamp,
+            prev_snapshot_id,
+            schema,
+            summary,
+            segments,
+            self.cluster_key_meta.clone(),
+            prev_statistics_location,
+        );
+
+        let mut table_info = self.table_info.clone();
+        table_info.meta = new_table_meta;
+
+        FuseTable::commit_to_meta_server(
+            ctx.as_ref(),
+            &table_info,
+            &self.meta_location_generator,
+            new_snapshot,
+            None,
+            &self.operator,
+        )
+        .await
+    }
+}
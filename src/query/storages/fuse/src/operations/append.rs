@@ -77,6 +77,8 @@ impl FuseTable {
         let cluster_stats_gen =
             self.get_cluster_stats_gen(ctx.clone(), pipeline, 0, block_compact_thresholds)?;
 
+        let skipping_indexes = self.skipping_indexes(ctx.clone())?;
+
         let cluster_keys = &cluster_stats_gen.cluster_key_index;
         if !cluster_keys.is_empty() {
             let sort_descs: Vec<SortColumnDescription> = cluster_keys
@@ -109,6 +111,7 @@ impl FuseTable {
                     cluster_stats_gen.clone(),
                     block_compact_thresholds,
                     self.table_info.schema(),
+                    skipping_indexes.clone(),
                     self.storage_format,
                     self.table_compression,
                     Some(transform_output_port),
@@ -125,6 +128,7 @@ impl FuseTable {
                     cluster_stats_gen.clone(),
                     block_compact_thresholds,
                     self.table_info.schema(),
+                    skipping_indexes.clone(),
                     self.storage_format,
                     self.table_compression,
                     None,
@@ -0,0 +1,59 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_expression::DataBlock;
+use storages_common_table_meta::meta::Location;
+use storages_common_table_meta::meta::SegmentInfo;
+use storages_common_table_meta::meta::Statistics;
+use storages_common_table_meta::meta::Versioned;
+
+use crate::operations::AppendOperationLogEntry;
+use crate::statistics::reducers::reduce_statistics;
+use crate::FuseTable;
+
+impl FuseTable {
+    /// Build a table which only exposes the segments appended by `operations`,
+    /// i.e. the operation logs that have been committed by `commit_insertion`.
+    ///
+    /// Queries over the returned table see the newly inserted rows only, which is
+    /// how materialized views are maintained incrementally.
+    pub fn appended_table(&self, operations: &[DataBlock]) -> Result<Arc<FuseTable>> {
+        let entries = operations
+            .iter()
+            .map(AppendOperationLogEntry::try_from)
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut table = self.clone();
+        table.appended_entries = Some(Arc::new(entries));
+        Ok(Arc::new(table))
+    }
+
+    pub(crate) fn appended_segments(entries: &[AppendOperationLogEntry]) -> Vec<Location> {
+        entries
+            .iter()
+            .map(|entry| (entry.segment_location.clone(), SegmentInfo::VERSION))
+            .collect()
+    }
+
+    pub(crate) fn appended_summary(entries: &[AppendOperationLogEntry]) -> Result<Statistics> {
+        let stats = entries
+            .iter()
+            .map(|entry| &entry.segment_info.summary)
+            .collect::<Vec<_>>();
+        reduce_statistics(&stats)
+    }
+}
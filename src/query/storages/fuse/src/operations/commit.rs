@@ -13,6 +13,7 @@
 //  limitations under the License.
 
 use std::ops::Range;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
@@ -57,6 +58,7 @@ use crate::metrics::metrics_inc_commit_mutation_success;
 use crate::metrics::metrics_inc_commit_mutation_unresolvable_conflict;
 use crate::operations::commit::utils::no_side_effects_in_meta_store;
 use crate::operations::mutation::AbortOperation;
+use crate::operations::quota::record_table_storage_usage;
 use crate::operations::AppendOperationLogEntry;
 use crate::operations::TableOperationLog;
 use crate::statistics;
@@ -181,6 +183,13 @@ impl FuseTable {
                     }
                 }
 
+                Err(e) if e.code() == ErrorCode::STORAGE_QUOTA_EXCEEDED => {
+                    // quota is checked before anything is written to the meta store,
+                    // the newly written blocks and segments are useless.
+                    let _ = utils::abort_operations(self.get_operator(), operation_log).await;
+                    break Err(e);
+                }
+
                 Err(e) => {
                     // we are not sure about if the table state has been modified or not, just propagate the error
                     // and return, without aborting anything.
@@ -248,6 +257,9 @@ impl FuseTable {
             index_data_bytes: new_snapshot.summary.index_size,
         };
 
+        self.check_quota(ctx.as_ref(), &new_snapshot.summary)
+            .await?;
+
         FuseTable::commit_to_meta_server(
             ctx.as_ref(),
             &self.table_info,
@@ -307,6 +319,13 @@ impl FuseTable {
         table_statistics: Option<TableSnapshotStatistics>,
         operator: &Operator,
     ) -> Result<()> {
+        // The query is killed, e.g., it has lost the lock of the table, do not commit.
+        if ctx.get_aborting().load(Ordering::Acquire) {
+            return Err(ErrorCode::AbortedQuery(
+                "Aborted query, because the server is shutting down or the query was killed.",
+            ));
+        }
+
         let snapshot_location = location_generator
             .snapshot_location_from_uuid(&snapshot.snapshot_id, snapshot.format_version())?;
         let need_to_save_statistics =
@@ -342,6 +361,7 @@ impl FuseTable {
             compressed_data_bytes: stats.compressed_byte_size,
             index_data_bytes: stats.index_size,
         };
+        let new_statistics = new_table_meta.statistics.clone();
 
         // 3. prepare the request
         let catalog = ctx.get_catalog(&table_info.meta.catalog)?;
@@ -352,12 +372,15 @@ impl FuseTable {
             table_id,
             seq: MatchSeq::Exact(table_version),
             new_table_meta,
+            // advance the offsets of the streams consumed by this statement
+            upsert_table_options: ctx.get_consumed_streams(),
         };
 
         // 3. let's roll
         let reply = catalog.update_table_meta(table_info, req).await;
         match reply {
             Ok(_) => {
+                record_table_storage_usage(&ctx.get_tenant(), table_id, &new_statistics);
                 if let Some(snapshot_cache) = CacheManager::instance().get_table_snapshot_cache() {
                     let cache = &mut snapshot_cache.write();
                     cache.put(snapshot_location.clone(), Arc::new(snapshot.clone()));
@@ -679,6 +702,9 @@ mod utils {
                 if let Some(index) = &block.bloom_filter_index_location {
                     let _ = operator.object(&index.0).delete().await;
                 }
+                if let Some(index) = &block.skipping_index_location {
+                    let _ = operator.object(&index.0).delete().await;
+                }
             }
             let _ = operator.object(&entry.segment_location).delete().await;
         }
@@ -142,6 +142,7 @@ impl FuseTable {
         let all_col_ids = self.all_the_columns_ids();
         let projection = Projection::Columns(all_col_ids);
         let block_reader = self.create_block_reader(projection)?;
+        let skipping_indexes = self.skipping_indexes(ctx.clone())?;
 
         pipeline.add_transform(|input, output| {
             CompactTransform::try_create(
@@ -153,6 +154,7 @@ impl FuseTable {
                 self.meta_location_generator().clone(),
                 self.operator.clone(),
                 self.schema(),
+                skipping_indexes.clone(),
                 self.storage_format,
                 self.table_compression,
                 thresholds,
@@ -131,7 +131,7 @@ impl FuseTable {
         Ok(())
     }
 
-    fn try_eval_const(
+    pub(crate) fn try_eval_const(
         &self,
         ctx: Arc<dyn TableContext>,
         schema: &TableSchema,
@@ -189,6 +189,7 @@ impl FuseTable {
             self.table_info.schema(),
             &push_down,
             segments_location,
+            self.skipping_indexes(ctx.clone())?,
         )
         .await?;
 
@@ -231,6 +232,7 @@ impl FuseTable {
             ))
         };
 
+        let skipping_indexes = self.skipping_indexes(ctx.clone())?;
         let max_threads = ctx.get_settings().get_max_threads()? as usize;
         // Add source pipe.
         pipeline.add_source(
@@ -242,13 +244,14 @@ impl FuseTable {
                     block_reader.clone(),
                     Arc::new(filter.clone()),
                     remain_reader.clone(),
+                    skipping_indexes.clone(),
                 )
             },
             max_threads,
         )
     }
 
-    fn try_add_deletion_transform(
+    pub(crate) fn try_add_deletion_transform(
         &self,
         ctx: Arc<dyn TableContext>,
         base_segments: Vec<Location>,
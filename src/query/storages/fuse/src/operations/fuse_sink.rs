@@ -24,6 +24,7 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_expression::BlockCompactThresholds;
 use common_expression::DataBlock;
+use common_expression::TableSchema;
 use common_expression::TableSchemaRef;
 use common_pipeline_core::processors::port::OutputPort;
 use opendal::Operator;
@@ -96,6 +97,40 @@ impl BloomIndexState {
     }
 }
 
+pub struct SkippingIndexState {
+    pub(crate) data: Vec<u8>,
+    pub(crate) size: u64,
+    pub(crate) location: Location,
+}
+
+impl SkippingIndexState {
+    pub fn try_create(
+        ctx: Arc<dyn TableContext>,
+        source_schema: &TableSchema,
+        indexes: &[SkippingIndex],
+        block: &DataBlock,
+        location: Location,
+    ) -> Result<Option<Self>> {
+        if indexes.is_empty() {
+            return Ok(None);
+        }
+
+        let skipping_index = build_block_skipping_index(
+            ctx.try_get_function_context()?,
+            source_schema,
+            indexes,
+            block,
+        )?;
+        let data = serde_json::to_vec(&skipping_index)?;
+        let size = data.len() as u64;
+        Ok(Some(Self {
+            data,
+            size,
+            location,
+        }))
+    }
+}
+
 enum State {
     None,
     NeedSerialize(DataBlock),
@@ -105,6 +140,7 @@ enum State {
         meta_data: HashMap<ColumnId, ColumnMeta>,
         block_statistics: BlockStatistics,
         bloom_index_state: BloomIndexState,
+        skipping_index_state: Option<SkippingIndexState>,
     },
     GenerateSegment,
     SerializedSegment {
@@ -130,6 +166,7 @@ pub struct FuseTableSink {
     cluster_stats_gen: ClusterStatsGenerator,
 
     source_schema: TableSchemaRef,
+    skipping_indexes: Vec<SkippingIndex>,
     storage_format: FuseStorageFormat,
     table_compression: TableCompression,
     // A dummy output port for distributed insert select to connect Exchange Sink.
@@ -147,6 +184,7 @@ impl FuseTableSink {
         cluster_stats_gen: ClusterStatsGenerator,
         thresholds: BlockCompactThresholds,
         source_schema: TableSchemaRef,
+        skipping_indexes: Vec<SkippingIndex>,
         storage_format: FuseStorageFormat,
         table_compression: TableCompression,
         output: Option<Arc<OutputPort>>,
@@ -161,6 +199,7 @@ impl FuseTableSink {
             num_block_threshold: num_block_threshold as u64,
             cluster_stats_gen,
             source_schema,
+            skipping_indexes,
             storage_format,
             table_compression,
             output,
@@ -230,11 +269,21 @@ impl Processor for FuseTableSink {
                     location,
                 )?;
 
+                let location = self.meta_locations.block_skipping_index_location(&block_id);
+                let skipping_index_state = SkippingIndexState::try_create(
+                    self.ctx.clone(),
+                    &self.source_schema,
+                    &self.skipping_indexes,
+                    &block,
+                    location,
+                )?;
+
                 let block_statistics = BlockStatistics::from(
                     &block,
                     block_location.0,
                     cluster_stats,
                     Some(column_distinct_count),
+                    &self.source_schema,
                 )?;
 
                 let write_settings = WriteSettings {
@@ -254,6 +303,7 @@ impl Processor for FuseTableSink {
                     block_statistics,
                     meta_data,
                     bloom_index_state,
+                    skipping_index_state,
                 };
             }
             State::GenerateSegment => {
@@ -303,6 +353,7 @@ impl Processor for FuseTableSink {
                 meta_data,
                 block_statistics,
                 bloom_index_state,
+                skipping_index_state,
             } => {
                 let start = Instant::now();
 
@@ -338,6 +389,18 @@ impl Processor for FuseTableSink {
                     metrics_inc_block_index_write_milliseconds(start.elapsed().as_millis() as u64);
                 }
 
+                // write skipping index
+                let (skipping_index_location, skipping_index_size) = match skipping_index_state {
+                    Some(state) => {
+                        self.data_accessor
+                            .object(&state.location.0)
+                            .write(state.data)
+                            .await?;
+                        (Some(state.location), state.size)
+                    }
+                    None => (None, 0),
+                };
+
                 let bloom_filter_index_size = bloom_index_state.size;
                 self.accumulator.add_block(
                     size,
@@ -345,6 +408,8 @@ impl Processor for FuseTableSink {
                     block_statistics,
                     Some(bloom_index_state.location),
                     bloom_filter_index_size,
+                    skipping_index_location,
+                    skipping_index_size,
                     self.table_compression.into(),
                 )?;
 
@@ -431,6 +431,10 @@ impl FuseTable {
                         .unwrap_or_default()
                         .0,
                 );
+                // skipping index files are purged together with the bloom filter indexes.
+                if let Some(loc) = &block_meta.skipping_index_location {
+                    blooms.insert(loc.0.clone());
+                }
             }
         }
 
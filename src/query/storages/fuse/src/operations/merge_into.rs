@@ -0,0 +1,218 @@
+//  Copyright 2023 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::sync::Arc;
+
+use common_catalog::plan::MergeIntoInfo;
+use common_catalog::plan::Partitions;
+use common_catalog::plan::PartitionsShuffleKind;
+use common_catalog::plan::Projection;
+use common_catalog::plan::PushDownInfo;
+use common_catalog::table::Table;
+use common_catalog::table_context::TableContext;
+use common_exception::Result;
+use storages_common_table_meta::meta::TableSnapshot;
+use uuid::Uuid;
+
+use crate::operations::mutation::DeletionPartInfo;
+use crate::operations::mutation::MergeIntoAppendTransform;
+use crate::operations::mutation::MergeIntoJoinSink;
+use crate::operations::mutation::MergeIntoSource;
+use crate::operations::mutation::MergeIntoState;
+use crate::operations::mutation::MergeIntoTargetSource;
+use crate::operations::mutation::MutationSink;
+use crate::pipelines::Pipeline;
+use crate::pruning::BlockPruner;
+use crate::FuseTable;
+
+impl FuseTable {
+    /// Create the state of a `MERGE INTO`, the current snapshot is taken as the base.
+    pub async fn create_merge_into_state(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        merge_into_info: MergeIntoInfo,
+    ) -> Result<Arc<MergeIntoState>> {
+        let snapshot = match self.read_table_snapshot().await? {
+            Some(snapshot) => snapshot,
+            // The table has never been written, take an empty snapshot as the base,
+            // so that the inserted rows are committed in the same way.
+            None => Arc::new(TableSnapshot::new(
+                Uuid::new_v4(),
+                &None,
+                None,
+                self.schema().as_ref().clone(),
+                Default::default(),
+                vec![],
+                self.cluster_key_meta.clone(),
+                None,
+            )),
+        };
+
+        Ok(Arc::new(MergeIntoState::create(
+            ctx.try_get_function_context()?,
+            merge_into_info,
+            self.schema(),
+            snapshot,
+        )))
+    }
+
+    /// Read the blocks of the base snapshot as the probe side of the join with the source.
+    pub async fn add_merge_into_target_source(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        merge_into_state: Arc<MergeIntoState>,
+        pipeline: &mut Pipeline,
+    ) -> Result<()> {
+        // all the columns are needed to rewrite the rows.
+        let projection = Projection::Columns(self.all_the_columns_ids());
+        let push_down = Some(PushDownInfo {
+            projection: Some(projection.clone()),
+            ..PushDownInfo::default()
+        });
+
+        let base_snapshot = merge_into_state.base_snapshot();
+        let segments_location = base_snapshot.segments.clone();
+        let block_metas = BlockPruner::prune(
+            &ctx,
+            self.operator.clone(),
+            self.table_info.schema(),
+            &push_down,
+            segments_location,
+            self.skipping_indexes(ctx.clone())?,
+        )
+        .await?;
+
+        let mut index_stats = Vec::with_capacity(block_metas.len());
+        let mut metas = Vec::with_capacity(block_metas.len());
+        for (index, block_meta) in block_metas.into_iter() {
+            index_stats.push((index, block_meta.cluster_stats.clone()));
+            metas.push(block_meta);
+        }
+
+        let (_, inner_parts) = self.read_partitions_with_metas(
+            ctx.clone(),
+            self.table_info.schema(),
+            None,
+            metas,
+            base_snapshot.summary.block_count as usize,
+        )?;
+
+        let parts = Partitions::create(
+            PartitionsShuffleKind::Mod,
+            index_stats
+                .into_iter()
+                .zip(inner_parts.partitions.into_iter())
+                .map(|((a, b), c)| DeletionPartInfo::create(a, b, c))
+                .collect(),
+        );
+        ctx.try_set_partitions(parts)?;
+
+        let block_reader = self.create_block_reader(projection)?;
+
+        let max_threads = ctx.get_settings().get_max_threads()? as usize;
+        pipeline.add_source(
+            |output| {
+                MergeIntoTargetSource::try_create(
+                    ctx.clone(),
+                    output,
+                    block_reader.clone(),
+                    merge_into_state.clone(),
+                )
+            },
+            max_threads,
+        )
+    }
+
+    /// Consume the rows joined by the hash join.
+    pub fn add_merge_into_join_sink(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        merge_into_state: Arc<MergeIntoState>,
+        pipeline: &mut Pipeline,
+    ) -> Result<()> {
+        let skipping_indexes = self.skipping_indexes(ctx.clone())?;
+        pipeline.add_sink(|input| {
+            MergeIntoJoinSink::try_create(
+                ctx.clone(),
+                self,
+                merge_into_state.clone(),
+                skipping_indexes.clone(),
+                input,
+            )
+        })
+    }
+
+    /// Rewrite the blocks containing the removed rows and commit, after the source is
+    /// joined with the target table.
+    ///
+    /// The flow of Pipeline is as follows:
+    /// +----------------+
+    /// |MergeIntoSource1| ------
+    /// +----------------+       |      +-----------------+      +------------------------+      +------------+
+    /// |      ...       | ...   | ---> |DeletionTransform| ---> |MergeIntoAppendTransform| ---> |MutationSink|
+    /// +----------------+       |      +-----------------+      +------------------------+      +------------+
+    /// |MergeIntoSourceN| ------
+    /// +----------------+
+    pub fn do_merge_into(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        merge_into_state: Arc<MergeIntoState>,
+        pipeline: &mut Pipeline,
+    ) -> Result<()> {
+        let parts = merge_into_state.take_removed_parts()?;
+        if parts.is_empty() && !merge_into_state.has_appended_blocks() {
+            // nothing to merge.
+            return Ok(());
+        }
+
+        let snapshot = merge_into_state.base_snapshot();
+        ctx.try_set_partitions(Partitions::create(PartitionsShuffleKind::Mod, parts))?;
+
+        // all the columns are needed to rewrite the blocks.
+        let projection = Projection::Columns(self.all_the_columns_ids());
+        let block_reader = self.create_block_reader(projection)?;
+        let skipping_indexes = self.skipping_indexes(ctx.clone())?;
+        let max_threads = ctx.get_settings().get_max_threads()? as usize;
+        pipeline.add_source(
+            |output| {
+                MergeIntoSource::try_create(
+                    ctx.clone(),
+                    output,
+                    self,
+                    block_reader.clone(),
+                    merge_into_state.clone(),
+                    skipping_indexes.clone(),
+                )
+            },
+            max_threads,
+        )?;
+
+        self.try_add_deletion_transform(ctx.clone(), snapshot.segments.clone(), pipeline)?;
+
+        pipeline.add_transform(|input, output| {
+            MergeIntoAppendTransform::try_create(
+                ctx.clone(),
+                self,
+                merge_into_state.clone(),
+                input,
+                output,
+            )
+        })?;
+
+        pipeline.add_sink(|input| {
+            MutationSink::try_create(self, ctx.clone(), snapshot.clone(), input)
+        })?;
+        Ok(())
+    }
+}
@@ -12,20 +12,26 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+mod alter_column;
 mod analyze;
 mod append;
+mod appended;
 mod commit;
 mod compact;
 mod delete;
 mod fuse_sink;
 mod gc;
+mod merge_into;
 mod mutation;
 mod navigate;
 mod operation_log;
+mod quota;
 mod read_data;
 mod read_partitions;
 mod recluster;
+mod skipping_index;
 mod truncate;
+mod update;
 
 mod fuse_source;
 mod read;
@@ -35,10 +41,14 @@ pub mod util;
 pub use compact::CompactOptions;
 pub use fuse_sink::BloomIndexState;
 pub use fuse_sink::FuseTableSink;
+pub use fuse_sink::SkippingIndexState;
+pub use mutation::MergeIntoState;
 pub use mutation::ReclusterMutator;
 pub use mutation::SegmentCompactMutator;
 pub use mutation::SegmentCompactionState;
 pub use mutation::SegmentCompactor;
 pub use operation_log::AppendOperationLogEntry;
 pub use operation_log::TableOperationLog;
+pub use quota::list_database_storage_usage;
+pub use quota::DatabaseStorageUsage;
 pub use util::column_metas;
@@ -29,6 +29,7 @@ use common_expression::TableSchemaRef;
 use opendal::Operator;
 use storages_common_blocks::blocks_to_parquet;
 use storages_common_index::BlockFilter;
+use storages_common_index::SkippingIndex;
 use storages_common_table_meta::caches::CacheManager;
 use storages_common_table_meta::meta::BlockMeta;
 use storages_common_table_meta::meta::SegmentInfo;
@@ -46,6 +47,7 @@ use crate::io::TableMetaLocationGenerator;
 use crate::io::WriteSettings;
 use crate::metrics::*;
 use crate::operations::mutation::AbortOperation;
+use crate::operations::SkippingIndexState;
 use crate::pipelines::processors::port::InputPort;
 use crate::pipelines::processors::port::OutputPort;
 use crate::pipelines::processors::processor::Event;
@@ -60,6 +62,7 @@ struct SerializeState {
     block_location: String,
     index_data: Vec<u8>,
     index_location: String,
+    skipping_index_state: Option<SkippingIndexState>,
 }
 
 enum State {
@@ -96,6 +99,7 @@ pub struct CompactTransform {
     location_gen: TableMetaLocationGenerator,
     dal: Operator,
     schema: TableSchemaRef,
+    skipping_indexes: Vec<SkippingIndex>,
     storage_format: FuseStorageFormat,
     table_compression: TableCompression,
 
@@ -120,6 +124,7 @@ impl CompactTransform {
         location_gen: TableMetaLocationGenerator,
         dal: Operator,
         schema: TableSchemaRef,
+        skipping_indexes: Vec<SkippingIndex>,
         storage_format: FuseStorageFormat,
         table_compression: TableCompression,
         thresholds: BlockCompactThresholds,
@@ -140,6 +145,7 @@ impl CompactTransform {
             location_gen,
             dal,
             schema,
+            skipping_indexes,
             storage_format,
             table_compression,
             max_memory,
@@ -235,7 +241,8 @@ impl Processor for CompactTransform {
                     let new_block = DataBlock::concat(&compact_blocks)?;
 
                     // generate block statistics.
-                    let col_stats = reduce_block_statistics(&stats, Some(&new_block))?;
+                    let col_stats =
+                        reduce_block_statistics(&stats, Some((&new_block, &self.schema)))?;
                     let row_count = new_block.num_rows() as u64;
                     let block_size = new_block.memory_size() as u64;
                     let (block_location, block_id) = self.location_gen.gen_block_location();
@@ -244,14 +251,12 @@ impl Processor for CompactTransform {
                     let (index_data, index_size, index_location) = {
                         // write index
                         let func_ctx = self.ctx.try_get_function_context()?;
-                        let bloom_index = BlockFilter::try_create(
-                            func_ctx,
-                            self.schema.clone(),
-                            block_location.1,
-                            &[&new_block],
-                        )?;
-                        let index_block = bloom_index.filter_block;
                         let location = self.location_gen.block_bloom_index_location(&block_id);
+                        let bloom_index =
+                            BlockFilter::try_create(func_ctx, self.schema.clone(), location.1, &[
+                                &new_block,
+                            ])?;
+                        let index_block = bloom_index.filter_block;
                         let mut data = Vec::with_capacity(100 * 1024);
                         let index_block_schema = &bloom_index.filter_schema;
                         let (size, _) = blocks_to_parquet(
@@ -263,6 +268,19 @@ impl Processor for CompactTransform {
                         (data, size, location)
                     };
 
+                    // build skipping index.
+                    let location = self.location_gen.block_skipping_index_location(&block_id);
+                    let skipping_index_state = SkippingIndexState::try_create(
+                        self.ctx.clone(),
+                        &self.schema,
+                        &self.skipping_indexes,
+                        &new_block,
+                        location,
+                    )?;
+                    let (skipping_index_location, skipping_index_size) = skipping_index_state
+                        .as_ref()
+                        .map_or((None, 0), |v| (Some(v.location.clone()), v.size));
+
                     let write_settings = WriteSettings {
                         storage_format: self.storage_format,
                         table_compression: self.table_compression,
@@ -285,6 +303,8 @@ impl Processor for CompactTransform {
                         block_location.clone(),
                         Some(index_location.clone()),
                         index_size,
+                        skipping_index_location,
+                        skipping_index_size,
                         self.table_compression.into(),
                     );
                     self.abort_operation.add_block(&new_meta);
@@ -295,6 +315,7 @@ impl Processor for CompactTransform {
                         block_location: block_location.0,
                         index_data,
                         index_location: index_location.0,
+                        skipping_index_state,
                     });
                 }
                 self.state = State::SerializedBlocks(serialize_states);
@@ -420,7 +441,14 @@ impl Processor for CompactTransform {
                         // write block data.
                         write_data(&state.block_data, dal, &state.block_location).await?;
                         // write index data.
-                        write_data(&state.index_data, dal, &state.index_location).await
+                        write_data(&state.index_data, dal, &state.index_location).await?;
+                        // write skipping index data.
+                        if let Some(skipping_index_state) = state.skipping_index_state {
+                            dal.object(&skipping_index_state.location.0)
+                                .write(skipping_index_state.data)
+                                .await?;
+                        }
+                        Ok::<_, ErrorCode>(())
                     });
                 }
 
@@ -32,6 +32,7 @@ use common_expression::Value;
 use common_functions::scalars::BUILTIN_FUNCTIONS;
 use opendal::Operator;
 use storages_common_blocks::blocks_to_parquet;
+use storages_common_index::SkippingIndex;
 use storages_common_pruner::BlockMetaIndex;
 use storages_common_table_meta::meta::BlockMeta;
 use storages_common_table_meta::meta::ClusterStatistics;
@@ -47,6 +48,7 @@ use crate::io::ReadSettings;
 use crate::io::TableMetaLocationGenerator;
 use crate::operations::util;
 use crate::operations::BloomIndexState;
+use crate::operations::SkippingIndexState;
 use crate::pipelines::processors::port::OutputPort;
 use crate::pipelines::processors::processor::Event;
 use crate::pipelines::processors::processor::ProcessorPtr;
@@ -63,6 +65,7 @@ struct SerializeState {
     block_location: String,
     index_data: Vec<u8>,
     index_location: String,
+    skipping_index_state: Option<SkippingIndexState>,
 }
 
 enum State {
@@ -102,6 +105,7 @@ pub struct DeletionSource {
     cluster_stats_gen: ClusterStatsGenerator,
     origin_stats: Option<ClusterStatistics>,
     table_compression: TableCompression,
+    skipping_indexes: Vec<SkippingIndex>,
 }
 
 impl DeletionSource {
@@ -112,6 +116,7 @@ impl DeletionSource {
         block_reader: Arc<BlockReader>,
         filter: Arc<RemoteExpr<String>>,
         remain_reader: Arc<Option<BlockReader>>,
+        skipping_indexes: Vec<SkippingIndex>,
     ) -> Result<ProcessorPtr> {
         let mut srouce_fields = block_reader.schema().fields().clone();
         if let Some(remain_reader) = remain_reader.as_ref() {
@@ -136,6 +141,7 @@ impl DeletionSource {
             cluster_stats_gen: table.cluster_stats_gen(ctx)?,
             origin_stats: None,
             table_compression: table.table_compression,
+            skipping_indexes,
         })))
     }
 }
@@ -283,22 +289,39 @@ impl Processor for DeletionSource {
                 let location = self.location_gen.block_bloom_index_location(&block_id);
                 let (bloom_index_state, column_distinct_count) = BloomIndexState::try_create(
                     self.ctx.clone(),
-                    self.source_schema.clone(),
+                    self.output_schema.clone(),
                     &block,
                     location,
                 )?;
-                let col_stats = gen_columns_statistics(&block, Some(column_distinct_count))?;
+
+                // build skipping index.
+                let location = self.location_gen.block_skipping_index_location(&block_id);
+                let skipping_index_state = SkippingIndexState::try_create(
+                    self.ctx.clone(),
+                    &self.output_schema,
+                    &self.skipping_indexes,
+                    &block,
+                    location,
+                )?;
+                let (skipping_index_location, skipping_index_size) = skipping_index_state
+                    .as_ref()
+                    .map_or((None, 0), |v| (Some(v.location.clone()), v.size));
+                let col_stats = gen_columns_statistics(
+                    &block,
+                    Some(column_distinct_count),
+                    &self.output_schema,
+                )?;
 
                 // serialize data block.
                 let mut block_data = Vec::with_capacity(100 * 1024 * 1024);
-                let schema = self.source_schema.clone();
+                let schema = self.output_schema.clone();
                 let (file_size, meta_data) = blocks_to_parquet(
                     &schema,
                     vec![block],
                     &mut block_data,
                     self.table_compression,
                 )?;
-                let col_metas = util::column_metas(&meta_data)?;
+                let col_metas = util::column_metas(&meta_data, &schema)?;
 
                 // new block meta.
                 let new_meta = Arc::new(BlockMeta::new(
@@ -311,6 +334,8 @@ impl Processor for DeletionSource {
                     block_location.clone(),
                     Some(bloom_index_state.location.clone()),
                     bloom_index_state.size,
+                    skipping_index_location,
+                    skipping_index_size,
                     self.table_compression.into(),
                 ));
 
@@ -320,6 +345,7 @@ impl Processor for DeletionSource {
                         block_location: block_location.0,
                         index_data: bloom_index_state.data,
                         index_location: bloom_index_state.location.0,
+                        skipping_index_state,
                     },
                     new_meta,
                 );
@@ -407,6 +433,15 @@ impl Processor for DeletionSource {
                     &serialize_state.index_location,
                 )
                 .await?;
+                // write skipping index data.
+                if let Some(skipping_index_state) = serialize_state.skipping_index_state {
+                    write_data(
+                        &skipping_index_state.data,
+                        &self.dal,
+                        &skipping_index_state.location.0,
+                    )
+                    .await?;
+                }
                 self.state = State::Generated(Deletion::Replaced(block_meta));
             }
             _ => return Err(ErrorCode::Internal("It's a bug.")),
@@ -18,6 +18,7 @@ mod deletion_source;
 mod deletion_transform;
 
 pub use deletion_meta::Deletion;
+pub use deletion_meta::DeletionSourceMeta;
 pub use deletion_part::DeletionPartInfo;
 pub use deletion_source::DeletionSource;
 pub use deletion_transform::DeletionTransform;
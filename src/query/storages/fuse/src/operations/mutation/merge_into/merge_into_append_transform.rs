@@ -0,0 +1,214 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_cache::Cache;
+use common_catalog::table::Table;
+use common_catalog::table_context::TableContext;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::BlockCompactThresholds;
+use common_expression::BlockMetaInfoPtr;
+use common_expression::DataBlock;
+use common_expression::TableSchemaRef;
+use opendal::Operator;
+use storages_common_table_meta::caches::CacheManager;
+use storages_common_table_meta::meta::BlockMeta;
+use storages_common_table_meta::meta::SegmentInfo;
+
+use crate::io::write_data;
+use crate::io::TableMetaLocationGenerator;
+use crate::operations::mutation::MergeIntoState;
+use crate::operations::mutation::MutationMeta;
+use crate::pipelines::processors::port::InputPort;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::Event;
+use crate::pipelines::processors::processor::ProcessorPtr;
+use crate::pipelines::processors::Processor;
+use crate::statistics::reducers::merge_statistics_mut;
+use crate::statistics::reducers::reduce_block_metas;
+use crate::FuseTable;
+use crate::DEFAULT_BLOCK_PER_SEGMENT;
+use crate::FUSE_OPT_KEY_BLOCK_PER_SEGMENT;
+
+enum State {
+    None,
+    ReadMeta(BlockMetaInfoPtr),
+    Append {
+        block_metas: Vec<Arc<BlockMeta>>,
+        meta: MutationMeta,
+    },
+}
+
+/// The blocks written by the `MergeIntoJoinSink`, which are the inserted rows and the new
+/// versions of the updated rows, are collected into new segments. They are added to the
+/// `MutationMeta` so that the whole `MERGE INTO` is committed in a single snapshot.
+pub struct MergeIntoAppendTransform {
+    state: State,
+    ctx: Arc<dyn TableContext>,
+    schema: TableSchemaRef,
+    dal: Operator,
+    location_gen: TableMetaLocationGenerator,
+    thresholds: BlockCompactThresholds,
+    block_per_seg: usize,
+    merge_into_state: Arc<MergeIntoState>,
+
+    input: Arc<InputPort>,
+    output: Arc<OutputPort>,
+    output_data: Option<DataBlock>,
+}
+
+impl MergeIntoAppendTransform {
+    pub fn try_create(
+        ctx: Arc<dyn TableContext>,
+        table: &FuseTable,
+        merge_into_state: Arc<MergeIntoState>,
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+    ) -> Result<ProcessorPtr> {
+        Ok(ProcessorPtr::create(Box::new(MergeIntoAppendTransform {
+            state: State::None,
+            ctx,
+            schema: table.schema(),
+            dal: table.get_operator(),
+            location_gen: table.meta_location_generator().clone(),
+            thresholds: table.get_block_compact_thresholds(),
+            block_per_seg: table
+                .get_option(FUSE_OPT_KEY_BLOCK_PER_SEGMENT, DEFAULT_BLOCK_PER_SEGMENT),
+            merge_into_state,
+            input,
+            output,
+            output_data: None,
+        })))
+    }
+
+    async fn write_segments(
+        &self,
+        block_metas: Vec<Arc<BlockMeta>>,
+        meta: &mut MutationMeta,
+    ) -> Result<()> {
+        let mut new_segments = Vec::new();
+        for chunk in block_metas.chunks(self.block_per_seg) {
+            let summary = reduce_block_metas(chunk, self.thresholds)?;
+            merge_statistics_mut(&mut meta.summary, &summary)?;
+            let segment = SegmentInfo::new(chunk.to_vec(), summary);
+            let location = self.location_gen.gen_segment_info_location();
+            write_data(&serde_json::to_vec(&segment)?, &self.dal, &location).await?;
+            meta.abort_operation.add_segment(location.clone());
+            new_segments.push((location.clone(), segment.format_version()));
+
+            if let Some(segment_cache) = CacheManager::instance().get_table_segment_cache() {
+                let cache = &mut segment_cache.write();
+                cache.put(location, Arc::new(segment));
+            }
+        }
+
+        // The new segments are placed ahead, the same as the appended ones.
+        new_segments.append(&mut meta.segments);
+        meta.segments = new_segments;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for MergeIntoAppendTransform {
+    fn name(&self) -> String {
+        "MergeIntoAppendTransform".to_string()
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if matches!(self.state, State::ReadMeta(_)) {
+            return Ok(Event::Sync);
+        }
+
+        if matches!(self.state, State::Append { .. }) {
+            return Ok(Event::Async);
+        }
+
+        if self.output.is_finished() {
+            self.input.finish();
+            return Ok(Event::Finished);
+        }
+
+        if !self.output.can_push() {
+            return Ok(Event::NeedConsume);
+        }
+
+        if let Some(data_block) = self.output_data.take() {
+            self.output.push_data(Ok(data_block));
+            return Ok(Event::NeedConsume);
+        }
+
+        if self.input.is_finished() {
+            self.output.finish();
+            return Ok(Event::Finished);
+        }
+
+        if !self.input.has_data() {
+            self.input.set_need_data();
+            return Ok(Event::NeedData);
+        }
+
+        let input_meta = self
+            .input
+            .pull_data()
+            .unwrap()?
+            .get_meta()
+            .cloned()
+            .ok_or_else(|| ErrorCode::Internal("No block meta. It's a bug"))?;
+        self.state = State::ReadMeta(input_meta);
+        // Only one mutation meta is generated by the `DeletionTransform`.
+        self.input.finish();
+        Ok(Event::Sync)
+    }
+
+    fn process(&mut self) -> Result<()> {
+        match std::mem::replace(&mut self.state, State::None) {
+            State::ReadMeta(input_meta) => {
+                let meta = MutationMeta::from_meta(&input_meta)?.clone();
+                let block_metas = self.merge_into_state.take_appended_blocks();
+                if block_metas.is_empty() {
+                    self.output_data = Some(DataBlock::empty_with_meta(Box::new(meta)));
+                } else {
+                    self.state = State::Append { block_metas, meta };
+                }
+            }
+            _ => return Err(ErrorCode::Internal("It's a bug.")),
+        }
+        Ok(())
+    }
+
+    async fn async_process(&mut self) -> Result<()> {
+        match std::mem::replace(&mut self.state, State::None) {
+            State::Append {
+                block_metas,
+                mut meta,
+            } => {
+                for block_meta in block_metas.iter() {
+                    meta.abort_operation.add_block(block_meta);
+                }
+                self.write_segments(block_metas, &mut meta).await?;
+                self.output_data = Some(DataBlock::empty_with_meta(Box::new(meta)));
+            }
+            _ => return Err(ErrorCode::Internal("It's a bug.")),
+        }
+        Ok(())
+    }
+}
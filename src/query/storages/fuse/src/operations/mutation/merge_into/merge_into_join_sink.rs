@@ -0,0 +1,181 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_catalog::table::Table;
+use common_catalog::table_context::TableContext;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::DataBlock;
+use common_expression::TableSchemaRef;
+use opendal::Operator;
+use storages_common_index::SkippingIndex;
+use storages_common_table_meta::meta::BlockMeta;
+use storages_common_table_meta::table::TableCompression;
+
+use crate::io::write_data;
+use crate::io::TableMetaLocationGenerator;
+use crate::operations::mutation::serialize_block;
+use crate::operations::mutation::MergeIntoState;
+use crate::operations::mutation::SerializedBlock;
+use crate::pipelines::processors::port::InputPort;
+use crate::pipelines::processors::processor::Event;
+use crate::pipelines::processors::processor::ProcessorPtr;
+use crate::pipelines::processors::Processor;
+use crate::FuseTable;
+
+enum State {
+    None,
+    Merge(DataBlock),
+    Serialized(Vec<(SerializedBlock, Arc<BlockMeta>)>),
+}
+
+/// Consumes the rows joined by the hash join, the inserted rows and the new versions of the
+/// updated rows are written into new blocks as they arrive.
+pub struct MergeIntoJoinSink {
+    state: State,
+    ctx: Arc<dyn TableContext>,
+    schema: TableSchemaRef,
+    dal: Operator,
+    location_gen: TableMetaLocationGenerator,
+    max_rows_per_block: usize,
+    table_compression: TableCompression,
+    merge_into_state: Arc<MergeIntoState>,
+    skipping_indexes: Vec<SkippingIndex>,
+
+    input: Arc<InputPort>,
+}
+
+impl MergeIntoJoinSink {
+    pub fn try_create(
+        ctx: Arc<dyn TableContext>,
+        table: &FuseTable,
+        merge_into_state: Arc<MergeIntoState>,
+        skipping_indexes: Vec<SkippingIndex>,
+        input: Arc<InputPort>,
+    ) -> Result<ProcessorPtr> {
+        Ok(ProcessorPtr::create(Box::new(MergeIntoJoinSink {
+            state: State::None,
+            ctx,
+            schema: table.schema(),
+            dal: table.get_operator(),
+            location_gen: table.meta_location_generator().clone(),
+            max_rows_per_block: table.get_block_compact_thresholds().max_rows_per_block,
+            table_compression: table.table_compression,
+            merge_into_state,
+            skipping_indexes,
+            input,
+        })))
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for MergeIntoJoinSink {
+    fn name(&self) -> String {
+        "MergeIntoJoinSink".to_string()
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if matches!(self.state, State::Merge(_)) {
+            return Ok(Event::Sync);
+        }
+
+        if matches!(self.state, State::Serialized(_)) {
+            return Ok(Event::Async);
+        }
+
+        if self.input.is_finished() {
+            return Ok(Event::Finished);
+        }
+
+        if !self.input.has_data() {
+            self.input.set_need_data();
+            return Ok(Event::NeedData);
+        }
+
+        self.state = State::Merge(self.input.pull_data().unwrap()?);
+        Ok(Event::Sync)
+    }
+
+    fn process(&mut self) -> Result<()> {
+        match std::mem::replace(&mut self.state, State::None) {
+            State::Merge(data_block) => {
+                let block = match self.merge_into_state.merge_joined_block(data_block)? {
+                    None => return Ok(()),
+                    Some(block) => block,
+                };
+
+                let num_rows = block.num_rows();
+                let mut serialized = Vec::new();
+                let mut start = 0;
+                while start < num_rows {
+                    let end = std::cmp::min(start + self.max_rows_per_block, num_rows);
+                    serialized.push(serialize_block(
+                        self.ctx.clone(),
+                        self.schema.clone(),
+                        &self.location_gen,
+                        self.table_compression,
+                        &self.skipping_indexes,
+                        block.slice(start..end),
+                    )?);
+                    start = end;
+                }
+                self.state = State::Serialized(serialized);
+            }
+            _ => return Err(ErrorCode::Internal("It's a bug.")),
+        }
+        Ok(())
+    }
+
+    async fn async_process(&mut self) -> Result<()> {
+        match std::mem::replace(&mut self.state, State::None) {
+            State::Serialized(serialized_blocks) => {
+                for (serialized, block_meta) in serialized_blocks {
+                    // write block data.
+                    write_data(
+                        &serialized.block_data,
+                        &self.dal,
+                        &serialized.block_location,
+                    )
+                    .await?;
+                    // write index data.
+                    write_data(
+                        &serialized.index_data,
+                        &self.dal,
+                        &serialized.index_location,
+                    )
+                    .await?;
+                    // write skipping index data.
+                    if let Some(skipping_index_state) = serialized.skipping_index_state {
+                        write_data(
+                            &skipping_index_state.data,
+                            &self.dal,
+                            &skipping_index_state.location.0,
+                        )
+                        .await?;
+                    }
+                    self.merge_into_state.add_appended_block(block_meta);
+                }
+            }
+            _ => return Err(ErrorCode::Internal("It's a bug.")),
+        }
+        Ok(())
+    }
+}
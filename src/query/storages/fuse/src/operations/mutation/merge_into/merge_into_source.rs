@@ -0,0 +1,246 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_catalog::plan::PartInfoPtr;
+use common_catalog::table_context::TableContext;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::DataBlock;
+use common_expression::TableSchemaRef;
+use opendal::Operator;
+use storages_common_index::SkippingIndex;
+use storages_common_pruner::BlockMetaIndex;
+use storages_common_table_meta::meta::BlockMeta;
+use storages_common_table_meta::table::TableCompression;
+
+use crate::fuse_part::FusePartInfo;
+use crate::io::write_data;
+use crate::io::BlockReader;
+use crate::io::ReadSettings;
+use crate::io::TableMetaLocationGenerator;
+use crate::operations::mutation::serialize_block;
+use crate::operations::mutation::Deletion;
+use crate::operations::mutation::DeletionPartInfo;
+use crate::operations::mutation::DeletionSourceMeta;
+use crate::operations::mutation::MergeIntoState;
+use crate::operations::mutation::SerializedBlock;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::Event;
+use crate::pipelines::processors::processor::ProcessorPtr;
+use crate::pipelines::processors::Processor;
+use crate::FuseTable;
+use crate::Table;
+
+type DataChunks = Vec<(usize, Vec<u8>)>;
+
+enum State {
+    ReadData(Option<PartInfoPtr>),
+    MergeData(PartInfoPtr, DataChunks),
+    Serialized(SerializedBlock, Arc<BlockMeta>),
+    Generated(Deletion),
+    Output(Option<PartInfoPtr>, DataBlock),
+    Finish,
+}
+
+/// Rewrites the blocks of the target table containing the rows deleted or updated by the
+/// `WHEN MATCHED` clauses, the removed rows are filtered out. The resulting block metas are
+/// sent to the `DeletionTransform` in the same way as the deletion does.
+pub struct MergeIntoSource {
+    state: State,
+    ctx: Arc<dyn TableContext>,
+    output: Arc<OutputPort>,
+    location_gen: TableMetaLocationGenerator,
+    dal: Operator,
+    block_reader: Arc<BlockReader>,
+    merge_into_state: Arc<MergeIntoState>,
+
+    schema: TableSchemaRef,
+    index: BlockMetaIndex,
+    table_compression: TableCompression,
+    skipping_indexes: Vec<SkippingIndex>,
+}
+
+impl MergeIntoSource {
+    pub fn try_create(
+        ctx: Arc<dyn TableContext>,
+        output: Arc<OutputPort>,
+        table: &FuseTable,
+        block_reader: Arc<BlockReader>,
+        merge_into_state: Arc<MergeIntoState>,
+        skipping_indexes: Vec<SkippingIndex>,
+    ) -> Result<ProcessorPtr> {
+        Ok(ProcessorPtr::create(Box::new(MergeIntoSource {
+            state: State::ReadData(None),
+            ctx,
+            output,
+            location_gen: table.meta_location_generator().clone(),
+            dal: table.get_operator(),
+            block_reader,
+            merge_into_state,
+            schema: table.schema(),
+            index: BlockMetaIndex {
+                segment_idx: 0,
+                block_idx: 0,
+            },
+            table_compression: table.table_compression,
+            skipping_indexes,
+        })))
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for MergeIntoSource {
+    fn name(&self) -> String {
+        "MergeIntoSource".to_string()
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if matches!(self.state, State::ReadData(None)) {
+            self.state = match self.ctx.try_get_part() {
+                None => State::Finish,
+                Some(part) => State::ReadData(Some(part)),
+            }
+        }
+
+        if matches!(self.state, State::Finish) {
+            self.output.finish();
+            return Ok(Event::Finished);
+        }
+
+        if self.output.is_finished() {
+            return Ok(Event::Finished);
+        }
+
+        if !self.output.can_push() {
+            return Ok(Event::NeedConsume);
+        }
+
+        if matches!(self.state, State::Output(_, _)) {
+            if let State::Output(part, data_block) =
+                std::mem::replace(&mut self.state, State::Finish)
+            {
+                self.state = match part {
+                    None => State::Finish,
+                    Some(part) => State::ReadData(Some(part)),
+                };
+
+                self.output.push_data(Ok(data_block));
+                return Ok(Event::NeedConsume);
+            }
+        }
+
+        if matches!(self.state, State::ReadData(_) | State::Serialized(_, _)) {
+            Ok(Event::Async)
+        } else {
+            Ok(Event::Sync)
+        }
+    }
+
+    fn process(&mut self) -> Result<()> {
+        match std::mem::replace(&mut self.state, State::Finish) {
+            State::MergeData(part, chunks) => {
+                let data_block = self.block_reader.deserialize_parquet_chunks(part, chunks)?;
+                let removed = self
+                    .merge_into_state
+                    .take_removed_rows(&self.index)
+                    .ok_or_else(|| ErrorCode::Internal("No removed rows. It's a bug."))?;
+                let block = DataBlock::filter_with_bitmap(data_block, &(!&removed))?;
+                self.state = match block.num_rows() {
+                    0 => State::Generated(Deletion::Deleted),
+                    _ => {
+                        let (serialized, block_meta) = serialize_block(
+                            self.ctx.clone(),
+                            self.schema.clone(),
+                            &self.location_gen,
+                            self.table_compression,
+                            &self.skipping_indexes,
+                            block,
+                        )?;
+                        State::Serialized(serialized, block_meta)
+                    }
+                };
+            }
+            State::Generated(op) => {
+                let meta = DeletionSourceMeta::create(self.index.clone(), op);
+                let new_part = self.ctx.try_get_part();
+                self.state = State::Output(new_part, DataBlock::empty_with_meta(meta));
+            }
+            _ => return Err(ErrorCode::Internal("It's a bug.")),
+        }
+        Ok(())
+    }
+
+    async fn async_process(&mut self) -> Result<()> {
+        match std::mem::replace(&mut self.state, State::Finish) {
+            State::ReadData(Some(part)) => {
+                let settings = ReadSettings::from_ctx(&self.ctx)?;
+                let deletion_part = DeletionPartInfo::from_part(&part)?;
+                self.index = deletion_part.index.clone();
+                let part = deletion_part.inner_part.clone();
+                let fuse_part = FusePartInfo::from_part(&part)?;
+
+                let read_res = self
+                    .block_reader
+                    .read_columns_data_by_merge_io(
+                        &settings,
+                        &fuse_part.location,
+                        &fuse_part.columns_meta,
+                    )
+                    .await?;
+                let chunks = read_res
+                    .columns_chunks()?
+                    .into_iter()
+                    .map(|(column_idx, column_chunk)| (column_idx, column_chunk.to_vec()))
+                    .collect::<Vec<_>>();
+
+                self.state = State::MergeData(part, chunks);
+            }
+            State::Serialized(serialized, block_meta) => {
+                // write block data.
+                write_data(
+                    &serialized.block_data,
+                    &self.dal,
+                    &serialized.block_location,
+                )
+                .await?;
+                // write index data.
+                write_data(
+                    &serialized.index_data,
+                    &self.dal,
+                    &serialized.index_location,
+                )
+                .await?;
+                // write skipping index data.
+                if let Some(skipping_index_state) = serialized.skipping_index_state {
+                    write_data(
+                        &skipping_index_state.data,
+                        &self.dal,
+                        &skipping_index_state.location.0,
+                    )
+                    .await?;
+                }
+                self.state = State::Generated(Deletion::Replaced(block_meta));
+            }
+            _ => return Err(ErrorCode::Internal("It's a bug.")),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,490 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use common_arrow::arrow::bitmap::Bitmap;
+use common_arrow::arrow::bitmap::MutableBitmap;
+use common_catalog::plan::MergeIntoInfo;
+use common_catalog::plan::PartInfoPtr;
+use common_catalog::table_context::TableContext;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::types::number::UInt64Type;
+use common_expression::types::DataType;
+use common_expression::types::NumberDataType;
+use common_expression::types::ValueType;
+use common_expression::BlockEntry;
+use common_expression::Column;
+use common_expression::DataBlock;
+use common_expression::DataField;
+use common_expression::DataSchema;
+use common_expression::DataSchemaRef;
+use common_expression::Evaluator;
+use common_expression::FromData;
+use common_expression::FunctionContext;
+use common_expression::RemoteExpr;
+use common_expression::TableSchemaRef;
+use common_expression::Value;
+use common_functions::scalars::BUILTIN_FUNCTIONS;
+use storages_common_blocks::blocks_to_parquet;
+use storages_common_index::SkippingIndex;
+use storages_common_pruner::BlockMetaIndex;
+use storages_common_table_meta::meta::BlockMeta;
+use storages_common_table_meta::meta::TableSnapshot;
+use storages_common_table_meta::table::TableCompression;
+
+use crate::io::TableMetaLocationGenerator;
+use crate::operations::mutation::DeletionPartInfo;
+use crate::operations::util;
+use crate::operations::BloomIndexState;
+use crate::operations::SkippingIndexState;
+use crate::statistics::gen_columns_statistics;
+
+/// The column appended to the blocks of the target table before they are probed, it locates
+/// the row in the target table as `block_id << 32 | row`.
+pub const MERGE_INTO_ROW_ID: &str = "_row_id";
+
+struct TargetBlock {
+    part: PartInfoPtr,
+    num_rows: usize,
+    // The rows matched by the source, allocated when the first row is matched.
+    matched: Option<MutableBitmap>,
+    // The rows matched by more than one row of the source.
+    duplicated: Option<MutableBitmap>,
+    // The rows deleted or updated by the `WHEN MATCHED` clauses.
+    removed: Option<MutableBitmap>,
+}
+
+/// The state shared by the processors of a `MERGE INTO`.
+///
+/// The source is joined with the target table by the hash join of the pipeline, the source
+/// is the build side and the blocks of the target table are the probe side. The joined rows
+/// are merged as they arrive: the inserted rows and the new versions of the updated rows are
+/// written into new blocks, the deleted and updated rows are recorded by their row ids.
+/// After the join finishes, only the target blocks containing removed rows are rewritten.
+pub struct MergeIntoState {
+    func_ctx: FunctionContext,
+    info: MergeIntoInfo,
+    schema: TableSchemaRef,
+    base_snapshot: Arc<TableSnapshot>,
+    blocks: Mutex<Vec<TargetBlock>>,
+    removed_rows: Mutex<HashMap<(usize, usize), Bitmap>>,
+    appended_blocks: Mutex<Vec<Arc<BlockMeta>>>,
+}
+
+impl MergeIntoState {
+    pub fn create(
+        func_ctx: FunctionContext,
+        info: MergeIntoInfo,
+        schema: TableSchemaRef,
+        base_snapshot: Arc<TableSnapshot>,
+    ) -> Self {
+        MergeIntoState {
+            func_ctx,
+            info,
+            schema,
+            base_snapshot,
+            blocks: Mutex::new(vec![]),
+            removed_rows: Mutex::new(HashMap::new()),
+            appended_blocks: Mutex::new(vec![]),
+        }
+    }
+
+    pub fn info(&self) -> &MergeIntoInfo {
+        &self.info
+    }
+
+    pub fn base_snapshot(&self) -> Arc<TableSnapshot> {
+        self.base_snapshot.clone()
+    }
+
+    /// The schema of the probe side, the columns of the target table and the row id.
+    pub fn probe_schema(&self) -> DataSchemaRef {
+        let mut fields = DataSchema::from(self.schema.as_ref()).fields().clone();
+        fields.push(DataField::new(
+            MERGE_INTO_ROW_ID,
+            DataType::Number(NumberDataType::UInt64),
+        ));
+        Arc::new(DataSchema::new(fields))
+    }
+
+    /// Append the row ids to a block of the target table read from the part.
+    pub fn attach_row_ids(&self, part: PartInfoPtr, block: DataBlock) -> Result<DataBlock> {
+        let num_rows = block.num_rows();
+        let block_id = {
+            let mut blocks = self.blocks.lock().unwrap();
+            blocks.push(TargetBlock {
+                part,
+                num_rows,
+                matched: None,
+                duplicated: None,
+                removed: None,
+            });
+            (blocks.len() - 1) as u64
+        };
+        let row_ids = (0..num_rows as u64)
+            .map(|row| block_id << 32 | row)
+            .collect::<Vec<_>>();
+        let mut block = block;
+        block.add_column(BlockEntry {
+            data_type: DataType::Number(NumberDataType::UInt64),
+            value: Value::Column(UInt64Type::from_data(row_ids)),
+        });
+        Ok(block)
+    }
+
+    /// Apply the clauses to a block of the joined rows, whose columns are the columns of the
+    /// target table, the row id and the columns of the source. The row id is null if the
+    /// source row is not matched by any row of the target table.
+    /// Returns the rows to be inserted, `None` if there is no such row.
+    pub fn merge_joined_block(&self, block: DataBlock) -> Result<Option<DataBlock>> {
+        if block.is_empty() {
+            return Ok(None);
+        }
+
+        let num_rows = block.num_rows();
+        let num_fields = self.schema.num_fields();
+        let row_id_entry = block.get_by_offset(num_fields);
+        let (row_ids, matched) = match row_id_entry
+            .value
+            .convert_to_full_column(&row_id_entry.data_type, num_rows)
+        {
+            Column::Nullable(column) => (column.column, column.validity),
+            column => (column, MutableBitmap::from_len_set(num_rows).into()),
+        };
+
+        let mut blocks = vec![];
+        if matched.unset_bits() != 0 {
+            let rows = DataBlock::filter_with_bitmap(block.clone(), &(!&matched))?;
+            let source = DataBlock::new(rows.columns()[num_fields + 1..].to_vec(), rows.num_rows());
+            if let Some(inserted) = self.insert_unmatched(source)? {
+                blocks.push(inserted);
+            }
+        }
+        if matched.unset_bits() != num_rows {
+            let row_ids = row_ids.filter(&matched);
+            let row_ids = UInt64Type::try_downcast_column(&row_ids).ok_or_else(|| {
+                ErrorCode::Internal("The row id of MERGE INTO must be UInt64. It's a bug.")
+            })?;
+            let rows = DataBlock::filter_with_bitmap(block, &matched)?;
+            if let Some(updated) = self.merge_matched(rows, &row_ids)? {
+                blocks.push(updated);
+            }
+        }
+
+        if blocks.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(DataBlock::concat(&blocks)?))
+        }
+    }
+
+    /// Take the parts of the target blocks containing the rows deleted or updated, which
+    /// are to be rewritten after the join finishes.
+    ///
+    /// A target row matched by multiple source rows is only an error if it is deleted or
+    /// updated, the duplicated matches are harmless if no `WHEN MATCHED` clause applies.
+    pub fn take_removed_parts(&self) -> Result<Vec<PartInfoPtr>> {
+        let blocks = std::mem::take(&mut *self.blocks.lock().unwrap());
+        let mut removed_rows = self.removed_rows.lock().unwrap();
+        let mut parts = vec![];
+        for block in blocks {
+            if let Some(removed) = block.removed {
+                if let Some(duplicated) = &block.duplicated {
+                    let duplicated: &Bitmap = &duplicated.clone().into();
+                    let removed: &Bitmap = &removed.clone().into();
+                    if (duplicated & removed).unset_bits() != block.num_rows {
+                        return Err(ErrorCode::BadArguments(
+                            "MERGE INTO failed, a row of the target table matches multiple rows of the source",
+                        ));
+                    }
+                }
+                let deletion_part = DeletionPartInfo::from_part(&block.part)?;
+                let index = &deletion_part.index;
+                removed_rows.insert((index.segment_idx, index.block_idx), removed.into());
+                parts.push(block.part);
+            }
+        }
+        Ok(parts)
+    }
+
+    /// Take the rows of the target block to be removed.
+    pub fn take_removed_rows(&self, index: &BlockMetaIndex) -> Option<Bitmap> {
+        self.removed_rows
+            .lock()
+            .unwrap()
+            .remove(&(index.segment_idx, index.block_idx))
+    }
+
+    pub fn add_appended_block(&self, block_meta: Arc<BlockMeta>) {
+        self.appended_blocks.lock().unwrap().push(block_meta);
+    }
+
+    pub fn has_appended_blocks(&self) -> bool {
+        !self.appended_blocks.lock().unwrap().is_empty()
+    }
+
+    pub fn take_appended_blocks(&self) -> Vec<Arc<BlockMeta>> {
+        std::mem::take(&mut *self.appended_blocks.lock().unwrap())
+    }
+
+    // Apply the `WHEN MATCHED` clauses to the matched rows, returns the new versions of the
+    // updated rows.
+    fn merge_matched(&self, rows: DataBlock, row_ids: &[u64]) -> Result<Option<DataBlock>> {
+        let num_rows = rows.num_rows();
+        let num_fields = self.schema.num_fields();
+
+        // The columns of the target table are wrapped nullable by the right outer join,
+        // the matched rows are never null.
+        let mut columns = Vec::with_capacity(rows.num_columns() - 1);
+        for (field, entry) in self.schema.fields().iter().zip(rows.columns()) {
+            let data_type = DataType::from(field.data_type());
+            let column = entry
+                .value
+                .convert_to_full_column(&entry.data_type, num_rows);
+            let column = if data_type.is_nullable() {
+                column
+            } else {
+                column.remove_nullable()
+            };
+            columns.push(BlockEntry {
+                data_type,
+                value: Value::Column(column),
+            });
+        }
+        columns.extend_from_slice(&rows.columns()[num_fields + 1..]);
+        let joined = DataBlock::new(columns, num_rows);
+
+        self.mark_rows(row_ids, None)?;
+        if self.info.matched_actions.is_empty() {
+            return Ok(None);
+        }
+
+        let mut remaining: Bitmap = MutableBitmap::from_len_set(num_rows).into();
+        let mut blocks = vec![];
+        for action in self.info.matched_actions.iter() {
+            let selected = self.select_rows(&joined, &action.condition, &remaining)?;
+            if selected.unset_bits() == selected.len() {
+                continue;
+            }
+            remaining = &remaining & &(!&selected);
+
+            // The deleted rows are simply not written back.
+            if let Some(update_list) = &action.update_list {
+                let rows = DataBlock::filter_with_bitmap(joined.clone(), &selected)?;
+                let evaluator = Evaluator::new(&rows, self.func_ctx, &BUILTIN_FUNCTIONS);
+                let mut columns = rows.columns()[..num_fields].to_vec();
+                for (index, remote_expr) in update_list.iter() {
+                    let expr = remote_expr.as_expr(&BUILTIN_FUNCTIONS).unwrap();
+                    let value = evaluator.run(&expr).map_err(|(_, e)| {
+                        ErrorCode::Internal(format!("eval merge update expression failed: {}.", e))
+                    })?;
+                    columns[*index] = BlockEntry {
+                        data_type: expr.data_type().clone(),
+                        value,
+                    };
+                }
+                blocks.push(DataBlock::new(columns, rows.num_rows()).convert_to_full());
+            }
+        }
+
+        // The matched rows not affected by any clause are kept in place.
+        let removed = !&remaining;
+        if removed.unset_bits() != removed.len() {
+            self.mark_rows(row_ids, Some(&removed))?;
+        }
+
+        if blocks.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(DataBlock::concat(&blocks)?))
+        }
+    }
+
+    // Record the matched rows if `removed` is `None`, otherwise record the removed ones.
+    fn mark_rows(&self, row_ids: &[u64], removed: Option<&Bitmap>) -> Result<()> {
+        let mut blocks = self.blocks.lock().unwrap();
+        for (i, row_id) in row_ids.iter().enumerate() {
+            let block = &mut blocks[(row_id >> 32) as usize];
+            let row = (row_id & 0xFFFF_FFFF) as usize;
+            match removed {
+                None => {
+                    let matched = block
+                        .matched
+                        .get_or_insert_with(|| MutableBitmap::from_len_zeroed(block.num_rows));
+                    if matched.get(row) {
+                        block
+                            .duplicated
+                            .get_or_insert_with(|| MutableBitmap::from_len_zeroed(block.num_rows))
+                            .set(row, true);
+                    }
+                    matched.set(row, true);
+                }
+                Some(removed) => {
+                    if removed.get_bit(i) {
+                        block
+                            .removed
+                            .get_or_insert_with(|| MutableBitmap::from_len_zeroed(block.num_rows))
+                            .set(row, true);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Apply the `WHEN NOT MATCHED` clauses to the source rows not matched by any row of
+    // the target table.
+    fn insert_unmatched(&self, rows: DataBlock) -> Result<Option<DataBlock>> {
+        if self.info.unmatched_actions.is_empty() || rows.num_rows() == 0 {
+            return Ok(None);
+        }
+
+        let mut remaining: Bitmap = MutableBitmap::from_len_set(rows.num_rows()).into();
+        let mut blocks = vec![];
+        for action in self.info.unmatched_actions.iter() {
+            let selected = self.select_rows(&rows, &action.condition, &remaining)?;
+            if selected.unset_bits() == selected.len() {
+                continue;
+            }
+            remaining = &remaining & &(!&selected);
+
+            let selected_rows = DataBlock::filter_with_bitmap(rows.clone(), &selected)?;
+            let evaluator = Evaluator::new(&selected_rows, self.func_ctx, &BUILTIN_FUNCTIONS);
+            let columns = action
+                .values
+                .iter()
+                .map(|remote_expr| {
+                    let expr = remote_expr.as_expr(&BUILTIN_FUNCTIONS).unwrap();
+                    let value = evaluator.run(&expr).map_err(|(_, e)| {
+                        ErrorCode::Internal(format!("eval merge insert expression failed: {}.", e))
+                    })?;
+                    Ok(BlockEntry {
+                        data_type: expr.data_type().clone(),
+                        value,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            blocks.push(DataBlock::new(columns, selected_rows.num_rows()).convert_to_full());
+        }
+
+        if blocks.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(DataBlock::concat(&blocks)?))
+        }
+    }
+
+    // Select the remaining rows satisfying the condition.
+    fn select_rows(
+        &self,
+        block: &DataBlock,
+        condition: &Option<RemoteExpr>,
+        remaining: &Bitmap,
+    ) -> Result<Bitmap> {
+        let condition = match condition {
+            None => return Ok(remaining.clone()),
+            Some(condition) => condition,
+        };
+
+        let expr = condition.as_expr(&BUILTIN_FUNCTIONS).unwrap();
+        let evaluator = Evaluator::new(block, self.func_ctx, &BUILTIN_FUNCTIONS);
+        let res = evaluator.run(&expr).map_err(|(_, e)| {
+            ErrorCode::Internal(format!("eval merge condition failed: {}.", e))
+        })?;
+        let predicates = DataBlock::cast_to_nonull_boolean(&res).ok_or_else(|| {
+            ErrorCode::BadArguments(
+                "Result of condition expression cannot be converted to boolean.",
+            )
+        })?;
+        Ok(match predicates {
+            Value::Scalar(true) => remaining.clone(),
+            Value::Scalar(false) => MutableBitmap::from_len_zeroed(remaining.len()).into(),
+            Value::Column(bitmap) => remaining & &bitmap,
+        })
+    }
+}
+
+pub struct SerializedBlock {
+    pub block_data: Vec<u8>,
+    pub block_location: String,
+    pub index_data: Vec<u8>,
+    pub index_location: String,
+    pub skipping_index_state: Option<SkippingIndexState>,
+}
+
+/// Serialize a block of the table and generate the block meta. The cluster statistics are
+/// not generated, since the rows written by `MERGE INTO` are no longer sorted by the cluster key.
+pub fn serialize_block(
+    ctx: Arc<dyn TableContext>,
+    schema: TableSchemaRef,
+    location_gen: &TableMetaLocationGenerator,
+    table_compression: TableCompression,
+    skipping_indexes: &[SkippingIndex],
+    block: DataBlock,
+) -> Result<(SerializedBlock, Arc<BlockMeta>)> {
+    let row_count = block.num_rows() as u64;
+    let block_size = block.memory_size() as u64;
+    let (block_location, block_id) = location_gen.gen_block_location();
+
+    // build block index.
+    let location = location_gen.block_bloom_index_location(&block_id);
+    let (bloom_index_state, column_distinct_count) =
+        BloomIndexState::try_create(ctx.clone(), schema.clone(), &block, location)?;
+    let col_stats = gen_columns_statistics(&block, Some(column_distinct_count), &schema)?;
+
+    // build skipping index.
+    let location = location_gen.block_skipping_index_location(&block_id);
+    let skipping_index_state =
+        SkippingIndexState::try_create(ctx, &schema, skipping_indexes, &block, location)?;
+    let (skipping_index_location, skipping_index_size) = skipping_index_state
+        .as_ref()
+        .map_or((None, 0), |v| (Some(v.location.clone()), v.size));
+
+    // serialize data block.
+    let mut block_data = Vec::with_capacity(100 * 1024 * 1024);
+    let (file_size, meta_data) =
+        blocks_to_parquet(&schema, vec![block], &mut block_data, table_compression)?;
+    let col_metas = util::column_metas(&meta_data, &schema)?;
+
+    // new block meta.
+    let new_meta = Arc::new(BlockMeta::new(
+        row_count,
+        block_size,
+        file_size,
+        col_stats,
+        col_metas,
+        None,
+        block_location.clone(),
+        Some(bloom_index_state.location.clone()),
+        bloom_index_state.size,
+        skipping_index_location,
+        skipping_index_size,
+        table_compression.into(),
+    ));
+
+    Ok((
+        SerializedBlock {
+            block_data,
+            block_location: block_location.0,
+            index_data: bloom_index_state.data,
+            index_location: bloom_index_state.location.0,
+            skipping_index_state,
+        },
+        new_meta,
+    ))
+}
@@ -0,0 +1,165 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_catalog::plan::PartInfoPtr;
+use common_catalog::table_context::TableContext;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::DataBlock;
+
+use crate::fuse_part::FusePartInfo;
+use crate::io::BlockReader;
+use crate::io::ReadSettings;
+use crate::operations::mutation::DeletionPartInfo;
+use crate::operations::mutation::MergeIntoState;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::Event;
+use crate::pipelines::processors::processor::ProcessorPtr;
+use crate::pipelines::processors::Processor;
+
+type DataChunks = Vec<(usize, Vec<u8>)>;
+
+enum State {
+    ReadData(Option<PartInfoPtr>),
+    Deserialize(PartInfoPtr, DataChunks),
+    Output(Option<PartInfoPtr>, DataBlock),
+    Finish,
+}
+
+/// Reads the blocks of the target table as the probe side of the join with the source,
+/// the row ids are appended to the blocks to locate the matched rows.
+pub struct MergeIntoTargetSource {
+    state: State,
+    ctx: Arc<dyn TableContext>,
+    output: Arc<OutputPort>,
+    block_reader: Arc<BlockReader>,
+    merge_into_state: Arc<MergeIntoState>,
+}
+
+impl MergeIntoTargetSource {
+    pub fn try_create(
+        ctx: Arc<dyn TableContext>,
+        output: Arc<OutputPort>,
+        block_reader: Arc<BlockReader>,
+        merge_into_state: Arc<MergeIntoState>,
+    ) -> Result<ProcessorPtr> {
+        Ok(ProcessorPtr::create(Box::new(MergeIntoTargetSource {
+            state: State::ReadData(None),
+            ctx,
+            output,
+            block_reader,
+            merge_into_state,
+        })))
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for MergeIntoTargetSource {
+    fn name(&self) -> String {
+        "MergeIntoTargetSource".to_string()
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if matches!(self.state, State::ReadData(None)) {
+            self.state = match self.ctx.try_get_part() {
+                None => State::Finish,
+                Some(part) => State::ReadData(Some(part)),
+            }
+        }
+
+        if matches!(self.state, State::Finish) {
+            self.output.finish();
+            return Ok(Event::Finished);
+        }
+
+        if self.output.is_finished() {
+            return Ok(Event::Finished);
+        }
+
+        if !self.output.can_push() {
+            return Ok(Event::NeedConsume);
+        }
+
+        if matches!(self.state, State::Output(_, _)) {
+            if let State::Output(part, data_block) =
+                std::mem::replace(&mut self.state, State::Finish)
+            {
+                self.state = match part {
+                    None => State::Finish,
+                    Some(part) => State::ReadData(Some(part)),
+                };
+
+                self.output.push_data(Ok(data_block));
+                return Ok(Event::NeedConsume);
+            }
+        }
+
+        if matches!(self.state, State::ReadData(_)) {
+            Ok(Event::Async)
+        } else {
+            Ok(Event::Sync)
+        }
+    }
+
+    fn process(&mut self) -> Result<()> {
+        match std::mem::replace(&mut self.state, State::Finish) {
+            State::Deserialize(part, chunks) => {
+                let deletion_part = DeletionPartInfo::from_part(&part)?;
+                let data_block = self
+                    .block_reader
+                    .deserialize_parquet_chunks(deletion_part.inner_part.clone(), chunks)?;
+                let data_block = self.merge_into_state.attach_row_ids(part, data_block)?;
+                let new_part = self.ctx.try_get_part();
+                self.state = State::Output(new_part, data_block);
+            }
+            _ => return Err(ErrorCode::Internal("It's a bug.")),
+        }
+        Ok(())
+    }
+
+    async fn async_process(&mut self) -> Result<()> {
+        match std::mem::replace(&mut self.state, State::Finish) {
+            State::ReadData(Some(part)) => {
+                let settings = ReadSettings::from_ctx(&self.ctx)?;
+                let deletion_part = DeletionPartInfo::from_part(&part)?;
+                let fuse_part = FusePartInfo::from_part(&deletion_part.inner_part)?;
+
+                let read_res = self
+                    .block_reader
+                    .read_columns_data_by_merge_io(
+                        &settings,
+                        &fuse_part.location,
+                        &fuse_part.columns_meta,
+                    )
+                    .await?;
+                let chunks = read_res
+                    .columns_chunks()?
+                    .into_iter()
+                    .map(|(column_idx, column_chunk)| (column_idx, column_chunk.to_vec()))
+                    .collect::<Vec<_>>();
+
+                self.state = State::Deserialize(part, chunks);
+            }
+            _ => return Err(ErrorCode::Internal("It's a bug.")),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod merge_into_append_transform;
+mod merge_into_join_sink;
+mod merge_into_source;
+mod merge_into_state;
+mod merge_into_target_source;
+
+pub use merge_into_append_transform::MergeIntoAppendTransform;
+pub use merge_into_join_sink::MergeIntoJoinSink;
+pub use merge_into_source::MergeIntoSource;
+pub use merge_into_state::serialize_block;
+pub use merge_into_state::MergeIntoState;
+pub use merge_into_state::SerializedBlock;
+pub use merge_into_target_source::MergeIntoTargetSource;
@@ -16,9 +16,11 @@ pub mod abort_operation;
 pub mod base_mutator;
 mod compact;
 mod deletion;
+mod merge_into;
 pub mod mutation_meta;
 pub mod mutation_sink;
 pub mod recluster_mutator;
+mod update;
 
 pub use abort_operation::AbortOperation;
 pub use base_mutator::BaseMutator;
@@ -29,9 +31,19 @@ pub use compact::MergeSegmentsTransform;
 pub use compact::SegmentCompactMutator;
 pub use compact::SegmentCompactionState;
 pub use compact::SegmentCompactor;
+pub use deletion::Deletion;
 pub use deletion::DeletionPartInfo;
 pub use deletion::DeletionSource;
+pub use deletion::DeletionSourceMeta;
 pub use deletion::DeletionTransform;
+pub use merge_into::serialize_block;
+pub use merge_into::MergeIntoAppendTransform;
+pub use merge_into::MergeIntoJoinSink;
+pub use merge_into::MergeIntoSource;
+pub use merge_into::MergeIntoState;
+pub use merge_into::MergeIntoTargetSource;
+pub use merge_into::SerializedBlock;
 pub use mutation_meta::MutationMeta;
 pub use mutation_sink::MutationSink;
 pub use recluster_mutator::ReclusterMutator;
+pub use update::UpdateSource;
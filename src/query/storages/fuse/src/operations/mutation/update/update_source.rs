@@ -0,0 +1,372 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_catalog::plan::PartInfoPtr;
+use common_catalog::table_context::TableContext;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::BlockEntry;
+use common_expression::DataBlock;
+use common_expression::Evaluator;
+use common_expression::RemoteExpr;
+use common_expression::TableSchemaRef;
+use common_expression::Value;
+use common_functions::scalars::BUILTIN_FUNCTIONS;
+use opendal::Operator;
+use storages_common_blocks::blocks_to_parquet;
+use storages_common_index::SkippingIndex;
+use storages_common_pruner::BlockMetaIndex;
+use storages_common_table_meta::meta::BlockMeta;
+use storages_common_table_meta::meta::ClusterStatistics;
+use storages_common_table_meta::table::TableCompression;
+
+use crate::fuse_part::FusePartInfo;
+use crate::io::write_data;
+use crate::io::BlockReader;
+use crate::io::ReadSettings;
+use crate::io::TableMetaLocationGenerator;
+use crate::operations::mutation::Deletion;
+use crate::operations::mutation::DeletionPartInfo;
+use crate::operations::mutation::DeletionSourceMeta;
+use crate::operations::util;
+use crate::operations::BloomIndexState;
+use crate::operations::SkippingIndexState;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::Event;
+use crate::pipelines::processors::processor::ProcessorPtr;
+use crate::pipelines::processors::Processor;
+use crate::statistics::gen_columns_statistics;
+use crate::statistics::ClusterStatsGenerator;
+use crate::FuseTable;
+use crate::Table;
+
+type DataChunks = Vec<(usize, Vec<u8>)>;
+
+struct SerializeState {
+    block_data: Vec<u8>,
+    block_location: String,
+    index_data: Vec<u8>,
+    index_location: String,
+    skipping_index_state: Option<SkippingIndexState>,
+}
+
+enum State {
+    ReadData(Option<PartInfoPtr>),
+    UpdateData(PartInfoPtr, DataChunks),
+    NeedSerialize(DataBlock),
+    Serialized(SerializeState, Arc<BlockMeta>),
+    Generated(Deletion),
+    Output(Option<PartInfoPtr>, DataBlock),
+    Finish,
+}
+
+/// Reads the blocks of the table, rewrites the updated columns of the rows matching
+/// the filter, and writes the new blocks out. The resulting block metas are sent to
+/// the `DeletionTransform` in the same way as the deletion does.
+pub struct UpdateSource {
+    state: State,
+    ctx: Arc<dyn TableContext>,
+    output: Arc<OutputPort>,
+    location_gen: TableMetaLocationGenerator,
+    dal: Operator,
+    block_reader: Arc<BlockReader>,
+    filter: Arc<Option<RemoteExpr<String>>>,
+    update_list: Arc<Vec<(usize, RemoteExpr<String>)>>,
+
+    schema: TableSchemaRef,
+    index: BlockMetaIndex,
+    cluster_stats_gen: ClusterStatsGenerator,
+    origin_stats: Option<ClusterStatistics>,
+    table_compression: TableCompression,
+    skipping_indexes: Vec<SkippingIndex>,
+}
+
+impl UpdateSource {
+    pub fn try_create(
+        ctx: Arc<dyn TableContext>,
+        output: Arc<OutputPort>,
+        table: &FuseTable,
+        block_reader: Arc<BlockReader>,
+        filter: Arc<Option<RemoteExpr<String>>>,
+        update_list: Arc<Vec<(usize, RemoteExpr<String>)>>,
+        skipping_indexes: Vec<SkippingIndex>,
+    ) -> Result<ProcessorPtr> {
+        Ok(ProcessorPtr::create(Box::new(UpdateSource {
+            state: State::ReadData(None),
+            ctx: ctx.clone(),
+            output,
+            location_gen: table.meta_location_generator().clone(),
+            dal: table.get_operator(),
+            block_reader,
+            filter,
+            update_list,
+            schema: table.schema(),
+            index: BlockMetaIndex {
+                segment_idx: 0,
+                block_idx: 0,
+            },
+            cluster_stats_gen: table.cluster_stats_gen(ctx)?,
+            origin_stats: None,
+            table_compression: table.table_compression,
+            skipping_indexes,
+        })))
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for UpdateSource {
+    fn name(&self) -> String {
+        "UpdateSource".to_string()
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if matches!(self.state, State::ReadData(None)) {
+            self.state = match self.ctx.try_get_part() {
+                None => State::Finish,
+                Some(part) => State::ReadData(Some(part)),
+            }
+        }
+
+        if matches!(self.state, State::Finish) {
+            self.output.finish();
+            return Ok(Event::Finished);
+        }
+
+        if self.output.is_finished() {
+            return Ok(Event::Finished);
+        }
+
+        if !self.output.can_push() {
+            return Ok(Event::NeedConsume);
+        }
+
+        if matches!(self.state, State::Output(_, _)) {
+            if let State::Output(part, data_block) =
+                std::mem::replace(&mut self.state, State::Finish)
+            {
+                self.state = match part {
+                    None => State::Finish,
+                    Some(part) => State::ReadData(Some(part)),
+                };
+
+                self.output.push_data(Ok(data_block));
+                return Ok(Event::NeedConsume);
+            }
+        }
+
+        if matches!(self.state, State::ReadData(_) | State::Serialized(_, _)) {
+            Ok(Event::Async)
+        } else {
+            Ok(Event::Sync)
+        }
+    }
+
+    fn process(&mut self) -> Result<()> {
+        match std::mem::replace(&mut self.state, State::Finish) {
+            State::UpdateData(part, chunks) => {
+                let data_block = self.block_reader.deserialize_parquet_chunks(part, chunks)?;
+                let num_rows = data_block.num_rows();
+
+                let func_ctx = self.ctx.try_get_function_context()?;
+                let evaluator = Evaluator::new(&data_block, func_ctx, &BUILTIN_FUNCTIONS);
+
+                if let Some(filter) = self.filter.as_ref() {
+                    let expr = filter
+                        .as_expr(&BUILTIN_FUNCTIONS)
+                        .unwrap()
+                        .project_column_ref(|name| self.schema.index_of(name).unwrap());
+                    let res = evaluator.run(&expr).map_err(|(_, e)| {
+                        ErrorCode::Internal(format!("eval update filter failed: {}.", e))
+                    })?;
+                    let predicates = DataBlock::cast_to_nonull_boolean(&res).ok_or_else(|| {
+                        ErrorCode::BadArguments(
+                            "Result of filter expression cannot be converted to boolean.",
+                        )
+                    })?;
+
+                    let affected = match &predicates {
+                        Value::Scalar(v) => *v,
+                        Value::Column(bitmap) => bitmap.unset_bits() < bitmap.len(),
+                    };
+                    if !affected {
+                        // none of the rows should be updated.
+                        self.state = State::Generated(Deletion::DoNothing);
+                        return Ok(());
+                    }
+                }
+
+                // The update expressions already keep the original value of
+                // the rows which do not match the filter.
+                let mut columns = data_block.columns().to_vec();
+                for (index, remote_expr) in self.update_list.iter() {
+                    let expr = remote_expr
+                        .as_expr(&BUILTIN_FUNCTIONS)
+                        .unwrap()
+                        .project_column_ref(|name| self.schema.index_of(name).unwrap());
+                    let value = evaluator.run(&expr).map_err(|(_, e)| {
+                        ErrorCode::Internal(format!("eval update expression failed: {}.", e))
+                    })?;
+                    columns[*index] = BlockEntry {
+                        data_type: expr.data_type().clone(),
+                        value,
+                    };
+                }
+
+                let block = DataBlock::new(columns, num_rows).convert_to_full();
+                self.state = State::NeedSerialize(block);
+            }
+            State::NeedSerialize(block) => {
+                let cluster_stats = self
+                    .cluster_stats_gen
+                    .gen_with_origin_stats(&block, std::mem::take(&mut self.origin_stats))?;
+
+                let row_count = block.num_rows() as u64;
+                let block_size = block.memory_size() as u64;
+                let (block_location, block_id) = self.location_gen.gen_block_location();
+
+                // build block index.
+                let location = self.location_gen.block_bloom_index_location(&block_id);
+                let (bloom_index_state, column_distinct_count) = BloomIndexState::try_create(
+                    self.ctx.clone(),
+                    self.schema.clone(),
+                    &block,
+                    location,
+                )?;
+
+                // build skipping index.
+                let location = self.location_gen.block_skipping_index_location(&block_id);
+                let skipping_index_state = SkippingIndexState::try_create(
+                    self.ctx.clone(),
+                    &self.schema,
+                    &self.skipping_indexes,
+                    &block,
+                    location,
+                )?;
+                let (skipping_index_location, skipping_index_size) = skipping_index_state
+                    .as_ref()
+                    .map_or((None, 0), |v| (Some(v.location.clone()), v.size));
+                let col_stats =
+                    gen_columns_statistics(&block, Some(column_distinct_count), &self.schema)?;
+
+                // serialize data block.
+                let mut block_data = Vec::with_capacity(100 * 1024 * 1024);
+                let (file_size, meta_data) = blocks_to_parquet(
+                    &self.schema,
+                    vec![block],
+                    &mut block_data,
+                    self.table_compression,
+                )?;
+                let col_metas = util::column_metas(&meta_data, &self.schema)?;
+
+                // new block meta.
+                let new_meta = Arc::new(BlockMeta::new(
+                    row_count,
+                    block_size,
+                    file_size,
+                    col_stats,
+                    col_metas,
+                    cluster_stats,
+                    block_location.clone(),
+                    Some(bloom_index_state.location.clone()),
+                    bloom_index_state.size,
+                    skipping_index_location,
+                    skipping_index_size,
+                    self.table_compression.into(),
+                ));
+
+                self.state = State::Serialized(
+                    SerializeState {
+                        block_data,
+                        block_location: block_location.0,
+                        index_data: bloom_index_state.data,
+                        index_location: bloom_index_state.location.0,
+                        skipping_index_state,
+                    },
+                    new_meta,
+                );
+            }
+            State::Generated(op) => {
+                let meta = DeletionSourceMeta::create(self.index.clone(), op);
+                let new_part = self.ctx.try_get_part();
+                self.state = State::Output(new_part, DataBlock::empty_with_meta(meta));
+            }
+            _ => return Err(ErrorCode::Internal("It's a bug.")),
+        }
+        Ok(())
+    }
+
+    async fn async_process(&mut self) -> Result<()> {
+        match std::mem::replace(&mut self.state, State::Finish) {
+            State::ReadData(Some(part)) => {
+                let settings = ReadSettings::from_ctx(&self.ctx)?;
+                let deletion_part = DeletionPartInfo::from_part(&part)?;
+                self.index = deletion_part.index.clone();
+                self.origin_stats = deletion_part.cluster_stats.clone();
+                let part = deletion_part.inner_part.clone();
+                let fuse_part = FusePartInfo::from_part(&part)?;
+
+                let read_res = self
+                    .block_reader
+                    .read_columns_data_by_merge_io(
+                        &settings,
+                        &fuse_part.location,
+                        &fuse_part.columns_meta,
+                    )
+                    .await?;
+                let chunks = read_res
+                    .columns_chunks()?
+                    .into_iter()
+                    .map(|(column_idx, column_chunk)| (column_idx, column_chunk.to_vec()))
+                    .collect::<Vec<_>>();
+
+                self.state = State::UpdateData(part, chunks);
+            }
+            State::Serialized(serialize_state, block_meta) => {
+                // write block data.
+                write_data(
+                    &serialize_state.block_data,
+                    &self.dal,
+                    &serialize_state.block_location,
+                )
+                .await?;
+                // write index data.
+                write_data(
+                    &serialize_state.index_data,
+                    &self.dal,
+                    &serialize_state.index_location,
+                )
+                .await?;
+                // write skipping index data.
+                if let Some(skipping_index_state) = serialize_state.skipping_index_state {
+                    write_data(
+                        &skipping_index_state.data,
+                        &self.dal,
+                        &skipping_index_state.location.0,
+                    )
+                    .await?;
+                }
+                self.state = State::Generated(Deletion::Replaced(block_meta));
+            }
+            _ => return Err(ErrorCode::Internal("It's a bug.")),
+        }
+        Ok(())
+    }
+}
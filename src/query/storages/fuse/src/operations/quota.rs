@@ -0,0 +1,204 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::collections::HashMap;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_catalog::catalog::Catalog;
+use common_catalog::table_context::TableContext;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::TableStatistics;
+use common_meta_types::MetaId;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use storages_common_table_meta::meta::Statistics;
+
+use crate::FuseTable;
+
+/// How long the storage usage of a tenant is cached before it's listed from meta again.
+///
+/// The commits of this node are applied to the cache as they happen, the commits
+/// of the other nodes are only seen after the usage is refreshed.
+const STORAGE_USAGE_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+struct CachedStorageUsage {
+    refreshed_at: Instant,
+    usages: Vec<DatabaseStorageUsage>,
+}
+
+/// The storage usage of the tenants, checked against the quotas on commits.
+static STORAGE_USAGE_CACHE: Lazy<Mutex<HashMap<String, CachedStorageUsage>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Storage used by the tables of a database.
+#[derive(Clone)]
+pub struct DatabaseStorageUsage {
+    pub database: String,
+    /// Bytes used by each table, keyed by table id.
+    pub tables: HashMap<MetaId, u64>,
+}
+
+impl DatabaseStorageUsage {
+    pub fn used_bytes(&self) -> u64 {
+        self.tables.values().sum()
+    }
+}
+
+/// Collect the storage usage of the databases of a tenant.
+///
+/// The usage of a table is taken from the statistics in its table meta,
+/// which is updated from the summary of the snapshot on every commit.
+/// Databases created from a share are skipped, they are accounted to the sharing tenant.
+pub async fn list_database_storage_usage(
+    catalog: &dyn Catalog,
+    tenant: &str,
+) -> Result<Vec<DatabaseStorageUsage>> {
+    let mut usages = vec![];
+    for database in catalog.list_databases(tenant).await? {
+        if database.get_db_info().meta.from_share.is_some() {
+            continue;
+        }
+
+        let tables = catalog.list_tables(tenant, database.name()).await?;
+        let tables = tables
+            .iter()
+            .map(|table| {
+                let table_info = table.get_table_info();
+                (
+                    table_info.ident.table_id,
+                    table_storage_bytes(&table_info.meta.statistics),
+                )
+            })
+            .collect();
+
+        usages.push(DatabaseStorageUsage {
+            database: database.name().to_string(),
+            tables,
+        });
+    }
+    Ok(usages)
+}
+
+/// Get the storage usage of a tenant from the cache, which is refreshed if it's
+/// out of date or doesn't know the table yet.
+async fn cached_database_storage_usage(
+    catalog: &dyn Catalog,
+    tenant: &str,
+    table_id: MetaId,
+) -> Result<Vec<DatabaseStorageUsage>> {
+    if let Some(cached) = STORAGE_USAGE_CACHE.lock().get(tenant) {
+        if cached.refreshed_at.elapsed() < STORAGE_USAGE_REFRESH_INTERVAL
+            && cached
+                .usages
+                .iter()
+                .any(|u| u.tables.contains_key(&table_id))
+        {
+            return Ok(cached.usages.clone());
+        }
+    }
+
+    let usages = list_database_storage_usage(catalog, tenant).await?;
+    STORAGE_USAGE_CACHE
+        .lock()
+        .insert(tenant.to_string(), CachedStorageUsage {
+            refreshed_at: Instant::now(),
+            usages: usages.clone(),
+        });
+    Ok(usages)
+}
+
+/// Apply a committed table statistics to the cached storage usage of the tenant.
+pub(crate) fn record_table_storage_usage(
+    tenant: &str,
+    table_id: MetaId,
+    statistics: &TableStatistics,
+) {
+    if let Some(cached) = STORAGE_USAGE_CACHE.lock().get_mut(tenant) {
+        for usage in cached.usages.iter_mut() {
+            if let Some(bytes) = usage.tables.get_mut(&table_id) {
+                *bytes = table_storage_bytes(statistics);
+            }
+        }
+    }
+}
+
+fn table_storage_bytes(statistics: &TableStatistics) -> u64 {
+    statistics.compressed_data_bytes + statistics.index_data_bytes
+}
+
+impl FuseTable {
+    /// Check the quotas of the tenant before `summary` is committed as the new statistics of the table.
+    ///
+    /// Only a commit that grows the table is rejected, thus a tenant exceeding its quota
+    /// can still shrink its tables.
+    pub(crate) async fn check_quota(
+        &self,
+        ctx: &dyn TableContext,
+        summary: &Statistics,
+    ) -> Result<()> {
+        let quota = ctx.get_tenant_quota().await?;
+        let prev = &self.table_info.meta.statistics;
+
+        if quota.max_rows_per_table != 0
+            && summary.row_count > quota.max_rows_per_table
+            && summary.row_count > prev.number_of_rows
+        {
+            return Err(ErrorCode::StorageQuotaExceeded(format!(
+                "Max rows per table quota exceeded: {}, table '{}' would have {} rows",
+                quota.max_rows_per_table, self.table_info.name, summary.row_count
+            )));
+        }
+
+        if quota.max_storage_in_bytes == 0 && quota.max_storage_per_database_in_bytes == 0 {
+            return Ok(());
+        }
+
+        let table_bytes = summary.compressed_byte_size + summary.index_size;
+        if table_bytes <= table_storage_bytes(prev) {
+            return Ok(());
+        }
+
+        let table_id = self.table_info.ident.table_id;
+        let tenant = ctx.get_tenant();
+        let catalog = ctx.get_catalog(self.table_info.catalog())?;
+        let usages = cached_database_storage_usage(catalog.as_ref(), &tenant, table_id).await?;
+
+        let mut tenant_bytes = 0;
+        for usage in usages.iter() {
+            let mut database_bytes = usage.used_bytes();
+            if let Some(prev_table_bytes) = usage.tables.get(&table_id) {
+                database_bytes = database_bytes - prev_table_bytes + table_bytes;
+                if quota.max_storage_per_database_in_bytes != 0
+                    && database_bytes > quota.max_storage_per_database_in_bytes
+                {
+                    return Err(ErrorCode::StorageQuotaExceeded(format!(
+                        "Max storage per database quota exceeded: {} bytes, database '{}' would use {} bytes",
+                        quota.max_storage_per_database_in_bytes, usage.database, database_bytes
+                    )));
+                }
+            }
+            tenant_bytes += database_bytes;
+        }
+
+        if quota.max_storage_in_bytes != 0 && tenant_bytes > quota.max_storage_in_bytes {
+            return Err(ErrorCode::StorageQuotaExceeded(format!(
+                "Max storage quota exceeded: {} bytes, tenant '{}' would use {} bytes",
+                quota.max_storage_in_bytes, tenant, tenant_bytes
+            )));
+        }
+
+        Ok(())
+    }
+}
@@ -27,6 +27,7 @@ use common_pipeline_core::processors::processor::Event;
 use common_pipeline_core::processors::processor::ProcessorPtr;
 use common_pipeline_core::processors::Processor;
 
+use crate::fuse_part::FusePartInfo;
 use crate::io::BlockReader;
 use crate::operations::read::native_data_source::DataChunks;
 use crate::operations::read::native_data_source::NativeDataSourceMeta;
@@ -124,20 +125,31 @@ impl Processor for NativeDeserializeDataTransform {
     }
 
     fn process(&mut self) -> Result<()> {
+        let num_parts = self.chunks.len();
         if let Some(chunks) = self.chunks.last_mut() {
-            let mut arrays = Vec::with_capacity(chunks.len());
-
-            for (index, chunk) in chunks.iter_mut() {
-                if !chunk.has_next() {
-                    // No data anymore
-                    let _ = self.chunks.pop();
-                    return Ok(());
+            let data_block = if chunks.is_empty() {
+                // All the projected columns are added after the block was written,
+                // the whole block is filled with the default values.
+                let part = FusePartInfo::from_part(&self.parts[num_parts - 1])?;
+                let data_block = self.block_reader.build_block(vec![], part.nums_rows)?;
+                let _ = self.chunks.pop();
+                data_block
+            } else {
+                let mut arrays = Vec::with_capacity(chunks.len());
+
+                for (index, chunk) in chunks.iter_mut() {
+                    if !chunk.has_next() {
+                        // No data anymore
+                        let _ = self.chunks.pop();
+                        return Ok(());
+                    }
+
+                    arrays.push((*index, chunk.next_array()?));
                 }
 
-                arrays.push((*index, chunk.next_array()?));
-            }
-
-            let data_block = self.block_reader.build_block(arrays)?;
+                let num_rows = arrays[0].1.len();
+                self.block_reader.build_block(arrays, num_rows)?
+            };
 
             let progress_values = ProgressValues {
                 rows: data_block.num_rows(),
@@ -21,6 +21,8 @@ use common_catalog::plan::PushDownInfo;
 use common_catalog::table_context::TableContext;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_expression::types::DataType;
+use common_expression::Scalar;
 use common_pipeline_core::Pipeline;
 
 use crate::fuse_lazy_part::FuseLazyPartInfo;
@@ -31,7 +33,13 @@ use crate::FuseTable;
 impl FuseTable {
     pub fn create_block_reader(&self, projection: Projection) -> Result<Arc<BlockReader>> {
         let table_schema = self.table_info.schema();
-        BlockReader::create(self.operator.clone(), table_schema, projection)
+        let default_vals = self.field_default_values();
+        BlockReader::create(
+            self.operator.clone(),
+            table_schema,
+            projection,
+            default_vals,
+        )
     }
 
     // Build the block reader.
@@ -42,6 +50,25 @@ impl FuseTable {
         ))
     }
 
+    /// The default values of the table fields, which are used to fill the columns missing
+    /// from the blocks written before the columns are added.
+    /// The values are evaluated once by `ALTER TABLE ADD COLUMN`, so that every read of
+    /// the old blocks sees the same value even if the default expression is not deterministic.
+    fn field_default_values(&self) -> Vec<Scalar> {
+        let column_default_values = &self.table_info.meta.column_default_values;
+        self.table_info
+            .schema()
+            .fields()
+            .iter()
+            .map(
+                |field| match column_default_values.get(&field.column_id()) {
+                    Some(value) => value.clone(),
+                    None => DataType::from(field.data_type()).default_value(),
+                },
+            )
+            .collect()
+    }
+
     fn adjust_io_request(&self, ctx: &Arc<dyn TableContext>) -> Result<usize> {
         let max_threads = ctx.get_settings().get_max_threads()? as usize;
         let max_io_requests = ctx.get_settings().get_max_storage_io_requests()? as usize;
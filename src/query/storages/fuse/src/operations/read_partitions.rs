@@ -48,6 +48,24 @@ impl FuseTable {
     ) -> Result<(PartStatistics, Partitions)> {
         debug!("fuse table do read partitions, push downs:{:?}", push_downs);
 
+        if let Some(entries) = &self.appended_entries {
+            let segments_location = Self::appended_segments(entries);
+            let summary = entries
+                .iter()
+                .map(|entry| entry.segment_info.blocks.len())
+                .sum();
+            return self
+                .prune_snapshot_blocks(
+                    ctx,
+                    self.operator.clone(),
+                    push_downs,
+                    self.table_info.clone(),
+                    segments_location,
+                    summary,
+                )
+                .await;
+        }
+
         let snapshot = self.read_table_snapshot().await?;
         match snapshot {
             Some(snapshot) => {
@@ -113,6 +131,7 @@ impl FuseTable {
             table_info.schema(),
             &push_downs,
             segments_location,
+            self.skipping_indexes(ctx.clone())?,
         )
         .await?
         .into_iter()
@@ -137,7 +156,13 @@ impl FuseTable {
         partitions_total: usize,
     ) -> Result<(PartStatistics, Partitions)> {
         let arrow_schema = schema.to_arrow();
-        let column_leaves = ColumnLeaves::new_from_schema(&arrow_schema);
+        let leaf_column_ids = schema
+            .to_leaf_column_ids()
+            .into_iter()
+            .map(|column_id| column_id as usize)
+            .collect::<Vec<_>>();
+        let column_leaves =
+            ColumnLeaves::new_from_schema_with_leaf_ids(&arrow_schema, &leaf_column_ids);
 
         let partitions_scanned = block_metas.len();
 
@@ -251,8 +276,9 @@ impl FuseTable {
             for column in &columns {
                 let indices = &column.leaf_ids;
                 for index in indices {
-                    let col_metas = &block_meta.col_metas[&(*index as u32)];
-                    statistics.read_bytes += col_metas.len as usize;
+                    if let Some(col_metas) = block_meta.col_metas.get(&(*index as u32)) {
+                        statistics.read_bytes += col_metas.len as usize;
+                    }
                 }
             }
 
@@ -299,9 +325,10 @@ impl FuseTable {
         for column in &columns {
             let indices = &column.leaf_ids;
             for index in indices {
-                let column_meta = &meta.col_metas[&(*index as u32)];
-
-                columns_meta.insert(*index, column_meta.clone());
+                // The columns added after the block was written are not in the block.
+                if let Some(column_meta) = meta.col_metas.get(&(*index as u32)) {
+                    columns_meta.insert(*index, column_meta.clone());
+                }
             }
         }
 
@@ -36,6 +36,7 @@ use crate::operations::FuseTableSink;
 use crate::operations::ReclusterMutator;
 use crate::pipelines::Pipeline;
 use crate::pruning::BlockPruner;
+use crate::table_functions::ClusteringInformation;
 use crate::FuseTable;
 use crate::TableMutator;
 use crate::DEFAULT_AVG_DEPTH_THRESHOLD;
@@ -44,6 +45,38 @@ use crate::FUSE_OPT_KEY_BLOCK_PER_SEGMENT;
 use crate::FUSE_OPT_KEY_ROW_AVG_DEPTH_THRESHOLD;
 
 impl FuseTable {
+    /// The average depth of blocks above which the blocks are reclustered.
+    fn recluster_depth_threshold(&self, block_count: u64) -> f64 {
+        let avg_depth_threshold = self.get_option(
+            FUSE_OPT_KEY_ROW_AVG_DEPTH_THRESHOLD,
+            DEFAULT_AVG_DEPTH_THRESHOLD,
+        );
+        if block_count > 100 {
+            block_count as f64 * avg_depth_threshold
+        } else {
+            1.0
+        }
+    }
+
+    /// Whether the blocks of the table overlap deep enough on the cluster key to be
+    /// reclustered, judged by the clustering statistics of the current snapshot.
+    pub async fn need_recluster(&self, ctx: Arc<dyn TableContext>) -> Result<bool> {
+        if self.cluster_key_meta.is_none() {
+            return Ok(false);
+        }
+        let block_count = match self.read_table_snapshot().await? {
+            Some(snapshot) => snapshot.summary.block_count,
+            None => return Ok(false),
+        };
+
+        let cluster_keys = self.cluster_keys(ctx.clone());
+        let info = ClusteringInformation::new(ctx, self, String::new(), cluster_keys);
+        Ok(match info.get_average_depth().await? {
+            Some(average_depth) => average_depth > self.recluster_depth_threshold(block_count),
+            None => false,
+        })
+    }
+
     pub(crate) async fn do_recluster(
         &self,
         ctx: Arc<dyn TableContext>,
@@ -63,6 +96,7 @@ impl FuseTable {
         };
 
         let schema = self.table_info.schema();
+        let skipping_indexes = self.skipping_indexes(ctx.clone())?;
         let segments_locations = snapshot.segments.clone();
         let block_metas = BlockPruner::prune(
             &ctx,
@@ -70,6 +104,7 @@ impl FuseTable {
             schema,
             &push_downs,
             segments_locations,
+            skipping_indexes.clone(),
         )
         .await?;
 
@@ -87,16 +122,7 @@ impl FuseTable {
         });
 
         let block_compact_thresholds = self.get_block_compact_thresholds();
-        let avg_depth_threshold = self.get_option(
-            FUSE_OPT_KEY_ROW_AVG_DEPTH_THRESHOLD,
-            DEFAULT_AVG_DEPTH_THRESHOLD,
-        );
-        let block_count = snapshot.summary.block_count;
-        let threshold = if block_count > 100 {
-            block_count as f64 * avg_depth_threshold
-        } else {
-            1.0
-        };
+        let threshold = self.recluster_depth_threshold(snapshot.summary.block_count);
         let block_per_seg =
             self.get_option(FUSE_OPT_KEY_BLOCK_PER_SEGMENT, DEFAULT_BLOCK_PER_SEGMENT);
         let mut mutator = ReclusterMutator::try_create(
@@ -205,6 +231,7 @@ impl FuseTable {
                 cluster_stats_gen.clone(),
                 block_compact_thresholds,
                 self.table_info.schema(),
+                skipping_indexes.clone(),
                 self.storage_format,
                 self.table_compression,
                 None,
@@ -0,0 +1,82 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+
+use common_catalog::table::Table;
+use common_catalog::table_context::TableContext;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::SkippingIndexMeta;
+use storages_common_index::SkippingIndex;
+
+use crate::FuseTable;
+
+impl FuseTable {
+    pub async fn do_create_skipping_index(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        name: &str,
+        meta: SkippingIndexMeta,
+        if_not_exists: bool,
+    ) -> Result<()> {
+        if self.table_info.meta.skipping_indexes.contains_key(name) {
+            return if if_not_exists {
+                Ok(())
+            } else {
+                Err(ErrorCode::IndexAlreadyExists(format!(
+                    "Index '{}' already exists on table '{}'",
+                    name,
+                    self.name()
+                )))
+            };
+        }
+
+        // Validate the expression and the index type before committing,
+        // the index only applies to blocks written afterwards.
+        let expr =
+            self.parse_skipping_index_expr(ctx.clone(), Arc::new(self.clone()), &meta.expr)?;
+        SkippingIndex::create(name, &meta, expr)?;
+
+        let mut new_table_meta = self.get_table_info().meta.clone();
+        new_table_meta
+            .skipping_indexes
+            .insert(name.to_string(), meta);
+
+        self.commit_table_meta(ctx, new_table_meta).await
+    }
+
+    pub async fn do_drop_skipping_index(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        name: &str,
+        if_exists: bool,
+    ) -> Result<()> {
+        if !self.table_info.meta.skipping_indexes.contains_key(name) {
+            return if if_exists {
+                Ok(())
+            } else {
+                Err(ErrorCode::UnknownIndex(format!(
+                    "Unknown index '{}' on table '{}'",
+                    name,
+                    self.name()
+                )))
+            };
+        }
+
+        let mut new_table_meta = self.get_table_info().meta.clone();
+        new_table_meta.skipping_indexes.remove(name);
+
+        self.commit_table_meta(ctx, new_table_meta).await
+    }
+}
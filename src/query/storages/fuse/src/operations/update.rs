@@ -0,0 +1,162 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::sync::Arc;
+
+use common_catalog::plan::Partitions;
+use common_catalog::plan::PartitionsShuffleKind;
+use common_catalog::plan::Projection;
+use common_catalog::plan::PushDownInfo;
+use common_catalog::table_context::TableContext;
+use common_exception::Result;
+use common_expression::RemoteExpr;
+use storages_common_table_meta::meta::TableSnapshot;
+
+use crate::operations::mutation::DeletionPartInfo;
+use crate::operations::mutation::MutationSink;
+use crate::operations::mutation::UpdateSource;
+use crate::pipelines::Pipeline;
+use crate::pruning::BlockPruner;
+use crate::FuseTable;
+
+impl FuseTable {
+    /// The flow of Pipeline is as follows:
+    /// +-------------+
+    /// |UpdateSource1| ------
+    /// +-------------+       |      +-----------------+      +------------+
+    /// |     ...     | ...   | ---> |DeletionTransform| ---> |MutationSink|
+    /// +-------------+       |      +-----------------+      +------------+
+    /// |UpdateSourceN| ------
+    /// +-------------+
+    pub async fn do_update(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        filter: Option<RemoteExpr<String>>,
+        col_indices: Vec<usize>,
+        update_list: Vec<(usize, RemoteExpr<String>)>,
+        pipeline: &mut Pipeline,
+    ) -> Result<()> {
+        let snapshot_opt = self.read_table_snapshot().await?;
+
+        // check if table is empty
+        let snapshot = if let Some(val) = snapshot_opt {
+            val
+        } else {
+            // no snapshot, no update
+            return Ok(());
+        };
+
+        if snapshot.summary.row_count == 0 {
+            // empty snapshot, no update
+            return Ok(());
+        }
+
+        let mut filter = filter;
+        if col_indices.is_empty() && filter.is_some() {
+            // the filter is unrelated to the value of rows, e.g. `update t set a = 1 where 1 = 1`,
+            // so either all of the rows or none of them should be updated.
+            let filter_expr = filter.as_ref().unwrap();
+            if !self.try_eval_const(ctx.clone(), &self.table_info.schema(), filter_expr)? {
+                // do nothing.
+                return Ok(());
+            }
+            filter = None;
+        }
+
+        self.try_add_update_source(ctx.clone(), filter, update_list, &snapshot, pipeline)
+            .await?;
+
+        self.try_add_deletion_transform(ctx.clone(), snapshot.segments.clone(), pipeline)?;
+
+        pipeline.add_sink(|input| {
+            MutationSink::try_create(self, ctx.clone(), snapshot.clone(), input)
+        })?;
+        Ok(())
+    }
+
+    async fn try_add_update_source(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        filter: Option<RemoteExpr<String>>,
+        update_list: Vec<(usize, RemoteExpr<String>)>,
+        base_snapshot: &TableSnapshot,
+        pipeline: &mut Pipeline,
+    ) -> Result<()> {
+        // all the columns are needed to rewrite the blocks.
+        let projection = Projection::Columns(self.all_the_columns_ids());
+        let push_down = Some(PushDownInfo {
+            projection: Some(projection.clone()),
+            filters: filter.iter().cloned().collect(),
+            ..PushDownInfo::default()
+        });
+
+        let segments_location = base_snapshot.segments.clone();
+        let block_metas = BlockPruner::prune(
+            &ctx,
+            self.operator.clone(),
+            self.table_info.schema(),
+            &push_down,
+            segments_location,
+            self.skipping_indexes(ctx.clone())?,
+        )
+        .await?;
+
+        let mut index_stats = Vec::with_capacity(block_metas.len());
+        let mut metas = Vec::with_capacity(block_metas.len());
+        for (index, block_meta) in block_metas.into_iter() {
+            index_stats.push((index, block_meta.cluster_stats.clone()));
+            metas.push(block_meta);
+        }
+
+        let (_, inner_parts) = self.read_partitions_with_metas(
+            ctx.clone(),
+            self.table_info.schema(),
+            None,
+            metas,
+            base_snapshot.summary.block_count as usize,
+        )?;
+
+        let parts = Partitions::create(
+            PartitionsShuffleKind::Mod,
+            index_stats
+                .into_iter()
+                .zip(inner_parts.partitions.into_iter())
+                .map(|((a, b), c)| DeletionPartInfo::create(a, b, c))
+                .collect(),
+        );
+        ctx.try_set_partitions(parts)?;
+
+        let block_reader = self.create_block_reader(projection)?;
+        let filter = Arc::new(filter);
+        let update_list = Arc::new(update_list);
+
+        let skipping_indexes = self.skipping_indexes(ctx.clone())?;
+        let max_threads = ctx.get_settings().get_max_threads()? as usize;
+        // Add source pipe.
+        pipeline.add_source(
+            |output| {
+                UpdateSource::try_create(
+                    ctx.clone(),
+                    output,
+                    self,
+                    block_reader.clone(),
+                    filter.clone(),
+                    update_list.clone(),
+                    skipping_indexes.clone(),
+                )
+            },
+            max_threads,
+        )
+    }
+}
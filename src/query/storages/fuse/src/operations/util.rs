@@ -17,10 +17,16 @@ use std::collections::HashMap;
 use common_arrow::parquet::metadata::ThriftFileMetaData;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_expression::TableSchema;
 use storages_common_table_meta::meta::ColumnId;
 use storages_common_table_meta::meta::ColumnMeta;
 
-pub fn column_metas(file_meta: &ThriftFileMetaData) -> Result<HashMap<ColumnId, ColumnMeta>> {
+/// Returns the metas of the leaf columns of the parquet file written by `schema`,
+/// which are keyed by the column ids of the leaf columns.
+pub fn column_metas(
+    file_meta: &ThriftFileMetaData,
+    schema: &TableSchema,
+) -> Result<HashMap<ColumnId, ColumnMeta>> {
     // currently we use one group only
     let num_row_groups = file_meta.row_groups.len();
     if num_row_groups != 1 {
@@ -30,6 +36,7 @@ pub fn column_metas(file_meta: &ThriftFileMetaData) -> Result<HashMap<ColumnId,
         )));
     }
     let row_group = &file_meta.row_groups[0];
+    let leaf_column_ids = schema.to_leaf_column_ids();
     let mut col_metas = HashMap::with_capacity(row_group.columns.len());
     for (idx, col_chunk) in row_group.columns.iter().enumerate() {
         match &col_chunk.meta_data {
@@ -50,7 +57,7 @@ pub fn column_metas(file_meta: &ThriftFileMetaData) -> Result<HashMap<ColumnId,
                     len: col_len as u64,
                     num_values,
                 };
-                col_metas.insert(idx as u32, res);
+                col_metas.insert(leaf_column_ids[idx], res);
             }
             None => {
                 return Err(ErrorCode::ParquetFileInvalid(format!(
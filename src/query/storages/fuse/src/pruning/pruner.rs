@@ -20,10 +20,15 @@ use common_expression::type_check::check_function;
 use common_expression::ConstantFolder;
 use common_expression::Domain;
 use common_expression::Expr;
+use common_expression::TableField;
 use common_expression::TableSchemaRef;
 use common_functions::scalars::BUILTIN_FUNCTIONS;
 use opendal::Operator;
+use storages_common_index::eval_block_skipping_index;
+use storages_common_index::is_skipping_index_applicable;
 use storages_common_index::BlockFilter;
+use storages_common_index::SkippingIndex;
+use storages_common_table_meta::meta::BlockSkippingIndex;
 use storages_common_table_meta::meta::Location;
 
 use crate::io::BlockFilterReader;
@@ -37,8 +42,8 @@ pub trait Pruner {
 struct FilterPruner {
     ctx: Arc<dyn TableContext>,
 
-    /// indices that should be loaded from filter block
-    index_columns: Vec<String>,
+    /// fields of the indices that should be loaded from filter block
+    index_fields: Vec<TableField>,
 
     /// the expression that would be evaluate
     filter_expression: Expr<String>,
@@ -53,14 +58,14 @@ struct FilterPruner {
 impl FilterPruner {
     pub fn new(
         ctx: Arc<dyn TableContext>,
-        index_columns: Vec<String>,
+        index_fields: Vec<TableField>,
         filter_expression: Expr<String>,
         dal: Operator,
         data_schema: TableSchemaRef,
     ) -> Self {
         Self {
             ctx,
-            index_columns,
+            index_fields,
             filter_expression,
             dal,
             data_schema,
@@ -79,7 +84,7 @@ impl Pruner for FilterPruner {
                 self.dal.clone(),
                 &self.data_schema,
                 &self.filter_expression,
-                &self.index_columns,
+                &self.index_fields,
                 loc,
                 index_length,
             )
@@ -98,6 +103,45 @@ impl Pruner for FilterPruner {
     }
 }
 
+struct SkippingIndexPruner {
+    ctx: Arc<dyn TableContext>,
+
+    /// the skipping indexes defined on the table
+    indexes: Vec<SkippingIndex>,
+
+    /// the expression that would be evaluate
+    filter_expression: Expr<String>,
+
+    /// the data accessor
+    dal: Operator,
+}
+
+#[async_trait::async_trait]
+impl Pruner for SkippingIndexPruner {
+    async fn should_keep(&self, index_location: &Option<Location>, _index_length: u64) -> bool {
+        if let Some(loc) = index_location {
+            match should_keep_by_skipping_index(
+                self.ctx.clone(),
+                self.dal.clone(),
+                &self.indexes,
+                &self.filter_expression,
+                loc,
+            )
+            .await
+            {
+                Ok(v) => v,
+                Err(e) => {
+                    // swallow exceptions intentionally, corrupted index should not prevent execution
+                    tracing::warn!("failed to apply skipping index, returning ture. {}", e);
+                    true
+                }
+            }
+        } else {
+            true
+        }
+    }
+}
+
 /// Try to build a pruner.
 ///
 /// if `filter_expr` is empty, or is not applicable, e.g. have no point queries
@@ -147,16 +191,16 @@ pub fn new_filter_pruner(
             point_query_cols
         );
 
-        if !point_query_cols.is_empty() {
-            // convert to filter column names
-            let filter_block_cols = point_query_cols
-                .iter()
-                .map(|n| BlockFilter::build_filter_column_name(n))
-                .collect();
+        // the names of filter columns depend on the version of index, which are built for each block.
+        let index_fields = point_query_cols
+            .iter()
+            .filter_map(|n| schema.field_with_name(n).ok().cloned())
+            .collect::<Vec<_>>();
 
+        if !index_fields.is_empty() {
             return Ok(Some(Arc::new(FilterPruner::new(
                 ctx.clone(),
-                filter_block_cols,
+                index_fields,
                 optimized_expr,
                 dal,
                 schema.clone(),
@@ -168,6 +212,47 @@ pub fn new_filter_pruner(
     Ok(None)
 }
 
+/// Try to build a pruner backed by the skipping indexes of the table.
+///
+/// None will be returned, if there are no filters or none of the indexes is
+/// referred by the filters.
+pub fn new_skipping_index_pruner(
+    ctx: &Arc<dyn TableContext>,
+    filter_exprs: Option<&[Expr<String>]>,
+    indexes: Vec<SkippingIndex>,
+    dal: Operator,
+) -> Result<Option<Arc<dyn Pruner + Send + Sync>>> {
+    if indexes.is_empty() {
+        return Ok(None);
+    }
+
+    if let Some(exprs) = filter_exprs {
+        if exprs.is_empty() {
+            return Ok(None);
+        }
+
+        let expr: Expr<String> = exprs
+            .iter()
+            .cloned()
+            .reduce(|lhs, rhs| {
+                check_function(None, "and", &[], &[lhs, rhs], &BUILTIN_FUNCTIONS).unwrap()
+            })
+            .unwrap();
+
+        if is_skipping_index_applicable(&indexes, &expr) {
+            return Ok(Some(Arc::new(SkippingIndexPruner {
+                ctx: ctx.clone(),
+                indexes,
+                filter_expression: expr,
+                dal,
+            })));
+        } else {
+            tracing::debug!("no skipping index is applicable to the filters");
+        }
+    }
+    Ok(None)
+}
+
 mod util {
     use common_exception::ErrorCode;
     use storages_common_index::FilterEvalResult;
@@ -179,13 +264,18 @@ mod util {
         dal: Operator,
         schema: &TableSchemaRef,
         filter_expr: &Expr<String>,
-        filter_col_names: &[String],
+        index_fields: &[TableField],
         index_location: &Location,
         index_length: u64,
     ) -> Result<bool> {
+        let filter_col_names = index_fields
+            .iter()
+            .map(|field| BlockFilter::build_filter_column_name(index_location.1, field))
+            .collect::<Vec<_>>();
+
         // load the relevant index columns
         let maybe_filter = index_location
-            .read_filter(ctx.clone(), dal, filter_col_names, index_length)
+            .read_filter(ctx.clone(), dal, &filter_col_names, index_length)
             .await;
 
         match maybe_filter {
@@ -207,4 +297,22 @@ mod util {
             Err(e) => Err(e),
         }
     }
+
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn should_keep_by_skipping_index(
+        ctx: Arc<dyn TableContext>,
+        dal: Operator,
+        indexes: &[SkippingIndex],
+        filter_expr: &Expr<String>,
+        index_location: &Location,
+    ) -> Result<bool> {
+        let data = dal.object(&index_location.0).read().await?;
+        let skipping_index: BlockSkippingIndex = serde_json::from_slice(&data)?;
+        Ok(eval_block_skipping_index(
+            ctx.try_get_function_context()?,
+            indexes,
+            &skipping_index,
+            filter_expr,
+        )? != FilterEvalResult::MustFalse)
+    }
 }
@@ -29,6 +29,7 @@ use common_expression::TableSchemaRef;
 use common_functions::scalars::BUILTIN_FUNCTIONS;
 use futures::future;
 use opendal::Operator;
+use storages_common_index::SkippingIndex;
 use storages_common_pruner::BlockMetaIndex;
 use storages_common_pruner::LimiterPruner;
 use storages_common_pruner::LimiterPrunerCreator;
@@ -53,10 +54,40 @@ struct PruningContext {
     limiter: LimiterPruner,
     range_pruner: Arc<dyn RangePruner + Send + Sync>,
     filter_pruner: Option<Arc<dyn Pruner + Send + Sync>>,
+    skipping_index_pruner: Option<Arc<dyn Pruner + Send + Sync>>,
     rt: Arc<Runtime>,
     semaphore: Arc<Semaphore>,
 }
 
+impl PruningContext {
+    // returns true, if the block should NOT be pruned by the bloom filter and skipping indexes.
+    async fn should_keep_block(&self, block_meta: &BlockMeta) -> bool {
+        if let Some(filter_pruner) = &self.filter_pruner {
+            if !filter_pruner
+                .should_keep(
+                    &block_meta.bloom_filter_index_location,
+                    block_meta.bloom_filter_index_size,
+                )
+                .await
+            {
+                return false;
+            }
+        }
+        if let Some(skipping_index_pruner) = &self.skipping_index_pruner {
+            if !skipping_index_pruner
+                .should_keep(
+                    &block_meta.skipping_index_location,
+                    block_meta.skipping_index_size,
+                )
+                .await
+            {
+                return false;
+            }
+        }
+        true
+    }
+}
+
 pub struct BlockPruner;
 impl BlockPruner {
     // prune blocks by utilizing min_max index and filter, according to the pushdowns
@@ -67,6 +98,7 @@ impl BlockPruner {
         schema: TableSchemaRef,
         push_down: &Option<PushDownInfo>,
         segment_locs: Vec<Location>,
+        skipping_indexes: Vec<SkippingIndex>,
     ) -> Result<Vec<(BlockMetaIndex, Arc<BlockMeta>)>> {
         if segment_locs.is_empty() {
             return Ok(vec![]);
@@ -100,6 +132,15 @@ impl BlockPruner {
         let filter_pruner =
             pruner::new_filter_pruner(ctx, filter_exprs.as_deref(), &schema, dal.clone())?;
 
+        // prepare the skipping index pruner.
+        // None will be returned, if none of the skipping indexes is referred by the filters
+        let skipping_index_pruner = pruner::new_skipping_index_pruner(
+            ctx,
+            filter_exprs.as_deref(),
+            skipping_indexes,
+            dal.clone(),
+        )?;
+
         // 2. constraint the degree of parallelism
         let max_threads = ctx.get_settings().get_max_threads()? as usize;
         let max_concurrency = {
@@ -127,6 +168,7 @@ impl BlockPruner {
             limiter: limiter.clone(),
             range_pruner: range_pruner.clone(),
             filter_pruner,
+            skipping_index_pruner,
             rt: pruning_runtime.clone(),
             semaphore: semaphore.clone(),
         });
@@ -216,8 +258,8 @@ impl BlockPruner {
             .range_pruner
             .should_keep(&segment_info.summary.col_stats)
         {
-            if let Some(filter_pruner) = &pruning_ctx.filter_pruner {
-                Self::prune_blocks(&pruning_ctx, filter_pruner, segment_idx, &segment_info).await?
+            if pruning_ctx.filter_pruner.is_some() || pruning_ctx.skipping_index_pruner.is_some() {
+                Self::prune_blocks(&pruning_ctx, segment_idx, &segment_info).await?
             } else {
                 // if no available filter pruners, just prune the blocks by
                 // using zone map index, and do not spawn async tasks
@@ -232,7 +274,6 @@ impl BlockPruner {
     #[tracing::instrument(level = "debug", skip_all)]
     async fn prune_blocks(
         pruning_ctx: &Arc<PruningContext>,
-        filter_pruner: &Arc<dyn Pruner + Send + Sync>,
         segment_idx: usize,
         segment_info: &SegmentInfo,
     ) -> Result<Vec<(BlockMetaIndex, Arc<BlockMeta>)>> {
@@ -253,13 +294,11 @@ impl BlockPruner {
                 if pruning_ctx.range_pruner.should_keep(&block_meta.col_stats) {
                     // not pruned by block zone map index,
                     let ctx = pruning_ctx.clone();
-                    let filter_pruner = filter_pruner.clone();
-                    let index_location = block_meta.bloom_filter_index_location.clone();
-                    let index_size = block_meta.bloom_filter_index_size;
+                    let block_meta = block_meta.clone();
                     let v: BlockPruningFuture = Box::new(move |permit: OwnedSemaphorePermit| {
                         Box::pin(async move {
                             let _permit = permit;
-                            let keep = filter_pruner.should_keep(&index_location, index_size).await
+                            let keep = ctx.should_keep_block(&block_meta).await
                                 && ctx.limiter.within_limit(row_count);
                             (block_idx, keep)
                         })
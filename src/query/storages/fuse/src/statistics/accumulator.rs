@@ -57,6 +57,8 @@ impl StatisticsAccumulator {
         block_statistics: BlockStatistics,
         bloom_filter_index_location: Option<Location>,
         bloom_filter_index_size: u64,
+        skipping_index_location: Option<Location>,
+        skipping_index_size: u64,
         block_compression: meta::Compression,
     ) -> Result<()> {
         self.add(
@@ -65,6 +67,8 @@ impl StatisticsAccumulator {
             block_statistics,
             bloom_filter_index_location,
             bloom_filter_index_size,
+            skipping_index_location,
+            skipping_index_size,
             block_compression,
         )
     }
@@ -77,6 +81,8 @@ impl StatisticsAccumulator {
     ) -> Result<()> {
         let bloom_filter_index_location = block_meta.bloom_filter_index_location;
         let bloom_filter_index_size = block_meta.bloom_filter_index_size;
+        let skipping_index_location = block_meta.skipping_index_location;
+        let skipping_index_size = block_meta.skipping_index_size;
         let file_size = block_meta.file_size;
         let col_metas = block_meta.col_metas;
 
@@ -86,6 +92,8 @@ impl StatisticsAccumulator {
             block_statistics,
             bloom_filter_index_location,
             bloom_filter_index_size,
+            skipping_index_location,
+            skipping_index_size,
             block_compression,
         )
     }
@@ -101,10 +109,12 @@ impl StatisticsAccumulator {
         block_statistics: BlockStatistics,
         bloom_filter_index_location: Option<Location>,
         bloom_filter_index_size: u64,
+        skipping_index_location: Option<Location>,
+        skipping_index_size: u64,
         block_compression: meta::Compression,
     ) -> Result<()> {
         self.file_size += file_size;
-        self.index_size += bloom_filter_index_size;
+        self.index_size += bloom_filter_index_size + skipping_index_size;
         self.summary_block_count += 1;
         self.in_memory_size += block_statistics.block_bytes_size;
         self.summary_row_count += block_statistics.block_rows_size;
@@ -134,6 +144,8 @@ impl StatisticsAccumulator {
             data_location,
             bloom_filter_index_location,
             bloom_filter_index_size,
+            skipping_index_location,
+            skipping_index_size,
             block_compression,
         )));
 
@@ -21,6 +21,7 @@ use common_expression::types::ValueType;
 use common_expression::Column;
 use common_expression::DataBlock;
 use common_expression::Scalar;
+use common_expression::TableSchemaRef;
 use common_functions::aggregates::eval_aggr;
 use storages_common_index::MinMaxIndex;
 use storages_common_index::SupportedType;
@@ -47,16 +48,20 @@ pub fn get_traverse_columns_dfs(data_block: &DataBlock) -> traverse::TraverseRes
     traverse::traverse_columns_dfs(data_block.columns())
 }
 
+/// Generate the statistics of the leaf columns of the block, which are keyed by
+/// the column ids of the leaf columns in `schema`.
 pub fn gen_columns_statistics(
     data_block: &DataBlock,
     column_distinct_count: Option<HashMap<usize, usize>>,
+    schema: &TableSchemaRef,
 ) -> Result<StatisticsOfColumns> {
     let mut statistics = StatisticsOfColumns::new();
     let data_block = data_block.convert_to_full();
     let rows = data_block.num_rows();
 
     let leaves = get_traverse_columns_dfs(&data_block)?;
-    for (idx, (col_idx, col, data_type)) in leaves.iter().enumerate() {
+    let leaf_column_ids = schema.to_leaf_column_ids();
+    for ((col_idx, col, data_type), column_id) in leaves.iter().zip(leaf_column_ids) {
         if !MinMaxIndex::is_supported_type(data_type) {
             continue;
         }
@@ -126,7 +131,7 @@ pub fn gen_columns_statistics(
             distinct_of_values: Some(distinct_of_values),
         };
 
-        statistics.insert(idx as u32, col_stats);
+        statistics.insert(column_id, col_stats);
     }
     Ok(statistics)
 }
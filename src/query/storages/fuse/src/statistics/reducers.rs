@@ -19,6 +19,7 @@ use common_exception::Result;
 use common_expression::BlockCompactThresholds;
 use common_expression::DataBlock;
 use common_expression::Scalar;
+use common_expression::TableSchema;
 use storages_common_table_meta::meta::BlockMeta;
 use storages_common_table_meta::meta::ColumnId;
 use storages_common_table_meta::meta::ColumnStatistics;
@@ -28,9 +29,15 @@ use storages_common_table_meta::meta::StatisticsOfColumns;
 use crate::statistics::column_statistic::calc_column_distinct_of_values;
 use crate::statistics::column_statistic::get_traverse_columns_dfs;
 
+/// Reduce the statistics of columns of blocks. If `data_block` is given, which is the merged
+/// block of the same `schema`, the distinct count of values is calculated from it.
+///
+/// The columns added after some of the blocks were written have no statistics in these
+/// blocks, such columns are not reduced, because the statistics of the other blocks do not
+/// cover the default values of them.
 pub fn reduce_block_statistics<T: Borrow<StatisticsOfColumns>>(
     stats_of_columns: &[T],
-    data_block: Option<&DataBlock>,
+    data_block: Option<(&DataBlock, &TableSchema)>,
 ) -> Result<StatisticsOfColumns> {
     // Combine statistics of a column into `Vec`, that is:
     // from : `&[HashMap<ColumnId, ColumnStatistics>]`
@@ -45,8 +52,15 @@ pub fn reduce_block_statistics<T: Borrow<StatisticsOfColumns>>(
         )
     });
 
-    let leaves = if let Some(data_block) = data_block {
-        Some(get_traverse_columns_dfs(data_block)?)
+    let leaves = if let Some((data_block, schema)) = data_block {
+        let leaves = get_traverse_columns_dfs(data_block)?;
+        Some(
+            schema
+                .to_leaf_column_ids()
+                .into_iter()
+                .zip(leaves.into_iter())
+                .collect::<HashMap<_, _>>(),
+        )
     } else {
         None
     };
@@ -57,6 +71,7 @@ pub fn reduce_block_statistics<T: Borrow<StatisticsOfColumns>>(
     let len = stats_of_columns.len();
     col_to_stats_lit
         .iter()
+        .filter(|(_, stats)| stats.len() == len)
         .try_fold(HashMap::with_capacity(len), |mut acc, (id, stats)| {
             let mut min_stats = Vec::with_capacity(stats.len());
             let mut max_stats = Vec::with_capacity(stats.len());
@@ -92,8 +107,8 @@ pub fn reduce_block_statistics<T: Borrow<StatisticsOfColumns>>(
                 .unwrap_or(Scalar::Null);
 
             let distinct_of_values = match data_block {
-                Some(data_block) => {
-                    if let Some(col) = leaves.as_ref().unwrap().get(*id as usize) {
+                Some((data_block, _)) => {
+                    if let Some(col) = leaves.as_ref().unwrap().get(id) {
                         let column = col.1.convert_to_full_column(&col.2, data_block.num_rows());
                         calc_column_distinct_of_values(&column, &col.2, data_block.num_rows())?
                     } else {
@@ -122,21 +137,32 @@ pub fn merge_statistics(l: &Statistics, r: &Statistics) -> Result<Statistics> {
         uncompressed_byte_size: l.uncompressed_byte_size + r.uncompressed_byte_size,
         compressed_byte_size: l.compressed_byte_size + r.compressed_byte_size,
         index_size: l.index_size + r.index_size,
-        col_stats: reduce_block_statistics(&[&l.col_stats, &r.col_stats], None)?,
+        col_stats: merge_col_stats(l, r)?,
     };
     Ok(s)
 }
 
 pub fn merge_statistics_mut(l: &mut Statistics, r: &Statistics) -> Result<()> {
+    l.col_stats = merge_col_stats(l, r)?;
     l.row_count += r.row_count;
     l.block_count += r.block_count;
     l.uncompressed_byte_size += r.uncompressed_byte_size;
     l.compressed_byte_size += r.compressed_byte_size;
     l.index_size += r.index_size;
-    l.col_stats = reduce_block_statistics(&[&l.col_stats, &r.col_stats], None)?;
     Ok(())
 }
 
+// The empty statistics are used as the initial value of merging, which have no statistics of columns.
+fn merge_col_stats(l: &Statistics, r: &Statistics) -> Result<StatisticsOfColumns> {
+    if l.row_count == 0 {
+        Ok(r.col_stats.clone())
+    } else if r.row_count == 0 {
+        Ok(l.col_stats.clone())
+    } else {
+        reduce_block_statistics(&[&l.col_stats, &r.col_stats], None)
+    }
+}
+
 pub fn reduce_statistics<T: Borrow<Statistics>>(stats: &[T]) -> Result<Statistics> {
     let mut statistics = Statistics::default();
     for item in stats {
@@ -0,0 +1,24 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod stream_part;
+mod stream_source;
+mod stream_table;
+
+pub use stream_part::ChangeAction;
+pub use stream_part::StreamPartInfo;
+pub use stream_table::StreamTable;
+pub use stream_table::CHANGE_ACTION_COLUMN;
+pub use stream_table::CHANGE_ROW_ID_COLUMN;
+pub use stream_table::STREAM_ENGINE;
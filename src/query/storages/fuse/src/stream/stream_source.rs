@@ -0,0 +1,127 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_catalog::plan::Projection;
+use common_catalog::table::Table;
+use common_catalog::table_context::TableContext;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::types::DataType;
+use common_expression::types::StringType;
+use common_expression::utils::FromData;
+use common_expression::BlockEntry;
+use common_expression::DataBlock;
+use common_expression::Value;
+
+use crate::io::BlockReader;
+use crate::io::ReadSettings;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::ProcessorPtr;
+use crate::pipelines::processors::AsyncSource;
+use crate::pipelines::processors::AsyncSourcer;
+use crate::stream::stream_part::ChangeAction;
+use crate::stream::stream_part::StreamPartInfo;
+use crate::FuseTable;
+
+/// Reads the changed blocks of a stream, appending the change columns to each block.
+pub struct StreamSource {
+    ctx: Arc<dyn TableContext>,
+    source: (String, String, String),
+    block_reader: Option<Arc<BlockReader>>,
+}
+
+impl StreamSource {
+    pub fn create(
+        ctx: Arc<dyn TableContext>,
+        output: Arc<OutputPort>,
+        source: (String, String, String),
+    ) -> Result<ProcessorPtr> {
+        AsyncSourcer::create(ctx.clone(), output, StreamSource {
+            ctx,
+            source,
+            block_reader: None,
+        })
+    }
+
+    async fn block_reader(&mut self) -> Result<Arc<BlockReader>> {
+        if let Some(block_reader) = &self.block_reader {
+            return Ok(block_reader.clone());
+        }
+
+        let (catalog, database, table) = &self.source;
+        let table = self.ctx.get_table(catalog, database, table).await?;
+        let table = FuseTable::try_from_table(table.as_ref())?;
+        let projection = Projection::Columns((0..table.schema().num_fields()).collect());
+        let block_reader = table.create_block_reader(projection)?;
+        self.block_reader = Some(block_reader.clone());
+        Ok(block_reader)
+    }
+}
+
+#[async_trait::async_trait]
+impl AsyncSource for StreamSource {
+    const NAME: &'static str = "StreamSource";
+
+    #[async_trait::unboxed_simple]
+    async fn generate(&mut self) -> Result<Option<DataBlock>> {
+        let part = match self.ctx.try_get_part() {
+            None => return Ok(None),
+            Some(part) => part,
+        };
+        let part = StreamPartInfo::from_part(&part)?;
+
+        let block_reader = self.block_reader().await?;
+        let settings = ReadSettings::from_ctx(&self.ctx)?;
+        let mut block = block_reader
+            .read_parquet_by_meta(&settings, &part.block_meta)
+            .await
+            .map_err(|e| match part.action {
+                // The deleted blocks are only referenced by the snapshot at the offset.
+                ChangeAction::Delete if e.code() == ErrorCode::STORAGE_NOT_FOUND => {
+                    let (_, database, table) = &self.source;
+                    ErrorCode::IllegalStream(format!(
+                        "The offset of the stream has been purged from the source table {}.{}, \
+                         recreate the stream by DROP STREAM and CREATE STREAM to reset its offset: {}",
+                        database,
+                        table,
+                        e.message()
+                    ))
+                }
+                _ => e,
+            })?;
+
+        let num_rows = block.num_rows();
+        let location = &part.block_meta.location.0;
+        let block_id = location
+            .rsplit('/')
+            .next()
+            .and_then(|name| name.split('.').next())
+            .unwrap_or(location);
+        let actions = vec![part.action.as_str().as_bytes().to_vec(); num_rows];
+        let row_ids = (0..num_rows)
+            .map(|row| format!("{}:{}", block_id, row).into_bytes())
+            .collect::<Vec<_>>();
+        block.add_column(BlockEntry {
+            data_type: DataType::String,
+            value: Value::Column(StringType::from_data(actions)),
+        });
+        block.add_column(BlockEntry {
+            data_type: DataType::String,
+            value: Value::Column(StringType::from_data(row_ids)),
+        });
+        Ok(Some(block))
+    }
+}
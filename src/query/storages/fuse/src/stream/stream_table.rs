@@ -0,0 +1,317 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_catalog::catalog::StorageDescription;
+use common_catalog::plan::DataSourcePlan;
+use common_catalog::plan::PartStatistics;
+use common_catalog::plan::Partitions;
+use common_catalog::plan::PartitionsShuffleKind;
+use common_catalog::plan::PushDownInfo;
+use common_catalog::table::Table;
+use common_catalog::table_context::TableContext;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::TableDataType;
+use common_expression::TableField;
+use common_expression::TableSchema;
+use common_expression::TableSchemaRef;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::UpsertTableOptionReq;
+use storages_common_table_meta::caches::LoadParams;
+use storages_common_table_meta::meta::BlockMeta;
+use storages_common_table_meta::meta::Location;
+use storages_common_table_meta::meta::TableSnapshot;
+use storages_common_table_meta::table::OPT_KEY_STREAM_OFFSET;
+use storages_common_table_meta::table::OPT_KEY_STREAM_SOURCE;
+use storages_common_table_meta::table::OPT_KEY_STREAM_SOURCE_TABLE_ID;
+
+use crate::io::MetaReaders;
+use crate::io::SegmentsIO;
+use crate::io::TableMetaLocationGenerator;
+use crate::pipelines::Pipeline;
+use crate::stream::stream_part::ChangeAction;
+use crate::stream::stream_part::StreamPartInfo;
+use crate::stream::stream_source::StreamSource;
+use crate::FuseStorageFormat;
+use crate::FuseTable;
+
+pub const STREAM_ENGINE: &str = "STREAM";
+/// Whether a row of a stream is inserted into or deleted from the source table
+pub const CHANGE_ACTION_COLUMN: &str = "$action";
+/// The position of a row of a stream in the source table
+pub const CHANGE_ROW_ID_COLUMN: &str = "$row_id";
+
+/// A stream tracks the changes made to a fuse table since its offset.
+///
+/// The changes are computed by diffing the blocks of the current snapshot of the
+/// source table with the blocks of the snapshot at the offset, at block granularity:
+/// the rows of a block rewritten by a mutation are all reported as deleted and
+/// the rows of the new block are all reported as inserted.
+///
+/// Reading a stream within a statement that commits a table advances the offset of
+/// the stream to the snapshot read, in the same meta transaction as the commit.
+///
+/// The snapshot at the offset is not protected from being purged, reading a stream
+/// whose offset has been purged fails, until the stream is recreated.
+pub struct StreamTable {
+    table_info: TableInfo,
+    source: (String, String, String),
+    source_table_id: u64,
+    offset: Option<String>,
+}
+
+impl StreamTable {
+    pub fn try_create(table_info: TableInfo) -> Result<Box<dyn Table>> {
+        let options = table_info.options();
+        let source = options
+            .get(OPT_KEY_STREAM_SOURCE)
+            .ok_or_else(|| ErrorCode::Internal("Need `stream_source` when creating StreamTable"))?;
+        let source = serde_json::from_str(source)?;
+        let source_table_id = options
+            .get(OPT_KEY_STREAM_SOURCE_TABLE_ID)
+            .ok_or_else(|| {
+                ErrorCode::Internal("Need `stream_source_table_id` when creating StreamTable")
+            })?
+            .parse::<u64>()?;
+        let offset = options.get(OPT_KEY_STREAM_OFFSET).cloned();
+        Ok(Box::new(StreamTable {
+            table_info,
+            source,
+            source_table_id,
+            offset,
+        }))
+    }
+
+    pub fn description() -> StorageDescription {
+        StorageDescription {
+            engine_name: STREAM_ENGINE.to_string(),
+            comment: "STREAM Storage Engine".to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// The schema of a stream on a table of the given schema.
+    pub fn stream_schema(source_schema: &TableSchema) -> TableSchemaRef {
+        let mut fields = source_schema.fields().clone();
+        fields.push(TableField::new(CHANGE_ACTION_COLUMN, TableDataType::String));
+        fields.push(TableField::new(CHANGE_ROW_ID_COLUMN, TableDataType::String));
+        Arc::new(TableSchema::new_from(fields, source_schema.meta().clone()))
+    }
+
+    /// Get the source table, it must not have been recreated or altered since
+    /// the stream was created.
+    pub(crate) async fn source_table(&self, ctx: Arc<dyn TableContext>) -> Result<Arc<dyn Table>> {
+        let (catalog, database, table) = &self.source;
+        let source = ctx.get_table(catalog, database, table).await?;
+        if source.get_id() != self.source_table_id {
+            return Err(ErrorCode::IllegalStream(format!(
+                "The source table {}.{} of stream {} has been recreated",
+                database, table, self.table_info.name
+            )));
+        }
+        if Self::stream_schema(&source.schema()).fields() != self.schema().fields() {
+            return Err(ErrorCode::IllegalStream(format!(
+                "The schema of the source table {}.{} of stream {} has been altered",
+                database, table, self.table_info.name
+            )));
+        }
+        Ok(source)
+    }
+
+    async fn read_snapshot(source: &FuseTable, location: &str) -> Result<Arc<TableSnapshot>> {
+        let reader = MetaReaders::table_snapshot_reader(source.get_operator());
+        let params = LoadParams {
+            location: location.to_string(),
+            len_hint: None,
+            ver: TableMetaLocationGenerator::snapshot_version(location),
+            schema: None,
+        };
+        reader.read(&params).await
+    }
+
+    async fn read_blocks(
+        ctx: Arc<dyn TableContext>,
+        source: &FuseTable,
+        segments: &[Location],
+    ) -> Result<Vec<Arc<BlockMeta>>> {
+        let segments_io = SegmentsIO::create(ctx, source.get_operator(), source.schema());
+        let mut blocks = vec![];
+        for segment in segments_io.read_segments(segments).await? {
+            blocks.extend(segment?.blocks.iter().cloned());
+        }
+        Ok(blocks)
+    }
+
+    /// The snapshot at the offset, or the segments it refers to, have been purged.
+    fn offset_purged(&self, e: ErrorCode) -> ErrorCode {
+        if e.code() != ErrorCode::STORAGE_NOT_FOUND {
+            return e;
+        }
+        let (_, database, table) = &self.source;
+        ErrorCode::IllegalStream(format!(
+            "The offset of stream {} has been purged from the source table {}.{}, \
+             recreate the stream by DROP STREAM and CREATE STREAM to reset its offset: {}",
+            self.table_info.name,
+            database,
+            table,
+            e.message()
+        ))
+    }
+
+    /// The blocks inserted into and deleted from the source table since the offset.
+    async fn changed_blocks(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        source: &FuseTable,
+        current: Option<&TableSnapshot>,
+    ) -> Result<Vec<(ChangeAction, Arc<BlockMeta>)>> {
+        let current_segments = current.map(|s| s.segments.clone()).unwrap_or_default();
+        let offset_segments = match &self.offset {
+            Some(location) => Self::read_snapshot(source, location)
+                .await
+                .map_err(|e| self.offset_purged(e))?
+                .segments
+                .clone(),
+            None => vec![],
+        };
+
+        // Only the segments that differ are loaded, the blocks of the segments
+        // kept by both snapshots are unchanged.
+        let current_set = current_segments.iter().collect::<HashSet<_>>();
+        let offset_set = offset_segments.iter().collect::<HashSet<_>>();
+        let added_segments = current_segments
+            .iter()
+            .filter(|s| !offset_set.contains(s))
+            .cloned()
+            .collect::<Vec<_>>();
+        let removed_segments = offset_segments
+            .iter()
+            .filter(|s| !current_set.contains(s))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let added_blocks = Self::read_blocks(ctx.clone(), source, &added_segments).await?;
+        let removed_blocks = Self::read_blocks(ctx, source, &removed_segments)
+            .await
+            .map_err(|e| self.offset_purged(e))?;
+
+        // Blocks moved between segments, e.g. by segment compaction, are unchanged.
+        let added_locations = added_blocks
+            .iter()
+            .map(|b| (b.location.0.clone(), b.clone()))
+            .collect::<HashMap<_, _>>();
+        let removed_locations = removed_blocks
+            .iter()
+            .map(|b| b.location.0.clone())
+            .collect::<HashSet<_>>();
+
+        let mut changes = Vec::new();
+        for block in removed_blocks {
+            if !added_locations.contains_key(&block.location.0) {
+                changes.push((ChangeAction::Delete, block));
+            }
+        }
+        for block in added_blocks {
+            if !removed_locations.contains(&block.location.0) {
+                changes.push((ChangeAction::Insert, block));
+            }
+        }
+        Ok(changes)
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for StreamTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn read_partitions(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        _push_downs: Option<PushDownInfo>,
+    ) -> Result<(PartStatistics, Partitions)> {
+        let source = self.source_table(ctx.clone()).await?;
+        let source = FuseTable::try_from_table(source.as_ref())?;
+        if matches!(source.storage_format, FuseStorageFormat::Native) {
+            return Err(ErrorCode::Unimplemented(
+                "Stream is not supported for tables of native storage format",
+            ));
+        }
+
+        let current_location = source.snapshot_loc().await?;
+        if current_location == self.offset {
+            return Ok((PartStatistics::default_exact(), Partitions::default()));
+        }
+
+        let current = source.read_table_snapshot().await?;
+        let changes = self
+            .changed_blocks(ctx.clone(), source, current.as_deref())
+            .await?;
+
+        let mut read_rows = 0;
+        let mut read_bytes = 0;
+        let mut parts = Vec::with_capacity(changes.len());
+        for (action, block) in changes {
+            read_rows += block.row_count as usize;
+            read_bytes += block.block_size as usize;
+            parts.push(StreamPartInfo::create(action, block));
+        }
+        let statistics = PartStatistics::new_exact(read_rows, read_bytes, parts.len(), parts.len());
+
+        // The offset is advanced only if the statement commits a table.
+        if let Some(location) = current_location {
+            ctx.consume_stream(UpsertTableOptionReq::new(
+                &self.table_info.ident,
+                OPT_KEY_STREAM_OFFSET,
+                location,
+            ));
+        }
+
+        Ok((
+            statistics,
+            Partitions::create(PartitionsShuffleKind::Mod, parts),
+        ))
+    }
+
+    fn read_data(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        plan: &DataSourcePlan,
+        pipeline: &mut Pipeline,
+    ) -> Result<()> {
+        let max_threads = ctx.get_settings().get_max_threads()? as usize;
+        let max_threads = std::cmp::min(max_threads, plan.parts.len()).max(1);
+        let (catalog, database, table) = self.source.clone();
+        pipeline.add_source(
+            |output| {
+                StreamSource::create(
+                    ctx.clone(),
+                    output,
+                    (catalog.clone(), database.clone(), table.clone()),
+                )
+            },
+            max_threads,
+        )
+    }
+}
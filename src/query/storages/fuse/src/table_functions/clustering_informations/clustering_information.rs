@@ -83,27 +83,30 @@ impl<'a> ClusteringInformation<'a> {
         }
     }
 
+    async fn read_blocks(&self) -> Result<Vec<Arc<BlockMeta>>> {
+        let snapshot = match self.table.read_table_snapshot().await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(vec![]),
+        };
+        let segments_io = SegmentsIO::create(
+            self.ctx.clone(),
+            self.table.operator.clone(),
+            self.table.schema(),
+        );
+        let mut blocks = vec![];
+        for segment in segments_io.read_segments(&snapshot.segments).await? {
+            blocks.extend(segment?.blocks.iter().cloned());
+        }
+        Ok(blocks)
+    }
+
     pub async fn get_clustering_info(&self) -> Result<DataBlock> {
-        let snapshot = self.table.read_table_snapshot().await?;
+        let blocks = self.read_blocks().await?;
 
         let mut info = ClusteringStatistics::default();
-        if let Some(snapshot) = snapshot {
-            let segment_locations = &snapshot.segments;
-            let segments_io = SegmentsIO::create(
-                self.ctx.clone(),
-                self.table.operator.clone(),
-                self.table.schema(),
-            );
-            let segments = segments_io
-                .read_segments(segment_locations)
-                .await?
-                .into_iter()
-                .collect::<Result<Vec<_>>>()?;
-            if !segments.is_empty() {
-                let blocks = segments.iter().flat_map(|s| s.blocks.iter());
-                info = self.get_clustering_stats(blocks)?
-            }
-        };
+        if !blocks.is_empty() {
+            info = self.get_clustering_stats(blocks.iter())?
+        }
 
         let cluster_by_keys = self.plain_cluster_keys.clone();
 
@@ -148,6 +151,31 @@ impl<'a> ClusteringInformation<'a> {
         ))
     }
 
+    /// The average depth of the blocks clustered by the cluster key of the table,
+    /// `None` if there is no such block.
+    pub async fn get_average_depth(&self) -> Result<Option<f64>> {
+        let default_cluster_key_id = match &self.table.cluster_key_meta {
+            Some((cluster_key_id, _)) => *cluster_key_id,
+            None => return Ok(None),
+        };
+        let blocks = self
+            .read_blocks()
+            .await?
+            .into_iter()
+            .filter(|block| {
+                block.cluster_stats.as_ref().map_or(false, |stats| {
+                    stats.cluster_key_id == default_cluster_key_id
+                })
+            })
+            .collect::<Vec<_>>();
+        if blocks.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(
+            self.get_clustering_stats(blocks.iter())?.average_depth,
+        ))
+    }
+
     fn get_min_max_stats(&self, block: &BlockMeta) -> Result<(Vec<Scalar>, Vec<Scalar>)> {
         if self.table.cluster_keys(self.ctx.clone()) != self.cluster_keys
             || block.cluster_stats.is_none()
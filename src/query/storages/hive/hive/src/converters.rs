@@ -17,6 +17,8 @@ use std::sync::Arc;
 use chrono::Utc;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_expression::types::decimal::DecimalDataType;
+use common_expression::types::decimal::DecimalSize;
 use common_expression::types::NumberDataType;
 use common_expression::TableDataType;
 use common_expression::TableField;
@@ -135,6 +137,30 @@ fn try_from_filed_type_name(type_name: impl AsRef<str>) -> Result<TableDataType>
     // Hive string data type could be varchar(n), where n is the maximum number of characters
     if name.starts_with("VARCHAR") {
         Ok(TableDataType::String)
+    } else if name.starts_with("DECIMAL") || name.starts_with("NUMERIC") {
+        // Hive decimal is `DECIMAL(precision, scale)`, and defaults to `DECIMAL(10, 0)`.
+        let (precision, scale) = match (name.find('('), name.rfind(')')) {
+            (Some(start), Some(end)) if start < end => {
+                let mut args = name[start + 1..end]
+                    .split(',')
+                    .map(|v| v.trim().parse::<u8>());
+                let precision = args.next().transpose();
+                let scale = args.next().transpose();
+                match (precision, scale) {
+                    (Ok(Some(precision)), Ok(scale)) => (precision, scale.unwrap_or(0)),
+                    _ => {
+                        return Err(ErrorCode::IllegalDataType(format!(
+                            "Invalid decimal data type: {}",
+                            name
+                        )));
+                    }
+                }
+            }
+            _ => (10, 0),
+        };
+        let decimal = DecimalDataType::from_size(DecimalSize { precision, scale })
+            .map_err(ErrorCode::IllegalDataType)?;
+        Ok(TableDataType::Decimal(decimal))
     } else if name.starts_with("ARRAY<") {
         let sub_type = &name["ARRAY<".len()..name.len() - 1];
         let sub_type = try_from_filed_type_name(sub_type)?;
@@ -145,7 +171,6 @@ fn try_from_filed_type_name(type_name: impl AsRef<str>) -> Result<TableDataType>
             "SMALLINT" => Ok(NumberDataType::Int16),
             "INT" => Ok(NumberDataType::Int32),
             "BIGINT" => Ok(NumberDataType::Int64),
-            //"DECIMAL", "NUMERIC" type not supported
             "FLOAT" => Ok(NumberDataType::Float32),
             "DOUBLE" | "DOUBLE PRECISION" => Ok(NumberDataType::Float64),
 
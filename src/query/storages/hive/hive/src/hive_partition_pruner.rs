@@ -83,9 +83,13 @@ impl HivePartitionPruner {
                 .iter()
                 .map(|(k, v)| {
                     let partition_col_name = self.partition_schema.field(*k as usize).name();
-                    let index = self.full_schema.index_of(partition_col_name).unwrap();
+                    let column_id = self
+                        .full_schema
+                        .field_with_name(partition_col_name)
+                        .unwrap()
+                        .column_id();
 
-                    (index as u32, v.clone())
+                    (column_id, v.clone())
                 })
                 .collect();
 
@@ -0,0 +1,274 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A minimal reader of Avro object container files.
+//!
+//! Manifest lists and manifests of Iceberg are small Avro files with nested records,
+//! maps and arrays, it is simpler to decode them into generic values than into arrow arrays.
+
+use std::io::Cursor;
+
+use common_arrow::arrow::io::avro::avro_schema;
+use common_arrow::arrow::io::avro::avro_schema::file::Block;
+use common_arrow::arrow::io::avro::avro_schema::file::CompressedBlock;
+use common_arrow::arrow::io::avro::avro_schema::schema::Schema;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_io::prelude::read_zigzag_varint;
+
+const SYNC_MARKER_SIZE: usize = 16;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AvroValue {
+    Null,
+    Boolean(bool),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    Bytes(Vec<u8>),
+    String(String),
+    Enum(String),
+    Array(Vec<AvroValue>),
+    Map(Vec<(String, AvroValue)>),
+    Record(Vec<(String, AvroValue)>),
+}
+
+impl AvroValue {
+    /// Get the field of a record, `None` if it is not a record or the field is absent.
+    pub fn field(&self, name: &str) -> Option<&AvroValue> {
+        match self {
+            AvroValue::Record(fields) => fields.iter().find(|(n, _)| n == name).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            AvroValue::Int(v) => Some(*v as i64),
+            AvroValue::Long(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            AvroValue::String(v) | AvroValue::Enum(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            AvroValue::Bytes(v) => Some(v),
+            AvroValue::String(v) => Some(v.as_bytes()),
+            _ => None,
+        }
+    }
+
+    /// Iceberg stores maps with non-string keys as arrays of `key`, `value` records.
+    pub fn as_int_map(&self) -> Vec<(i32, &AvroValue)> {
+        match self {
+            AvroValue::Array(items) => items
+                .iter()
+                .filter_map(|item| {
+                    let key = item.field("key")?.as_i64()? as i32;
+                    let value = item.field("value")?;
+                    Some((key, value))
+                })
+                .collect(),
+            _ => vec![],
+        }
+    }
+}
+
+fn avro_error(path: &str, e: avro_schema::error::Error) -> ErrorCode {
+    ErrorCode::BadBytes(format!("invalid avro file {}: {:?}", path, e))
+}
+
+/// Decode all the records of an Avro object container file.
+pub fn read_avro_records(path: &str, data: Vec<u8>) -> Result<Vec<AvroValue>> {
+    let size = data.len();
+    let mut cursor = Cursor::new(data);
+    let metadata =
+        avro_schema::read::read_metadata(&mut cursor).map_err(|e| avro_error(path, e))?;
+    let mut pos = cursor.position() as usize;
+    let data = cursor.into_inner();
+    let schema = Schema::Record(metadata.record.clone());
+
+    let mut records = vec![];
+    let mut decompressed = Block::new(0, vec![]);
+    while pos < size {
+        let rows = decode_long(&data, &mut pos)?;
+        let block_size = decode_long(&data, &mut pos)?;
+        if rows < 0 || block_size < 0 {
+            return Err(ErrorCode::BadBytes(format!(
+                "invalid avro file {}: {} rows of {} bytes",
+                path, rows, block_size
+            )));
+        }
+        let block_end = pos + block_size as usize;
+        if block_end + SYNC_MARKER_SIZE > size {
+            return Err(ErrorCode::BadBytes(format!(
+                "invalid avro file {}: unexpected end of file",
+                path
+            )));
+        }
+        if data[block_end..block_end + SYNC_MARKER_SIZE] != metadata.marker {
+            return Err(ErrorCode::BadBytes(format!(
+                "invalid avro file {}: sync marker mismatch",
+                path
+            )));
+        }
+
+        let mut compressed = CompressedBlock::new(rows as usize, data[pos..block_end].to_vec());
+        avro_schema::read::decompress_block(
+            &mut compressed,
+            &mut decompressed,
+            metadata.compression,
+        )
+        .map_err(|e| avro_error(path, e))?;
+
+        let mut block_pos = 0;
+        for _ in 0..rows {
+            records.push(decode_value(&schema, &decompressed.data, &mut block_pos)?);
+        }
+        pos = block_end + SYNC_MARKER_SIZE;
+    }
+    Ok(records)
+}
+
+fn unexpected_eof() -> ErrorCode {
+    ErrorCode::BadBytes("invalid avro block: unexpected end of block")
+}
+
+/// Decode a long encoded with variable-length zig-zag coding.
+fn decode_long(data: &[u8], pos: &mut usize) -> Result<i64> {
+    read_zigzag_varint(data, pos)?.ok_or_else(unexpected_eof)
+}
+
+fn decode_fixed<'a>(data: &'a [u8], pos: &mut usize, len: usize) -> Result<&'a [u8]> {
+    let end = *pos + len;
+    if end > data.len() {
+        return Err(unexpected_eof());
+    }
+    let bytes = &data[*pos..end];
+    *pos = end;
+    Ok(bytes)
+}
+
+fn decode_bytes<'a>(data: &'a [u8], pos: &mut usize) -> Result<&'a [u8]> {
+    let len = decode_long(data, pos)?;
+    if len < 0 {
+        return Err(ErrorCode::BadBytes(
+            "invalid avro block: negative length of bytes",
+        ));
+    }
+    decode_fixed(data, pos, len as usize)
+}
+
+fn decode_string(data: &[u8], pos: &mut usize) -> Result<String> {
+    let bytes = decode_bytes(data, pos)?;
+    String::from_utf8(bytes.to_vec())
+        .map_err(|_| ErrorCode::BadBytes("invalid avro block: string is not valid utf8"))
+}
+
+/// Arrays and maps are encoded as a series of blocks, each starts with its number of items.
+/// A negative count is followed by the size of the block in bytes, which we don't need.
+fn decode_blocks(
+    data: &[u8],
+    pos: &mut usize,
+    mut decode_item: impl FnMut(&mut usize) -> Result<()>,
+) -> Result<()> {
+    loop {
+        let mut count = decode_long(data, pos)?;
+        if count == 0 {
+            return Ok(());
+        }
+        if count < 0 {
+            count = -count;
+            decode_long(data, pos)?;
+        }
+        for _ in 0..count {
+            decode_item(pos)?;
+        }
+    }
+}
+
+fn decode_value(schema: &Schema, data: &[u8], pos: &mut usize) -> Result<AvroValue> {
+    Ok(match schema {
+        Schema::Null => AvroValue::Null,
+        Schema::Boolean => {
+            let byte = decode_fixed(data, pos, 1)?[0];
+            AvroValue::Boolean(byte != 0)
+        }
+        Schema::Int(_) => AvroValue::Int(decode_long(data, pos)? as i32),
+        Schema::Long(_) => AvroValue::Long(decode_long(data, pos)?),
+        Schema::Float => {
+            let bytes = decode_fixed(data, pos, 4)?;
+            AvroValue::Float(f32::from_le_bytes(bytes.try_into().unwrap()))
+        }
+        Schema::Double => {
+            let bytes = decode_fixed(data, pos, 8)?;
+            AvroValue::Double(f64::from_le_bytes(bytes.try_into().unwrap()))
+        }
+        Schema::Bytes(_) => AvroValue::Bytes(decode_bytes(data, pos)?.to_vec()),
+        Schema::String(_) => AvroValue::String(decode_string(data, pos)?),
+        Schema::Fixed(fixed) => AvroValue::Bytes(decode_fixed(data, pos, fixed.size)?.to_vec()),
+        Schema::Enum(e) => {
+            let index = decode_long(data, pos)?;
+            let symbol = e.symbols.get(index as usize).ok_or_else(|| {
+                ErrorCode::BadBytes(format!(
+                    "invalid avro block: enum index {} out of range",
+                    index
+                ))
+            })?;
+            AvroValue::Enum(symbol.clone())
+        }
+        Schema::Union(schemas) => {
+            let index = decode_long(data, pos)?;
+            let schema = schemas.get(index as usize).ok_or_else(|| {
+                ErrorCode::BadBytes(format!(
+                    "invalid avro block: union index {} out of range",
+                    index
+                ))
+            })?;
+            decode_value(schema, data, pos)?
+        }
+        Schema::Array(item) => {
+            let mut items = vec![];
+            decode_blocks(data, pos, |pos| {
+                items.push(decode_value(item, data, pos)?);
+                Ok(())
+            })?;
+            AvroValue::Array(items)
+        }
+        Schema::Map(value) => {
+            let mut entries = vec![];
+            decode_blocks(data, pos, |pos| {
+                let key = decode_string(data, pos)?;
+                entries.push((key, decode_value(value, data, pos)?));
+                Ok(())
+            })?;
+            AvroValue::Map(entries)
+        }
+        Schema::Record(record) => {
+            let mut fields = Vec::with_capacity(record.fields.len());
+            for field in record.fields.iter() {
+                fields.push((field.name.clone(), decode_value(&field.schema, data, pos)?));
+            }
+            AvroValue::Record(fields)
+        }
+    })
+}
@@ -0,0 +1,356 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::io::ErrorKind;
+use std::sync::Arc;
+
+use common_catalog::catalog::Catalog;
+use common_catalog::database::Database;
+use common_catalog::table::Table;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::CountTablesReply;
+use common_meta_app::schema::CountTablesReq;
+use common_meta_app::schema::CreateDatabaseReply;
+use common_meta_app::schema::CreateDatabaseReq;
+use common_meta_app::schema::CreateTableReq;
+use common_meta_app::schema::DropDatabaseReq;
+use common_meta_app::schema::DropTableReply;
+use common_meta_app::schema::DropTableReq;
+use common_meta_app::schema::GetTableCopiedFileReply;
+use common_meta_app::schema::GetTableCopiedFileReq;
+use common_meta_app::schema::RenameDatabaseReply;
+use common_meta_app::schema::RenameDatabaseReq;
+use common_meta_app::schema::RenameTableReply;
+use common_meta_app::schema::RenameTableReq;
+use common_meta_app::schema::TableIdent;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
+use common_meta_app::schema::TruncateTableReply;
+use common_meta_app::schema::TruncateTableReq;
+use common_meta_app::schema::UndropDatabaseReply;
+use common_meta_app::schema::UndropDatabaseReq;
+use common_meta_app::schema::UndropTableReply;
+use common_meta_app::schema::UndropTableReq;
+use common_meta_app::schema::UpdateTableMetaReply;
+use common_meta_app::schema::UpdateTableMetaReq;
+use common_meta_app::schema::UpsertTableCopiedFileReply;
+use common_meta_app::schema::UpsertTableCopiedFileReq;
+use common_meta_app::schema::UpsertTableOptionReply;
+use common_meta_app::schema::UpsertTableOptionReq;
+use common_meta_types::MetaId;
+use common_storage::init_operator;
+use common_storage::StorageParams;
+use futures::TryStreamExt;
+use opendal::ObjectMode;
+use opendal::Operator;
+
+use crate::database::IcebergDatabase;
+use crate::table::IcebergTable;
+use crate::table_metadata::TableMetadata;
+
+const METADATA_DIR: &str = "metadata/";
+const VERSION_HINT: &str = "version-hint.text";
+const METADATA_FILE_SUFFIX: &str = ".metadata.json";
+
+/// A read-only catalog of Iceberg tables stored in the layout of the hadoop catalog:
+/// databases are the directories under the root, and tables are the directories
+/// under a database, each of which has a `metadata` directory.
+#[derive(Clone)]
+pub struct IcebergCatalog {
+    name: String,
+    storage_params: StorageParams,
+    operator: Operator,
+}
+
+impl IcebergCatalog {
+    pub fn try_create(name: &str, storage_params: StorageParams) -> Result<IcebergCatalog> {
+        let operator = init_operator(&storage_params)?;
+        Ok(IcebergCatalog {
+            name: name.to_string(),
+            storage_params,
+            operator,
+        })
+    }
+
+    /// The names of the directories directly under `path`.
+    async fn list_dirs(&self, path: &str) -> Result<Vec<String>> {
+        let mut lister = match self.operator.object(path).list().await {
+            Ok(lister) => lister,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e.into()),
+        };
+        let mut dirs = vec![];
+        while let Some(de) = lister.try_next().await? {
+            if de.mode().await? == ObjectMode::DIR {
+                dirs.push(de.name().trim_end_matches('/').to_string());
+            }
+        }
+        Ok(dirs)
+    }
+
+    /// Find the latest metadata file of the table, which is the version in `version-hint.text`
+    /// if it exists, otherwise the metadata file with the highest version.
+    async fn find_metadata_location(&self, location: &str) -> Result<Option<String>> {
+        let metadata_dir = format!("{}{}", location, METADATA_DIR);
+        let version_hint = format!("{}{}", metadata_dir, VERSION_HINT);
+        match self.operator.object(&version_hint).read().await {
+            Ok(data) => {
+                let version = String::from_utf8_lossy(&data).trim().to_string();
+                return Ok(Some(format!(
+                    "{}v{}{}",
+                    metadata_dir, version, METADATA_FILE_SUFFIX
+                )));
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        let mut lister = match self.operator.object(&metadata_dir).list().await {
+            Ok(lister) => lister,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let mut latest: Option<(u64, String)> = None;
+        while let Some(de) = lister.try_next().await? {
+            // Named as `v<version>.metadata.json` or `<version>-<uuid>.metadata.json`.
+            let name = de.name();
+            if !name.ends_with(METADATA_FILE_SUFFIX) {
+                continue;
+            }
+            let digits = name
+                .trim_start_matches('v')
+                .chars()
+                .take_while(|c| c.is_ascii_digit())
+                .collect::<String>();
+            if let Ok(version) = digits.parse::<u64>() {
+                if latest.as_ref().map_or(true, |(v, _)| version > *v) {
+                    latest = Some((version, de.path().to_string()));
+                }
+            }
+        }
+        Ok(latest.map(|(_, path)| path))
+    }
+
+    async fn load_table(&self, db_name: &str, table_name: &str) -> Result<Arc<dyn Table>> {
+        let location = format!("{}/{}/", db_name, table_name);
+        let metadata_location = self
+            .find_metadata_location(&location)
+            .await?
+            .ok_or_else(|| {
+                ErrorCode::UnknownTable(format!(
+                    "Unknown table '{}'.'{}' in catalog {}",
+                    db_name, table_name, self.name
+                ))
+            })?;
+        let data = self.operator.object(&metadata_location).read().await?;
+        let metadata = TableMetadata::parse(&metadata_location, &data)?;
+        let table_info = IcebergTable::create_table_info(
+            &self.name,
+            db_name,
+            table_name,
+            &self.storage_params,
+            &location,
+            &metadata_location,
+            &metadata,
+            None,
+        )?;
+        Ok(Arc::new(IcebergTable::try_create(table_info)?))
+    }
+}
+
+#[async_trait::async_trait]
+impl Catalog for IcebergCatalog {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn get_database(&self, tenant: &str, db_name: &str) -> Result<Arc<dyn Database>> {
+        if !self.list_dirs("/").await?.iter().any(|d| d == db_name) {
+            return Err(ErrorCode::UnknownDatabase(format!(
+                "Unknown database '{}' in catalog {}",
+                db_name, self.name
+            )));
+        }
+        Ok(Arc::new(IcebergDatabase::create(tenant, db_name)))
+    }
+
+    async fn list_databases(&self, tenant: &str) -> Result<Vec<Arc<dyn Database>>> {
+        Ok(self
+            .list_dirs("/")
+            .await?
+            .iter()
+            .map(|db_name| {
+                let db: Arc<dyn Database> = Arc::new(IcebergDatabase::create(tenant, db_name));
+                db
+            })
+            .collect())
+    }
+
+    async fn create_database(&self, _req: CreateDatabaseReq) -> Result<CreateDatabaseReply> {
+        Err(ErrorCode::Unimplemented(
+            "Cannot create database in ICEBERG catalog",
+        ))
+    }
+
+    async fn drop_database(&self, _req: DropDatabaseReq) -> Result<()> {
+        Err(ErrorCode::Unimplemented(
+            "Cannot drop database in ICEBERG catalog",
+        ))
+    }
+
+    async fn undrop_database(&self, _req: UndropDatabaseReq) -> Result<UndropDatabaseReply> {
+        Err(ErrorCode::Unimplemented(
+            "Cannot undrop database in ICEBERG catalog",
+        ))
+    }
+
+    async fn rename_database(&self, _req: RenameDatabaseReq) -> Result<RenameDatabaseReply> {
+        Err(ErrorCode::Unimplemented(
+            "Cannot rename database in ICEBERG catalog",
+        ))
+    }
+
+    fn get_table_by_info(&self, table_info: &TableInfo) -> Result<Arc<dyn Table>> {
+        let res: Arc<dyn Table> = Arc::new(IcebergTable::try_create(table_info.clone())?);
+        Ok(res)
+    }
+
+    async fn get_table_meta_by_id(
+        &self,
+        _table_id: MetaId,
+    ) -> Result<(TableIdent, Arc<TableMeta>)> {
+        Err(ErrorCode::Unimplemented(
+            "Cannot get table by id in ICEBERG catalog",
+        ))
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn get_table(
+        &self,
+        _tenant: &str,
+        db_name: &str,
+        table_name: &str,
+    ) -> Result<Arc<dyn Table>> {
+        self.load_table(db_name, table_name).await
+    }
+
+    async fn list_tables(&self, _tenant: &str, db_name: &str) -> Result<Vec<Arc<dyn Table>>> {
+        let mut tables = vec![];
+        for table_name in self.list_dirs(&format!("{}/", db_name)).await? {
+            // Skip the directories which are not iceberg tables.
+            match self.load_table(db_name, &table_name).await {
+                Ok(table) => tables.push(table),
+                Err(e) if e.code() == ErrorCode::UNKNOWN_TABLE => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(tables)
+    }
+
+    async fn list_tables_history(
+        &self,
+        _tenant: &str,
+        _db_name: &str,
+    ) -> Result<Vec<Arc<dyn Table>>> {
+        Err(ErrorCode::Unimplemented(
+            "Cannot list table history in ICEBERG catalog",
+        ))
+    }
+
+    async fn create_table(&self, _req: CreateTableReq) -> Result<()> {
+        Err(ErrorCode::Unimplemented(
+            "Cannot create table in ICEBERG catalog",
+        ))
+    }
+
+    async fn drop_table(&self, _req: DropTableReq) -> Result<DropTableReply> {
+        Err(ErrorCode::Unimplemented(
+            "Cannot drop table in ICEBERG catalog",
+        ))
+    }
+
+    async fn undrop_table(&self, _req: UndropTableReq) -> Result<UndropTableReply> {
+        Err(ErrorCode::Unimplemented(
+            "Cannot undrop table in ICEBERG catalog",
+        ))
+    }
+
+    async fn rename_table(&self, _req: RenameTableReq) -> Result<RenameTableReply> {
+        Err(ErrorCode::Unimplemented(
+            "Cannot rename table in ICEBERG catalog",
+        ))
+    }
+
+    async fn upsert_table_option(
+        &self,
+        _tenant: &str,
+        _db_name: &str,
+        _req: UpsertTableOptionReq,
+    ) -> Result<UpsertTableOptionReply> {
+        Err(ErrorCode::Unimplemented(
+            "Cannot upsert table option in ICEBERG catalog",
+        ))
+    }
+
+    async fn update_table_meta(
+        &self,
+        _table_info: &TableInfo,
+        _req: UpdateTableMetaReq,
+    ) -> Result<UpdateTableMetaReply> {
+        Err(ErrorCode::Unimplemented(
+            "Cannot update table meta in ICEBERG catalog",
+        ))
+    }
+
+    async fn get_table_copied_file_info(
+        &self,
+        _tenant: &str,
+        _db_name: &str,
+        _req: GetTableCopiedFileReq,
+    ) -> Result<GetTableCopiedFileReply> {
+        Err(ErrorCode::Unimplemented(
+            "Cannot get copied file info in ICEBERG catalog",
+        ))
+    }
+
+    async fn upsert_table_copied_file_info(
+        &self,
+        _tenant: &str,
+        _db_name: &str,
+        _req: UpsertTableCopiedFileReq,
+    ) -> Result<UpsertTableCopiedFileReply> {
+        Err(ErrorCode::Unimplemented(
+            "Cannot upsert copied file info in ICEBERG catalog",
+        ))
+    }
+
+    async fn truncate_table(
+        &self,
+        _table_info: &TableInfo,
+        _req: TruncateTableReq,
+    ) -> Result<TruncateTableReply> {
+        Err(ErrorCode::Unimplemented(
+            "Cannot truncate table in ICEBERG catalog",
+        ))
+    }
+
+    async fn count_tables(&self, _req: CountTablesReq) -> Result<CountTablesReply> {
+        Err(ErrorCode::Unimplemented(
+            "Cannot count tables in ICEBERG catalog",
+        ))
+    }
+}
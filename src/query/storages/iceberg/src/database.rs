@@ -0,0 +1,58 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::Utc;
+use common_catalog::database::Database;
+use common_meta_app::schema::DatabaseIdent;
+use common_meta_app::schema::DatabaseInfo;
+use common_meta_app::schema::DatabaseMeta;
+use common_meta_app::schema::DatabaseNameIdent;
+
+pub const ICEBERG_DATABASE_ENGINE: &str = "iceberg";
+
+/// A directory under the root of an Iceberg catalog.
+#[derive(Clone)]
+pub struct IcebergDatabase {
+    pub database_info: DatabaseInfo,
+}
+
+impl IcebergDatabase {
+    pub fn create(tenant: &str, db_name: &str) -> IcebergDatabase {
+        IcebergDatabase {
+            database_info: DatabaseInfo {
+                ident: DatabaseIdent { db_id: 0, seq: 0 },
+                name_ident: DatabaseNameIdent {
+                    tenant: tenant.to_string(),
+                    db_name: db_name.to_string(),
+                },
+                meta: DatabaseMeta {
+                    engine: ICEBERG_DATABASE_ENGINE.to_string(),
+                    created_on: Utc::now(),
+                    ..Default::default()
+                },
+            },
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Database for IcebergDatabase {
+    fn name(&self) -> &str {
+        &self.database_info.name_ident.db_name
+    }
+
+    fn get_db_info(&self) -> &DatabaseInfo {
+        &self.database_info
+    }
+}
@@ -0,0 +1,143 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Manifest lists and manifests, see <https://iceberg.apache.org/spec/#manifests>.
+
+use std::collections::HashMap;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::avro::AvroValue;
+
+/// The content of a manifest or a data file.
+pub const CONTENT_DATA: i64 = 0;
+
+/// The status of a manifest entry.
+const STATUS_DELETED: i64 = 2;
+
+/// An entry of the manifest list of a snapshot.
+#[derive(Debug, Clone)]
+pub struct ManifestFile {
+    pub manifest_path: String,
+    /// Always data in format v1.
+    pub content: i64,
+    pub partition_spec_id: i32,
+}
+
+impl ManifestFile {
+    pub fn try_from_avro(value: &AvroValue) -> Result<ManifestFile> {
+        Ok(ManifestFile {
+            manifest_path: required_str(value, "manifest_path")?.to_string(),
+            content: value
+                .field("content")
+                .and_then(|v| v.as_i64())
+                .unwrap_or(CONTENT_DATA),
+            partition_spec_id: value
+                .field("partition_spec_id")
+                .and_then(|v| v.as_i64())
+                .unwrap_or_default() as i32,
+        })
+    }
+
+    /// Manifests listed directly by snapshots of format v1 don't have any other information.
+    pub fn from_path(manifest_path: &str) -> ManifestFile {
+        ManifestFile {
+            manifest_path: manifest_path.to_string(),
+            content: CONTENT_DATA,
+            partition_spec_id: 0,
+        }
+    }
+}
+
+/// A data file which is alive in the snapshot.
+#[derive(Debug, Clone)]
+pub struct DataFile {
+    pub content: i64,
+    pub file_path: String,
+    pub file_format: String,
+    pub record_count: i64,
+    /// Partition values, in the order of the fields of the partition spec.
+    pub partition: Vec<AvroValue>,
+    pub null_value_counts: HashMap<i32, i64>,
+    pub lower_bounds: HashMap<i32, Vec<u8>>,
+    pub upper_bounds: HashMap<i32, Vec<u8>>,
+}
+
+impl DataFile {
+    /// Returns `None` if the entry is deleted in the snapshot.
+    pub fn try_from_entry(entry: &AvroValue) -> Result<Option<DataFile>> {
+        let status = entry.field("status").and_then(|v| v.as_i64());
+        if status == Some(STATUS_DELETED) {
+            return Ok(None);
+        }
+        let value = entry
+            .field("data_file")
+            .ok_or_else(|| missing_field("data_file"))?;
+
+        let partition = match value.field("partition") {
+            Some(AvroValue::Record(fields)) => fields.iter().map(|(_, v)| v.clone()).collect(),
+            _ => vec![],
+        };
+        let null_value_counts = value
+            .field("null_value_counts")
+            .map(|v| {
+                v.as_int_map()
+                    .into_iter()
+                    .filter_map(|(k, v)| Some((k, v.as_i64()?)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Some(DataFile {
+            content: value
+                .field("content")
+                .and_then(|v| v.as_i64())
+                .unwrap_or(CONTENT_DATA),
+            file_path: required_str(value, "file_path")?.to_string(),
+            file_format: required_str(value, "file_format")?.to_uppercase(),
+            record_count: value
+                .field("record_count")
+                .and_then(|v| v.as_i64())
+                .unwrap_or_default(),
+            partition,
+            null_value_counts,
+            lower_bounds: bounds(value, "lower_bounds"),
+            upper_bounds: bounds(value, "upper_bounds"),
+        }))
+    }
+}
+
+fn bounds(value: &AvroValue, name: &str) -> HashMap<i32, Vec<u8>> {
+    value
+        .field(name)
+        .map(|v| {
+            v.as_int_map()
+                .into_iter()
+                .filter_map(|(k, v)| Some((k, v.as_bytes()?.to_vec())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn missing_field(name: &str) -> ErrorCode {
+    ErrorCode::BadBytes(format!("invalid iceberg manifest: missing field {}", name))
+}
+
+fn required_str<'a>(value: &'a AvroValue, name: &str) -> Result<&'a str> {
+    value
+        .field(name)
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| missing_field(name))
+}
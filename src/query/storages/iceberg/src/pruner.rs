@@ -0,0 +1,221 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_catalog::table_context::TableContext;
+use common_exception::Result;
+use common_expression::types::decimal::DecimalDataType;
+use common_expression::types::decimal::DecimalScalar;
+use common_expression::types::number::F32;
+use common_expression::types::number::F64;
+use common_expression::types::NumberDataType;
+use common_expression::types::NumberScalar;
+use common_expression::Expr;
+use common_expression::Scalar;
+use common_expression::TableDataType;
+use common_expression::TableSchemaRef;
+use storages_common_pruner::RangePruner;
+use storages_common_pruner::RangePrunerCreator;
+use storages_common_table_meta::meta::ColumnStatistics;
+use storages_common_table_meta::meta::StatisticsOfColumns;
+
+use crate::avro::AvroValue;
+use crate::manifest::DataFile;
+use crate::table_metadata::PartitionField;
+use crate::table_metadata::Schema;
+
+/// Prunes the data files of a snapshot by their partition values and the
+/// lower and upper bounds of their columns, before reading any of them.
+pub struct DataFilePruner {
+    range_pruner: Arc<dyn RangePruner + Send + Sync>,
+    /// Iceberg field id to the column id and type of the top level columns.
+    columns: HashMap<i32, (u32, TableDataType)>,
+}
+
+impl DataFilePruner {
+    pub fn try_create(
+        ctx: &Arc<dyn TableContext>,
+        filters: Option<&[Expr<String>]>,
+        iceberg_schema: &Schema,
+        table_schema: &TableSchemaRef,
+    ) -> Result<Self> {
+        let range_pruner = RangePrunerCreator::try_create(ctx, filters, table_schema)?;
+        let columns = iceberg_schema
+            .fields
+            .iter()
+            .zip(table_schema.fields().iter())
+            .map(|(f, table_field)| {
+                (
+                    f.id,
+                    (table_field.column_id(), table_field.data_type().clone()),
+                )
+            })
+            .collect();
+        Ok(DataFilePruner {
+            range_pruner,
+            columns,
+        })
+    }
+
+    pub fn should_keep(&self, file: &DataFile, partition_spec: &[PartitionField]) -> bool {
+        let stats = self.file_statistics(file, partition_spec);
+        self.range_pruner.should_keep(&stats)
+    }
+
+    fn file_statistics(
+        &self,
+        file: &DataFile,
+        partition_spec: &[PartitionField],
+    ) -> StatisticsOfColumns {
+        let mut stats = StatisticsOfColumns::new();
+        for (field_id, (column_id, ty)) in self.columns.iter() {
+            // Without the null count, a domain can't tell whether the column has nulls.
+            let null_count = match file.null_value_counts.get(field_id) {
+                Some(n) => *n as u64,
+                None => continue,
+            };
+            if ty.is_nullable() && null_count == file.record_count as u64 {
+                stats.insert(*column_id, null_statistics(null_count));
+                continue;
+            }
+            let (lower, upper) = match (
+                file.lower_bounds.get(field_id),
+                file.upper_bounds.get(field_id),
+            ) {
+                (Some(lower), Some(upper)) => (lower, upper),
+                _ => continue,
+            };
+            if let (Some(min), Some(max)) = (decode_bound(ty, lower), decode_bound(ty, upper)) {
+                stats.insert(*column_id, column_statistics(min, max, null_count));
+            }
+        }
+
+        // All the rows of a file share the same value of an identity partition,
+        // which is more precise than the bounds.
+        for (field, value) in partition_spec.iter().zip(file.partition.iter()) {
+            if field.transform != "identity" {
+                continue;
+            }
+            if let Some((column_id, ty)) = self.columns.get(&field.source_id) {
+                if matches!(value, AvroValue::Null) {
+                    if ty.is_nullable() {
+                        let null_count = file.record_count as u64;
+                        stats.insert(*column_id, null_statistics(null_count));
+                    }
+                } else if let Some(v) = partition_to_scalar(ty, value) {
+                    stats.insert(*column_id, column_statistics(v.clone(), v, 0));
+                }
+            }
+        }
+        stats
+    }
+}
+
+fn column_statistics(min: Scalar, max: Scalar, null_count: u64) -> ColumnStatistics {
+    ColumnStatistics {
+        min,
+        max,
+        null_count,
+        in_memory_size: 0,
+        distinct_of_values: None,
+    }
+}
+
+fn null_statistics(null_count: u64) -> ColumnStatistics {
+    column_statistics(Scalar::Null, Scalar::Null, null_count)
+}
+
+/// Decode a bound serialized as a single value,
+/// see <https://iceberg.apache.org/spec/#binary-single-value-serialization>.
+pub fn decode_bound(ty: &TableDataType, bytes: &[u8]) -> Option<Scalar> {
+    let scalar = match ty.remove_nullable() {
+        TableDataType::Number(NumberDataType::Int32) => Scalar::Number(NumberScalar::Int32(
+            i32::from_le_bytes(bytes.try_into().ok()?),
+        )),
+        TableDataType::Number(NumberDataType::Int64) => {
+            Scalar::Number(NumberScalar::Int64(decode_le_i64(bytes)?))
+        }
+        TableDataType::Number(NumberDataType::Float32) => {
+            let v = f32::from_le_bytes(bytes.try_into().ok()?);
+            if v.is_nan() {
+                return None;
+            }
+            Scalar::Number(NumberScalar::Float32(F32::from(v)))
+        }
+        TableDataType::Number(NumberDataType::Float64) => {
+            let v = match bytes.len() {
+                4 => f32::from_le_bytes(bytes.try_into().ok()?) as f64,
+                _ => f64::from_le_bytes(bytes.try_into().ok()?),
+            };
+            if v.is_nan() {
+                return None;
+            }
+            Scalar::Number(NumberScalar::Float64(F64::from(v)))
+        }
+        TableDataType::Date => Scalar::Date(i32::from_le_bytes(bytes.try_into().ok()?)),
+        TableDataType::Timestamp => Scalar::Timestamp(decode_le_i64(bytes)?),
+        TableDataType::String => Scalar::String(bytes.to_vec()),
+        TableDataType::Decimal(DecimalDataType::Decimal128(size)) => {
+            Scalar::Decimal(DecimalScalar::Decimal128(decode_be_i128(bytes)?, size))
+        }
+        _ => return None,
+    };
+    Some(scalar)
+}
+
+/// Longs may be written as ints before the column is promoted.
+fn decode_le_i64(bytes: &[u8]) -> Option<i64> {
+    match bytes.len() {
+        4 => Some(i32::from_le_bytes(bytes.try_into().ok()?) as i64),
+        _ => Some(i64::from_le_bytes(bytes.try_into().ok()?)),
+    }
+}
+
+/// Decode the unscaled value of a decimal, stored as big-endian two's complement.
+fn decode_be_i128(bytes: &[u8]) -> Option<i128> {
+    if bytes.is_empty() || bytes.len() > 16 {
+        return None;
+    }
+    let fill = if bytes[0] & 0x80 != 0 { 0xff } else { 0 };
+    let mut buf = [fill; 16];
+    buf[16 - bytes.len()..].copy_from_slice(bytes);
+    Some(i128::from_be_bytes(buf))
+}
+
+fn partition_to_scalar(ty: &TableDataType, value: &AvroValue) -> Option<Scalar> {
+    let scalar = match (ty.remove_nullable(), value) {
+        (TableDataType::Number(NumberDataType::Int32), AvroValue::Int(v)) => {
+            Scalar::Number(NumberScalar::Int32(*v))
+        }
+        (TableDataType::Number(NumberDataType::Int64), v) => {
+            Scalar::Number(NumberScalar::Int64(v.as_i64()?))
+        }
+        (TableDataType::Number(NumberDataType::Float32), AvroValue::Float(v)) => {
+            Scalar::Number(NumberScalar::Float32(F32::from(*v)))
+        }
+        (TableDataType::Number(NumberDataType::Float64), AvroValue::Double(v)) => {
+            Scalar::Number(NumberScalar::Float64(F64::from(*v)))
+        }
+        (TableDataType::Date, AvroValue::Int(v)) => Scalar::Date(*v),
+        (TableDataType::Timestamp, AvroValue::Long(v)) => Scalar::Timestamp(*v),
+        (TableDataType::String, v) => Scalar::String(v.as_bytes()?.to_vec()),
+        (TableDataType::Decimal(DecimalDataType::Decimal128(size)), AvroValue::Bytes(v)) => {
+            Scalar::Decimal(DecimalScalar::Decimal128(decode_be_i128(v)?, size))
+        }
+        _ => return None,
+    };
+    Some(scalar)
+}
@@ -0,0 +1,554 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_arrow::arrow::datatypes::Schema as ArrowSchema;
+use common_arrow::arrow::io::parquet::read::infer_schema;
+use common_arrow::arrow::io::parquet::read::read_metadata_async;
+use common_arrow::parquet::metadata::FileMetaData;
+use common_catalog::plan::DataSourcePlan;
+use common_catalog::plan::PartInfoPtr;
+use common_catalog::plan::PartStatistics;
+use common_catalog::plan::Partitions;
+use common_catalog::plan::PartitionsShuffleKind;
+use common_catalog::plan::PushDownInfo;
+use common_catalog::table::NavigationPoint;
+use common_catalog::table::Table;
+use common_catalog::table::TableStatistics;
+use common_catalog::table_context::TableContext;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::DataSchema;
+use common_expression::TableDataType;
+use common_functions::scalars::BUILTIN_FUNCTIONS;
+use common_meta_app::schema::TableIdent;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
+use common_meta_app::schema::TableStatistics as MetaTableStatistics;
+use common_pipeline_core::Pipeline;
+use common_storage::init_operator;
+use common_storage::ColumnLeaves;
+use common_storage::StorageParams;
+use common_storages_parquet::ColumnMeta;
+use common_storages_parquet::ParquetReader;
+use common_storages_parquet::ParquetRowGroupPart;
+use common_storages_parquet::ParquetSource;
+use opendal::Operator;
+use storages_common_pruner::RangePrunerCreator;
+
+use crate::avro::read_avro_records;
+use crate::manifest::DataFile;
+use crate::manifest::ManifestFile;
+use crate::manifest::CONTENT_DATA;
+use crate::pruner::DataFilePruner;
+use crate::table_metadata::Schema;
+use crate::table_metadata::Snapshot;
+use crate::table_metadata::TableMetadata;
+
+pub const ICEBERG_ENGINE: &str = "ICEBERG";
+
+const LOCATION: &str = "location";
+const METADATA_LOCATION: &str = "metadata_location";
+const SNAPSHOT_ID: &str = "snapshot_id";
+
+/// Where to find the table, all the paths are relative to the root of the catalog.
+///
+/// location, the directory of the table, such as: db/t/
+/// metadata_location, the metadata file, such as: db/t/metadata/v3.metadata.json
+/// snapshot_id, the snapshot to read, absent if the table has no snapshot yet
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IcebergTableOptions {
+    pub location: String,
+    pub metadata_location: String,
+    pub snapshot_id: Option<i64>,
+}
+
+impl From<IcebergTableOptions> for BTreeMap<String, String> {
+    fn from(options: IcebergTableOptions) -> BTreeMap<String, String> {
+        let mut map = BTreeMap::new();
+        map.insert(LOCATION.to_string(), options.location);
+        map.insert(METADATA_LOCATION.to_string(), options.metadata_location);
+        if let Some(snapshot_id) = options.snapshot_id {
+            map.insert(SNAPSHOT_ID.to_string(), snapshot_id.to_string());
+        }
+        map
+    }
+}
+
+impl TryFrom<&BTreeMap<String, String>> for IcebergTableOptions {
+    type Error = ErrorCode;
+    fn try_from(options: &BTreeMap<String, String>) -> Result<IcebergTableOptions> {
+        let get = |key: &str| {
+            options.get(key).cloned().ok_or_else(|| {
+                ErrorCode::Internal(format!("Iceberg engine table missing {} key", key))
+            })
+        };
+        let snapshot_id = match options.get(SNAPSHOT_ID) {
+            Some(v) => Some(v.parse::<i64>().map_err(|_| {
+                ErrorCode::Internal(format!(
+                    "Iceberg engine table has invalid snapshot id {}",
+                    v
+                ))
+            })?),
+            None => None,
+        };
+        Ok(IcebergTableOptions {
+            location: get(LOCATION)?,
+            metadata_location: get(METADATA_LOCATION)?,
+            snapshot_id,
+        })
+    }
+}
+
+/// The statistics of a snapshot are in its summary, which is optional.
+fn snapshot_statistics(snapshot: Option<&Snapshot>) -> MetaTableStatistics {
+    let mut statistics = MetaTableStatistics::default();
+    if let Some(summary) = snapshot.map(|s| &s.summary) {
+        let get = |key: &str| summary.get(key).and_then(|v| v.parse::<u64>().ok());
+        statistics.number_of_rows = get("total-records").unwrap_or_default();
+        statistics.compressed_data_bytes = get("total-files-size").unwrap_or_default();
+    }
+    statistics
+}
+
+/// A snapshot of an Iceberg table, the data files are read with the parquet reader.
+pub struct IcebergTable {
+    table_info: TableInfo,
+    table_options: IcebergTableOptions,
+    operator: Operator,
+}
+
+impl IcebergTable {
+    pub fn try_create(table_info: TableInfo) -> Result<IcebergTable> {
+        let table_options = IcebergTableOptions::try_from(&table_info.meta.engine_options)?;
+        let operator = match &table_info.meta.storage_params {
+            Some(sp) => init_operator(sp)?,
+            None => {
+                return Err(ErrorCode::Internal(
+                    "Iceberg engine table missing storage params",
+                ));
+            }
+        };
+        Ok(IcebergTable {
+            table_info,
+            table_options,
+            operator,
+        })
+    }
+
+    /// Build the table info of the given snapshot, or of the current snapshot if it's `None`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_table_info(
+        catalog: &str,
+        db_name: &str,
+        table_name: &str,
+        storage_params: &StorageParams,
+        location: &str,
+        metadata_location: &str,
+        metadata: &TableMetadata,
+        snapshot: Option<&Snapshot>,
+    ) -> Result<TableInfo> {
+        let snapshot = snapshot.or_else(|| metadata.current_snapshot());
+        let schema = metadata.schema(snapshot)?.to_table_schema()?;
+        let table_options = IcebergTableOptions {
+            location: location.to_string(),
+            metadata_location: metadata_location.to_string(),
+            snapshot_id: snapshot.map(|s| s.snapshot_id),
+        };
+
+        let meta = TableMeta {
+            schema: Arc::new(schema),
+            catalog: catalog.to_string(),
+            engine: ICEBERG_ENGINE.to_string(),
+            engine_options: table_options.into(),
+            storage_params: Some(storage_params.clone()),
+            created_on: metadata.last_updated_on(),
+            updated_on: metadata.last_updated_on(),
+            statistics: snapshot_statistics(snapshot),
+            ..Default::default()
+        };
+
+        Ok(TableInfo {
+            ident: TableIdent::new(0, 0),
+            desc: format!("'{}'.'{}'", db_name, table_name),
+            name: table_name.to_string(),
+            meta,
+            ..Default::default()
+        })
+    }
+
+    fn arrow_schema(&self) -> ArrowSchema {
+        self.table_info.schema().to_arrow()
+    }
+
+    async fn read_metadata(&self) -> Result<TableMetadata> {
+        let path = &self.table_options.metadata_location;
+        let data = self.operator.object(path).read().await?;
+        TableMetadata::parse(path, &data)
+    }
+
+    /// Map a path in the metadata, which is a full uri under the location of the table,
+    /// to the path relative to the root of the catalog.
+    fn resolve_path(&self, metadata: &TableMetadata, path: &str) -> Result<String> {
+        let table_location = metadata.location.trim_end_matches('/');
+        match path.strip_prefix(table_location) {
+            Some(relative) if relative.starts_with('/') => Ok(format!(
+                "{}{}",
+                self.table_options.location.trim_end_matches('/'),
+                relative
+            )),
+            _ => Err(ErrorCode::Unimplemented(format!(
+                "iceberg file {} is out of the table location {}",
+                path, metadata.location
+            ))),
+        }
+    }
+
+    async fn read_manifest_files(
+        &self,
+        metadata: &TableMetadata,
+        snapshot: &Snapshot,
+    ) -> Result<Vec<ManifestFile>> {
+        match &snapshot.manifest_list {
+            Some(manifest_list) => {
+                let path = self.resolve_path(metadata, manifest_list)?;
+                let data = self.operator.object(&path).read().await?;
+                read_avro_records(&path, data)?
+                    .iter()
+                    .map(ManifestFile::try_from_avro)
+                    .collect()
+            }
+            None => Ok(snapshot
+                .manifests
+                .iter()
+                .map(|path| ManifestFile::from_path(path))
+                .collect()),
+        }
+    }
+
+    async fn read_data_files(
+        &self,
+        metadata: &TableMetadata,
+        manifest: &ManifestFile,
+    ) -> Result<Vec<DataFile>> {
+        let path = self.resolve_path(metadata, &manifest.manifest_path)?;
+        let data = self.operator.object(&path).read().await?;
+        let mut data_files = vec![];
+        for entry in read_avro_records(&path, data)?.iter() {
+            if let Some(data_file) = DataFile::try_from_entry(entry)? {
+                data_files.push(data_file);
+            }
+        }
+        Ok(data_files)
+    }
+
+    #[tracing::instrument(level = "info", skip_all, fields(table = %self.table_info.desc))]
+    async fn do_read_partitions(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        push_downs: Option<PushDownInfo>,
+    ) -> Result<(PartStatistics, Partitions)> {
+        let metadata = self.read_metadata().await?;
+        let snapshot = match self.table_options.snapshot_id {
+            Some(id) => metadata.snapshot(id).ok_or_else(|| {
+                ErrorCode::TableHistoricalDataNotFound(format!("iceberg snapshot {} not found", id))
+            })?,
+            None => return Ok((PartStatistics::default(), Partitions::default())),
+        };
+
+        let table_schema = self.table_info.schema();
+        let filters = push_downs.as_ref().map(|extra| {
+            extra
+                .filters
+                .iter()
+                .map(|f| f.as_expr(&BUILTIN_FUNCTIONS).unwrap())
+                .collect::<Vec<_>>()
+        });
+        let file_pruner = DataFilePruner::try_create(
+            &ctx,
+            filters.as_deref(),
+            metadata.schema(Some(snapshot))?,
+            &table_schema,
+        )?;
+
+        // Prune the data files by the statistics in the manifests.
+        let mut total_files = 0;
+        let mut data_files = vec![];
+        for manifest in self.read_manifest_files(&metadata, snapshot).await? {
+            if manifest.content != CONTENT_DATA {
+                return Err(ErrorCode::Unimplemented(
+                    "iceberg tables with delete files are not supported yet",
+                ));
+            }
+            let partition_spec = metadata
+                .partition_spec(manifest.partition_spec_id)
+                .unwrap_or_default();
+            for data_file in self.read_data_files(&metadata, &manifest).await? {
+                if data_file.content != CONTENT_DATA {
+                    return Err(ErrorCode::Unimplemented(
+                        "iceberg tables with delete files are not supported yet",
+                    ));
+                }
+                if data_file.file_format != "PARQUET" {
+                    return Err(ErrorCode::Unimplemented(format!(
+                        "iceberg data file format {} is not supported yet",
+                        data_file.file_format
+                    )));
+                }
+                total_files += 1;
+                if file_pruner.should_keep(&data_file, partition_spec) {
+                    data_files.push(data_file);
+                }
+            }
+        }
+        tracing::info!(
+            "prune iceberg snapshot {}: {} of {} data files are kept",
+            snapshot.snapshot_id,
+            data_files.len(),
+            total_files
+        );
+
+        // Then prune the row groups by the statistics in the parquet files.
+        let arrow_schema = self.arrow_schema();
+        let projection = PushDownInfo::projection_of_push_downs(&table_schema, &push_downs);
+        let (_, _, _, columns_to_read) = ParquetReader::do_projection(&arrow_schema, &projection)?;
+        let row_group_pruner =
+            RangePrunerCreator::try_create(&ctx, filters.as_deref(), &table_schema)?;
+        // The statistics of row groups are collected by the indices of the leaf columns,
+        // which are only the same as the column ids for flat schemas.
+        let prune_row_groups = table_schema
+            .fields()
+            .iter()
+            .all(|f| f.leaf_column_ids().len() == 1);
+
+        let iceberg_schema = metadata.schema(Some(snapshot))?;
+        let mut read_rows = 0;
+        let mut read_bytes = 0;
+        let mut partitions: Vec<PartInfoPtr> = vec![];
+        for data_file in data_files.iter() {
+            let location = self.resolve_path(&metadata, &data_file.file_path)?;
+            let mut reader = self.operator.object(&location).reader().await?;
+            let file_meta = read_metadata_async(&mut reader).await?;
+            // The leaf columns of the file, if they are not the same as the table's.
+            let file_columns =
+                map_file_columns(iceberg_schema, &arrow_schema, &file_meta, &location)?;
+
+            let mut row_group_pruned = vec![false; file_meta.row_groups.len()];
+            if prune_row_groups && file_columns.is_none() {
+                // If collecting stats fails, we still read the row groups.
+                if let Ok(row_group_stats) = ParquetReader::collect_row_group_stats(
+                    &arrow_schema,
+                    &file_meta.row_groups,
+                    &columns_to_read,
+                ) {
+                    for (idx, stats) in row_group_stats.iter().enumerate() {
+                        row_group_pruned[idx] = !row_group_pruner.should_keep(stats);
+                    }
+                }
+            }
+
+            for (idx, rg) in file_meta.row_groups.iter().enumerate() {
+                if row_group_pruned[idx] {
+                    continue;
+                }
+                let mut column_metas = HashMap::with_capacity(columns_to_read.len());
+                for index in &columns_to_read {
+                    let file_index = match &file_columns {
+                        Some(file_columns) => file_columns[index],
+                        None => *index,
+                    };
+                    let c = &rg.columns()[file_index];
+                    let (offset, length) = c.byte_range();
+                    column_metas.insert(*index, ColumnMeta {
+                        offset,
+                        length,
+                        compression: c.compression().into(),
+                    });
+                }
+                read_rows += rg.num_rows();
+                read_bytes += rg.total_byte_size();
+                partitions.push(ParquetRowGroupPart::create(
+                    location.clone(),
+                    rg.num_rows(),
+                    column_metas,
+                    vec![],
+                ));
+            }
+        }
+
+        Ok((
+            PartStatistics::new_exact(read_rows, read_bytes, data_files.len(), total_files),
+            Partitions::create(PartitionsShuffleKind::Mod, partitions),
+        ))
+    }
+
+    fn do_read_data(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        plan: &DataSourcePlan,
+        pipeline: &mut Pipeline,
+    ) -> Result<()> {
+        let table_schema = plan.source_info.schema();
+        let projection = PushDownInfo::projection_of_push_downs(&table_schema, &plan.push_downs);
+        let output_schema = Arc::new(DataSchema::from(&projection.project_schema(&table_schema)));
+        let reader = ParquetReader::create(self.operator.clone(), self.arrow_schema(), projection)?;
+
+        let max_threads = ctx.get_settings().get_max_threads()? as usize;
+        let max_io_requests = ctx.get_settings().get_max_storage_io_requests()? as usize;
+        let max_io_requests = std::cmp::max(max_threads, max_io_requests);
+        pipeline.add_source(
+            |output| {
+                ParquetSource::create(
+                    ctx.clone(),
+                    output,
+                    output_schema.clone(),
+                    reader.clone(),
+                    Arc::new(None),
+                    Arc::new(None),
+                    vec![],
+                )
+            },
+            max_io_requests,
+        )?;
+
+        pipeline.resize(std::cmp::min(max_threads, max_io_requests))
+    }
+}
+
+/// Map the leaf columns of the table to the leaf columns of a data file.
+///
+/// The columns of the file are found by the Iceberg field ids, or by name if the file was
+/// written without field ids, so the files written before a column is renamed, dropped or
+/// reordered are still read correctly. Returns `None` if the leaf columns of the file are
+/// the same as the table's.
+fn map_file_columns(
+    iceberg_schema: &Schema,
+    arrow_schema: &ArrowSchema,
+    file_meta: &FileMetaData,
+    location: &str,
+) -> Result<Option<HashMap<usize, usize>>> {
+    let file_fields = file_meta.schema().fields();
+    let file_arrow_schema = infer_schema(file_meta)?;
+    let table_leaves = ColumnLeaves::new_from_schema(arrow_schema);
+    let file_leaves = ColumnLeaves::new_from_schema(&file_arrow_schema);
+    let has_field_ids = file_fields.iter().any(|f| f.get_field_info().id.is_some());
+
+    let mut identical = true;
+    let mut file_columns = HashMap::new();
+    for (field, table_leaf) in iceberg_schema
+        .fields
+        .iter()
+        .zip(table_leaves.column_leaves.iter())
+    {
+        let position = file_fields.iter().position(|f| {
+            let info = f.get_field_info();
+            if has_field_ids {
+                info.id == Some(field.id)
+            } else {
+                info.name.eq_ignore_ascii_case(&field.name)
+            }
+        });
+        let file_leaf = match position {
+            Some(position) => &file_leaves.column_leaves[position],
+            None => {
+                return Err(ErrorCode::Unimplemented(format!(
+                    "iceberg data file {} has no column {}, reading the files written before a column is added is not supported yet",
+                    location, field.name
+                )));
+            }
+        };
+        let table_type = TableDataType::from(&table_leaf.field).remove_nullable();
+        let file_type = TableDataType::from(&file_leaf.field).remove_nullable();
+        if table_type != file_type || table_leaf.leaf_ids.len() != file_leaf.leaf_ids.len() {
+            return Err(ErrorCode::Unimplemented(format!(
+                "column {} of iceberg data file {} has type {} instead of {}, reading the files written before the type of a column is changed is not supported yet",
+                field.name, location, file_type, table_type
+            )));
+        }
+        for (table_index, file_index) in table_leaf.leaf_ids.iter().zip(file_leaf.leaf_ids.iter()) {
+            identical &= table_index == file_index;
+            file_columns.insert(*table_index, *file_index);
+        }
+    }
+
+    if identical {
+        Ok(None)
+    } else {
+        Ok(Some(file_columns))
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for IcebergTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    fn benefit_column_prune(&self) -> bool {
+        true
+    }
+
+    fn has_exact_total_row_count(&self) -> bool {
+        true
+    }
+
+    fn table_statistics(&self) -> Result<Option<TableStatistics>> {
+        let stats = &self.table_info.meta.statistics;
+        Ok(Some(TableStatistics {
+            num_rows: Some(stats.number_of_rows),
+            data_size: None,
+            data_size_compressed: Some(stats.compressed_data_bytes),
+            index_size: None,
+        }))
+    }
+
+    async fn read_partitions(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        push_downs: Option<PushDownInfo>,
+    ) -> Result<(PartStatistics, Partitions)> {
+        self.do_read_partitions(ctx, push_downs).await
+    }
+
+    fn read_data(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        plan: &DataSourcePlan,
+        pipeline: &mut Pipeline,
+    ) -> Result<()> {
+        self.do_read_data(ctx, plan, pipeline)
+    }
+
+    async fn navigate_to(&self, point: &NavigationPoint) -> Result<Arc<dyn Table>> {
+        let metadata = self.read_metadata().await?;
+        let snapshot = metadata.navigate(point)?;
+        let mut table_info = self.table_info.clone();
+        table_info.meta.schema = Arc::new(metadata.schema(Some(snapshot))?.to_table_schema()?);
+        table_info.meta.engine_options = IcebergTableOptions {
+            snapshot_id: Some(snapshot.snapshot_id),
+            ..self.table_options.clone()
+        }
+        .into();
+        table_info.meta.statistics = snapshot_statistics(Some(snapshot));
+        Ok(Arc::new(IcebergTable::try_create(table_info)?))
+    }
+}
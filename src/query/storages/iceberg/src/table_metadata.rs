@@ -0,0 +1,312 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The table metadata file of Iceberg, see <https://iceberg.apache.org/spec/#table-metadata>.
+
+use std::collections::BTreeMap;
+
+use chrono::DateTime;
+use chrono::TimeZone;
+use chrono::Utc;
+use common_catalog::table::NavigationPoint;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::types::decimal::DecimalDataType;
+use common_expression::types::decimal::DecimalSize;
+use common_expression::types::NumberDataType;
+use common_expression::TableDataType;
+use common_expression::TableField;
+use common_expression::TableSchema;
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableMetadata {
+    pub format_version: i32,
+    pub location: String,
+    #[serde(default)]
+    pub last_updated_ms: i64,
+    /// Only in format v1, replaced by `schemas` in v2.
+    #[serde(default)]
+    pub schema: Option<Schema>,
+    #[serde(default)]
+    pub schemas: Vec<Schema>,
+    #[serde(default)]
+    pub current_schema_id: Option<i32>,
+    /// Only in format v1, replaced by `partition_specs` in v2.
+    #[serde(default)]
+    pub partition_spec: Option<Vec<PartitionField>>,
+    #[serde(default)]
+    pub partition_specs: Vec<PartitionSpec>,
+    #[serde(default)]
+    pub default_spec_id: Option<i32>,
+    #[serde(default)]
+    pub current_snapshot_id: Option<i64>,
+    #[serde(default)]
+    pub snapshots: Vec<Snapshot>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct Schema {
+    #[serde(default)]
+    pub schema_id: i32,
+    pub fields: Vec<NestedField>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct NestedField {
+    pub id: i32,
+    pub name: String,
+    pub required: bool,
+    #[serde(rename = "type")]
+    pub field_type: IcebergType,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum IcebergType {
+    /// `boolean`, `int`, `long`, `decimal(P, S)`, ...
+    Primitive(String),
+    Struct {
+        fields: Vec<NestedField>,
+    },
+    List {
+        element: Box<IcebergType>,
+        #[serde(rename = "element-required")]
+        element_required: bool,
+    },
+    // Only recognized to be rejected, reading maps is not supported yet.
+    #[allow(dead_code)]
+    Map {
+        key: Box<IcebergType>,
+        value: Box<IcebergType>,
+    },
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct PartitionSpec {
+    pub spec_id: i32,
+    pub fields: Vec<PartitionField>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct PartitionField {
+    pub name: String,
+    pub transform: String,
+    pub source_id: i32,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct Snapshot {
+    pub snapshot_id: i64,
+    pub timestamp_ms: i64,
+    /// Only absent in format v1, where the snapshot may list its manifests directly.
+    #[serde(default)]
+    pub manifest_list: Option<String>,
+    #[serde(default)]
+    pub manifests: Vec<String>,
+    #[serde(default)]
+    pub summary: BTreeMap<String, String>,
+    #[serde(default)]
+    pub schema_id: Option<i32>,
+}
+
+impl TableMetadata {
+    pub fn parse(path: &str, data: &[u8]) -> Result<TableMetadata> {
+        let metadata: TableMetadata = serde_json::from_slice(data).map_err(|e| {
+            ErrorCode::BadBytes(format!("invalid iceberg metadata file {}: {}", path, e))
+        })?;
+        if metadata.format_version > 2 {
+            return Err(ErrorCode::Unimplemented(format!(
+                "iceberg format version {} is not supported",
+                metadata.format_version
+            )));
+        }
+        Ok(metadata)
+    }
+
+    /// The snapshot read by default, `None` if the table has no data yet.
+    pub fn current_snapshot(&self) -> Option<&Snapshot> {
+        // `-1` is written by some writers instead of omitting the field.
+        let id = self.current_snapshot_id.filter(|id| *id != -1)?;
+        self.snapshots.iter().find(|s| s.snapshot_id == id)
+    }
+
+    pub fn snapshot(&self, snapshot_id: i64) -> Option<&Snapshot> {
+        self.snapshots.iter().find(|s| s.snapshot_id == snapshot_id)
+    }
+
+    /// Find the snapshot to read for the given navigation point.
+    ///
+    /// A time point selects the latest snapshot committed at or before it.
+    pub fn navigate(&self, point: &NavigationPoint) -> Result<&Snapshot> {
+        match point {
+            NavigationPoint::SnapshotID(id) => {
+                let snapshot_id = id.parse::<i64>().map_err(|_| {
+                    ErrorCode::TableHistoricalDataNotFound(format!(
+                        "invalid iceberg snapshot id {}",
+                        id
+                    ))
+                })?;
+                self.snapshot(snapshot_id).ok_or_else(|| {
+                    ErrorCode::TableHistoricalDataNotFound(format!(
+                        "No historical data found at given point: snapshot {}",
+                        id
+                    ))
+                })
+            }
+            NavigationPoint::TimePoint(time_point) => {
+                let millis = time_point.timestamp_millis();
+                self.snapshots
+                    .iter()
+                    .filter(|s| s.timestamp_ms <= millis)
+                    .max_by_key(|s| s.timestamp_ms)
+                    .ok_or_else(|| {
+                        ErrorCode::TableHistoricalDataNotFound(format!(
+                            "No historical data found at given point: {}",
+                            time_point
+                        ))
+                    })
+            }
+        }
+    }
+
+    /// The schema of the snapshot, or the current schema of the table.
+    pub fn schema(&self, snapshot: Option<&Snapshot>) -> Result<&Schema> {
+        let schema_id = snapshot
+            .and_then(|s| s.schema_id)
+            .or(self.current_schema_id);
+        let schema = match schema_id {
+            Some(id) => self.schemas.iter().find(|s| s.schema_id == id),
+            None => None,
+        };
+        schema
+            .or(self.schema.as_ref())
+            .or_else(|| self.schemas.last())
+            .ok_or_else(|| ErrorCode::BadBytes("iceberg metadata has no schema"))
+    }
+
+    pub fn partition_spec(&self, spec_id: i32) -> Option<&[PartitionField]> {
+        match self.partition_specs.iter().find(|s| s.spec_id == spec_id) {
+            Some(spec) => Some(&spec.fields),
+            None => self.partition_spec.as_deref(),
+        }
+    }
+
+    pub fn last_updated_on(&self) -> DateTime<Utc> {
+        Utc.timestamp_millis_opt(self.last_updated_ms)
+            .single()
+            .unwrap_or_else(Utc::now)
+    }
+}
+
+impl Schema {
+    pub fn to_table_schema(&self) -> Result<TableSchema> {
+        let fields = self
+            .fields
+            .iter()
+            .map(|f| {
+                Ok(TableField::new(
+                    &f.name.to_lowercase(),
+                    f.to_table_data_type()?,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(TableSchema::new(fields))
+    }
+}
+
+impl NestedField {
+    fn to_table_data_type(&self) -> Result<TableDataType> {
+        Ok(wrap_nullable(
+            self.field_type.to_table_data_type()?,
+            self.required,
+        ))
+    }
+}
+
+fn wrap_nullable(ty: TableDataType, required: bool) -> TableDataType {
+    if required {
+        ty
+    } else {
+        TableDataType::Nullable(Box::new(ty))
+    }
+}
+
+impl IcebergType {
+    pub fn to_table_data_type(&self) -> Result<TableDataType> {
+        match self {
+            IcebergType::Primitive(name) => primitive_to_table_data_type(name),
+            IcebergType::Struct { fields } => {
+                let fields_name = fields.iter().map(|f| f.name.to_lowercase()).collect();
+                let fields_type = fields
+                    .iter()
+                    .map(|f| f.to_table_data_type())
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(TableDataType::Tuple {
+                    fields_name,
+                    fields_type,
+                })
+            }
+            IcebergType::List {
+                element,
+                element_required,
+            } => {
+                let ty = wrap_nullable(element.to_table_data_type()?, *element_required);
+                Ok(TableDataType::Array(Box::new(ty)))
+            }
+            IcebergType::Map { .. } => Err(ErrorCode::Unimplemented(
+                "iceberg type map is not supported yet",
+            )),
+        }
+    }
+}
+
+fn primitive_to_table_data_type(name: &str) -> Result<TableDataType> {
+    let ty = match name {
+        "boolean" => TableDataType::Boolean,
+        "int" => TableDataType::Number(NumberDataType::Int32),
+        "long" => TableDataType::Number(NumberDataType::Int64),
+        "float" => TableDataType::Number(NumberDataType::Float32),
+        "double" => TableDataType::Number(NumberDataType::Float64),
+        "date" => TableDataType::Date,
+        "timestamp" | "timestamptz" => TableDataType::Timestamp,
+        "string" | "binary" => TableDataType::String,
+        _ => {
+            if let Some(args) = name
+                .strip_prefix("decimal(")
+                .and_then(|s| s.strip_suffix(')'))
+            {
+                let mut args = args.split(',').map(|s| s.trim().parse::<u8>());
+                if let (Some(Ok(precision)), Some(Ok(scale)), None) =
+                    (args.next(), args.next(), args.next())
+                {
+                    let size = DecimalSize { precision, scale };
+                    return DecimalDataType::from_size(size)
+                        .map(TableDataType::Decimal)
+                        .map_err(ErrorCode::BadBytes);
+                }
+            }
+            return Err(ErrorCode::Unimplemented(format!(
+                "iceberg type {} is not supported yet",
+                name
+            )));
+        }
+    };
+    Ok(ty)
+}
@@ -0,0 +1,91 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use common_storages_iceberg::avro::read_avro_records;
+use common_storages_iceberg::avro::AvroValue;
+
+use crate::read_fixture;
+
+#[test]
+fn test_read_manifest_list() -> Result<()> {
+    let records = read_avro_records("snap-2.avro", read_fixture("metadata/snap-2.avro"))?;
+    assert_eq!(records.len(), 2);
+
+    let first = &records[0];
+    assert_eq!(
+        first.field("manifest_path").and_then(|v| v.as_str()),
+        Some("s3://warehouse/default/customer/metadata/m1.avro")
+    );
+    assert_eq!(first.field("partition_spec_id"), Some(&AvroValue::Int(0)));
+    assert_eq!(
+        first.field("added_snapshot_id").and_then(|v| v.as_i64()),
+        Some(1)
+    );
+    assert_eq!(
+        records[1]
+            .field("added_snapshot_id")
+            .and_then(|v| v.as_i64()),
+        Some(2)
+    );
+    assert_eq!(first.field("not_exists"), None);
+    Ok(())
+}
+
+#[test]
+fn test_read_nested_values() -> Result<()> {
+    let records = read_avro_records("m1.avro", read_fixture("metadata/m1.avro"))?;
+    assert_eq!(records.len(), 1);
+
+    let entry = &records[0];
+    // union of null and long
+    assert_eq!(entry.field("snapshot_id"), Some(&AvroValue::Long(1)));
+
+    let data_file = entry.field("data_file").unwrap();
+    assert_eq!(
+        data_file.field("partition"),
+        Some(&AvroValue::Record(vec![]))
+    );
+
+    // map with int keys, stored as an array of records
+    let lower_bounds = data_file.field("lower_bounds").unwrap().as_int_map();
+    let lower_bounds = lower_bounds
+        .iter()
+        .map(|(k, v)| (*k, v.as_bytes().unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(lower_bounds, vec![
+        (1, b"foo".as_slice()),
+        (2, b"ASIA".as_slice()),
+        (3, b"CHINA".as_slice()),
+    ]);
+    Ok(())
+}
+
+#[test]
+fn test_read_invalid_file() -> Result<()> {
+    let data = read_fixture("metadata/snap-1.avro");
+
+    // the data of the block ends before the sync marker
+    let truncated = data[..data.len() - 4].to_vec();
+    assert!(read_avro_records("snap-1.avro", truncated).is_err());
+
+    let mut corrupted = data.clone();
+    let last = corrupted.len() - 1;
+    corrupted[last] ^= 0xff;
+    let err = read_avro_records("snap-1.avro", corrupted).unwrap_err();
+    assert!(err.message().contains("sync marker mismatch"));
+
+    assert!(read_avro_records("empty.avro", vec![]).is_err());
+    Ok(())
+}
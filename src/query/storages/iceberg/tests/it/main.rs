@@ -0,0 +1,26 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod avro;
+mod manifest;
+mod pruner;
+mod table_metadata;
+
+/// The iceberg table under `tests/data`, of which snapshot 1 has one data file of
+/// `ASIA` and snapshot 2 appends another of `EUROPE`.
+pub const TABLE_DIR: &str = "../../../../tests/data/iceberg/warehouse/default/customer";
+
+pub fn read_fixture(path: &str) -> Vec<u8> {
+    std::fs::read(format!("{}/{}", TABLE_DIR, path)).unwrap()
+}
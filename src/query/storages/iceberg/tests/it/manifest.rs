@@ -0,0 +1,91 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use common_storages_iceberg::avro::read_avro_records;
+use common_storages_iceberg::avro::AvroValue;
+use common_storages_iceberg::manifest::DataFile;
+use common_storages_iceberg::manifest::ManifestFile;
+use common_storages_iceberg::manifest::CONTENT_DATA;
+
+use crate::read_fixture;
+
+#[test]
+fn test_manifest_files() -> Result<()> {
+    let records = read_avro_records("snap-2.avro", read_fixture("metadata/snap-2.avro"))?;
+    let manifests = records
+        .iter()
+        .map(ManifestFile::try_from_avro)
+        .collect::<Result<Vec<_>>>()?;
+    let paths = manifests
+        .iter()
+        .map(|m| m.manifest_path.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(paths, vec![
+        "s3://warehouse/default/customer/metadata/m1.avro",
+        "s3://warehouse/default/customer/metadata/m2.avro",
+    ]);
+    assert!(manifests.iter().all(|m| m.content == CONTENT_DATA));
+    assert!(manifests.iter().all(|m| m.partition_spec_id == 0));
+
+    // a manifest without a path is invalid
+    let invalid = AvroValue::Record(vec![("content".to_string(), AvroValue::Int(0))]);
+    assert!(ManifestFile::try_from_avro(&invalid).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_data_files() -> Result<()> {
+    let records = read_avro_records("m2.avro", read_fixture("metadata/m2.avro"))?;
+    assert_eq!(records.len(), 1);
+    let data_file = DataFile::try_from_entry(&records[0])?.unwrap();
+    assert_eq!(
+        data_file.file_path,
+        "s3://warehouse/default/customer/data/france.parquet"
+    );
+    assert_eq!(data_file.file_format, "PARQUET");
+    assert_eq!(data_file.content, CONTENT_DATA);
+    assert_eq!(data_file.record_count, 2);
+    assert!(data_file.partition.is_empty());
+    assert_eq!(data_file.null_value_counts.get(&2), Some(&0));
+    assert_eq!(
+        data_file.lower_bounds.get(&2).map(|v| v.as_slice()),
+        Some(b"EUROPE".as_slice())
+    );
+    assert_eq!(
+        data_file.upper_bounds.get(&3).map(|v| v.as_slice()),
+        Some(b"FRANCE2".as_slice())
+    );
+    Ok(())
+}
+
+#[test]
+fn test_deleted_entry() -> Result<()> {
+    let records = read_avro_records("m1.avro", read_fixture("metadata/m1.avro"))?;
+    let mut fields = match records[0].clone() {
+        AvroValue::Record(fields) => fields,
+        other => unreachable!("{:?}", other),
+    };
+    for (name, value) in fields.iter_mut() {
+        if name == "status" {
+            *value = AvroValue::Int(2);
+        }
+    }
+    assert!(DataFile::try_from_entry(&AvroValue::Record(fields))?.is_none());
+
+    // the data file is required for alive entries
+    let invalid = AvroValue::Record(vec![("status".to_string(), AvroValue::Int(1))]);
+    assert!(DataFile::try_from_entry(&invalid).is_err());
+    Ok(())
+}
@@ -0,0 +1,109 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_expression::types::decimal::DecimalDataType;
+use common_expression::types::decimal::DecimalScalar;
+use common_expression::types::decimal::DecimalSize;
+use common_expression::types::number::F32;
+use common_expression::types::number::F64;
+use common_expression::types::NumberDataType;
+use common_expression::types::NumberScalar;
+use common_expression::Scalar;
+use common_expression::TableDataType;
+use common_storages_iceberg::pruner::decode_bound;
+
+fn nullable(ty: TableDataType) -> TableDataType {
+    TableDataType::Nullable(Box::new(ty))
+}
+
+#[test]
+fn test_decode_numbers() {
+    let int = TableDataType::Number(NumberDataType::Int32);
+    assert_eq!(
+        decode_bound(&int, &(-7i32).to_le_bytes()),
+        Some(Scalar::Number(NumberScalar::Int32(-7)))
+    );
+    // wrong size
+    assert_eq!(decode_bound(&int, &[1, 0]), None);
+
+    let long = nullable(TableDataType::Number(NumberDataType::Int64));
+    assert_eq!(
+        decode_bound(&long, &(1i64 << 40).to_le_bytes()),
+        Some(Scalar::Number(NumberScalar::Int64(1 << 40)))
+    );
+    // written as an int before the column was promoted to long
+    assert_eq!(
+        decode_bound(&long, &(-3i32).to_le_bytes()),
+        Some(Scalar::Number(NumberScalar::Int64(-3)))
+    );
+
+    let float = TableDataType::Number(NumberDataType::Float32);
+    assert_eq!(
+        decode_bound(&float, &1.5f32.to_le_bytes()),
+        Some(Scalar::Number(NumberScalar::Float32(F32::from(1.5))))
+    );
+    assert_eq!(decode_bound(&float, &f32::NAN.to_le_bytes()), None);
+
+    let double = TableDataType::Number(NumberDataType::Float64);
+    assert_eq!(
+        decode_bound(&double, &(-2.25f64).to_le_bytes()),
+        Some(Scalar::Number(NumberScalar::Float64(F64::from(-2.25))))
+    );
+    // written as a float before the column was promoted to double
+    assert_eq!(
+        decode_bound(&double, &0.5f32.to_le_bytes()),
+        Some(Scalar::Number(NumberScalar::Float64(F64::from(0.5))))
+    );
+    assert_eq!(decode_bound(&double, &f64::NAN.to_le_bytes()), None);
+}
+
+#[test]
+fn test_decode_others() {
+    assert_eq!(
+        decode_bound(&TableDataType::Date, &19000i32.to_le_bytes()),
+        Some(Scalar::Date(19000))
+    );
+    assert_eq!(
+        decode_bound(
+            &TableDataType::Timestamp,
+            &1672531200000000i64.to_le_bytes()
+        ),
+        Some(Scalar::Timestamp(1672531200000000))
+    );
+    assert_eq!(
+        decode_bound(&nullable(TableDataType::String), b"EUROPE"),
+        Some(Scalar::String(b"EUROPE".to_vec()))
+    );
+
+    // decimals are big-endian two's complement of the minimal length
+    let size = DecimalSize {
+        precision: 10,
+        scale: 2,
+    };
+    let decimal = TableDataType::Decimal(DecimalDataType::Decimal128(size));
+    assert_eq!(
+        decode_bound(&decimal, &[0x01, 0x00]),
+        Some(Scalar::Decimal(DecimalScalar::Decimal128(256, size)))
+    );
+    assert_eq!(
+        decode_bound(&decimal, &[0xff, 0x38]),
+        Some(Scalar::Decimal(DecimalScalar::Decimal128(-200, size)))
+    );
+    assert_eq!(decode_bound(&decimal, &[]), None);
+    assert_eq!(decode_bound(&decimal, &[0; 17]), None);
+
+    // bounds of nested types are not used
+    let array = TableDataType::Array(Box::new(TableDataType::String));
+    assert_eq!(decode_bound(&array, b"a"), None);
+}
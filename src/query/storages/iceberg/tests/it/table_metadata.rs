@@ -0,0 +1,199 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::TimeZone;
+use chrono::Utc;
+use common_catalog::table::NavigationPoint;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::types::decimal::DecimalDataType;
+use common_expression::types::decimal::DecimalSize;
+use common_expression::types::NumberDataType;
+use common_expression::TableDataType;
+use common_storages_iceberg::table_metadata::TableMetadata;
+
+use crate::read_fixture;
+
+fn read_table_metadata(version: u32) -> Result<TableMetadata> {
+    let path = format!("metadata/v{}.metadata.json", version);
+    TableMetadata::parse(&path, &read_fixture(&path))
+}
+
+#[test]
+fn test_parse_v2() -> Result<()> {
+    let metadata = read_table_metadata(2)?;
+    assert_eq!(metadata.format_version, 2);
+    assert_eq!(metadata.location, "s3://warehouse/default/customer");
+    assert_eq!(metadata.snapshots.len(), 2);
+    assert_eq!(metadata.current_snapshot().unwrap().snapshot_id, 2);
+    assert_eq!(
+        metadata.snapshot(1).unwrap().manifest_list.as_deref(),
+        Some("s3://warehouse/default/customer/metadata/snap-1.avro")
+    );
+    assert!(metadata.snapshot(3).is_none());
+    assert_eq!(metadata.partition_spec(0).map(|spec| spec.len()), Some(0));
+
+    let schema = metadata
+        .schema(metadata.current_snapshot())?
+        .to_table_schema()?;
+    let names = schema
+        .fields()
+        .iter()
+        .map(|f| f.name().as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["foo", "c_region2", "c_nation2"]);
+    assert!(
+        schema
+            .fields()
+            .iter()
+            .all(|f| f.data_type() == &TableDataType::Nullable(Box::new(TableDataType::String)))
+    );
+
+    // the older version only has the first snapshot
+    let metadata = read_table_metadata(1)?;
+    assert_eq!(metadata.current_snapshot().unwrap().snapshot_id, 1);
+    assert_eq!(metadata.snapshots.len(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_navigate() -> Result<()> {
+    let metadata = read_table_metadata(2)?;
+
+    let point = NavigationPoint::SnapshotID("1".to_string());
+    assert_eq!(metadata.navigate(&point)?.snapshot_id, 1);
+
+    let point = NavigationPoint::SnapshotID("3".to_string());
+    let err = metadata.navigate(&point).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::TABLE_HISTORICAL_DATA_NOT_FOUND);
+
+    let point = NavigationPoint::SnapshotID("abc".to_string());
+    let err = metadata.navigate(&point).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::TABLE_HISTORICAL_DATA_NOT_FOUND);
+
+    // snapshot 1 is committed at 2023-01-01, snapshot 2 at 2023-02-01
+    // 2023-01-15
+    let point = NavigationPoint::TimePoint(Utc.timestamp_millis_opt(1673740800000).unwrap());
+    assert_eq!(metadata.navigate(&point)?.snapshot_id, 1);
+
+    // 2023-02-01, the time of snapshot 2 itself
+    let point = NavigationPoint::TimePoint(Utc.timestamp_millis_opt(1675209600000).unwrap());
+    assert_eq!(metadata.navigate(&point)?.snapshot_id, 2);
+
+    // 2022-12-01, before any snapshot
+    let point = NavigationPoint::TimePoint(Utc.timestamp_millis_opt(1669852800000).unwrap());
+    assert!(metadata.navigate(&point).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_parse_v1() -> Result<()> {
+    let json = r#"{
+        "format-version": 1,
+        "location": "s3://warehouse/db/t",
+        "last-updated-ms": 1672531200000,
+        "schema": {
+            "type": "struct",
+            "fields": [
+                {"id": 1, "name": "ID", "required": true, "type": "long"},
+                {"id": 2, "name": "price", "required": false, "type": "decimal(10, 2)"},
+                {"id": 3, "name": "tags", "required": false, "type": {
+                    "type": "list", "element-id": 4, "element": "string", "element-required": false
+                }},
+                {"id": 5, "name": "point", "required": true, "type": {
+                    "type": "struct", "fields": [
+                        {"id": 6, "name": "x", "required": true, "type": "double"},
+                        {"id": 7, "name": "y", "required": true, "type": "double"}
+                    ]
+                }}
+            ]
+        },
+        "partition-spec": [{"name": "id_part", "transform": "identity", "source-id": 1, "field-id": 1000}],
+        "current-snapshot-id": -1,
+        "snapshots": [
+            {"snapshot-id": 7, "timestamp-ms": 1672531200000, "manifests": ["s3://warehouse/db/t/metadata/m.avro"]}
+        ]
+    }"#;
+    let metadata = TableMetadata::parse("v1.metadata.json", json.as_bytes())?;
+    // `-1` means the table has no current snapshot
+    assert!(metadata.current_snapshot().is_none());
+    assert_eq!(metadata.snapshot(7).unwrap().manifests, vec![
+        "s3://warehouse/db/t/metadata/m.avro".to_string()
+    ]);
+
+    let partition_spec = metadata.partition_spec(0).unwrap();
+    assert_eq!(partition_spec.len(), 1);
+    assert_eq!(partition_spec[0].source_id, 1);
+    assert_eq!(partition_spec[0].transform, "identity");
+
+    let schema = metadata.schema(None)?.to_table_schema()?;
+    let fields = schema.fields();
+    assert_eq!(fields[0].name(), "id");
+    assert_eq!(
+        fields[0].data_type(),
+        &TableDataType::Number(NumberDataType::Int64)
+    );
+    assert_eq!(
+        fields[1].data_type(),
+        &TableDataType::Nullable(Box::new(TableDataType::Decimal(
+            DecimalDataType::Decimal128(DecimalSize {
+                precision: 10,
+                scale: 2
+            })
+        )))
+    );
+    assert_eq!(
+        fields[2].data_type(),
+        &TableDataType::Nullable(Box::new(TableDataType::Array(Box::new(
+            TableDataType::Nullable(Box::new(TableDataType::String))
+        ))))
+    );
+    assert_eq!(fields[3].data_type(), &TableDataType::Tuple {
+        fields_name: vec!["x".to_string(), "y".to_string()],
+        fields_type: vec![
+            TableDataType::Number(NumberDataType::Float64),
+            TableDataType::Number(NumberDataType::Float64)
+        ],
+    });
+    Ok(())
+}
+
+#[test]
+fn test_parse_unsupported() -> Result<()> {
+    let json = r#"{"format-version": 3, "location": "s3://warehouse/db/t"}"#;
+    let err = TableMetadata::parse("v1.metadata.json", json.as_bytes()).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::UNIMPLEMENTED);
+
+    let err = TableMetadata::parse("v1.metadata.json", b"{").unwrap_err();
+    assert_eq!(err.code(), ErrorCode::BAD_BYTES);
+
+    let json = r#"{
+        "format-version": 2,
+        "location": "s3://warehouse/db/t",
+        "current-schema-id": 0,
+        "schemas": [{"schema-id": 0, "type": "struct", "fields": [
+            {"id": 1, "name": "m", "required": true, "type": {
+                "type": "map", "key-id": 2, "key": "string", "value-id": 3, "value": "int", "value-required": true
+            }},
+            {"id": 4, "name": "u", "required": true, "type": "uuid"}
+        ]}]
+    }"#;
+    let metadata = TableMetadata::parse("v1.metadata.json", json.as_bytes())?;
+    let schema = metadata.schema(None)?;
+    for field in schema.fields.iter() {
+        let err = field.field_type.to_table_data_type().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::UNIMPLEMENTED);
+    }
+    Ok(())
+}
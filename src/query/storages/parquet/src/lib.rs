@@ -20,7 +20,9 @@ mod parquet_reader;
 mod parquet_source;
 mod table_function;
 
+pub use parquet_part::ColumnMeta;
 pub use parquet_part::ParquetLocationPart;
+pub use parquet_part::ParquetRowGroupPart;
 pub use parquet_reader::ParquetReader;
 pub use parquet_source::ParquetSource;
 pub use table_function::ParquetTable;
@@ -24,6 +24,7 @@ use common_catalog::plan::PartInfo;
 use common_catalog::plan::PartInfoPtr;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_expression::Scalar;
 
 #[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq)]
 pub struct ParquetLocationPart {
@@ -119,6 +120,9 @@ pub struct ParquetRowGroupPart {
     pub location: String,
     pub num_rows: usize,
     pub column_metas: HashMap<usize, ColumnMeta>,
+    /// The values of the partition columns which are not stored in the file,
+    /// in the order of the partition fields given to the source.
+    pub partition_values: Vec<Scalar>,
 }
 
 #[typetag::serde(name = "parquet_row_group")]
@@ -146,11 +150,13 @@ impl ParquetRowGroupPart {
         location: String,
         num_rows: usize,
         column_metas: HashMap<usize, ColumnMeta>,
+        partition_values: Vec<Scalar>,
     ) -> Arc<Box<dyn PartInfo>> {
         Arc::new(Box::new(ParquetRowGroupPart {
             location,
             num_rows,
             column_metas,
+            partition_values,
         }))
     }
 
@@ -22,7 +22,9 @@ use common_catalog::table_context::TableContext;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_expression::types::BooleanType;
+use common_expression::BlockEntry;
 use common_expression::DataBlock;
+use common_expression::DataField;
 use common_expression::DataSchemaRef;
 use common_expression::DataSchemaRefExt;
 use common_expression::Evaluator;
@@ -67,6 +69,9 @@ pub struct ParquetSource {
     prewhere_reader: Arc<ParquetReader>,
     prewhere_filter: Arc<Option<Expr>>,
     remain_reader: Arc<Option<ParquetReader>>,
+    /// The partition columns which are not stored in the files,
+    /// their values are carried by the parts.
+    partition_fields: Vec<DataField>,
 }
 
 impl ParquetSource {
@@ -77,6 +82,7 @@ impl ParquetSource {
         prewhere_reader: Arc<ParquetReader>,
         prewhere_filter: Arc<Option<Expr>>,
         remain_reader: Arc<Option<ParquetReader>>,
+        partition_fields: Vec<DataField>,
     ) -> Result<ProcessorPtr> {
         let scan_progress = ctx.get_scan_progress();
         let mut src_fields = prewhere_reader.output_schema().fields().clone();
@@ -84,6 +90,7 @@ impl ParquetSource {
             let remain_field = reader.output_schema().fields();
             src_fields.extend_from_slice(remain_field);
         }
+        src_fields.extend_from_slice(&partition_fields);
         let src_schema = DataSchemaRefExt::create(src_fields);
 
         Ok(ProcessorPtr::create(Box::new(ParquetSource {
@@ -96,9 +103,30 @@ impl ParquetSource {
             prewhere_reader,
             prewhere_filter,
             remain_reader,
+            partition_fields,
         })))
     }
 
+    /// Append the values of the partition columns as constant columns.
+    fn fill_partition_values(&self, block: DataBlock, part: &ParquetRowGroupPart) -> DataBlock {
+        if self.partition_fields.is_empty() {
+            return block;
+        }
+        let num_rows = block.num_rows();
+        let mut columns = block.columns().to_vec();
+        for (field, value) in self
+            .partition_fields
+            .iter()
+            .zip(part.partition_values.iter())
+        {
+            columns.push(BlockEntry {
+                data_type: field.data_type().clone(),
+                value: Value::Scalar(value.clone()),
+            });
+        }
+        DataBlock::new(columns, num_rows)
+    }
+
     fn do_prewhere_filter(
         &mut self,
         part: PartInfoPtr,
@@ -161,8 +189,9 @@ impl ParquetSource {
                 // shortcut, we don't need to read remain data
                 let progress_values = ProgressValues { rows, bytes };
                 self.scan_progress.incr(&progress_values);
-                let block =
-                    filtered_block.resort(self.src_schema.as_ref(), self.output_schema.as_ref())?;
+                let block = self
+                    .fill_partition_values(filtered_block, rg_part)
+                    .resort(self.src_schema.as_ref(), self.output_schema.as_ref())?;
                 self.state = Generated(self.ctx.try_get_part(), block);
             } else {
                 self.state = State::ReadDataRemain(part, PrewhereData {
@@ -239,8 +268,9 @@ impl ParquetSource {
         };
         self.scan_progress.incr(&progress_values);
 
-        let output_block =
-            output_block.resort(self.src_schema.as_ref(), self.output_schema.as_ref())?;
+        let output_block = self
+            .fill_partition_values(output_block, rg_part)
+            .resort(self.src_schema.as_ref(), self.output_schema.as_ref())?;
         self.state = State::Generated(self.ctx.try_get_part(), output_block);
         Ok(())
     }
@@ -176,6 +176,7 @@ impl ParquetTable {
                         location.clone(),
                         rg.num_rows(),
                         column_metas,
+                        vec![],
                     ))
                 }
             }
@@ -211,6 +212,7 @@ impl ParquetTable {
                     prewhere_reader.clone(),
                     prewhere_filter.clone(),
                     remain_reader.clone(),
+                    vec![],
                 )
             },
             max_io_requests,
@@ -25,6 +25,7 @@ mod databases_table;
 mod engines_table;
 mod functions_table;
 mod log_queue;
+mod maintenance_history_table;
 mod malloc_stats_table;
 mod malloc_stats_totals_table;
 mod metrics_table;
@@ -34,6 +35,7 @@ mod query_log_table;
 mod roles_table;
 mod settings_table;
 mod stages_table;
+mod storage_quotas_table;
 mod table;
 mod tables_table;
 mod tracing_table;
@@ -54,6 +56,9 @@ pub use functions_table::FunctionsTable;
 pub use log_queue::SystemLogElement;
 pub use log_queue::SystemLogQueue;
 pub use log_queue::SystemLogTable;
+pub use maintenance_history_table::MaintenanceHistoryLogElement;
+pub use maintenance_history_table::MaintenanceHistoryQueue;
+pub use maintenance_history_table::MaintenanceHistoryTable;
 pub use malloc_stats_table::MallocStatsTable;
 pub use malloc_stats_totals_table::MallocStatsTotalsTable;
 pub use metrics_table::MetricsTable;
@@ -66,6 +71,7 @@ pub use query_log_table::QueryLogTable;
 pub use roles_table::RolesTable;
 pub use settings_table::SettingsTable;
 pub use stages_table::StagesTable;
+pub use storage_quotas_table::StorageQuotasTable;
 pub use table::SyncOneBlockSystemTable;
 pub use table::SyncSystemTable;
 pub use tables_table::TablesTable;
@@ -0,0 +1,100 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+
+use common_catalog::catalog_kind::CATALOG_DEFAULT;
+use common_catalog::table::Table;
+use common_catalog::table_context::TableContext;
+use common_exception::Result;
+use common_expression::types::number::UInt64Type;
+use common_expression::types::NumberDataType;
+use common_expression::types::StringType;
+use common_expression::utils::FromData;
+use common_expression::DataBlock;
+use common_expression::TableDataType;
+use common_expression::TableField;
+use common_expression::TableSchemaRefExt;
+use common_meta_app::schema::TableIdent;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
+use common_storages_fuse::operations::list_database_storage_usage;
+
+use crate::table::AsyncOneBlockSystemTable;
+use crate::table::AsyncSystemTable;
+
+/// Storage used by the tenant and each of its databases, against the limit of the tenant quota.
+pub struct StorageQuotasTable {
+    table_info: TableInfo,
+}
+
+#[async_trait::async_trait]
+impl AsyncSystemTable for StorageQuotasTable {
+    const NAME: &'static str = "system.storage_quotas";
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn get_full_data(&self, ctx: Arc<dyn TableContext>) -> Result<DataBlock> {
+        let tenant = ctx.get_tenant();
+        let quota = ctx.get_tenant_quota().await?;
+        let catalog = ctx.get_catalog(CATALOG_DEFAULT)?;
+        let usages = list_database_storage_usage(catalog.as_ref(), tenant.as_str()).await?;
+
+        let mut scopes = vec!["TENANT".as_bytes().to_vec()];
+        let mut names = vec![tenant.clone().into_bytes()];
+        let mut used_bytes = vec![usages.iter().map(|u| u.used_bytes()).sum::<u64>()];
+        let mut max_bytes = vec![quota.max_storage_in_bytes];
+
+        for usage in usages {
+            scopes.push("DATABASE".as_bytes().to_vec());
+            names.push(usage.database.clone().into_bytes());
+            used_bytes.push(usage.used_bytes());
+            max_bytes.push(quota.max_storage_per_database_in_bytes);
+        }
+
+        Ok(DataBlock::new_from_columns(vec![
+            StringType::from_data(scopes),
+            StringType::from_data(names),
+            UInt64Type::from_data(used_bytes),
+            UInt64Type::from_data(max_bytes),
+        ]))
+    }
+}
+
+impl StorageQuotasTable {
+    pub fn create(table_id: u64) -> Arc<dyn Table> {
+        let schema = TableSchemaRefExt::create(vec![
+            TableField::new("scope", TableDataType::String),
+            TableField::new("name", TableDataType::String),
+            TableField::new("used_bytes", TableDataType::Number(NumberDataType::UInt64)),
+            // 0 is no limited.
+            TableField::new("max_bytes", TableDataType::Number(NumberDataType::UInt64)),
+        ]);
+
+        let table_info = TableInfo {
+            desc: "'system'.'storage_quotas'".to_string(),
+            name: "storage_quotas".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                schema,
+                engine: "SystemStorageQuotas".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        AsyncOneBlockSystemTable::create(StorageQuotasTable { table_info })
+    }
+}
@@ -19,6 +19,7 @@ use common_meta_types::GrantObject;
 use common_meta_types::UserIdentity;
 use common_meta_types::UserInfo;
 use common_meta_types::UserOption;
+use common_meta_types::UserOptionFlag;
 use common_meta_types::UserPrivilegeSet;
 
 use crate::role_mgr::BUILTIN_ROLE_ACCOUNT_ADMIN;
@@ -40,7 +41,10 @@ impl UserApiProvider {
                 user_info
                     .option
                     .set_default_role(Some(BUILTIN_ROLE_ACCOUNT_ADMIN.to_string()));
-                user_info.option.set_all_flag();
+                // Not all the flags: root@localhost must be able to connect without TLS.
+                user_info
+                    .option
+                    .set_option_flag(UserOptionFlag::TenantSetting);
             } else {
                 return Err(ErrorCode::UnknownUser(format!(
                     "only accept root from localhost, current: '{}'@'{}'",
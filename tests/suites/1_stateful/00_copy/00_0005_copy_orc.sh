@@ -0,0 +1,35 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+# Should be <root>/tests/data/
+DATADIR=$(realpath $CURDIR/../../../data/)
+
+echo "drop table if exists orc_types;" | $MYSQL_CLIENT_CONNECT
+echo "drop table if exists orc_cast;" | $MYSQL_CLIENT_CONNECT
+echo "drop stage if exists orc_stage;" | $MYSQL_CLIENT_CONNECT
+
+echo "create table orc_types(id int, name string null, score double null, flag boolean null, big bigint null);" | $MYSQL_CLIENT_CONNECT
+# columns in another order than the file, with types which need a cast
+echo "create table orc_cast(big string null, id bigint, flag boolean null, score float null);" | $MYSQL_CLIENT_CONNECT
+
+echo "create stage orc_stage url = 'fs://${DATADIR}/';" | $MYSQL_CLIENT_CONNECT
+
+echo "--copy from stage"
+echo "copy into orc_types from @orc_stage FILES = ('types.orc') FILE_FORMAT = (type = 'ORC');" | $MYSQL_CLIENT_CONNECT
+echo "select * from orc_types order by id;" | $MYSQL_CLIENT_CONNECT
+echo "select count(name), count(score), count(flag), count(big) from orc_types;" | $MYSQL_CLIENT_CONNECT
+
+echo "--copy with cast"
+echo "copy into orc_cast from @orc_stage FILES = ('types.orc') FILE_FORMAT = (type = 'ORC');" | $MYSQL_CLIENT_CONNECT
+echo "select * from orc_cast order by id;" | $MYSQL_CLIENT_CONNECT
+
+echo "--streaming load"
+echo "truncate table orc_types;" | $MYSQL_CLIENT_CONNECT
+curl -H "insert_sql:insert into orc_types file_format = (type = 'ORC')" -F "upload=@${DATADIR}/types.orc" -u root: -XPUT "http://localhost:${QUERY_HTTP_HANDLER_PORT}/v1/streaming_load" > /dev/null 2>&1
+echo "select * from orc_types order by id;" | $MYSQL_CLIENT_CONNECT
+
+echo "drop table orc_types;" | $MYSQL_CLIENT_CONNECT
+echo "drop table orc_cast;" | $MYSQL_CLIENT_CONNECT
+echo "drop stage orc_stage;" | $MYSQL_CLIENT_CONNECT
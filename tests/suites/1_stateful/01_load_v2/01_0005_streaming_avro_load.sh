@@ -0,0 +1,52 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+echo "drop table if exists avro_users;" | $MYSQL_CLIENT_CONNECT
+## create avro_users table
+echo "create table avro_users(id bigint, name string, score double null,
+tags array(string), address tuple(city string, zip int));" | $MYSQL_CLIENT_CONNECT
+
+aws --endpoint-url http://127.0.0.1:9900/ s3 cp s3://testbucket/admin/data/users.avro /tmp/users.avro > /dev/null 2>&1
+
+# do the Data integrity check
+echo "8aa14cd0a2968c44c683af6d4aab5de871aeb531a22dd8c76a7d4034a5025eb6 /tmp/users.avro" | sha256sum --check > /dev/null 2>&1
+if [ $? -ne 0 ]; then
+	echo "The downloaded dataset has been corrupted, please remove and fetch it again."
+	exit 1
+fi
+
+# load avro
+echo "--streaming load"
+curl -H "insert_sql:insert into avro_users file_format = (type = 'Avro')" -F "upload=@/tmp/users.avro" -u root: -XPUT "http://localhost:${QUERY_HTTP_HANDLER_PORT}/v1/streaming_load" > /dev/null 2>&1
+echo "select * from avro_users order by id" | $MYSQL_CLIENT_CONNECT
+echo "truncate table avro_users" | $MYSQL_CLIENT_CONNECT
+
+# copy avro, with splits smaller than the blocks
+echo "--copy"
+echo "copy into avro_users from 's3://testbucket/admin/data/users.avro' connection=(aws_key_id='minioadmin' aws_secret_key='minioadmin' endpoint_url='${STORAGE_S3_ENDPOINT_URL}') FILE_FORMAT = (type = 'Avro') split_size = 64" | $MYSQL_CLIENT_CONNECT
+echo "select * from avro_users order by id" | $MYSQL_CLIENT_CONNECT
+echo "drop table avro_users;" | $MYSQL_CLIENT_CONNECT
+
+## an avro map is loaded as an array of (key, value) tuples
+echo "drop table if exists avro_maps;" | $MYSQL_CLIENT_CONNECT
+echo "create table avro_maps(id bigint, attrs array(tuple(string, int)));" | $MYSQL_CLIENT_CONNECT
+
+aws --endpoint-url http://127.0.0.1:9900/ s3 cp s3://testbucket/admin/data/maps.avro /tmp/maps.avro > /dev/null 2>&1
+
+echo "f75816b37c69c5c3e42083895be6cbf99d4e932afb6da49f2f051728a21ecb11 /tmp/maps.avro" | sha256sum --check > /dev/null 2>&1
+if [ $? -ne 0 ]; then
+	echo "The downloaded dataset has been corrupted, please remove and fetch it again."
+	exit 1
+fi
+
+echo "--streaming load map"
+curl -H "insert_sql:insert into avro_maps file_format = (type = 'Avro')" -F "upload=@/tmp/maps.avro" -u root: -XPUT "http://localhost:${QUERY_HTTP_HANDLER_PORT}/v1/streaming_load" > /dev/null 2>&1
+echo "select * from avro_maps order by id" | $MYSQL_CLIENT_CONNECT
+echo "truncate table avro_maps" | $MYSQL_CLIENT_CONNECT
+
+echo "--copy map"
+echo "copy into avro_maps from 's3://testbucket/admin/data/maps.avro' connection=(aws_key_id='minioadmin' aws_secret_key='minioadmin' endpoint_url='${STORAGE_S3_ENDPOINT_URL}') FILE_FORMAT = (type = 'Avro') split_size = 64" | $MYSQL_CLIENT_CONNECT
+echo "select * from avro_maps order by id" | $MYSQL_CLIENT_CONNECT
+echo "drop table avro_maps;" | $MYSQL_CLIENT_CONNECT
@@ -0,0 +1,40 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+# Should be <root>/tests/data/iceberg/warehouse/
+WAREHOUSE=$(realpath $CURDIR/../../../data/iceberg/warehouse/)
+
+echo "drop catalog if exists ice;" | $MYSQL_CLIENT_CONNECT
+echo "create catalog ice type = iceberg connection = (url = 'fs://${WAREHOUSE}/');" | $MYSQL_CLIENT_CONNECT
+
+echo "--- schema"
+echo "desc ice.default.customer;" | $MYSQL_CLIENT_CONNECT
+
+echo "--- current snapshot"
+echo "select * from ice.default.customer order by c_nation2;" | $MYSQL_CLIENT_CONNECT
+echo "select count(*) from ice.default.customer;" | $MYSQL_CLIENT_CONNECT
+
+echo "--- time travel"
+echo "select * from ice.default.customer at (snapshot => '1') order by c_nation2;" | $MYSQL_CLIENT_CONNECT
+echo "select count(*) from ice.default.customer at (timestamp => '2023-01-15 00:00:00'::TIMESTAMP);" | $MYSQL_CLIENT_CONNECT
+echo "select count(*) from ice.default.customer at (timestamp => '2023-02-15 00:00:00'::TIMESTAMP);" | $MYSQL_CLIENT_CONNECT
+echo "select count(*) from ice.default.customer at (snapshot => '3');" | $MYSQL_CLIENT_CONNECT 2>&1 | grep -o "No historical data found at given point: snapshot 3"
+
+echo "--- pruning"
+echo "select * from ice.default.customer where c_region2 = 'EUROPE';" | $MYSQL_CLIENT_CONNECT
+echo "explain select * from ice.default.customer where c_region2 = 'EUROPE';" | $MYSQL_CLIENT_CONNECT | grep -o "partitions [a-z]*: [0-9]*"
+echo "explain select * from ice.default.customer where c_nation2 > 'Z';" | $MYSQL_CLIENT_CONNECT | grep -o "partitions [a-z]*: [0-9]*"
+
+# orders is partitioned by region, amount is renamed to total after the first snapshot,
+# and the data file of the second snapshot is written with the columns in another order.
+echo "--- partitioned table"
+echo "desc ice.default.orders;" | $MYSQL_CLIENT_CONNECT
+echo "select * from ice.default.orders order by id;" | $MYSQL_CLIENT_CONNECT
+echo "select id, total from ice.default.orders where region = 'EUROPE';" | $MYSQL_CLIENT_CONNECT
+echo "select * from ice.default.orders at (snapshot => '1') order by id;" | $MYSQL_CLIENT_CONNECT
+echo "explain select * from ice.default.orders where region = 'EUROPE';" | $MYSQL_CLIENT_CONNECT | grep -o "partitions [a-z]*: [0-9]*"
+echo "explain select * from ice.default.orders where region = 'AFRICA';" | $MYSQL_CLIENT_CONNECT | grep -o "partitions [a-z]*: [0-9]*"
+
+echo "drop catalog ice;" | $MYSQL_CLIENT_CONNECT